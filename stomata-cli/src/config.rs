@@ -0,0 +1,373 @@
+//! User configuration file
+//!
+//! Settings are read from `<config dir>/stomata/config.toml`, e.g.
+//! `~/.config/stomata/config.toml` on Linux. Every setting is optional and
+//! a missing file is the same as an empty one.
+//!
+//! # Example
+//!
+//! ```toml
+//! theme = "my-theme"
+//! units = "si"
+//! keymap = "vim"
+//!
+//! [themes.my-theme]
+//! accent = "magenta"
+//! gauge = "#83a598"
+//!
+//! [connections]
+//! allowed_processes = ["syncthing"]
+//! allowed_ports = [22000]
+//!
+//! [geoip]
+//! country_db = "/usr/share/GeoIP/GeoLite2-Country.mmdb"
+//! asn_db = "/usr/share/GeoIP/GeoLite2-ASN.mmdb"
+//!
+//! [libvirt]
+//! uri = "qemu:///system"
+//!
+//! [[serve.tokens]]
+//! name = "grafana"
+//! secret = "change-me"
+//! scope = "read"
+//!
+//! [agent]
+//! token = "change-me-too"
+//!
+//! [[agent.tokens]]
+//! name = "laptop"
+//! secret = "change-me-too"
+//! scope = "read"
+//!
+//! [store]
+//! raw_retention = "6h"
+//! minute_retention = "7d"
+//! hour_retention = "365d"
+//!
+//! [anomalies]
+//! sigmas = 3.0
+//! window = 60
+//!
+//! [alerts]
+//! inode_percent = 90.0
+//! throttling_secs = 30
+//!
+//! [disks]
+//! exclude = ["/dev/loop*", "overlay", "/boot/*"]
+//!
+//! [processes]
+//! columns = ["pid", "name", "cpu", "memory", "read", "write", "command"]
+//!
+//! [metrics]
+//! layout = "server"
+//! ```
+
+use std::{collections::HashMap, fs, path::PathBuf, sync::OnceLock};
+
+use anyhow::Context;
+use serde::Deserialize;
+use stomata_core::{
+    access::ApiToken,
+    collectors::{connections::ConnectionAllowlist, disks::MountFilter},
+    constants::{
+        ANOMALY_DEFAULT_SIGMAS, ANOMALY_DEFAULT_WINDOW, INODE_ALERT_DEFAULT_PERCENT,
+        THROTTLE_ALERT_DEFAULT_SECS,
+    },
+    history::parse_range,
+    storage::RetentionPolicy,
+    units::UnitSystem,
+};
+
+use crate::{
+    constants::CONFIG_FILE_NAME,
+    features::core::{
+        keymap::KeymapProfile,
+        metrics_layout::{LayoutPreset, MetricsRow},
+        process_columns::ProcessColumn,
+    },
+    theme::Theme,
+};
+
+/// Settings loaded from the config file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Name of the theme to use, built-in or from `themes`
+    pub theme: Option<String>,
+
+    /// User-defined themes by name. Colors that aren't set fall back to
+    /// the dark theme.
+    pub themes: HashMap<String, Theme>,
+
+    /// Byte counts in `binary` (GiB) or `si` (GB) units, `b` switches
+    /// between them at runtime
+    pub units: UnitSystem,
+
+    /// Keybinding profile, `default` or `vim`
+    pub keymap: KeymapProfile,
+
+    /// Suspicious connection heuristics
+    pub connections: ConnectionsConfig,
+
+    /// MaxMind databases used to annotate remote addresses
+    pub geoip: GeoIpConfig,
+
+    /// Connection used by the VMs page
+    pub libvirt: LibvirtConfig,
+
+    /// Access to `stomata serve`
+    pub serve: ServeConfig,
+
+    /// Access to `stomata agent`, and the token `stomata connect` presents
+    pub agent: AgentConfig,
+
+    /// Retention of the `--store` history
+    pub store: StoreConfig,
+
+    /// Sensitivity of the anomaly highlighting
+    pub anomalies: AnomaliesConfig,
+
+    /// Thresholds of the built-in alert rules
+    pub alerts: AlertsConfig,
+
+    /// Mounts shown on the Disks page
+    pub disks: DisksConfig,
+
+    /// Layout of the Processes page
+    pub processes: ProcessesConfig,
+
+    /// Layout of the Metrics page
+    pub metrics: MetricsConfig,
+}
+
+/// Rows of the Metrics page
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// Built-in layout, `compact`, `detailed` or `server`
+    pub layout: LayoutPreset,
+
+    /// Custom rows replacing the ones of `layout`
+    pub rows: Vec<MetricsRow>,
+}
+
+impl MetricsConfig {
+    /// Rows of the page, top to bottom
+    pub fn rows(&self) -> Vec<MetricsRow> {
+        if self.rows.is_empty() {
+            self.layout.rows()
+        } else {
+            self.rows.clone()
+        }
+    }
+}
+
+/// Columns of the process list
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessesConfig {
+    /// Columns to show, in order; the default columns when empty. The
+    /// column editor (`v`) changes them until the next start.
+    pub columns: Vec<ProcessColumn>,
+}
+
+/// Mounts to show or hide, as patterns matched against the mount point,
+/// the device and the filesystem type, with `*` as a wildcard
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisksConfig {
+    /// Only show the mounts matching one of these, every mount when empty
+    pub include: Vec<String>,
+
+    /// Hide the mounts matching one of these
+    pub exclude: Vec<String>,
+}
+
+impl DisksConfig {
+    /// The mount filter of the disk collector
+    pub fn mount_filter(&self) -> MountFilter {
+        MountFilter {
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+        }
+    }
+}
+
+/// When the built-in alert rules fire
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// Inode usage in percent from which a filesystem raises an alert
+    pub inode_percent: f64,
+
+    /// Seconds the CPUs must stay throttled before raising an alert, 0
+    /// turns the alert off
+    pub throttling_secs: u64,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            inode_percent: INODE_ALERT_DEFAULT_PERCENT,
+            throttling_secs: THROTTLE_ALERT_DEFAULT_SECS,
+        }
+    }
+}
+
+/// When a sample counts as an anomaly
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnomaliesConfig {
+    /// Standard deviations from the rolling mean beyond which a sample is
+    /// highlighted
+    pub sigmas: f64,
+
+    /// Samples of the rolling baseline
+    pub window: usize,
+}
+
+impl Default for AnomaliesConfig {
+    fn default() -> Self {
+        Self {
+            sigmas: ANOMALY_DEFAULT_SIGMAS,
+            window: ANOMALY_DEFAULT_WINDOW,
+        }
+    }
+}
+
+/// Ages after which the `--store` history is rolled up, as ranges such as
+/// `6h` or `7d`; unset ages keep their defaults
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StoreConfig {
+    /// Age of raw samples rolled into minute averages
+    pub raw_retention: Option<String>,
+
+    /// Age of minute averages rolled into hour averages
+    pub minute_retention: Option<String>,
+
+    /// Age of hour averages deleted
+    pub hour_retention: Option<String>,
+}
+
+impl StoreConfig {
+    /// The configured ages over the default policy
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an age isn't a valid range or the ages aren't
+    /// increasing.
+    pub fn retention(&self) -> anyhow::Result<RetentionPolicy> {
+        let mut policy = RetentionPolicy::default();
+        for (value, age) in [
+            (&self.raw_retention, &mut policy.raw_for),
+            (&self.minute_retention, &mut policy.minute_for),
+            (&self.hour_retention, &mut policy.hour_for),
+        ] {
+            if let Some(value) = value {
+                *age = parse_range(value)?;
+            }
+        }
+        policy.validate()?;
+        Ok(policy)
+    }
+}
+
+/// `stomata serve` settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServeConfig {
+    /// Tokens accepted by the API, which is open to anyone reaching it
+    /// while none is set
+    pub tokens: Vec<ApiToken>,
+}
+
+/// `stomata agent` and `stomata connect` settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentConfig {
+    /// Tokens accepted by the agent. Without any it only listens on
+    /// localhost.
+    pub tokens: Vec<ApiToken>,
+
+    /// Secret `stomata connect` presents to agents, overridden by
+    /// `--token`
+    pub token: Option<String>,
+}
+
+/// libvirt connection settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LibvirtConfig {
+    /// Connection URI such as `qemu:///system`, virsh's default when unset
+    pub uri: Option<String>,
+}
+
+/// Paths of local MaxMind databases, both optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeoIpConfig {
+    /// GeoLite2/GeoIP2 Country or City database
+    pub country_db: Option<PathBuf>,
+
+    /// GeoLite2/GeoIP2 ASN database
+    pub asn_db: Option<PathBuf>,
+}
+
+/// Additions to the built-in connection allowlist
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConnectionsConfig {
+    /// Process names expected to open network connections
+    pub allowed_processes: Vec<String>,
+
+    /// Remote ports that aren't considered uncommon
+    pub allowed_ports: Vec<u16>,
+}
+
+impl ConnectionsConfig {
+    /// The built-in allowlist extended with the configured entries
+    pub fn allowlist(&self) -> ConnectionAllowlist {
+        let mut allowlist = ConnectionAllowlist::default();
+        allowlist
+            .processes
+            .extend(self.allowed_processes.iter().cloned());
+        allowlist.ports.extend(&self.allowed_ports);
+        allowlist
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The config loaded at startup, defaults if none was set
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+impl Config {
+    /// Location of the config file
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("stomata").join(CONFIG_FILE_NAME))
+    }
+
+    /// Loads the config file, returning the defaults when it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be read or parsed.
+    pub fn load() -> anyhow::Result<Self> {
+        let Some(path) = Self::path().filter(|path| path.exists()) else {
+            return Ok(Self::default());
+        };
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("invalid config file {}", path.display()))
+    }
+
+    /// Makes this the config returned by `config()` for the rest of the
+    /// run. Only the first call has an effect.
+    pub fn set_global(self) {
+        let _ = CONFIG.set(self);
+    }
+}
@@ -1,4 +1,73 @@
-pub const MAX_HISTORY: usize = 1000;
 pub const MAX_NETWORK_IN_MEMORY: usize = 40;
 pub const MAX_HISTORY_IN_MEMORY: usize = 60;
 pub const CLAMP_TREND_VALUE: f64 = 0.95;
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+#[cfg(feature = "scripting")]
+pub const SCRIPT_FILE_NAME: &str = "hooks.rhai";
+#[cfg(feature = "scripting")]
+pub const SCRIPT_MAX_OPERATIONS: u64 = 100_000;
+/// Longest string a hooks script may build, so it can't exhaust memory
+/// within its operation budget
+#[cfg(feature = "scripting")]
+pub const SCRIPT_MAX_STRING_BYTES: usize = 64 * 1024;
+/// Largest array or map a hooks script may build
+#[cfg(feature = "scripting")]
+pub const SCRIPT_MAX_COLLECTION_LEN: usize = 10_000;
+pub const STALE_HANDSHAKE_SECS: i64 = 180;
+/// Longest text a text input takes. Its buffer is allocated at this size
+/// up front, so typing never leaves a copy of a secret in a freed buffer.
+pub const TEXT_INPUT_MAX_BYTES: usize = 256;
+pub const VOLUME_STEP_PERCENT: i32 = 5;
+pub const BRIGHTNESS_STEP_PERCENT: u32 = 5;
+/// How often the System page reads the backlight and power profile again,
+/// they only change on a key press or a brightness key
+pub const POWER_REFRESH_SECS: u64 = 10;
+pub const REDACTED_VALUE: &str = "********";
+pub const AFFINITY_CPUS_PER_LINE: usize = 8;
+pub const CRASH_EVENTS_REFRESH_SECS: u64 = 30;
+/// Average disk latency above which it is highlighted
+pub const DISK_AWAIT_WARNING_MS: f64 = 20.0;
+/// Average disk queue depth above which it is highlighted
+pub const DISK_QUEUE_WARNING: f64 = 4.0;
+/// 10 second PSI average, in percent, above which a pressure gauge is highlighted
+pub const PRESSURE_WARNING_PERCENT: f32 = 10.0;
+/// 10 second PSI average, in percent, above which a pressure gauge turns critical
+pub const PRESSURE_CRITICAL_PERCENT: f32 = 40.0;
+/// CPU, memory or swap usage, in percent, above which the summary strip highlights it
+pub const SUMMARY_WARNING_PERCENT: f64 = 75.0;
+/// CPU, memory or swap usage, in percent, above which the summary strip turns critical
+pub const SUMMARY_CRITICAL_PERCENT: f64 = 90.0;
+/// How long a status bar message stays visible
+pub const STATUS_MESSAGE_SECS: u64 = 5;
+/// Log file in the state directory written through `tracing`
+pub const LOG_FILE_NAME: &str = "stomata.log";
+/// Errors kept for the error popup
+pub const MAX_RECENT_ERRORS: usize = 50;
+/// How often the alert rules run
+pub const ALERT_EVALUATION_SECS: u64 = 5;
+/// Time `stomata collect` waits so rates cover a meaningful interval
+pub const COLLECT_WARMUP_MS: u64 = 1000;
+/// File name of a process table export without the timestamp and extension
+pub const PROCESS_EXPORT_FILE_PREFIX: &str = "stomata-processes";
+/// Port `stomata agent` listens on and `stomata connect` assumes
+pub const AGENT_DEFAULT_PORT: u16 = 7070;
+/// Width of the host sidebar of `stomata connect` with several agents
+pub const HOST_SIDEBAR_WIDTH: u16 = 24;
+/// Port `stomata serve` listens on by default
+pub const API_DEFAULT_PORT: u16 = 8686;
+/// How often the History page queries the metric store again
+pub const HISTORY_REFRESH_SECS: u64 = 5;
+/// Points of every History chart, the store averages the samples into them
+pub const HISTORY_CHART_POINTS: usize = 300;
+/// How long a newly started process stays highlighted in the process list
+pub const NEW_PROCESS_HIGHLIGHT_SECS: i64 = 10;
+/// Height of the recently exited panel below the process list, borders
+/// included
+pub const RECENTLY_EXITED_PANEL_HEIGHT: u16 = 7;
+/// Width of the Container column of the process list
+pub const CONTAINER_COLUMN_WIDTH: u16 = 24;
+/// Width of the Swap column of the process list
+pub const SWAP_COLUMN_WIDTH: u16 = 10;
+/// Characters the command line of the process list moves per horizontal
+/// scroll step
+pub const COMMAND_SCROLL_STEP: usize = 8;
@@ -0,0 +1,111 @@
+//! Error reporting for the TUI
+//!
+//! Printing to stderr while the alternate screen is active garbles the
+//! display, so errors are sent as [`AppError`]s over a channel instead.
+//! The render loop drains the channel, shows the message in the status
+//! bar, keeps the recent errors for the error popup and logs the full
+//! details to the log file.
+
+use std::{
+    collections::VecDeque,
+    sync::mpsc::{self, Receiver, Sender},
+};
+
+use chrono::{DateTime, Local};
+
+use crate::constants::MAX_RECENT_ERRORS;
+
+/// An error raised while the TUI is running
+#[derive(Debug, Clone)]
+pub struct AppError {
+    /// What was being done, e.g. `render Disks`
+    pub context: String,
+    /// One line summary for the status bar
+    pub message: String,
+    /// Full error chain for the log file
+    pub details: String,
+    pub at: DateTime<Local>,
+}
+
+impl AppError {
+    pub fn new(context: impl Into<String>, error: &anyhow::Error) -> Self {
+        Self {
+            context: context.into(),
+            message: format!("{error:#}"),
+            details: format!("{error:?}"),
+            at: Local::now(),
+        }
+    }
+}
+
+/// Sending half of the error channel, cheap to clone into collectors
+#[derive(Debug, Clone)]
+pub struct ErrorReporter {
+    sender: Sender<AppError>,
+}
+
+impl ErrorReporter {
+    /// Sends `error` to the render loop. Errors raised after the TUI shut
+    /// down are dropped.
+    pub fn report(&self, context: &str, error: &anyhow::Error) {
+        let _ = self.sender.send(AppError::new(context, error));
+    }
+
+    /// Reports the error of `result`, if any
+    pub fn check<T>(&self, context: &str, result: anyhow::Result<T>) {
+        if let Err(error) = result {
+            self.report(context, &error);
+        }
+    }
+}
+
+/// Receiving half of the error channel with the recent errors
+#[derive(Debug)]
+pub struct ErrorLog {
+    receiver: Receiver<AppError>,
+    recent: VecDeque<AppError>,
+    /// Whether the error popup is open
+    pub open: bool,
+}
+
+impl ErrorLog {
+    /// Creates the channel
+    pub fn channel() -> (ErrorReporter, ErrorLog) {
+        let (sender, receiver) = mpsc::channel();
+        let log = ErrorLog {
+            receiver,
+            recent: VecDeque::new(),
+            open: false,
+        };
+        (ErrorReporter { sender }, log)
+    }
+
+    /// Takes the errors sent since the last call, newest last, and logs
+    /// them.
+    ///
+    /// An error repeating the previous one, like a page failing on every
+    /// frame, only refreshes its time instead of filling the log.
+    pub fn drain(&mut self) -> Vec<AppError> {
+        let errors: Vec<AppError> = self.receiver.try_iter().collect();
+        for error in &errors {
+            if let Some(last) = self.recent.back_mut()
+                && last.context == error.context
+                && last.message == error.message
+            {
+                last.at = error.at;
+                continue;
+            }
+            tracing::error!(context = %error.context, "{}", error.details);
+            if self.recent.len() == MAX_RECENT_ERRORS {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(error.clone());
+        }
+        errors
+    }
+
+    /// Recent errors, oldest first
+    pub fn recent(&self) -> &VecDeque<AppError> {
+        &self.recent
+    }
+}
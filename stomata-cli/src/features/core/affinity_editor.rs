@@ -0,0 +1,86 @@
+//! CPU affinity editor of the SingleProcess page
+//!
+//! A modal grid with a checkbox per CPU, applied with `sched_setaffinity`
+//! through stomata-core.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::process::actions::{format_cpu_list, set_affinity};
+
+use crate::constants::AFFINITY_CPUS_PER_LINE;
+
+/// An open affinity editor
+#[derive(Debug)]
+pub struct AffinityEditor {
+    /// Process being pinned
+    pub pid: u32,
+
+    /// Whether the process may run on each CPU, indexed by CPU number
+    pub allowed: Vec<bool>,
+
+    /// CPU under the cursor
+    pub cursor: usize,
+}
+
+/// What the editor did with a key press
+pub enum AffinityEditorOutcome {
+    /// The editor stays open
+    Open,
+    /// The editor was closed without changes
+    Cancelled,
+    /// The affinity was applied, with the message or error to show the user
+    Applied(Result<String, String>),
+}
+
+impl AffinityEditor {
+    /// Opens the editor for `pid` with its `current` affinity checked
+    pub fn new(pid: u32, cpu_count: usize, current: &[usize]) -> Self {
+        Self {
+            pid,
+            allowed: (0..cpu_count).map(|cpu| current.contains(&cpu)).collect(),
+            cursor: 0,
+        }
+    }
+
+    /// Handles a key press while the editor is open.
+    ///
+    /// - Arrow keys - Move the cursor
+    /// - `Space` - Allow or disallow the CPU under the cursor
+    /// - `a` - Allow every CPU
+    /// - `Enter` - Apply
+    /// - `Esc` - Cancel
+    pub fn handle_key(&mut self, key: KeyEvent) -> AffinityEditorOutcome {
+        let last = self.allowed.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(AFFINITY_CPUS_PER_LINE),
+            KeyCode::Down => self.cursor = (self.cursor + AFFINITY_CPUS_PER_LINE).min(last),
+            KeyCode::Char(' ') => {
+                if let Some(allowed) = self.allowed.get_mut(self.cursor) {
+                    *allowed = !*allowed;
+                }
+            }
+            KeyCode::Char('a') => self.allowed.iter_mut().for_each(|allowed| *allowed = true),
+            KeyCode::Enter => {
+                let cpus = self.cpus();
+                return AffinityEditorOutcome::Applied(match set_affinity(self.pid, &cpus) {
+                    Ok(()) => Ok(format!("Pinned to CPUs {}", format_cpu_list(&cpus))),
+                    Err(err) => Err(format!("Affinity change failed: {err:#}")),
+                });
+            }
+            KeyCode::Esc => return AffinityEditorOutcome::Cancelled,
+            _ => {}
+        }
+        AffinityEditorOutcome::Open
+    }
+
+    /// Checked CPU numbers
+    pub fn cpus(&self) -> Vec<usize> {
+        self.allowed
+            .iter()
+            .enumerate()
+            .filter(|(_, allowed)| **allowed)
+            .map(|(cpu, _)| cpu)
+            .collect()
+    }
+}
@@ -0,0 +1,109 @@
+//! Alert history export
+//!
+//! Prints the alerts fired within a time range, together with the OOM
+//! kills, segfaults and service crashes from the journal, as one JSON
+//! document for post-incident reviews.
+//!
+//! ```bash
+//! stomata alerts export --range 7d > incident.json
+//! stomata alerts export --range 12h
+//! ```
+
+use std::iter::once;
+
+use chrono::{DateTime, Duration, Utc};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use stomata_core::{
+    alerts::{AlertLog, LoggedAlert},
+    collectors::CrashEvent,
+    history::parse_range,
+};
+
+/// Arguments of `stomata alerts`
+#[derive(Parser, Clone)]
+#[command(name = "alerts")]
+#[command(about = "Work with the history of fired alerts")]
+pub struct AlertsCli {
+    #[command(subcommand)]
+    pub command: AlertsCommand,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum AlertsCommand {
+    /// Print the alerts and crash events of a time range as JSON
+    Export {
+        /// How far back to export, e.g. `7d`, `12h` or `30m`
+        #[arg(short, long, default_value = "7d", value_parser = parse_range)]
+        range: Duration,
+    },
+}
+
+/// JSON document printed by `stomata alerts export`
+#[derive(Debug, Serialize)]
+struct AlertExport {
+    /// RFC 3339 start of the exported range
+    since: String,
+    /// RFC 3339 time the export was made
+    until: String,
+    alerts: Vec<LoggedAlert>,
+    crashes: Vec<ExportedCrash>,
+    /// Why the crash events couldn't be read, e.g. no access to the journal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    crashes_error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportedCrash {
+    timestamp: String,
+    kind: &'static str,
+    subject: String,
+    message: String,
+}
+
+impl From<CrashEvent> for ExportedCrash {
+    fn from(event: CrashEvent) -> Self {
+        Self {
+            timestamp: event.timestamp.to_rfc3339(),
+            kind: event.kind.as_str(),
+            subject: event.subject,
+            message: event.message,
+        }
+    }
+}
+
+/// Parses the alerts arguments and prints the export to stdout
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid, the alert log can't be
+/// read or the export can't be serialized.
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    let cli = AlertsCli::try_parse_from(once("alerts".to_string()).chain(args.iter().cloned()))?;
+    match cli.command {
+        AlertsCommand::Export { range } => {
+            let until = Utc::now();
+            let export = export(until - range, until)?;
+            println!("{}", serde_json::to_string_pretty(&export)?);
+        }
+    }
+    Ok(())
+}
+
+fn export(since: DateTime<Utc>, until: DateTime<Utc>) -> anyhow::Result<AlertExport> {
+    let alerts = match AlertLog::default_location() {
+        Some(log) => log.read_since(since)?,
+        None => Vec::new(),
+    };
+    let (crashes, crashes_error) = match CrashEvent::fetch(since) {
+        Ok(events) => (events.into_iter().map(ExportedCrash::from).collect(), None),
+        Err(err) => (Vec::new(), Some(err.to_string())),
+    };
+    Ok(AlertExport {
+        since: since.to_rfc3339(),
+        until: until.to_rfc3339(),
+        alerts,
+        crashes,
+        crashes_error,
+    })
+}
@@ -0,0 +1,64 @@
+//! Volume and mute controls of the Controls page
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::{audio::AudioDevice, poller::PollerRefresh};
+
+use crate::{constants::VOLUME_STEP_PERCENT, structs::TableUIState};
+
+/// State of the audio panel
+#[derive(Debug, Default)]
+pub struct AudioUIState {
+    /// Selection state of the audio devices table
+    pub table: TableUIState,
+
+    /// Devices in table order, to resolve the selection
+    pub devices: Vec<AudioDevice>,
+
+    /// Result of the last failed action
+    pub status: Option<String>,
+}
+
+impl AudioUIState {
+    /// Handles a key press on the Controls page.
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `+`/`-` - Raise or lower the volume of the selected device
+    /// - `m` - Mute or unmute the selected device
+    /// - `d` - Make the selected device the default
+    ///
+    /// After a successful action `devices` lists the devices again, so the
+    /// table shows the change without waiting for the next listing.
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent, devices: PollerRefresh) -> bool {
+        let selected = self
+            .table
+            .list
+            .selected()
+            .and_then(|index| self.devices.get(index));
+
+        let result = match (key.code, selected) {
+            (KeyCode::Down, _) => {
+                self.table.select_next();
+                self.status = None;
+                return true;
+            }
+            (KeyCode::Up, _) => {
+                self.table.select_previous();
+                self.status = None;
+                return true;
+            }
+            (KeyCode::Char('+' | '='), Some(device)) => device.change_volume(VOLUME_STEP_PERCENT),
+            (KeyCode::Char('-'), Some(device)) => device.change_volume(-VOLUME_STEP_PERCENT),
+            (KeyCode::Char('m'), Some(device)) => device.toggle_mute(),
+            (KeyCode::Char('d'), Some(device)) => device.set_default(),
+            _ => return false,
+        };
+        if result.is_ok() {
+            devices.refresh();
+        }
+        self.status = result.err().map(|err| format!("{err:#}"));
+        true
+    }
+}
@@ -0,0 +1,74 @@
+//! Connect and disconnect actions of the Controls page's Bluetooth panel
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::{bluetooth::BluetoothDevice, poller::PollerRefresh};
+
+use crate::structs::{StatusBar, TableUIState};
+
+/// State of the Bluetooth panel
+#[derive(Debug, Default)]
+pub struct BluetoothUIState {
+    /// Selection state of the devices table
+    pub table: TableUIState,
+
+    /// Devices in table order, to resolve the selection
+    pub devices: Vec<BluetoothDevice>,
+}
+
+impl BluetoothUIState {
+    /// Handles a key press while the Bluetooth panel has focus.
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `c` - Connect the selected device
+    /// - `x` - Disconnect the selected device
+    ///
+    /// Connecting waits until the device answers or the attempt times out,
+    /// so both run in the background. The outcome is shown in
+    /// `status_bar`, then `devices` lists the devices again.
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        status_bar: &mut StatusBar,
+        devices: PollerRefresh,
+    ) -> bool {
+        let selected = self
+            .table
+            .list
+            .selected()
+            .and_then(|index| self.devices.get(index));
+
+        match (key.code, selected) {
+            (KeyCode::Down, _) => self.table.select_next(),
+            (KeyCode::Up, _) => self.table.select_previous(),
+            (KeyCode::Char('c'), Some(device)) => {
+                let device = device.clone();
+                let text = format!("Connecting '{}'…", device.name);
+                status_bar.run_in_background(text, move || {
+                    let result = device
+                        .connect()
+                        .map(|()| format!("Connected '{}'", device.name))
+                        .map_err(|err| format!("Failed to connect '{}': {err:#}", device.name));
+                    devices.refresh();
+                    result
+                });
+            }
+            (KeyCode::Char('x'), Some(device)) => {
+                let device = device.clone();
+                let text = format!("Disconnecting '{}'…", device.name);
+                status_bar.run_in_background(text, move || {
+                    let result = device
+                        .disconnect()
+                        .map(|()| format!("Disconnected '{}'", device.name))
+                        .map_err(|err| format!("Failed to disconnect '{}': {err:#}", device.name));
+                    devices.refresh();
+                    result
+                });
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,97 @@
+//! One-shot metrics from the collector registry
+//!
+//! Runs every registered collector once and prints one value per line as
+//! `<collector>.<instance>.<name> <value> <unit>`, easy to grep or feed to
+//! another tool. `--human` prints byte values scaled instead, in the units
+//! chosen in the config file.
+//!
+//! ```bash
+//! stomata collect
+//! stomata collect --human
+//! stomata collect --only cpu,network
+//! stomata collect --list
+//! ```
+
+use std::{iter::once, thread, time::Duration};
+
+use anyhow::bail;
+use clap::Parser;
+use stomata_core::collectors::{CollectorRegistry, Metric};
+
+use crate::{
+    constants::COLLECT_WARMUP_MS,
+    utils::{format_bytes, format_rate},
+};
+
+/// Arguments of `stomata collect`
+#[derive(Parser, Clone)]
+#[command(name = "collect")]
+#[command(about = "Print the current values of the registered collectors")]
+pub struct CollectCli {
+    /// Collectors to run, all of them by default
+    #[arg(long, value_delimiter = ',')]
+    pub only: Vec<String>,
+    /// Print the ids of the registered collectors and exit
+    #[arg(long, default_value_t = false)]
+    pub list: bool,
+    /// Print byte counts and rates scaled, e.g. `3.2 GiB`
+    #[arg(long, default_value_t = false)]
+    pub human: bool,
+}
+
+/// Parses the collect arguments and prints the values to stdout. Failing
+/// collectors are reported on stderr without stopping the others.
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid or name an unknown
+/// collector.
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    let cli = CollectCli::try_parse_from(once("collect".to_string()).chain(args.iter().cloned()))?;
+    let mut registry = CollectorRegistry::with_builtin();
+    if cli.list {
+        for id in registry.ids() {
+            println!("{id}");
+        }
+        return Ok(());
+    }
+
+    if !cli.only.is_empty() {
+        if let Some(unknown) = cli
+            .only
+            .iter()
+            .find(|id| !registry.ids().any(|known| known == id.as_str()))
+        {
+            let known: Vec<&str> = registry.ids().collect();
+            bail!(
+                "unknown collector `{unknown}`, expected one of {}",
+                known.join(", ")
+            );
+        }
+        let only: Vec<&str> = cli.only.iter().map(String::as_str).collect();
+        registry.retain(&only);
+    }
+
+    // rates are computed against the reading taken when the collectors
+    // were created
+    thread::sleep(Duration::from_millis(COLLECT_WARMUP_MS));
+    for (id, result) in registry.collect_all() {
+        match result {
+            Ok(metric) => print_metric(&metric, cli.human),
+            Err(err) => eprintln!("{id}: {err}"),
+        }
+    }
+    Ok(())
+}
+
+fn print_metric(metric: &Metric, human: bool) {
+    for value in &metric.values {
+        let unit = value.unit.as_deref().unwrap_or_default();
+        let formatted = match unit {
+            "bytes" if human => format_bytes(value.value as u64),
+            "bytes/s" if human => format_rate(value.value),
+            _ => format!("{} {unit}", value.value),
+        };
+        println!("{}.{} {}", metric.collector, value.key(), formatted);
+    }
+}
@@ -0,0 +1,204 @@
+//! Command line of the vim keymap profile
+//!
+//! `:` opens it in the status bar; `Enter` runs the typed command and
+//! `Esc` closes it. Like the signals menu it takes every key while open.
+//!
+//! # Commands
+//!
+//! - `:kill <pid> [signal]` - Send a signal to a process, `TERM` by default
+//! - `:sort <pid|cpu|memory|swap|read|write>` - Sort the process list
+//! - `:group <user|name|off>` - Group the process list
+//! - `:export` - Export the process list to a CSV file
+//! - `:q` or `:quit` - Quit
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::process::{ProcessGroupBy, actions::Signal};
+
+use crate::structs::{ProcessSort, TextInput};
+
+/// A parsed command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Send `signal` to `pid`
+    Kill {
+        /// Process the signal is sent to
+        pid: u32,
+        /// Signal to send
+        signal: Signal,
+    },
+    /// Sort the process list by a column
+    Sort(ProcessSort),
+    /// Group the process list, `None` for the flat list
+    Group(Option<ProcessGroupBy>),
+    /// Export the process list to a CSV file
+    Export,
+    /// Quit the application
+    Quit,
+}
+
+impl Command {
+    /// Parses a command line without the leading `:`
+    ///
+    /// # Errors
+    ///
+    /// Returns a message for the status bar when the command is unknown or
+    /// its arguments are invalid
+    pub fn parse(line: &str) -> Result<Command, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        match (command, args.as_slice()) {
+            ("kill", [pid, rest @ ..]) if rest.len() <= 1 => {
+                let pid = pid.parse().map_err(|_| format!("Not a PID: {pid}"))?;
+                let signal = match rest.first() {
+                    Some(name) => {
+                        Signal::from_name(name).ok_or_else(|| format!("Unknown signal: {name}"))?
+                    }
+                    None => Signal::Term,
+                };
+                Ok(Command::Kill { pid, signal })
+            }
+            ("kill", _) => Err("Usage: :kill <pid> [signal]".to_string()),
+            ("sort", [column]) => ProcessSort::from_name(column)
+                .map(Command::Sort)
+                .ok_or_else(|| format!("Unknown sort column: {column}")),
+            ("sort", _) => Err("Usage: :sort <pid|cpu|memory|swap|read|write>".to_string()),
+            ("group", ["user"]) => Ok(Command::Group(Some(ProcessGroupBy::User))),
+            ("group", ["name"]) => Ok(Command::Group(Some(ProcessGroupBy::Name))),
+            ("group", ["off"]) => Ok(Command::Group(None)),
+            ("group", _) => Err("Usage: :group <user|name|off>".to_string()),
+            ("export", []) => Ok(Command::Export),
+            ("q" | "quit", []) => Ok(Command::Quit),
+            ("", _) => Err("No command".to_string()),
+            (command, _) => Err(format!("Unknown command: {command}")),
+        }
+    }
+}
+
+/// An open command line
+#[derive(Debug)]
+pub struct CommandLine {
+    /// Text typed after the `:`
+    pub input: TextInput,
+}
+
+impl Default for CommandLine {
+    fn default() -> Self {
+        Self {
+            input: TextInput::new("Command", false),
+        }
+    }
+}
+
+/// What the command line did with a key press
+pub enum CommandLineOutcome {
+    /// The command line stays open
+    Open,
+    /// The command line was closed without running anything
+    Cancelled,
+    /// A command was entered, or the message of why it can't run
+    Run(Result<Command, String>),
+}
+
+impl CommandLine {
+    /// Handles a key press while the command line is open.
+    ///
+    /// - `Enter` - Parse the typed command
+    /// - `Esc` - Close the command line, as does `Backspace` on an empty line
+    /// - Any other key edits the command
+    pub fn handle_key(&mut self, key: KeyEvent) -> CommandLineOutcome {
+        match key.code {
+            KeyCode::Enter => CommandLineOutcome::Run(Command::parse(&self.input.value)),
+            KeyCode::Esc => CommandLineOutcome::Cancelled,
+            KeyCode::Backspace if self.input.value.is_empty() => CommandLineOutcome::Cancelled,
+            _ => {
+                self.input.handle_key(key);
+                CommandLineOutcome::Open
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_sends_term_unless_a_signal_is_named() {
+        assert_eq!(
+            Command::parse("kill 42"),
+            Ok(Command::Kill {
+                pid: 42,
+                signal: Signal::Term
+            })
+        );
+        assert_eq!(
+            Command::parse("kill 42 KILL"),
+            Ok(Command::Kill {
+                pid: 42,
+                signal: Signal::Kill
+            })
+        );
+        assert_eq!(
+            Command::parse("  kill   42   sighup "),
+            Ok(Command::Kill {
+                pid: 42,
+                signal: Signal::Hup
+            })
+        );
+    }
+
+    #[test]
+    fn test_kill_rejects_bad_arguments() {
+        assert_eq!(
+            Command::parse("kill abc"),
+            Err("Not a PID: abc".to_string())
+        );
+        assert_eq!(Command::parse("kill -1"), Err("Not a PID: -1".to_string()));
+        assert_eq!(
+            Command::parse("kill 42 NOPE"),
+            Err("Unknown signal: NOPE".to_string())
+        );
+        assert_eq!(
+            Command::parse("kill"),
+            Err("Usage: :kill <pid> [signal]".to_string())
+        );
+        assert_eq!(
+            Command::parse("kill 42 TERM extra"),
+            Err("Usage: :kill <pid> [signal]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parses_the_other_commands() {
+        assert_eq!(
+            Command::parse("sort mem"),
+            Ok(Command::Sort(ProcessSort::Memory))
+        );
+        assert_eq!(
+            Command::parse("group user"),
+            Ok(Command::Group(Some(ProcessGroupBy::User)))
+        );
+        assert_eq!(Command::parse("group off"), Ok(Command::Group(None)));
+        assert_eq!(Command::parse("export"), Ok(Command::Export));
+        assert_eq!(Command::parse("q"), Ok(Command::Quit));
+        assert_eq!(Command::parse("quit"), Ok(Command::Quit));
+        assert_eq!(
+            Command::parse("sort name"),
+            Err("Unknown sort column: name".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_empty_commands_are_errors() {
+        assert_eq!(
+            Command::parse("reboot"),
+            Err("Unknown command: reboot".to_string())
+        );
+        assert_eq!(
+            Command::parse("quit now"),
+            Err("Unknown command: quit".to_string())
+        );
+        assert_eq!(Command::parse("   "), Err("No command".to_string()));
+    }
+}
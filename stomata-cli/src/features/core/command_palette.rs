@@ -0,0 +1,278 @@
+//! Command palette
+//!
+//! `Ctrl+P` opens a searchable list of the actions of the application:
+//! switching pages, sorting and grouping the process list, signalling the
+//! selected process, exporting the process list, splitting the view and
+//! switching themes.
+//! Typing narrows the list with a fuzzy match, so `srtcpu` finds "Sort
+//! processes by CPU". The palette is modal: it takes every key until an
+//! action is run or it is closed.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::process::{ProcessGroupBy, actions::Signal};
+
+use crate::{
+    features::core::command_line::Command,
+    structs::{Page, ProcessSort, TableUIState, TextInput},
+};
+
+/// What an entry of the palette does
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteAction {
+    /// Switch to the page at this tab index
+    Page(usize),
+    /// Open the signals menu for the selected process with this signal
+    /// selected, to be confirmed there
+    Signal(Signal),
+    /// Open the signals menu for the selected process
+    SignalMenu,
+    /// Open the column editor of the process list
+    Columns,
+    /// Split the view in two panes, or close the split
+    Split,
+    /// Use the theme of this name
+    Theme(String),
+    /// Run a command of the command line
+    Command(Command),
+}
+
+/// An action of the palette with the text it is searched by
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    /// Text shown in the list
+    pub label: String,
+    /// What choosing the entry does
+    pub action: PaletteAction,
+}
+
+/// An open command palette
+#[derive(Debug)]
+pub struct CommandPalette {
+    /// Text the entries are searched for
+    pub query: TextInput,
+    /// Every action of the palette
+    pub entries: Vec<PaletteEntry>,
+    /// Indexes into `entries` matching the query, best match first
+    pub matches: Vec<usize>,
+    /// Selection state of the matches list
+    pub table: TableUIState,
+}
+
+/// What the palette did with a key press
+pub enum PaletteOutcome {
+    /// The palette stays open
+    Open,
+    /// The palette was closed without running anything
+    Cancelled,
+    /// An entry was chosen
+    Run(PaletteAction),
+}
+
+impl CommandPalette {
+    /// Opens the palette with every action, offering `themes` to switch
+    /// to
+    pub fn new(themes: &[String]) -> Self {
+        let mut entries: Vec<PaletteEntry> = Page::titles()
+            .into_iter()
+            .enumerate()
+            .map(|(index, title)| entry(format!("Go to {title}"), PaletteAction::Page(index)))
+            .collect();
+        let mut sort = ProcessSort::default();
+        loop {
+            entries.push(entry(
+                format!("Sort processes by {}", sort.label()),
+                PaletteAction::Command(Command::Sort(sort)),
+            ));
+            sort = sort.next();
+            if sort == ProcessSort::default() {
+                break;
+            }
+        }
+        entries.extend([
+            entry(
+                "Group processes by user".to_string(),
+                PaletteAction::Command(Command::Group(Some(ProcessGroupBy::User))),
+            ),
+            entry(
+                "Group processes by name".to_string(),
+                PaletteAction::Command(Command::Group(Some(ProcessGroupBy::Name))),
+            ),
+            entry(
+                "Ungroup processes".to_string(),
+                PaletteAction::Command(Command::Group(None)),
+            ),
+            entry(
+                "Terminate selected process (SIGTERM)".to_string(),
+                PaletteAction::Signal(Signal::Term),
+            ),
+            entry(
+                "Kill selected process (SIGKILL)".to_string(),
+                PaletteAction::Signal(Signal::Kill),
+            ),
+            entry(
+                "Send signal to selected process".to_string(),
+                PaletteAction::SignalMenu,
+            ),
+            entry("Edit process columns".to_string(), PaletteAction::Columns),
+            entry("Toggle split view".to_string(), PaletteAction::Split),
+            entry(
+                "Export process list to CSV".to_string(),
+                PaletteAction::Command(Command::Export),
+            ),
+        ]);
+        entries.extend(
+            themes
+                .iter()
+                .map(|name| entry(format!("Theme: {name}"), PaletteAction::Theme(name.clone()))),
+        );
+        entries.push(entry(
+            "Quit".to_string(),
+            PaletteAction::Command(Command::Quit),
+        ));
+
+        let mut palette = Self {
+            query: TextInput::new("Search actions", false),
+            matches: Vec::new(),
+            entries,
+            table: TableUIState::default(),
+        };
+        palette.search();
+        palette
+    }
+
+    /// Handles a key press while the palette is open.
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `Enter` - Run the selected action
+    /// - `Esc` - Close the palette
+    /// - Any other key edits the search
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteOutcome {
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Enter => {
+                return match self
+                    .table
+                    .list
+                    .selected()
+                    .and_then(|row| self.matches.get(row))
+                {
+                    Some(&index) => PaletteOutcome::Run(self.entries[index].action.clone()),
+                    None => PaletteOutcome::Open,
+                };
+            }
+            KeyCode::Esc => return PaletteOutcome::Cancelled,
+            _ => {
+                if self.query.handle_key(key) {
+                    self.search();
+                }
+            }
+        }
+        PaletteOutcome::Open
+    }
+
+    /// Lists the entries matching the query, best match first, and
+    /// selects the top one
+    fn search(&mut self) {
+        let mut scored: Vec<(usize, u32)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_score(&self.query.value, &entry.label).map(|score| (index, score))
+            })
+            .collect();
+        // stable, so equally good matches keep the order of the palette
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(index, _)| index).collect();
+        self.table.set_count(self.matches.len());
+        self.table.list.select(Some(0));
+    }
+}
+
+/// Builds a palette entry
+fn entry(label: String, action: PaletteAction) -> PaletteEntry {
+    PaletteEntry { label, action }
+}
+
+/// Scores how well `query` matches `text`, `None` when the characters of
+/// the query don't all appear in `text` in order. Case is ignored;
+/// characters matched right after the previous one or at the start of a
+/// word score higher, so typing the start of each word of an action finds
+/// it first.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut position = 0;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|c| *c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 4;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best_match(palette: &mut CommandPalette, query: &str) -> String {
+        palette.query.value = query.to_string().into();
+        palette.search();
+        palette.entries[palette.matches[0]].label.clone()
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything_in_order() {
+        assert_eq!(fuzzy_score("", "Quit"), Some(0));
+        assert_eq!(fuzzy_score("  ", "Quit"), Some(0));
+
+        let palette = CommandPalette::new(&["dark".to_string()]);
+        let all: Vec<usize> = (0..palette.entries.len()).collect();
+        assert_eq!(palette.matches, all);
+        assert_eq!(palette.table.list.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_characters_must_appear_in_order() {
+        assert_eq!(fuzzy_score("xyz", "Sort processes by CPU"), None);
+        assert_eq!(fuzzy_score("upc", "Sort processes by CPU"), None);
+        assert!(fuzzy_score("SORT", "Sort processes by CPU").is_some());
+    }
+
+    #[test]
+    fn test_word_starts_and_runs_rank_higher() {
+        // "s", "p" and "c" all start a word
+        let initials = fuzzy_score("spc", "Sort processes by CPU").unwrap();
+        let scattered = fuzzy_score("spc", "Inspect").unwrap();
+        assert!(initials > scattered);
+
+        let run = fuzzy_score("kill", "Kill selected process (SIGKILL)").unwrap();
+        let gaps = fuzzy_score("kill", "Keep it all lit").unwrap();
+        assert!(run > gaps);
+    }
+
+    #[test]
+    fn test_best_matches_come_first() {
+        let mut palette = CommandPalette::new(&["dark".to_string(), "light".to_string()]);
+        assert_eq!(best_match(&mut palette, "srtcpu"), "Sort processes by CPU");
+        assert_eq!(
+            best_match(&mut palette, "kill"),
+            "Kill selected process (SIGKILL)"
+        );
+        assert_eq!(best_match(&mut palette, "theme light"), "Theme: light");
+        assert_eq!(best_match(&mut palette, "quit"), "Quit");
+
+        palette.query.value = "zzzz".to_string().into();
+        palette.search();
+        assert!(palette.matches.is_empty());
+    }
+}
@@ -86,37 +86,60 @@ pub fn run(
 ) -> anyhow::Result<bool> {
     match terminal {
         Some(terminal) => {
-            let store_metrics_data = cli.store;
-            let mut app = App::new(store_metrics_data);
+            let mut app = App::new(cli.store);
+            if cli.inhibit_sleep {
+                app.toggle_sleep_inhibitor();
+            }
+            run_app(&mut app, cli, terminal)
+        }
+        None => Ok(false),
+    }
+}
 
-            // get the refresh interval from the cli arg. Default 1000 ms
-            let refresh_interval = Duration::from_millis(cli.interval);
-            let mut last_tick = Instant::now();
+/// Runs the render loop of `app` until the user quits, redrawing every
+/// `cli.interval` milliseconds and after every key press
+///
+/// # Errors
+///
+/// Returns an error if polling for input, handling a key or drawing fails.
+pub fn run_app(
+    app: &mut App,
+    cli: &Cli,
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+) -> anyhow::Result<bool> {
+    // get the refresh interval from the cli arg. Default 1000 ms
+    let refresh_interval = Duration::from_millis(cli.interval);
+    tracing::info!(interval_ms = cli.interval, "starting the system monitor");
+    app.start_recording(refresh_interval);
+    // listing the services queries systemd once per running unit, more
+    // than a redraw after a key press should cost
+    app.metrics.services.set_refresh_interval(refresh_interval);
+    app.set_remote_interval(refresh_interval);
+    let mut last_tick = Instant::now();
 
-            // main render loop
-            while app.render {
-                let timeout = refresh_interval
-                    .checked_sub(last_tick.elapsed())
-                    .unwrap_or(Duration::from_secs(0));
+    // main render loop
+    while app.render {
+        let timeout = refresh_interval
+            .checked_sub(last_tick.elapsed())
+            .unwrap_or(Duration::from_secs(0));
 
-                // poll for inputs only until timeout
-                if event::poll(timeout)? {
-                    if let Event::Key(key) = event::read()? {
-                        // handle events
-                        app.handle_events(key)?;
-                        // redraw immediately after an event
-                        terminal.draw(|frame| app.render(frame))?;
-                    }
-                }
+        // poll for inputs only until timeout
+        if event::poll(timeout)?
+            && let Event::Key(key) = event::read()?
+        {
+            // handle events
+            app.handle_events(key)?;
+            // redraw immediately after an event
+            terminal.draw(|frame| app.render(frame))?;
+        }
 
-                if last_tick.elapsed() >= refresh_interval {
-                    // draw
-                    terminal.draw(|frame| app.render(frame))?;
-                    last_tick = Instant::now();
-                }
-            }
-            Ok(app.render)
+        if last_tick.elapsed() >= refresh_interval {
+            // draw
+            let started = Instant::now();
+            terminal.draw(|frame| app.render(frame))?;
+            tracing::trace!(elapsed_ms = started.elapsed().as_millis(), "frame drawn");
+            last_tick = Instant::now();
         }
-        None => Ok(false),
     }
+    Ok(app.render)
 }
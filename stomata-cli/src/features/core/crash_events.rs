@@ -0,0 +1,80 @@
+//! OOM kills, segfaults and service crashes of the Events page
+//!
+//! The journal is read again every `CRASH_EVENTS_REFRESH_SECS` seconds
+//! instead of on every refresh, since journalctl is slow on big journals.
+//! The events are also used to mark failures on the network sparklines.
+
+use std::time::{Duration, Instant};
+
+use chrono::DateTime;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::CrashEvent;
+use sysinfo::System;
+
+use crate::{constants::CRASH_EVENTS_REFRESH_SECS, structs::TableUIState};
+
+/// State of the Events page
+#[derive(Debug, Default)]
+pub struct CrashEventsUIState {
+    /// Events since boot, newest first, or the error reading the journal.
+    /// `None` until first read.
+    pub events: Option<Result<Vec<CrashEvent>, String>>,
+
+    /// When the journal was last read
+    pub fetched_at: Option<Instant>,
+
+    /// Selection state of the events table
+    pub table: TableUIState,
+}
+
+impl CrashEventsUIState {
+    /// Reads the events logged since boot
+    pub fn refresh(&mut self) {
+        let since = DateTime::from_timestamp(System::boot_time() as i64, 0).unwrap_or_default();
+        self.events = Some(
+            CrashEvent::fetch(since)
+                .map(|mut events| {
+                    events.reverse();
+                    events
+                })
+                .map_err(|err| format!("{err:#}")),
+        );
+        self.fetched_at = Some(Instant::now());
+    }
+
+    /// Reads the journal again when the events are older than the refresh
+    /// interval
+    pub fn refresh_if_stale(&mut self) {
+        let stale = self.fetched_at.is_none_or(|fetched_at| {
+            fetched_at.elapsed() >= Duration::from_secs(CRASH_EVENTS_REFRESH_SECS)
+        });
+        if stale {
+            self.refresh();
+        }
+    }
+
+    /// Events read so far, empty when the journal couldn't be read
+    pub fn events(&self) -> &[CrashEvent] {
+        match &self.events {
+            Some(Ok(events)) => events,
+            _ => &[],
+        }
+    }
+
+    /// Handles a key press on the Events page.
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `r` - Read the journal again
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('r') => self.refresh(),
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,105 @@
+//! Disk selection and unmount action of the Disks page
+//!
+//! The page has two tables, disk activity and mounted filesystems, and
+//! `f` switches which one the arrow keys move. Unmounting asks for
+//! confirmation first since programs may still be writing to the media,
+//! then runs on a worker thread as a busy device can take up to
+//! `UNMOUNT_TIMEOUT_MS` to answer.
+
+use std::collections::HashMap;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::disks::Mount;
+
+use crate::structs::{DiskIoHistory, StatusBar, TableUIState};
+
+/// Table of the Disks page that the arrow keys move
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DisksPanel {
+    /// Disk activity table
+    #[default]
+    Io,
+    /// Mounted filesystems table
+    Mounts,
+}
+
+/// State of the Disks page
+#[derive(Debug, Default)]
+pub struct DisksUIState {
+    /// Selected disk of the activity table
+    pub table: TableUIState,
+
+    /// Latency, queue depth and utilization history per disk name
+    pub history: HashMap<String, DiskIoHistory>,
+
+    /// Selected filesystem of the mounts table
+    pub mounts_table: TableUIState,
+
+    /// Mounts in table order, to resolve the selection
+    pub mounts: Vec<Mount>,
+
+    /// Table the arrow keys move
+    pub focus: DisksPanel,
+
+    /// Mount waiting for the unmount to be confirmed
+    pub confirm_unmount: Option<Mount>,
+}
+
+impl DisksUIState {
+    /// Selected mount of the mounts table
+    pub fn selected_mount(&self) -> Option<&Mount> {
+        self.mounts_table
+            .list
+            .selected()
+            .and_then(|index| self.mounts.get(index))
+    }
+
+    /// Handles a key press on the Disks page.
+    ///
+    /// While an unmount waits for confirmation, `y` starts it in the
+    /// background and any other key cancels, the outcome is shown in
+    /// `status_bar`. Otherwise:
+    ///
+    /// - `Up`/`Down` - Move the selection of the focused table
+    /// - `f` - Switch focus between the disks and mounts tables
+    /// - `u` - Unmount the selected filesystem, after confirmation
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent, status_bar: &mut StatusBar) -> bool {
+        if let Some(mount) = self.confirm_unmount.take() {
+            if key.code == KeyCode::Char('y') {
+                let text = format!("Unmounting {}…", mount.mount_point);
+                status_bar.run_in_background(text, move || {
+                    mount
+                        .unmount()
+                        .map(|()| format!("Unmounted {}", mount.mount_point))
+                        .map_err(|err| format!("{err:#}"))
+                });
+            } else {
+                status_bar.info(format!("Unmount of {} cancelled", mount.mount_point));
+            }
+            return true;
+        }
+
+        let table = match self.focus {
+            DisksPanel::Io => &mut self.table,
+            DisksPanel::Mounts => &mut self.mounts_table,
+        };
+        match key.code {
+            KeyCode::Down => table.select_next(),
+            KeyCode::Up => table.select_previous(),
+            KeyCode::Char('f') => {
+                self.focus = match self.focus {
+                    DisksPanel::Io => DisksPanel::Mounts,
+                    DisksPanel::Mounts => DisksPanel::Io,
+                };
+            }
+            KeyCode::Char('u') if self.focus == DisksPanel::Mounts => {
+                self.confirm_unmount = self.selected_mount().cloned();
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,116 @@
+//! Executable checksum verification
+//!
+//! Hashes the executable of a process with SHA-256 on demand and compares
+//! it against a user-provided expected digest, e.g. from a vendor's release
+//! page. Uses the same hashing utilities as the web3 tools. Executables
+//! can be hundreds of megabytes, so the file is hashed on a worker thread
+//! and the page shows "hashing…" until the digest arrives.
+
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use stomata_web3::providers::hashing::{digests_match, sha256_file};
+
+/// SHA-256 checksum of a process executable
+#[derive(Debug)]
+pub struct ExeChecksum {
+    /// Process the executable belongs to
+    pub pid: u32,
+
+    /// Hex digest, or the reason the file couldn't be read, `None` while
+    /// the file is being hashed
+    pub digest: Option<Result<String, String>>,
+
+    /// Digest entered by the user to compare against
+    pub expected: Option<String>,
+
+    /// Delivers the digest once the worker thread finished
+    pending: Option<Receiver<Result<String, String>>>,
+}
+
+impl ExeChecksum {
+    /// Starts hashing the executable at `path` on a worker thread, the
+    /// digest is picked up by [`ExeChecksum::poll`].
+    pub fn compute(pid: u32, path: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // the page may be gone by the time the digest is ready
+            let _ = sender.send(sha256_file(&path).map_err(|err| err.to_string()));
+        });
+        Self {
+            pid,
+            digest: None,
+            expected: None,
+            pending: Some(receiver),
+        }
+    }
+
+    /// Takes the digest of the worker thread if it finished
+    pub fn poll(&mut self) {
+        let Some(receiver) = &self.pending else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(digest) => self.digest = Some(digest),
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.digest = Some(Err("hashing stopped unexpectedly".to_string()));
+            }
+        }
+        self.pending = None;
+    }
+
+    /// Whether the digest matches the expected one, `None` if there is
+    /// nothing to compare yet
+    pub fn matches(&self) -> Option<bool> {
+        let digest = self.digest.as_ref()?.as_ref().ok()?;
+        let expected = self.expected.as_ref()?;
+        Some(digests_match(digest, expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn test_digest_arrives_from_the_worker() {
+        let path = std::env::temp_dir().join(format!("stomata-exe-{}", std::process::id()));
+        fs::write(&path, "abc").unwrap();
+        let mut checksum = ExeChecksum::compute(1, path.clone());
+        checksum.expected =
+            Some("0xBA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD".to_string());
+
+        for _ in 0..200 {
+            checksum.poll();
+            if checksum.digest.is_some() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = fs::remove_file(&path);
+        assert_eq!(
+            checksum.digest,
+            Some(Ok(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()
+            ))
+        );
+        assert_eq!(checksum.matches(), Some(true));
+    }
+
+    #[test]
+    fn test_missing_files_report_the_error() {
+        let mut checksum = ExeChecksum::compute(1, PathBuf::from("/nonexistent/stomata-exe"));
+        assert_eq!(checksum.matches(), None);
+        while checksum.digest.is_none() {
+            checksum.poll();
+            thread::yield_now();
+        }
+        assert!(matches!(checksum.digest, Some(Err(_))));
+    }
+}
@@ -0,0 +1,234 @@
+//! Streaming the collector registry to a metrics pipeline
+//!
+//! Runs the registered collectors on their own intervals and forwards every
+//! value to the chosen sink until interrupted. By default it exports CPU,
+//! memory with swap, and the per-interface network rates.
+//!
+//! The MQTT password is read from `STOMATA_MQTT_PASSWORD` so it doesn't
+//! show up in the process list.
+//!
+//! ```bash
+//! stomata export --sink statsd --statsd-addr 127.0.0.1:8125
+//! stomata export --sink statsd --dogstatsd --tag env:prod --tag team:infra
+//! stomata export --sink statsd --only cpu,memory,network,disk
+//! stomata export --sink mqtt --mqtt-addr broker.lan:1883 --home-assistant
+//! ```
+
+use std::{env, iter::once, thread, time::Instant};
+
+use anyhow::{Context, bail};
+use clap::{Parser, ValueEnum};
+use stomata_core::{
+    collectors::{CollectorRegistry, SystemInfo},
+    export::{MetricSink, MqttCredentials, MqttSink, StatsdSink},
+};
+
+/// Environment variable holding the MQTT password
+const MQTT_PASSWORD_ENV: &str = "STOMATA_MQTT_PASSWORD";
+
+/// Where `stomata export` sends the metrics
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sink {
+    /// StatsD gauges over UDP
+    Statsd,
+    /// MQTT messages, one topic per value
+    Mqtt,
+}
+
+/// Arguments of `stomata export`
+#[derive(Parser, Clone)]
+#[command(name = "export")]
+#[command(about = "Stream the registered collectors to a metrics pipeline")]
+pub struct ExportCli {
+    /// Target of the metrics
+    #[arg(long, value_enum, default_value_t = Sink::Statsd)]
+    pub sink: Sink,
+    /// Collectors to export
+    #[arg(long, value_delimiter = ',', default_value = "cpu,memory,network")]
+    pub only: Vec<String>,
+    /// Address of the StatsD server
+    #[arg(long, default_value = "127.0.0.1:8125")]
+    pub statsd_addr: String,
+    /// Prefix of every metric name
+    #[arg(long, default_value = "stomata")]
+    pub prefix: String,
+    /// Send DogStatsD lines with the instance as a tag
+    #[arg(long, default_value_t = false)]
+    pub dogstatsd: bool,
+    /// Extra DogStatsD tag, e.g. `env:prod`, can be repeated
+    #[arg(long = "tag", requires = "dogstatsd")]
+    pub tags: Vec<String>,
+    /// Address of the MQTT broker
+    #[arg(long, default_value = "127.0.0.1:1883")]
+    pub mqtt_addr: String,
+    /// Topic prefix, `{hostname}` is replaced by the host name
+    #[arg(long, default_value = "stomata/{hostname}")]
+    pub mqtt_topic: String,
+    /// MQTT user name, the password is read from `STOMATA_MQTT_PASSWORD`
+    #[arg(long)]
+    pub mqtt_username: Option<String>,
+    /// Publish Home Assistant discovery configs for every sensor
+    #[arg(long, default_value_t = false)]
+    pub home_assistant: bool,
+    /// Topic prefix Home Assistant listens on for discovery
+    #[arg(long, default_value = "homeassistant", requires = "home_assistant")]
+    pub discovery_prefix: String,
+}
+
+/// Parses the export arguments and forwards metrics until the process is
+/// stopped. Failing collectors and sends are reported on stderr without
+/// stopping the export.
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid, name an unknown
+/// collector, or the sink can't be set up.
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    let cli = ExportCli::try_parse_from(once("export".to_string()).chain(args.iter().cloned()))?;
+    let mut registry = CollectorRegistry::with_builtin();
+    if let Some(unknown) = cli
+        .only
+        .iter()
+        .find(|id| !registry.ids().any(|known| known == id.as_str()))
+    {
+        let known: Vec<&str> = registry.ids().collect();
+        bail!(
+            "unknown collector `{unknown}`, expected one of {}",
+            known.join(", ")
+        );
+    }
+    let only: Vec<&str> = cli.only.iter().map(String::as_str).collect();
+    registry.retain(&only);
+
+    let mut sink: Box<dyn MetricSink> = match cli.sink {
+        Sink::Statsd => {
+            let sink = StatsdSink::connect(cli.statsd_addr.as_str(), cli.prefix.clone())
+                .with_context(|| format!("failed to set up StatsD at {}", cli.statsd_addr))?;
+            if cli.dogstatsd {
+                Box::new(sink.with_dogstatsd_tags(cli.tags.clone()))
+            } else {
+                Box::new(sink)
+            }
+        }
+        Sink::Mqtt => {
+            let hostname = SystemInfo::new().hostname;
+            let credentials = cli.mqtt_username.clone().map(|username| MqttCredentials {
+                username,
+                password: env::var(MQTT_PASSWORD_ENV).ok(),
+            });
+            let sink = MqttSink::connect(
+                cli.mqtt_addr.as_str(),
+                format!("stomata-{hostname}"),
+                cli.mqtt_topic.replace("{hostname}", &hostname),
+                credentials,
+            )
+            .with_context(|| format!("failed to connect to MQTT broker {}", cli.mqtt_addr))?;
+            if cli.home_assistant {
+                Box::new(sink.with_home_assistant(cli.discovery_prefix.clone(), hostname))
+            } else {
+                Box::new(sink)
+            }
+        }
+    };
+
+    loop {
+        forward_due(&mut registry, sink.as_mut(), Instant::now());
+        thread::sleep(registry.next_due(Instant::now()));
+    }
+}
+
+/// Sends the values of the collectors due at `now` to `sink`, reporting
+/// failures on stderr
+fn forward_due(registry: &mut CollectorRegistry, sink: &mut dyn MetricSink, now: Instant) {
+    for (id, result) in registry.collect_due(now) {
+        let sent = result.and_then(|metric| sink.send(&metric));
+        if let Err(err) = sent {
+            eprintln!("{id}: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stomata_core::{
+        collectors::{FakeSystem, Metric, backend::FakeFrame, network::metrics::NetworkInterfaces},
+        errors::CoreResult,
+    };
+
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    /// Sink keeping every metric it was sent
+    #[derive(Default)]
+    struct Recorder(Vec<Metric>);
+
+    impl MetricSink for Recorder {
+        fn name(&self) -> &'static str {
+            "recorder"
+        }
+
+        fn send(&mut self, metric: &Metric) -> CoreResult<()> {
+            self.0.push(metric.clone());
+            Ok(())
+        }
+    }
+
+    fn frame(cpu_usage: f32, received: u64) -> FakeFrame {
+        FakeFrame {
+            cpu_count: 2,
+            cpu_usage,
+            per_cpu_usage: vec![cpu_usage - 10.0, cpu_usage + 10.0],
+            memory_total: 8 * GIB,
+            memory_used: 2 * GIB,
+            memory_available: 6 * GIB,
+            interfaces: vec![NetworkInterfaces {
+                name: "eth0".to_string(),
+                total_bytes_received: received,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exports_the_fake_system() {
+        let system = FakeSystem::new(vec![frame(20.0, 1_000), frame(40.0, 5_000)]);
+        let mut registry = CollectorRegistry::with_backend(|| Box::new(system.clone()));
+        registry.retain(&["cpu", "memory", "network"]);
+        let mut sink = Recorder::default();
+
+        forward_due(&mut registry, &mut sink, Instant::now());
+
+        let collectors: Vec<&str> = sink.0.iter().map(|metric| metric.collector).collect();
+        assert_eq!(collectors, ["cpu", "memory", "network"]);
+        let values = |collector: &str| -> Vec<(String, f64)> {
+            sink.0
+                .iter()
+                .find(|metric| metric.collector == collector)
+                .unwrap()
+                .values
+                .iter()
+                .map(|value| (value.key(), value.value))
+                .collect()
+        };
+        // every collector refreshed once, onto the second frame
+        assert_eq!(
+            values("cpu"),
+            [
+                ("usage".to_string(), 40.0),
+                ("cpu0.usage".to_string(), 30.0),
+                ("cpu1.usage".to_string(), 50.0),
+            ]
+        );
+        let memory = values("memory");
+        assert!(memory.contains(&("used".to_string(), (2 * GIB) as f64)));
+        assert!(memory.contains(&("used_percent".to_string(), 25.0)));
+        let network = values("network");
+        let (_, received) = network
+            .iter()
+            .find(|(key, _)| key == "eth0.rx_bytes_per_sec")
+            .unwrap();
+        assert!(*received > 0.0);
+    }
+}
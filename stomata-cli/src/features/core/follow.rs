@@ -0,0 +1,57 @@
+//! Follow mode of the SingleProcess page
+//!
+//! With follow mode on, a process that exits and is started again, e.g.
+//! by systemd or a supervisor, is picked up under its new PID. The charts
+//! keep their history and mark the samples where the process restarted.
+
+use chrono::{DateTime, Utc};
+use stomata_core::collectors::process::ProcessData;
+
+/// The process followed across restarts
+#[derive(Debug, Default)]
+pub struct FollowTarget {
+    /// PID of the running instance
+    pub pid: u32,
+
+    /// Executable name of the process
+    pub name: String,
+
+    /// Path of the executable, matched first when looking for the restarted
+    /// process
+    pub exe_path: Option<String>,
+
+    /// When the process was seen restarting, oldest first
+    pub restarts: Vec<DateTime<Utc>>,
+}
+
+impl FollowTarget {
+    /// Starts following `pid`; the name and executable are filled in by
+    /// [`FollowTarget::observe`]
+    pub fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            ..Self::default()
+        }
+    }
+
+    /// Remembers the name and executable of the running instance
+    pub fn observe(&mut self, process: &ProcessData) {
+        if process.pid != self.pid {
+            return;
+        }
+        self.name.clone_from(&process.name);
+        self.exe_path.clone_from(&process.exe_path);
+    }
+
+    /// Whether the name of the process is known, so a successor can be
+    /// looked for
+    pub fn is_known(&self) -> bool {
+        !self.name.is_empty()
+    }
+
+    /// Switches to the instance `pid` that replaced the exited one
+    pub fn restarted(&mut self, pid: u32, at: DateTime<Utc>) {
+        self.pid = pid;
+        self.restarts.push(at);
+    }
+}
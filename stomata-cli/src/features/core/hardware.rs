@@ -0,0 +1,82 @@
+//! Hardware panel of the System page
+//!
+//! Devices are enumerated when a view is first shown and again on
+//! request, not on every refresh, since they rarely change.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::{pci::PciDevice, usb::UsbDevice};
+
+use crate::structs::TableUIState;
+
+/// Device list shown in the hardware panel
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum HardwareView {
+    #[default]
+    Usb,
+    Pci,
+}
+
+/// State of the hardware panel
+#[derive(Debug, Default)]
+pub struct HardwareUIState {
+    /// Device list currently shown
+    pub view: HardwareView,
+
+    /// USB device tree, or the error enumerating it. `None` until the
+    /// view is first shown.
+    pub usb: Option<Result<Vec<UsbDevice>, String>>,
+
+    /// PCI devices, or the error listing them. `None` until the view is
+    /// first shown.
+    pub pci: Option<Result<Vec<PciDevice>, String>>,
+
+    /// Selection state of the devices table
+    pub table: TableUIState,
+}
+
+impl HardwareUIState {
+    /// Enumerates the devices of the current view again
+    pub fn refresh(&mut self) {
+        match self.view {
+            HardwareView::Usb => {
+                self.usb = Some(UsbDevice::fetch().map_err(|err| format!("{err:#}")));
+            }
+            HardwareView::Pci => {
+                self.pci = Some(PciDevice::fetch().map_err(|err| format!("{err:#}")));
+            }
+        }
+    }
+
+    /// Whether the current view was never enumerated
+    pub fn needs_refresh(&self) -> bool {
+        match self.view {
+            HardwareView::Usb => self.usb.is_none(),
+            HardwareView::Pci => self.pci.is_none(),
+        }
+    }
+
+    /// Handles a key press on the System page.
+    ///
+    /// - `Up`/`Down` - Scroll the device list
+    /// - `t` - Switch between USB and PCI devices
+    /// - `r` - Enumerate the devices again
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('t') => {
+                self.view = match self.view {
+                    HardwareView::Usb => HardwareView::Pci,
+                    HardwareView::Pci => HardwareView::Usb,
+                };
+                self.table = TableUIState::default();
+            }
+            KeyCode::Char('r') => self.refresh(),
+            _ => return false,
+        }
+        true
+    }
+}
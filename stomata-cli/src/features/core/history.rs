@@ -0,0 +1,150 @@
+//! Stored history of the History page
+//!
+//! With `--store`, CPU, memory and network samples are written to the
+//! SQLite store. The History page reads them back for the selected range,
+//! averaged into a fixed number of points, and queries the store again
+//! every `HISTORY_REFRESH_SECS` seconds or when the range changes.
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::{
+    history::Sample,
+    storage::{MetricStore, RangeStats},
+};
+
+use crate::constants::{HISTORY_CHART_POINTS, HISTORY_REFRESH_SECS};
+
+/// Time range shown on the History page
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryRange {
+    /// The last 15 minutes
+    #[default]
+    FifteenMinutes,
+    /// The last hour
+    Hour,
+    /// The last 6 hours
+    SixHours,
+    /// The last 24 hours
+    Day,
+}
+
+impl HistoryRange {
+    /// Short label, e.g. `15m`
+    pub fn label(self) -> &'static str {
+        match self {
+            HistoryRange::FifteenMinutes => "15m",
+            HistoryRange::Hour => "1h",
+            HistoryRange::SixHours => "6h",
+            HistoryRange::Day => "24h",
+        }
+    }
+
+    /// Length of the range
+    pub fn duration(self) -> chrono::Duration {
+        match self {
+            HistoryRange::FifteenMinutes => chrono::Duration::minutes(15),
+            HistoryRange::Hour => chrono::Duration::hours(1),
+            HistoryRange::SixHours => chrono::Duration::hours(6),
+            HistoryRange::Day => chrono::Duration::hours(24),
+        }
+    }
+
+    /// The next longer range, wrapping to the shortest
+    pub fn next(self) -> Self {
+        match self {
+            HistoryRange::FifteenMinutes => HistoryRange::Hour,
+            HistoryRange::Hour => HistoryRange::SixHours,
+            HistoryRange::SixHours => HistoryRange::Day,
+            HistoryRange::Day => HistoryRange::FifteenMinutes,
+        }
+    }
+
+    /// The next shorter range, wrapping to the longest
+    pub fn previous(self) -> Self {
+        match self {
+            HistoryRange::FifteenMinutes => HistoryRange::Day,
+            HistoryRange::Hour => HistoryRange::FifteenMinutes,
+            HistoryRange::SixHours => HistoryRange::Hour,
+            HistoryRange::Day => HistoryRange::SixHours,
+        }
+    }
+}
+
+/// Stored samples of one metric over the selected range
+#[derive(Debug, Clone)]
+pub struct HistorySeries {
+    /// Name of the metric in the store, e.g. `cpu`
+    pub metric: &'static str,
+    /// Averaged samples, oldest first
+    pub samples: Vec<Sample<f64>>,
+    /// Lowest, average and highest value over the range, `None` without
+    /// samples
+    pub stats: Option<RangeStats>,
+}
+
+/// State of the History page
+#[derive(Debug, Default)]
+pub struct HistoryUIState {
+    /// Range the charts cover
+    pub range: HistoryRange,
+
+    /// Series of the last query, or the error it failed with. `None`
+    /// until first queried.
+    pub series: Option<Result<Vec<HistorySeries>, String>>,
+
+    /// When the store was last queried
+    pub fetched_at: Option<Instant>,
+}
+
+impl HistoryUIState {
+    /// Metrics charted on the page, in display order
+    pub const METRICS: [&'static str; 4] = ["cpu", "memory", "network_rx", "network_tx"];
+
+    /// Whether the series are older than the refresh interval or were
+    /// never read
+    pub fn needs_refresh(&self) -> bool {
+        self.fetched_at.is_none_or(|fetched_at| {
+            fetched_at.elapsed() >= Duration::from_secs(HISTORY_REFRESH_SECS)
+        })
+    }
+
+    /// Reads the series of the selected range from `store`
+    pub fn refresh(&mut self, store: &MetricStore) {
+        let to = Utc::now();
+        let from = to - self.range.duration();
+        self.series = Some(
+            Self::METRICS
+                .into_iter()
+                .map(|metric| {
+                    Ok(HistorySeries {
+                        metric,
+                        samples: store.query(metric, from, to, HISTORY_CHART_POINTS)?,
+                        stats: store.stats(metric, from, to)?,
+                    })
+                })
+                .collect::<Result<_, stomata_core::CoreError>>()
+                .map_err(|err| format!("{err:#}")),
+        );
+        self.fetched_at = Some(Instant::now());
+    }
+
+    /// Handles a key press on the History page.
+    ///
+    /// - `r` - Show the next longer range
+    /// - `R` - Show the next shorter range
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('r') => self.range = self.range.next(),
+            KeyCode::Char('R') => self.range = self.range.previous(),
+            _ => return false,
+        }
+        // query the new range on the next frame
+        self.fetched_at = None;
+        true
+    }
+}
@@ -0,0 +1,49 @@
+//! Hardware inventory export
+//!
+//! Prints the CPU, memory modules, disks, NICs and GPUs of the machine as a
+//! single JSON or YAML document, for asset tracking across a fleet.
+//!
+//! ```bash
+//! stomata inventory
+//! stomata inventory --format yaml > $(hostname).yaml
+//! ```
+
+use std::iter::once;
+
+use clap::{Parser, ValueEnum};
+use stomata_core::collectors::Inventory;
+
+/// Arguments of `stomata inventory`
+#[derive(Parser, Clone)]
+#[command(name = "inventory")]
+#[command(about = "Print a machine-readable hardware inventory")]
+pub struct InventoryCli {
+    /// Document format to print
+    #[arg(short, long, value_enum, default_value_t = InventoryFormat::Json)]
+    pub format: InventoryFormat,
+}
+
+/// Output format of the inventory document
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum InventoryFormat {
+    Json,
+    Yaml,
+}
+
+/// Parses the inventory arguments and prints the document to stdout
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid or the inventory can't
+/// be serialized.
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    let cli =
+        InventoryCli::try_parse_from(once("inventory".to_string()).chain(args.iter().cloned()))?;
+    let inventory = Inventory::fetch();
+    let document = match cli.format {
+        InventoryFormat::Json => inventory.to_json()?,
+        InventoryFormat::Yaml => inventory.to_yaml()?,
+    };
+    println!("{document}");
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Keybinding profiles
+//!
+//! `keymap` in the config file picks the profile. The default profile
+//! uses the arrow keys; the `vim` profile adds vim motions on top of it:
+//! keys of the profile are translated to the keys the pages already
+//! handle, so every page picks them up without knowing about profiles.
+//! Keys are only translated while no text input has focus.
+//!
+//! # Vim profile
+//!
+//! - `j`/`k` - Move the selection down or up on every table
+//! - `gg`/`G` - First and last row of the process list, like in the
+//!   default profile
+//! - `/` - Search the process list, like in the default profile
+//! - `:` - Open the command line, see [`super::command_line`]
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+/// Keybinding profile, named in lowercase in the config file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapProfile {
+    /// Arrow keys and single letter shortcuts
+    #[default]
+    Default,
+    /// The default keys plus vim motions and the `:` command line
+    Vim,
+}
+
+impl KeymapProfile {
+    /// Translates a key of this profile to the key the pages handle
+    pub fn translate(self, key: KeyEvent) -> KeyEvent {
+        if self != KeymapProfile::Vim || key.modifiers != KeyModifiers::NONE {
+            return key;
+        }
+        let code = match key.code {
+            KeyCode::Char('j') => KeyCode::Down,
+            KeyCode::Char('k') => KeyCode::Up,
+            code => code,
+        };
+        KeyEvent { code, ..key }
+    }
+
+    /// Whether `:` opens the command line
+    pub fn has_command_line(self) -> bool {
+        self == KeymapProfile::Vim
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::UIState;
+
+    fn press(profile: KeymapProfile, code: KeyCode) -> KeyCode {
+        profile.translate(KeyEvent::from(code)).code
+    }
+
+    #[test]
+    fn test_vim_profile_translates_j_and_k() {
+        assert_eq!(press(KeymapProfile::Vim, KeyCode::Char('j')), KeyCode::Down);
+        assert_eq!(press(KeymapProfile::Vim, KeyCode::Char('k')), KeyCode::Up);
+        assert_eq!(
+            press(KeymapProfile::Vim, KeyCode::Char('x')),
+            KeyCode::Char('x')
+        );
+    }
+
+    #[test]
+    fn test_default_profile_and_modified_keys_are_unchanged() {
+        assert_eq!(
+            press(KeymapProfile::Default, KeyCode::Char('j')),
+            KeyCode::Char('j')
+        );
+        let ctrl_j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(KeymapProfile::Vim.translate(ctrl_j), ctrl_j);
+        assert!(KeymapProfile::Vim.has_command_line());
+        assert!(!KeymapProfile::Default.has_command_line());
+    }
+
+    #[test]
+    fn test_vim_motions_move_the_process_selection() {
+        let mut table = UIState::default().process_table;
+        table.process_count = 10;
+        let mut press_on_table = |code| {
+            let consumed = table.navigate(press(KeymapProfile::Vim, code));
+            (consumed, table.process_list.selected())
+        };
+
+        assert_eq!(press_on_table(KeyCode::Char('G')), (true, Some(9)));
+        assert_eq!(press_on_table(KeyCode::Char('k')), (true, Some(8)));
+        // the first `g` waits for the second one
+        assert_eq!(press_on_table(KeyCode::Char('g')), (true, Some(8)));
+        assert_eq!(press_on_table(KeyCode::Char('g')), (true, Some(0)));
+        assert_eq!(press_on_table(KeyCode::Char('j')), (true, Some(1)));
+        // another key in between cancels the pending `g`
+        assert_eq!(press_on_table(KeyCode::Char('g')), (true, Some(1)));
+        assert_eq!(press_on_table(KeyCode::Char('j')), (true, Some(2)));
+        assert_eq!(press_on_table(KeyCode::Char('g')), (true, Some(2)));
+        assert_eq!(press_on_table(KeyCode::Char('x')), (false, Some(2)));
+        assert_eq!(press_on_table(KeyCode::Char('g')), (true, Some(2)));
+    }
+}
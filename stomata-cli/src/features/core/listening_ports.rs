@@ -0,0 +1,70 @@
+//! Filter and selection of the Ports page
+//!
+//! `/` opens the filter box; the table follows the filter while typing,
+//! `Enter` keeps it and `Esc` clears it.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use crate::structs::{TableUIState, TextInput};
+
+/// State of the Ports page
+#[derive(Debug)]
+pub struct PortsUIState {
+    /// Selection state of the listening sockets table
+    pub table: TableUIState,
+
+    /// Port, address or process name the sockets are filtered by
+    pub filter: TextInput,
+
+    /// Whether keys go to the filter box
+    pub editing: bool,
+}
+
+impl Default for PortsUIState {
+    fn default() -> Self {
+        Self {
+            table: TableUIState::default(),
+            filter: TextInput::new("Filter (port, address or process)", false),
+            editing: false,
+        }
+    }
+}
+
+impl PortsUIState {
+    /// Handles a key press on the Ports page.
+    ///
+    /// While the filter box is open every key edits it, `Enter` closes it
+    /// keeping the filter and `Esc` closes it clearing the filter.
+    /// Otherwise:
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `/` - Open the filter box
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.editing {
+            match key.code {
+                KeyCode::Enter => self.editing = false,
+                KeyCode::Esc => {
+                    self.editing = false;
+                    self.filter.value.clear();
+                }
+                _ => {
+                    if self.filter.handle_key(key) {
+                        self.table.list.select(Some(0));
+                    }
+                }
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('/') => self.editing = true,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,95 @@
+//! Filter, pause and scrolling of the Logs page
+//!
+//! The page follows the log with the newest entry on top. `p` pauses it so
+//! the entries can be read without them moving, and scrolling down pauses
+//! it too. `/` opens the filter box like on the Ports page.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::LogMetrics;
+
+use crate::structs::{TableUIState, TextInput};
+
+/// State of the Logs page
+#[derive(Debug)]
+pub struct LogsUIState {
+    /// Selection state of the log table
+    pub table: TableUIState,
+
+    /// Text the entries are filtered by
+    pub filter: TextInput,
+
+    /// Whether keys go to the filter box
+    pub editing: bool,
+
+    /// Whether the page stopped following the log
+    pub paused: bool,
+
+    /// Entries shown on the page, kept while paused
+    pub metrics: Option<LogMetrics>,
+}
+
+impl Default for LogsUIState {
+    fn default() -> Self {
+        Self {
+            table: TableUIState::default(),
+            filter: TextInput::new("Filter (message or program)", false),
+            editing: false,
+            paused: false,
+            metrics: None,
+        }
+    }
+}
+
+impl LogsUIState {
+    /// Whether the log should be read again on this refresh
+    pub fn needs_refresh(&self) -> bool {
+        !self.paused || self.metrics.is_none()
+    }
+
+    /// Handles a key press on the Logs page.
+    ///
+    /// While the filter box is open every key edits it, `Enter` closes it
+    /// keeping the filter and `Esc` closes it clearing the filter.
+    /// Otherwise:
+    ///
+    /// - `Up`/`Down` - Scroll, leaving the newest entry pauses the page
+    /// - `p` - Pause or follow the log again from the newest entry
+    /// - `/` - Open the filter box
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.editing {
+            match key.code {
+                KeyCode::Enter => self.editing = false,
+                KeyCode::Esc => {
+                    self.editing = false;
+                    self.filter.value.clear();
+                }
+                _ => {
+                    if self.filter.handle_key(key) {
+                        self.table.list.select(Some(0));
+                    }
+                }
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Down => {
+                self.table.select_next();
+                self.paused |= self.table.list.selected().is_some_and(|index| index > 0);
+            }
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('p') => {
+                self.paused = !self.paused;
+                if !self.paused {
+                    self.table.list.select(Some(0));
+                }
+            }
+            KeyCode::Char('/') => self.editing = true,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,187 @@
+//! Layout of the Metrics page
+//!
+//! The page is a column of rows, each showing one widget at a fixed number
+//! of lines, a share of the page or the space left. The rows come from a
+//! built-in preset picked with `[metrics] layout` in the config file, or
+//! from a `[[metrics.rows]]` list replacing it:
+//!
+//! ```toml
+//! [metrics]
+//! layout = "server"
+//!
+//! [[metrics.rows]]
+//! widget = "cpu"
+//! height = 3
+//!
+//! [[metrics.rows]]
+//! widget = "panels"
+//! panels = ["cpu", "memory", "anomalies"]
+//! ```
+//!
+//! Rows whose data the machine doesn't report, like pressure without PSI,
+//! are left out and their space goes to the others.
+
+use ratatui::layout::Constraint;
+use serde::Deserialize;
+
+/// Built-in layouts of the Metrics page
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutPreset {
+    /// Gauges at their smallest and the memory, swap and CPU panels, for
+    /// small terminals
+    Compact,
+    /// Every widget, with large gauges
+    #[default]
+    Detailed,
+    /// CPU first, with pressure, the scheduler rates and anomalies for
+    /// spotting contention on busy machines
+    Server,
+}
+
+/// A widget taking a row of the Metrics page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsWidget {
+    /// Memory breakdown bar
+    Memory,
+    /// Swap usage gauge
+    Swap,
+    /// CPU usage gauge
+    Cpu,
+    /// CPU, memory and IO pressure gauges, with PSI
+    Pressure,
+    /// Context switch, interrupt and fork rate sparklines, on Linux
+    Scheduler,
+    /// Statistics panels side by side
+    Panels,
+}
+
+/// A statistics panel of a `panels` row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsPanel {
+    /// Exact memory figures
+    Memory,
+    /// Exact swap figures
+    Swap,
+    /// CPU count, governor and frequencies
+    Cpu,
+    /// Readings that strayed from their baseline
+    Anomalies,
+    /// Output of the hooks script, when one is active
+    Script,
+}
+
+impl MetricsPanel {
+    /// Panels of a `panels` row that doesn't list any
+    pub const ALL: [MetricsPanel; 5] = [
+        MetricsPanel::Memory,
+        MetricsPanel::Swap,
+        MetricsPanel::Cpu,
+        MetricsPanel::Anomalies,
+        MetricsPanel::Script,
+    ];
+}
+
+/// A row of the Metrics page
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsRow {
+    /// Widget shown in the row
+    pub widget: MetricsWidget,
+
+    /// Height in lines
+    #[serde(default)]
+    pub height: Option<u16>,
+
+    /// Height as a percentage of the page, when `height` isn't set. Rows
+    /// with neither share the space left.
+    #[serde(default)]
+    pub percent: Option<u16>,
+
+    /// Panels of a `panels` row, every panel when empty
+    #[serde(default)]
+    pub panels: Vec<MetricsPanel>,
+}
+
+impl MetricsRow {
+    /// A row of `widget` taking `constraint`
+    fn new(widget: MetricsWidget, constraint: Constraint) -> Self {
+        let (height, percent) = match constraint {
+            Constraint::Length(lines) => (Some(lines), None),
+            Constraint::Percentage(percent) => (None, Some(percent)),
+            _ => (None, None),
+        };
+        Self {
+            widget,
+            height,
+            percent,
+            panels: Vec::new(),
+        }
+    }
+
+    /// Shows `panels` in a `panels` row
+    fn with_panels(mut self, panels: &[MetricsPanel]) -> Self {
+        self.panels = panels.to_vec();
+        self
+    }
+
+    /// Height of the row in the page layout
+    pub fn constraint(&self) -> Constraint {
+        match (self.height, self.percent) {
+            (Some(lines), _) => Constraint::Length(lines),
+            (None, Some(percent)) => Constraint::Percentage(percent),
+            (None, None) => Constraint::Fill(1),
+        }
+    }
+
+    /// Panels shown in a `panels` row
+    pub fn panels(&self) -> &[MetricsPanel] {
+        if self.panels.is_empty() {
+            &MetricsPanel::ALL
+        } else {
+            &self.panels
+        }
+    }
+}
+
+impl LayoutPreset {
+    /// Rows of the preset, top to bottom
+    pub fn rows(self) -> Vec<MetricsRow> {
+        use MetricsWidget as W;
+        match self {
+            LayoutPreset::Compact => vec![
+                MetricsRow::new(W::Memory, Constraint::Length(3)),
+                MetricsRow::new(W::Swap, Constraint::Length(3)),
+                MetricsRow::new(W::Cpu, Constraint::Length(3)),
+                MetricsRow::new(W::Panels, Constraint::Fill(1)).with_panels(&[
+                    MetricsPanel::Memory,
+                    MetricsPanel::Swap,
+                    MetricsPanel::Cpu,
+                ]),
+            ],
+            LayoutPreset::Detailed => vec![
+                MetricsRow::new(W::Memory, Constraint::Percentage(20)),
+                MetricsRow::new(W::Swap, Constraint::Percentage(20)),
+                MetricsRow::new(W::Cpu, Constraint::Percentage(20)),
+                MetricsRow::new(W::Pressure, Constraint::Length(3)),
+                MetricsRow::new(W::Scheduler, Constraint::Length(6)),
+                MetricsRow::new(W::Panels, Constraint::Fill(1)),
+            ],
+            LayoutPreset::Server => vec![
+                MetricsRow::new(W::Cpu, Constraint::Length(3)),
+                MetricsRow::new(W::Pressure, Constraint::Length(3)),
+                MetricsRow::new(W::Scheduler, Constraint::Percentage(30)),
+                MetricsRow::new(W::Memory, Constraint::Length(3)),
+                MetricsRow::new(W::Swap, Constraint::Length(3)),
+                MetricsRow::new(W::Panels, Constraint::Fill(1)).with_panels(&[
+                    MetricsPanel::Cpu,
+                    MetricsPanel::Memory,
+                    MetricsPanel::Anomalies,
+                    MetricsPanel::Script,
+                ]),
+            ],
+        }
+    }
+}
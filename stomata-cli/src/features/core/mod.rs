@@ -26,6 +26,73 @@
 //!
 //! # Modules
 //!
+//! - [`affinity_editor`] - Pinning a process to CPUs
+//! - [`alerts_export`] - `stomata alerts export` of the alert log and crash events as JSON
+//! - [`audio_controls`] - Volume and mute of audio devices
+//! - [`bluetooth_controls`] - Connect and disconnect of Bluetooth devices
+//! - [`command_palette`] - `Ctrl+P` fuzzy search over the actions of the application
+//! - [`command_line`] - `:` command line of the vim keymap profile
+//! - [`collect`] - `stomata collect` of the collector registry values
 //! - [`core_feature`] - Main entry point and render loop implementation
+//! - [`crash_events`] - OOM kills, segfaults and service crashes read from the journal
+//! - [`disks`] - Disk and mount selection with unmount of removable media
+//! - [`exe_checksum`] - SHA-256 verification of process executables, with the
+//!   `web3` feature providing the hashing
+//! - [`export`] - `stomata export` of the collector registry to StatsD or MQTT
+//! - [`follow`] - Following a process across restarts on the SingleProcess page
+//! - [`hardware`] - On-demand USB and PCI device enumeration for the System page
+//! - [`history`] - Range selection and stored series of the History page
+//! - [`inventory`] - `stomata inventory` hardware export as JSON or YAML
+//! - [`keymap`] - Keybinding profiles, default and vim
+//! - [`listening_ports`] - Filter of the listening sockets on the Ports page
+//! - [`logs`] - Filter, pause and scrolling of the kernel log tail
+//! - [`metrics_layout`] - Presets and custom rows of the Metrics page
+//! - [`open_files`] - Open file descriptors of a process
+//! - [`power_controls`] - Screen brightness and power profile switching
+//! - [`process_columns`] - Order and visibility of the process list columns
+//! - [`process_export`] - CSV export of the process table
+//! - [`process_env`] - Environment variables of a process, redacted by default
+//! - [`remote`] - `stomata agent` and `stomata connect` for monitoring another machine
+//! - [`script_hooks`] - Rhai scripting hooks run on every metrics refresh
+//! - [`serve`] - `stomata serve` JSON API over HTTP
+//! - [`service_actions`] - Start, stop and restart of systemd services
+//! - [`signal_menu`] - Sending signals to a process
+//! - [`snapshot`] - `stomata snapshot` periodic snapshots with rotation
+//! - [`tasks`] - Sorting and details of the threads of a process
+//! - [`vm_actions`] - Start and shutdown of libvirt VMs
 
+pub mod affinity_editor;
+pub mod alerts_export;
+pub mod audio_controls;
+pub mod bluetooth_controls;
+pub mod collect;
+pub mod command_line;
+pub mod command_palette;
 pub mod core_feature;
+pub mod crash_events;
+pub mod disks;
+#[cfg(feature = "web3")]
+pub mod exe_checksum;
+pub mod export;
+pub mod follow;
+pub mod hardware;
+pub mod history;
+pub mod inventory;
+pub mod keymap;
+pub mod listening_ports;
+pub mod logs;
+pub mod metrics_layout;
+pub mod open_files;
+pub mod power_controls;
+pub mod process_columns;
+pub mod process_env;
+pub mod process_export;
+pub mod remote;
+#[cfg(feature = "scripting")]
+pub mod script_hooks;
+pub mod serve;
+pub mod service_actions;
+pub mod signal_menu;
+pub mod snapshot;
+pub mod tasks;
+pub mod vm_actions;
@@ -0,0 +1,34 @@
+//! Open files panel of the SingleProcess page
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use crate::structs::TableUIState;
+
+/// State of the open files panel
+#[derive(Debug, Default)]
+pub struct OpenFilesUIState {
+    /// Whether the panel is shown
+    pub open: bool,
+
+    /// Selection state of the open files table
+    pub table: TableUIState,
+}
+
+impl OpenFilesUIState {
+    /// Handles a key press while the panel is open.
+    ///
+    /// - `Up`/`Down` - Scroll the list
+    /// - `o`/`Esc` - Close the panel
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('o') | KeyCode::Esc => self.open = false,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,77 @@
+//! Brightness and power profile controls of the System page
+//!
+//! Reading the power profile runs `powerprofilesctl`, so the values are
+//! kept and only read again every `POWER_REFRESH_SECS` or after a change.
+
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::power::PowerMetrics;
+
+use crate::constants::{BRIGHTNESS_STEP_PERCENT, POWER_REFRESH_SECS};
+
+/// State of the power panel
+#[derive(Debug, Default)]
+pub struct PowerUIState {
+    /// Last rendered backlight and profile, the values the keys adjust
+    pub metrics: PowerMetrics,
+
+    /// Result of the last failed action
+    pub status: Option<String>,
+
+    /// When `metrics` were read, `None` until the first read and after a
+    /// change
+    pub fetched_at: Option<Instant>,
+}
+
+impl PowerUIState {
+    /// Whether the backlight and profile should be read again on this
+    /// refresh
+    pub fn needs_refresh(&self) -> bool {
+        self.fetched_at
+            .is_none_or(|at| at.elapsed() >= Duration::from_secs(POWER_REFRESH_SECS))
+    }
+
+    /// Keeps `metrics` as the values to show until the next refresh
+    pub fn update(&mut self, metrics: PowerMetrics) {
+        self.metrics = metrics;
+        self.fetched_at = Some(Instant::now());
+    }
+
+    /// Handles a key press on the System page.
+    ///
+    /// - `+`/`-` - Raise or lower the screen brightness
+    /// - `p` - Switch to the next power profile
+    ///
+    /// A successful change is read back on the next refresh.
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let result = match key.code {
+            KeyCode::Char(sign @ ('+' | '=' | '-')) => {
+                let Some(backlight) = &self.metrics.backlight else {
+                    return false;
+                };
+                let percent = if sign == '-' {
+                    backlight.percent().saturating_sub(BRIGHTNESS_STEP_PERCENT)
+                } else {
+                    backlight.percent() + BRIGHTNESS_STEP_PERCENT
+                };
+                backlight.set_percent(percent)
+            }
+            KeyCode::Char('p') => {
+                let Some(profile) = self.metrics.next_profile() else {
+                    return false;
+                };
+                profile.apply()
+            }
+            _ => return false,
+        };
+        if result.is_ok() {
+            self.fetched_at = None;
+        }
+        self.status = result.err().map(|err| format!("{err:#}"));
+        true
+    }
+}
@@ -0,0 +1,241 @@
+//! Columns of the Processes table
+//!
+//! Every column of the process list is a [`ProcessColumn`] with its header
+//! and width, and [`ProcessColumns`] keeps their order and which of them
+//! are shown. The initial layout comes from `[processes] columns` in the
+//! config file. `v` opens a [`ColumnEditor`] to change it at runtime; the
+//! editor is modal and takes every key until it is closed.
+
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Constraint,
+};
+use serde::Deserialize;
+
+use crate::{
+    constants::{CONTAINER_COLUMN_WIDTH, SWAP_COLUMN_WIDTH},
+    structs::TableUIState,
+};
+
+/// A column of the process list, named in lowercase in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessColumn {
+    /// Process identifier
+    Pid,
+    /// Owner of the process
+    User,
+    /// Process name
+    Name,
+    /// CPU usage percentage
+    Cpu,
+    /// Memory usage
+    Memory,
+    /// Memory swapped out
+    Swap,
+    /// Bytes read from disk per second
+    Read,
+    /// Bytes written to disk per second
+    Write,
+    /// Number of threads
+    Threads,
+    /// Process state
+    Status,
+    /// Container or systemd unit of the process cgroup
+    Container,
+    /// Command line
+    Command,
+}
+
+impl ProcessColumn {
+    /// Every column, in the order the hidden ones are listed in the editor
+    pub const ALL: [ProcessColumn; 12] = [
+        ProcessColumn::Pid,
+        ProcessColumn::User,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::Memory,
+        ProcessColumn::Swap,
+        ProcessColumn::Read,
+        ProcessColumn::Write,
+        ProcessColumn::Threads,
+        ProcessColumn::Status,
+        ProcessColumn::Container,
+        ProcessColumn::Command,
+    ];
+
+    /// Columns shown when the config file doesn't list any
+    pub const DEFAULT: [ProcessColumn; 10] = [
+        ProcessColumn::Pid,
+        ProcessColumn::User,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::Memory,
+        ProcessColumn::Read,
+        ProcessColumn::Write,
+        ProcessColumn::Threads,
+        ProcessColumn::Status,
+        ProcessColumn::Command,
+    ];
+
+    /// Header of the column in the table
+    pub fn header(self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::User => "User",
+            ProcessColumn::Name => "Name",
+            ProcessColumn::Cpu => "CPU",
+            ProcessColumn::Memory => "Memory",
+            ProcessColumn::Swap => "Swap",
+            ProcessColumn::Read => "Read",
+            ProcessColumn::Write => "Write",
+            ProcessColumn::Threads => "Threads",
+            ProcessColumn::Status => "Status",
+            ProcessColumn::Container => "Container",
+            ProcessColumn::Command => "Command",
+        }
+    }
+
+    /// Width of the column; the command takes the space left
+    pub fn width(self) -> Constraint {
+        match self {
+            ProcessColumn::Pid | ProcessColumn::Threads => Constraint::Length(8),
+            ProcessColumn::User
+            | ProcessColumn::Memory
+            | ProcessColumn::Read
+            | ProcessColumn::Write => Constraint::Length(12),
+            ProcessColumn::Name => Constraint::Length(20),
+            ProcessColumn::Cpu | ProcessColumn::Status => Constraint::Length(10),
+            ProcessColumn::Swap => Constraint::Length(SWAP_COLUMN_WIDTH),
+            ProcessColumn::Container => Constraint::Length(CONTAINER_COLUMN_WIDTH),
+            ProcessColumn::Command => Constraint::Min(20),
+        }
+    }
+}
+
+/// Order of the process list columns and whether each is shown
+#[derive(Debug, Clone)]
+pub struct ProcessColumns {
+    /// Every column once, with `true` when shown
+    entries: Vec<(ProcessColumn, bool)>,
+}
+
+impl Default for ProcessColumns {
+    fn default() -> Self {
+        Self::new(&ProcessColumn::DEFAULT)
+    }
+}
+
+impl ProcessColumns {
+    /// Shows `visible` in that order, followed by the other columns hidden.
+    /// Falls back to the default columns when `visible` is empty.
+    pub fn new(visible: &[ProcessColumn]) -> Self {
+        let visible = if visible.is_empty() {
+            &ProcessColumn::DEFAULT[..]
+        } else {
+            visible
+        };
+        let mut entries: Vec<(ProcessColumn, bool)> = Vec::new();
+        for column in visible {
+            if !entries.iter().any(|(shown, _)| shown == column) {
+                entries.push((*column, true));
+            }
+        }
+        for column in ProcessColumn::ALL {
+            if !entries.iter().any(|(shown, _)| *shown == column) {
+                entries.push((column, false));
+            }
+        }
+        Self { entries }
+    }
+
+    /// Every column in order, with `true` when shown
+    pub fn entries(&self) -> &[(ProcessColumn, bool)] {
+        &self.entries
+    }
+
+    /// The shown columns, in order
+    pub fn visible(&self) -> Vec<ProcessColumn> {
+        self.entries
+            .iter()
+            .filter(|(_, shown)| *shown)
+            .map(|(column, _)| *column)
+            .collect()
+    }
+
+    /// Shows or hides `column`. The last shown column stays shown so the
+    /// table never ends up empty.
+    pub fn toggle(&mut self, column: ProcessColumn) {
+        let shown_count = self.entries.iter().filter(|(_, shown)| *shown).count();
+        if let Some((_, shown)) = self.entries.iter_mut().find(|(c, _)| *c == column)
+            && (!*shown || shown_count > 1)
+        {
+            *shown = !*shown;
+        }
+    }
+
+    /// Swaps the column at `index` with the one before it. Returns the new
+    /// index of the column.
+    pub fn move_up(&mut self, index: usize) -> usize {
+        if index == 0 || index >= self.entries.len() {
+            return index;
+        }
+        self.entries.swap(index, index - 1);
+        index - 1
+    }
+
+    /// Swaps the column at `index` with the one after it. Returns the new
+    /// index of the column.
+    pub fn move_down(&mut self, index: usize) -> usize {
+        if index + 1 >= self.entries.len() {
+            return index;
+        }
+        self.entries.swap(index, index + 1);
+        index + 1
+    }
+}
+
+/// An open column editor
+#[derive(Debug)]
+pub struct ColumnEditor {
+    /// Selection state of the columns list
+    pub table: TableUIState,
+}
+
+impl Default for ColumnEditor {
+    fn default() -> Self {
+        Self {
+            table: TableUIState {
+                count: ProcessColumn::ALL.len(),
+                ..TableUIState::default()
+            },
+        }
+    }
+}
+
+impl ColumnEditor {
+    /// Handles a key press while the editor is open. Returns `false` when
+    /// the editor was closed.
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `Space`/`Enter` - Show or hide the selected column
+    /// - `K`/`J` - Move the selected column up or down
+    /// - `Esc`/`v` - Close the editor
+    pub fn handle_key(&mut self, key: KeyEvent, columns: &mut ProcessColumns) -> bool {
+        let selected = self.table.list.selected().unwrap_or_default();
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if let Some((column, _)) = columns.entries().get(selected) {
+                    columns.toggle(*column);
+                }
+            }
+            KeyCode::Char('K') => self.table.list.select(Some(columns.move_up(selected))),
+            KeyCode::Char('J') => self.table.list.select(Some(columns.move_down(selected))),
+            KeyCode::Esc | KeyCode::Char('v') => return false,
+            _ => {}
+        }
+        true
+    }
+}
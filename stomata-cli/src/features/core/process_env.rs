@@ -0,0 +1,73 @@
+//! Environment variables panel of the SingleProcess page
+//!
+//! Environment variables often carry credentials, so values are redacted
+//! until revealed one by one.
+
+use std::collections::HashSet;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use crate::structs::TableUIState;
+
+/// State of the environment variables panel
+#[derive(Debug, Default)]
+pub struct ProcessEnvUIState {
+    /// Whether the panel is shown
+    pub open: bool,
+
+    /// Selection state of the variables table
+    pub table: TableUIState,
+
+    /// Variable names in table order, to resolve the selection
+    pub names: Vec<String>,
+
+    /// Names of the variables whose value is shown
+    pub revealed: HashSet<String>,
+
+    /// Process the panel was opened for; revealed values don't carry over
+    /// to another process
+    pub pid: Option<u32>,
+}
+
+impl ProcessEnvUIState {
+    /// Shows the panel for `pid`, or hides it if it is shown
+    pub fn toggle(&mut self, pid: u32) {
+        if self.pid != Some(pid) {
+            *self = Self {
+                pid: Some(pid),
+                ..Self::default()
+            };
+        }
+        self.open = !self.open;
+    }
+
+    /// Handles a key press while the panel is open.
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `Enter` - Reveal or redact the selected value
+    /// - `e`/`Esc` - Close the panel
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Enter => {
+                let selected = self
+                    .table
+                    .list
+                    .selected()
+                    .and_then(|index| self.names.get(index));
+                if let Some(name) = selected
+                    && !self.revealed.remove(name)
+                {
+                    self.revealed.insert(name.clone());
+                }
+            }
+            KeyCode::Char('e') | KeyCode::Esc => self.open = false,
+            _ => return false,
+        }
+        true
+    }
+}
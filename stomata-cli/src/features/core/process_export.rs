@@ -0,0 +1,175 @@
+//! CSV export of the process table
+//!
+//! `x` on the Processes page writes the listed processes to
+//! `stomata-processes-<timestamp>.csv` in the working directory. The same
+//! file can be written without the TUI:
+//!
+//! ```bash
+//! stomata --export-csv processes.csv
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+};
+
+use anyhow::Context;
+use chrono::Local;
+use stomata_core::{
+    Metrics, MetricsToFetch, ProcessData, StomataSystemMetrics,
+    sysinfo::MINIMUM_CPU_UPDATE_INTERVAL,
+};
+
+use crate::constants::PROCESS_EXPORT_FILE_PREFIX;
+
+/// Column headers, the process table columns with raw values
+const HEADERS: [&str; 12] = [
+    "pid",
+    "user",
+    "name",
+    "cpu_percent",
+    "memory_bytes",
+    "swap_bytes",
+    "disk_read_bytes_per_sec",
+    "disk_write_bytes_per_sec",
+    "threads",
+    "status",
+    "cgroup",
+    "command",
+];
+
+/// Timestamped export file in the working directory
+pub fn default_path() -> PathBuf {
+    PathBuf::from(format!(
+        "{PROCESS_EXPORT_FILE_PREFIX}-{}.csv",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Writes `processes` to `path` as CSV, in the given order
+///
+/// # Errors
+///
+/// Returns an error if the file can't be created or written.
+pub fn export(path: &Path, processes: &[ProcessData]) -> anyhow::Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    write_csv(&mut writer, processes)
+        .and_then(|_| writer.flush())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Exports the processes of a fresh refresh, sorted by PID, for
+/// `--export-csv`
+///
+/// # Errors
+///
+/// Returns an error if the file can't be written.
+pub fn run_headless(path: &Path) -> anyhow::Result<()> {
+    let mut metrics = StomataSystemMetrics::new();
+    // CPU usage is measured between two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    let Metrics::Processes(mut processes) = metrics.fetch(MetricsToFetch::Process) else {
+        return Ok(());
+    };
+    processes.sort_by_key(|process| process.pid);
+    export(path, &processes)?;
+    println!("wrote {} processes to {}", processes.len(), path.display());
+    Ok(())
+}
+
+fn write_csv(writer: &mut impl Write, processes: &[ProcessData]) -> io::Result<()> {
+    writeln!(writer, "{}", HEADERS.join(","))?;
+    for process in processes {
+        let fields = [
+            process.pid.to_string(),
+            process.user.clone().unwrap_or_default(),
+            process.name.clone(),
+            format!("{:.2}", process.cpu_usage),
+            process.memory.to_string(),
+            process
+                .swap
+                .map(|swap| swap.to_string())
+                .unwrap_or_default(),
+            process
+                .disk_read_rate
+                .map(|rate| format!("{rate:.0}"))
+                .unwrap_or_default(),
+            process
+                .disk_write_rate
+                .map(|rate| format!("{rate:.0}"))
+                .unwrap_or_default(),
+            process
+                .threads
+                .map(|threads| threads.to_string())
+                .unwrap_or_default(),
+            process.status.clone(),
+            process.cgroup.clone().unwrap_or_default(),
+            process.command.join(" "),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| escape(field)).collect();
+        writeln!(writer, "{}", line.join(","))?;
+    }
+    Ok(())
+}
+
+/// Quotes a field containing a separator, quote or line break, doubling
+/// its quotes as RFC 4180 requires
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stomata_core::collectors::backend::{FakeFrame, FakeSystem, ProcessSample};
+
+    use super::*;
+
+    #[test]
+    fn test_writes_the_processes_of_the_backend() {
+        let system = FakeSystem::fixed(FakeFrame {
+            processes: vec![
+                ProcessSample {
+                    pid: 4242,
+                    name: "postgres".to_string(),
+                    cpu_usage: 12.5,
+                    memory: 1024 * 1024,
+                    ..Default::default()
+                },
+                ProcessSample {
+                    pid: 17,
+                    name: "worker, \"main\"".to_string(),
+                    zombie: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+        let mut processes = ProcessData::fetch(&system);
+        processes[0].command = vec![
+            "postgres".to_string(),
+            "-D".to_string(),
+            "/data".to_string(),
+        ];
+
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &processes).unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], HEADERS.join(","));
+        assert_eq!(
+            lines[1],
+            "4242,,postgres,12.50,1048576,,,,,Runnable,,postgres -D /data"
+        );
+        assert_eq!(lines[2], "17,,\"worker, \"\"main\"\"\",0.00,0,,,,,Zombie,,");
+        assert_eq!(lines.len(), 3);
+    }
+}
@@ -0,0 +1,149 @@
+//! Remote monitoring with an agent and a TUI client
+//!
+//! `stomata agent` runs the collectors headlessly and serves them over TCP;
+//! `stomata connect` opens the usual TUI with the pages fetched from an
+//! agent. The System, Metrics, Processes and Network pages and the summary
+//! strip are served; pages reading local state stay empty and actions that
+//! would change the local machine are disabled. Signals are sent through
+//! the agent, which only carries them out for a control token.
+//!
+//! Given several agents, a sidebar lists the hosts: `[` and `]` switch
+//! between them and `H` opens a grid comparing their CPU and memory. Hosts
+//! that are down are retried on every refresh.
+//!
+//! Clients present a token listed under `[agent] tokens` in the agent's
+//! config file; an agent without tokens only listens on localhost. The
+//! protocol isn't encrypted, so tunnel it through SSH or bind it to a
+//! private network only. `--compress` asks the agents for zstd-compressed
+//! answers, which pays off over slow links.
+//!
+//! ```bash
+//! stomata agent --listen 0.0.0.0:7070
+//! stomata connect --token change-me server:7070
+//! stomata connect web1 web2 db1
+//! stomata connect --compress remote-site:7070
+//! stomata --interval 2000 connect server
+//! ```
+
+use std::iter::once;
+
+use anyhow::Context;
+use clap::Parser;
+use stomata_core::{
+    access::TokenStore,
+    remote::{Agent, RemoteMetrics},
+};
+
+use crate::{
+    config::config, constants::AGENT_DEFAULT_PORT, features::core::core_feature::run_app,
+    renders::core_displays::display_app::App, structs::Cli,
+};
+
+/// Arguments of `stomata agent`
+#[derive(Parser, Clone)]
+#[command(name = "agent")]
+#[command(about = "Serve this machine's metrics to `stomata connect` clients")]
+pub struct AgentCli {
+    /// Address to listen on
+    #[arg(long, default_value_t = format!("127.0.0.1:{AGENT_DEFAULT_PORT}"))]
+    pub listen: String,
+}
+
+/// Arguments of `stomata connect`
+#[derive(Parser, Clone)]
+#[command(name = "connect")]
+#[command(about = "Open the TUI on the metrics of a `stomata agent`")]
+pub struct ConnectCli {
+    /// Agent addresses, the port defaults to 7070
+    #[arg(required = true)]
+    pub addresses: Vec<String>,
+    /// Token presented to the agents, `[agent] token` of the config file
+    /// by default
+    #[arg(long)]
+    pub token: Option<String>,
+    /// Ask the agents for zstd-compressed answers, for slow links
+    #[arg(long)]
+    pub compress: bool,
+}
+
+/// Parses the agent arguments and serves clients until the process is
+/// stopped
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid, the address can't be
+/// bound, or no token is configured for an address other than localhost.
+pub fn run_agent(args: &[String]) -> anyhow::Result<()> {
+    let cli = AgentCli::try_parse_from(once("agent".to_string()).chain(args.iter().cloned()))?;
+    let tokens = TokenStore::new(config().agent.tokens.clone());
+    let agent = Agent::bind(cli.listen.as_str(), tokens)
+        .with_context(|| format!("failed to listen on {}", cli.listen))?;
+    eprintln!("serving metrics on {}", agent.local_addr()?);
+    agent.serve()?;
+    Ok(())
+}
+
+/// Connects to the agents named in the arguments and runs the TUI on their
+/// metrics until the user quits. With several agents, those that can't be
+/// reached are reported and retried while the TUI runs.
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid, the only agent can't be
+/// reached, or the TUI fails.
+pub fn run_connect(cli: &Cli) -> anyhow::Result<()> {
+    let args =
+        ConnectCli::try_parse_from(once("connect".to_string()).chain(cli.args.iter().cloned()))?;
+    let addresses: Vec<String> = args
+        .addresses
+        .iter()
+        .map(|address| with_default_port(address))
+        .collect();
+    let token = args.token.or_else(|| config().agent.token.clone());
+    let client = |address: &str| {
+        let remote = RemoteMetrics::new(address, token.clone());
+        if args.compress {
+            remote.with_compression()
+        } else {
+            remote
+        }
+    };
+    let mut unreachable = Vec::new();
+    let remotes = match addresses.as_slice() {
+        [address] => vec![client(address).open()?],
+        _ => addresses
+            .iter()
+            .map(|address| {
+                client(address).open().unwrap_or_else(|err| {
+                    unreachable.push(err);
+                    client(address)
+                })
+            })
+            .collect(),
+    };
+
+    let mut app = App::new(cli.store).with_remotes(remotes);
+    for err in unreachable {
+        app.errors.report("connect", &err.into());
+    }
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut app, cli, &mut terminal);
+    ratatui::restore();
+    result.map(|_| ())
+}
+
+/// `host` with the agent port appended when it names none. Bracketed IPv6
+/// addresses keep their brackets, `[::1]` becomes `[::1]:7070`.
+fn with_default_port(address: &str) -> String {
+    let has_port = match address.rsplit_once(':') {
+        Some((host, port)) => {
+            port.parse::<u16>().is_ok() && (!host.contains(':') || host.ends_with(']'))
+        }
+        None => false,
+    };
+    if has_port {
+        address.to_string()
+    } else {
+        format!("{address}:{AGENT_DEFAULT_PORT}")
+    }
+}
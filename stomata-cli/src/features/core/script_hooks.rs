@@ -0,0 +1,266 @@
+//! Rhai scripting hooks
+//!
+//! Loads a user script from `<config dir>/stomata/hooks.rhai` and calls its
+//! `on_tick(metrics)` function after every system metrics refresh. The
+//! script can derive custom values, raise alerts and rename gauge labels
+//! without recompiling stomata.
+//!
+//! # Script Contract
+//!
+//! `on_tick` receives a map with `cpu_usage`, `cpu_count`, `memory_used`,
+//! `memory_total`, `memory_available`, `swap_used` and `swap_total`, and
+//! may return a map with any of these keys:
+//!
+//! - `values` - map of label to value, shown in the Script panel
+//! - `alerts` - array of alert messages, shown in red
+//! - `labels` - map of default gauge title to replacement title
+//!
+//! ```rhai
+//! fn on_tick(m) {
+//!     let out = #{ values: #{}, alerts: [], labels: #{} };
+//!     out.values["Memory %"] = m.memory_used * 100.0 / m.memory_total;
+//!     if m.cpu_usage > 90.0 { out.alerts.push("CPU above 90%"); }
+//!     out
+//! }
+//! ```
+
+use std::{fmt, path::PathBuf};
+
+use rhai::{AST, Array, Dynamic, Engine, Map, Scope};
+use stomata_core::collectors::system::metrics::SystemMetrics;
+
+use crate::{
+    constants::{
+        SCRIPT_FILE_NAME, SCRIPT_MAX_COLLECTION_LEN, SCRIPT_MAX_OPERATIONS, SCRIPT_MAX_STRING_BYTES,
+    },
+    structs::ScriptOutput,
+};
+
+/// Compiled user script and the engine that runs it
+pub struct ScriptHooks {
+    engine: Engine,
+    ast: AST,
+    path: PathBuf,
+}
+
+impl fmt::Debug for ScriptHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptHooks")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl ScriptHooks {
+    /// Location of the per-user hooks script
+    pub fn script_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("stomata").join(SCRIPT_FILE_NAME))
+    }
+
+    /// Compiles the per-user hooks script.
+    ///
+    /// # Returns
+    ///
+    /// * `None` - No script exists, scripting is disabled
+    /// * `Some(Ok(hooks))` - Script compiled and ready to run
+    /// * `Some(Err(message))` - Script exists but failed to compile
+    pub fn load() -> Option<Result<Self, String>> {
+        let path = Self::script_path()?;
+        if !path.exists() {
+            return None;
+        }
+
+        let engine = sandboxed_engine();
+        Some(
+            engine
+                .compile_file(path.clone())
+                .map(|ast| Self { engine, ast, path })
+                .map_err(|err| format!("Failed to compile hooks script: {err}")),
+        )
+    }
+
+    /// Runs the script's `on_tick` function against the latest metrics.
+    ///
+    /// Errors raised by the script are returned in `ScriptOutput::error`
+    /// rather than interrupting rendering.
+    pub fn on_tick(&self, metrics: &SystemMetrics) -> ScriptOutput {
+        let mut input = Map::new();
+        input.insert(
+            "cpu_usage".into(),
+            Dynamic::from_float(metrics.cpu_usage as f64),
+        );
+        input.insert(
+            "cpu_count".into(),
+            Dynamic::from_int(metrics.cpu_count as i64),
+        );
+        input.insert(
+            "memory_used".into(),
+            Dynamic::from_float(metrics.memory_used as f64),
+        );
+        input.insert(
+            "memory_total".into(),
+            Dynamic::from_float(metrics.memory_total as f64),
+        );
+        input.insert(
+            "memory_available".into(),
+            Dynamic::from_float(metrics.memory_available as f64),
+        );
+        input.insert(
+            "swap_used".into(),
+            Dynamic::from_float(metrics.swap_used as f64),
+        );
+        input.insert(
+            "swap_total".into(),
+            Dynamic::from_float(metrics.swap_total as f64),
+        );
+
+        let mut scope = Scope::new();
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(&mut scope, &self.ast, "on_tick", (input,));
+
+        match result {
+            Ok(value) => parse_script_output(value),
+            Err(err) => ScriptOutput {
+                error: Some(format!("on_tick failed: {err}")),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Engine with limits guarding the render loop against runaway scripts:
+/// endless loops run out of operations and huge strings or arrays fail
+/// instead of exhausting memory
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+    engine.set_max_string_size(SCRIPT_MAX_STRING_BYTES);
+    engine.set_max_array_size(SCRIPT_MAX_COLLECTION_LEN);
+    engine.set_max_map_size(SCRIPT_MAX_COLLECTION_LEN);
+    engine
+}
+
+/// Converts the map returned by `on_tick` into a `ScriptOutput`.
+///
+/// Unknown keys are ignored; a non-map return value is treated as "nothing
+/// to show".
+fn parse_script_output(value: Dynamic) -> ScriptOutput {
+    let mut output = ScriptOutput::default();
+    let Some(map) = value.try_cast::<Map>() else {
+        return output;
+    };
+
+    if let Some(values) = map.get("values").and_then(|v| v.clone().try_cast::<Map>()) {
+        output.values = values
+            .into_iter()
+            .map(|(label, value)| (label.to_string(), format_value(&value)))
+            .collect();
+    }
+
+    if let Some(alerts) = map
+        .get("alerts")
+        .and_then(|v| v.clone().try_cast::<Array>())
+    {
+        output.alerts = alerts.iter().map(|alert| alert.to_string()).collect();
+    }
+
+    if let Some(labels) = map.get("labels").and_then(|v| v.clone().try_cast::<Map>()) {
+        output.labels = labels
+            .into_iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect();
+    }
+
+    output
+}
+
+/// Formats floats with two decimals and everything else as-is
+fn format_value(value: &Dynamic) -> String {
+    match value.as_float() {
+        Ok(float) => format!("{float:.2}"),
+        Err(_) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    fn hooks(source: &str) -> ScriptHooks {
+        let engine = sandboxed_engine();
+        let ast = engine.compile(source).unwrap();
+        ScriptHooks {
+            engine,
+            ast,
+            path: PathBuf::from(SCRIPT_FILE_NAME),
+        }
+    }
+
+    fn eval(source: &str) -> Dynamic {
+        sandboxed_engine().eval::<Dynamic>(source).unwrap()
+    }
+
+    #[test]
+    fn test_valid_output_is_parsed() {
+        let output = parse_script_output(eval(
+            r#"#{ values: #{ "Load": 1.5, "Hosts": 3 }, alerts: ["CPU high"], labels: #{ "CPU": "Processor" } }"#,
+        ));
+        let mut values = output.values;
+        values.sort();
+        assert_eq!(
+            values,
+            [
+                ("Hosts".to_string(), "3".to_string()),
+                ("Load".to_string(), "1.50".to_string())
+            ]
+        );
+        assert_eq!(output.alerts, ["CPU high"]);
+        assert_eq!(output.labels["CPU"], "Processor");
+        assert!(output.error.is_none());
+    }
+
+    #[test]
+    fn test_malformed_output_shows_nothing() {
+        for source in ["42", r#""text""#, "[1, 2]", "()"] {
+            let output = parse_script_output(eval(source));
+            assert!(output.values.is_empty(), "{source}");
+            assert!(output.alerts.is_empty(), "{source}");
+            assert!(output.labels.is_empty(), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_keys_of_the_wrong_type_are_skipped() {
+        let output = parse_script_output(eval(
+            r#"#{ values: [1, 2], alerts: "not a list", labels: 3, unknown: 1 }"#,
+        ));
+        assert!(output.values.is_empty());
+        assert!(output.alerts.is_empty());
+        assert!(output.labels.is_empty());
+    }
+
+    #[test]
+    fn test_endless_scripts_fail_instead_of_hanging() {
+        let started = Instant::now();
+        let output = hooks("fn on_tick(m) { loop {} }").on_tick(&SystemMetrics::default());
+        assert!(output.error.is_some());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_huge_strings_and_arrays_fail() {
+        let metrics = SystemMetrics::default();
+        // both stop on their size limit long before running out of
+        // operations
+        for source in [
+            r#"fn on_tick(m) { let s = "x"; loop { s += s; } }"#,
+            "fn on_tick(m) { let a = []; loop { a.push(1); } }",
+        ] {
+            let error = hooks(source).on_tick(&metrics).error.unwrap();
+            assert!(!error.contains("operations"), "{error}");
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! JSON API for dashboards and scripts
+//!
+//! Serves the system, process and network metrics and an in-memory usage
+//! history over HTTP until interrupted. Tokens listed under `[serve]` in
+//! the config file are required as `Authorization: Bearer <secret>` once
+//! any is set. The server listens on localhost unless told otherwise, and
+//! refuses any other address while no token is configured.
+//!
+//! ```bash
+//! stomata serve --port 8686
+//! stomata serve --bind 0.0.0.0 --port 8686
+//! curl localhost:8686/api/v1/system
+//! curl 'localhost:8686/api/v1/history?metric=cpu&range=10m'
+//! ```
+
+use std::iter::once;
+
+use anyhow::Context;
+use clap::Parser;
+use stomata_core::{access::TokenStore, remote::ApiServer};
+
+use crate::{config::config, constants::API_DEFAULT_PORT};
+
+/// Arguments of `stomata serve`
+#[derive(Parser, Clone)]
+#[command(name = "serve")]
+#[command(about = "Serve the metrics as JSON over HTTP")]
+pub struct ServeCli {
+    /// Port to listen on
+    #[arg(long, default_value_t = API_DEFAULT_PORT)]
+    pub port: u16,
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub bind: String,
+}
+
+/// Parses the serve arguments and answers requests until the process is
+/// stopped
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid or the address can't be
+/// bound.
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    let cli = ServeCli::try_parse_from(once("serve".to_string()).chain(args.iter().cloned()))?;
+    let tokens = TokenStore::new(config().serve.tokens.clone());
+    if tokens.is_empty() {
+        eprintln!("no tokens configured, every local request is allowed");
+    }
+    let server = ApiServer::bind((cli.bind.as_str(), cli.port), tokens)
+        .with_context(|| format!("failed to listen on {}:{}", cli.bind, cli.port))?;
+    eprintln!("serving the API on http://{}", server.local_addr()?);
+    server.serve()?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! Start, stop and restart actions of the Services page
+//!
+//! Every action asks for confirmation in a dialog first, since stopping
+//! the wrong unit can take the machine off the network or end the session.
+//! The job then runs on a worker thread, systemd can take up to
+//! `SYSTEMD_JOB_TIMEOUT_MS` to finish it, and its outcome shows up in the
+//! status bar.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::services::ServiceAction;
+
+use crate::structs::{StatusBar, TableUIState};
+
+/// State of the Services page
+#[derive(Debug, Default)]
+pub struct ServicesUIState {
+    /// Selection state of the services table
+    pub table: TableUIState,
+
+    /// Unit names in table order, to resolve the selection
+    pub units: Vec<String>,
+
+    /// Unit and action waiting for confirmation in the dialog
+    pub confirm: Option<(String, ServiceAction)>,
+}
+
+impl ServicesUIState {
+    /// Name of the selected unit
+    pub fn selected(&self) -> Option<&String> {
+        self.table
+            .list
+            .selected()
+            .and_then(|index| self.units.get(index))
+    }
+
+    /// Handles a key press on the Services page.
+    ///
+    /// While the confirmation dialog is open, `y` or `Enter` starts the
+    /// action in the background and any other key cancels, the outcome is
+    /// shown in `status_bar`. Otherwise:
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `s` - Start the selected unit
+    /// - `x` - Stop the selected unit
+    /// - `r` - Restart the selected unit
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent, status_bar: &mut StatusBar) -> bool {
+        if let Some((unit, action)) = self.confirm.take() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    let text = format!("{} {unit}…", action.command());
+                    status_bar.run_in_background(text, move || {
+                        action
+                            .apply(&unit)
+                            .map(|()| format!("{} {unit}: done", action.command()))
+                            .map_err(|err| format!("Failed to {} {unit}: {err}", action.command()))
+                    });
+                }
+                _ => status_bar.info(format!("{} {unit} cancelled", action.command())),
+            }
+            return true;
+        }
+
+        let action = match key.code {
+            KeyCode::Down => {
+                self.table.select_next();
+                return true;
+            }
+            KeyCode::Up => {
+                self.table.select_previous();
+                return true;
+            }
+            KeyCode::Char('s') => ServiceAction::Start,
+            KeyCode::Char('x') => ServiceAction::Stop,
+            KeyCode::Char('r') => ServiceAction::Restart,
+            _ => return false,
+        };
+        if let Some(unit) = self.selected().cloned() {
+            self.confirm = Some((unit, action));
+        }
+        true
+    }
+}
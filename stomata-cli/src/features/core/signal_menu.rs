@@ -0,0 +1,78 @@
+//! Signals menu for sending a signal to a process
+//!
+//! Opened with `s` on the Processes and SingleProcess pages. The menu is
+//! modal: it takes every key until a signal is sent or it is closed.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::process::actions::Signal;
+
+use crate::structs::TableUIState;
+
+/// An open signals menu
+#[derive(Debug)]
+pub struct SignalMenu {
+    /// Process the signal is sent to
+    pub pid: u32,
+
+    /// Selection state of the signals list
+    pub table: TableUIState,
+}
+
+/// What the menu did with a key press
+pub enum SignalMenuOutcome {
+    /// The menu stays open
+    Open,
+    /// The menu was closed without sending anything
+    Cancelled,
+    /// A signal was chosen to be sent to `pid`
+    Send(Signal),
+}
+
+impl SignalMenu {
+    pub fn new(pid: u32) -> Self {
+        let mut table = TableUIState {
+            count: Signal::ALL.len(),
+            ..TableUIState::default()
+        };
+        // preselect SIGTERM, the polite way to stop a process
+        table
+            .list
+            .select(Signal::ALL.iter().position(|s| *s == Signal::Term));
+        Self { pid, table }
+    }
+
+    /// Preselects `signal` instead of SIGTERM, leaving the user to confirm
+    /// it with `Enter`
+    pub fn with_selected(mut self, signal: Signal) -> Self {
+        self.table
+            .list
+            .select(Signal::ALL.iter().position(|s| *s == signal));
+        self
+    }
+
+    /// Handles a key press while the menu is open.
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `Enter` - Send the selected signal
+    /// - `Esc`/`s` - Close the menu
+    pub fn handle_key(&mut self, key: KeyEvent) -> SignalMenuOutcome {
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Enter => {
+                let Some(signal) = self
+                    .table
+                    .list
+                    .selected()
+                    .and_then(|index| Signal::ALL.get(index))
+                else {
+                    return SignalMenuOutcome::Open;
+                };
+                return SignalMenuOutcome::Send(*signal);
+            }
+            KeyCode::Esc | KeyCode::Char('s') => return SignalMenuOutcome::Cancelled,
+            _ => {}
+        }
+        SignalMenuOutcome::Open
+    }
+}
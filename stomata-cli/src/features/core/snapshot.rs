@@ -0,0 +1,129 @@
+//! Periodic snapshots for long-term trends
+//!
+//! Runs as a lightweight daemon that reads the registered collectors on a
+//! fixed schedule and writes each reading as a JSON file to a directory,
+//! keeping only the latest ones. 288 snapshots five minutes apart cover a
+//! day, enough to see trends on servers without a time series database.
+//!
+//! ```bash
+//! stomata snapshot --every 5m --out-dir ./snaps --keep 288
+//! stomata snapshot --every 1h --keep 0 --only cpu,memory,disk --gzip
+//! stomata snapshot --every 1m --keep 10080 --zstd
+//! ```
+
+use std::{
+    iter::once,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, bail};
+use clap::Parser;
+use stomata_core::{
+    collectors::{CollectorRegistry, SystemInfo},
+    history::parse_range,
+    storage::{Snapshot, SnapshotDir},
+};
+
+use crate::constants::COLLECT_WARMUP_MS;
+
+/// Arguments of `stomata snapshot`
+#[derive(Parser, Clone)]
+#[command(name = "snapshot")]
+#[command(about = "Write periodic snapshots of the collectors to a directory")]
+pub struct SnapshotCli {
+    /// Time between two snapshots, e.g. `30s`, `5m` or `1h`
+    #[arg(long, default_value = "5m")]
+    pub every: String,
+    /// Directory the snapshots are written to
+    #[arg(long, default_value = "./snaps")]
+    pub out_dir: PathBuf,
+    /// Number of snapshots to keep, the oldest are removed first, `0`
+    /// keeps all of them
+    #[arg(long, default_value_t = 288)]
+    pub keep: usize,
+    /// Collectors to include, all of them by default
+    #[arg(long, value_delimiter = ',')]
+    pub only: Vec<String>,
+    /// Write the snapshots gzip-compressed, as `.json.gz`
+    #[arg(long, conflicts_with = "zstd")]
+    pub gzip: bool,
+    /// Write the snapshots zstd-compressed, as `.json.zst`
+    #[arg(long)]
+    pub zstd: bool,
+}
+
+/// Parses the snapshot arguments and writes snapshots until the process is
+/// stopped. Failing collectors and writes are reported on stderr without
+/// stopping the schedule.
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid or name an unknown
+/// collector.
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    let cli =
+        SnapshotCli::try_parse_from(once("snapshot".to_string()).chain(args.iter().cloned()))?;
+    let every = parse_range(&cli.every)?
+        .to_std()
+        .ok()
+        .filter(|every| *every >= Duration::from_secs(1))
+        .with_context(|| format!("`--every {}` must be at least one second", cli.every))?;
+
+    let mut registry = CollectorRegistry::with_builtin();
+    if !cli.only.is_empty() {
+        if let Some(unknown) = cli
+            .only
+            .iter()
+            .find(|id| !registry.ids().any(|known| known == id.as_str()))
+        {
+            let known: Vec<&str> = registry.ids().collect();
+            bail!(
+                "unknown collector `{unknown}`, expected one of {}",
+                known.join(", ")
+            );
+        }
+        let only: Vec<&str> = cli.only.iter().map(String::as_str).collect();
+        registry.retain(&only);
+    }
+
+    let hostname = SystemInfo::new().hostname;
+    let mut snapshots = SnapshotDir::new(&cli.out_dir, cli.keep);
+    if cli.gzip {
+        snapshots = snapshots.with_gzip();
+    } else if cli.zstd {
+        snapshots = snapshots.with_zstd();
+    }
+    eprintln!(
+        "writing a snapshot every {} to {}",
+        cli.every,
+        snapshots.dir().display()
+    );
+
+    // rates are computed against the reading taken when the collectors
+    // were created
+    thread::sleep(Duration::from_millis(COLLECT_WARMUP_MS));
+    let mut next = Instant::now();
+    loop {
+        let mut metrics = Vec::new();
+        for (id, result) in registry.collect_all() {
+            match result {
+                Ok(metric) => metrics.push(metric),
+                Err(err) => eprintln!("{id}: {err}"),
+            }
+        }
+        if let Err(err) = snapshots.write(&Snapshot::new(hostname.as_str(), metrics)) {
+            eprintln!("{err}");
+        }
+
+        // a fixed schedule, so slow collections don't make snapshots drift
+        next += every;
+        let now = Instant::now();
+        if next > now {
+            thread::sleep(next - now);
+        } else {
+            next = now;
+        }
+    }
+}
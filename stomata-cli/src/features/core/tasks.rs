@@ -0,0 +1,136 @@
+//! Tasks table of the SingleProcess page
+//!
+//! The threads of the viewed process can be sorted by CPU, memory, TID or
+//! name, and `Enter` opens the scheduling details of the selected one, so
+//! the busy threads of a process stand out.
+
+use std::cmp::Reverse;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::process::{ProcessData, ThreadDetails};
+
+use crate::structs::TableUIState;
+
+/// Column the tasks table is sorted by
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSort {
+    /// Highest CPU usage first
+    #[default]
+    Cpu,
+    /// Highest memory first
+    Memory,
+    /// Lowest thread id first
+    Tid,
+    /// Name in alphabetical order
+    Name,
+}
+
+impl TaskSort {
+    /// The sort after this one
+    pub fn next(self) -> Self {
+        match self {
+            TaskSort::Cpu => TaskSort::Memory,
+            TaskSort::Memory => TaskSort::Tid,
+            TaskSort::Tid => TaskSort::Name,
+            TaskSort::Name => TaskSort::Cpu,
+        }
+    }
+
+    /// Column name for the table title
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskSort::Cpu => "CPU",
+            TaskSort::Memory => "Memory",
+            TaskSort::Tid => "TID",
+            TaskSort::Name => "Name",
+        }
+    }
+
+    /// Sorts `tasks` by this column
+    pub fn sort(self, tasks: &mut [ProcessData]) {
+        match self {
+            TaskSort::Cpu => tasks.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+            TaskSort::Memory => tasks.sort_by_key(|task| Reverse(task.memory)),
+            TaskSort::Tid => tasks.sort_by_key(|task| task.pid),
+            TaskSort::Name => tasks.sort_by(|a, b| a.name.cmp(&b.name)),
+        }
+    }
+}
+
+/// State of the tasks table
+#[derive(Debug, Default)]
+pub struct TasksUIState {
+    /// Process the tasks belong to, the selection starts over for another
+    pub pid: u32,
+
+    /// Selection state of the tasks table
+    pub table: TableUIState,
+
+    /// Column the table is sorted by
+    pub sort: TaskSort,
+
+    /// Thread ids in table order, to resolve the selection and keep it on
+    /// the same thread when the table is sorted again
+    pub tids: Vec<u32>,
+
+    /// Details of the opened thread, `Err` when it couldn't be read
+    pub details: Option<Result<ThreadDetails, String>>,
+}
+
+impl TasksUIState {
+    /// Starts over with the first row when `pid` isn't the process the
+    /// table showed before
+    pub fn track(&mut self, pid: u32) {
+        if pid != self.pid {
+            *self = Self {
+                pid,
+                sort: self.sort,
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Handles a key press on the SingleProcess page.
+    ///
+    /// While the details of a thread are shown, `Esc` or `Enter` closes
+    /// them. Otherwise:
+    ///
+    /// - `Up`/`Down` - Select the previous or next thread
+    /// - `t` - Sort by the next column, keeping the selected thread
+    /// - `Enter` - Show the details of the selected thread
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.details.is_some() {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
+                self.details = None;
+            }
+            return true;
+        }
+        if self.tids.is_empty() {
+            return false;
+        }
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('t') => self.sort = self.sort.next(),
+            KeyCode::Enter => {
+                let Some(&tid) = self
+                    .table
+                    .list
+                    .selected()
+                    .and_then(|index| self.tids.get(index))
+                else {
+                    return true;
+                };
+                self.details = Some(
+                    ThreadDetails::fetch(self.pid, tid)
+                        .ok_or_else(|| format!("Thread {tid} has exited or can't be read")),
+                );
+            }
+            _ => return false,
+        }
+        true
+    }
+}
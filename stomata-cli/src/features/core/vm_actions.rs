@@ -0,0 +1,86 @@
+//! Start and shutdown actions of the VMs page
+//!
+//! Starting a VM happens right away; shutting one down asks for
+//! confirmation first since it takes the guest offline.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_core::collectors::vms::{VmAction, VmCollector};
+
+use crate::structs::{StatusBar, TableUIState};
+
+/// State of the VMs page
+#[derive(Debug, Default)]
+pub struct VmsUIState {
+    /// Selection state of the VMs table
+    pub table: TableUIState,
+
+    /// VM names in table order, to resolve the selection
+    pub names: Vec<String>,
+
+    /// VM waiting for the shutdown to be confirmed
+    pub confirm_shutdown: Option<String>,
+}
+
+impl VmsUIState {
+    /// Name of the selected VM
+    pub fn selected(&self) -> Option<&String> {
+        self.table
+            .list
+            .selected()
+            .and_then(|index| self.names.get(index))
+    }
+
+    /// Handles a key press on the VMs page.
+    ///
+    /// While a shutdown waits for confirmation, `y` confirms and any other
+    /// key cancels. The outcome of an action is shown in `status_bar`.
+    /// Otherwise:
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `s` - Start the selected VM
+    /// - `x` - Shut down the selected VM, after confirmation
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        collector: &VmCollector,
+        status_bar: &mut StatusBar,
+    ) -> bool {
+        if let Some(name) = self.confirm_shutdown.take() {
+            if key.code == KeyCode::Char('y') {
+                status_bar.report(apply(collector, &name, VmAction::Shutdown));
+            } else {
+                status_bar.info(format!("Shutdown of '{name}' cancelled"));
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Char('s') => {
+                if let Some(name) = self.selected() {
+                    status_bar.report(apply(collector, name, VmAction::Start));
+                }
+            }
+            KeyCode::Char('x') => {
+                self.confirm_shutdown = self.selected().cloned();
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Applies `action` to the VM `name`, returning the message to show
+fn apply(collector: &VmCollector, name: &str, action: VmAction) -> Result<String, String> {
+    match collector.apply(name, action) {
+        Ok(()) => Ok(match action {
+            VmAction::Start => format!("Started '{name}'"),
+            VmAction::Shutdown => format!("Shutdown requested for '{name}'"),
+        }),
+        Err(err) => Err(format!("Failed to {} '{name}': {err}", action.command())),
+    }
+}
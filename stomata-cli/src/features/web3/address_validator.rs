@@ -0,0 +1,128 @@
+//! Interactive address validation for the Web3 TUI
+//!
+//! Holds the state behind the Address Validation page: the address being
+//! typed and the validation result, which is recomputed on every edit so
+//! the page always reflects the current input.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use stomata_web3::providers::address::{AddressValidator, Chain, ValidationResult};
+
+use crate::structs::TextInput;
+
+/// How the typed address compares to its EIP-55 checksummed form
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChecksumStatus {
+    /// Mixed case input that matches the checksum
+    Matches,
+
+    /// All lowercase or all uppercase input, carries no checksum
+    NotChecksummed,
+
+    /// Mixed case input that doesn't match the checksum, likely a typo
+    Mismatch,
+}
+
+/// State of the Address Validation page
+pub struct AddressValidatorState {
+    /// Address being typed
+    pub input: TextInput,
+
+    /// Whether key presses edit the input
+    pub editing: bool,
+
+    /// Validation result of the current input, `None` while empty
+    pub result: Option<ValidationResult>,
+
+    /// Chain detected from the address format
+    pub chain: Option<Chain>,
+
+    /// Checksum comparison, only set for valid EVM addresses
+    pub checksum: Option<ChecksumStatus>,
+
+    /// Whether a QR code of the checksummed address is shown
+    pub show_qr: bool,
+}
+
+impl AddressValidatorState {
+    /// Creates the page state with an empty input ready for typing.
+    pub fn new() -> Self {
+        Self {
+            input: TextInput::new("Address", false),
+            editing: true,
+            result: None,
+            chain: None,
+            checksum: None,
+            show_qr: false,
+        }
+    }
+
+    /// Handles a key press on the Address Validation page.
+    ///
+    /// While editing, typed and pasted characters go to the input and `Esc`
+    /// stops editing so global shortcuts work again. Otherwise:
+    ///
+    /// - `Enter` or `i` - Start editing
+    /// - `c` - Clear the input
+    /// - `r` - Toggle the QR code of the checksummed address
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.editing {
+            if key.code == KeyCode::Esc {
+                self.editing = false;
+                return true;
+            }
+            let consumed = self.input.handle_key(key);
+            if consumed {
+                self.validate();
+            }
+            return consumed;
+        }
+
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('i') => self.editing = true,
+            KeyCode::Char('c') => {
+                self.input.value.clear();
+                self.validate();
+            }
+            KeyCode::Char('r') => self.show_qr = !self.show_qr,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Revalidates the current input.
+    fn validate(&mut self) {
+        let address = self.input.value.trim();
+        if address.is_empty() {
+            self.result = None;
+            self.chain = None;
+            self.checksum = None;
+            return;
+        }
+
+        let result = AddressValidator::validate(address);
+        self.checksum = match &result {
+            ValidationResult::Valid { checksummed } => Some(checksum_status(address, checksummed)),
+            _ => None,
+        };
+        self.chain = AddressValidator::detect_chain(address);
+        self.result = Some(result);
+    }
+}
+
+/// Compares the hex part of a valid address against its checksummed form.
+fn checksum_status(address: &str, checksummed: &str) -> ChecksumStatus {
+    let hex = &address[2..];
+    let all_lower = hex == hex.to_lowercase();
+    let all_upper = hex == hex.to_uppercase();
+
+    if all_lower || all_upper {
+        ChecksumStatus::NotChecksummed
+    } else if address[2..] == checksummed[2..] {
+        ChecksumStatus::Matches
+    } else {
+        ChecksumStatus::Mismatch
+    }
+}
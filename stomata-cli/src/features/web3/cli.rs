@@ -16,6 +16,9 @@ use clap::{Parser, Subcommand, ValueEnum};
 ///
 /// # List all stored keys
 /// stomata web3 key list
+///
+/// # Check the key store for problems
+/// stomata web3 key verify
 /// ```
 #[derive(Parser, Clone)]
 #[command(name = "web3")]
@@ -42,6 +45,7 @@ pub enum Web3Tool {
     /// ```bash
     /// stomata web3 av -a 0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb
     /// stomata web3 av -a 0xinvalid  # using alias
+    /// stomata web3 av -a 0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb --qr
     /// ```
     #[command(name = "address-validator", alias = "av")]
     AddressValidator {
@@ -51,6 +55,12 @@ pub enum Web3Tool {
         /// 40 hexadecimal characters. The checksum will be validated if present.
         #[arg(short, long, required = true)]
         address: String,
+
+        /// Print the checksummed address as a QR code
+        ///
+        /// Drawn with half-block characters so it can be scanned by a phone.
+        #[arg(long)]
+        qr: bool,
     },
     /// Key management operations
     ///
@@ -142,6 +152,39 @@ pub enum KeySubCommands {
     #[command(name = "list", alias = "l")]
     List {},
 
+    /// Check the key store for problems
+    ///
+    /// Reports key files that aren't valid JSON key files, key files or
+    /// the keys directory readable by other users (expected 0600/0700),
+    /// and key files sharing the same name. Exits with status 1 when a
+    /// problem is found.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// stomata web3 key verify
+    /// ```
+    #[command(name = "verify")]
+    Verify {},
+
+    /// Measure Argon2 key derivation time on this machine
+    ///
+    /// Times several Argon2id parameter sets and recommends the most
+    /// expensive one that still derives a key within the target time.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// stomata web3 key kdf-bench
+    /// stomata web3 key kdf-bench --target-ms 500
+    /// ```
+    #[command(name = "kdf-bench")]
+    KdfBench {
+        /// Longest acceptable derivation time in milliseconds
+        #[arg(short, long, default_value_t = 250)]
+        target_ms: u64,
+    },
+
     /// Delete a stored key permanently
     ///
     /// Removes the encrypted key from storage. This action cannot be undone.
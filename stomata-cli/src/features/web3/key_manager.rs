@@ -0,0 +1,280 @@
+//! Interactive key management for the Web3 TUI
+//!
+//! Holds the state behind the Key Management page: the list of stored keys
+//! and the modal forms used to encrypt, decrypt and delete them. Every
+//! action goes through the same `stomata_web3` storage functions used by
+//! the `stomata web3 key` subcommands.
+
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    widgets::TableState,
+};
+use stomata_web3::providers::{
+    KeyMetadata, KeyStoreIssue, SecretBytes, delete_key, list_keys_with_metadata, retrieve_key,
+    store_key, verify_key_store,
+};
+
+use crate::structs::{StatusBar, TextInput};
+
+/// Action performed when a key manager modal is submitted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyAction {
+    /// Encrypt and store a new key
+    Encrypt,
+
+    /// Decrypt and reveal the selected key
+    Decrypt,
+
+    /// Permanently delete the selected key
+    Delete,
+}
+
+impl KeyAction {
+    /// Title shown in the modal border
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyAction::Encrypt => "Encrypt new key",
+            KeyAction::Decrypt => "Decrypt key",
+            KeyAction::Delete => "Delete key",
+        }
+    }
+}
+
+/// Modal form currently open on the key manager page
+pub struct KeyModal {
+    /// What happens on submit
+    pub action: KeyAction,
+
+    /// Key the action applies to (`None` when creating a new key)
+    pub key_name: Option<String>,
+
+    /// Input fields of the form, in tab order
+    pub fields: Vec<TextInput>,
+
+    /// Index of the focused field
+    pub focused: usize,
+
+    /// Error from the last submit attempt
+    pub error: Option<String>,
+
+    /// Decrypted key material, shown once decryption succeeds
+    pub revealed: Option<SecretBytes>,
+}
+
+impl KeyModal {
+    fn new(action: KeyAction, key_name: Option<String>) -> Self {
+        let fields = match action {
+            KeyAction::Encrypt => vec![
+                TextInput::new("Name", false),
+                TextInput::new("Key to encrypt", true),
+                TextInput::new("Password", true),
+            ],
+            KeyAction::Decrypt => vec![TextInput::new("Password", true)],
+            KeyAction::Delete => Vec::new(),
+        };
+
+        Self {
+            action,
+            key_name,
+            fields,
+            focused: 0,
+            error: None,
+            revealed: None,
+        }
+    }
+}
+
+/// State of the Key Management page
+#[derive(Default)]
+pub struct KeyManagerState {
+    /// Stored keys with their metadata
+    pub keys: Vec<KeyMetadata>,
+
+    /// Table state for the key list selection
+    pub key_list: TableState,
+
+    /// Open modal form, if any
+    pub modal: Option<KeyModal>,
+
+    /// Problems found in the key store on the last reload
+    pub issues: Vec<KeyStoreIssue>,
+}
+
+impl KeyManagerState {
+    /// Creates the page state and loads the stored keys, a failure to
+    /// list them is shown in `status_bar`.
+    pub fn new(status_bar: &mut StatusBar) -> Self {
+        let mut state = Self::default();
+        state.reload(status_bar);
+        state
+    }
+
+    /// Reloads the key list from disk, keeping the selection in bounds,
+    /// and checks the key store for problems.
+    pub fn reload(&mut self, status_bar: &mut StatusBar) {
+        match list_keys_with_metadata() {
+            Ok(keys) => self.keys = keys,
+            Err(err) => status_bar.error(format!("Failed to list keys: {err}")),
+        }
+        self.issues = verify_key_store().unwrap_or_default();
+
+        if self.keys.is_empty() {
+            self.key_list.select(None);
+        } else {
+            let selected = self.key_list.selected().unwrap_or(0);
+            self.key_list
+                .select(Some(selected.min(self.keys.len() - 1)));
+        }
+    }
+
+    /// Name of the currently selected key
+    pub fn selected_key(&self) -> Option<String> {
+        self.key_list
+            .selected()
+            .and_then(|index| self.keys.get(index))
+            .map(|key| key.name.clone())
+    }
+
+    /// Handles a key press on the Key Management page.
+    ///
+    /// While a modal is open every key goes to the modal. Completed
+    /// actions are reported in `status_bar`. Otherwise:
+    ///
+    /// - `Up`/`Down` - Move the selection
+    /// - `n` - Encrypt a new key
+    /// - `d` or `Enter` - Decrypt the selected key
+    /// - `x` or `Delete` - Delete the selected key
+    /// - `r` - Reload the key list
+    ///
+    /// Returns `true` if the key was consumed and should not be handled
+    /// as a global shortcut.
+    pub fn handle_key(&mut self, key: KeyEvent, status_bar: &mut StatusBar) -> bool {
+        if self.modal.is_some() {
+            self.handle_modal_key(key, status_bar);
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Up => {
+                if let Some(selected) = self.key_list.selected() {
+                    self.key_list.select(Some(selected.saturating_sub(1)));
+                }
+            }
+            KeyCode::Down => {
+                if let Some(selected) = self.key_list.selected() {
+                    let next = (selected + 1).min(self.keys.len().saturating_sub(1));
+                    self.key_list.select(Some(next));
+                }
+            }
+            KeyCode::Char('n') => {
+                self.modal = Some(KeyModal::new(KeyAction::Encrypt, None));
+            }
+            KeyCode::Char('d') | KeyCode::Enter => {
+                if let Some(name) = self.selected_key() {
+                    self.modal = Some(KeyModal::new(KeyAction::Decrypt, Some(name)));
+                }
+            }
+            KeyCode::Char('x') | KeyCode::Delete => {
+                if let Some(name) = self.selected_key() {
+                    self.modal = Some(KeyModal::new(KeyAction::Delete, Some(name)));
+                }
+            }
+            KeyCode::Char('r') => {
+                status_bar.info("Key list reloaded");
+                self.reload(status_bar);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Routes a key press to the open modal.
+    ///
+    /// `Esc` closes the modal (dropping any revealed secret), `Tab`/`Up`/`Down`
+    /// move between fields, `Enter` submits and any other key edits the
+    /// focused field.
+    fn handle_modal_key(&mut self, key: KeyEvent, status_bar: &mut StatusBar) {
+        let Some(modal) = self.modal.as_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.modal = None;
+            }
+            KeyCode::Tab | KeyCode::Down if !modal.fields.is_empty() => {
+                modal.focused = (modal.focused + 1) % modal.fields.len();
+            }
+            KeyCode::BackTab | KeyCode::Up if !modal.fields.is_empty() => {
+                modal.focused = modal
+                    .focused
+                    .checked_sub(1)
+                    .unwrap_or(modal.fields.len() - 1);
+            }
+            KeyCode::Enter => {
+                if modal.revealed.is_some() {
+                    self.modal = None;
+                } else {
+                    self.submit(status_bar);
+                }
+            }
+            _ => {
+                if modal.revealed.is_none()
+                    && let Some(field) = modal.fields.get_mut(modal.focused)
+                {
+                    field.handle_key(key);
+                }
+            }
+        }
+    }
+
+    /// Runs the action of the open modal against the key store.
+    fn submit(&mut self, status_bar: &mut StatusBar) {
+        let Some(modal) = self.modal.as_mut() else {
+            return;
+        };
+
+        match modal.action {
+            KeyAction::Encrypt => {
+                let name = modal.fields[0].value.trim().to_string();
+                let key = SecretBytes::new(modal.fields[1].value.as_bytes().to_vec());
+                let password = &modal.fields[2].value;
+
+                if name.is_empty() || key.is_empty() || password.is_empty() {
+                    modal.error = Some("All fields are required".to_string());
+                    return;
+                }
+
+                match store_key(&name, &key, password) {
+                    Ok(()) => {
+                        self.modal = None;
+                        status_bar.info(format!("Key '{name}' encrypted and stored"));
+                        self.reload(status_bar);
+                    }
+                    Err(err) => modal.error = Some(err.to_string()),
+                }
+            }
+            KeyAction::Decrypt => {
+                let name = modal.key_name.clone().unwrap_or_default();
+                match retrieve_key(&name, &modal.fields[0].value) {
+                    Ok(secret) => {
+                        modal.error = None;
+                        modal.revealed = Some(secret);
+                    }
+                    Err(err) => modal.error = Some(err.to_string()),
+                }
+            }
+            KeyAction::Delete => {
+                let name = modal.key_name.clone().unwrap_or_default();
+                match delete_key(&name) {
+                    Ok(()) => {
+                        self.modal = None;
+                        status_bar.info(format!("Key '{name}' deleted"));
+                        self.reload(status_bar);
+                    }
+                    Err(err) => modal.error = Some(err.to_string()),
+                }
+            }
+        }
+    }
+}
@@ -19,7 +19,11 @@
 //!
 //! # Modules
 //!
+//! - [`address_validator`] - State of the interactive Address Validation page
 //! - [`cli`] - Command-line interface definitions and argument parsing
+//! - [`key_manager`] - State and actions of the interactive Key Management page
 //! - [`web3_feature`] - Core Web3 functionality implementations
+pub mod address_validator;
 pub mod cli;
+pub mod key_manager;
 pub mod web3_feature;
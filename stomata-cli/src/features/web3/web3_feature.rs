@@ -17,23 +17,37 @@ use ratatui::{
     crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
     layout::{Constraint, Layout, Rect},
     prelude::CrosstermBackend,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::Line,
     widgets::{Block, Borders, Tabs},
 };
 
 use crate::{
-    features::web3::cli::{KeySubCommands, Web3Cli, Web3Tool},
+    features::web3::{
+        address_validator::AddressValidatorState,
+        cli::{KeySubCommands, Web3Cli, Web3Tool},
+        key_manager::KeyManagerState,
+    },
     renders::{
-        render_widgets::render_paragraph::paragraph_widget,
+        core_displays::display_status_bar::render_status_bar,
         web3_displays::{
             address_validation::validate_address,
-            key_encryption::{decrypt_key, delete_encrypted_key, encrypt_key, list_all_keys},
+            display_address_validator::render_address_validator,
+            display_key_manager::render_key_manager,
+            key_encryption::{
+                decrypt_key, delete_encrypted_key, encrypt_key, kdf_bench, list_all_keys,
+                verify_keys,
+            },
         },
     },
-    structs::Cli,
+    structs::{Cli, StatusBar},
+    theme::theme,
 };
 
+/// Global shortcuts of the Web3 TUI, shown in the status bar when there is
+/// no message
+const SHORTCUTS: &str = "q quit  ←/→ switch page  1/2 jump to page";
+
 /// Available pages in the Web3 TUI
 ///
 /// Each variant represents a different feature page that can be
@@ -41,6 +55,9 @@ use crate::{
 pub enum Web3Page {
     /// Page for validating Ethereum addresses
     AddressValidation,
+
+    /// Page for managing encrypted keys
+    KeyManagement,
 }
 
 impl Web3Page {
@@ -48,7 +65,7 @@ impl Web3Page {
     ///
     /// Used for rendering the tab bar in the TUI.
     pub fn titles() -> Vec<&'static str> {
-        vec!["Address Validation"]
+        vec!["Address Validation", "Key Management"]
     }
 
     /// Converts a tab index to the corresponding page
@@ -64,6 +81,7 @@ impl Web3Page {
     pub fn from_index(index: usize) -> Self {
         match index {
             0 => Web3Page::AddressValidation,
+            1 => Web3Page::KeyManagement,
             _ => Web3Page::AddressValidation,
         }
     }
@@ -71,8 +89,17 @@ impl Web3Page {
 
 /// UI-specific state for the Web3 interactive interface
 ///
-/// Currently a placeholder for future UI state management.
-pub struct Web3UIState;
+/// Holds the per-page state that must survive between render cycles.
+pub struct Web3UIState {
+    /// Address input and live result of the Address Validation page
+    pub address_validator: AddressValidatorState,
+
+    /// Key list and modal forms of the Key Management page
+    pub key_manager: KeyManagerState,
+
+    /// Outcome of the last action, shown at the bottom of the screen
+    pub status_bar: StatusBar,
+}
 
 /// State manager for the Web3 feature
 ///
@@ -88,20 +115,27 @@ pub struct Web3State {
     /// Index of the currently selected tab
     pub tab_index: usize,
 
-    /// Optional UI-specific state
-    pub ui_state: Option<Web3UIState>,
+    /// UI-specific state for the individual pages
+    pub ui_state: Web3UIState,
 }
 
 impl Web3State {
     /// Creates a new Web3State with default values
     ///
-    /// Initializes to the Address Validation page with rendering enabled.
+    /// Initializes to the Address Validation page with rendering enabled,
+    /// ready for typing an address, and loads the stored keys for the Key Management page.
     pub fn new() -> Self {
+        let mut status_bar = StatusBar::default();
+        let key_manager = KeyManagerState::new(&mut status_bar);
         Self {
             render: true,
             current_page: Web3Page::AddressValidation,
             tab_index: 0,
-            ui_state: None,
+            ui_state: Web3UIState {
+                address_validator: AddressValidatorState::new(),
+                key_manager,
+                status_bar,
+            },
         }
     }
 
@@ -127,21 +161,25 @@ impl Web3State {
     ///
     /// * `frame` - The ratatui frame to render into
     pub fn render(&mut self, frame: &mut Frame) {
-        let chunks =
-            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(frame.area());
+        let chunks = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
 
         // render tabs
         self.render_tabs(frame, chunks[0]);
 
         match &self.current_page {
             Web3Page::AddressValidation => {
-                let para = paragraph_widget(
-                    "Hi! We are adding more interactive features to Stomata Web3",
-                    "About",
-                );
-                frame.render_widget(para, chunks[1]);
+                render_address_validator(frame, chunks[1], &self.ui_state.address_validator);
+            }
+            Web3Page::KeyManagement => {
+                render_key_manager(frame, chunks[1], &mut self.ui_state.key_manager);
             }
         }
+        render_status_bar(frame, chunks[2], &self.ui_state.status_bar, SHORTCUTS);
     }
 
     /// Renders the tab bar at the top of the interface
@@ -155,10 +193,10 @@ impl Web3State {
         let tabs = Tabs::new(titles)
             .block(Block::default().borders(Borders::ALL).title("Stomata"))
             .select(self.tab_index)
-            .style(Style::default().fg(Color::White))
+            .style(Style::default().fg(theme().text))
             .highlight_style(
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme().accent)
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -167,6 +205,10 @@ impl Web3State {
 
     /// Processes keyboard events from the user
     ///
+    /// Page-specific handlers get the first look at a key press so that
+    /// typing into a form doesn't trigger global shortcuts. Keys they don't
+    /// consume fall through to the global shortcuts.
+    ///
     /// # Arguments
     ///
     /// * `key` - The keyboard event to process
@@ -176,7 +218,16 @@ impl Web3State {
     /// Returns an error if event processing fails.
     pub fn handle_events(&mut self, key: KeyEvent) -> anyhow::Result<()> {
         if key.kind == KeyEventKind::Press {
-            self.process_global_events(key);
+            let consumed = match self.current_page {
+                Web3Page::KeyManagement => self
+                    .ui_state
+                    .key_manager
+                    .handle_key(key, &mut self.ui_state.status_bar),
+                Web3Page::AddressValidation => self.ui_state.address_validator.handle_key(key),
+            };
+            if !consumed {
+                self.process_global_events(key);
+            }
         }
         Ok(())
     }
@@ -204,6 +255,10 @@ impl Web3State {
                 self.tab_index = 0;
                 self.current_page = Web3Page::AddressValidation;
             }
+            KeyCode::Char('2') => {
+                self.tab_index = 1;
+                self.current_page = Web3Page::KeyManagement;
+            }
             _ => {}
         }
     }
@@ -233,10 +288,11 @@ impl Web3State {
 ///
 /// # Interactive Mode Keybindings
 ///
-/// - `q` - Quit the application
+/// - `q` - Quit the application (press `Esc` first while typing an address)
 /// - `Tab` or `Right Arrow` - Next tab
 /// - `Left Arrow` - Previous tab
 /// - `1` - Jump to Address Validation tab
+/// - `2` - Jump to Key Management tab
 ///
 /// # Examples
 ///
@@ -267,13 +323,13 @@ pub fn run(
                     .unwrap_or(Duration::from_secs(0));
 
                 // poll for inputs only until timeout
-                if event::poll(timeout)? {
-                    if let Event::Key(key) = event::read()? {
-                        // handle events
-                        web3_state.handle_events(key)?;
-                        // redraw immediately after an event
-                        terminal.draw(|frame| web3_state.render(frame))?;
-                    }
+                if event::poll(timeout)?
+                    && let Event::Key(key) = event::read()?
+                {
+                    // handle events
+                    web3_state.handle_events(key)?;
+                    // redraw immediately after an event
+                    terminal.draw(|frame| web3_state.render(frame))?;
                 }
 
                 if last_tick.elapsed() >= refresh_interval {
@@ -290,11 +346,15 @@ pub fn run(
             match web3_cli {
                 Ok(cli) => {
                     match cli.tool {
-                        Web3Tool::AddressValidator { address } => validate_address(&address),
+                        Web3Tool::AddressValidator { address, qr } => {
+                            validate_address(&address, qr)
+                        }
                         Web3Tool::Key(key_cmd) => match key_cmd {
                             KeySubCommands::Encrypt { name } => encrypt_key(name),
                             KeySubCommands::Decrypt { name, format } => decrypt_key(name, format),
                             KeySubCommands::List {} => list_all_keys(),
+                            KeySubCommands::Verify {} => verify_keys(),
+                            KeySubCommands::KdfBench { target_ms } => kdf_bench(target_ms),
                             KeySubCommands::Delete { name } => delete_encrypted_key(name),
                         },
                     };
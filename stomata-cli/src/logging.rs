@@ -0,0 +1,65 @@
+//! Log file setup
+//!
+//! The TUI owns the terminal, so diagnostics from the collectors and the
+//! render loop go through `tracing` to a log file instead, by default
+//! `~/.local/state/stomata/stomata.log`.
+
+use std::{
+    fs::{self, OpenOptions},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use anyhow::Context;
+use tracing_subscriber::EnvFilter;
+
+use crate::constants::LOG_FILE_NAME;
+
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Path of the log file, `None` when logging is off or the file couldn't
+/// be opened
+pub fn log_path() -> Option<&'static Path> {
+    LOG_PATH.get().map(PathBuf::as_path)
+}
+
+/// Starts writing log events at `level` or above to the log file.
+///
+/// `level` is a level name (`error`, `warn`, `info`, `debug`, `trace`),
+/// `off`, or filter directives like `stomata_core=debug,warn`. A log file
+/// that can't be opened leaves logging off rather than stopping stomata.
+///
+/// # Errors
+///
+/// Returns an error if `level` isn't a valid filter.
+pub fn init(level: &str) -> anyhow::Result<()> {
+    let filter =
+        EnvFilter::try_new(level).with_context(|| format!("invalid --log-level {level:?}"))?;
+    if level.eq_ignore_ascii_case("off") {
+        return Ok(());
+    }
+    let Some(path) = default_log_path() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let Ok(file) = OpenOptions::new().create(true).append(true).open(&path) else {
+        return Ok(());
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .init();
+    let _ = LOG_PATH.set(path);
+    Ok(())
+}
+
+/// The state directory on Linux, the data directory elsewhere
+fn default_log_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_dir)
+        .map(|dir| dir.join("stomata").join(LOG_FILE_NAME))
+}
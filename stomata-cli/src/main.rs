@@ -1,22 +1,35 @@
-use std::time::{Duration, Instant};
-
 use crate::{
+    config::Config,
     features::run_feature,
-    renders::core_displays::display_app::App,
     structs::{AppState, Cli, StomataState},
+    theme::{Theme, set_theme},
+    utils::set_unit_system,
 };
 use clap::Parser;
 use ratatui::crossterm::event::{self, Event};
 
+mod config;
 mod constants;
+mod errors;
 mod features;
+mod logging;
 mod renders;
 mod stomata_state;
 mod structs;
+mod theme;
 mod utils;
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    logging::init(&cli.log_level)?;
+    let config = Config::load()?;
+    set_theme(Theme::resolve(cli.theme.as_deref(), &config)?);
+    set_unit_system(config.units);
+    config.set_global();
+    #[cfg(feature = "core")]
+    if let Some(path) = &cli.export_csv {
+        return features::core::process_export::run_headless(path);
+    }
     let enable_ui = cli.interactive;
     let mut app = StomataState::new();
 
@@ -31,10 +44,10 @@ fn main() -> anyhow::Result<()> {
             match app.state {
                 AppState::FeatureSelection => {
                     terminal.draw(|frame| app.render_feature_selection(frame))?;
-                    if let Event::Key(key) = event::read()? {
-                        if !app.handle_feature_selection(key) {
-                            break; // User quit
-                        }
+                    if let Event::Key(key) = event::read()?
+                        && !app.handle_feature_selection(key)
+                    {
+                        break; // User quit
                     }
                 }
                 AppState::RunningFeature(feature) => {
@@ -45,8 +58,10 @@ fn main() -> anyhow::Result<()> {
                                 app.state = AppState::FeatureSelection;
                             }
                         }
-                        Err(_) => {
-                            eprint!("Error in rendering feature");
+                        Err(err) => {
+                            // stderr is the alternate screen here, so the
+                            // error goes to the log file instead
+                            tracing::error!(?feature, "feature stopped: {err:#}");
                             app.state = AppState::FeatureSelection;
                         }
                     }
@@ -58,9 +73,41 @@ fn main() -> anyhow::Result<()> {
         let cli_clone = cli.clone();
         let cli_feature = cli_clone.feature;
         match cli_feature {
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "inventory" => {
+                features::core::inventory::run(&cli.args)?;
+            }
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "collect" => {
+                features::core::collect::run(&cli.args)?;
+            }
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "export" => {
+                features::core::export::run(&cli.args)?;
+            }
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "agent" => {
+                features::core::remote::run_agent(&cli.args)?;
+            }
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "connect" => {
+                features::core::remote::run_connect(&cli)?;
+            }
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "serve" => {
+                features::core::serve::run(&cli.args)?;
+            }
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "snapshot" => {
+                features::core::snapshot::run(&cli.args)?;
+            }
+            #[cfg(feature = "core")]
+            Some(feature) if feature == "alerts" => {
+                features::core::alerts_export::run(&cli.args)?;
+            }
             Some(feature) => {
                 if let Some(feature) = app.available_features.get(&feature) {
-                    run_feature(*feature, &cli, None);
+                    run_feature(*feature, &cli, None)?;
                 };
             }
             None => println!("No feature selected"),
@@ -4,22 +4,81 @@
 //! application state, handles user input, and coordinates rendering of
 //! different pages in the TUI.
 
+#[cfg(feature = "web3")]
+use std::path::PathBuf;
+use std::sync::PoisonError;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
 use ratatui::{
     Frame,
-    crossterm::event::{KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::Line,
     widgets::{Block, Borders, Tabs},
 };
-use stomata_core::collectors::structs::{Metrics, MetricsToFetch, StomataSystemMetrics};
+use stomata_core::{
+    alerts::{
+        AlertEngine, AlertLog, DeletedExecutableRule, InodeUsageRule, SuspiciousConnectionRule,
+        ThrottlingRule,
+    },
+    collectors::{
+        BootAnalysis, SystemBackend,
+        power::SleepInhibitor,
+        process::{
+            actions::{Signal, get_affinity, get_priority, send_signal, set_priority},
+            find_successor,
+        },
+        structs::{Metrics, MetricsToFetch, StomataSystemMetrics},
+        vms::VmCollector,
+    },
+    remote::{RemoteHost, RemoteMetrics},
+    storage::{HistoryRecorder, MetricStore, RetentionPolicy},
+    units::UnitSystem,
+};
 
 use crate::{
-    renders::core_displays::traits::{Display, SingleProcessDisplay},
-    structs::{Page, SingleProcessUI, UIState},
-    utils::bytes_to_mb,
+    config::config,
+    constants::{ALERT_EVALUATION_SECS, HOST_SIDEBAR_WIDTH},
+    errors::{ErrorLog, ErrorReporter},
+    features::core::{
+        affinity_editor::{AffinityEditor, AffinityEditorOutcome},
+        command_line::{Command, CommandLine, CommandLineOutcome},
+        command_palette::{CommandPalette, PaletteAction, PaletteOutcome},
+        follow::FollowTarget,
+        process_columns::{ColumnEditor, ProcessColumn},
+        process_export,
+        signal_menu::{SignalMenu, SignalMenuOutcome},
+    },
+    renders::core_displays::{
+        display_column_editor::render_column_editor,
+        display_command_palette::render_command_palette,
+        display_connections::render_listening_ports,
+        display_crash_events::render_crash_events,
+        display_error_log::render_error_log,
+        display_history::render_history,
+        display_hosts::{HostRow, render_host_grid, render_host_sidebar},
+        display_logs::render_logs,
+        display_network::render_interface_detail,
+        display_signal_menu::render_signal_menu,
+        display_status_bar::{SHORTCUTS, render_command_line, render_status_bar},
+        display_summary::render_summary,
+        display_system_info::{render_boot_analysis, render_hardware, render_sessions},
+        traits::{Display, SingleProcessDisplay},
+    },
+    structs::{ControlsPanel, Page, Pane, SchedulerHistory, SingleProcessUI, SplitView, UIState},
+    theme::{BUILTIN_THEMES, Theme, set_theme, theme},
+    utils::{set_unit_system, unit_system},
 };
 
+#[cfg(feature = "web3")]
+use crate::{features::core::exe_checksum::ExeChecksum, structs::TextInput};
+#[cfg(feature = "scripting")]
+use crate::{features::core::script_hooks::ScriptHooks, structs::ScriptOutput};
+#[cfg(feature = "geoip")]
+use stomata_core::collectors::connections::geoip::GeoIp;
+
 /// Main application state manager
 ///
 /// Manages the entire application lifecycle including:
@@ -35,17 +94,55 @@ pub struct App {
     /// System metrics collector and storage
     pub metrics: StomataSystemMetrics,
 
+    /// Agents the pages are fetched from instead of `metrics`, set by
+    /// `stomata connect`
+    pub remotes: Vec<RemoteHost>,
+
+    /// Index in `remotes` of the host the pages show
+    pub selected_remote: usize,
+
     /// Index of the currently selected tab (0-based)
     pub tab_index: usize,
 
-    /// The currently active page being displayed
+    /// The currently active page being displayed, the focused pane's
+    /// while the view is split
     pub current_page: Page,
 
+    /// Page of the second pane while the view is split with `|`
+    pub split: Option<SplitView>,
+
     /// Whether to store historical metrics data
     pub store_data: bool,
 
+    /// Writes the history to the metric store, set by `start_recording`
+    /// when `store_data` is on
+    pub recorder: Option<HistoryRecorder>,
+
     /// UI state for stateful widgets (tables, lists, charts)
     pub ui_state: UIState,
+
+    /// Alert rules evaluated every `ALERT_EVALUATION_SECS`, regardless of
+    /// the active page
+    pub alerts: AlertEngine,
+
+    /// When the alert rules last ran
+    pub alerts_evaluated_at: Option<Instant>,
+
+    /// Lock keeping the machine awake while held, toggled with `w`
+    pub sleep_inhibitor: Option<SleepInhibitor>,
+
+    /// Why the sleep inhibitor couldn't be taken
+    pub sleep_inhibitor_error: Option<String>,
+
+    /// Sends errors raised while running to the status bar and debug log
+    pub errors: ErrorReporter,
+
+    /// Errors received from `errors`, listed in a popup opened with `!`
+    pub error_log: ErrorLog,
+
+    /// User hooks script run after every system metrics refresh
+    #[cfg(feature = "scripting")]
+    pub script_hooks: Option<ScriptHooks>,
 }
 
 impl App {
@@ -67,13 +164,183 @@ impl App {
     /// let app_with_history = App::new(true);
     /// ```
     pub fn new(store_metrics: bool) -> Self {
+        #[allow(unused_mut)]
+        let mut ui_state = UIState::default();
+
+        #[cfg(feature = "scripting")]
+        let script_hooks = match ScriptHooks::load() {
+            Some(Ok(hooks)) => Some(hooks),
+            Some(Err(error)) => {
+                // surface compile errors on the Metrics page instead of failing startup
+                ui_state.script_output = Some(ScriptOutput {
+                    error: Some(error),
+                    ..Default::default()
+                });
+                None
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "geoip")]
+        {
+            let geoip = &config().geoip;
+            if geoip.country_db.is_some() || geoip.asn_db.is_some() {
+                ui_state.geoip = Some(
+                    GeoIp::open(geoip.country_db.as_deref(), geoip.asn_db.as_deref())
+                        .map_err(|err| format!("{err:#}")),
+                );
+            }
+        }
+
+        let mut metrics = StomataSystemMetrics::new();
+        metrics.vms = VmCollector::new(config().libvirt.uri.clone());
+        metrics.disks.mounts.filter = config().disks.mount_filter();
+
+        let mut alerts = AlertEngine::new(vec![
+            Box::new(DeletedExecutableRule),
+            Box::new(SuspiciousConnectionRule::new(
+                config().connections.allowlist(),
+            )),
+            Box::new(
+                InodeUsageRule::new(config().alerts.inode_percent)
+                    .with_filter(config().disks.mount_filter()),
+            ),
+        ]);
+        if config().alerts.throttling_secs > 0 {
+            alerts.add_rule(Box::new(ThrottlingRule::new(Duration::from_secs(
+                config().alerts.throttling_secs,
+            ))));
+        }
+        if let Some(log) = AlertLog::default_location() {
+            alerts = alerts.with_log(log);
+        }
+
+        let (errors, error_log) = ErrorLog::channel();
+
         Self {
             render: true,
-            metrics: StomataSystemMetrics::new(),
+            metrics,
+            remotes: Vec::new(),
+            selected_remote: 0,
             tab_index: 0,
             current_page: Page::System,
+            split: None,
             store_data: store_metrics, // by default don't store history data
-            ui_state: UIState::default(),
+            recorder: None,
+            ui_state,
+            alerts,
+            alerts_evaluated_at: None,
+            #[cfg(feature = "scripting")]
+            script_hooks,
+            sleep_inhibitor: None,
+            sleep_inhibitor_error: None,
+            errors,
+            error_log,
+        }
+    }
+
+    /// Shows the metrics of the agents behind `remotes` instead of the
+    /// local machine, starting with the first. Pages the agents don't serve
+    /// stay empty and their actions are disabled, as they would act on the
+    /// local machine. Each agent is polled on background threads.
+    pub fn with_remotes(mut self, remotes: Vec<RemoteMetrics>) -> Self {
+        self.remotes = remotes.into_iter().map(RemoteHost::new).collect();
+        self.selected_remote = 0;
+        self
+    }
+
+    /// Opens the metric store under the data directory and records the
+    /// local machine into it every `interval` from now on, rolled up by the
+    /// `[store]` retention of the config file. Does nothing
+    /// unless `store_data` is on or when showing an agent; a store that
+    /// can't be opened is reported.
+    pub fn start_recording(&mut self, interval: Duration) {
+        if !self.store_data || self.is_remote() {
+            return;
+        }
+        let Some(path) = MetricStore::default_location() else {
+            self.ui_state
+                .status_bar
+                .error("No data directory to keep the history in");
+            return;
+        };
+        let retention = match config().store.retention() {
+            Ok(retention) => retention,
+            Err(err) => {
+                self.errors.report("read [store] retention", &err);
+                RetentionPolicy::default()
+            }
+        };
+        match MetricStore::open(&path) {
+            Ok(store) => {
+                self.recorder =
+                    Some(HistoryRecorder::new(store, interval).with_retention(retention))
+            }
+            Err(err) => self.errors.report("open metric store", &err.into()),
+        }
+    }
+
+    /// Whether the pages show an agent instead of the local machine
+    fn is_remote(&self) -> bool {
+        !self.remotes.is_empty()
+    }
+
+    /// Shows the host at `index` of `remotes`. The charts start afresh so
+    /// they don't mix the history of two hosts.
+    fn select_remote(&mut self, index: usize) {
+        let Some(remote) = self.remotes.get(index) else {
+            return;
+        };
+        self.selected_remote = index;
+        self.ui_state.scheduler_history = SchedulerHistory::default();
+        self.ui_state.anomalies.clear();
+        self.ui_state.process_table.lifetimes.clear();
+        self.ui_state.networks_state = None;
+        self.ui_state.process_table.select_row(0);
+        self.ui_state
+            .status_bar
+            .info(format!("Showing {}", remote.hostname()));
+    }
+
+    /// Fetches `request` from the local machine, or reads the latest poll
+    /// of the agent when connected to one. `None` when the agent doesn't
+    /// serve the request, hasn't answered yet or couldn't be reached, which
+    /// is reported.
+    fn fetch(&mut self, request: MetricsToFetch) -> Option<Metrics> {
+        let Some(remote) = self.remotes.get_mut(self.selected_remote) else {
+            return Some(self.metrics.fetch(request));
+        };
+        if !request.is_remote() {
+            return None;
+        }
+        match remote.latest(request)? {
+            Ok(metrics) => Some(metrics),
+            Err(err) => {
+                let context = format!("fetch from {}", remote.address());
+                self.errors.report(&context, &anyhow::anyhow!(err));
+                None
+            }
+        }
+    }
+
+    /// Sets the time between two polls of the agents
+    pub fn set_remote_interval(&mut self, interval: Duration) {
+        for remote in &mut self.remotes {
+            remote.set_interval(interval);
+        }
+    }
+
+    /// Takes the sleep inhibitor, or releases it when held
+    pub fn toggle_sleep_inhibitor(&mut self) {
+        if self.sleep_inhibitor.take().is_some() {
+            return;
+        }
+        match SleepInhibitor::acquire("Monitoring with stomata") {
+            Ok(inhibitor) => {
+                self.sleep_inhibitor = Some(inhibitor);
+                self.sleep_inhibitor_error = None;
+            }
+            Err(err) => self.sleep_inhibitor_error = Some(format!("{err:#}")),
         }
     }
 
@@ -97,11 +364,48 @@ impl App {
         self.current_page = Page::from_index(self.tab_index);
     }
 
+    /// Splits the view into two panes side by side, the new right pane
+    /// showing the next tab with the focus, or goes back to the page of the
+    /// focused pane alone when already split
+    pub fn toggle_split(&mut self) {
+        if self.split.take().is_some() {
+            return;
+        }
+        self.split = Some(SplitView {
+            page: self.current_page.clone(),
+            tab_index: self.tab_index,
+            focus: Pane::Right,
+        });
+        self.next_tab();
+    }
+
+    /// Moves the focus to `pane` of the split view
+    fn focus_pane(&mut self, pane: Pane) {
+        if self.split.as_ref().is_some_and(|split| split.focus != pane) {
+            self.swap_panes();
+            if let Some(split) = self.split.as_mut() {
+                split.focus = pane;
+            }
+        }
+    }
+
+    /// Swaps the focused page with the page of the other pane
+    fn swap_panes(&mut self) {
+        if let Some(split) = self.split.as_mut() {
+            std::mem::swap(&mut self.current_page, &mut split.page);
+            std::mem::swap(&mut self.tab_index, &mut split.tab_index);
+        }
+    }
+
     /// Renders the current page to the terminal frame
     ///
-    /// Divides the screen into a tab bar and content area, then renders
-    /// the appropriate content based on the current page. Fetches fresh
-    /// metrics data for the current page before rendering.
+    /// Divides the screen into a summary strip, a tab bar, the content area
+    /// and a status bar, then renders the appropriate content based on the current page. Fetches fresh
+    /// metrics data for the current page before rendering and evaluates
+    /// the alert rules when they are due. Only what the visible page shows
+    /// is refreshed; the summary strip refreshes the process list at most
+    /// every few seconds for its top process. A split view renders the
+    /// pages of both panes side by side.
     ///
     /// # Arguments
     ///
@@ -114,56 +418,452 @@ impl App {
     /// - **Processes**: Lists all running processes with sortable columns
     /// - **SingleProcess**: Detailed view of a specific process
     /// - **Network**: Network interface statistics and traffic
+    /// - **InterfaceDetail**: Addresses and larger charts of one interface
+    /// - **Plugins**: Metrics reported by external plugin executables
+    /// - **Connections**: TCP/UDP sockets with suspicious connections flagged
+    /// - **Cgroups**: Resource usage aggregated per cgroup / systemd unit
+    /// - **Pods**: Kubernetes pods on this node, when a kubelet is detected
+    /// - **VMs**: libvirt virtual machines with start/shutdown actions
+    /// - **Controls**: Audio and Bluetooth devices with their controls
+    /// - **Events**: OOM kills, segfaults and service crashes since boot
+    /// - **Disks**: Disk throughput, latency and queue depth
+    /// - **Sensors**: hwmon temperatures and fan speeds with fan curves
+    /// - **Ports**: Listening sockets with their owning process, filterable
+    /// - **Services**: systemd services with start/stop/restart actions
+    /// - **Logs**: Kernel log or journal tail with a filter box
+    /// - **History**: Stored CPU, memory and network charts, with `--store`
     pub fn render(&mut self, frame: &mut Frame) {
-        let chunks =
-            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(frame.area());
+        let _span = tracing::debug_span!("render", page = ?self.current_page).entered();
+        let chunks = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
+        // with several hosts a sidebar lists them left of the page
+        let content = if self.remotes.len() > 1 {
+            let [sidebar, content] =
+                Layout::horizontal([Constraint::Length(HOST_SIDEBAR_WIDTH), Constraint::Min(0)])
+                    .areas(chunks[2]);
+            render_host_sidebar(frame, sidebar, &self.remotes, self.selected_remote);
+            content
+        } else {
+            chunks[2]
+        };
+
+        match self.split.as_ref().map(|split| split.focus) {
+            None => self.render_page(frame, content),
+            Some(focus) => {
+                let [left, right] =
+                    Layout::horizontal([Constraint::Percentage(50); 2]).areas(content);
+                let (focused, other) = match focus {
+                    Pane::Left => (left, right),
+                    Pane::Right => (right, left),
+                };
+                self.render_pane(frame, focused, true);
+                // the other pane renders as the current page, so whatever
+                // its page changes, like following a restarted process,
+                // stays in its pane
+                self.swap_panes();
+                self.render_pane(frame, other, false);
+                self.swap_panes();
+            }
+        }
+
+        if let Some(menu) = self.ui_state.signal_menu.as_mut() {
+            render_signal_menu(frame, menu);
+        }
+        if let Some(editor) = self.ui_state.column_editor.as_mut() {
+            render_column_editor(frame, editor, &self.ui_state.process_table.columns);
+        }
+        if let Some(palette) = self.ui_state.command_palette.as_mut() {
+            render_command_palette(frame, palette);
+        }
+
+        // after the page, so the summary reuses the refresh the page just
+        // made instead of measuring a near zero interval
+        let summary = match self.fetch(MetricsToFetch::Summary) {
+            Some(Metrics::Summary(summary)) => Some(summary),
+            _ => None,
+        };
+
+        // the rules walk every process and socket, far more than a frame
+        // needs, and only see the local machine
+        if !self.is_remote()
+            && self
+                .alerts_evaluated_at
+                .is_none_or(|at| at.elapsed() >= Duration::from_secs(ALERT_EVALUATION_SECS))
+        {
+            self.alerts.evaluate(&self.metrics.sysinfo());
+            self.alerts_evaluated_at = Some(Instant::now());
+        }
+
+        if !self.ui_state.paused
+            && let Some(summary) = &summary
+        {
+            let now = Utc::now();
+            let detector = &mut self.ui_state.anomalies;
+            detector.observe("CPU", now, summary.cpu_usage as f64);
+            detector.observe("Memory", now, summary.memory_percent);
+            detector.observe("Swap", now, summary.swap_percent);
+        }
+
+        if let Some(summary) = &summary {
+            render_summary(frame, chunks[0], summary, self.alerts.active().len());
+        }
+
+        if let Some(error) = self.alerts.take_log_error() {
+            self.errors.report("write alert log", &error.into());
+        }
+
+        // a failing store would fail on every frame, so recording stops
+        // after the first error
+        if !self.ui_state.paused
+            && let Some(recorder) = self.recorder.as_mut()
+            && let Err(err) = recorder.tick()
+        {
+            self.errors.report("record history", &err.into());
+            self.recorder = None;
+        }
 
         // render tabs
-        self.render_tabs(frame, chunks[0]);
+        self.render_tabs(frame, chunks[1]);
+
+        self.ui_state.status_bar.poll();
+        // errors raised while rendering this frame show up right away
+        for error in self.error_log.drain() {
+            self.ui_state
+                .status_bar
+                .error(format!("{}: {}", error.context, error.message));
+        }
+        render_status_bar(frame, chunks[3], &self.ui_state.status_bar, SHORTCUTS);
+        if let Some(command_line) = &self.ui_state.command_line {
+            render_command_line(frame, chunks[3], command_line);
+        }
+        if self.error_log.open {
+            render_error_log(frame, &self.error_log);
+        }
+    }
 
-        match &self.current_page {
+    /// Renders the current page in one pane of the split view, under a
+    /// line naming it, highlighted when the pane has the focus
+    fn render_pane(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        let [header, content] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        let title = Page::titles()
+            .get(self.tab_index)
+            .copied()
+            .unwrap_or_default();
+        let style = if focused {
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme().muted)
+        };
+        frame.render_widget(Line::from(format!(" {title} ")).style(style), header);
+        self.render_page(frame, content);
+    }
+
+    /// Fetches the metrics of the current page and renders it into
+    /// `content`
+    fn render_page(&mut self, frame: &mut Frame, content: Rect) {
+        let context = format!("render {:?} page", self.current_page);
+
+        match self.current_page.clone() {
             Page::Metrics => {
-                if let Metrics::SystemResource(system_collector) =
-                    self.metrics.fetch(MetricsToFetch::SystemResource)
+                if let Some(Metrics::SystemResource(system_collector)) =
+                    self.fetch(MetricsToFetch::SystemResource)
                 {
-                    let _ = system_collector.display(frame, chunks[1], None);
+                    if !self.ui_state.paused
+                        && let Some(rates) = &system_collector.system_metrics.scheduler
+                    {
+                        self.ui_state.scheduler_history.update(rates);
+                        let now = Utc::now();
+                        let detector = &mut self.ui_state.anomalies;
+                        detector.observe("Context switches", now, rates.context_switches);
+                        detector.observe("Interrupts", now, rates.interrupts);
+                        detector.observe("Forks", now, rates.forks);
+                    }
+                    #[cfg(feature = "scripting")]
+                    if let Some(hooks) = &self.script_hooks {
+                        self.ui_state.script_output =
+                            Some(hooks.on_tick(&system_collector.system_metrics));
+                    }
+                    self.errors.check(
+                        &context,
+                        system_collector.display(frame, content, Some(&mut self.ui_state)),
+                    );
                 };
             }
             Page::System => {
-                if let Metrics::SystemInfo(system_info) =
-                    self.metrics.fetch(MetricsToFetch::SystemInfo)
+                if let Some(Metrics::SystemInfo(system_info)) =
+                    self.fetch(MetricsToFetch::SystemInfo)
                 {
-                    let _ = system_info.display(frame, chunks[1], None);
+                    let [left, right] = Layout::horizontal([
+                        Constraint::Percentage(50),
+                        Constraint::Percentage(50),
+                    ])
+                    .areas(content);
+                    let [boot, hardware] =
+                        Layout::vertical([Constraint::Length(12), Constraint::Min(8)]).areas(right);
+                    let layout = Layout::vertical([
+                        Constraint::Min(10),
+                        Constraint::Length(8),
+                        Constraint::Length(6),
+                    ])
+                    .split(left);
+                    self.errors
+                        .check(&context, system_info.display(frame, layout[0], None));
+                    if let Some(Metrics::Sessions(sessions)) = self.fetch(MetricsToFetch::Sessions)
+                    {
+                        render_sessions(frame, layout[1], &sessions);
+                    }
+                    // kept between frames like the boot analysis, it
+                    // shells out to powerprofilesctl
+                    if self.ui_state.power.needs_refresh()
+                        && let Some(Metrics::Power(power)) = self.fetch(MetricsToFetch::Power)
+                    {
+                        self.ui_state.power.update(power);
+                    }
+                    if self.ui_state.power.fetched_at.is_some() {
+                        let power = self.ui_state.power.metrics.clone();
+                        self.errors.check(
+                            &context,
+                            power.display(frame, layout[2], Some(&mut self.ui_state)),
+                        );
+                    }
+                    // both are read from the local machine
+                    if !self.is_remote() {
+                        if self.ui_state.hardware.needs_refresh() {
+                            self.ui_state.hardware.refresh();
+                        }
+                        let boot_analysis = self.ui_state.boot.get_or_insert_with(|| {
+                            BootAnalysis::fetch().map_err(|err| format!("{err:#}"))
+                        });
+                        render_boot_analysis(frame, boot, boot_analysis);
+                        render_hardware(frame, hardware, &mut self.ui_state.hardware);
+                    }
                 };
             }
             Page::Processes => {
-                if let Metrics::Processes(processes) = self.metrics.fetch(MetricsToFetch::Process) {
+                if let Some(Metrics::Processes(processes)) = self.fetch(MetricsToFetch::Process) {
                     self.ui_state.process_table.process_count = processes.len();
-                    let _ = processes.display(frame, chunks[1], Some(&mut self.ui_state));
+                    if !self.ui_state.paused {
+                        self.ui_state
+                            .process_table
+                            .lifetimes
+                            .update(&processes, Utc::now());
+                    }
+                    self.errors.check(
+                        &context,
+                        processes.display(frame, content, Some(&mut self.ui_state)),
+                    );
                 }
             }
             Page::SingleProcess(pid) => {
-                let total_memory = bytes_to_mb(self.metrics.system.total_memory());
-                if let Metrics::SingleProcessPid(Some(process)) =
-                    self.metrics.fetch(MetricsToFetch::SingleProcessPid(*pid))
-                {
-                    self.ui_state
-                        .single_process_disk_usage
-                        .update_disk_history(process.basic_process_data.pid, &process.disk_usage);
+                let total_memory = self.metrics.sysinfo().total_memory();
+                let process = match self.fetch(MetricsToFetch::SingleProcessPid(pid)) {
+                    Some(Metrics::SingleProcessPid(Some(process))) => Some(process),
+                    Some(Metrics::SingleProcessPid(None)) if !self.ui_state.paused => {
+                        self.follow_restart(pid);
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(process) = process {
+                    if let Some(follow) = self.ui_state.follow.as_mut() {
+                        follow.observe(&process.basic_process_data);
+                    }
+                    if !self.ui_state.paused {
+                        self.ui_state.single_process_disk_usage.update_disk_history(
+                            process.basic_process_data.pid,
+                            &process.disk_usage,
+                        );
+                        self.ui_state.single_process_usage.update_usage_history(
+                            process.basic_process_data.pid,
+                            process.basic_process_data.cpu_usage,
+                            process.basic_process_data.memory,
+                        );
+                    }
 
-                    let _ = SingleProcessUI { data: process }.display_process_metrics(
+                    self.errors.check(
+                        &context,
+                        SingleProcessUI { data: *process }.display_process_metrics(
+                            frame,
+                            content,
+                            total_memory,
+                            &mut self.ui_state,
+                        ),
+                    );
+                }
+            }
+            Page::Network => {
+                if !self.ui_state.paused {
+                    self.ui_state.crash_events.refresh_if_stale();
+                }
+                if let Some(Metrics::Networks(network_metrics)) =
+                    self.fetch(MetricsToFetch::Networks)
+                {
+                    self.errors.check(
+                        &context,
+                        network_metrics.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::Plugins => {
+                if let Some(Metrics::Plugins(plugin_metrics)) = self.fetch(MetricsToFetch::Plugins)
+                {
+                    self.errors
+                        .check(&context, plugin_metrics.display(frame, content, None));
+                }
+            }
+            Page::Connections => {
+                if let Some(Metrics::Connections(connections)) =
+                    self.fetch(MetricsToFetch::Connections)
+                {
+                    self.errors.check(
+                        &context,
+                        connections.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::Cgroups => {
+                if let Some(Metrics::Cgroups(cgroups)) = self.fetch(MetricsToFetch::Cgroups) {
+                    self.errors.check(
+                        &context,
+                        cgroups.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::Pods => {
+                if let Some(Metrics::Pods(pods)) = self.fetch(MetricsToFetch::Pods) {
+                    self.errors.check(
+                        &context,
+                        pods.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::Vms => {
+                if let Some(Metrics::Vms(vms)) = self.fetch(MetricsToFetch::Vms) {
+                    self.errors.check(
+                        &context,
+                        vms.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::InterfaceDetail(name) => {
+                if let Some(Metrics::Networks(network_metrics)) =
+                    self.fetch(MetricsToFetch::Networks)
+                {
+                    render_interface_detail(
                         frame,
-                        chunks[1],
-                        total_memory,
+                        content,
+                        &network_metrics,
+                        &name,
                         &mut self.ui_state,
                     );
                 }
             }
-            Page::Network => {
-                if let Metrics::Networks(network_metrics) =
-                    self.metrics.fetch(MetricsToFetch::Networks)
+            Page::Disks => {
+                if let Some(Metrics::Disks(disks)) = self.fetch(MetricsToFetch::Disks) {
+                    self.errors.check(
+                        &context,
+                        disks.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::Services => {
+                if let Some(Metrics::Services(services)) = self.fetch(MetricsToFetch::Services) {
+                    self.errors.check(
+                        &context,
+                        services.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::Logs => {
+                if self.ui_state.logs.needs_refresh()
+                    && let Some(Metrics::Logs(logs)) = self.fetch(MetricsToFetch::Logs)
                 {
-                    let _ = network_metrics.display(frame, chunks[1], Some(&mut self.ui_state));
+                    self.ui_state.logs.metrics = Some(logs);
+                }
+                render_logs(frame, content, &mut self.ui_state.logs);
+            }
+            Page::Ports => {
+                if let Some(Metrics::Connections(connections)) =
+                    self.fetch(MetricsToFetch::Connections)
+                {
+                    render_listening_ports(frame, content, &connections, &mut self.ui_state.ports);
+                }
+            }
+            Page::Sensors => {
+                if let Some(Metrics::Hwmon(hwmon)) = self.fetch(MetricsToFetch::Hwmon) {
+                    self.errors.check(
+                        &context,
+                        hwmon.display(frame, content, Some(&mut self.ui_state)),
+                    );
+                }
+            }
+            Page::Events => {
+                if !self.is_remote() {
+                    if !self.ui_state.paused {
+                        self.ui_state.crash_events.refresh_if_stale();
+                    }
+                    render_crash_events(frame, content, &mut self.ui_state.crash_events);
+                }
+            }
+            Page::History => {
+                let unavailable = if self.is_remote() {
+                    Some("History is only recorded for the local machine")
+                } else if self.recorder.is_none() {
+                    Some("History isn't recorded, start stomata with --store to keep it")
+                } else {
+                    None
+                };
+                if let Some(recorder) = &self.recorder
+                    && self.ui_state.history.needs_refresh()
+                {
+                    self.ui_state.history.refresh(recorder.store());
+                }
+                render_history(frame, content, &self.ui_state.history, unavailable);
+            }
+            Page::Hosts => {
+                let rows: Vec<HostRow> = self
+                    .remotes
+                    .iter_mut()
+                    .map(|remote| {
+                        // every host is polled on its own threads, so a
+                        // down one only leaves its row empty
+                        let summary = match remote.latest(MetricsToFetch::Summary) {
+                            Some(Ok(Metrics::Summary(summary))) => Some(summary),
+                            _ => None,
+                        };
+                        HostRow {
+                            hostname: remote.hostname(),
+                            address: remote.address().to_string(),
+                            summary,
+                        }
+                    })
+                    .collect();
+                render_host_grid(frame, content, &rows, &mut self.ui_state.hosts_table);
+            }
+            Page::Controls => {
+                let layout =
+                    Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .split(content);
+                if let Some(Metrics::Audio(audio)) = self.fetch(MetricsToFetch::Audio) {
+                    self.errors.check(
+                        &context,
+                        audio.display(frame, layout[0], Some(&mut self.ui_state)),
+                    );
+                }
+                if let Some(Metrics::Bluetooth(bluetooth)) = self.fetch(MetricsToFetch::Bluetooth) {
+                    self.errors.check(
+                        &context,
+                        bluetooth.display(frame, layout[1], Some(&mut self.ui_state)),
+                    );
                 }
             }
         }
@@ -172,7 +872,9 @@ impl App {
     /// Renders the tab bar at the top of the screen
     ///
     /// Displays all available pages as tabs with the current tab highlighted
-    /// in green and bold.
+    /// in green and bold. The number of active alerts is shown in red in
+    /// the border title, next to a "PAUSED" badge while collection is paused
+    /// and an "AWAKE" badge while the sleep inhibitor is held.
     ///
     /// # Arguments
     ///
@@ -180,13 +882,54 @@ impl App {
     /// * `area` - The rectangular area to render the tabs in
     pub fn render_tabs(&self, frame: &mut Frame, area: Rect) {
         let titles: Vec<Line> = Page::titles().iter().map(|t| Line::from(*t)).collect();
+        let mut block = Block::default().borders(Borders::ALL).title("Stomata");
+        if let Some(remote) = self.remotes.get(self.selected_remote) {
+            block = block.title(
+                Line::from(format!(" {} ({}) ", remote.hostname(), remote.address()))
+                    .style(Style::default().fg(theme().accent)),
+            );
+        }
+        if self.ui_state.paused {
+            block = block.title(
+                Line::from(" PAUSED (space to resume) ")
+                    .style(
+                        Style::default()
+                            .fg(theme().warning)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .centered(),
+            );
+        }
+        if self.sleep_inhibitor.is_some() {
+            block = block.title(
+                Line::from(" AWAKE: sleep inhibited (w to release) ")
+                    .style(Style::default().fg(theme().ok).add_modifier(Modifier::BOLD)),
+            );
+        } else if let Some(error) = &self.sleep_inhibitor_error {
+            block = block.title(
+                Line::from(format!(" sleep inhibitor failed: {error} "))
+                    .style(Style::default().fg(theme().warning)),
+            );
+        }
+        let alert_count = self.alerts.active().len();
+        if alert_count > 0 {
+            block = block.title(
+                Line::from(format!(" {alert_count} alert(s) "))
+                    .style(
+                        Style::default()
+                            .fg(theme().critical)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .right_aligned(),
+            );
+        }
         let tabs = Tabs::new(titles)
-            .block(Block::default().borders(Borders::ALL).title("Stomata"))
+            .block(block)
             .select(self.tab_index)
-            .style(Style::default().fg(Color::White))
+            .style(Style::default().fg(theme().text))
             .highlight_style(
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme().accent)
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -198,6 +941,8 @@ impl App {
     /// Processes both global keyboard shortcuts (navigation, quit) and
     /// page-specific shortcuts (e.g., process list navigation). Only
     /// key press events are processed; key release events are ignored.
+    /// Pages with text inputs get the key first so typing doesn't trigger
+    /// global shortcuts.
     ///
     /// # Arguments
     ///
@@ -208,11 +953,151 @@ impl App {
     /// Returns an error if event processing fails (currently always returns `Ok`).
     pub fn handle_events(&mut self, key: KeyEvent) -> anyhow::Result<()> {
         if key.kind == KeyEventKind::Press {
+            if let Some(recorder) = self.recorder.as_mut() {
+                recorder.record_input();
+            }
+            if self.error_log.open {
+                if matches!(key.code, KeyCode::Esc | KeyCode::Char('!')) {
+                    self.error_log.open = false;
+                }
+                return Ok(());
+            }
+
+            if let Some(palette) = self.ui_state.command_palette.as_mut() {
+                match palette.handle_key(key) {
+                    PaletteOutcome::Open => {}
+                    PaletteOutcome::Cancelled => self.ui_state.command_palette = None,
+                    PaletteOutcome::Run(action) => {
+                        self.ui_state.command_palette = None;
+                        self.run_palette_action(action);
+                    }
+                }
+                return Ok(());
+            }
+            // before the pages, which would take it for their own `p`
+            if key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                let mut themes: Vec<String> =
+                    BUILTIN_THEMES.iter().map(|name| name.to_string()).collect();
+                themes.extend(config().themes.keys().cloned());
+                self.ui_state.command_palette = Some(CommandPalette::new(&themes));
+                return Ok(());
+            }
+
+            if let Some(command_line) = self.ui_state.command_line.as_mut() {
+                match command_line.handle_key(key) {
+                    CommandLineOutcome::Open => {}
+                    CommandLineOutcome::Cancelled => self.ui_state.command_line = None,
+                    CommandLineOutcome::Run(command) => {
+                        self.ui_state.command_line = None;
+                        match command {
+                            Ok(command) => self.run_command(command),
+                            Err(message) => self.ui_state.status_bar.error(message),
+                        }
+                    }
+                }
+                return Ok(());
+            }
+
+            let key = if self.is_typing() {
+                key
+            } else {
+                config().keymap.translate(key)
+            };
+
+            if let Some(menu) = self.ui_state.signal_menu.as_mut() {
+                match menu.handle_key(key) {
+                    SignalMenuOutcome::Open => {}
+                    SignalMenuOutcome::Cancelled => self.ui_state.signal_menu = None,
+                    SignalMenuOutcome::Send(signal) => {
+                        let pid = menu.pid;
+                        self.ui_state.signal_menu = None;
+                        self.signal_process(pid, signal);
+                    }
+                }
+                return Ok(());
+            }
+
+            if let Some(editor) = self.ui_state.column_editor.as_mut() {
+                if !editor.handle_key(key, &mut self.ui_state.process_table.columns) {
+                    self.ui_state.column_editor = None;
+                }
+                return Ok(());
+            }
+
+            // before the pages, which take the arrows for their own
+            if self.split.is_some()
+                && key.modifiers.contains(KeyModifiers::CONTROL)
+                && let Some(pane) = match key.code {
+                    KeyCode::Left => Some(Pane::Left),
+                    KeyCode::Right => Some(Pane::Right),
+                    _ => None,
+                }
+            {
+                self.focus_pane(pane);
+                return Ok(());
+            }
+
+            let consumed = match self.current_page {
+                Page::Network => self.process_network_events(key),
+                Page::InterfaceDetail(_) if key.code == KeyCode::Esc => {
+                    self.current_page = Page::Network;
+                    true
+                }
+                Page::Hosts => self.process_hosts_events(key),
+                Page::Processes => self.ui_state.process_table.handle_search_key(key),
+                // the page actions would act on the local machine
+                _ if self.is_remote() => false,
+                Page::SingleProcess(pid) => self.process_single_process_events(key, pid),
+                Page::Vms => self.ui_state.vms.handle_key(
+                    key,
+                    &self.metrics.vms,
+                    &mut self.ui_state.status_bar,
+                ),
+                Page::Controls => self.process_controls_events(key),
+                Page::Events => self.ui_state.crash_events.handle_key(key),
+                Page::Disks => self
+                    .ui_state
+                    .disks
+                    .handle_key(key, &mut self.ui_state.status_bar),
+                Page::Ports => self.ui_state.ports.handle_key(key),
+                Page::Services => self
+                    .ui_state
+                    .services
+                    .handle_key(key, &mut self.ui_state.status_bar),
+                Page::Logs => self.ui_state.logs.handle_key(key),
+                Page::History => self.ui_state.history.handle_key(key),
+                Page::System => {
+                    self.ui_state.power.handle_key(key) || self.ui_state.hardware.handle_key(key)
+                }
+                _ => false,
+            };
+            if consumed {
+                return Ok(());
+            }
+
             self.process_global_events(key);
             match self.current_page {
-                Page::Processes => {
-                    self.process_page_events(key);
-                }
+                Page::Processes => self.process_page_events(key),
+                Page::Connections => match key.code {
+                    KeyCode::Down => self.ui_state.connections_table.select_next(),
+                    KeyCode::Up => self.ui_state.connections_table.select_previous(),
+                    _ => {}
+                },
+                Page::Cgroups => match key.code {
+                    KeyCode::Down => self.ui_state.cgroups_table.select_next(),
+                    KeyCode::Up => self.ui_state.cgroups_table.select_previous(),
+                    _ => {}
+                },
+                Page::Sensors => match key.code {
+                    KeyCode::Down => self.ui_state.fans_table.select_next(),
+                    KeyCode::Up => self.ui_state.fans_table.select_previous(),
+                    _ => {}
+                },
+                Page::Pods => match key.code {
+                    KeyCode::Down => self.ui_state.pods_table.select_next(),
+                    KeyCode::Up => self.ui_state.pods_table.select_previous(),
+                    _ => {}
+                },
                 _ => {}
             }
         }
@@ -224,12 +1109,18 @@ impl App {
     /// # Keybindings
     ///
     /// - `q` - Quit the application
+    /// - `Space` - Pause or resume metrics collection
+    /// - `w` - Take or release the sleep inhibitor
+    /// - `[`/`]` - Show the previous or next host, with several agents
+    ///   connected
+    /// - `H` - Open the Hosts grid, with several agents connected
+    /// - `b` - Switch byte counts between binary (GiB) and SI (GB) units
+    /// - `|` - Split the view to show two pages side by side, or close the
+    ///   split; `Ctrl+Left`/`Ctrl+Right` move the focus between the panes
     /// - `Tab` or `Right Arrow` - Next tab
     /// - `Left Arrow` - Previous tab
-    /// - `1` - Jump to System page
-    /// - `2` - Jump to Metrics page
-    /// - `3` - Jump to Processes page
-    /// - `4` - Jump to Network page
+    /// - `1`-`9` - Jump to the page of that tab (System, Metrics, Processes,
+    ///   Network, Plugins, Connections, Cgroups, Pods, VMs)
     ///
     /// # Arguments
     ///
@@ -239,27 +1130,53 @@ impl App {
             KeyCode::Char('q') => {
                 self.render = false;
             }
+            KeyCode::Char(' ') if self.is_remote() => {
+                self.ui_state
+                    .status_bar
+                    .info("Pausing isn't available on a remote host");
+            }
+            KeyCode::Char(' ') => {
+                if self.metrics.is_paused() {
+                    self.metrics.resume();
+                } else {
+                    self.metrics.pause();
+                }
+                self.ui_state.paused = self.metrics.is_paused();
+            }
+            KeyCode::Char('w') => self.toggle_sleep_inhibitor(),
+            KeyCode::Char('[') if self.remotes.len() > 1 => {
+                let count = self.remotes.len();
+                self.select_remote((self.selected_remote + count - 1) % count);
+            }
+            KeyCode::Char(']') if self.remotes.len() > 1 => {
+                self.select_remote((self.selected_remote + 1) % self.remotes.len());
+            }
+            KeyCode::Char('H') if self.remotes.len() > 1 => self.current_page = Page::Hosts,
+            KeyCode::Char('b') => {
+                let system = unit_system().toggled();
+                set_unit_system(system);
+                self.ui_state.status_bar.info(match system {
+                    UnitSystem::Binary => "Showing binary units (KiB, MiB, GiB)",
+                    UnitSystem::Si => "Showing SI units (kB, MB, GB)",
+                });
+            }
+            KeyCode::Char('!') => self.error_log.open = true,
+            KeyCode::Char('|') => self.toggle_split(),
+            KeyCode::Char(':') if config().keymap.has_command_line() => {
+                self.ui_state.command_line = Some(CommandLine::default());
+            }
             KeyCode::Right | KeyCode::Tab => {
                 self.next_tab();
             }
             KeyCode::Left => {
                 self.previous_tab();
             }
-            KeyCode::Char('1') => {
-                self.tab_index = 0;
-                self.current_page = Page::System;
-            }
-            KeyCode::Char('2') => {
-                self.tab_index = 1;
-                self.current_page = Page::Metrics;
-            }
-            KeyCode::Char('3') => {
-                self.tab_index = 2;
-                self.current_page = Page::Processes;
-            }
-            KeyCode::Char('4') => {
-                self.tab_index = 3;
-                self.current_page = Page::Network;
+            KeyCode::Char(digit @ '1'..='9') => {
+                let index = digit as usize - '1' as usize;
+                if index < Page::titles().len() {
+                    self.tab_index = index;
+                    self.current_page = Page::from_index(index);
+                }
             }
             _ => {}
         }
@@ -274,38 +1191,503 @@ impl App {
     ///
     /// - `Up Arrow` - Select previous process in the list
     /// - `Down Arrow` - Select next process in the list
-    /// - `Enter` - Open detailed view for the selected process
+    /// - `PageUp`/`PageDown` - Move the selection by a screen of rows
+    /// - `Home`/`gg` - Select the first row
+    /// - `End`/`G` - Select the last row
+    /// - `Enter` - Open detailed view for the selected process, or expand
+    ///   or collapse the selected group
+    /// - `c` - Toggle the full path of the program in the command column
+    /// - `u` - Group by user, then by name, then show the flat list again
+    /// - `C` - Show or hide the Container column
+    /// - `f` - Filter to the cgroup of the selected process, or clear the
+    ///   filter
+    /// - `z` - List only zombies, then only orphans, then every process
+    /// - `t` - Sort by PID, CPU, memory, swap, disk reads or disk writes
+    /// - `S` - Show or hide the Swap column
+    /// - `v` - Open the column editor
+    /// - `/` - Search by name or command line, handled before the global
+    ///   shortcuts so typing doesn't trigger them
+    /// - `h`/`l` - Scroll the columns left or right, the PID column stays
+    ///   in place
+    /// - `s` - Open the signals menu for the selected process
+    /// - `x` - Export the process list to a CSV file
     ///
     /// # Arguments
     ///
     /// * `key` - The keyboard event to process
     fn process_page_events(&mut self, key: KeyEvent) {
-        let max_processes = self.ui_state.process_table.process_count;
+        if self.ui_state.process_table.navigate(key.code) {
+            return;
+        }
         match key.code {
-            KeyCode::Down => {
-                if let Some(selected_row) = self.ui_state.process_table.process_list.selected() {
-                    let next_row = (selected_row + 1).min(max_processes.saturating_sub(1));
-                    self.ui_state
-                        .process_table
-                        .process_list
-                        .select(Some(next_row));
+            KeyCode::Enter if self.ui_state.process_table.toggle_selected_group() => {}
+            KeyCode::Enter if self.is_remote() => {
+                self.ui_state
+                    .status_bar
+                    .info("Process details only work on the local machine");
+            }
+            KeyCode::Enter => {
+                if let Some(selected_process_pid) = self.ui_state.process_table.selected_pid {
+                    self.ui_state.status_bar.clear();
+                    self.current_page = Page::SingleProcess(selected_process_pid);
+                }
+            }
+            KeyCode::Char('c') => {
+                self.ui_state.process_table.full_command =
+                    !self.ui_state.process_table.full_command;
+            }
+            KeyCode::Char('s') => {
+                if let Some(pid) = self.ui_state.process_table.selected_pid {
+                    self.ui_state.signal_menu = Some(SignalMenu::new(pid));
+                }
+            }
+            KeyCode::Char('u') => self.ui_state.process_table.cycle_group_by(),
+            KeyCode::Char('C') => self
+                .ui_state
+                .process_table
+                .columns
+                .toggle(ProcessColumn::Container),
+            KeyCode::Char('f') => self.ui_state.process_table.toggle_cgroup_filter(),
+            KeyCode::Char('z') => self.ui_state.process_table.cycle_state_filter(),
+            KeyCode::Char('t') => self.ui_state.process_table.cycle_sort(),
+            KeyCode::Char('S') => self
+                .ui_state
+                .process_table
+                .columns
+                .toggle(ProcessColumn::Swap),
+            KeyCode::Char('v') => self.ui_state.column_editor = Some(ColumnEditor::default()),
+            KeyCode::Char('h') => {
+                let table = &mut self.ui_state.process_table;
+                table.horizontal_scroll = table.horizontal_scroll.saturating_sub(1);
+            }
+            KeyCode::Char('l') => self.ui_state.process_table.horizontal_scroll += 1,
+            KeyCode::Char('x') => self.export_processes(),
+            _ => {}
+        }
+    }
+
+    /// Looks for the process that replaced the followed process `pid`
+    /// after it exited, and shows it instead. The charts keep their
+    /// history, with a marker at the restart.
+    fn follow_restart(&mut self, pid: u32) {
+        let Some(follow) = self.ui_state.follow.as_ref() else {
+            return;
+        };
+        if follow.pid != pid || !follow.is_known() {
+            return;
+        }
+        let (name, exe_path) = (follow.name.clone(), follow.exe_path.clone());
+        let Some(Metrics::Processes(processes)) = self.fetch(MetricsToFetch::Process) else {
+            return;
+        };
+        let Some(successor) = find_successor(&processes, pid, &name, exe_path.as_deref()) else {
+            return;
+        };
+        let new_pid = successor.pid;
+        if let Some(follow) = self.ui_state.follow.as_mut() {
+            follow.restarted(new_pid, Utc::now());
+        }
+        self.ui_state.single_process_disk_usage.reattach(new_pid);
+        self.ui_state.single_process_usage.reattach(new_pid);
+        self.current_page = Page::SingleProcess(new_pid);
+        self.ui_state
+            .status_bar
+            .info(format!("{name} restarted, following PID {new_pid}"));
+    }
+
+    /// Whether a text input has focus, so keys are typed instead of being
+    /// translated by the keymap profile
+    fn is_typing(&self) -> bool {
+        let typing = self.ui_state.process_table.searching
+            || self.ui_state.logs.editing
+            || self.ui_state.ports.editing;
+        #[cfg(feature = "web3")]
+        let typing = typing || self.ui_state.expected_hash_input.is_some();
+        typing
+    }
+
+    /// Sends `signal` to `pid` on the shown host, through the agent when
+    /// connected to one, and reports the outcome in the status bar. Agents
+    /// only signal for a token with the control scope.
+    fn signal_process(&mut self, pid: u32, signal: Signal) {
+        if let Some(remote) = self.remotes.get(self.selected_remote) {
+            // the agent may take seconds to answer, or the client may be
+            // busy polling it
+            let client = remote.client();
+            let text = format!("Sending {} to {pid}…", signal.as_str());
+            self.ui_state.status_bar.run_in_background(text, move || {
+                client
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .signal(pid, signal)
+                    .map(|()| format!("Sent {} to {pid}", signal.as_str()))
+                    .map_err(|err| format!("{err:#}"))
+            });
+            return;
+        }
+        self.ui_state
+            .status_bar
+            .report(match send_signal(pid, signal) {
+                Ok(()) => Ok(format!("Sent {} to {pid}", signal.as_str())),
+                Err(err) => Err(format!("{err:#}")),
+            });
+    }
+
+    /// Runs a command entered on the command line, reporting the result in
+    /// the status bar
+    fn run_command(&mut self, command: Command) {
+        match command {
+            Command::Kill { pid, signal } => self.signal_process(pid, signal),
+            Command::Sort(sort) => {
+                self.ui_state.process_table.sort = sort;
+                self.ui_state
+                    .status_bar
+                    .info(format!("Processes sorted by {}", sort.label()));
+            }
+            Command::Group(by) => {
+                let table = &mut self.ui_state.process_table;
+                table.group_by = by;
+                table.expanded_groups.clear();
+                table.select_row(0);
+            }
+            Command::Export => self.export_processes(),
+            Command::Quit => self.render = false,
+        }
+    }
+
+    /// The process selected on the Processes page, for the palette actions
+    /// signalling it. `None`, with the reason in the status bar, on any
+    /// other page or when the process exited since the last refresh.
+    fn selected_running_process(&mut self) -> Option<u32> {
+        let pid = match (&self.current_page, self.ui_state.process_table.selected_pid) {
+            (Page::Processes, Some(pid)) => pid,
+            _ => {
+                self.ui_state
+                    .status_bar
+                    .info("Select a process on the Processes page first");
+                return None;
+            }
+        };
+        match self.fetch(MetricsToFetch::Process) {
+            Some(Metrics::Processes(processes))
+                if processes.iter().any(|process| process.pid == pid) =>
+            {
+                Some(pid)
+            }
+            Some(Metrics::Processes(_)) => {
+                self.ui_state
+                    .status_bar
+                    .error(format!("Process {pid} no longer exists"));
+                None
+            }
+            _ => {
+                self.ui_state.status_bar.error(format!(
+                    "Could not check that process {pid} is still running"
+                ));
+                None
+            }
+        }
+    }
+
+    /// Runs an action chosen in the command palette
+    fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::Page(index) => {
+                self.tab_index = index;
+                self.current_page = Page::from_index(index);
+            }
+            PaletteAction::Signal(signal) => {
+                if let Some(pid) = self.selected_running_process() {
+                    self.ui_state.signal_menu = Some(SignalMenu::new(pid).with_selected(signal));
+                }
+            }
+            PaletteAction::SignalMenu => {
+                if let Some(pid) = self.selected_running_process() {
+                    self.ui_state.signal_menu = Some(SignalMenu::new(pid));
                 }
             }
-            KeyCode::Up => {
-                if let Some(selected_row) = self.ui_state.process_table.process_list.selected() {
-                    let next_row = selected_row.saturating_sub(1);
+            PaletteAction::Columns => {
+                self.ui_state.column_editor = Some(ColumnEditor::default());
+            }
+            PaletteAction::Split => self.toggle_split(),
+            PaletteAction::Theme(name) => match Theme::resolve(Some(&name), config()) {
+                Ok(theme) => {
+                    set_theme(theme);
                     self.ui_state
-                        .process_table
-                        .process_list
-                        .select(Some(next_row));
+                        .status_bar
+                        .info(format!("Using the {name} theme"));
+                }
+                Err(err) => self.ui_state.status_bar.error(format!("{err:#}")),
+            },
+            PaletteAction::Command(command) => self.run_command(command),
+        }
+    }
+
+    /// Writes the process list, in the order of the table, to a
+    /// timestamped CSV file in the working directory and reports the path
+    /// in the status bar
+    fn export_processes(&mut self) {
+        let Some(Metrics::Processes(mut processes)) = self.fetch(MetricsToFetch::Process) else {
+            return;
+        };
+        self.ui_state.process_table.sort.sort(&mut processes);
+        let path = process_export::default_path();
+        match process_export::export(&path, &processes) {
+            Ok(()) => self.ui_state.status_bar.info(format!(
+                "Exported {} processes to {}",
+                processes.len(),
+                path.display()
+            )),
+            Err(err) => self.errors.report("export processes", &err),
+        }
+    }
+
+    /// Processes page-specific keyboard events for the Network page
+    ///
+    /// # Keybindings (Network page only)
+    ///
+    /// - `Up`/`Down` - Select the previous or next interface
+    /// - `Enter` - Open the detail page of the selected interface
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was consumed and should not be handled as a
+    /// global shortcut.
+    fn process_network_events(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Down => self.ui_state.network_table.select_next(),
+            KeyCode::Up => self.ui_state.network_table.select_previous(),
+            KeyCode::Enter => {
+                if let Some(name) = self
+                    .ui_state
+                    .network_table
+                    .list
+                    .selected()
+                    .and_then(|index| self.ui_state.network_names.get(index))
+                {
+                    self.current_page = Page::InterfaceDetail(name.clone());
                 }
             }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Processes keyboard events of the Hosts grid
+    ///
+    /// # Keybindings (Hosts page only)
+    ///
+    /// - `Up Arrow`/`Down Arrow` - Select a host
+    /// - `Enter` - Show the selected host on the page of the current tab
+    /// - `Esc` - Back to the page of the current tab
+    fn process_hosts_events(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Down => self.ui_state.hosts_table.select_next(),
+            KeyCode::Up => self.ui_state.hosts_table.select_previous(),
             KeyCode::Enter => {
-                if let Some(selected_process_pid) = self.ui_state.process_table.selected_pid {
-                    self.current_page = Page::SingleProcess(selected_process_pid);
+                if let Some(index) = self.ui_state.hosts_table.list.selected() {
+                    self.select_remote(index);
                 }
+                self.current_page = Page::from_index(self.tab_index);
             }
-            _ => {}
+            KeyCode::Esc => self.current_page = Page::from_index(self.tab_index),
+            _ => return false,
         }
+        true
+    }
+
+    /// Processes page-specific keyboard events for the SingleProcess page
+    ///
+    /// # Keybindings (SingleProcess page only)
+    ///
+    /// - `h` - Hash the process executable with SHA-256, with the `web3`
+    ///   feature
+    /// - `H` - Enter an expected SHA-256 digest to compare against, with
+    ///   the `web3` feature
+    /// - `e` - Show or hide the environment variables panel
+    /// - `o` - Show the open files panel
+    /// - `m` - Show or hide the memory breakdown panel
+    /// - `f` - Follow the process across restarts, or stop following it
+    /// - `+`/`-` - Raise or lower the nice value by one
+    /// - `s` - Open the signals menu
+    /// - `a` - Open the CPU affinity editor
+    /// - `Up`/`Down` - Select a thread in the tasks table
+    /// - `t` - Sort the tasks table by the next column
+    /// - `Enter` - Show the details of the selected thread
+    ///
+    /// While the expected digest input is open every key goes to it:
+    /// `Enter` compares, `Esc` cancels, and likewise to the affinity editor
+    /// while it is open. While the environment or open files
+    /// panel is open it handles the keys it knows first, `Esc` closes the
+    /// memory breakdown panel. Only one of the three panels is open at a
+    /// time.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The keyboard event to process
+    /// * `pid` - PID of the displayed process
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was consumed and should not be handled as a
+    /// global shortcut.
+    fn process_single_process_events(&mut self, key: KeyEvent, pid: u32) -> bool {
+        #[cfg(feature = "web3")]
+        if let Some(input) = self.ui_state.expected_hash_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.ui_state.expected_hash_input = None,
+                KeyCode::Enter => {
+                    let expected = input.value.trim().to_string();
+                    self.ui_state.expected_hash_input = None;
+                    self.hash_executable(pid);
+                    if let Some(checksum) = self.ui_state.exe_checksum.as_mut()
+                        && !expected.is_empty()
+                    {
+                        checksum.expected = Some(expected);
+                    }
+                }
+                _ => {
+                    input.handle_key(key);
+                }
+            }
+            return true;
+        }
+        if let Some(editor) = self.ui_state.affinity_editor.as_mut() {
+            match editor.handle_key(key) {
+                AffinityEditorOutcome::Open => {}
+                AffinityEditorOutcome::Cancelled => self.ui_state.affinity_editor = None,
+                AffinityEditorOutcome::Applied(result) => {
+                    self.ui_state.status_bar.report(result);
+                    self.ui_state.affinity_editor = None;
+                }
+            }
+            return true;
+        }
+        if self.ui_state.process_env.open && self.ui_state.process_env.handle_key(key) {
+            return true;
+        }
+        if self.ui_state.open_files.open && self.ui_state.open_files.handle_key(key) {
+            return true;
+        }
+        if self.ui_state.memory_breakdown_open && key.code == KeyCode::Esc {
+            self.ui_state.memory_breakdown_open = false;
+            return true;
+        }
+        if self.ui_state.tasks.handle_key(key) {
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Char('e') => {
+                self.ui_state.open_files.open = false;
+                self.ui_state.memory_breakdown_open = false;
+                self.ui_state.process_env.toggle(pid);
+            }
+            KeyCode::Char('o') => {
+                self.ui_state.process_env.open = false;
+                self.ui_state.memory_breakdown_open = false;
+                self.ui_state.open_files.open = true;
+            }
+            KeyCode::Char('f') => {
+                let following = self
+                    .ui_state
+                    .follow
+                    .as_ref()
+                    .is_some_and(|follow| follow.pid == pid);
+                self.ui_state.follow = if following {
+                    None
+                } else {
+                    Some(FollowTarget::new(pid))
+                };
+            }
+            KeyCode::Char('m') => {
+                self.ui_state.process_env.open = false;
+                self.ui_state.open_files.open = false;
+                self.ui_state.memory_breakdown_open = !self.ui_state.memory_breakdown_open;
+            }
+            KeyCode::Char('s') => self.ui_state.signal_menu = Some(SignalMenu::new(pid)),
+            KeyCode::Char('a') => match get_affinity(pid) {
+                Ok(current) => {
+                    let cpu_count = self.metrics.sysinfo().cpu_count();
+                    self.ui_state.affinity_editor =
+                        Some(AffinityEditor::new(pid, cpu_count, &current));
+                }
+                Err(err) => self.ui_state.status_bar.error(format!("{err:#}")),
+            },
+            KeyCode::Char(sign @ ('+' | '-')) => {
+                let step = if sign == '+' { 1 } else { -1 };
+                self.ui_state.status_bar.report(
+                    match get_priority(pid).and_then(|nice| {
+                        set_priority(pid, nice + step)?;
+                        Ok(nice + step)
+                    }) {
+                        Ok(nice) => Ok(format!("Reniced to {nice}")),
+                        Err(err) => Err(format!("Renice failed: {err:#}")),
+                    },
+                );
+            }
+            #[cfg(feature = "web3")]
+            KeyCode::Char('h') => {
+                self.ui_state.exe_checksum = None;
+                self.hash_executable(pid);
+            }
+            #[cfg(feature = "web3")]
+            KeyCode::Char('H') => {
+                self.ui_state.expected_hash_input = Some(TextInput::new("Expected SHA-256", false));
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Processes page-specific keyboard events for the Controls page
+    ///
+    /// `f` moves the focus between the audio and Bluetooth panels, every
+    /// other key goes to the focused panel.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was consumed and should not be handled as a
+    /// global shortcut.
+    fn process_controls_events(&mut self, key: KeyEvent) -> bool {
+        if key.code == KeyCode::Char('f') {
+            self.ui_state.controls_focus = match self.ui_state.controls_focus {
+                ControlsPanel::Audio => ControlsPanel::Bluetooth,
+                ControlsPanel::Bluetooth => ControlsPanel::Audio,
+            };
+            return true;
+        }
+        match self.ui_state.controls_focus {
+            ControlsPanel::Audio => self
+                .ui_state
+                .audio
+                .handle_key(key, self.metrics.audio.refresher()),
+            ControlsPanel::Bluetooth => self.ui_state.bluetooth.handle_key(
+                key,
+                &mut self.ui_state.status_bar,
+                self.metrics.bluetooth.refresher(),
+            ),
+        }
+    }
+
+    /// Starts hashing the executable of `pid` unless it was already
+    /// hashed.
+    #[cfg(feature = "web3")]
+    fn hash_executable(&mut self, pid: u32) {
+        if self
+            .ui_state
+            .exe_checksum
+            .as_ref()
+            .is_some_and(|checksum| checksum.pid == pid)
+        {
+            return;
+        }
+
+        let exe = self
+            .metrics
+            .sysinfo()
+            .process_details(pid)
+            .and_then(|process| process.basic_process_data.exe_path);
+        self.ui_state.exe_checksum = exe.map(|path| ExeChecksum::compute(pid, PathBuf::from(path)));
     }
 }
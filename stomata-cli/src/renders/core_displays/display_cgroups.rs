@@ -0,0 +1,79 @@
+//! Cgroups display implementation
+//!
+//! Renders processes grouped by cgroup, which on systemd hosts maps each
+//! row to a service, scope or slice (`nginx.service`, `docker-<id>.scope`).
+//! CPU, memory and disk I/O are summed over the member processes.
+
+use ratatui::{Frame, layout::Constraint, layout::Rect, widgets::Cell};
+use stomata_core::collectors::cgroups::{CgroupMetrics, CgroupUsage};
+
+use crate::{
+    renders::{core_displays::traits::Display, render_widgets::render_table::render_table},
+    structs::{TableRow, UIState},
+    utils::format_bytes,
+};
+
+/// Implements table row conversion for per-cgroup usage.
+///
+/// # Column Layout
+///
+/// 1. **Unit** (flexible): Unit name, the last cgroup path component
+/// 2. **Procs** (7 chars): Number of member processes
+/// 3. **CPU%** (10 chars): Summed CPU usage
+/// 4. **Memory** (12 chars): Summed resident memory
+/// 5. **Read** (12 chars): Disk bytes read since the last refresh
+/// 6. **Write** (12 chars): Disk bytes written since the last refresh
+/// 7. **Cgroup** (flexible): Full cgroup path
+impl TableRow for CgroupUsage {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![
+            Cell::from(self.unit.as_str()),
+            Cell::from(self.pids.len().to_string()),
+            Cell::from(format!("{:.2}%", self.cpu_usage)),
+            Cell::from(format_bytes(self.memory)),
+            Cell::from(format_bytes(self.disk_read_bytes)),
+            Cell::from(format_bytes(self.disk_written_bytes)),
+            Cell::from(self.path.as_str()),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(24),    // Unit
+            Constraint::Length(7),  // Procs
+            Constraint::Length(10), // CPU%
+            Constraint::Length(12), // Memory
+            Constraint::Length(12), // Read
+            Constraint::Length(12), // Write
+            Constraint::Min(30),    // Cgroup
+        ]
+    }
+}
+
+/// Display implementation for per-cgroup resource usage
+impl Display for CgroupMetrics {
+    /// Renders the cgroups table, busiest cgroup first.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the cgroups page
+    /// * `ui_state` - Table selection state, the table is not rendered
+    ///   without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        let headers = vec!["Unit", "Procs", "CPU", "Memory", "Read", "Write", "Cgroup"];
+        let title = format!("Cgroups ({})", self.cgroups.len());
+        let table = render_table(headers, &self.cgroups, &title);
+
+        if let Some(ui_state) = ui_state {
+            ui_state.cgroups_table.set_count(self.cgroups.len());
+            frame.render_stateful_widget(table, area, &mut ui_state.cgroups_table.list);
+        }
+        Ok(())
+    }
+}
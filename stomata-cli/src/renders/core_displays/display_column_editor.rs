@@ -0,0 +1,91 @@
+//! Column editor display implementation
+//!
+//! Renders the modal listing the columns of the process list, in their
+//! order, with a checkbox for the ones shown.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::Style,
+    widgets::{Cell, Paragraph},
+};
+
+use crate::{
+    features::core::process_columns::{ColumnEditor, ProcessColumn, ProcessColumns},
+    renders::render_widgets::{
+        render_modal::{centered_rect, render_modal},
+        render_table::render_table,
+    },
+    structs::TableRow,
+    theme::theme,
+};
+
+/// A column of the editor list and whether it is shown
+struct ColumnRow {
+    column: ProcessColumn,
+    shown: bool,
+}
+
+/// Implements table row conversion for the editor list.
+///
+/// # Column Layout
+///
+/// 1. **Shown** (5 chars): `[x]` when the column is shown
+/// 2. **Column** (flexible): Header of the column, muted when hidden
+impl TableRow for ColumnRow {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let (mark, style) = if self.shown {
+            ("[x]", Style::default().fg(theme().text))
+        } else {
+            ("[ ]", Style::default().fg(theme().muted))
+        };
+        vec![
+            Cell::from(mark).style(style),
+            Cell::from(self.column.header()).style(style),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(5), // Shown
+            Constraint::Min(12),   // Column
+        ]
+    }
+}
+
+/// Renders the column editor in a modal over the Processes page.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `editor` - The open editor with its selection
+/// * `columns` - The process list columns being edited
+pub fn render_column_editor(
+    frame: &mut Frame,
+    editor: &mut ColumnEditor,
+    columns: &ProcessColumns,
+) {
+    let rows: Vec<ColumnRow> = columns
+        .entries()
+        .iter()
+        .map(|(column, shown)| ColumnRow {
+            column: *column,
+            shown: *shown,
+        })
+        .collect();
+    let height = rows.len() as u16 + 6;
+    let modal_area = centered_rect(frame.area(), 50, height);
+    let inner = render_modal(frame, modal_area, "Process columns");
+    let [table_area, hint] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(inner);
+
+    frame.render_stateful_widget(
+        render_table(vec!["Shown", "Column"], &rows, "Columns"),
+        table_area,
+        &mut editor.table.list,
+    );
+    frame.render_widget(
+        Paragraph::new("Space: show/hide  K/J: move up/down  Esc: close"),
+        hint,
+    );
+}
@@ -0,0 +1,70 @@
+//! Command palette display implementation
+//!
+//! Renders the palette in a modal: the search box on top and the matching
+//! actions below it, best match first.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    widgets::{Cell, Paragraph},
+};
+
+use crate::{
+    features::core::command_palette::{CommandPalette, PaletteEntry},
+    renders::render_widgets::{
+        render_input::input_widget,
+        render_modal::{centered_rect, render_modal},
+        render_table::render_table,
+    },
+    structs::TableRow,
+};
+
+/// Implements table row conversion for palette entries.
+///
+/// # Column Layout
+///
+/// 1. **Action** (flexible): Label of the action
+impl TableRow for PaletteEntry {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![Cell::from(self.label.as_str())]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![Constraint::Min(20)]
+    }
+}
+
+/// Renders the command palette in a modal over the current page.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `palette` - The open palette with its search and selection
+pub fn render_command_palette(frame: &mut Frame, palette: &mut CommandPalette) {
+    let height = frame.area().height.saturating_sub(4).min(24);
+    let modal_area = centered_rect(frame.area(), 60, height);
+    let inner = render_modal(frame, modal_area, "Command palette");
+    let [search_area, table_area, hint] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+
+    frame.render_widget(
+        input_widget(&palette.query.value, palette.query.label, false, true),
+        search_area,
+    );
+    let rows: Vec<PaletteEntry> = palette
+        .matches
+        .iter()
+        .map(|index| palette.entries[*index].clone())
+        .collect();
+    let title = format!("Actions ({})", rows.len());
+    frame.render_stateful_widget(
+        render_table(vec!["Action"], &rows, &title),
+        table_area,
+        &mut palette.table.list,
+    );
+    frame.render_widget(Paragraph::new("Enter: run  Esc: close"), hint);
+}
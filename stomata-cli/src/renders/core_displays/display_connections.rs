@@ -0,0 +1,356 @@
+//! Network connections display implementation
+//!
+//! Renders every TCP and UDP socket with its owning process. Outbound
+//! connections that look suspicious according to the configured allowlist
+//! are highlighted with the reason they were flagged. With GeoIP databases
+//! configured, remote addresses are annotated with country and ASN. SSH
+//! clients with port forwards are listed in their own panel.
+//!
+//! The Ports page lists only the listening sockets with their owning
+//! process, filtered by port, address or process name.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Cell, TableState},
+};
+use stomata_core::collectors::connections::{
+    Connection, ConnectionsMetrics,
+    ssh::{Forward, ForwardKind, SshTunnel},
+};
+
+use crate::{
+    config::config,
+    features::core::listening_ports::PortsUIState,
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{
+            render_input::input_widget, render_paragraph::paragraph_widget,
+            render_table::render_table,
+        },
+    },
+    structs::{TableRow, UIState},
+    theme::theme,
+};
+
+/// A connection with its GeoIP annotation and the reason it was flagged
+struct ConnectionRow<'a> {
+    connection: &'a Connection,
+    geo: String,
+    flag: Option<&'static str>,
+}
+
+/// Implements table row conversion for connections.
+///
+/// # Column Layout
+///
+/// 1. **Proto** (5 chars): TCP or UDP
+/// 2. **Local** (flexible): Local address and port
+/// 3. **Remote** (flexible): Remote address and port
+/// 4. **Geo** (flexible): Country and ASN of the remote address
+/// 5. **State** (12 chars): Socket state
+/// 6. **PID** (8 chars): Owning process, empty if unknown
+/// 7. **Process** (16 chars): Owning process name
+/// 8. **Flag** (flexible): Why the connection looks suspicious
+impl TableRow for ConnectionRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let connection = self.connection;
+        let flag = match self.flag {
+            Some(reason) => Cell::from(reason).style(Style::default().fg(theme().warning)),
+            None => Cell::from(""),
+        };
+        vec![
+            Cell::from(connection.protocol.as_str()),
+            Cell::from(connection.local.to_string()),
+            Cell::from(connection.remote.to_string()),
+            Cell::from(self.geo.as_str()),
+            Cell::from(connection.state.as_str()),
+            Cell::from(
+                connection
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_default(),
+            ),
+            Cell::from(connection.process_name.clone().unwrap_or_default()),
+            flag,
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(5),  // Proto
+            Constraint::Min(22),    // Local
+            Constraint::Min(22),    // Remote
+            Constraint::Min(12),    // Geo
+            Constraint::Length(12), // State
+            Constraint::Length(8),  // PID
+            Constraint::Length(16), // Process
+            Constraint::Min(20),    // Flag
+        ]
+    }
+}
+
+/// A listening socket of the Ports page
+struct ListeningRow<'a>(&'a Connection);
+
+/// Implements table row conversion for listening sockets.
+///
+/// # Column Layout
+///
+/// 1. **Proto** (5 chars): TCP or UDP
+/// 2. **Port** (7 chars): Local port
+/// 3. **Address** (flexible): Address the socket is bound to, `0.0.0.0`
+///    and `::` meaning every interface
+/// 4. **PID** (8 chars): Owning process, empty if unknown
+/// 5. **Process** (flexible): Owning process name
+impl TableRow for ListeningRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let connection = self.0;
+        vec![
+            Cell::from(connection.protocol.as_str()),
+            Cell::from(connection.local.port().to_string()),
+            Cell::from(connection.local.ip().to_string()),
+            Cell::from(
+                connection
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_default(),
+            ),
+            Cell::from(connection.process_name.clone().unwrap_or_default()),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(5), // Proto
+            Constraint::Length(7), // Port
+            Constraint::Min(16),   // Address
+            Constraint::Length(8), // PID
+            Constraint::Min(16),   // Process
+        ]
+    }
+}
+
+/// Renders the listening sockets below the filter box of the Ports page.
+///
+/// Sockets owned by other users show no process unless stomata runs as
+/// root, since their file descriptors can't be read.
+pub fn render_listening_ports(
+    frame: &mut Frame,
+    area: Rect,
+    metrics: &ConnectionsMetrics,
+    state: &mut PortsUIState,
+) {
+    let [filter_area, table_area] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+    let filter_title = if state.editing {
+        format!("{} - Enter: keep  Esc: clear", state.filter.label)
+    } else {
+        format!("{} - /: edit", state.filter.label)
+    };
+    frame.render_widget(
+        input_widget(&state.filter.value, &filter_title, false, state.editing),
+        filter_area,
+    );
+
+    let rows: Vec<ListeningRow> = metrics
+        .listening()
+        .into_iter()
+        .filter(|connection| connection.matches(&state.filter.value))
+        .map(ListeningRow)
+        .collect();
+    let headers = vec!["Proto", "Port", "Address", "PID", "Process"];
+    let title = format!("Listening ({})", rows.len());
+    state.table.set_count(rows.len());
+    frame.render_stateful_widget(
+        render_table(headers, &rows, &title),
+        table_area,
+        &mut state.table.list,
+    );
+}
+
+/// An SSH port forward with the client it belongs to
+struct ForwardRow<'a> {
+    tunnel: &'a SshTunnel,
+    forward: &'a Forward,
+}
+
+impl ForwardRow<'_> {
+    /// Whether the client listens on the forwarded port. Remote forwards
+    /// listen on the server, so they can't be checked locally.
+    fn is_up(&self) -> Option<bool> {
+        if self.forward.kind == ForwardKind::Remote {
+            return None;
+        }
+        let port = self
+            .forward
+            .listen
+            .rsplit(':')
+            .next()?
+            .parse::<u16>()
+            .ok()?;
+        Some(self.tunnel.listening_ports.contains(&port))
+    }
+}
+
+/// Implements table row conversion for SSH port forwards.
+///
+/// # Column Layout
+///
+/// 1. **PID** (8 chars): ssh client process
+/// 2. **Destination** (flexible): Host the client connected to
+/// 3. **Kind** (8 chars): local, remote or dynamic
+/// 4. **Listen** (flexible): Listening side of the forward
+/// 5. **Target** (flexible): Where connections are forwarded to
+/// 6. **Status** (14 chars): Whether the local port is listening
+impl TableRow for ForwardRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let status = match self.is_up() {
+            Some(true) => Cell::from("listening").style(Style::default().fg(theme().ok)),
+            Some(false) => Cell::from("not listening").style(Style::default().fg(theme().warning)),
+            None => Cell::from("on server").style(Style::default().fg(theme().muted)),
+        };
+        vec![
+            Cell::from(self.tunnel.pid.to_string()),
+            Cell::from(self.tunnel.destination.clone().unwrap_or_default()),
+            Cell::from(self.forward.kind.as_str()),
+            Cell::from(self.forward.listen.as_str()),
+            Cell::from(
+                self.forward
+                    .target
+                    .clone()
+                    .unwrap_or_else(|| "SOCKS".to_string()),
+            ),
+            status,
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(8),  // PID
+            Constraint::Min(16),    // Destination
+            Constraint::Length(8),  // Kind
+            Constraint::Min(16),    // Listen
+            Constraint::Min(20),    // Target
+            Constraint::Length(14), // Status
+        ]
+    }
+}
+
+/// Display implementation for network connections
+///
+/// Shows a summary line with the number of sockets and flagged connections,
+/// the SSH port forwards when there are any, and a selectable table of all
+/// sockets, flagged ones first.
+impl Display for ConnectionsMetrics {
+    /// Renders the connections table.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the connections page
+    /// * `ui_state` - Table selection state, the table is not rendered
+    ///   without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        let allowlist = config().connections.allowlist();
+        let listening_ports = self.listening_ports();
+
+        #[cfg_attr(not(feature = "geoip"), allow(unused_mut))]
+        let mut ui_state = ui_state;
+        #[cfg(feature = "geoip")]
+        let mut geoip = ui_state
+            .as_deref_mut()
+            .and_then(|state| state.geoip.as_mut());
+
+        let mut rows: Vec<ConnectionRow> = self
+            .connections
+            .iter()
+            .map(|connection| {
+                #[cfg(feature = "geoip")]
+                let geo = match geoip.as_mut() {
+                    Some(Ok(geoip)) => geoip
+                        .lookup(connection.remote.ip())
+                        .map(|info| info.to_string())
+                        .unwrap_or_default(),
+                    _ => String::new(),
+                };
+                #[cfg(not(feature = "geoip"))]
+                let geo = String::new();
+
+                ConnectionRow {
+                    connection,
+                    geo,
+                    flag: allowlist.check(connection, &listening_ports),
+                }
+            })
+            .collect();
+        rows.sort_by_key(|row| row.flag.is_none());
+
+        let flagged = rows.iter().filter(|row| row.flag.is_some()).count();
+        #[allow(unused_mut)]
+        let mut summary = format!(
+            "{} sockets, {} established, {} flagged. Processes and ports can be allowlisted in the config file.",
+            rows.len(),
+            self.connections
+                .iter()
+                .filter(|connection| connection.is_established())
+                .count(),
+            flagged
+        );
+        #[cfg(feature = "geoip")]
+        if let Some(Err(err)) = geoip {
+            summary.push_str(&format!("\nGeoIP disabled: {err}"));
+        }
+
+        let forwards: Vec<ForwardRow> = self
+            .ssh_tunnels
+            .iter()
+            .flat_map(|tunnel| {
+                tunnel
+                    .forwards
+                    .iter()
+                    .map(move |forward| ForwardRow { tunnel, forward })
+            })
+            .collect();
+        // rows plus borders and header, capped so the sockets stay visible
+        let forwards_height = if forwards.is_empty() {
+            0
+        } else {
+            (forwards.len() as u16 + 3).min(area.height / 3)
+        };
+
+        let layout = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(forwards_height),
+            Constraint::Min(0),
+        ])
+        .split(area);
+        frame.render_widget(paragraph_widget(&summary, "Summary"), layout[0]);
+
+        if !forwards.is_empty() {
+            let headers = vec!["PID", "Destination", "Kind", "Listen", "Target", "Status"];
+            frame.render_stateful_widget(
+                render_table(headers, &forwards, "SSH Tunnels"),
+                layout[1],
+                &mut TableState::default(),
+            );
+        }
+
+        let headers = vec![
+            "Proto", "Local", "Remote", "Geo", "State", "PID", "Process", "Flag",
+        ];
+        let table = render_table(headers, &rows, "Connections");
+
+        if let Some(ui_state) = ui_state {
+            ui_state.connections_table.set_count(rows.len());
+            frame.render_stateful_widget(table, layout[2], &mut ui_state.connections_table.list);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+//! Controls page display implementation
+//!
+//! Renders the audio devices with their volume and mute state and the
+//! paired Bluetooth devices, each panel with the keybindings to control
+//! them. The panel with keyboard focus has a highlighted border.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell},
+};
+use stomata_core::collectors::{
+    audio::{AudioDevice, AudioMetrics},
+    bluetooth::{BluetoothDevice, BluetoothMetrics},
+};
+
+use crate::{
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{render_paragraph::paragraph_widget, render_table::render_table},
+    },
+    structs::{ControlsPanel, TableRow, UIState},
+    theme::theme,
+};
+
+/// Bordered block of a Controls panel, highlighted when it has focus
+fn panel_block(title: &str, focused: bool) -> Block<'_> {
+    let block = Block::default().title(title).borders(Borders::ALL);
+    if focused {
+        block.border_style(Style::default().fg(theme().focus))
+    } else {
+        block
+    }
+}
+
+/// Implements table row conversion for audio devices.
+///
+/// # Column Layout
+///
+/// 1. **Default** (3 chars): `*` for the default device of its kind
+/// 2. **Kind** (8 chars): Output or input
+/// 3. **Device** (flexible): Description of the device
+/// 4. **Volume** (8 chars): Volume in percent, in the muted color while muted
+/// 5. **Muted** (6 chars): Whether the device is muted
+impl TableRow for AudioDevice {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let volume_color = if self.muted {
+            theme().muted
+        } else if self.volume_percent > 100 {
+            theme().warning
+        } else {
+            theme().text
+        };
+        let mut description = Cell::from(self.description.as_str());
+        if self.is_default {
+            description = description.style(Style::default().add_modifier(Modifier::BOLD));
+        }
+        vec![
+            Cell::from(if self.is_default { "*" } else { "" }),
+            Cell::from(self.kind.as_str()),
+            description,
+            Cell::from(format!("{}%", self.volume_percent))
+                .style(Style::default().fg(volume_color)),
+            Cell::from(if self.muted { "yes" } else { "" }),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(3), // Default
+            Constraint::Length(8), // Kind
+            Constraint::Min(20),   // Device
+            Constraint::Length(8), // Volume
+            Constraint::Length(6), // Muted
+        ]
+    }
+}
+
+/// Display implementation for the audio panel of the Controls page
+impl Display for AudioMetrics {
+    /// Renders the audio devices table above a status and keybindings
+    /// panel, or the error when the sound server couldn't be queried. The
+    /// table stays empty while the devices are listed for the first time.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the audio panel
+    /// * `ui_state` - Selection state, the table is not rendered without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if let Some(error) = &self.error {
+            let text = format!(
+                "\n{error}\n\nAudio controls need pactl (pulseaudio-utils) talking to PulseAudio or PipeWire."
+            );
+            frame.render_widget(
+                paragraph_widget(&text, "Audio").alignment(Alignment::Center),
+                area,
+            );
+            return Ok(());
+        }
+
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+        let focused = ui_state.controls_focus == ControlsPanel::Audio;
+        let state = &mut ui_state.audio;
+
+        let layout = Layout::vertical([Constraint::Min(3), Constraint::Length(4)]).split(area);
+
+        let headers = vec!["", "Kind", "Device", "Volume", "Muted"];
+        let title = if self.pending {
+            "Audio (listing devices…)".to_string()
+        } else {
+            format!("Audio ({})", self.devices.len())
+        };
+        let table =
+            render_table(headers, &self.devices, &title).block(panel_block(&title, focused));
+        state.table.set_count(self.devices.len());
+        state.devices = self.devices.clone();
+        frame.render_stateful_widget(table, layout[0], &mut state.table.list);
+
+        let mut help = state.status.clone().unwrap_or_default();
+        help.push_str("\n+/-: volume  m: mute  d: make default  f: switch panel");
+        frame.render_widget(paragraph_widget(&help, "Actions"), layout[1]);
+        Ok(())
+    }
+}
+
+/// Implements table row conversion for Bluetooth devices.
+///
+/// # Column Layout
+///
+/// 1. **Device** (flexible): Device name
+/// 2. **Address** (19 chars): MAC address
+/// 3. **State** (14 chars): Connected or paired
+/// 4. **Battery** (8 chars): Battery level when the device reports it
+impl TableRow for BluetoothDevice {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let (state, color) = if self.connected {
+            ("connected", theme().ok)
+        } else {
+            ("paired", theme().muted)
+        };
+        let battery_color = match self.battery_percent {
+            Some(0..20) => theme().critical,
+            _ => theme().text,
+        };
+        vec![
+            Cell::from(self.name.as_str()),
+            Cell::from(self.address.as_str()),
+            Cell::from(state).style(Style::default().fg(color)),
+            Cell::from(
+                self.battery_percent
+                    .map(|percent| format!("{percent}%"))
+                    .unwrap_or_default(),
+            )
+            .style(Style::default().fg(battery_color)),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(20),    // Device
+            Constraint::Length(19), // Address
+            Constraint::Length(14), // State
+            Constraint::Length(8),  // Battery
+        ]
+    }
+}
+
+/// Display implementation for the Bluetooth panel of the Controls page
+impl Display for BluetoothMetrics {
+    /// Renders the paired devices table above a keybindings panel, or the
+    /// error when BlueZ couldn't be queried. The table stays empty while
+    /// the devices are listed for the first time.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the Bluetooth panel
+    /// * `ui_state` - Selection state, the table is not rendered without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if let Some(error) = &self.error {
+            let text = format!(
+                "\n{error}\n\nBluetooth controls need bluetoothctl (bluez) and a running bluetooth service."
+            );
+            frame.render_widget(
+                paragraph_widget(&text, "Bluetooth").alignment(Alignment::Center),
+                area,
+            );
+            return Ok(());
+        }
+
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+        let focused = ui_state.controls_focus == ControlsPanel::Bluetooth;
+        let state = &mut ui_state.bluetooth;
+
+        let layout = Layout::vertical([Constraint::Min(3), Constraint::Length(3)]).split(area);
+
+        let headers = vec!["Device", "Address", "State", "Battery"];
+        let title = if self.pending {
+            "Bluetooth (listing devices…)".to_string()
+        } else {
+            format!("Bluetooth ({})", self.devices.len())
+        };
+        let table =
+            render_table(headers, &self.devices, &title).block(panel_block(&title, focused));
+        state.table.set_count(self.devices.len());
+        state.devices = self.devices.clone();
+        frame.render_stateful_widget(table, layout[0], &mut state.table.list);
+
+        frame.render_widget(
+            paragraph_widget("c: connect  x: disconnect  f: switch panel", "Actions"),
+            layout[1],
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! Crash events display implementation
+//!
+//! Renders the OOM kills, segfaults and failed services logged since boot,
+//! newest first, and the markers drawn under the network sparklines.
+
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Cell,
+};
+use stomata_core::collectors::{CrashEvent, crashes::CrashKind};
+
+use crate::{
+    features::core::crash_events::CrashEventsUIState,
+    renders::render_widgets::{render_paragraph::paragraph_widget, render_table::render_table},
+    structs::TableRow,
+    theme::theme,
+};
+
+/// Color of the events of a kind, in the table and on the markers
+fn kind_color(kind: CrashKind) -> Color {
+    match kind {
+        CrashKind::OomKill | CrashKind::Segfault => theme().critical,
+        CrashKind::ServiceCrash => theme().warning,
+    }
+}
+
+/// Implements table row conversion for crash events.
+///
+/// # Column Layout
+///
+/// 1. **Time** (20 chars): Local time the event was logged
+/// 2. **Kind** (14 chars): OOM kill, segfault or service crash, colored
+/// 3. **Subject** (24 chars): Process or unit that failed
+/// 4. **Message** (flexible): Log message
+impl TableRow for CrashEvent {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![
+            Cell::from(
+                self.timestamp
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+            ),
+            Cell::from(self.kind.as_str()).style(Style::default().fg(kind_color(self.kind))),
+            Cell::from(self.subject.as_str()),
+            Cell::from(self.message.as_str()),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(20), // Time
+            Constraint::Length(14), // Kind
+            Constraint::Length(24), // Subject
+            Constraint::Min(30),    // Message
+        ]
+    }
+}
+
+/// Renders the Events page, or the error reading the journal
+pub fn render_crash_events(frame: &mut Frame, area: Rect, state: &mut CrashEventsUIState) {
+    match &state.events {
+        Some(Ok(events)) => {
+            let headers = vec!["Time", "Kind", "Subject", "Message"];
+            let title = format!(
+                "OOM kills, segfaults and service crashes since boot ({}) - r: refresh",
+                events.len()
+            );
+            state.table.set_count(events.len());
+            frame.render_stateful_widget(
+                render_table(headers, events, &title),
+                area,
+                &mut state.table.list,
+            );
+        }
+        Some(Err(err)) => {
+            let text = format!(
+                "\n\n{err}\n\nThe Events page reads the journal with journalctl, which needs the user to be in the systemd-journal or adm group to see system logs."
+            );
+            frame.render_widget(
+                paragraph_widget(&text, "Events").alignment(Alignment::Center),
+                area,
+            );
+        }
+        None => {}
+    }
+}
+
+/// Builds a line with a marker under every sample of a sparkline that
+/// covers an event. `events` are newest first.
+///
+/// Sample `i` covers the time since sample `i - 1`. The line starts with
+/// a space to line up with the sparkline inside its border.
+pub fn event_markers<'a>(
+    sample_times: impl Iterator<Item = &'a DateTime<Utc>>,
+    events: &[CrashEvent],
+) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    let mut previous: Option<DateTime<Utc>> = None;
+    for time in sample_times {
+        let event = events.iter().find(|event| {
+            event.timestamp <= *time && previous.is_none_or(|previous| event.timestamp > previous)
+        });
+        spans.push(match event {
+            Some(event) => Span::styled("▲", Style::default().fg(kind_color(event.kind))),
+            None => Span::raw(" "),
+        });
+        previous = Some(*time);
+    }
+    Line::from(spans)
+}
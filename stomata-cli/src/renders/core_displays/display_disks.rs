@@ -0,0 +1,353 @@
+//! Disks display implementation
+//!
+//! Lists the whole disks with their throughput, latency, queue depth and
+//! utilization, and plots the history of the selected disk below. Latency
+//! and queue depth are highlighted when high, since a saturated disk shows
+//! up there long before its throughput looks alarming.
+//!
+//! With the `smart` feature a health badge per physical disk is shown
+//! above the table, in red when a disk fails its self-assessment, has
+//! reallocated sectors or runs hot.
+//!
+//! The mounted filesystems are listed beside the charts. Removable media
+//! mounted while the app runs are highlighted and can be unmounted.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::Cell,
+};
+#[cfg(feature = "smart")]
+use std::collections::HashMap;
+
+#[cfg(feature = "smart")]
+use ratatui::{
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+#[cfg(feature = "smart")]
+use stomata_core::collectors::disks::smart::SmartHealth;
+use stomata_core::collectors::{
+    DiskMetrics,
+    disks::{DiskIo, Mount},
+};
+
+use crate::{
+    config::config,
+    constants::{DISK_AWAIT_WARNING_MS, DISK_QUEUE_WARNING},
+    features::core::disks::DisksPanel,
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{
+            render_paragraph::paragraph_widget, render_sparkline::render_sparkline,
+            render_table::render_table,
+        },
+    },
+    structs::{TableRow, UIState},
+    theme::theme,
+    utils::{format_bytes, format_rate},
+};
+
+/// Style of a value that is highlighted above its warning threshold
+fn threshold_style(value: f64, warning: f64) -> Style {
+    if value >= warning {
+        Style::default().fg(theme().warning)
+    } else {
+        Style::default()
+    }
+}
+
+/// Implements table row conversion for disks.
+///
+/// # Column Layout
+///
+/// 1. **Disk** (flexible): Device name
+/// 2. **r/s** (8 chars): Reads completed per second
+/// 3. **w/s** (8 chars): Writes completed per second
+/// 4. **Read** (12 chars): Bytes read per second
+/// 5. **Write** (12 chars): Bytes written per second
+/// 6. **Await** (10 chars): Average request latency, highlighted when high
+/// 7. **Queue** (8 chars): Average queue depth, highlighted when high
+/// 8. **Util** (8 chars): Share of the time the disk was busy
+impl TableRow for DiskIo {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let await_ms = self.await_ms();
+        vec![
+            Cell::from(self.name.as_str()),
+            Cell::from(format!("{:.0}", self.reads_per_sec)),
+            Cell::from(format!("{:.0}", self.writes_per_sec)),
+            Cell::from(format_rate(self.read_bytes_per_sec)),
+            Cell::from(format_rate(self.write_bytes_per_sec)),
+            Cell::from(
+                await_ms
+                    .map(|await_ms| format!("{await_ms:.2} ms"))
+                    .unwrap_or_else(|| "-".to_string()),
+            )
+            .style(threshold_style(
+                await_ms.unwrap_or(0.0),
+                DISK_AWAIT_WARNING_MS,
+            )),
+            Cell::from(format!("{:.2}", self.queue_depth))
+                .style(threshold_style(self.queue_depth, DISK_QUEUE_WARNING)),
+            Cell::from(format!("{:.0}%", self.utilization)),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(10),    // Disk
+            Constraint::Length(8),  // r/s
+            Constraint::Length(8),  // w/s
+            Constraint::Length(12), // Read
+            Constraint::Length(12), // Write
+            Constraint::Length(10), // Await
+            Constraint::Length(8),  // Queue
+            Constraint::Length(8),  // Util
+        ]
+    }
+}
+
+/// Implements table row conversion for mounted filesystems.
+///
+/// Removable media mounted while the app runs are highlighted.
+///
+/// # Column Layout
+///
+/// 1. **Mount point** (flexible): Where the filesystem is mounted
+/// 2. **Device** (14 chars): Block device
+/// 3. **FS** (8 chars): Filesystem type
+/// 4. **Size** (10 chars): Total size
+/// 5. **Free** (10 chars): Available space
+/// 6. **Inodes** (8 chars): Inodes in use in percent, in the warning color
+///    from the `inode_percent` alert threshold; blank for filesystems
+///    without a fixed inode table
+impl TableRow for Mount {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let style = if self.is_new && self.removable {
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let mount_point = if self.removable {
+            format!("{} (removable)", self.mount_point)
+        } else {
+            self.mount_point.clone()
+        };
+        vec![
+            Cell::from(mount_point),
+            Cell::from(self.device.as_str()),
+            Cell::from(self.file_system.as_str()),
+            Cell::from(format_bytes(self.total_bytes)),
+            Cell::from(format_bytes(self.available_bytes)),
+        ]
+        .into_iter()
+        .map(|cell| cell.style(style))
+        .chain(std::iter::once(inodes_cell(self)))
+        .collect()
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(12),    // Mount point
+            Constraint::Length(14), // Device
+            Constraint::Length(8),  // FS
+            Constraint::Length(10), // Size
+            Constraint::Length(10), // Free
+            Constraint::Length(8),  // Inodes
+        ]
+    }
+}
+
+/// Inode usage of a mount, highlighted from the alert threshold
+fn inodes_cell(mount: &Mount) -> Cell<'_> {
+    let Some(inodes) = mount.inodes else {
+        return Cell::default();
+    };
+    let percent = inodes.percent();
+    let style = if percent >= config().alerts.inode_percent {
+        Style::default().fg(theme().warning)
+    } else {
+        Style::default()
+    };
+    Cell::from(format!("{percent:.0}%")).style(style)
+}
+
+/// Health badge of every physical disk, e.g. `sda OK 38 °C 21034 h`,
+/// replaced by the exceeded thresholds in red
+#[cfg(feature = "smart")]
+fn smart_badges(smart: &HashMap<String, SmartHealth>) -> Line<'static> {
+    let mut disks: Vec<(&String, &SmartHealth)> = smart.iter().collect();
+    disks.sort_by_key(|(disk, _)| *disk);
+
+    let mut spans = vec![Span::styled(
+        "S.M.A.R.T. ",
+        Style::default().fg(theme().label),
+    )];
+    for (disk, health) in disks {
+        let warnings = health.warnings();
+        let (text, color) = if let Some(error) = &health.error {
+            (format!("{disk} n/a ({error})"), theme().muted)
+        } else if !warnings.is_empty() {
+            (format!("{disk} {}", warnings.join(", ")), theme().critical)
+        } else {
+            let mut text = format!("{disk} OK");
+            if let Some(temperature) = health.temperature_c {
+                text.push_str(&format!(" {temperature} °C"));
+            }
+            if let Some(hours) = health.power_on_hours {
+                text.push_str(&format!(" {hours} h"));
+            }
+            (text, theme().ok)
+        };
+        spans.push(Span::styled(
+            format!("[{text}] "),
+            Style::default().fg(color),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Renders the mounted filesystems and the unmount confirmation or the
+/// result of the last unmount
+fn render_mounts(frame: &mut Frame, area: Rect, mounts: &[Mount], ui_state: &mut UIState) {
+    let state = &mut ui_state.disks;
+    let [table_area, help_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(4)]).areas(area);
+
+    let headers = vec!["Mount point", "Device", "FS", "Size", "Free", "Inodes"];
+    let title = format!("Mounts ({})", mounts.len());
+    state.mounts_table.set_count(mounts.len());
+    state.mounts = mounts.to_vec();
+    frame.render_stateful_widget(
+        render_table(headers, mounts, &title),
+        table_area,
+        &mut state.mounts_table.list,
+    );
+
+    let mut help = match &state.confirm_unmount {
+        Some(mount) => format!(
+            "Unmount {} ({})? y: confirm  any other key: cancel",
+            mount.mount_point, mount.device
+        ),
+        None => String::new(),
+    };
+    help.push_str(match state.focus {
+        DisksPanel::Io => "\nf: select mounts",
+        DisksPanel::Mounts => "\nf: select disks  u: unmount",
+    });
+    frame.render_widget(paragraph_widget(&help, "Actions"), help_area);
+}
+
+/// Display implementation for disk activity
+impl Display for DiskMetrics {
+    /// Renders the disks table above the latency, queue depth and
+    /// utilization charts of the selected disk and the mounts table.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the Disks page
+    /// * `ui_state` - Selection and chart history, nothing is rendered
+    ///   without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if let Some(error) = &self.error {
+            frame.render_widget(
+                paragraph_widget(&format!("\n\n{error}"), "Disks").alignment(Alignment::Center),
+                area,
+            );
+            return Ok(());
+        }
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+
+        if !ui_state.paused {
+            for disk in &self.disks {
+                ui_state
+                    .disks
+                    .history
+                    .entry(disk.name.clone())
+                    .or_default()
+                    .update(disk);
+            }
+        }
+
+        #[cfg(feature = "smart")]
+        let area = {
+            let [badges_area, area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+            frame.render_widget(Paragraph::new(smart_badges(&self.smart)), badges_area);
+            area
+        };
+
+        let disks_height = (self.disks.len() as u16 + 3).min(area.height / 2);
+        let [table_area, bottom_area] =
+            Layout::vertical([Constraint::Length(disks_height), Constraint::Min(6)]).areas(area);
+        let [charts_area, mounts_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(bottom_area);
+        render_mounts(frame, mounts_area, &self.mounts, ui_state);
+
+        let headers = vec![
+            "Disk", "r/s", "w/s", "Read", "Write", "Await", "Queue", "Util",
+        ];
+        ui_state.disks.table.set_count(self.disks.len());
+        frame.render_stateful_widget(
+            render_table(headers, &self.disks, "Disks"),
+            table_area,
+            &mut ui_state.disks.table.list,
+        );
+
+        let selected = ui_state
+            .disks
+            .table
+            .list
+            .selected()
+            .and_then(|index| self.disks.get(index));
+        let Some(disk) = selected else {
+            frame.render_widget(
+                paragraph_widget("\n\nCollecting disk activity...", "Disks")
+                    .alignment(Alignment::Center),
+                charts_area,
+            );
+            return Ok(());
+        };
+        let Some(history) = ui_state.disks.history.get_mut(&disk.name) else {
+            return Ok(());
+        };
+
+        let titles = [
+            format!(
+                "{} latency: {}",
+                disk.name,
+                disk.await_ms()
+                    .map(|await_ms| format!("{await_ms:.2} ms"))
+                    .unwrap_or_else(|| "-".to_string())
+            ),
+            format!(
+                "{} queue depth: {:.2} ({} in flight)",
+                disk.name, disk.queue_depth, disk.in_flight
+            ),
+            format!("{} utilization: {:.0}%", disk.name, disk.utilization),
+        ];
+        let chart_areas: [Rect; 3] =
+            Layout::vertical([Constraint::Ratio(1, 3); 3]).areas(charts_area);
+        let series = [
+            history.await_us.to_vec(),
+            history.queue_depth.to_vec(),
+            history.utilization.to_vec(),
+        ];
+        for ((data, title), area) in series.iter().zip(&titles).zip(chart_areas) {
+            frame.render_widget(render_sparkline(data, title), area);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Error popup display implementation
+//!
+//! Renders the modal listing the errors raised since startup, newest
+//! first, with the path of the log file holding their full details.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::Style,
+    text::Line,
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::{
+    errors::ErrorLog,
+    logging::log_path,
+    renders::render_widgets::render_modal::{centered_rect, render_modal},
+    theme::theme,
+};
+
+/// Renders the error popup over the current page.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `error_log` - The recent errors
+pub fn render_error_log(frame: &mut Frame, error_log: &ErrorLog) {
+    let height = frame.area().height.saturating_sub(6).max(8);
+    let modal_area = centered_rect(frame.area(), 80, height);
+    let inner = render_modal(frame, modal_area, "Errors");
+    let [list, hint] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+
+    let lines: Vec<Line> = if error_log.recent().is_empty() {
+        vec![Line::styled(
+            "No errors since startup",
+            Style::default().fg(theme().muted),
+        )]
+    } else {
+        error_log
+            .recent()
+            .iter()
+            .rev()
+            .map(|error| {
+                Line::styled(
+                    format!(
+                        "{} [{}] {}",
+                        error.at.format("%H:%M:%S"),
+                        error.context,
+                        error.message
+                    ),
+                    Style::default().fg(theme().critical),
+                )
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), list);
+
+    let hint_text = match log_path() {
+        Some(path) => format!("Esc: close  Full details: {}", path.display()),
+        None => "Esc: close".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(hint_text).style(Style::default().fg(theme().muted)),
+        hint,
+    );
+}
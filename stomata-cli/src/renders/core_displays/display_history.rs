@@ -0,0 +1,185 @@
+//! History page display implementation
+//!
+//! Charts the stored CPU and memory usage and the network rates over the
+//! selected range, each with its lowest, average and highest value drawn
+//! as horizontal lines so a spike can be told from the usual level.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    text::Line,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, LegendPosition},
+};
+use stomata_core::storage::RangeStats;
+
+use crate::{
+    features::core::history::{HistorySeries, HistoryUIState},
+    renders::render_widgets::render_paragraph::paragraph_widget,
+    theme::theme,
+    utils::format_rate,
+};
+
+/// Title of the chart of a stored metric
+fn series_title(metric: &str) -> &'static str {
+    match metric {
+        "cpu" => "CPU usage",
+        "memory" => "Memory usage",
+        "network_rx" => "Network received",
+        "network_tx" => "Network transmitted",
+        _ => "",
+    }
+}
+
+/// Value of a metric formatted with its unit
+fn format_value(metric: &str, value: f64) -> String {
+    match metric {
+        "network_rx" | "network_tx" => format_rate(value),
+        _ => format!("{value:.1}%"),
+    }
+}
+
+/// Renders the History page: a message without a store, otherwise the
+/// charts of the selected range
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - The rectangular area allocated for the page
+/// * `state` - Range and series read from the store
+/// * `unavailable` - Why nothing is charted, e.g. no `--store`
+pub fn render_history(
+    frame: &mut Frame,
+    area: Rect,
+    state: &HistoryUIState,
+    unavailable: Option<&str>,
+) {
+    let title = format!("History, last {} (r/R: range)", state.range.label());
+    let message = match (unavailable, &state.series) {
+        (Some(reason), _) => Some(reason.to_string()),
+        (None, Some(Err(err))) => Some(format!("Reading the history failed: {err}")),
+        (None, None) => Some("Reading the history...".to_string()),
+        (None, Some(Ok(_))) => None,
+    };
+    if let Some(message) = message {
+        frame.render_widget(
+            paragraph_widget(&format!("\n\n{message}"), &title).alignment(Alignment::Center),
+            area,
+        );
+        return;
+    }
+    let Some(Ok(series)) = &state.series else {
+        return;
+    };
+
+    let [header, charts] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    frame.render_widget(
+        Line::from(title).style(Style::default().fg(theme().accent)),
+        header,
+    );
+    let [top, bottom] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(charts);
+    let [top_left, top_right] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(top);
+    let [bottom_left, bottom_right] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+    let range_secs = state.range.duration().num_seconds() as f64;
+    for (series, area) in series
+        .iter()
+        .zip([top_left, top_right, bottom_left, bottom_right])
+    {
+        render_series(frame, area, series, range_secs, state.range.label());
+    }
+}
+
+/// One chart with the min, average and max overlays
+fn render_series(
+    frame: &mut Frame,
+    area: Rect,
+    series: &HistorySeries,
+    range_secs: f64,
+    range_label: &str,
+) {
+    let title = series_title(series.metric);
+    let (Some(stats), Some(last)) = (series.stats, series.samples.last()) else {
+        frame.render_widget(
+            paragraph_widget("\nNo samples in this range yet", title).alignment(Alignment::Center),
+            area,
+        );
+        return;
+    };
+
+    // x is the number of seconds into the range, so the chart always
+    // spans the whole range even while it is only partly recorded
+    let start = last.time.timestamp() as f64 - range_secs;
+    let points: Vec<(f64, f64)> = series
+        .samples
+        .iter()
+        .map(|sample| {
+            (
+                (sample.time.timestamp() as f64 - start).max(0.0),
+                sample.value,
+            )
+        })
+        .collect();
+    let overlays = overlay_lines(stats, range_secs);
+    let upper = match series.metric {
+        "cpu" | "memory" => 100.0,
+        _ => (stats.max * 1.1).max(1.0),
+    };
+
+    let mut datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme().accent))
+            .data(&points),
+    ];
+    for ((name, value, color), line) in overlay_labels(series.metric, stats)
+        .into_iter()
+        .zip(&overlays)
+    {
+        datasets.push(
+            Dataset::default()
+                .name(format!("{name} {value}"))
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(line),
+        );
+    }
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .legend_position(Some(LegendPosition::TopLeft))
+        .hidden_legend_constraints((Constraint::Percentage(60), Constraint::Percentage(60)))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme().muted))
+                .bounds([0.0, range_secs])
+                .labels([format!("-{range_label}"), "now".to_string()]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme().muted))
+                .bounds([0.0, upper])
+                .labels(["0".to_string(), format_value(series.metric, upper)]),
+        );
+    frame.render_widget(chart, area);
+}
+
+/// Horizontal lines across the range at the min, average and max
+fn overlay_lines(stats: RangeStats, range_secs: f64) -> [[(f64, f64); 2]; 3] {
+    [stats.min, stats.avg, stats.max].map(|value| [(0.0, value), (range_secs, value)])
+}
+
+/// Legend name, formatted value and color of the min, average and max
+fn overlay_labels(metric: &str, stats: RangeStats) -> [(&'static str, String, Color); 3] {
+    [
+        ("min", format_value(metric, stats.min), theme().ok),
+        ("avg", format_value(metric, stats.avg), theme().warning),
+        ("max", format_value(metric, stats.max), theme().critical),
+    ]
+}
@@ -0,0 +1,131 @@
+//! Host sidebar and comparison grid of `stomata connect`
+//!
+//! With several agents connected, a sidebar lists the hosts next to the
+//! page of the selected one, and the Hosts grid compares CPU, memory and
+//! swap usage of all of them side by side.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, List, ListItem},
+};
+use stomata_core::{collectors::system::summary::SystemSummary, remote::RemoteHost};
+
+use crate::{
+    renders::{
+        core_displays::display_summary::usage_color, render_widgets::render_table::render_table,
+    },
+    structs::{TableRow, TableUIState},
+    theme::theme,
+};
+
+/// One host of the Hosts grid
+#[derive(Debug, Clone)]
+pub struct HostRow {
+    /// Host name the agent reported, or its address before it answered
+    pub hostname: String,
+    /// Address the agent was connected with
+    pub address: String,
+    /// Latest summary, `None` until the agent answered or when it couldn't be
+    /// reached
+    pub summary: Option<SystemSummary>,
+}
+
+/// Cell of a usage percentage colored like the summary strip
+fn usage_cell(percent: f64) -> Cell<'static> {
+    Cell::from(format!("{percent:.1}%")).style(Style::default().fg(usage_color(percent)))
+}
+
+/// Implements table row conversion for the Hosts grid.
+///
+/// # Column Layout
+///
+/// 1. **Host** (flexible): Host name reported by the agent
+/// 2. **Address** (22 chars): Address the agent was connected with
+/// 3. **CPU** (8 chars): Global CPU usage
+/// 4. **Memory** (8 chars): Used memory percentage
+/// 5. **Swap** (8 chars): Used swap percentage
+/// 6. **Top process** (flexible): Busiest process with its CPU usage
+impl TableRow for HostRow {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let mut cells = vec![
+            Cell::from(self.hostname.as_str()),
+            Cell::from(self.address.as_str()),
+        ];
+        match &self.summary {
+            Some(summary) => {
+                cells.push(usage_cell(summary.cpu_usage as f64));
+                cells.push(usage_cell(summary.memory_percent));
+                cells.push(usage_cell(summary.swap_percent));
+                cells.push(Cell::from(match &summary.top_process {
+                    Some(top) => format!("{} ({}) {:.1}%", top.name, top.pid, top.cpu_usage),
+                    None => "-".to_string(),
+                }));
+            }
+            None => {
+                cells.extend(["-", "-", "-"].map(Cell::from));
+                cells.push(Cell::from("unreachable").style(Style::default().fg(theme().critical)));
+            }
+        }
+        cells
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(16),    // Host
+            Constraint::Length(22), // Address
+            Constraint::Length(8),  // CPU
+            Constraint::Length(8),  // Memory
+            Constraint::Length(8),  // Swap
+            Constraint::Min(24),    // Top process
+        ]
+    }
+}
+
+/// Renders the list of connected hosts with the selected one highlighted
+/// and unreachable ones dimmed
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - Narrow area left of the page
+/// * `hosts` - Clients of the connected agents
+/// * `selected` - Index of the host the pages show
+pub fn render_host_sidebar(frame: &mut Frame, area: Rect, hosts: &[RemoteHost], selected: usize) {
+    let items: Vec<ListItem> = hosts
+        .iter()
+        .enumerate()
+        .map(|(index, host)| {
+            let style = if index == selected {
+                Style::default()
+                    .fg(theme().accent)
+                    .add_modifier(Modifier::BOLD)
+            } else if host.is_connected() {
+                Style::default().fg(theme().text)
+            } else {
+                Style::default().fg(theme().muted)
+            };
+            let marker = if index == selected { "> " } else { "  " };
+            ListItem::new(Line::from(format!("{marker}{}", host.hostname()))).style(style)
+        })
+        .collect();
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title("Hosts [ ] H"));
+    frame.render_widget(list, area);
+}
+
+/// Renders the Hosts grid
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - The content area of the page
+/// * `rows` - One row per connected host, in connection order
+/// * `table` - Selection of the grid
+pub fn render_host_grid(frame: &mut Frame, area: Rect, rows: &[HostRow], table: &mut TableUIState) {
+    let headers = vec!["Host", "Address", "CPU", "Memory", "Swap", "Top process"];
+    let title = format!("Hosts ({}), Enter to show one", rows.len());
+    table.set_count(rows.len());
+    frame.render_stateful_widget(render_table(headers, rows, &title), area, &mut table.list);
+}
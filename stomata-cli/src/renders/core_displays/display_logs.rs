@@ -0,0 +1,127 @@
+//! Logs display implementation
+//!
+//! Renders the tail of the kernel log or journal, newest entry first,
+//! with each entry colored by its severity, below the filter box.
+
+use chrono::Local;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style},
+    widgets::Cell,
+};
+use stomata_core::collectors::logs::{LogEntry, LogLevel};
+
+use crate::{
+    features::core::logs::LogsUIState,
+    renders::render_widgets::{
+        render_input::input_widget, render_paragraph::paragraph_widget, render_table::render_table,
+    },
+    structs::TableRow,
+    theme::theme,
+};
+
+/// Color of the entries of a level
+fn level_color(level: LogLevel) -> Color {
+    match level {
+        LogLevel::Emergency | LogLevel::Alert | LogLevel::Critical | LogLevel::Error => {
+            theme().critical
+        }
+        LogLevel::Warning => theme().warning,
+        LogLevel::Notice => theme().accent,
+        LogLevel::Info => theme().text,
+        LogLevel::Debug => theme().muted,
+    }
+}
+
+/// Implements table row conversion for log entries.
+///
+/// # Column Layout
+///
+/// 1. **Time** (15 chars): Local time the entry was logged
+/// 2. **Level** (6 chars): Severity
+/// 3. **Program** (16 chars): Program that logged the entry
+/// 4. **Message** (flexible): Log message
+///
+/// The whole row is colored by the severity.
+impl TableRow for LogEntry {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let style = Style::default().fg(level_color(self.level));
+        vec![
+            Cell::from(
+                self.timestamp
+                    .with_timezone(&Local)
+                    .format("%b %d %H:%M:%S")
+                    .to_string(),
+            )
+            .style(style),
+            Cell::from(self.level.as_str()).style(style),
+            Cell::from(self.source.as_str()).style(style),
+            Cell::from(self.message.as_str()).style(style),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(15), // Time
+            Constraint::Length(6),  // Level
+            Constraint::Length(16), // Program
+            Constraint::Min(30),    // Message
+        ]
+    }
+}
+
+/// Renders the Logs page, or the error reading the log when nothing
+/// could be read
+pub fn render_logs(frame: &mut Frame, area: Rect, state: &mut LogsUIState) {
+    let Some(metrics) = &state.metrics else {
+        return;
+    };
+    if metrics.entries.is_empty()
+        && let Some(error) = &metrics.error
+    {
+        let text = format!(
+            "\n\n{error}\n\nThe Logs page reads the journal with journalctl, or the kernel ring buffer with dmesg. Reading them needs the user to be in the systemd-journal or adm group, or root when kernel.dmesg_restrict is set."
+        );
+        frame.render_widget(
+            paragraph_widget(&text, "Logs").alignment(Alignment::Center),
+            area,
+        );
+        return;
+    }
+
+    let [filter_area, table_area] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+    let filter_title = if state.editing {
+        format!("{} - Enter: keep  Esc: clear", state.filter.label)
+    } else {
+        format!("{} - /: edit", state.filter.label)
+    };
+    frame.render_widget(
+        input_widget(&state.filter.value, &filter_title, false, state.editing),
+        filter_area,
+    );
+
+    let rows: Vec<LogEntry> = metrics
+        .entries
+        .iter()
+        .rev()
+        .filter(|entry| entry.matches(&state.filter.value))
+        .cloned()
+        .collect();
+    let mode = if state.paused {
+        "PAUSED - p: follow"
+    } else {
+        "following - p: pause"
+    };
+    let mut title = format!("{} ({}) {mode}", metrics.source, rows.len());
+    if let Some(error) = &metrics.error {
+        title.push_str(&format!(" - {error}"));
+    }
+    state.table.set_count(rows.len());
+    frame.render_stateful_widget(
+        render_table(vec!["Time", "Level", "Program", "Message"], &rows, &title),
+        table_area,
+        &mut state.table.list,
+    );
+}
@@ -1,46 +1,76 @@
 //! System metrics display implementation
 //!
 //! Provides the visual rendering logic for real-time system resource metrics
-//! including CPU, memory, and swap usage. This module implements the `Display`
-//! trait for `SystemCollector` to render gauges and detailed statistics.
+//! including CPU, memory, and swap usage, plus CPU, memory and IO pressure
+//! and the frequency and governor of every CPU on Linux. This module
+//! implements the `Display` trait for `SystemCollector` to render gauges
+//! and detailed statistics.
 
+use chrono::Local;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Gauge, Paragraph},
+};
+use stomata_core::{
+    collectors::system::{
+        CpuFrequency,
+        cpufreq::is_throttled,
+        metrics::{PressureAverages, SystemCollector},
+    },
+    history::AnomalyDetector,
 };
-use stomata_core::collectors::system::metrics::SystemCollector;
 
 use crate::{
+    constants::{PRESSURE_CRITICAL_PERCENT, PRESSURE_WARNING_PERCENT},
+    features::core::metrics_layout::{LayoutPreset, MetricsPanel, MetricsRow, MetricsWidget},
     renders::{
         core_displays::traits::Display,
-        render_widgets::{render_gauge::render_gauge, render_paragraph::paragraph_widget},
+        render_widgets::{
+            render_gauge::{render_bytes_gauge, render_gauge},
+            render_paragraph::paragraph_widget,
+            render_sparkline::render_anomaly_sparkline,
+            render_stacked_bar::{Segment, render_stacked_bar},
+        },
     },
-    structs::UIState,
-    utils::bytes_to_mb,
+    structs::{ScriptOutput, UIState},
+    theme::theme,
+    utils::format_bytes,
 };
 
 // Display implementation for system resource metrics
 ///
 /// Renders a comprehensive view of system resources divided into four sections:
-/// 1. Memory usage gauge
+/// 1. Memory breakdown bar
 /// 2. Swap usage gauge
 /// 3. CPU usage gauge
 /// 4. Detailed statistics panels
 ///
+/// Kernels with Pressure Stall Information get a row of CPU, memory and IO
+/// pressure gauges above the statistics panels, and on Linux a row of
+/// context switch, interrupt and fork rate sparklines follows it.
+///
 /// The detailed statistics section is horizontally divided into three equal panels
-/// showing memory info, swap info, and CPU count.
+/// showing memory info, swap info, and the CPUs with their frequencies, plus a fourth Script panel when
+/// a hooks script is active. An Anomalies panel lists the readings that
+/// strayed from their rolling baseline, and flagged sparkline bars are drawn
+/// in the critical color.
 impl Display for SystemCollector {
     /// Renders system metrics to the terminal frame
     ///
-    /// Creates a vertical layout with visual gauges for quick assessment
-    /// and detailed text panels for precise values. All memory values are
-    /// converted from bytes to megabytes for better readability.
+    /// Stacks the rows of the layout in `ui_state`, or of the default
+    /// preset without one: visual gauges for quick assessment and detailed
+    /// text panels for precise values. Rows without data on this machine
+    /// are skipped.
     ///
     /// # Arguments
     ///
     /// * `frame` - The ratatui frame to render into
     /// * `area` - The rectangular area allocated for system metrics display
-    /// * `_ui_state` - Unused for this display (no interactive state needed)
+    /// * `ui_state` - Optional UI state carrying the page layout and the hooks
+    ///   script output, used for gauge title overrides and the Script panel
     ///
     /// # Returns
     ///
@@ -48,15 +78,27 @@ impl Display for SystemCollector {
     ///
     /// # Gauge Details
     ///
-    /// - **Memory Gauge**: Shows used vs total memory in MB with percentage
-    /// - **Swap Gauge**: Shows used vs total swap space in MB with percentage
+    /// - **Memory Breakdown**: Stacked bar of memory used by applications,
+    ///   buffers, cache and free memory. "Used" alone overstates memory
+    ///   pressure on Linux, where the page cache grows to fill free memory
+    ///   but is given back on demand. Platforms that don't report cache show
+    ///   used vs free only.
+    /// - **Swap Gauge**: Shows used vs total swap space with percentage
     /// - **CPU Gauge**: Shows overall CPU utilization as a percentage (0-100%)
+    /// - **Pressure Gauges**: Share of time tasks stalled on CPU, memory and
+    ///   IO over the last 10 seconds, with the 60 and 300 second averages
+    /// - **Scheduler Sparklines**: Context switches, interrupts and forks
+    ///   per second, a spike in context switches hints at scheduler thrash
     ///
     /// # Statistics Panels
     ///
-    /// - **Memory Info**: Exact bytes used/total and usage percentage
+    /// - **Memory Info**: Exact bytes used/available/total, cache and buffers
+    ///   where reported, and the usage percentage
     /// - **Swap Info**: Exact bytes used/total and usage percentage
-    /// - **CPU Count**: Number of logical CPU cores available
+    /// - **CPU**: Number of logical CPU cores available and, with Linux
+    ///   cpufreq, the scaling governor, the average frequency against the
+    ///   hardware range (flagged when the busy CPUs look throttled) and the
+    ///   frequency of every core
     ///
     /// # Examples
     ///
@@ -71,83 +113,359 @@ impl Display for SystemCollector {
         &self,
         frame: &mut Frame,
         area: Rect,
-        _ui_state: Option<&mut UIState>,
+        ui_state: Option<&mut UIState>,
     ) -> anyhow::Result<()> {
-        let layout = Layout::vertical([
-            Constraint::Percentage(23),
-            Constraint::Percentage(23),
-            Constraint::Percentage(24),
-            Constraint::Percentage(30),
-        ])
-        .split(area);
-
-        // render memory usage gauge
-        frame.render_widget(
-            render_gauge(
-                bytes_to_mb(self.system_metrics.memory_used),
-                bytes_to_mb(self.system_metrics.memory_total),
-                "Memory Usage",
-                "MB",
+        let default_rows;
+        let (script_output, scheduler_history, detector, rows) = match ui_state {
+            Some(state) => (
+                state.script_output.as_ref(),
+                Some(&state.scheduler_history),
+                Some(&state.anomalies),
+                &state.metrics_layout[..],
             ),
-            layout[0],
-        );
+            None => {
+                default_rows = LayoutPreset::default().rows();
+                (None, None, None, &default_rows[..])
+            }
+        };
+        let label =
+            |default: &'static str| script_output.map_or(default, |output| output.label(default));
 
-        // render swap usage gauge
-        frame.render_widget(
-            render_gauge(
-                bytes_to_mb(self.system_metrics.swap_used),
-                bytes_to_mb(self.system_metrics.swap_total),
-                "Swap Usage",
-                "MB",
-            ),
-            layout[1],
-        );
+        let metrics = &self.system_metrics;
+        let pressure = metrics.pressure;
+        let scheduler = metrics.scheduler.zip(scheduler_history);
+        let available = |panel: &MetricsPanel| match panel {
+            MetricsPanel::Anomalies => detector.is_some(),
+            MetricsPanel::Script => script_output.is_some(),
+            _ => true,
+        };
+        // rows without data give their space to the others
+        let rows: Vec<&MetricsRow> = rows
+            .iter()
+            .filter(|row| match row.widget {
+                MetricsWidget::Pressure => pressure.is_some(),
+                MetricsWidget::Scheduler => scheduler.is_some(),
+                MetricsWidget::Panels => row.panels().iter().any(available),
+                _ => true,
+            })
+            .collect();
+        let areas = Layout::vertical(rows.iter().map(|row| row.constraint())).split(area);
 
-        // render cpu usage gauge
-        frame.render_widget(
-            render_gauge(
-                self.system_metrics.cpu_usage as f64,
-                100.0,
-                "CPU Usage",
-                "%",
-            ),
-            layout[2],
-        );
+        for (row, area) in rows.iter().zip(areas.iter().copied()) {
+            match row.widget {
+                MetricsWidget::Memory => render_stacked_bar(
+                    frame,
+                    area,
+                    label("Memory Usage"),
+                    &memory_segments(self),
+                    |bytes| format_bytes(bytes as u64),
+                ),
+                MetricsWidget::Swap => frame.render_widget(
+                    render_bytes_gauge(metrics.swap_used, metrics.swap_total, label("Swap Usage")),
+                    area,
+                ),
+                MetricsWidget::Cpu => frame.render_widget(
+                    render_gauge(metrics.cpu_usage as f64, 100.0, label("CPU Usage"), "%"),
+                    area,
+                ),
+                MetricsWidget::Pressure => {
+                    let Some(pressure) = pressure else {
+                        continue;
+                    };
+                    let gauges: [Rect; 3] =
+                        Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(area);
+                    frame.render_widget(pressure_gauge("CPU Pressure", pressure.cpu), gauges[0]);
+                    frame.render_widget(
+                        pressure_gauge("Memory Pressure", pressure.memory),
+                        gauges[1],
+                    );
+                    frame.render_widget(pressure_gauge("IO Pressure", pressure.io), gauges[2]);
+                }
+                MetricsWidget::Scheduler => {
+                    let Some((rates, history)) = scheduler else {
+                        continue;
+                    };
+                    let areas: [Rect; 3] =
+                        Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(area);
+                    let titles = [
+                        format!("Context switches: {:.0}/s", rates.context_switches),
+                        format!("Interrupts: {:.0}/s", rates.interrupts),
+                        format!("Forks: {:.1}/s", rates.forks),
+                    ];
+                    let series = [
+                        &history.context_switches,
+                        &history.interrupts,
+                        &history.forks,
+                    ];
+                    for ((series, title), area) in series.into_iter().zip(&titles).zip(areas) {
+                        let anomalies = detector.map_or_else(Vec::new, |detector| {
+                            series.anomalies(detector.window, detector.sigmas)
+                        });
+                        frame.render_widget(
+                            render_anomaly_sparkline(&series.to_vec(), &anomalies, title),
+                            area,
+                        );
+                    }
+                }
+                MetricsWidget::Panels => {
+                    let panels: Vec<MetricsPanel> =
+                        row.panels().iter().copied().filter(available).collect();
+                    let panel_count = panels.len() as u32;
+                    let panel_areas =
+                        Layout::horizontal(vec![Constraint::Ratio(1, panel_count); panels.len()])
+                            .split(area);
+                    for (panel, area) in panels.into_iter().zip(panel_areas.iter().copied()) {
+                        match panel {
+                            MetricsPanel::Memory => frame.render_widget(
+                                paragraph_widget(&memory_text(self), "Memory Info"),
+                                area,
+                            ),
+                            MetricsPanel::Swap => frame.render_widget(
+                                paragraph_widget(&swap_text(self), "Swap Info"),
+                                area,
+                            ),
+                            MetricsPanel::Cpu => frame.render_widget(
+                                cpu_paragraph(
+                                    metrics.cpu_count,
+                                    &metrics.cpu_frequencies,
+                                    metrics.cpu_usage,
+                                ),
+                                area,
+                            ),
+                            MetricsPanel::Anomalies => {
+                                if let Some(detector) = detector {
+                                    frame.render_widget(anomalies_paragraph(detector), area);
+                                }
+                            }
+                            MetricsPanel::Script => {
+                                if let Some(output) = script_output {
+                                    frame.render_widget(script_paragraph(output), area);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
 
-        // --- PARAGRAPH ---
-        let memory_used = self.system_metrics.memory_used as f64
-            / self.system_metrics.memory_total as f64
-            * 100.0;
+        Ok(())
+    }
+}
 
-        let text = format!(
-            "Memory Used: {:.2} Bytes\nTotal Memory: {:.2} Bytes\nUsage: {:.2}%",
-            self.system_metrics.memory_used, self.system_metrics.memory_total, memory_used,
-        );
+/// Segments of the memory breakdown bar: memory used by applications,
+/// buffers, cache and free memory, or used vs free where the platform
+/// doesn't report the cache
+fn memory_segments(collector: &SystemCollector) -> Vec<Segment<'static>> {
+    let metrics = &collector.system_metrics;
+    match (metrics.memory_cached, metrics.memory_buffers) {
+        (Some(cached), Some(buffers)) => {
+            let applications = metrics
+                .memory_total
+                .saturating_sub(metrics.memory_free)
+                .saturating_sub(cached)
+                .saturating_sub(buffers);
+            vec![
+                Segment {
+                    label: "Used",
+                    value: applications as f64,
+                    color: theme().gauge,
+                },
+                Segment {
+                    label: "Buffers",
+                    value: buffers as f64,
+                    color: theme().label,
+                },
+                Segment {
+                    label: "Cached",
+                    value: cached as f64,
+                    color: theme().accent,
+                },
+                Segment {
+                    label: "Free",
+                    value: metrics.memory_free as f64,
+                    color: theme().muted,
+                },
+            ]
+        }
+        _ => vec![
+            Segment {
+                label: "Used",
+                value: metrics.memory_used as f64,
+                color: theme().gauge,
+            },
+            Segment {
+                label: "Free",
+                value: metrics.memory_total.saturating_sub(metrics.memory_used) as f64,
+                color: theme().muted,
+            },
+        ],
+    }
+}
 
-        let swap_used =
-            self.system_metrics.swap_used as f64 / self.system_metrics.swap_total as f64 * 100.0;
-        let text_swap = format!(
-            "Swap Used: {:.2} Bytes\nTotal Swap: {:.2} Bytes\nUsage: {:.2}%",
-            self.system_metrics.swap_used, self.system_metrics.swap_total, swap_used,
-        );
+/// Text of the Memory Info panel: the exact byte counts, cache and buffers
+/// where reported, and the usage percentage
+fn memory_text(collector: &SystemCollector) -> String {
+    let metrics = &collector.system_metrics;
+    let memory_used = metrics.memory_used as f64 / metrics.memory_total as f64 * 100.0;
+    let mut text = format!(
+        "Memory Used: {:.2} Bytes\nAvailable: {:.2} Bytes\nTotal Memory: {:.2} Bytes\nUsage: {:.2}%",
+        metrics.memory_used, metrics.memory_available, metrics.memory_total, memory_used,
+    );
+    if let (Some(cached), Some(buffers)) = (metrics.memory_cached, metrics.memory_buffers) {
+        text.push_str(&format!(
+            "\nCached: {cached} Bytes\nBuffers: {buffers} Bytes"
+        ));
+    }
+    text
+}
+
+/// Text of the Swap Info panel: the exact byte counts and the usage
+/// percentage
+fn swap_text(collector: &SystemCollector) -> String {
+    let metrics = &collector.system_metrics;
+    let swap_used = metrics.swap_used as f64 / metrics.swap_total as f64 * 100.0;
+    format!(
+        "Swap Used: {:.2} Bytes\nTotal Swap: {:.2} Bytes\nUsage: {:.2}%",
+        metrics.swap_used, metrics.swap_total, swap_used,
+    )
+}
 
-        let processes_count_text = format!("CPU count: {}", self.system_metrics.cpu_count);
-        let process_paragraph = paragraph_widget(&processes_count_text, "Processes Count");
+/// Builds a compact gauge filled to the 10 second pressure average, with
+/// the 60 and 300 second averages in its label
+fn pressure_gauge(title: &str, averages: PressureAverages) -> Gauge<'_> {
+    let color = if averages.avg10 >= PRESSURE_CRITICAL_PERCENT {
+        theme().critical
+    } else if averages.avg10 >= PRESSURE_WARNING_PERCENT {
+        theme().warning
+    } else {
+        theme().gauge
+    };
+    let label = format!(
+        "{:.1}%  60s {:.1}%  300s {:.1}%",
+        averages.avg10, averages.avg60, averages.avg300
+    );
+    Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .gauge_style(
+            Style::default()
+                .fg(color)
+                .bg(theme().background)
+                .add_modifier(Modifier::BOLD),
+        )
+        .label(Span::styled(label, Style::default().fg(theme().text)))
+        .ratio((averages.avg10 as f64 / 100.0).clamp(0.0, 1.0))
+}
 
-        let paragraph = paragraph_widget(&text, "Memory Info");
-        let swap_paragraph = paragraph_widget(&text_swap, "Swap Info");
+/// Builds the Anomalies panel, newest first, one line per anomaly with its
+/// time, metric and how many standard deviations it sat from the baseline
+fn anomalies_paragraph(detector: &AnomalyDetector) -> Paragraph<'_> {
+    let lines: Vec<Line> = detector
+        .recent()
+        .map(|anomaly| {
+            Line::from(vec![
+                Span::styled(
+                    anomaly
+                        .time
+                        .with_timezone(&Local)
+                        .format("%H:%M:%S ")
+                        .to_string(),
+                    Style::default().fg(theme().muted),
+                ),
+                Span::styled(anomaly.metric.as_str(), Style::default().fg(theme().text)),
+                Span::styled(
+                    format!(" {:+.1}\u{3c3}", anomaly.sigmas()),
+                    Style::default().fg(theme().critical),
+                ),
+            ])
+        })
+        .collect();
+    let text = if lines.is_empty() {
+        Text::styled("None", Style::default().fg(theme().muted))
+    } else {
+        Text::from(lines)
+    };
+    Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Anomalies"))
+}
 
-        let layout_paragraph = Layout::horizontal([
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-        ])
-        .split(layout[3]);
+/// Number of CPU frequencies per line of the CPU panel
+const FREQUENCIES_PER_LINE: usize = 4;
 
-        frame.render_widget(paragraph, layout_paragraph[0]);
-        frame.render_widget(swap_paragraph, layout_paragraph[1]);
-        frame.render_widget(process_paragraph, layout_paragraph[2]);
+/// Builds the CPU panel with the CPU count and, where cpufreq is
+/// available, the governor, the average frequency against the hardware
+/// range and the frequency of every CPU. The average is drawn in the
+/// warning color while the CPUs look throttled.
+fn cpu_paragraph(cpu_count: usize, frequencies: &[CpuFrequency], cpu_usage: f32) -> Paragraph<'_> {
+    let ghz = |mhz: u64| format!("{:.2}", mhz as f64 / 1000.0);
+    let mut lines = vec![Line::from(format!("CPU count: {cpu_count}"))];
+    if !frequencies.is_empty() {
+        let mut governors: Vec<&str> = frequencies
+            .iter()
+            .filter_map(|frequency| frequency.governor.as_deref())
+            .collect();
+        governors.sort_unstable();
+        governors.dedup();
+        if !governors.is_empty() {
+            lines.push(Line::from(format!("Governor: {}", governors.join(", "))));
+        }
 
-        Ok(())
+        let average = frequencies
+            .iter()
+            .map(|frequency| frequency.current_mhz)
+            .sum::<u64>()
+            / frequencies.len() as u64;
+        let min = frequencies.iter().map(|frequency| frequency.min_mhz).min();
+        let max = frequencies.iter().map(|frequency| frequency.max_mhz).max();
+        let (style, note) = if is_throttled(frequencies, cpu_usage) {
+            (Style::default().fg(theme().warning), "  throttled")
+        } else {
+            (Style::default(), "")
+        };
+        lines.push(Line::styled(
+            format!(
+                "Frequency: {} GHz ({}-{} GHz){note}",
+                ghz(average),
+                ghz(min.unwrap_or_default()),
+                ghz(max.unwrap_or_default())
+            ),
+            style,
+        ));
+        lines.extend(frequencies.chunks(FREQUENCIES_PER_LINE).map(|chunk| {
+            let cells: Vec<String> = chunk
+                .iter()
+                .map(|frequency| format!("{:>3}: {}", frequency.cpu, ghz(frequency.current_mhz)))
+                .collect();
+            Line::styled(cells.join("  "), Style::default().fg(theme().muted))
+        }));
     }
+    Paragraph::new(Text::from(lines)).block(Block::default().borders(Borders::ALL).title("CPU"))
+}
+
+/// Builds the Script panel from the hooks script output.
+///
+/// Shows the script error if there is one, otherwise the derived values
+/// followed by any alerts highlighted in red.
+fn script_paragraph(output: &ScriptOutput) -> Paragraph<'_> {
+    let mut lines: Vec<Line> = Vec::new();
+
+    if let Some(error) = &output.error {
+        lines.push(Line::styled(
+            error.as_str(),
+            Style::default().fg(theme().critical),
+        ));
+    } else {
+        lines.extend(
+            output
+                .values
+                .iter()
+                .map(|(label, value)| Line::from(format!("{label}: {value}"))),
+        );
+        lines.extend(output.alerts.iter().map(|alert| {
+            Line::styled(
+                format!("ALERT: {alert}"),
+                Style::default().fg(theme().critical),
+            )
+        }));
+    }
+
+    Paragraph::new(Text::from(lines)).block(Block::default().borders(Borders::ALL).title("Script"))
 }
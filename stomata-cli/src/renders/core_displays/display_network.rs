@@ -1,59 +1,174 @@
 //! Network metrics display implementation
 //!
 //! Provides real-time visualization of network interface statistics including
-//! traffic rates, packet counts, and error rates. Each network interface gets
-//! its own column with metadata and sparkline charts showing traffic trends.
+//! traffic rates, packet counts, and error rates. Interfaces are listed on the
+//! left and the selected one is shown on the right with metadata and
+//! sparkline charts of its traffic trends. Up/Down select an interface and
+//! Enter opens its detail page with its addresses, MTU and larger charts.
+//! A marker line under the sparklines flags the samples during which an OOM
+//! kill, segfault or service crash was logged. WireGuard and Tailscale peers
+//! are listed below when a tunnel is up.
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Cell, Paragraph, TableState},
+};
+use stomata_core::{
+    NetworkMetrics,
+    collectors::{
+        CrashEvent,
+        network::{
+            metrics::NetworkInterfaces,
+            tunnels::{Tunnel, TunnelPeer},
+            wifi::WifiLink,
+        },
+    },
+    history::AnomalyDetector,
 };
-use stomata_core::NetworkMetrics;
 
 use crate::{
+    constants::STALE_HANDSHAKE_SECS,
     renders::{
-        core_displays::traits::Display,
-        render_widgets::{render_paragraph::paragraph_widget, render_sparkline::render_sparkline},
+        core_displays::{display_crash_events::event_markers, traits::Display},
+        render_widgets::{
+            render_paragraph::paragraph_widget, render_sparkline::render_anomaly_sparkline,
+            render_table::render_table,
+        },
     },
-    structs::{NetworkInterfaceData, UIState},
+    structs::{NetworkInterfaceData, TableRow, UIState},
+    theme::theme,
+    utils::{format_bytes, format_rate},
 };
 
+/// A tunnel peer with the tunnel it belongs to
+struct TunnelPeerRow<'a> {
+    tunnel: &'a Tunnel,
+    peer: &'a TunnelPeer,
+}
+
+/// Implements table row conversion for tunnel peers.
+///
+/// # Column Layout
+///
+/// 1. **Tunnel** (22 chars): VPN kind and interface
+/// 2. **Peer** (flexible): Host name or shortened public key
+/// 3. **Endpoint** (flexible): Current remote endpoint
+/// 4. **Addresses** (flexible): Allowed or Tailscale IPs
+/// 5. **Handshake** (14 chars): Age of the latest handshake, highlighted
+///    when stale
+/// 6. **RX/TX** (20 chars): Bytes received and transmitted
+impl TableRow for TunnelPeerRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let peer = self.peer;
+        let handshake = match peer.last_handshake {
+            Some(time) => {
+                let age = Utc::now().signed_duration_since(time).num_seconds().max(0);
+                let color = if age > STALE_HANDSHAKE_SECS {
+                    theme().warning
+                } else {
+                    theme().ok
+                };
+                Cell::from(format_age(time)).style(Style::default().fg(color))
+            }
+            None => Cell::from("never").style(Style::default().fg(theme().critical)),
+        };
+        let name = match peer.online {
+            Some(false) => format!("{} (offline)", peer.name),
+            _ => peer.name.clone(),
+        };
+        vec![
+            Cell::from(format!(
+                "{} {}",
+                self.tunnel.kind.as_str(),
+                self.tunnel.interface
+            )),
+            Cell::from(name),
+            Cell::from(peer.endpoint.clone().unwrap_or_default()),
+            Cell::from(peer.addresses.as_str()),
+            handshake,
+            Cell::from(format!(
+                "{} / {}",
+                format_bytes(peer.rx_bytes),
+                format_bytes(peer.tx_bytes)
+            )),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(22), // Tunnel
+            Constraint::Min(16),    // Peer
+            Constraint::Min(22),    // Endpoint
+            Constraint::Min(18),    // Addresses
+            Constraint::Length(14), // Handshake
+            Constraint::Length(20), // RX/TX
+        ]
+    }
+}
+
+/// Implements table row conversion for the interface list.
+///
+/// # Column Layout
+///
+/// 1. **Name** (flexible): Interface name
+/// 2. **RX** (11 chars): Receive rate
+/// 3. **TX** (11 chars): Transmit rate
+impl TableRow for NetworkInterfaces {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![
+            Cell::from(self.name.as_str()),
+            Cell::from(format_rate(self.rates.bytes_received)),
+            Cell::from(format_rate(self.rates.bytes_transmitted)),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(10),    // Name
+            Constraint::Length(11), // RX
+            Constraint::Length(11), // TX
+        ]
+    }
+}
+
+/// Formats how long ago `time` was, e.g. `1m 05s ago`
+fn format_age(time: DateTime<Utc>) -> String {
+    let seconds = Utc::now().signed_duration_since(time).num_seconds().max(0);
+    match seconds {
+        0..60 => format!("{seconds}s ago"),
+        60..3600 => format!("{}m {:02}s ago", seconds / 60, seconds % 60),
+        3600..86400 => format!("{}h {:02}m ago", seconds / 3600, seconds % 3600 / 60),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
 /// Display implementation for network interface metrics
 ///
-/// Renders a dynamic multi-column layout where each network interface
-/// (eth0, wlan0, etc.) gets its own vertical section containing:
+/// Renders a selectable list of the network interfaces (eth0, wlan0,
+/// docker bridges, veth pairs, ...) next to the details of the selected one:
 /// - Interface metadata panel (cumulative stats)
 /// - Four sparkline charts showing traffic history
 ///
-/// The display automatically adapts to the number of active interfaces,
-/// distributing screen space equally among them.
+/// The list scrolls, so hosts with many interfaces stay readable.
 impl Display for NetworkMetrics {
-    /// Renders network metrics for all active interfaces
-    ///
-    /// Creates a dynamic layout that scales with the number of network interfaces.
-    /// Each interface displays both current statistics and historical trends.
+    /// Renders the interface list and the selected interface
     ///
     /// # Layout Structure
     ///
     /// ```text
-    /// ┌────────────────────────────────────────┐
-    /// │  Interface 1  │  Interface 2  │  ...   │ (8 lines)
-    /// │   Metadata    │   Metadata    │  ...   │
-    /// ├────────────────────────────────────────┤
-    /// │  Bytes RX     │  Bytes RX     │  ...   │
-    /// │  ▁▂▃▅▇█      │  ▁▂▃▅▇█      │  ...   │
-    /// ├────────────────────────────────────────┤
-    /// │  Bytes TX     │  Bytes TX     │  ...   │
-    /// │  ▁▂▃▅▇█      │  ▁▂▃▅▇█      │  ...   │
-    /// ├────────────────────────────────────────┤
-    /// │  Packets RX   │  Packets RX   │  ...   │
-    /// │  ▁▂▃▅▇█      │  ▁▂▃▅▇█      │  ...   │
-    /// ├────────────────────────────────────────┤
-    /// │  Packets TX   │  Packets TX   │  ...   │
-    /// │  ▁▂▃▅▇█      │  ▁▂▃▅▇█      │  ...   │
-    /// └────────────────────────────────────────┘
+    /// ┌──────────────┬─────────────────────────┐
+    /// │ Interfaces   │  Metadata (8 lines)     │
+    /// │ > eth0  RX TX├─────────────────────────┤
+    /// │   wlan0      │  Received  ▁▂▃▅▇█       │
+    /// │   docker0    │  Transmitted ▁▂▃▅▇█     │
+    /// │   veth1a2b   │  Packets RX ▁▂▃▅▇█      │
+    /// │   ...        │  Packets TX ▁▂▃▅▇█      │
+    /// └──────────────┴─────────────────────────┘
     /// ```
     ///
     /// # Arguments
@@ -69,7 +184,7 @@ impl Display for NetworkMetrics {
     ///
     /// # Metadata Panel Contents
     ///
-    /// Each interface's metadata panel shows cumulative statistics:
+    /// The metadata panel of the selected interface shows cumulative statistics:
     /// - Total bytes received (since boot/interface up)
     /// - Total bytes transmitted
     /// - Total packets received
@@ -79,21 +194,22 @@ impl Display for NetworkMetrics {
     ///
     /// # Sparkline Charts
     ///
-    /// Four sparkline charts per interface showing recent trends:
-    /// 1. **Bytes Received**: Current receive rate with history
-    /// 2. **Bytes Transmitted**: Current transmit rate with history
-    /// 3. **Packets Received**: Current packet receive rate with history
-    /// 4. **Packets Transmitted**: Current packet transmit rate with history
+    /// Four sparkline charts of the selected interface showing recent trends:
+    /// 1. **Received**: Receive rate with history, e.g. `1.5 MiB/s`
+    /// 2. **Transmitted**: Transmit rate with history
+    /// 3. **Packets Received**: Packets received per second with history
+    /// 4. **Packets Transmitted**: Packets transmitted per second with history
     ///
-    /// Each sparkline displays the most recent data point in the title
-    /// and shows historical trend as a mini ASCII chart.
+    /// Each sparkline displays the current rate in the title and shows
+    /// historical trend as a mini ASCII chart.
     ///
     /// # State Management
     ///
     /// Historical data for sparklines is maintained in `ui_state.networks_state`,
     /// which is a `HashMap<String, NetworkInterfaceData>` keyed by interface name.
-    /// Each interface maintains a rolling buffer of recent values for smooth
-    /// trend visualization.
+    /// Every interface records its history, not only the selected one, so
+    /// the charts are already filled when the selection moves. The selection
+    /// is kept in `ui_state.network_table`.
     ///
     /// # Examples
     ///
@@ -101,7 +217,7 @@ impl Display for NetworkMetrics {
     /// use stomata_core::NetworkMetrics;
     /// use stomata::renders::core_displays::traits::Display;
     ///
-    /// let network_metrics = NetworkMetrics::fetch();
+    /// let network_metrics = NetworkMetrics::fetch(&metrics.sysinfo(), &metrics.network_rates);
     /// network_metrics.display(frame, area, Some(&mut ui_state))?;
     /// ```
     ///
@@ -115,86 +231,300 @@ impl Display for NetworkMetrics {
         area: Rect,
         ui_state: Option<&mut UIState>,
     ) -> anyhow::Result<()> {
+        let rows: Vec<TunnelPeerRow> = self
+            .tunnels
+            .tunnels
+            .iter()
+            .flat_map(|tunnel| {
+                tunnel
+                    .peers
+                    .iter()
+                    .map(move |peer| TunnelPeerRow { tunnel, peer })
+            })
+            .collect();
+        let show_tunnels = !rows.is_empty() || !self.tunnels.errors.is_empty();
+        // rows plus borders, header and the error line, leaving room for the interfaces
+        let tunnel_height = if show_tunnels {
+            (rows.len() as u16 + 4).min(area.height / 3)
+        } else {
+            0
+        };
+
         let parent_layout =
-            Layout::vertical([Constraint::Length(8), Constraint::Min(1)]).split(area);
-
-        let number_of_interfaces: u16 = self.interfaces.len().try_into().unwrap_or(5);
-        let constraints =
-            vec![Constraint::Percentage(100 / number_of_interfaces); number_of_interfaces.into()];
-
-        let para_layout = Layout::horizontal(&constraints).split(parent_layout[0]);
-        let sparkline_layout = Layout::horizontal(&constraints).split(parent_layout[1]);
-
-        if let Some(ui_state) = ui_state {
-            let map = ui_state.networks_state.get_or_insert(HashMap::new());
-
-            for (index, interface) in self.interfaces.iter().enumerate() {
-                let iface = map
-                    .entry(interface.name.clone())
-                    .or_insert_with(NetworkInterfaceData::default);
-
-                iface.update_network_history(interface);
-
-                // -- para widgets --
-                let interface_metadata_info = format!(
-                    "Total Bytes received: {}\nTotal Bytes Transmitted: {}\nTotal Packets Received: {}\nTotal Packets Transmitted: {}\nTotal Errors on receive: {}\nTotal Errors on transmit: {}",
-                    interface.total_bytes_received,
-                    interface.total_bytes_transmitted,
-                    interface.total_packets_received,
-                    interface.total_packets_transmitted,
-                    interface.total_errors_on_received,
-                    interface.total_errors_on_transmitted
-                );
-                let metadata_para_widget =
-                    paragraph_widget(&interface_metadata_info, &interface.name);
-
-                // -- sparkline widgets --
-                let received_bytes_sparkline_title =
-                    format!("Bytes received: {}", interface.bytes_received);
-
-                let transmitted_bytes_sparkline_title =
-                    format!("Bytes transmitted: {}", interface.bytes_transmitted);
-
-                let packets_received_sparkline_title =
-                    format!("Packets received: {}", interface.packets_received);
-
-                let packets_transmitted_sparkline_title =
-                    format!("Packets transmitted: {}", interface.packets_transmitted);
-
-                //-- widgets --
-                let sparkline_widgets = vec![
-                    render_sparkline(
-                        iface.received_bytes.make_contiguous(),
-                        &received_bytes_sparkline_title,
-                    ),
-                    render_sparkline(
-                        iface.transmitted_bytes.make_contiguous(),
-                        &transmitted_bytes_sparkline_title,
-                    ),
-                    render_sparkline(
-                        iface.packets_received.make_contiguous(),
-                        &packets_received_sparkline_title,
-                    ),
-                    render_sparkline(
-                        iface.packets_transmitted.make_contiguous(),
-                        &packets_transmitted_sparkline_title,
-                    ),
-                ];
-
-                let secondart_constraints =
-                    vec![
-                        Constraint::Percentage(100 / sparkline_widgets.len() as u16);
-                        sparkline_widgets.len()
-                    ];
-                let secondary_layout =
-                    Layout::vertical(&secondart_constraints).split(sparkline_layout[index]);
-
-                for (widget_index, widget) in sparkline_widgets.iter().enumerate() {
-                    frame.render_widget(widget, secondary_layout[widget_index]);
-                }
-                frame.render_widget(metadata_para_widget, para_layout[index]);
-            }
+            Layout::vertical([Constraint::Min(1), Constraint::Length(tunnel_height)]).split(area);
+
+        if show_tunnels {
+            let [table_area, error_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)])
+                    .areas(parent_layout[1]);
+            let headers = vec![
+                "Tunnel",
+                "Peer",
+                "Endpoint",
+                "Addresses",
+                "Handshake",
+                "RX/TX",
+            ];
+            frame.render_stateful_widget(
+                render_table(headers, &rows, "Tunnels"),
+                table_area,
+                &mut TableState::default(),
+            );
+            frame.render_widget(
+                Paragraph::new(self.tunnels.errors.join("  "))
+                    .style(Style::default().fg(theme().warning)),
+                error_area,
+            );
+        }
+
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+        update_interface_history(self, ui_state);
+        ui_state.network_table.set_count(self.interfaces.len());
+        ui_state.network_names = self
+            .interfaces
+            .iter()
+            .map(|interface| interface.name.clone())
+            .collect();
+
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Length(38), Constraint::Min(20)])
+                .areas(parent_layout[0]);
+        let title = format!("Interfaces ({}) - Enter: details", self.interfaces.len());
+        frame.render_stateful_widget(
+            render_table(vec!["Name", "RX", "TX"], &self.interfaces, &title),
+            list_area,
+            &mut ui_state.network_table.list,
+        );
+
+        let Some(interface) = ui_state
+            .network_table
+            .list
+            .selected()
+            .and_then(|index| self.interfaces.get(index))
+        else {
+            return Ok(());
+        };
+        let [metadata_area, sparklines_area, markers_area] = Layout::vertical([
+            Constraint::Length(8),
+            Constraint::Min(4),
+            Constraint::Length(1),
+        ])
+        .areas(detail_area);
+
+        let interface_metadata_info = format!(
+            "Total Bytes received: {}\nTotal Bytes Transmitted: {}\nTotal Packets Received: {}\nTotal Packets Transmitted: {}\nTotal Errors on receive: {}\nTotal Errors on transmit: {}",
+            interface.total_bytes_received,
+            interface.total_bytes_transmitted,
+            interface.total_packets_received,
+            interface.total_packets_transmitted,
+            interface.total_errors_on_received,
+            interface.total_errors_on_transmitted
+        );
+        frame.render_widget(
+            paragraph_widget(&interface_metadata_info, &interface.name),
+            metadata_area,
+        );
+
+        let events = ui_state.crash_events.events();
+        if let Some(history) = ui_state
+            .networks_state
+            .as_mut()
+            .and_then(|map| map.get_mut(&interface.name))
+        {
+            let chart_areas: [Rect; 4] =
+                Layout::vertical([Constraint::Ratio(1, 4); 4]).areas(sparklines_area);
+            render_interface_charts(
+                frame,
+                chart_areas,
+                markers_area,
+                history,
+                interface,
+                events,
+                &ui_state.anomalies,
+            );
         }
         Ok(())
     }
 }
+
+/// Pushes the latest rates of every interface to its history and the
+/// anomaly detector, unless collection is paused
+fn update_interface_history(metrics: &NetworkMetrics, ui_state: &mut UIState) {
+    let map = ui_state.networks_state.get_or_insert(HashMap::new());
+    let now = Utc::now();
+    for interface in &metrics.interfaces {
+        let history = map.entry(interface.name.clone()).or_default();
+        if !ui_state.paused {
+            history.update_network_history(interface);
+            let rates = &interface.rates;
+            for (direction, rate) in [
+                ("received", rates.bytes_received),
+                ("transmitted", rates.bytes_transmitted),
+            ] {
+                ui_state
+                    .anomalies
+                    .observe(&format!("{} {direction}", interface.name), now, rate);
+            }
+        }
+    }
+}
+
+/// Renders the received/transmitted bytes and packets sparklines of an
+/// interface, with the crash event markers below them. Bars far from the
+/// rolling baseline of `detector` are highlighted.
+fn render_interface_charts(
+    frame: &mut Frame,
+    chart_areas: [Rect; 4],
+    markers_area: Rect,
+    history: &mut NetworkInterfaceData,
+    interface: &NetworkInterfaces,
+    events: &[CrashEvent],
+    detector: &AnomalyDetector,
+) {
+    let rates = &interface.rates;
+    let titles = [
+        format!("Received: {}", format_rate(rates.bytes_received)),
+        format!("Transmitted: {}", format_rate(rates.bytes_transmitted)),
+        format!("Packets received: {:.0}/s", rates.packets_received),
+        format!("Packets transmitted: {:.0}/s", rates.packets_transmitted),
+    ];
+    frame.render_widget(
+        Paragraph::new(event_markers(history.received_bytes.times(), events)),
+        markers_area,
+    );
+    let series = [
+        &history.received_bytes,
+        &history.transmitted_bytes,
+        &history.packets_received,
+        &history.packets_transmitted,
+    ];
+    for ((series, title), area) in series.iter().zip(&titles).zip(chart_areas) {
+        let anomalies = series.anomalies(detector.window, detector.sigmas);
+        frame.render_widget(
+            render_anomaly_sparkline(&series.to_vec(), &anomalies, title),
+            area,
+        );
+    }
+}
+
+/// Text of the Wi-Fi panel: SSID, signal, channel and bitrates
+fn wifi_summary(wifi: &WifiLink) -> String {
+    if let Some(error) = &wifi.error {
+        return error.clone();
+    }
+    let Some(ssid) = &wifi.ssid else {
+        return "Not connected".to_string();
+    };
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    format!(
+        "SSID: {ssid}\nSignal: {}\nChannel: {}\nRX bitrate: {}\nTX bitrate: {}",
+        or_dash(
+            wifi.signal_dbm
+                .map(|dbm| format!("{dbm} dBm ({}%)", wifi.quality().unwrap_or_default()))
+        ),
+        or_dash(wifi.frequency_mhz.map(|freq| match wifi.channel() {
+            Some(channel) => format!("{channel} ({freq} MHz)"),
+            None => format!("{freq} MHz"),
+        })),
+        or_dash(wifi.rx_bitrate_mbps.map(|rate| format!("{rate:.1} Mbit/s"))),
+        or_dash(wifi.tx_bitrate_mbps.map(|rate| format!("{rate:.1} Mbit/s"))),
+    )
+}
+
+/// Renders the detail page of one interface: its addresses, MTU,
+/// counters and Wi-Fi link above larger charts of its traffic.
+///
+/// Shows a notice instead when the interface went away.
+pub fn render_interface_detail(
+    frame: &mut Frame,
+    area: Rect,
+    metrics: &NetworkMetrics,
+    name: &str,
+    ui_state: &mut UIState,
+) {
+    update_interface_history(metrics, ui_state);
+    let Some(interface) = metrics
+        .interfaces
+        .iter()
+        .find(|interface| interface.name == name)
+    else {
+        frame.render_widget(
+            paragraph_widget(
+                &format!("\n\nInterface {name} is gone\n\nEsc: back to the Network page"),
+                name,
+            )
+            .alignment(ratatui::layout::Alignment::Center),
+            area,
+        );
+        return;
+    };
+
+    let [info_area, charts_area, markers_area] = Layout::vertical([
+        Constraint::Length(9),
+        Constraint::Min(8),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+    let info_columns = if interface.wifi.is_some() { 3 } else { 2 };
+    let info_areas = Layout::horizontal(vec![
+        Constraint::Ratio(1, info_columns);
+        info_columns as usize
+    ])
+    .split(info_area);
+    let (addresses_area, counters_area) = (info_areas[0], info_areas[1]);
+    if let Some(wifi) = &interface.wifi {
+        frame.render_widget(
+            paragraph_widget(&wifi_summary(wifi), "Wi-Fi"),
+            info_areas[2],
+        );
+    }
+
+    let addresses = if interface.ip_networks.is_empty() {
+        "none".to_string()
+    } else {
+        interface.ip_networks.join("\n    ")
+    };
+    let info = format!(
+        "MAC: {}\nMTU: {}\nIPs: {}",
+        interface.mac_address, interface.mtu, addresses
+    );
+    let title = format!("{name} (Esc: back)");
+    frame.render_widget(paragraph_widget(&info, &title), addresses_area);
+
+    let counters = format!(
+        "Received: {}, {} packets\nTransmitted: {}, {} packets\nErrors on receive: {}\nErrors on transmit: {}",
+        format_bytes(interface.total_bytes_received),
+        interface.total_packets_received,
+        format_bytes(interface.total_bytes_transmitted),
+        interface.total_packets_transmitted,
+        interface.total_errors_on_received,
+        interface.total_errors_on_transmitted
+    );
+    frame.render_widget(paragraph_widget(&counters, "Totals"), counters_area);
+
+    let [top, bottom] = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .areas(charts_area);
+    let [received, transmitted] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(top);
+    let [packets_received, packets_transmitted] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+
+    let events = ui_state.crash_events.events();
+    if let Some(history) = ui_state
+        .networks_state
+        .as_mut()
+        .and_then(|map| map.get_mut(name))
+    {
+        render_interface_charts(
+            frame,
+            [received, transmitted, packets_received, packets_transmitted],
+            markers_area,
+            history,
+            interface,
+            events,
+            &ui_state.anomalies,
+        );
+    }
+}
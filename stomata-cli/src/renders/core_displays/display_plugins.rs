@@ -0,0 +1,113 @@
+//! Plugin metrics display implementation
+//!
+//! Renders the output of external plugin executables discovered in the
+//! plugins directory. Each plugin gets its own column with a text panel
+//! for plain values and a gauge for every value that reports a maximum.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+};
+use stomata_core::PluginMetrics;
+
+use crate::{
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{render_gauge::render_gauge, render_paragraph::paragraph_widget},
+    },
+    structs::UIState,
+};
+
+/// Display implementation for plugin metrics
+///
+/// Splits the area horizontally, one column per plugin. Within a column,
+/// values without a `max` are listed in a paragraph and values with a `max`
+/// are rendered as gauges below it. Plugins that failed to run show their
+/// error instead.
+impl Display for PluginMetrics {
+    /// Renders all plugin outputs, or a hint on where to install plugins
+    /// when none were discovered.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the plugins page
+    /// * `_ui_state` - Unused for this display
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        _ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if self.plugins.is_empty() {
+            let dir = self
+                .plugins_dir
+                .as_ref()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_else(|| "the stomata config directory".to_string());
+            let text = format!(
+                "\n\nNo plugins found.\n\nAdd executables to {dir} that print JSON metrics on stdout, e.g.\n{{\"title\": \"GPU\", \"metrics\": [{{\"label\": \"temp\", \"value\": 61, \"unit\": \"C\", \"max\": 100}}]}}"
+            );
+            let paragraph = paragraph_widget(&text, "Plugins");
+            frame.render_widget(
+                paragraph.alignment(ratatui::layout::Alignment::Center),
+                area,
+            );
+            return Ok(());
+        }
+
+        let number_of_plugins = self.plugins.len() as u32;
+        let constraints = vec![Constraint::Ratio(1, number_of_plugins); self.plugins.len()];
+        let columns = Layout::horizontal(&constraints).split(area);
+
+        for (index, plugin) in self.plugins.iter().enumerate() {
+            let title = plugin.record.title.as_deref().unwrap_or(&plugin.name);
+
+            if plugin.pending {
+                frame.render_widget(paragraph_widget("Running…", title), columns[index]);
+                continue;
+            }
+
+            if let Some(error) = &plugin.error {
+                let text = format!("Plugin failed: {error}");
+                frame.render_widget(paragraph_widget(&text, title), columns[index]);
+                continue;
+            }
+
+            let (gauges, values): (Vec<_>, Vec<_>) = plugin
+                .record
+                .metrics
+                .iter()
+                .partition(|metric| metric.max.is_some());
+
+            let text = values
+                .iter()
+                .map(|metric| {
+                    format!(
+                        "{}: {} {}",
+                        metric.label,
+                        metric.value,
+                        metric.unit.as_deref().unwrap_or("")
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            let mut row_constraints = vec![Constraint::Min(3)];
+            row_constraints.extend(vec![Constraint::Length(3); gauges.len()]);
+            let rows = Layout::vertical(row_constraints).split(columns[index]);
+
+            frame.render_widget(paragraph_widget(&text, title), rows[0]);
+            for (gauge_index, metric) in gauges.iter().enumerate() {
+                let gauge = render_gauge(
+                    metric.value,
+                    metric.max.unwrap_or(100.0),
+                    &metric.label,
+                    metric.unit.as_deref().unwrap_or(""),
+                );
+                frame.render_widget(gauge, rows[gauge_index + 1]);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+//! Kubernetes pods display implementation
+//!
+//! Renders the pods running on this node with the resource usage of their
+//! local processes summed per pod. Processes are mapped to pods through
+//! their cgroup paths, so this works without API server credentials.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Rect},
+    widgets::Cell,
+};
+use stomata_core::collectors::kubernetes::{PodMetrics, PodUsage};
+
+use crate::{
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{render_paragraph::paragraph_widget, render_table::render_table},
+    },
+    structs::{TableRow, UIState},
+    utils::format_bytes,
+};
+
+/// Implements table row conversion for per-pod usage.
+///
+/// # Column Layout
+///
+/// 1. **Namespace** (16 chars): Pod namespace, empty if unknown
+/// 2. **Pod** (flexible): Pod name, or the pod UID if the name is unknown
+/// 3. **QoS** (11 chars): Quality of service class
+/// 4. **Ctrs** (5 chars): Containers with running processes
+/// 5. **Procs** (7 chars): Number of processes
+/// 6. **CPU%** (10 chars): Summed CPU usage
+/// 7. **Memory** (12 chars): Summed resident memory
+/// 8. **Read** (12 chars): Disk bytes read since the last refresh
+/// 9. **Write** (12 chars): Disk bytes written since the last refresh
+impl TableRow for PodUsage {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![
+            Cell::from(self.namespace.as_deref().unwrap_or("")),
+            Cell::from(self.name.as_deref().unwrap_or(&self.uid)),
+            Cell::from(self.qos.as_str()),
+            Cell::from(self.containers.to_string()),
+            Cell::from(self.pids.len().to_string()),
+            Cell::from(format!("{:.2}%", self.cpu_usage)),
+            Cell::from(format_bytes(self.memory)),
+            Cell::from(format_bytes(self.disk_read_bytes)),
+            Cell::from(format_bytes(self.disk_written_bytes)),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(16), // Namespace
+            Constraint::Min(24),    // Pod
+            Constraint::Length(11), // QoS
+            Constraint::Length(5),  // Ctrs
+            Constraint::Length(7),  // Procs
+            Constraint::Length(10), // CPU%
+            Constraint::Length(12), // Memory
+            Constraint::Length(12), // Read
+            Constraint::Length(12), // Write
+        ]
+    }
+}
+
+/// Display implementation for Kubernetes pods
+impl Display for PodMetrics {
+    /// Renders the pods table, busiest pod first, or a hint when this host
+    /// isn't a Kubernetes node.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the pods page
+    /// * `ui_state` - Table selection state, the table is not rendered
+    ///   without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if !self.kubelet_detected {
+            let paragraph = paragraph_widget(
+                "\n\nNo kubelet detected.\n\nRun stomata on a Kubernetes node to see per-pod resource usage.",
+                "Pods",
+            );
+            frame.render_widget(paragraph.alignment(Alignment::Center), area);
+            return Ok(());
+        }
+
+        let headers = vec![
+            "Namespace",
+            "Pod",
+            "QoS",
+            "Ctrs",
+            "Procs",
+            "CPU",
+            "Memory",
+            "Read",
+            "Write",
+        ];
+        let title = format!("Pods ({})", self.pods.len());
+        let table = render_table(headers, &self.pods, &title);
+
+        if let Some(ui_state) = ui_state {
+            ui_state.pods_table.set_count(self.pods.len());
+            frame.render_stateful_widget(table, area, &mut ui_state.pods_table.list);
+        }
+        Ok(())
+    }
+}
@@ -2,20 +2,228 @@
 //!
 //! Provides an interactive table view of all running processes with sortable
 //! columns and keyboard navigation. Users can select processes to view detailed
-//! information about individual processes.
+//! information about individual processes, or group them by user or
+//! executable name with the summed usage of each group. Newly started processes are
+//! highlighted for a few seconds and the ones that exited are listed below
+//! the table. The list can be narrowed to zombie or orphaned processes, or
+//! searched by name and command line.
 
-use ratatui::{Frame, layout::Rect};
-use stomata_core::collectors::process::metrics::ProcessData;
+use std::{collections::HashMap, path::Path};
+
+use chrono::{Duration, Local, Utc};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph},
+};
+use stomata_core::collectors::{
+    cgroups::collectors::container_label,
+    process::{ProcessGroup, ProcessGroupBy, ProcessLifetimes, metrics::ProcessData},
+};
 
 use crate::{
-    renders::{core_displays::traits::Display, render_widgets::render_table::render_table},
-    structs::UIState,
+    constants::{COMMAND_SCROLL_STEP, NEW_PROCESS_HIGHLIGHT_SECS, RECENTLY_EXITED_PANEL_HEIGHT},
+    features::core::process_columns::ProcessColumn,
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{
+            render_input::input_widget, render_scrollbar::render_scrollbar,
+            render_table::render_table,
+        },
+    },
+    structs::{TableRow, UIState},
+    theme::theme,
+    utils::{format_bytes, format_rate},
 };
 
+/// A process table row, with the command shown either in full or with the
+/// directory of the program stripped
+struct ProcessRow<'a> {
+    process: &'a ProcessData,
+    full_command: bool,
+    /// Started within the last `NEW_PROCESS_HIGHLIGHT_SECS`
+    new: bool,
+    /// Shown under its group, with the PID indented
+    nested: bool,
+    /// Columns to show, in order
+    columns: &'a [ProcessColumn],
+    /// Characters of the command line scrolled out of view
+    command_shift: usize,
+}
+
+impl ProcessRow<'_> {
+    /// Command line of the process; kernel threads have none and show
+    /// their name in brackets like `ps` does
+    fn command(&self) -> String {
+        let Some((program, args)) = self.process.command.split_first() else {
+            return format!("[{}]", self.process.name);
+        };
+        let program = if self.full_command {
+            program.as_str()
+        } else {
+            Path::new(program)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(program)
+        };
+        std::iter::once(program)
+            .chain(args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Implements table row conversion for processes.
+///
+/// The cells follow `columns`, see [`ProcessColumn`] for their headers and
+/// widths. Memory values are shown with a unit, e.g. `45.2 MiB`; the disk
+/// rates are blank until the process was seen in two refreshes, and the
+/// swap and thread counts where they can't be read.
+impl TableRow for ProcessRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let cells = self.columns.iter().map(|column| self.cell(*column));
+        if self.new {
+            let style = Style::default().fg(theme().ok);
+            cells.map(|cell| cell.style(style)).collect()
+        } else {
+            cells.collect()
+        }
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        ProcessColumn::DEFAULT
+            .iter()
+            .map(|column| column.width())
+            .collect()
+    }
+}
+
+impl ProcessRow<'_> {
+    /// Cell of the process in `column`
+    fn cell(&self, column: ProcessColumn) -> Cell<'_> {
+        let process = self.process;
+        match column {
+            ProcessColumn::Pid if self.nested => Cell::from(format!("  {}", process.pid)),
+            ProcessColumn::Pid => Cell::from(process.pid.to_string()),
+            ProcessColumn::User => Cell::from(process.user.clone().unwrap_or_default()),
+            // badge processes running a binary that was deleted or replaced on disk
+            ProcessColumn::Name if process.exe_deleted => Cell::from(Line::from(vec![
+                Span::styled("[deleted] ", Style::default().fg(theme().warning)),
+                Span::raw(process.name.as_str()),
+            ])),
+            ProcessColumn::Name => Cell::from(process.name.as_str()),
+            ProcessColumn::Cpu => Cell::from(format!("{:.2}%", process.cpu_usage)),
+            ProcessColumn::Memory => Cell::from(format_bytes(process.memory)),
+            ProcessColumn::Swap => Cell::from(process.swap.map(format_bytes).unwrap_or_default()),
+            ProcessColumn::Read => {
+                Cell::from(process.disk_read_rate.map(format_rate).unwrap_or_default())
+            }
+            ProcessColumn::Write => {
+                Cell::from(process.disk_write_rate.map(format_rate).unwrap_or_default())
+            }
+            ProcessColumn::Threads => Cell::from(
+                process
+                    .threads
+                    .map(|threads| threads.to_string())
+                    .unwrap_or_default(),
+            ),
+            ProcessColumn::Status => status_cell(process),
+            ProcessColumn::Container => Cell::from(
+                process
+                    .cgroup
+                    .as_deref()
+                    .map(container_label)
+                    .unwrap_or_default(),
+            ),
+            ProcessColumn::Command => Cell::from(
+                self.command()
+                    .chars()
+                    .skip(self.command_shift)
+                    .collect::<String>(),
+            ),
+        }
+    }
+}
+
+/// Whether the name or command line of `process` contains `search`,
+/// ignoring case; every process matches an empty search
+fn matches_search(process: &ProcessData, search: &str) -> bool {
+    let search = search.to_lowercase();
+    search.is_empty()
+        || process.name.to_lowercase().contains(&search)
+        || process.command.join(" ").to_lowercase().contains(&search)
+}
+
+/// Status of a process, in the critical color for zombies and with an
+/// `orphan` note in the warning color for orphans
+fn status_cell(process: &ProcessData) -> Cell<'_> {
+    if process.is_zombie() {
+        Cell::from(process.status.as_str()).style(Style::default().fg(theme().critical))
+    } else if process.is_orphan() {
+        Cell::from(format!("{} (orphan)", process.status))
+            .style(Style::default().fg(theme().warning))
+    } else {
+        Cell::from(process.status.as_str())
+    }
+}
+
+/// A row of the process list: a group of processes sharing a user or a
+/// name, or a single process
+enum ListRow<'a> {
+    Group {
+        group: &'a ProcessGroup,
+        by: ProcessGroupBy,
+        expanded: bool,
+        columns: &'a [ProcessColumn],
+    },
+    Process(ProcessRow<'a>),
+}
+
+/// Group rows show an expand marker in the PID column, the group key in the
+/// User or Name column, the summed CPU, memory and threads, and the number of
+/// processes in the Status column.
+impl TableRow for ListRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let (group, by, expanded, columns) = match self {
+            ListRow::Process(row) => return row.to_cells(),
+            ListRow::Group {
+                group,
+                by,
+                expanded,
+                columns,
+            } => (group, *by, *expanded, *columns),
+        };
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        columns
+            .iter()
+            .map(|column| {
+                let cell = match (column, by) {
+                    (ProcessColumn::Pid, _) if expanded => Cell::from("\u{25be}"),
+                    (ProcessColumn::Pid, _) => Cell::from("\u{25b8}"),
+                    (ProcessColumn::User, ProcessGroupBy::User)
+                    | (ProcessColumn::Name, ProcessGroupBy::Name) => Cell::from(group.key.as_str()),
+                    (ProcessColumn::Cpu, _) => Cell::from(format!("{:.2}%", group.cpu_usage)),
+                    (ProcessColumn::Memory, _) => Cell::from(format_bytes(group.memory)),
+                    (ProcessColumn::Threads, _) => Cell::from(group.threads.to_string()),
+                    (ProcessColumn::Status, _) => Cell::from(format!("{} procs", group.pids.len())),
+                    _ => Cell::default(),
+                };
+                cell.style(style)
+            })
+            .collect()
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        ProcessRow::column_widths()
+    }
+}
+
 /// Display implementation for process list
 ///
 /// Renders all running processes as an interactive table with columns for
-/// PID, name, CPU usage, memory usage, and status. The table supports
+/// PID, user, name, CPU usage, memory usage, status and command line. The table supports
 /// keyboard navigation and selection tracking for drilling down into
 /// individual process details.
 impl Display for Vec<ProcessData> {
@@ -28,16 +236,15 @@ impl Display for Vec<ProcessData> {
     /// # Table Structure
     ///
     /// ```text
-    /// ┌─────────────────────────────────────────┐
-    /// │               Processes                  │
-    /// ├─────┬────────────┬─────┬────────┬───────┤
-    /// │ PID │ Name       │ CPU │ Memory │Status │
-    /// ├─────┼────────────┼─────┼────────┼───────┤
-    /// │ 1   │ systemd    │ 0.1 │  45 MB │Running│
-    /// │ 123 │ firefox    │ 5.2 │ 850 MB │Running│
-    /// │ 456 │ code       │ 3.1 │ 420 MB │Sleeping│
-    /// │ ... │ ...        │ ... │ ...    │ ...   │
-    /// └─────┴────────────┴─────┴────────┴───────┘
+    /// ┌──────────────────────────────────────────────────────────────────────────┐
+    /// │                                Processes                                 │
+    /// ├─────┬───────┬─────────┬─────┬────────┬───────┬─────┬────────┬───────────┤
+    /// │ PID │ User  │ Name    │ CPU │ Memory │ R / W │ Thr │ Status │ Command   │
+    /// ├─────┼───────┼─────────┼─────┼────────┼───────┼─────┼────────┼───────────┤
+    /// │ 1   │ root  │ systemd │ 0.1 │ 45 MiB │ 0 / 0 │ 1   │Sleeping│ systemd   │
+    /// │ 123 │ alice │ firefox │ 5.2 │850 MiB │ 2M/1M │ 92  │Running │ firefox   │
+    /// │ ... │ ...   │ ...     │ ... │ ...    │ ...   │ ... │ ...    │ ...       │
+    /// └─────┴───────┴─────────┴─────┴────────┴───────┴─────┴────────┴───────────┘
     /// ```
     ///
     /// # Arguments
@@ -54,22 +261,71 @@ impl Display for Vec<ProcessData> {
     /// # Table Columns
     ///
     /// - **PID**: Process ID (unique system identifier)
-    /// - **Name**: Process name/command
+    /// - **User**: Owner of the process
+    /// - **Name**: Process name, prefixed with a `[deleted]` badge when
+    ///   the executable was deleted or replaced on disk
     /// - **CPU**: Current CPU usage percentage
     /// - **Memory**: Current memory consumption
-    /// - **Status**: Process state (Running, Sleeping, Stopped, Zombie, etc.)
+    /// - **Swap**: Memory of the process swapped out, hidden by default
+    /// - **Read** / **Write**: Disk throughput of the process per second,
+    ///   computed in stomata-core from consecutive disk usage snapshots
+    /// - **Threads**: Number of threads of the process
+    /// - **Container**: Container or systemd unit the process belongs to,
+    ///   hidden by default
+    /// - **Status**: Process state (Running, Sleeping, Stopped, Zombie, etc.),
+    ///   red for zombies and marked `(orphan)` for processes re-parented
+    ///   to PID 1
+    /// - **Command**: Command line, with the program's directory stripped
+    ///   unless `process_table.full_command` is set
+    ///
+    /// The columns shown and their order come from `process_table.columns`,
+    /// set from `[processes] columns` in the config file and changed in the
+    /// column editor.
+    ///
+    /// Rows of processes started in the last `NEW_PROCESS_HIGHLIGHT_SECS`
+    /// are drawn in green, and a Recently exited panel below the table
+    /// lists the processes gone from the list with how long they were seen
+    /// running, which makes crash-looping daemons stand out.
     ///
     /// # Interactive Features
     ///
-    /// - **Keyboard Navigation**: Up/Down arrow keys to select processes
+    /// - **Keyboard Navigation**: Up/Down arrow keys to select processes,
+    ///   PageUp/PageDown to move by a screen, Home/End or `gg`/`G` to jump
+    ///   to the first or last row; a scrollbar shows the position within
+    ///   the whole list
     /// - **Selection Tracking**: Selected PID is stored in `ui_state.process_table.selected_pid`
     /// - **Enter Key**: Press Enter on a selected process to view detailed metrics
+    /// - **t Key**: Sort by PID, CPU, memory, swap, disk reads, then disk writes;
+    ///   the sort column is shown in the title
+    /// - **c Key**: Toggle between the short and the full command line
+    /// - **u Key**: Group the processes by user, then by executable name,
+    ///   then show the flat list again; Enter expands or collapses the
+    ///   selected group
+    /// - **C Key**: Show or hide the Container column
+    /// - **S Key**: Show or hide the Swap column
+    /// - **h/l Keys**: Scroll the columns left or right with the PID column
+    ///   pinned, then the command line once the last column is reached
+    /// - **/ Key**: Search the processes by name or command line
+    /// - **v Key**: Open the column editor to show, hide and reorder the
+    ///   columns
+    /// - **f Key**: List only the processes in the cgroup of the selected
+    ///   process, press again to list every process
+    /// - **z Key**: List only the zombies, then only the orphans, then
+    ///   every process again
+    /// - **s Key**: Open the signals menu for the selected process; the
+    ///   result of the last signal is shown in the title
+    /// - **x Key**: Export the process list to a CSV file
     ///
     /// # State Management
     ///
     /// The UI state maintains:
     /// - `process_table.process_list`: Ratatui's `TableState` for row selection
-    /// - `process_table.selected_pid`: The PID of the currently selected process
+    /// - `process_table.selected_pid`: The PID of the currently selected process;
+    ///   on the next render the selection moves to wherever that PID is
+    ///   listed then, so a refresh reordering the rows doesn't change the
+    ///   selected process. Group rows are followed by their key the same
+    ///   way, and the selected index is only kept when the selected
+    ///   process or group is gone
     /// - `process_table.process_count`: Total number of processes for bounds checking
     ///
     /// When a process is selected, its PID is stored for navigation to the
@@ -100,18 +356,342 @@ impl Display for Vec<ProcessData> {
         area: Rect,
         ui_state: Option<&mut UIState>,
     ) -> anyhow::Result<()> {
-        let headers = vec!["PID", "Name", "CPU", "Memory", "Status"];
-        let table_widget = render_table(headers, &self, "Processes");
         if let Some(ui_state) = ui_state {
-            if let Some(selected_index) = ui_state.process_table.process_list.selected() {
-                ui_state.process_table.selected_pid = Some(self[selected_index].pid);
+            let table = &ui_state.process_table;
+            // the search box only takes space while it is used
+            let search_height = if table.searching || !table.search.value.is_empty() {
+                3
+            } else {
+                0
+            };
+            let [search_area, table_area, exited_area] = Layout::vertical([
+                Constraint::Length(search_height),
+                Constraint::Min(0),
+                Constraint::Length(RECENTLY_EXITED_PANEL_HEIGHT),
+            ])
+            .areas(area);
+            if search_height > 0 {
+                let search_title = if table.searching {
+                    format!("{} - Enter: keep  Esc: clear", table.search.label)
+                } else {
+                    format!("{} - /: edit", table.search.label)
+                };
+                frame.render_widget(
+                    input_widget(&table.search.value, &search_title, false, table.searching),
+                    search_area,
+                );
+            }
+            let mut processes: Vec<ProcessData> = self
+                .iter()
+                .filter(|process| {
+                    table
+                        .cgroup_filter
+                        .as_ref()
+                        .is_none_or(|cgroup| process.cgroup.as_ref() == Some(cgroup))
+                        && table
+                            .state_filter
+                            .is_none_or(|state| state.matches(process))
+                        && matches_search(process, &table.search.value)
+                })
+                .cloned()
+                .collect();
+            table.sort.sort(&mut processes);
+            let processes = &processes;
+            let longest_command = processes
+                .iter()
+                .map(|process| process.command.iter().map(|arg| arg.len() + 1).sum())
+                .max()
+                .unwrap_or_default();
+            let (columns, horizontal_scroll, command_shift) = scroll_columns(
+                &table.columns.visible(),
+                table.horizontal_scroll,
+                longest_command,
+            );
+            let headers: Vec<&str> = columns.iter().map(|column| column.header()).collect();
+            let widths: Vec<Constraint> = columns.iter().map(|column| column.width()).collect();
+            let highlight_since = Utc::now() - Duration::seconds(NEW_PROCESS_HIGHLIGHT_SECS);
+            let process_row = |process, nested| ProcessRow {
+                process,
+                full_command: table.full_command,
+                new: table
+                    .lifetimes
+                    .born_at(process.pid)
+                    .is_some_and(|born| born >= highlight_since),
+                nested,
+                columns: &columns,
+                command_shift,
+            };
+
+            let groups;
+            let (rows, mut title): (Vec<ListRow>, String) = match table.group_by {
+                None => (
+                    processes
+                        .iter()
+                        .map(|process| ListRow::Process(process_row(process, false)))
+                        .collect(),
+                    format!("Processes (sorted by {})", table.sort.label()),
+                ),
+                Some(by) => {
+                    groups = ProcessGroup::group(processes, by);
+                    let by_pid: HashMap<u32, &ProcessData> = processes
+                        .iter()
+                        .map(|process| (process.pid, process))
+                        .collect();
+                    let mut rows = Vec::new();
+                    for group in &groups {
+                        let expanded = table.expanded_groups.contains(&group.key);
+                        rows.push(ListRow::Group {
+                            group,
+                            by,
+                            expanded,
+                            columns: &columns,
+                        });
+                        if expanded {
+                            rows.extend(group.pids.iter().filter_map(|pid| {
+                                by_pid
+                                    .get(pid)
+                                    .map(|process| ListRow::Process(process_row(process, true)))
+                            }));
+                        }
+                    }
+                    let title = format!("Processes by {} ({} groups)", by.as_str(), groups.len());
+                    (rows, title)
+                }
+            };
+
+            let tracked_row = rows.iter().position(|row| match row {
+                ListRow::Process(row) => table.selected_pid == Some(row.process.pid),
+                ListRow::Group { group, .. } => table.selected_group.as_ref() == Some(&group.key),
+            });
+            let row_count = rows.len();
+            ui_state
+                .process_table
+                .keep_selection(tracked_row, row_count);
+            let table = &ui_state.process_table;
+            let selected = table
+                .process_list
+                .selected()
+                .and_then(|index| rows.get(index));
+            let (selected_pid, selected_group, selected_cgroup) = match selected {
+                Some(ListRow::Process(row)) => {
+                    (Some(row.process.pid), None, row.process.cgroup.clone())
+                }
+                Some(ListRow::Group { group, .. }) => (None, Some(group.key.clone()), None),
+                None => (None, None, None),
             };
+            if let Some(cgroup) = &table.cgroup_filter {
+                title.push_str(&format!(" in {} - f: show all", container_label(cgroup)));
+            }
+            if let Some(state) = table.state_filter {
+                title.push_str(&format!(" - {} only, z: next", state.as_str()));
+            }
+            if horizontal_scroll > 0 {
+                title.push_str(" - scrolled right, h: back");
+            }
+            let table_widget = render_table(headers, &rows, &title).widths(widths);
+            let table = &mut ui_state.process_table;
+            table.process_count = row_count;
+            table.horizontal_scroll = horizontal_scroll;
+            table.selected_pid = selected_pid;
+            table.selected_group = selected_group;
+            table.selected_cgroup = selected_cgroup;
+            // borders and the header row
+            table.page_size = (table_area.height.saturating_sub(3) as usize).max(1);
+            frame.render_widget(
+                recently_exited(&ui_state.process_table.lifetimes, exited_area.height),
+                exited_area,
+            );
             frame.render_stateful_widget(
                 table_widget,
-                area,
+                table_area,
                 &mut ui_state.process_table.process_list,
             );
+            let selected_row = ui_state.process_table.process_list.selected();
+            render_scrollbar(
+                frame,
+                table_area,
+                row_count,
+                selected_row.unwrap_or_default(),
+            );
         }
         Ok(())
     }
 }
+
+/// Scrolls the table `scroll` steps to the right. A leading PID column
+/// stays pinned; each step hides the next column after it until only the
+/// last one is left, then shifts the command line, when it is the last
+/// column, by
+/// `COMMAND_SCROLL_STEP` characters, up to `longest_command`.
+///
+/// # Returns
+///
+/// The columns to show, `scroll` clamped to how far the table can scroll
+/// and the characters of the command line to skip
+fn scroll_columns(
+    columns: &[ProcessColumn],
+    scroll: usize,
+    longest_command: usize,
+) -> (Vec<ProcessColumn>, usize, usize) {
+    let (pinned, scrollable) = match columns.split_first() {
+        Some((ProcessColumn::Pid, rest)) => (&columns[..1], rest),
+        _ => (&columns[..0], columns),
+    };
+    let column_steps = scrollable.len().saturating_sub(1);
+    let command_steps = if scrollable.last() == Some(&ProcessColumn::Command) {
+        longest_command.div_ceil(COMMAND_SCROLL_STEP)
+    } else {
+        0
+    };
+    let scroll = scroll.min(column_steps + command_steps);
+    let skipped = scroll.min(column_steps);
+    let shown = pinned
+        .iter()
+        .chain(&scrollable[skipped..])
+        .copied()
+        .collect();
+    (shown, scroll, (scroll - skipped) * COMMAND_SCROLL_STEP)
+}
+
+/// Builds the Recently exited panel, newest first, with the time the exit
+/// was noticed and how long the process was seen running
+fn recently_exited(lifetimes: &ProcessLifetimes, height: u16) -> Paragraph<'_> {
+    let lines: Vec<Line> = lifetimes
+        .exited()
+        .take(height.saturating_sub(2) as usize)
+        .map(|event| {
+            let lifetime = event
+                .lifetime
+                .map(|lifetime| format!("  ran {}", format_lifetime(lifetime)))
+                .unwrap_or_default();
+            Line::from(vec![
+                Span::styled(
+                    event
+                        .time
+                        .with_timezone(&Local)
+                        .format("%H:%M:%S  ")
+                        .to_string(),
+                    Style::default().fg(theme().muted),
+                ),
+                Span::raw(format!("{:<8}", event.pid)),
+                Span::styled(event.name.as_str(), Style::default().fg(theme().text)),
+                Span::styled(lifetime, Style::default().fg(theme().muted)),
+            ])
+        })
+        .collect();
+    let lines = if lines.is_empty() {
+        vec![Line::styled(
+            "No process has exited yet",
+            Style::default().fg(theme().muted),
+        )]
+    } else {
+        lines
+    };
+    Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Recently exited"),
+    )
+}
+
+/// Formats how long a process ran, e.g. `1m 05s`
+fn format_lifetime(lifetime: Duration) -> String {
+    let seconds = lifetime.num_seconds().max(0);
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+    use stomata_core::collectors::{
+        backend::{FakeFrame, FakeSystem, ProcessSample},
+        process::ProcessStateFilter,
+    };
+
+    use super::*;
+
+    fn render(processes: &Vec<ProcessData>, ui_state: &mut UIState) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(160, 24)).unwrap();
+        terminal
+            .draw(|frame| {
+                processes
+                    .display(frame, frame.area(), Some(ui_state))
+                    .unwrap()
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_renders_the_processes_of_the_backend() {
+        let system = FakeSystem::fixed(FakeFrame {
+            processes: vec![
+                ProcessSample {
+                    pid: 4242,
+                    name: "postgres".to_string(),
+                    cpu_usage: 12.5,
+                    memory: 64 * 1024 * 1024,
+                    ..Default::default()
+                },
+                ProcessSample {
+                    pid: 17,
+                    name: "defunct".to_string(),
+                    zombie: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+        let processes = ProcessData::fetch(&system);
+        let mut ui_state = UIState::default();
+
+        let screen = render(&processes, &mut ui_state);
+
+        let row = |pid: &str| {
+            screen
+                .iter()
+                .position(|line| line.contains(pid))
+                .unwrap_or_else(|| panic!("no row for {pid}"))
+        };
+        // sorted by PID
+        assert!(row("17 ") < row("4242"));
+        assert!(screen[row("4242")].contains("postgres"));
+        assert!(screen[row("4242")].contains("64.0 MiB"));
+        assert!(screen[row("17 ")].contains("defunct"));
+        assert_eq!(ui_state.process_table.process_count, 2);
+        assert_eq!(ui_state.process_table.selected_pid, Some(17));
+    }
+
+    #[test]
+    fn test_state_filter_hides_the_other_processes() {
+        let system = FakeSystem::fixed(FakeFrame {
+            processes: (1..=50)
+                .map(|pid| ProcessSample {
+                    pid,
+                    name: format!("worker-{pid}"),
+                    zombie: pid % 10 == 0,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        });
+        let processes = ProcessData::fetch(&system);
+        let mut ui_state = UIState::default();
+        ui_state.process_table.state_filter = Some(ProcessStateFilter::Zombies);
+
+        let screen = render(&processes, &mut ui_state);
+
+        assert_eq!(ui_state.process_table.process_count, 5);
+        assert!(screen.iter().any(|line| line.contains("worker-50")));
+        assert!(!screen.iter().any(|line| line.contains("worker-49")));
+    }
+}
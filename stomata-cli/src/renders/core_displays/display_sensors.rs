@@ -0,0 +1,285 @@
+//! Sensors display implementation
+//!
+//! Lists the hwmon temperatures beside the fans, each fan shown with the
+//! hottest temperature of its chip since that is usually what drives its
+//! curve. The RPM of the selected fan is plotted against that temperature
+//! over time, so a fan curve can be checked without leaving the terminal.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    symbols::Marker,
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType},
+};
+use stomata_core::collectors::hwmon::{Fan, HwmonChip, HwmonMetrics, TemperatureSensor};
+
+use crate::{
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{render_paragraph::paragraph_widget, render_table::render_table},
+    },
+    structs::{Ring, TableRow, UIState},
+    theme::theme,
+};
+
+/// A temperature with the chip it belongs to
+struct TemperatureRow<'a> {
+    chip: &'a str,
+    sensor: &'a TemperatureSensor,
+}
+
+/// A fan with the chip it belongs to
+struct FanRow<'a> {
+    chip: &'a HwmonChip,
+    fan: &'a Fan,
+}
+
+impl FanRow<'_> {
+    /// Key of the fan in the RPM history
+    fn key(&self) -> String {
+        format!("{}/{}", self.chip.name, self.fan.label)
+    }
+}
+
+fn format_celsius(celsius: Option<f64>) -> String {
+    celsius
+        .map(|celsius| format!("{celsius:.1} °C"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Implements table row conversion for temperatures.
+///
+/// # Column Layout
+///
+/// 1. **Chip** (12 chars): hwmon driver name
+/// 2. **Sensor** (flexible): Sensor label
+/// 3. **Temp** (10 chars): Current temperature, highlighted from the high
+///    and critical thresholds
+/// 4. **High** (10 chars): Temperature the driver considers high
+/// 5. **Crit** (10 chars): Shutdown temperature
+impl TableRow for TemperatureRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let sensor = self.sensor;
+        let style = if sensor.critical.is_some_and(|crit| sensor.celsius >= crit) {
+            Style::default().fg(theme().critical)
+        } else if sensor.high.is_some_and(|high| sensor.celsius >= high) {
+            Style::default().fg(theme().warning)
+        } else {
+            Style::default()
+        };
+        vec![
+            Cell::from(self.chip),
+            Cell::from(sensor.label.as_str()),
+            Cell::from(format_celsius(Some(sensor.celsius))).style(style),
+            Cell::from(format_celsius(sensor.high)),
+            Cell::from(format_celsius(sensor.critical)),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(12), // Chip
+            Constraint::Min(10),    // Sensor
+            Constraint::Length(10), // Temp
+            Constraint::Length(10), // High
+            Constraint::Length(10), // Crit
+        ]
+    }
+}
+
+/// Implements table row conversion for fans.
+///
+/// # Column Layout
+///
+/// 1. **Chip** (12 chars): hwmon driver name
+/// 2. **Fan** (flexible): Fan label
+/// 3. **RPM** (8 chars): Current speed
+/// 4. **PWM** (7 chars): Duty cycle of the fan output
+/// 5. **Mode** (7 chars): Whether the duty cycle is set by the chip or by
+///    software
+/// 6. **Temp** (10 chars): Hottest temperature of the chip
+impl TableRow for FanRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![
+            Cell::from(self.chip.name.as_str()),
+            Cell::from(self.fan.label.as_str()),
+            Cell::from(self.fan.rpm.to_string()),
+            Cell::from(
+                self.fan
+                    .pwm_percent()
+                    .map(|pwm| format!("{pwm:.0}%"))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::from(self.fan.pwm_mode.map(|mode| mode.as_str()).unwrap_or("-")),
+            Cell::from(format_celsius(self.chip.hottest())),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(12), // Chip
+            Constraint::Min(8),     // Fan
+            Constraint::Length(8),  // RPM
+            Constraint::Length(7),  // PWM
+            Constraint::Length(7),  // Mode
+            Constraint::Length(10), // Temp
+        ]
+    }
+}
+
+/// Scatter plot of RPM against temperature for one fan
+fn fan_curve_chart<'a>(title: &'a str, samples: &'a [(f64, f64)]) -> Chart<'a> {
+    let (min_temp, max_temp) = samples
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), (temp, _)| {
+            (min.min(*temp), max.max(*temp))
+        });
+    // keep a few degrees of room so a steady temperature isn't a single column
+    let (min_temp, max_temp) = ((min_temp - 5.0).floor(), (max_temp + 5.0).ceil());
+    let max_rpm = samples
+        .iter()
+        .map(|(_, rpm)| *rpm)
+        .fold(0.0, f64::max)
+        .max(100.0)
+        * 1.1;
+
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Scatter)
+        .style(Style::default().fg(theme().accent))
+        .data(samples);
+    Chart::new(vec![dataset])
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .x_axis(
+            Axis::default()
+                .title("°C")
+                .style(Style::default().fg(theme().muted))
+                .bounds([min_temp, max_temp])
+                .labels([format!("{min_temp:.0}"), format!("{max_temp:.0}")]),
+        )
+        .y_axis(
+            Axis::default()
+                .title("RPM")
+                .style(Style::default().fg(theme().muted))
+                .bounds([0.0, max_rpm])
+                .labels(["0".to_string(), format!("{max_rpm:.0}")]),
+        )
+}
+
+/// Display implementation for hwmon sensors
+impl Display for HwmonMetrics {
+    /// Renders the temperatures on the left, and the fans above the fan
+    /// curve of the selected fan on the right.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the Sensors page
+    /// * `ui_state` - Fan selection and RPM history, nothing is rendered
+    ///   without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if self.chips.is_empty() {
+            frame.render_widget(
+                paragraph_widget("\n\nNo hwmon temperature or fan sensors found", "Sensors")
+                    .alignment(Alignment::Center),
+                area,
+            );
+            return Ok(());
+        }
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+
+        let temperatures: Vec<TemperatureRow> = self
+            .chips
+            .iter()
+            .flat_map(|chip| {
+                chip.temperatures.iter().map(|sensor| TemperatureRow {
+                    chip: &chip.name,
+                    sensor,
+                })
+            })
+            .collect();
+        let fans: Vec<FanRow> = self
+            .chips
+            .iter()
+            .flat_map(|chip| chip.fans.iter().map(move |fan| FanRow { chip, fan }))
+            .collect();
+
+        if !ui_state.paused {
+            for row in &fans {
+                if let Some(temperature) = row.chip.hottest().or(self.hottest()) {
+                    ui_state
+                        .fan_history
+                        .entry(row.key())
+                        .or_insert_with(Ring::new)
+                        .push((temperature, row.fan.rpm as f64));
+                }
+            }
+        }
+
+        let [temperatures_area, fans_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(area);
+        frame.render_widget(
+            render_table(
+                vec!["Chip", "Sensor", "Temp", "High", "Crit"],
+                &temperatures,
+                "Temperatures",
+            ),
+            temperatures_area,
+        );
+
+        if fans.is_empty() {
+            frame.render_widget(
+                paragraph_widget("\n\nNo fan speeds reported by hwmon", "Fans")
+                    .alignment(Alignment::Center),
+                fans_area,
+            );
+            return Ok(());
+        }
+        let fans_height = (fans.len() as u16 + 3).min(fans_area.height / 2);
+        let [table_area, chart_area] =
+            Layout::vertical([Constraint::Length(fans_height), Constraint::Min(6)])
+                .areas(fans_area);
+        ui_state.fans_table.set_count(fans.len());
+        frame.render_stateful_widget(
+            render_table(
+                vec!["Chip", "Fan", "RPM", "PWM", "Mode", "Temp"],
+                &fans,
+                "Fans",
+            ),
+            table_area,
+            &mut ui_state.fans_table.list,
+        );
+
+        let selected = ui_state
+            .fans_table
+            .list
+            .selected()
+            .and_then(|index| fans.get(index));
+        let Some(row) = selected else {
+            frame.render_widget(
+                paragraph_widget("\n\nSelect a fan to plot its curve", "Fan curve")
+                    .alignment(Alignment::Center),
+                chart_area,
+            );
+            return Ok(());
+        };
+        let Some(history) = ui_state.fan_history.get_mut(&row.key()) else {
+            return Ok(());
+        };
+        let title = format!("{} {}: RPM vs temperature", row.chip.name, row.fan.label);
+        frame.render_widget(
+            fan_curve_chart(&title, history.make_contiguous()),
+            chart_area,
+        );
+        Ok(())
+    }
+}
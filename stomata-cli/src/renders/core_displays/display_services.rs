@@ -0,0 +1,153 @@
+//! Services display implementation
+//!
+//! Lists the systemd service units with their state and the memory and
+//! CPU usage of their cgroup. Failed units are highlighted. Start, stop
+//! and restart are confirmed in a dialog drawn over the page.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Cell, Paragraph, Wrap},
+};
+use stomata_core::collectors::services::{Service, ServiceMetrics};
+
+use crate::{
+    features::core::service_actions::ServicesUIState,
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{
+            render_modal::{centered_rect, render_modal},
+            render_paragraph::paragraph_widget,
+            render_table::render_table,
+        },
+    },
+    structs::{TableRow, UIState},
+    theme::theme,
+    utils::format_bytes,
+};
+
+/// Implements table row conversion for systemd services.
+///
+/// # Column Layout
+///
+/// 1. **Unit** (flexible): Unit name
+/// 2. **Active** (10 chars): Active state, highlighted when failed
+/// 3. **Sub** (10 chars): Sub state such as running or exited
+/// 4. **CPU%** (8 chars): CPU usage of the unit's cgroup
+/// 5. **Memory** (12 chars): Memory charged to the unit's cgroup
+/// 6. **Description** (flexible): Unit description
+impl TableRow for Service {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let active_style = match self.active_state.as_str() {
+            "failed" => Style::default().fg(theme().critical),
+            "active" => Style::default().fg(theme().ok),
+            "inactive" => Style::default().fg(theme().muted),
+            _ => Style::default().fg(theme().warning),
+        };
+        vec![
+            Cell::from(self.unit.as_str()),
+            Cell::from(self.active_state.as_str()).style(active_style),
+            Cell::from(self.sub_state.as_str()),
+            Cell::from(
+                self.cpu_usage
+                    .map(|cpu| format!("{cpu:.1}%"))
+                    .unwrap_or_default(),
+            ),
+            Cell::from(self.memory.map(format_bytes).unwrap_or_default()),
+            Cell::from(self.description.as_str()),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(28),    // Unit
+            Constraint::Length(10), // Active
+            Constraint::Length(10), // Sub
+            Constraint::Length(8),  // CPU%
+            Constraint::Length(12), // Memory
+            Constraint::Min(20),    // Description
+        ]
+    }
+}
+
+/// Draws the confirmation dialog of a pending action over the page
+fn render_confirmation(frame: &mut Frame, state: &ServicesUIState) {
+    let Some((unit, action)) = &state.confirm else {
+        return;
+    };
+    let area = centered_rect(frame.area(), 50, 5);
+    let inner = render_modal(frame, area, "Confirm");
+    let text = format!(
+        "{} {unit}?\ny / Enter: confirm  any other key: cancel",
+        action.command()
+    );
+    frame.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
+}
+
+/// Display implementation for systemd services
+impl Display for ServiceMetrics {
+    /// Renders the services table above the action keys, and the
+    /// confirmation dialog while an action is pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the Services page
+    /// * `ui_state` - Selection and pending action, nothing is rendered
+    ///   without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if let Some(error) = &self.error {
+            let text = format!("\n\n{error}\n\nThe Services page needs systemd.");
+            frame.render_widget(
+                paragraph_widget(&text, "Services").alignment(Alignment::Center),
+                area,
+            );
+            return Ok(());
+        }
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+        let state = &mut ui_state.services;
+
+        let [table_area, help_area] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(3)]).areas(area);
+
+        let failed = self
+            .services
+            .iter()
+            .filter(|service| service.active_state == "failed")
+            .count();
+        let title = format!("Services ({}, {failed} failed)", self.services.len());
+        let headers = vec!["Unit", "Active", "Sub", "CPU", "Memory", "Description"];
+        state.table.set_count(self.services.len());
+        state.units = self
+            .services
+            .iter()
+            .map(|service| service.unit.clone())
+            .collect();
+        frame.render_stateful_widget(
+            render_table(headers, &self.services, &title),
+            table_area,
+            &mut state.table.list,
+        );
+
+        frame.render_widget(
+            paragraph_widget("s: start  x: stop  r: restart", "Actions"),
+            help_area,
+        );
+
+        render_confirmation(frame, state);
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! Signals menu display implementation
+//!
+//! Renders the modal listing the signals that can be sent to a process.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    widgets::{Cell, Paragraph},
+};
+use stomata_core::collectors::process::actions::Signal;
+
+use crate::{
+    features::core::signal_menu::SignalMenu,
+    renders::render_widgets::{
+        render_modal::{centered_rect, render_modal},
+        render_table::render_table,
+    },
+    structs::TableRow,
+};
+
+/// Implements table row conversion for signals.
+///
+/// # Column Layout
+///
+/// 1. **Signal** (8 chars): Signal name
+/// 2. **Effect** (flexible): What the signal usually does
+impl TableRow for Signal {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![Cell::from(self.as_str()), Cell::from(self.description())]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(8), // Signal
+            Constraint::Min(20),   // Effect
+        ]
+    }
+}
+
+/// Renders the signals menu in a modal over the current page.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `menu` - The open menu with the target PID and selection
+pub fn render_signal_menu(frame: &mut Frame, menu: &mut SignalMenu) {
+    let height = Signal::ALL.len() as u16 + 6;
+    let modal_area = centered_rect(frame.area(), 60, height);
+    let inner = render_modal(frame, modal_area, &format!("Send signal to {}", menu.pid));
+    let [table_area, hint] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(inner);
+
+    frame.render_stateful_widget(
+        render_table(vec!["Signal", "Effect"], &Signal::ALL, "Signals"),
+        table_area,
+        &mut menu.table.list,
+    );
+    frame.render_widget(Paragraph::new("Enter: send  Esc: cancel"), hint);
+}
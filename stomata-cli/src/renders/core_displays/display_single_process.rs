@@ -1,25 +1,40 @@
 //! Single process detailed view implementation
 //!
 //! Provides a comprehensive, multi-panel view of a single process including
-//! basic information, resource usage gauges, disk I/O trends, and associated
-//! tasks/threads. This is the detailed view accessible by pressing Enter on
+//! basic information, security context, resource usage gauges, disk I/O
+//! trends, and associated tasks/threads. This is the detailed view accessible by pressing Enter on
 //! a process in the process list.
 
+#[cfg(feature = "web3")]
+use crate::renders::render_widgets::render_input::input_widget;
 use crate::{
+    constants::{AFFINITY_CPUS_PER_LINE, REDACTED_VALUE},
+    features::core::affinity_editor::AffinityEditor,
     renders::{
         core_displays::traits::SingleProcessDisplay,
         render_widgets::{
-            render_gauge::render_gauge, render_paragraph::paragraph_widget,
-            render_sparkline::render_sparkline, render_table::render_table,
+            render_gauge::{render_bytes_gauge, render_gauge},
+            render_modal::{centered_rect, render_modal},
+            render_paragraph::paragraph_widget,
+            render_sparkline::render_sparkline,
+            render_stacked_bar::{Segment, render_stacked_bar},
+            render_table::render_table,
         },
     },
-    structs::{SingleProcessUI, UIState},
-    utils::bytes_to_mb,
+    structs::{SingleProcessUI, TableRow, UIState},
+    theme::theme,
+    utils::format_bytes,
 };
-use chrono::DateTime;
+use chrono::{DateTime, Utc};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Wrap},
+};
+use stomata_core::collectors::process::{
+    MemoryBreakdown, OpenFile, SecurityContext, ThreadDetails, actions::format_cpu_list,
 };
 
 /// Display implementation for detailed single process view
@@ -34,12 +49,16 @@ use ratatui::{
 /// ```text
 /// ┌─────────────┬─────────────┬─────────────┐
 /// │ Basic Info  │ Extra Info  │   Tasks     │
-/// │   (30%)     │             │   Table     │
+/// │   (25%)     │             │   Table     │
 /// ├─────────────┤ Disk Read   │             │
-/// │ CPU Gauge   │ Sparkline   │             │
-/// │   (35%)     │             │             │
-/// │ Memory      │ Disk Write  │             │
-/// │ Gauge (35%) │ Sparkline   │             │
+/// │ Security    │ Sparkline   │             │
+/// │   (30%)     │             │             │
+/// ├─────────────┤             │             │
+/// │ CPU Gauge   │ Disk Write  │             │
+/// │ CPU Spark   │ Sparkline   │             │
+/// │ Memory Gauge│             │             │
+/// │ Memory Spark│             │             │
+/// │   (45%)     │             │             │
 /// └─────────────┴─────────────┴─────────────┘
 ///    33%            33%            33%
 /// ```
@@ -48,27 +67,32 @@ use ratatui::{
 /// ```text
 /// ┌─────────────┬─────────────┐
 /// │ Basic Info  │ Extra Info  │
-/// │   (30%)     │             │
+/// │   (25%)     │             │
 /// ├─────────────┤ Disk Read   │
-/// │ CPU Gauge   │ Sparkline   │
-/// │   (35%)     │             │
-/// │ Memory      │ Disk Write  │
-/// │ Gauge (35%) │ Sparkline   │
+/// │ Security    │ Sparkline   │
+/// │   (30%)     │             │
+/// ├─────────────┤             │
+/// │ CPU Gauge   │ Disk Write  │
+/// │ CPU Spark   │ Sparkline   │
+/// │ Memory Gauge│             │
+/// │ Memory Spark│             │
+/// │   (45%)     │             │
 /// └─────────────┴─────────────┘
 ///      50%            50%
 /// ```
-impl SingleProcessDisplay for SingleProcessUI<'_> {
+impl SingleProcessDisplay for SingleProcessUI {
     /// Renders detailed metrics for a single process
     ///
     /// Creates an adaptive layout with 2-3 columns depending on whether the
     /// process has tasks (linux only). Displays comprehensive information including basic
-    /// metadata, resource usage, disk I/O trends, and associated threads.
+    /// metadata, security context (linux only), resource usage, disk I/O trends, and
+    /// associated threads.
     ///
     /// # Arguments
     ///
     /// * `frame` - The ratatui frame to render into
     /// * `area` - The rectangular area allocated for the process view
-    /// * `total_memory` - Total system memory in MB (for memory gauge scaling)
+    /// * `total_memory` - Total system memory in bytes (for memory gauge scaling)
     /// * `ui_state` - UI state containing disk I/O history buffers for sparklines
     ///
     /// # Returns
@@ -81,13 +105,16 @@ impl SingleProcessDisplay for SingleProcessUI<'_> {
     /// - `disk_read_usage`: Historical read byte rates for sparkline
     /// - `disk_write_usage`: Historical write byte rates for sparkline
     ///
+    /// and `ui_state.single_process_usage` of the CPU and memory usage shown
+    /// below their gauges.
+    ///
     /// These buffers are automatically updated when the display is rendered,
     /// providing smooth animated sparkline charts of disk activity.
     ///
     /// # Memory Calculation
     ///
     /// The memory gauge shows:
-    /// - Used: Process memory consumption in MB
+    /// - Used: Process memory consumption
     /// - Total: System total memory (passed as parameter)
     /// - This provides context for how much of system memory the process uses
     ///
@@ -98,12 +125,12 @@ impl SingleProcessDisplay for SingleProcessUI<'_> {
     /// use stomata::renders::core_displays::traits::SingleProcessDisplay;
     ///
     /// let process_ui = SingleProcessUI { data: process_data };
-    /// let total_memory_mb = bytes_to_mb(system.total_memory());
+    /// let total_memory = system.total_memory();
     ///
     /// process_ui.display_process_metrics(
     ///     frame,
     ///     area,
-    ///     total_memory_mb,
+    ///     total_memory,
     ///     &mut ui_state
     /// )?;
     /// ```
@@ -118,52 +145,99 @@ impl SingleProcessDisplay for SingleProcessUI<'_> {
         &self,
         frame: &mut Frame,
         area: Rect,
-        total_memory: f64,
+        total_memory: u64,
         ui_state: &mut UIState,
     ) -> anyhow::Result<()> {
-        let constraints: Vec<Constraint>;
-
         let tasks = &self.data.tasks;
-        if tasks.len() > 0 {
-            constraints = vec![
+        let constraints = if !tasks.is_empty() {
+            vec![
                 Constraint::Percentage(33),
                 Constraint::Percentage(33),
                 Constraint::Percentage(33),
-            ];
+            ]
         } else {
-            constraints = vec![Constraint::Percentage(50), Constraint::Percentage(50)];
-        }
+            vec![Constraint::Percentage(50), Constraint::Percentage(50)]
+        };
 
         let primary_layout = Layout::horizontal(&constraints).split(area);
-        let secondary_layout =
-            Layout::vertical([Constraint::Percentage(30), Constraint::Percentage(70)])
-                .split(primary_layout[0]);
+        let secondary_layout = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+            Constraint::Percentage(45),
+        ])
+        .split(primary_layout[0]);
 
-        let p_info = format!(
+        let mut p_info = format!(
             "PID: {}\nName: {}\nStatus: {}",
             self.data.basic_process_data.pid,
             self.data.basic_process_data.name,
             self.data.basic_process_data.status
         );
+        if let Some(nice) = self.data.nice {
+            p_info.push_str(&format!("\nNice: {nice}"));
+        }
+        if let Some(threads) = self.data.basic_process_data.threads {
+            p_info.push_str(&format!("\nThreads: {threads}"));
+        }
+        if let Some(swap) = self.data.basic_process_data.swap {
+            p_info.push_str(&format!("\nSwap: {}", format_bytes(swap)));
+        }
+        if let Some(user) = &self.data.basic_process_data.user {
+            p_info.push_str(&format!("\nUser: {user}"));
+        }
+        if !self.data.basic_process_data.command.is_empty() {
+            p_info.push_str(&format!(
+                "\nCommand: {}",
+                self.data.basic_process_data.command.join(" ")
+            ));
+        }
+        if let Some(exe_path) = &self.data.basic_process_data.exe_path {
+            p_info.push_str(&format!("\nExe: {exe_path}"));
+        }
+        if self.data.basic_process_data.exe_deleted {
+            p_info.push_str("\nWarning: executable deleted or replaced on disk");
+        }
+        let checksum_info = exe_checksum_info(ui_state, self.data.basic_process_data.pid);
+        let follow = ui_state
+            .follow
+            .as_ref()
+            .filter(|follow| follow.pid == self.data.basic_process_data.pid);
+        if let Some(follow) = follow {
+            p_info.push_str(&format!(
+                "\nFollowing across restarts ({} so far)",
+                follow.restarts.len()
+            ));
+        }
+        match checksum_info {
+            Some(info) => p_info.push_str(&info),
+            None => p_info.push_str(&format!(
+                "\n{HASH_KEYS}e: environment  o: open files  m: memory  f: follow  +/-: renice  s: signal  a: affinity"
+            )),
+        }
 
-        let basic_info_paragraph = paragraph_widget(&p_info, "Basic Task info");
+        let basic_info_paragraph =
+            paragraph_widget(&p_info, "Basic Task info").wrap(Wrap { trim: true });
         let start_timestamp = DateTime::from_timestamp_secs(self.data.start_time as i64).unwrap();
         let mut extra_info = format!(
             "Start time: {:?}\nRunning time: {}\nCWD: {}\nTotal written bytes: {}\nTotal read bytes: {}\nLatest Read bytes: {}\nLatest write bytes: {}",
             start_timestamp,
             self.data.running_time,
-            self.data
-                .current_working_dir
-                .clone()
-                .unwrap_or(String::new()),
+            self.data.current_working_dir.clone().unwrap_or_default(),
             self.data.disk_usage.total_written_bytes,
             self.data.disk_usage.total_read_bytes,
             self.data.disk_usage.read_bytes,
             self.data.disk_usage.written_bytes
         );
         if let Some(parent_pid) = self.data.parent_pid {
-            extra_info.push_str(&format!("\nParent PID: {}", parent_pid.as_u32()));
+            extra_info.push_str(&format!("\nParent PID: {}", parent_pid));
         };
+        if let Some(cpus) = &self.data.cpu_affinity {
+            extra_info.push_str(&format!("\nCPU affinity: {}", format_cpu_list(cpus)));
+        }
+        match &self.data.open_files {
+            Some(files) => extra_info.push_str(&format!("\nOpen files: {}", files.len())),
+            None => extra_info.push_str("\nOpen files: unknown"),
+        }
         let extra_info_paragraph = paragraph_widget(&extra_info, "More info");
         let cpu_gauge = render_gauge(
             self.data.basic_process_data.cpu_usage.into(),
@@ -177,6 +251,14 @@ impl SingleProcessDisplay for SingleProcessUI<'_> {
             secondary_layout[0],
         );
 
+        let security_info = security_summary(&self.data.security);
+        frame.render_widget(
+            paragraph_widget(&security_info, "Security")
+                .alignment(ratatui::layout::Alignment::Left)
+                .wrap(Wrap { trim: true }),
+            secondary_layout[1],
+        );
+
         // ---- Primary 1 layout -----
         let primary_1_layout = Layout::vertical([
             Constraint::Percentage(33),
@@ -185,16 +267,10 @@ impl SingleProcessDisplay for SingleProcessUI<'_> {
         ])
         .split(primary_layout[1]);
 
-        let disk_read_data = ui_state
-            .single_process_disk_usage
-            .disk_read_usage
-            .make_contiguous();
-        let disk_write_data = ui_state
-            .single_process_disk_usage
-            .disk_write_usage
-            .make_contiguous();
-        let disk_read_sparkline = render_sparkline(disk_read_data, "Disk Read Bytes");
-        let disk_write_sparkline = render_sparkline(disk_write_data, "Disk Write Bytes");
+        let disk_read_data = ui_state.single_process_disk_usage.disk_read_usage.to_vec();
+        let disk_write_data = ui_state.single_process_disk_usage.disk_write_usage.to_vec();
+        let disk_read_sparkline = render_sparkline(&disk_read_data, "Disk Read Bytes");
+        let disk_write_sparkline = render_sparkline(&disk_write_data, "Disk Write Bytes");
 
         frame.render_widget(extra_info_paragraph, primary_1_layout[0]);
         frame.render_widget(disk_read_sparkline, primary_1_layout[1]);
@@ -202,24 +278,457 @@ impl SingleProcessDisplay for SingleProcessUI<'_> {
 
         //---- Conditional Render ----
 
-        let tertiary_constraints = [Constraint::Percentage(50), Constraint::Percentage(50)];
+        let restarts = follow.map_or(&[][..], |follow| follow.restarts.as_slice());
+        let tertiary_constraints = [
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(u16::from(!restarts.is_empty())),
+        ];
         let process_memory_use = self.data.basic_process_data.memory;
-        let memory_gauge = render_gauge(
-            bytes_to_mb(process_memory_use),
-            total_memory,
-            "Memory",
-            "MB",
-        );
+        let memory_gauge = render_bytes_gauge(process_memory_use, total_memory, "Memory");
+        let usage = &ui_state.single_process_usage;
+        let cpu_data = usage.cpu_usage.to_vec();
+        let memory_data = usage.memory_usage.to_vec();
+        let cpu_peak = usage.cpu_usage.max().unwrap_or_default() as f64 / 100.0;
+        let memory_peak = usage.memory_usage.max().unwrap_or_default();
+        let cpu_title = format!("CPU history (peak {cpu_peak:.1}%)");
+        let memory_title = format!("Memory history (peak {})", format_bytes(memory_peak));
 
-        let tertiary_layout = Layout::vertical(tertiary_constraints).split(secondary_layout[1]);
+        let tertiary_layout = Layout::vertical(tertiary_constraints).split(secondary_layout[2]);
         frame.render_widget(cpu_gauge, tertiary_layout[0]);
-        frame.render_widget(memory_gauge, tertiary_layout[1]);
+        frame.render_widget(render_sparkline(&cpu_data, &cpu_title), tertiary_layout[1]);
+        frame.render_widget(memory_gauge, tertiary_layout[2]);
+        frame.render_widget(
+            render_sparkline(&memory_data, &memory_title),
+            tertiary_layout[3],
+        );
+        frame.render_widget(
+            Paragraph::new(restart_markers(usage.memory_usage.times(), restarts)),
+            tertiary_layout[4],
+        );
+
+        let state = &mut ui_state.tasks;
+        state.track(self.data.basic_process_data.pid);
+        let mut tasks = tasks.clone();
+        state.sort.sort(&mut tasks);
+        let selected_tid = state
+            .table
+            .list
+            .selected()
+            .and_then(|index| state.tids.get(index))
+            .copied();
+        state.tids = tasks.iter().map(|task| task.pid).collect();
+        state
+            .table
+            .set_keyed_rows(selected_tid.as_ref(), &state.tids);
+        if !tasks.is_empty() {
+            let task_headers = vec!["TID", "Name", "CPU", "Memory", "Status"];
+            let title = format!(
+                "Tasks ({}) by {} - t: sort  Enter: details",
+                tasks.len(),
+                state.sort.label()
+            );
+            let task_widget = render_table(task_headers, &tasks, &title);
+            frame.render_stateful_widget(task_widget, primary_layout[2], &mut state.table.list);
+        }
+        if let Some(details) = &ui_state.tasks.details {
+            render_thread_details(frame, details);
+        }
+
+        if ui_state.process_env.open {
+            render_environment(frame, &self.data.environment, ui_state);
+        }
+        if ui_state.open_files.open {
+            render_open_files(frame, self.data.open_files.as_deref(), ui_state);
+        }
+        if ui_state.memory_breakdown_open {
+            render_memory_breakdown(frame, self.data.memory_breakdown.as_ref());
+        }
+
+        if let Some(editor) = &ui_state.affinity_editor {
+            render_affinity_editor(frame, editor);
+        }
 
-        if tasks.len() > 0 {
-            let task_headers = vec!["PID", "Name", "CPU", "Memory", "Status"];
-            let task_widget = render_table(task_headers, &self.data.tasks, "Tasks");
-            frame.render_widget(task_widget, primary_layout[2]);
+        #[cfg(feature = "web3")]
+        if let Some(input) = &ui_state.expected_hash_input {
+            let modal_area = centered_rect(frame.area(), 60, 6);
+            let inner = render_modal(frame, modal_area, "Verify executable");
+            let [field, hint] =
+                Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).areas(inner);
+            frame.render_widget(
+                input_widget(&input.value, input.label, input.masked, true),
+                field,
+            );
+            frame.render_widget(
+                ratatui::widgets::Paragraph::new("Enter: compare  Esc: cancel"),
+                hint,
+            );
         }
         Ok(())
     }
 }
+
+/// An environment variable, with its value redacted unless revealed
+struct EnvRow<'a> {
+    name: &'a str,
+    value: &'a str,
+    revealed: bool,
+}
+
+/// Implements table row conversion for environment variables.
+///
+/// # Column Layout
+///
+/// 1. **Name** (30 chars): Variable name
+/// 2. **Value** (flexible): Value, or a placeholder until revealed
+impl TableRow for EnvRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let value = if self.revealed {
+            Cell::from(self.value)
+        } else {
+            Cell::from(REDACTED_VALUE).style(Style::default().fg(theme().muted))
+        };
+        vec![Cell::from(self.name), value]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(30), // Name
+            Constraint::Min(20),    // Value
+        ]
+    }
+}
+
+/// Renders the environment variables of the process in a modal over the
+/// single process view, with values redacted until revealed with Enter.
+fn render_environment(frame: &mut Frame, environment: &[(String, String)], ui_state: &mut UIState) {
+    let state = &mut ui_state.process_env;
+    let modal_area = centered_rect(frame.area(), 80, frame.area().height.saturating_sub(6));
+    let inner = render_modal(frame, modal_area, "Environment");
+    let [table_area, hint] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(inner);
+
+    if environment.is_empty() {
+        frame.render_widget(
+            ratatui::widgets::Paragraph::new(
+                "No environment variables, or they can't be read for another user's process",
+            ),
+            table_area,
+        );
+    } else {
+        let rows: Vec<EnvRow> = environment
+            .iter()
+            .map(|(name, value)| EnvRow {
+                name,
+                value,
+                revealed: state.revealed.contains(name),
+            })
+            .collect();
+        let title = format!("Variables ({})", rows.len());
+        state.table.set_count(rows.len());
+        state.names = environment.iter().map(|(name, _)| name.clone()).collect();
+        frame.render_stateful_widget(
+            render_table(vec!["Name", "Value"], &rows, &title),
+            table_area,
+            &mut state.table.list,
+        );
+    }
+    frame.render_widget(
+        ratatui::widgets::Paragraph::new("Enter: reveal/redact value  e/Esc: close"),
+        hint,
+    );
+}
+
+/// Shortcuts of the executable checksum, which needs the hashing of the
+/// `web3` feature
+#[cfg(feature = "web3")]
+const HASH_KEYS: &str = "h: hash exe  H: verify hash  ";
+#[cfg(not(feature = "web3"))]
+const HASH_KEYS: &str = "";
+
+/// Lines with the executable checksum of `pid` once it was requested,
+/// `None` to show the shortcuts instead
+#[cfg(feature = "web3")]
+fn exe_checksum_info(ui_state: &mut UIState, pid: u32) -> Option<String> {
+    let checksum = ui_state
+        .exe_checksum
+        .as_mut()
+        .filter(|checksum| checksum.pid == pid)?;
+    checksum.poll();
+    let mut info = match &checksum.digest {
+        Some(Ok(digest)) => format!("\nSHA-256: {digest}"),
+        Some(Err(err)) => format!("\nSHA-256 failed: {err}"),
+        None => "\nSHA-256: hashing…".to_string(),
+    };
+    match checksum.matches() {
+        Some(true) => info.push_str("\nExpected hash: match"),
+        Some(false) => info.push_str("\nExpected hash: MISMATCH"),
+        None => {}
+    }
+    Some(info)
+}
+
+#[cfg(not(feature = "web3"))]
+fn exe_checksum_info(_ui_state: &mut UIState, _pid: u32) -> Option<String> {
+    None
+}
+
+/// Builds a line with a marker under every sample of the usage sparklines
+/// during which the followed process restarted, like the crash event
+/// markers of the network charts
+fn restart_markers<'a>(
+    sample_times: impl Iterator<Item = &'a DateTime<Utc>>,
+    restarts: &[DateTime<Utc>],
+) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    let mut previous: Option<DateTime<Utc>> = None;
+    for time in sample_times {
+        let restarted = restarts
+            .iter()
+            .any(|restart| restart <= time && previous.is_none_or(|previous| *restart > previous));
+        spans.push(if restarted {
+            Span::styled("\u{25b2}", Style::default().fg(theme().warning))
+        } else {
+            Span::raw(" ")
+        });
+        previous = Some(*time);
+    }
+    Line::from(spans)
+}
+
+/// Renders the scheduling details of a thread in a modal over the single
+/// process view
+fn render_thread_details(frame: &mut Frame, details: &Result<ThreadDetails, String>) {
+    let details = match details {
+        Ok(details) => details,
+        Err(err) => {
+            let modal_area = centered_rect(frame.area(), 60, 5);
+            let inner = render_modal(frame, modal_area, "Thread");
+            frame.render_widget(
+                ratatui::widgets::Paragraph::new(format!("{err}\n\nEsc: close")),
+                inner,
+            );
+            return;
+        }
+    };
+    let optional =
+        |value: Option<u64>| value.map_or_else(|| "unknown".to_string(), |value| value.to_string());
+    let text = format!(
+        "Name: {}\nState: {}\nUser time: {:.2}s\nSystem time: {:.2}s\nPriority: {}\nNice: {}\nLast CPU: {}\nVoluntary switches: {}\nInvoluntary switches: {}",
+        details.name,
+        details.state,
+        details.user_time.as_secs_f64(),
+        details.system_time.as_secs_f64(),
+        details.priority,
+        details.nice,
+        details
+            .processor
+            .map_or_else(|| "unknown".to_string(), |cpu| cpu.to_string()),
+        optional(details.voluntary_switches),
+        optional(details.involuntary_switches),
+    );
+    let modal_area = centered_rect(frame.area(), 50, 13);
+    let inner = render_modal(frame, modal_area, &format!("Thread {}", details.tid));
+    let [body, hint] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+    frame.render_widget(ratatui::widgets::Paragraph::new(text), body);
+    frame.render_widget(ratatui::widgets::Paragraph::new("Esc: close"), hint);
+}
+
+/// Renders the CPU affinity editor in a modal over the single process view,
+/// eight CPUs per line with the cursor highlighted.
+fn render_affinity_editor(frame: &mut Frame, editor: &AffinityEditor) {
+    let lines: Vec<Line> = editor
+        .allowed
+        .chunks(AFFINITY_CPUS_PER_LINE)
+        .enumerate()
+        .map(|(row, chunk)| {
+            Line::from(
+                chunk
+                    .iter()
+                    .enumerate()
+                    .map(|(column, allowed)| {
+                        let cpu = row * AFFINITY_CPUS_PER_LINE + column;
+                        let mark = if *allowed { "x" } else { " " };
+                        let style = if cpu == editor.cursor {
+                            Style::default()
+                                .bg(theme().selection_bg)
+                                .fg(theme().selection_fg)
+                        } else {
+                            Style::default()
+                        };
+                        Span::styled(format!("[{mark}] {cpu:<4}"), style)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+
+    let height = lines.len() as u16 + 4;
+    let modal_area = centered_rect(frame.area(), 70, height);
+    let inner = render_modal(
+        frame,
+        modal_area,
+        &format!("CPU affinity of {}", editor.pid),
+    );
+    let [grid, hint] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+    frame.render_widget(ratatui::widgets::Paragraph::new(lines), grid);
+    frame.render_widget(
+        ratatui::widgets::Paragraph::new("Space: toggle  a: all  Enter: apply  Esc: cancel"),
+        hint,
+    );
+}
+
+/// Implements table row conversion for open file descriptors.
+///
+/// # Column Layout
+///
+/// 1. **FD** (6 chars): Descriptor number
+/// 2. **Kind** (11 chars): File, socket, pipe, anon inode or other
+/// 3. **Target** (flexible): Path or kernel object the descriptor refers to
+impl TableRow for OpenFile {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![
+            Cell::from(self.fd.to_string()),
+            Cell::from(self.kind.as_str()),
+            Cell::from(self.target.as_str()),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(6),  // FD
+            Constraint::Length(11), // Kind
+            Constraint::Min(20),    // Target
+        ]
+    }
+}
+
+/// Renders the open file descriptors of the process in a modal over the
+/// single process view.
+fn render_open_files(frame: &mut Frame, open_files: Option<&[OpenFile]>, ui_state: &mut UIState) {
+    let state = &mut ui_state.open_files;
+    let modal_area = centered_rect(frame.area(), 80, frame.area().height.saturating_sub(6));
+    let inner = render_modal(frame, modal_area, "Open files");
+    let [table_area, hint] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(inner);
+
+    match open_files {
+        Some(files) => {
+            let title = format!("Descriptors ({})", files.len());
+            state.table.set_count(files.len());
+            frame.render_stateful_widget(
+                render_table(vec!["FD", "Kind", "Target"], files, &title),
+                table_area,
+                &mut state.table.list,
+            );
+        }
+        None => frame.render_widget(
+            ratatui::widgets::Paragraph::new(
+                "Open files can't be read for another user's process or on this platform",
+            ),
+            table_area,
+        ),
+    }
+    frame.render_widget(
+        ratatui::widgets::Paragraph::new("Up/Down: scroll  o/Esc: close"),
+        hint,
+    );
+}
+
+/// Renders the smaps_rollup memory breakdown of the process in a modal over
+/// the single process view: resident memory split into private and shared
+/// pages next to the swapped out pages, with PSS for comparison
+fn render_memory_breakdown(frame: &mut Frame, breakdown: Option<&MemoryBreakdown>) {
+    let modal_area = centered_rect(frame.area(), 70, 13);
+    let inner = render_modal(frame, modal_area, "Memory breakdown");
+    let [bar, details, hint] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+    frame.render_widget(ratatui::widgets::Paragraph::new("m/Esc: close"), hint);
+
+    let Some(breakdown) = breakdown else {
+        frame.render_widget(
+            ratatui::widgets::Paragraph::new(
+                "smaps_rollup can't be read for another user's process, on kernels before 4.14 or on this platform",
+            )
+            .wrap(Wrap { trim: true }),
+            bar,
+        );
+        return;
+    };
+
+    let segments = [
+        Segment {
+            label: "Private",
+            value: breakdown.private() as f64,
+            color: theme().gauge,
+        },
+        Segment {
+            label: "Shared",
+            value: breakdown.shared() as f64,
+            color: theme().accent,
+        },
+        Segment {
+            label: "Swap",
+            value: breakdown.swap as f64,
+            color: theme().warning,
+        },
+    ];
+    render_stacked_bar(frame, bar, "Resident and swapped", &segments, |bytes| {
+        format_bytes(bytes as u64)
+    });
+
+    let text = format!(
+        "RSS: {}\nPSS: {}\nPrivate: {} clean, {} dirty\nShared: {} clean, {} dirty\nAnonymous: {}\nSwap: {} (PSS {})",
+        format_bytes(breakdown.rss),
+        format_bytes(breakdown.pss),
+        format_bytes(breakdown.private_clean),
+        format_bytes(breakdown.private_dirty),
+        format_bytes(breakdown.shared_clean),
+        format_bytes(breakdown.shared_dirty),
+        format_bytes(breakdown.anonymous),
+        format_bytes(breakdown.swap),
+        format_bytes(breakdown.swap_pss),
+    );
+    frame.render_widget(ratatui::widgets::Paragraph::new(text), details);
+}
+
+/// Formats the capabilities, seccomp mode, no_new_privs flag and LSM label
+/// of a process. Unreadable fields, e.g. for another user's process, are
+/// shown as `unknown`.
+fn security_summary(security: &SecurityContext) -> String {
+    let unknown = || "unknown".to_string();
+
+    let capabilities = match (security.cap_effective, security.cap_bounding) {
+        (Some(0), _) => "none".to_string(),
+        (Some(effective), Some(bounding)) if effective == bounding => "all".to_string(),
+        (Some(effective), _) => SecurityContext::capability_names(effective).join(", "),
+        (None, _) => unknown(),
+    };
+    let permitted = security
+        .cap_permitted
+        .map(|mask| mask.count_ones().to_string())
+        .unwrap_or_else(unknown);
+    let seccomp = security
+        .seccomp
+        .map(|mode| mode.as_str().to_string())
+        .unwrap_or_else(unknown);
+    let no_new_privs = match security.no_new_privs {
+        Some(true) => "yes".to_string(),
+        Some(false) => "no".to_string(),
+        None => unknown(),
+    };
+    let label = security
+        .lsm_label
+        .clone()
+        .unwrap_or_else(|| "none".to_string());
+
+    format!(
+        "Seccomp: {seccomp}\nNoNewPrivs: {no_new_privs}\nLSM label: {label}\nPermitted caps: {permitted}\nEffective caps: {capabilities}"
+    )
+}
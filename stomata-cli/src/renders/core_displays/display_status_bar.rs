@@ -0,0 +1,63 @@
+//! Status bar display implementation
+//!
+//! Renders the bottom line of the screen: the last status message while
+//! it hasn't expired, the global shortcuts otherwise. The command line of
+//! the vim keymap profile is drawn over it while open. The Web3 TUI shares
+//! the status bar with its own shortcuts.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Clear, Paragraph},
+};
+
+use crate::{
+    features::core::command_line::CommandLine,
+    structs::{StatusBar, StatusLevel},
+    theme::theme,
+};
+
+/// Global shortcuts of the system TUI, shown when there is no message
+pub const SHORTCUTS: &str =
+    "q quit  ←/→ switch page  space pause  w keep awake  b units  | split  ctrl+p commands";
+
+/// Renders the status bar
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - One line tall area at the bottom of the screen
+/// * `status_bar` - Holds the message to show
+/// * `shortcuts` - Shown while there is no message
+pub fn render_status_bar(frame: &mut Frame, area: Rect, status_bar: &StatusBar, shortcuts: &str) {
+    let line = match status_bar.current() {
+        Some(message) => {
+            let color = match message.level {
+                StatusLevel::Info => theme().ok,
+                StatusLevel::Error => theme().critical,
+            };
+            Line::styled(format!(" {}", message.text), Style::default().fg(color))
+        }
+        None => Line::styled(format!(" {shortcuts}"), Style::default().fg(theme().muted)),
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Renders the command line over the status bar, with a cursor after the
+/// typed text
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - One line tall area at the bottom of the screen
+/// * `command_line` - Holds the typed command
+pub fn render_command_line(frame: &mut Frame, area: Rect, command_line: &CommandLine) {
+    let line = Line::styled(
+        format!(":{}█", command_line.input.value.as_str()),
+        Style::default().fg(theme().text),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(line), area);
+}
@@ -0,0 +1,94 @@
+//! Summary strip display implementation
+//!
+//! Renders a single line above the tabs with CPU, memory and swap usage,
+//! the busiest process, the number of zombie and orphaned processes and
+//! the number of active alerts, so they stay in view whichever page is
+//! open.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use stomata_core::collectors::system::summary::SystemSummary;
+
+use crate::{
+    constants::{SUMMARY_CRITICAL_PERCENT, SUMMARY_WARNING_PERCENT},
+    theme::theme,
+};
+
+/// Color of a usage percentage
+pub fn usage_color(percent: f64) -> Color {
+    if percent >= SUMMARY_CRITICAL_PERCENT {
+        theme().critical
+    } else if percent >= SUMMARY_WARNING_PERCENT {
+        theme().warning
+    } else {
+        theme().ok
+    }
+}
+
+/// Label and value of one usage figure
+fn usage_spans(label: &str, percent: f64) -> [Span<'_>; 2] {
+    [
+        Span::styled(format!(" {label} "), Style::default().fg(theme().label)),
+        Span::styled(
+            format!("{percent:>5.1}% "),
+            Style::default().fg(usage_color(percent)),
+        ),
+    ]
+}
+
+/// Label and value of a process count, highlighted when not zero
+fn count_spans(label: &str, count: usize) -> [Span<'_>; 2] {
+    let style = if count > 0 {
+        Style::default().fg(theme().warning)
+    } else {
+        Style::default().fg(theme().ok)
+    };
+    [
+        Span::styled(format!(" {label} "), Style::default().fg(theme().label)),
+        Span::styled(format!("{count} "), style),
+    ]
+}
+
+/// Renders the summary strip
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - One line tall area above the tabs
+/// * `summary` - CPU, memory, swap and top process at the last refresh
+/// * `alert_count` - Number of alerts currently active
+pub fn render_summary(frame: &mut Frame, area: Rect, summary: &SystemSummary, alert_count: usize) {
+    let mut spans = Vec::new();
+    spans.extend(usage_spans("CPU", summary.cpu_usage as f64));
+    spans.extend(usage_spans("MEM", summary.memory_percent));
+    spans.extend(usage_spans("SWAP", summary.swap_percent));
+
+    spans.push(Span::styled(" TOP ", Style::default().fg(theme().label)));
+    match &summary.top_process {
+        Some(top) => spans.push(Span::styled(
+            format!("{} ({}) {:.1}% ", top.name, top.pid, top.cpu_usage),
+            Style::default().fg(theme().text),
+        )),
+        None => spans.push(Span::styled("- ", Style::default().fg(theme().muted))),
+    }
+
+    spans.extend(count_spans("ZOMBIES", summary.zombies));
+    spans.extend(count_spans("ORPHANS", summary.orphans));
+
+    spans.push(Span::styled(" ALERTS ", Style::default().fg(theme().label)));
+    let alert_style = if alert_count > 0 {
+        Style::default()
+            .fg(theme().critical)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme().ok)
+    };
+    spans.push(Span::styled(alert_count.to_string(), alert_style));
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
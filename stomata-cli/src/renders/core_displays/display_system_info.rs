@@ -2,14 +2,34 @@
 //!
 //! Provides a centered view of core system details including OS information,
 //! kernel version, and hostname, along with keyboard navigation instructions
-//! for the UI.
+//! for the UI. Below it the logged-in user sessions are listed like `w` does
+//! and a power panel shows the screen brightness and power profile on
+//! laptops that have them, and next to it a boot time breakdown
+//! above a hardware panel that lists the USB device tree or the PCI devices.
 
-use ratatui::{Frame, layout::Rect};
-use stomata_core::collectors::SystemInfo;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    widgets::Cell,
+};
+use stomata_core::collectors::{
+    BootAnalysis, PowerMetrics, SessionMetrics, SystemInfo, boot::UnitStartup, pci::PciDevice,
+    sessions::UserSession, usb::UsbDevice,
+};
 
 use crate::{
-    renders::{core_displays::traits::Display, render_widgets::render_paragraph::paragraph_widget},
-    structs::UIState,
+    features::core::hardware::{HardwareUIState, HardwareView},
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{
+            render_paragraph::paragraph_widget,
+            render_stacked_bar::{Segment, render_stacked_bar},
+            render_table::render_table,
+        },
+    },
+    structs::{TableRow, UIState},
+    theme::theme,
 };
 
 /// Display implementation for system information.
@@ -66,3 +86,374 @@ impl Display for SystemInfo {
         Ok(())
     }
 }
+
+/// Display implementation for the power panel of the System page
+impl Display for PowerMetrics {
+    /// Renders the brightness and power profile with their keybindings and
+    /// the error of the last failed change.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - Mutable reference to the ratatui frame for rendering
+    /// * `area` - Screen area of the power panel
+    /// * `ui_state` - Keeps these metrics for the key handlers, nothing is
+    ///   rendered without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+        ui_state.power.metrics = self.clone();
+
+        let mut text = String::new();
+        let mut keys = Vec::new();
+        match &self.backlight {
+            Some(backlight) => {
+                text.push_str(&format!(
+                    "Brightness: {}% ({})",
+                    backlight.percent(),
+                    backlight.name
+                ));
+                keys.push("+/-: brightness");
+            }
+            None => text.push_str("Brightness: no backlight found"),
+        }
+        match self.profile {
+            Some(profile) => {
+                let available: Vec<&str> = self
+                    .available_profiles
+                    .iter()
+                    .map(|profile| profile.as_str())
+                    .collect();
+                text.push_str(&format!(
+                    "\nPower profile: {} (available: {})",
+                    profile.as_str(),
+                    available.join(", ")
+                ));
+                keys.push("p: next profile");
+            }
+            None => text.push_str("\nPower profile: power-profiles-daemon not available"),
+        }
+        text.push('\n');
+        text.push_str(ui_state.power.status.as_deref().unwrap_or_default());
+        text.push('\n');
+        text.push_str(&keys.join("  "));
+
+        frame.render_widget(paragraph_widget(&text, "Power"), area);
+        Ok(())
+    }
+}
+
+/// A USB device with the tree branches leading to it
+struct UsbRow<'a> {
+    /// Indentation and branch characters, e.g. `│  └─ `
+    prefix: String,
+    device: &'a UsbDevice,
+}
+
+/// Implements table row conversion for the USB device tree.
+///
+/// # Column Layout
+///
+/// 1. **Device** (flexible): Tree branches and product name
+/// 2. **ID** (10 chars): vendor:product id
+/// 3. **Bus/Dev** (8 chars): Bus and device number as shown by `lsusb`
+/// 4. **Speed** (8 chars): Negotiated speed in Mbit/s
+/// 5. **Driver** (16 chars): Drivers bound to the device's interfaces
+impl TableRow for UsbRow<'_> {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let device = self.device;
+        vec![
+            Cell::from(format!("{}{}", self.prefix, device.display_name())),
+            Cell::from(format!("{}:{}", device.vendor_id, device.product_id)),
+            Cell::from(format!("{:03}/{:03}", device.bus, device.device_number)),
+            Cell::from(
+                device
+                    .speed
+                    .as_ref()
+                    .map(|speed| format!("{speed}M"))
+                    .unwrap_or_default(),
+            ),
+            Cell::from(device.drivers.join(",")),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(24),    // Device
+            Constraint::Length(10), // ID
+            Constraint::Length(8),  // Bus/Dev
+            Constraint::Length(8),  // Speed
+            Constraint::Length(16), // Driver
+        ]
+    }
+}
+
+/// Flattens the device tree into rows, drawing the branches like `tree`
+fn flatten_usb_tree<'a>(devices: &'a [UsbDevice], indent: &str, rows: &mut Vec<UsbRow<'a>>) {
+    for (index, device) in devices.iter().enumerate() {
+        let last = index + 1 == devices.len();
+        let (branch, continuation) = if last {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        rows.push(UsbRow {
+            prefix: format!("{indent}{branch}"),
+            device,
+        });
+        flatten_usb_tree(&device.children, &format!("{indent}{continuation}"), rows);
+    }
+}
+
+/// Implements table row conversion for PCI devices.
+///
+/// # Column Layout
+///
+/// 1. **Address** (13 chars): Domain, bus, device and function
+/// 2. **Class** (26 chars): Generic device class
+/// 3. **Device** (flexible): Vendor and device name, or ids without pci.ids
+/// 4. **Driver** (14 chars): Kernel driver in use, highlighted for vfio-pci
+/// 5. **IOMMU** (6 chars): IOMMU group
+impl TableRow for PciDevice {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let name = match (&self.vendor_name, &self.device_name) {
+            (Some(vendor), Some(device)) => format!("{vendor} {device}"),
+            (Some(vendor), None) => format!("{vendor} [{}]", self.device_id),
+            _ => format!("{}:{}", self.vendor_id, self.device_id),
+        };
+        let driver = self.driver.as_deref().unwrap_or("");
+        // devices bound to vfio-pci are reserved for passthrough
+        let driver_style = if driver == "vfio-pci" {
+            Style::default().fg(theme().accent)
+        } else {
+            Style::default()
+        };
+        vec![
+            Cell::from(self.address.as_str()),
+            Cell::from(self.class_name()),
+            Cell::from(name),
+            Cell::from(driver).style(driver_style),
+            Cell::from(
+                self.iommu_group
+                    .map(|group| group.to_string())
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(13), // Address
+            Constraint::Length(26), // Class
+            Constraint::Min(20),    // Device
+            Constraint::Length(14), // Driver
+            Constraint::Length(6),  // IOMMU
+        ]
+    }
+}
+
+/// Renders the hardware panel of the System page: the USB device tree or
+/// the PCI devices as they were last enumerated, or the error enumerating
+/// them.
+///
+/// # Arguments
+///
+/// * `frame` - Mutable reference to the ratatui frame for rendering
+/// * `area` - Screen area of the hardware panel
+/// * `state` - Enumerated devices and the table selection
+pub fn render_hardware(frame: &mut Frame, area: Rect, state: &mut HardwareUIState) {
+    if state.view == HardwareView::Pci {
+        match &state.pci {
+            Some(Ok(devices)) => {
+                let headers = vec!["Address", "Class", "Device", "Driver", "IOMMU"];
+                state.table.set_count(devices.len());
+                frame.render_stateful_widget(
+                    render_table(headers, devices, "PCI devices (t: USB, r: refresh)"),
+                    area,
+                    &mut state.table.list,
+                );
+            }
+            Some(Err(err)) => frame.render_widget(
+                paragraph_widget(&format!("\n{err}\n\nr: retry  t: USB"), "PCI devices")
+                    .alignment(Alignment::Center),
+                area,
+            ),
+            None => {}
+        }
+        return;
+    }
+
+    match &state.usb {
+        Some(Ok(devices)) => {
+            let mut rows = Vec::new();
+            // root hubs are drawn without branches
+            for device in devices {
+                rows.push(UsbRow {
+                    prefix: String::new(),
+                    device,
+                });
+                flatten_usb_tree(&device.children, "", &mut rows);
+            }
+            let headers = vec!["Device", "ID", "Bus/Dev", "Speed", "Driver"];
+            state.table.set_count(rows.len());
+            frame.render_stateful_widget(
+                render_table(headers, &rows, "USB devices (t: PCI, r: refresh)"),
+                area,
+                &mut state.table.list,
+            );
+        }
+        Some(Err(err)) => frame.render_widget(
+            paragraph_widget(&format!("\n{err}\n\nr: retry  t: PCI"), "USB devices")
+                .alignment(Alignment::Center),
+            area,
+        ),
+        None => {}
+    }
+}
+
+/// Table row for a unit in the boot analysis.
+///
+/// # Column Layout
+///
+/// 1. **Unit** (flexible): Name of the systemd unit
+/// 2. **Time** (10 chars): Time the unit took to start
+impl TableRow for UnitStartup {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        vec![
+            Cell::from(self.unit.as_str()),
+            Cell::from(format!("{:.3}s", self.duration.as_secs_f64())),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(20),    // Unit
+            Constraint::Length(10), // Time
+        ]
+    }
+}
+
+/// Renders the boot time breakdown of the System page.
+///
+/// The boot stages are drawn as a stacked bar with the slowest units
+/// listed below it. Systems not booted with systemd show the error
+/// instead.
+pub fn render_boot_analysis(frame: &mut Frame, area: Rect, boot: &Result<BootAnalysis, String>) {
+    let analysis = match boot {
+        Ok(analysis) => analysis,
+        Err(err) => {
+            frame.render_widget(
+                paragraph_widget(&format!("\n{err}"), "Boot").alignment(Alignment::Center),
+                area,
+            );
+            return;
+        }
+    };
+
+    let [stages_area, units_area] =
+        Layout::vertical([Constraint::Length(4), Constraint::Min(3)]).areas(area);
+    let colors = [
+        theme().muted,
+        theme().label,
+        theme().accent,
+        theme().warning,
+        theme().gauge,
+    ];
+    let segments: Vec<Segment> = analysis
+        .stages()
+        .into_iter()
+        .zip(colors)
+        .map(|((label, duration), color)| Segment {
+            label,
+            value: duration.as_secs_f64() * 1000.0,
+            color,
+        })
+        .collect();
+    let title = format!("Boot ({:.1}s)", analysis.total.as_secs_f64());
+    render_stacked_bar(frame, stages_area, &title, &segments, |ms| {
+        format!("{ms:.0} ms")
+    });
+    frame.render_widget(
+        render_table(
+            vec!["Unit", "Time"],
+            &analysis.slowest_units,
+            "Slowest units",
+        ),
+        units_area,
+    );
+}
+
+/// Formats the idle time of a session, e.g. `4m 10s`, blank while the
+/// terminal is in use
+fn format_idle(idle: Option<std::time::Duration>) -> String {
+    let Some(idle) = idle else {
+        return "?".to_string();
+    };
+    let seconds = idle.as_secs();
+    match seconds {
+        0..60 => String::new(),
+        60..3600 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        3600..86400 => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+/// Table row for a logged-in user session.
+///
+/// # Column Layout
+///
+/// 1. **User** (12 chars): Login name
+/// 2. **TTY** (8 chars): Terminal of the session
+/// 3. **From** (flexible): Remote host or display, blank for local logins
+/// 4. **Login** (12 chars): Local time the session started
+/// 5. **Idle** (8 chars): Time since the terminal last saw input
+impl TableRow for UserSession {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let login = self.login_time.with_timezone(&chrono::Local);
+        let login = if login.date_naive() == chrono::Local::now().date_naive() {
+            login.format("%H:%M").to_string()
+        } else {
+            login.format("%b %d %H:%M").to_string()
+        };
+        vec![
+            Cell::from(self.user.as_str()),
+            Cell::from(self.tty.as_str()),
+            Cell::from(self.host.as_deref().unwrap_or("")),
+            Cell::from(login),
+            Cell::from(format_idle(self.idle)),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Length(12), // User
+            Constraint::Length(8),  // TTY
+            Constraint::Min(12),    // From
+            Constraint::Length(12), // Login
+            Constraint::Length(8),  // Idle
+        ]
+    }
+}
+
+/// Renders the logged-in user sessions of the System page, oldest login
+/// first
+pub fn render_sessions(frame: &mut Frame, area: Rect, sessions: &SessionMetrics) {
+    let title = format!(
+        "Sessions ({} users, {} sessions)",
+        sessions.user_count(),
+        sessions.sessions.len()
+    );
+    frame.render_widget(
+        render_table(
+            vec!["User", "TTY", "From", "Login", "Idle"],
+            &sessions.sessions,
+            &title,
+        ),
+        area,
+    );
+}
@@ -0,0 +1,141 @@
+//! Virtual machines display implementation
+//!
+//! Renders the VMs defined in libvirt with their state and resource usage,
+//! plus the result of the last start/shutdown action and the keybindings.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    widgets::Cell,
+};
+use stomata_core::collectors::vms::{Vm, VmMetrics, VmState};
+
+use crate::{
+    renders::{
+        core_displays::traits::Display,
+        render_widgets::{render_paragraph::paragraph_widget, render_table::render_table},
+    },
+    structs::{TableRow, UIState},
+    theme::theme,
+    utils::format_bytes,
+};
+
+/// Implements table row conversion for VMs.
+///
+/// # Column Layout
+///
+/// 1. **Name** (flexible): Domain name
+/// 2. **State** (14 chars): Domain state, colored by health
+/// 3. **vCPUs** (6 chars): Number of virtual CPUs
+/// 4. **CPU%** (10 chars): CPU usage in percent of one core
+/// 5. **Memory** (20 chars): Used and maximum memory
+/// 6. **Disk R/W** (22 chars): Total bytes read and written
+/// 7. **Net RX/TX** (22 chars): Total bytes received and transmitted
+impl TableRow for Vm {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let state_color = match self.state {
+            VmState::Running => theme().ok,
+            VmState::Crashed => theme().critical,
+            VmState::ShutOff => theme().muted,
+            _ => theme().warning,
+        };
+        vec![
+            Cell::from(self.name.as_str()),
+            Cell::from(self.state.as_str()).style(Style::default().fg(state_color)),
+            Cell::from(self.vcpus.to_string()),
+            Cell::from(
+                self.cpu_usage
+                    .map(|usage| format!("{usage:.2}%"))
+                    .unwrap_or_default(),
+            ),
+            Cell::from(format!(
+                "{} / {}",
+                format_bytes(self.memory_used),
+                format_bytes(self.memory_max)
+            )),
+            Cell::from(format!(
+                "{} / {}",
+                format_bytes(self.disk_read_bytes),
+                format_bytes(self.disk_written_bytes)
+            )),
+            Cell::from(format!(
+                "{} / {}",
+                format_bytes(self.net_rx_bytes),
+                format_bytes(self.net_tx_bytes)
+            )),
+        ]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(20),    // Name
+            Constraint::Length(14), // State
+            Constraint::Length(6),  // vCPUs
+            Constraint::Length(10), // CPU%
+            Constraint::Length(20), // Memory
+            Constraint::Length(22), // Disk R/W
+            Constraint::Length(22), // Net RX/TX
+        ]
+    }
+}
+
+/// Display implementation for libvirt virtual machines
+impl Display for VmMetrics {
+    /// Renders the VMs table above a keybindings panel, or the
+    /// error when libvirt couldn't be queried.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The ratatui frame to render into
+    /// * `area` - The rectangular area allocated for the VMs page
+    /// * `ui_state` - Selection and action state, the table is not
+    ///   rendered without it
+    fn display(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_state: Option<&mut UIState>,
+    ) -> anyhow::Result<()> {
+        if let Some(error) = &self.error {
+            let text = format!(
+                "\n\n{error}\n\nThe VMs page needs virsh (libvirt-clients) and access to the libvirt daemon.\nSet the connection URI with `[libvirt] uri` in the config file."
+            );
+            frame.render_widget(
+                paragraph_widget(&text, "VMs").alignment(Alignment::Center),
+                area,
+            );
+            return Ok(());
+        }
+
+        let Some(ui_state) = ui_state else {
+            return Ok(());
+        };
+        let state = &mut ui_state.vms;
+
+        let layout = Layout::vertical([Constraint::Min(3), Constraint::Length(4)]).split(area);
+
+        let headers = vec![
+            "Name",
+            "State",
+            "vCPUs",
+            "CPU",
+            "Memory",
+            "Disk R/W",
+            "Net RX/TX",
+        ];
+        let title = format!("VMs ({})", self.vms.len());
+        let table = render_table(headers, &self.vms, &title);
+        state.table.set_count(self.vms.len());
+        state.names = self.vms.iter().map(|vm| vm.name.clone()).collect();
+        frame.render_stateful_widget(table, layout[0], &mut state.table.list);
+
+        let mut help = match &state.confirm_shutdown {
+            Some(name) => format!("Shut down '{name}'? y: confirm  any other key: cancel"),
+            None => String::new(),
+        };
+        help.push_str("\ns: start  x: shut down");
+        frame.render_widget(paragraph_widget(&help, "Actions"), layout[1]);
+        Ok(())
+    }
+}
@@ -7,18 +7,56 @@
 //! # Modules
 //!
 //! - `display_app` - Application-level display and layout
+//! - `display_column_editor` - Modal for showing, hiding and reordering process columns
+//! - `display_command_palette` - `Ctrl+P` modal searching the actions of the application
+//! - `display_connections` - TCP/UDP sockets with suspicious connections flagged
+//! - `display_controls` - Audio and Bluetooth devices with their controls
+//! - `display_cgroups` - Processes grouped by cgroup / systemd unit
+//! - `display_crash_events` - OOM kills, segfaults and service crashes from the logs
+//! - `display_disks` - Disk throughput, latency and queue depth
+//! - `display_error_log` - Popup listing the errors raised since startup
+//! - `display_history` - Stored CPU, memory and network charts with min/avg/max
+//! - `display_hosts` - Host sidebar and comparison grid of `stomata connect`
+//! - `display_logs` - Kernel log or journal tail colored by severity
 //! - `display_metrics` - System metrics visualization (CPU, memory, disk)
 //! - `display_network` - Network interface statistics and connections
+//! - `display_pods` - Kubernetes pods running on this node
+//! - `display_plugins` - Metrics reported by external plugin executables
 //! - `display_processes` - Interactive process list
+//! - `display_sensors` - hwmon temperatures and fan speeds with fan curves
+//! - `display_services` - systemd services with start/stop/restart actions
+//! - `display_signal_menu` - Modal for sending signals to a process
 //! - `display_single_process` - Detailed view of individual processes
+//! - `display_status_bar` - Transient messages at the bottom of the screen
+//! - `display_summary` - One line CPU/memory/swap/alerts strip above the tabs
 //! - `display_system_info` - OS and kernel information display
+//! - `display_vms` - libvirt virtual machines with start/shutdown actions
 //! - `traits` - Common display trait definitions
 
 pub mod display_app;
+pub mod display_cgroups;
+pub mod display_column_editor;
+pub mod display_command_palette;
+pub mod display_connections;
+pub mod display_controls;
+pub mod display_crash_events;
+pub mod display_disks;
+pub mod display_error_log;
+pub mod display_history;
+pub mod display_hosts;
+pub mod display_logs;
 pub mod display_metrics;
 pub mod display_network;
+pub mod display_plugins;
+pub mod display_pods;
 pub mod display_processes;
+pub mod display_sensors;
+pub mod display_services;
+pub mod display_signal_menu;
 pub mod display_single_process;
+pub mod display_status_bar;
+pub mod display_summary;
 pub mod display_system_info;
+pub mod display_vms;
 
 pub mod traits;
@@ -83,7 +83,7 @@ pub trait SingleProcessDisplay {
         &self,
         frame: &mut Frame,
         area: Rect,
-        total_memory: f64,
+        total_memory: u64,
         ui_state: &mut UIState,
     ) -> anyhow::Result<()>;
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "core")]
 pub mod core_displays;
 pub mod render_widgets;
+#[cfg(feature = "web3")]
 pub mod web3_displays;
@@ -6,14 +6,23 @@
 //!
 //! # Modules
 //!
-//! - `render_bar` - Bar chart widgets for categorical data visualization
 //! - `render_gauge` - Progress gauges for percentage-based metrics
+//! - `render_input` - Single-line text inputs with optional masking
+//! - `render_modal` - Popup dialogs drawn over the current page
+//! - `render_qr` - Terminal QR codes drawn with half-block characters
 //! - `render_paragraph` - Text paragraph widgets with borders and titles
+//! - `render_scrollbar` - Vertical scrollbars showing the position in a list
 //! - `render_sparkline` - Compact line charts for time-series data
+//! - `render_stacked_bar` - Horizontal bars split into colored segments
 //! - `render_table` - Tabular data display with sortable columns
 
-pub mod render_bar;
 pub mod render_gauge;
+pub mod render_input;
+pub mod render_modal;
 pub mod render_paragraph;
+#[cfg(feature = "web3")]
+pub mod render_qr;
+pub mod render_scrollbar;
 pub mod render_sparkline;
+pub mod render_stacked_bar;
 pub mod render_table;
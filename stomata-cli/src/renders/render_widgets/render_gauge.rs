@@ -5,11 +5,13 @@
 //! Gauges automatically change color based on usage thresholds.
 
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::Span,
     widgets::{Block, Borders, Gauge},
 };
 
+use crate::{theme::theme, utils::format_bytes};
+
 /// Creates a styled gauge widget for displaying resource usage.
 ///
 /// Renders a progress bar showing both percentage and absolute values
@@ -18,10 +20,13 @@ use ratatui::{
 ///
 /// # Arguments
 ///
-/// * `value` - Current usage value (e.g., used memory in GB)
-/// * `max` - Maximum available value (e.g., total memory in GB)
+/// * `value` - Current usage value (e.g., CPU usage in percent)
+/// * `max` - Maximum available value (e.g., 100 for percentages)
 /// * `label` - Title text displayed in the gauge border (e.g., "CPU Usage")
-/// * `unit` - Unit string for the values (e.g., "GB", "%", "MB/s")
+/// * `unit` - Unit string for the values (e.g., "%", "°C")
+///
+/// Byte counts should use [`render_bytes_gauge`] instead, which scales
+/// them to a readable unit.
 ///
 /// # Returns
 ///
@@ -31,7 +36,7 @@ use ratatui::{
 ///
 /// The gauge label shows: `{percentage:.2}% ({value:.2} {unit}/ {max:.2} {unit})`
 ///
-/// Example: `75.50% (6.04 GB/ 8.00 GB)`
+/// Example: `85.50% (85.50 %/ 100.00 %)`
 ///
 /// # Color Scheme
 ///
@@ -45,10 +50,6 @@ use ratatui::{
 /// ```ignore
 /// use crate::renders::render_widgets::render_gauge::render_gauge;
 ///
-/// // Memory usage gauge
-/// let gauge = render_gauge(6.04, 8.0, "Memory", "GB");
-/// frame.render_widget(gauge, area);
-///
 /// // CPU usage gauge
 /// let gauge = render_gauge(85.5, 100.0, "CPU", "%");
 /// frame.render_widget(gauge, area);
@@ -72,23 +73,44 @@ pub fn render_gauge<'a>(value: f64, max: f64, label: &'a str, unit: &'a str) ->
         unit
     );
 
-    let gauge = Gauge::default()
+    styled_gauge(ratio, display_label, label)
+}
+
+/// Creates a usage gauge for byte counts, e.g. memory or swap.
+///
+/// Works like [`render_gauge`] but shows the values in the largest
+/// fitting unit: `37.50% (3.0 GiB / 8.0 GiB)`.
+pub fn render_bytes_gauge(used: u64, total: u64, label: &str) -> Gauge<'_> {
+    let ratio = if total > 0 {
+        (used as f64 / total as f64).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let display_label = format!(
+        "{:.2}% ({} / {})",
+        ratio * 100.0,
+        format_bytes(used),
+        format_bytes(total)
+    );
+    styled_gauge(ratio, display_label, label)
+}
+
+fn styled_gauge(ratio: f64, display_label: String, label: &str) -> Gauge<'_> {
+    Gauge::default()
         .block(Block::default().borders(Borders::ALL).title(label))
         .gauge_style(
             Style::default()
                 .fg(if ratio > 0.9 {
-                    Color::Red
+                    theme().critical
                 } else {
-                    Color::LightBlue
+                    theme().gauge
                 })
-                .bg(Color::Black)
+                .bg(theme().background)
                 .add_modifier(Modifier::BOLD),
         )
         .label(Span::styled(
             display_label,
-            Style::default().fg(Color::White),
+            Style::default().fg(theme().text),
         ))
-        .ratio(ratio);
-
-    return gauge;
+        .ratio(ratio)
 }
@@ -0,0 +1,57 @@
+//! Text input widget rendering utilities
+//!
+//! Provides a single-line input box used by forms and modal dialogs.
+//! Sensitive values can be masked so passwords and keys never appear
+//! on screen.
+
+use ratatui::{
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::theme::theme;
+
+/// Creates a bordered single-line input box.
+///
+/// # Arguments
+///
+/// * `value` - Current text of the input
+/// * `title` - Label shown in the border
+/// * `masked` - Render every character as `*` instead of the real text
+/// * `focused` - Highlight the border and show a cursor at the end of the text
+///
+/// # Returns
+///
+/// A configured `Paragraph` widget ready for rendering
+///
+/// # Examples
+///
+/// ```ignore
+/// use crate::renders::render_widgets::render_input::input_widget;
+///
+/// let widget = input_widget(&password, "Password", true, true);
+/// frame.render_widget(widget, area);
+/// ```
+pub fn input_widget<'a>(value: &str, title: &'a str, masked: bool, focused: bool) -> Paragraph<'a> {
+    let mut text = if masked {
+        "*".repeat(value.chars().count())
+    } else {
+        value.to_string()
+    };
+    if focused {
+        text.push('█');
+    }
+
+    let border_style = if focused {
+        Style::default().fg(theme().focus)
+    } else {
+        Style::default().fg(theme().text)
+    };
+
+    Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .title(title),
+    )
+}
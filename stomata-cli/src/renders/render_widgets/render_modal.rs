@@ -0,0 +1,48 @@
+//! Modal dialog rendering utilities
+//!
+//! Provides helpers for drawing popups on top of the current page, such as
+//! forms and confirmation dialogs. The area behind the modal is cleared
+//! so the page underneath does not bleed through.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear},
+};
+
+use crate::theme::theme;
+
+/// Computes a rectangle centered inside `area`.
+///
+/// # Arguments
+///
+/// * `area` - The area to center within (usually the whole frame)
+/// * `width_percent` - Width of the popup as a percentage of `area`
+/// * `height` - Height of the popup in lines
+pub fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let [vertical] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [horizontal] = Layout::horizontal([Constraint::Percentage(width_percent)])
+        .flex(Flex::Center)
+        .areas(vertical);
+    horizontal
+}
+
+/// Clears `area` and draws a bordered modal frame with a title.
+///
+/// # Returns
+///
+/// The inner area of the modal, inside the border, for rendering content
+pub fn render_modal(frame: &mut Frame, area: Rect, title: &str) -> Rect {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().focus))
+        .title(title.to_string());
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    inner
+}
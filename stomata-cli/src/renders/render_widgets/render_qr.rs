@@ -0,0 +1,67 @@
+//! Terminal QR code rendering utilities
+//!
+//! Encodes text as a QR code and draws it with half-block characters, two
+//! modules per character cell, so addresses can be scanned off the screen
+//! by a phone on an air-gapped setup.
+
+use qrcode::{Color, QrCode};
+
+/// Light modules around the code required by scanners
+const QUIET_ZONE: usize = 2;
+
+/// Encodes `data` as a QR code drawn with half-block characters.
+///
+/// Light modules are drawn filled and dark modules blank, so the code scans
+/// on the usual dark terminal background.
+///
+/// # Arguments
+///
+/// * `data` - Text to encode
+///
+/// # Returns
+///
+/// One string per terminal row, or an error if `data` is too long to fit
+/// in a QR code.
+///
+/// # Examples
+///
+/// ```ignore
+/// use crate::renders::render_widgets::render_qr::qr_lines;
+///
+/// for line in qr_lines("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")? {
+///     println!("{line}");
+/// }
+/// ```
+pub fn qr_lines(data: &str) -> anyhow::Result<Vec<String>> {
+    let code = QrCode::new(data.as_bytes())?;
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = width + 2 * QUIET_ZONE;
+
+    let is_light = |x: usize, y: usize| {
+        if x < QUIET_ZONE || y < QUIET_ZONE || x >= width + QUIET_ZONE || y >= width + QUIET_ZONE {
+            return true;
+        }
+        colors[(y - QUIET_ZONE) * width + (x - QUIET_ZONE)] == Color::Light
+    };
+
+    let lines = (0..size)
+        .step_by(2)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let top = is_light(x, y);
+                    let bottom = y + 1 < size && is_light(x, y + 1);
+                    match (top, bottom) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(lines)
+}
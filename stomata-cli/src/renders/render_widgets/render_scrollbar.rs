@@ -0,0 +1,47 @@
+//! Scrollbar rendering utilities
+//!
+//! Draws a vertical scrollbar over the right border of a bordered table, so
+//! long lists show where the selection is within the whole list.
+
+use ratatui::{
+    Frame,
+    layout::{Margin, Rect},
+    style::Style,
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+
+use crate::theme::theme;
+
+/// Renders a scrollbar on the right border of `area`, between the corners.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - The bordered area the scrolled widget was rendered into
+/// * `total` - Number of rows of the whole list
+/// * `position` - Index of the selected row
+///
+/// Nothing is drawn when every row fits in `area`.
+pub fn render_scrollbar(frame: &mut Frame, area: Rect, total: usize, position: usize) {
+    // borders and the header row
+    let visible = area.height.saturating_sub(3) as usize;
+    if total <= visible {
+        return;
+    }
+    let mut state = ScrollbarState::new(total)
+        .viewport_content_length(visible)
+        .position(position);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .thumb_style(Style::default().fg(theme().accent))
+        .track_style(Style::default().fg(theme().muted));
+    frame.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut state,
+    );
+}
@@ -5,10 +5,12 @@
 //! trends in metrics like CPU usage, network throughput, or memory over time.
 
 use ratatui::{
-    style::{Color, Style},
-    widgets::{Block, Borders, Sparkline},
+    style::Style,
+    widgets::{Block, Borders, Sparkline, SparklineBar},
 };
 
+use crate::theme::theme;
+
 // Creates a styled sparkline widget for displaying time-series data.
 ///
 /// Renders a compact line chart with a border and title, showing the
@@ -18,7 +20,7 @@ use ratatui::{
 /// # Arguments
 ///
 /// * `data` - Slice of u64 values representing the time-series data points,
-///            ordered from oldest (left) to newest (right)
+///   ordered from oldest (left) to newest (right)
 /// * `title` - Title text displayed in the border
 ///
 /// # Returns
@@ -54,10 +56,39 @@ use ratatui::{
 /// - Works best with at least 10-20 data points for visible trends
 /// - Empty data will render an empty chart area
 pub fn render_sparkline<'a>(data: &'a [u64], title: &'a str) -> Sparkline<'a> {
-    let sparkline = Sparkline::default()
+    Sparkline::default()
         .block(Block::new().borders(Borders::ALL).title(title))
         .data(data)
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(theme().text))
+}
 
-    sparkline
+/// Creates a sparkline like [`render_sparkline`] with the bars flagged in
+/// `anomalies` drawn in the critical color.
+///
+/// # Arguments
+///
+/// * `data` - Values ordered from oldest (left) to newest (right)
+/// * `anomalies` - Whether each value is an anomaly, same order as `data`
+/// * `title` - Title text displayed in the border
+pub fn render_anomaly_sparkline<'a>(
+    data: &[u64],
+    anomalies: &[bool],
+    title: &'a str,
+) -> Sparkline<'a> {
+    let bars: Vec<SparklineBar> = data
+        .iter()
+        .zip(anomalies.iter().chain(std::iter::repeat(&false)))
+        .map(|(value, anomaly)| {
+            let bar = SparklineBar::from(*value);
+            if *anomaly {
+                bar.style(Style::default().fg(theme().critical))
+            } else {
+                bar
+            }
+        })
+        .collect();
+    Sparkline::default()
+        .block(Block::new().borders(Borders::ALL).title(title))
+        .data(bars)
+        .style(Style::default().fg(theme().text))
 }
@@ -0,0 +1,98 @@
+//! Stacked bar rendering utilities
+//!
+//! Provides a horizontal bar split into colored segments that add up to a
+//! total, with a legend below it. Used to show how memory is divided
+//! between applications, buffers, cache and free space.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::theme::theme;
+
+/// One part of a stacked bar
+pub struct Segment<'a> {
+    /// Name shown in the legend
+    pub label: &'a str,
+
+    /// Size of the segment, in the same unit as the total
+    pub value: f64,
+
+    /// Color of the segment and its legend entry
+    pub color: Color,
+}
+
+/// Renders a horizontal stacked bar with a legend.
+///
+/// Every segment gets a share of the bar width proportional to its value;
+/// rounding leftovers go to the last segment so the bar always fills the
+/// area. The legend lists each segment with its value and percentage.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - The rectangular area for the bar, including its border
+/// * `title` - Title text displayed in the border
+/// * `segments` - Segments in drawing order, left to right
+/// * `format_value` - Formats a segment value for the legend, e.g. as bytes
+pub fn render_stacked_bar(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    segments: &[Segment],
+    format_value: impl Fn(f64) -> String,
+) {
+    let total: f64 = segments.iter().map(|segment| segment.value.max(0.0)).sum();
+    let width = area.width.saturating_sub(2) as usize;
+
+    let mut bar = Vec::new();
+    let mut drawn = 0;
+    for (index, segment) in segments.iter().enumerate() {
+        let cells = if index + 1 == segments.len() {
+            width.saturating_sub(drawn)
+        } else if total > 0.0 {
+            ((segment.value.max(0.0) / total) * width as f64).round() as usize
+        } else {
+            0
+        }
+        .min(width.saturating_sub(drawn));
+        drawn += cells;
+        bar.push(Span::styled(
+            "█".repeat(cells),
+            Style::default().fg(segment.color),
+        ));
+    }
+
+    let mut legend = Vec::new();
+    for segment in segments {
+        let percentage = if total > 0.0 {
+            segment.value / total * 100.0
+        } else {
+            0.0
+        };
+        legend.push(Span::styled("■ ", Style::default().fg(segment.color)));
+        legend.push(Span::styled(
+            format!(
+                "{} {} ({:.1}%)   ",
+                segment.label,
+                format_value(segment.value),
+                percentage
+            ),
+            Style::default().fg(theme().text),
+        ));
+    }
+
+    // repeat the bar over the available height so it reads like a gauge
+    let bar_rows = area.height.saturating_sub(3).max(1);
+    let mut lines = vec![Line::from(bar.clone()); bar_rows as usize];
+    lines.push(Line::from(legend));
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(Style::default().bg(theme().background));
+    frame.render_widget(paragraph, area);
+}
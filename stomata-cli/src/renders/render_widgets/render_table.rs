@@ -6,69 +6,31 @@
 
 use ratatui::{
     layout::Constraint,
-    style::{Color, Style},
-    widgets::{Block, Borders, Cell, Row, Table},
+    style::Style,
+    widgets::{Block, Borders, Cell, HighlightSpacing, Row, Table},
 };
-use stomata_core::collectors::process::metrics::ProcessData;
-use sysinfo::Process;
+use stomata_core::ProcessData;
 
-use crate::{structs::TableRow, utils::bytes_to_mb};
+use crate::{structs::TableRow, theme::theme, utils::format_bytes};
 
-/// Implements table row conversion for `ProcessData`.
-///
-/// Formats process data into a 5-column table row with PID, name, CPU usage,
-/// memory consumption, and process status. Memory values are converted from
-/// bytes to megabytes for readability.
+/// Implements table row conversion for [`ProcessData`], used for the
+/// tasks of a process.
 ///
 /// # Column Layout
 ///
 /// 1. **PID** (8 chars): Process identifier
 /// 2. **Name** (20+ chars, flexible): Process name
 /// 3. **CPU%** (10 chars): CPU usage percentage with 2 decimal places
-/// 4. **Memory** (12 chars): Memory usage in MB
+/// 4. **Memory** (12 chars): Memory usage, e.g. `45.2 MiB`
 /// 5. **Status** (10 chars): Process status string
 impl TableRow for ProcessData {
     fn to_cells(&self) -> Vec<Cell<'_>> {
         vec![
             Cell::from(self.pid.to_string()),
-            Cell::from(self.name.clone()),
+            Cell::from(self.name.as_str()),
             Cell::from(format!("{:.2}%", self.cpu_usage)),
-            Cell::from(format!("{} MB", bytes_to_mb(self.memory))),
-            Cell::from(self.status.clone()),
-        ]
-    }
-
-    fn column_widths() -> Vec<Constraint> {
-        vec![
-            Constraint::Length(8),  // PID
-            Constraint::Min(20),    // Name (flexible)
-            Constraint::Length(10), // CPU%
-            Constraint::Length(12), // Memory
-            Constraint::Length(10), // Status
-        ]
-    }
-}
-
-/// Implements table row conversion for `sysinfo::Process` references.
-///
-/// Provides direct rendering of `sysinfo` process objects without intermediate
-/// conversion. Uses the same column layout as `ProcessData` for consistency.
-///
-/// # Column Layout
-///
-/// 1. **PID** (8 chars): Process identifier
-/// 2. **Name** (20+ chars, flexible): Process name
-/// 3. **CPU%** (10 chars): CPU usage percentage with 2 decimal places
-/// 4. **Memory** (12 chars): Memory usage in MB
-/// 5. **Status** (10 chars): Process status string
-impl TableRow for &Process {
-    fn to_cells(&self) -> Vec<Cell<'_>> {
-        vec![
-            Cell::from(self.pid().as_u32().to_string()),
-            Cell::from(self.name().to_string_lossy().to_string()),
-            Cell::from(format!("{:.2}%", self.cpu_usage())),
-            Cell::from(format!("{} MB", bytes_to_mb(self.memory()))),
-            Cell::from(self.status().to_string()),
+            Cell::from(format_bytes(self.memory)),
+            Cell::from(self.status.as_str()),
         ]
     }
 
@@ -92,7 +54,7 @@ impl TableRow for &Process {
 /// # Type Parameters
 ///
 /// * `T` - Any type implementing the `TableRow` trait, which defines how to
-///         convert the type into table cells and column widths
+///   convert the type into table cells and column widths
 ///
 /// # Arguments
 ///
@@ -129,11 +91,15 @@ impl TableRow for &Process {
 /// - Column widths are defined by the `TableRow::column_widths()` implementation
 /// - The table requires a `TableState` for rendering selection state
 /// - All rows have a fixed height of 1 line
+/// - The header is drawn outside the scrolled rows, so it stays in place
+///   however far the selection moves down
+/// - Tables whose rows come and go should set their row count with
+///   `TableUIState::set_count`, which keeps the selection on a row
 pub fn render_table<'a, T>(headers: Vec<&'a str>, items: &'a [T], title: &'a str) -> Table<'a>
 where
     T: TableRow,
 {
-    let header_style = Style::default().fg(Color::White).bg(Color::Black);
+    let header_style = Style::default().fg(theme().text).bg(theme().background);
 
     let header = headers
         .into_iter()
@@ -151,9 +117,16 @@ where
         .collect();
 
     Table::new(rows, T::column_widths())
-        .row_highlight_style(Style::default().bg(Color::White).fg(Color::Black))
+        .row_highlight_style(
+            Style::default()
+                .bg(theme().selection_bg)
+                .fg(theme().selection_fg),
+        )
         .highlight_symbol(">>")
+        // reserve the column of the symbol even without a selection, so
+        // the columns don't shift when the list empties and fills again
+        .highlight_spacing(HighlightSpacing::Always)
         .header(header)
         .block(Block::default().title(title).borders(Borders::ALL))
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme().text))
 }
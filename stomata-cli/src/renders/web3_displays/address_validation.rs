@@ -6,6 +6,8 @@
 
 use stomata_web3::providers::address::{AddressValidator, ValidationResult};
 
+use crate::renders::render_widgets::render_qr::qr_lines;
+
 /// Validates a blockchain address and prints the validation result.
 ///
 /// Performs comprehensive validation on the provided address string,
@@ -15,6 +17,7 @@ use stomata_web3::providers::address::{AddressValidator, ValidationResult};
 /// # Arguments
 ///
 /// * `address` - The blockchain address string to validate (e.g., Ethereum address)
+/// * `qr` - Also print the checksummed address of a valid address as a QR code
 ///
 /// # Validation Checks
 ///
@@ -29,11 +32,11 @@ use stomata_web3::providers::address::{AddressValidator, ValidationResult};
 /// use crate::validate_address;
 ///
 /// // Valid Ethereum address
-/// validate_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb");
+/// validate_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb", false);
 /// // Output: ValidationResult::Valid
 ///
 /// // Invalid address
-/// validate_address("0xinvalid");
+/// validate_address("0xinvalid", false);
 /// // Output: ValidationResult::Invalid { reason: ... }
 /// ```
 ///
@@ -48,7 +51,17 @@ use stomata_web3::providers::address::{AddressValidator, ValidationResult};
 /// - For production use, consider using `AddressValidator::validate()` directly
 ///   and handling the `ValidationResult` programmatically
 /// - The validation logic is provided by the `stomata_web3` crate
-pub fn validate_address(address: &str) {
+pub fn validate_address(address: &str, qr: bool) {
     let result = AddressValidator::validate(address);
     println!("{:?}", result);
+
+    if qr {
+        match &result {
+            ValidationResult::Valid { checksummed } => match qr_lines(checksummed) {
+                Ok(lines) => lines.iter().for_each(|line| println!("{line}")),
+                Err(err) => eprintln!("Failed to render QR code: {err}"),
+            },
+            _ => eprintln!("Not rendering a QR code for an invalid address"),
+        }
+    }
 }
@@ -0,0 +1,143 @@
+//! Address Validation page rendering
+//!
+//! Draws the address input box and the live validation result below it:
+//! validity with the reason for invalid addresses, the checksummed form
+//! and the chain detected from the address format. Valid addresses can also
+//! be shown as a QR code.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use stomata_web3::providers::address::{Chain, ValidationResult};
+
+use crate::{
+    features::web3::address_validator::{AddressValidatorState, ChecksumStatus},
+    renders::render_widgets::{
+        render_input::input_widget, render_paragraph::paragraph_widget, render_qr::qr_lines,
+    },
+    theme::theme,
+};
+
+/// Renders the Address Validation page.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - The rectangular area allocated for the page
+/// * `state` - Current input and validation result
+///
+/// # Layout
+///
+/// - Top: address input
+/// - Middle: validation result, next to the QR code when toggled on
+/// - Bottom: available keybindings
+pub fn render_address_validator(frame: &mut Frame, area: Rect, state: &AddressValidatorState) {
+    let layout = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(6),
+        Constraint::Length(3),
+    ])
+    .split(area);
+
+    let input = input_widget(
+        &state.input.value,
+        state.input.label,
+        state.input.masked,
+        state.editing,
+    );
+    frame.render_widget(input, layout[0]);
+
+    let result = Paragraph::new(result_lines(state))
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title("Result"));
+
+    match (&state.result, state.show_qr) {
+        (Some(ValidationResult::Valid { checksummed }), true) => {
+            let qr = qr_lines(checksummed).unwrap_or_else(|err| vec![err.to_string()]);
+            let qr_width = qr.first().map_or(0, |line| line.chars().count()) as u16 + 2;
+            let [result_area, qr_area] =
+                Layout::horizontal([Constraint::Min(20), Constraint::Length(qr_width)])
+                    .areas(layout[1]);
+            frame.render_widget(result, result_area);
+            frame.render_widget(
+                Paragraph::new(qr.join("\n"))
+                    .block(Block::default().borders(Borders::ALL).title("QR")),
+                qr_area,
+            );
+        }
+        _ => frame.render_widget(result, layout[1]),
+    }
+
+    let help = if state.editing {
+        "Type or paste an address  Esc: stop editing"
+    } else {
+        "Enter/i: edit  c: clear  r: toggle QR code  q: quit"
+    };
+    frame.render_widget(paragraph_widget(help, "Keys"), layout[2]);
+}
+
+/// Builds the lines of the result panel.
+fn result_lines(state: &AddressValidatorState) -> Vec<Line<'_>> {
+    let Some(result) = &state.result else {
+        return vec![Line::from(
+            "Enter an EVM address (0x followed by 40 hex characters) to validate it",
+        )];
+    };
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(theme().label));
+    let mut lines = Vec::new();
+
+    match result {
+        ValidationResult::Valid { checksummed } => {
+            lines.push(Line::from(vec![
+                label("Status:       "),
+                Span::styled("Valid", Style::default().fg(theme().ok)),
+            ]));
+            lines.push(Line::from(vec![
+                label("Checksummed:  "),
+                Span::raw(checksummed.as_str()),
+            ]));
+        }
+        _ if matches!(state.chain, Some(Chain::Bitcoin | Chain::Solana)) => {
+            lines.push(Line::from(vec![
+                label("Status:       "),
+                Span::styled(
+                    "Not validated, only EVM addresses are checked",
+                    Style::default().fg(theme().warning),
+                ),
+            ]));
+        }
+        invalid => {
+            lines.push(Line::from(vec![
+                label("Status:       "),
+                Span::styled(
+                    format!("Invalid, {}", invalid.reason().unwrap_or("unknown reason")),
+                    Style::default().fg(theme().critical),
+                ),
+            ]));
+        }
+    }
+
+    if let Some(checksum) = state.checksum {
+        let (text, color) = match checksum {
+            ChecksumStatus::Matches => ("matches EIP-55", theme().ok),
+            ChecksumStatus::NotChecksummed => ("not checksummed", theme().warning),
+            ChecksumStatus::Mismatch => ("mismatch, check for typos", theme().critical),
+        };
+        lines.push(Line::from(vec![
+            label("Checksum:     "),
+            Span::styled(text, Style::default().fg(color)),
+        ]));
+    }
+
+    lines.push(Line::from(vec![
+        label("Detected:     "),
+        Span::raw(state.chain.map_or("unknown format", |chain| chain.name())),
+    ]));
+
+    lines
+}
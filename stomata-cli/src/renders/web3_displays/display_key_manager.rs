@@ -0,0 +1,169 @@
+//! Key Management page rendering
+//!
+//! Draws the list of stored encrypted keys along with the modal forms used
+//! to encrypt, decrypt and delete keys. Passwords and key input are masked;
+//! a decrypted key is only shown after the correct password was entered.
+
+use chrono::DateTime;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Cell, Paragraph, Wrap},
+};
+use stomata_web3::providers::KeyMetadata;
+use zeroize::Zeroizing;
+
+use crate::{
+    features::web3::key_manager::{KeyAction, KeyManagerState, KeyModal},
+    renders::render_widgets::{
+        render_input::input_widget,
+        render_modal::{centered_rect, render_modal},
+        render_paragraph::paragraph_widget,
+        render_table::render_table,
+    },
+    structs::TableRow,
+    theme::theme,
+};
+
+/// Problems listed in the warning banner, the rest are counted in its title
+const MAX_BANNER_ISSUES: usize = 3;
+
+/// Implements table row conversion for stored key metadata.
+///
+/// # Column Layout
+///
+/// 1. **Name** (flexible): Key identifier
+/// 2. **Created at** (20 chars): Local creation time, or the raw value if it
+///    isn't a valid RFC 3339 timestamp
+impl TableRow for KeyMetadata {
+    fn to_cells(&self) -> Vec<Cell<'_>> {
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|_| self.created_at.clone());
+        vec![Cell::from(self.name.clone()), Cell::from(created_at)]
+    }
+
+    fn column_widths() -> Vec<Constraint> {
+        vec![
+            Constraint::Min(20),    // Name (flexible)
+            Constraint::Length(20), // Created at
+        ]
+    }
+}
+
+/// Renders the Key Management page.
+///
+/// # Arguments
+///
+/// * `frame` - The ratatui frame to render into
+/// * `area` - The rectangular area allocated for the page
+/// * `state` - Key list, selection and open modal
+///
+/// # Layout
+///
+/// - Top: warning banner listing the problems found in the key store, if any
+/// - Middle: table of stored keys with their creation time
+/// - Bottom: the available keybindings
+/// - Overlay: the open modal form, if any
+pub fn render_key_manager(frame: &mut Frame, area: Rect, state: &mut KeyManagerState) {
+    let banner_height = if state.issues.is_empty() {
+        0
+    } else {
+        state.issues.len().min(MAX_BANNER_ISSUES) as u16 + 2
+    };
+    let [banner_area, table_area, help_area] = Layout::vertical([
+        Constraint::Length(banner_height),
+        Constraint::Min(3),
+        Constraint::Length(3),
+    ])
+    .areas(area);
+
+    if !state.issues.is_empty() {
+        let text = state
+            .issues
+            .iter()
+            .take(MAX_BANNER_ISSUES)
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let title = format!(
+            "Key store: {} problem(s), run `stomata web3 key verify`",
+            state.issues.len()
+        );
+        frame.render_widget(
+            paragraph_widget(&text, &title).style(Style::default().fg(theme().warning)),
+            banner_area,
+        );
+    }
+
+    let headers = vec!["Name", "Created at"];
+    let table = render_table(headers, &state.keys, "Stored Keys");
+    frame.render_stateful_widget(table, table_area, &mut state.key_list);
+
+    frame.render_widget(
+        paragraph_widget("n: new key  d/Enter: decrypt  x: delete  r: reload", "Keys"),
+        help_area,
+    );
+
+    if let Some(modal) = &state.modal {
+        render_key_modal(frame, modal);
+    }
+}
+
+/// Renders the open key manager modal centered over the frame.
+fn render_key_modal(frame: &mut Frame, modal: &KeyModal) {
+    let title = match &modal.key_name {
+        Some(name) => format!("{} '{}'", modal.action.title(), name),
+        None => modal.action.title().to_string(),
+    };
+
+    if let Some(secret) = &modal.revealed {
+        let text = Zeroizing::new(match std::str::from_utf8(secret.expose_secret()) {
+            Ok(utf8) => utf8.to_string(),
+            Err(_) => format!("0x{}", hex::encode(secret.expose_secret())),
+        });
+        let area = centered_rect(frame.area(), 60, 8);
+        let inner = render_modal(frame, area, &title);
+        let [value, hint] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+        frame.render_widget(
+            Paragraph::new(text.as_str()).wrap(Wrap { trim: false }),
+            value,
+        );
+        frame.render_widget(Paragraph::new("Enter/Esc: close and wipe"), hint);
+        return;
+    }
+
+    let message = match modal.action {
+        KeyAction::Delete => "This permanently removes the key. Enter: confirm  Esc: cancel",
+        _ => "Tab: next field  Enter: submit  Esc: cancel",
+    };
+
+    let height = modal.fields.len() as u16 * 3 + 4;
+    let area = centered_rect(frame.area(), 60, height);
+    let inner = render_modal(frame, area, &title);
+
+    let mut constraints = vec![Constraint::Length(3); modal.fields.len()];
+    constraints.push(Constraint::Length(1));
+    constraints.push(Constraint::Length(1));
+    let rows = Layout::vertical(constraints).split(inner);
+
+    for (index, field) in modal.fields.iter().enumerate() {
+        let widget = input_widget(
+            &field.value,
+            field.label,
+            field.masked,
+            index == modal.focused,
+        );
+        frame.render_widget(widget, rows[index]);
+    }
+
+    if let Some(error) = &modal.error {
+        frame.render_widget(
+            Paragraph::new(error.as_str()).style(Style::default().fg(theme().critical)),
+            rows[modal.fields.len()],
+        );
+    }
+    frame.render_widget(Paragraph::new(message), rows[modal.fields.len() + 1]);
+}
@@ -4,9 +4,13 @@
 //! using password-based encryption. Keys are stored locally in encrypted
 //! form and can only be decrypted with the correct password.
 
-use std::process::exit;
+use std::{process::exit, time::Duration};
 
-use stomata_web3::providers::{delete_key, list_keys, retrieve_key, store_key};
+use stomata_web3::providers::{
+    KdfParams, SecretBytes, bench_kdf, candidate_params, delete_key, list_keys, recommend,
+    retrieve_key, store_key, verify_key_store,
+};
+use zeroize::Zeroizing;
 
 use crate::features::web3::cli::OutputFormat;
 
@@ -21,7 +25,7 @@ use crate::features::web3::cli::OutputFormat;
 ///
 /// # Returns
 ///
-/// The entered string, or exits the program on error
+/// The entered string, wiped from memory when dropped, or exits the program on error
 ///
 /// # Examples
 ///
@@ -33,15 +37,14 @@ use crate::features::web3::cli::OutputFormat;
 /// # Panics
 ///
 /// Calls `exit(0)` if reading from stdin fails
-fn ask_sensitive_info(ask_text: &str) -> String {
-    let info = match rpassword::prompt_password(ask_text) {
-        Ok(pw) => pw,
-        Err(err) => {
+fn ask_sensitive_info(ask_text: &str) -> Zeroizing<String> {
+    match rpassword::prompt_password(ask_text) {
+        Ok(pw) => Zeroizing::new(pw),
+        Err(_err) => {
             eprintln!("Error in reading entered data");
             exit(0)
         }
-    };
-    info
+    }
 }
 
 /// Encrypts and stores a key with password-based encryption.
@@ -77,11 +80,12 @@ fn ask_sensitive_info(ask_text: &str) -> String {
 ///
 /// - Password is never stored, only used for encryption
 /// - Key input is not echoed to terminal
+/// - Password and key are zeroed in memory once encrypted
 /// - Encrypted data is stored locally by `stomata_web3`
 pub fn encrypt_key(name: String) {
     let password = ask_sensitive_info("Password: ");
-    let pk = ask_sensitive_info("Key to encrypt: ");
-    let res = store_key(name.as_str(), pk.as_bytes(), password.as_str());
+    let pk = SecretBytes::new(ask_sensitive_info("Key to encrypt: ").as_bytes().to_vec());
+    let res = store_key(name.as_str(), &pk, password.as_str());
     if let Err(err) = res {
         eprintln!("Error in encrypting key {:?}", err);
     }
@@ -130,15 +134,19 @@ pub fn encrypt_key(name: String) {
 ///
 /// - Password verification is implicit (wrong password = decryption failure)
 /// - Decrypted data is printed to stdout (use with caution)
+/// - Decrypted bytes are zeroed in memory after printing
 pub fn decrypt_key(name: String, format: OutputFormat) {
     let password = ask_sensitive_info("Password: ");
     let res = retrieve_key(name.as_str(), password.as_str());
     if let Ok(data) = res {
         match format {
-            OutputFormat::Hex => println!("{:?}", hex::encode(&data)),
+            OutputFormat::Hex => {
+                let encoded = Zeroizing::new(hex::encode(data.expose_secret()));
+                println!("{:?}", encoded.as_str())
+            }
             OutputFormat::Utf8 => println!(
                 "{:?}",
-                String::from_utf8(data).expect("Failed to decrypt key to utf-8")
+                std::str::from_utf8(data.expose_secret()).expect("Failed to decrypt key to utf-8")
             ),
         }
     };
@@ -210,3 +218,84 @@ pub fn delete_encrypted_key(name: String) {
         eprintln!("Error in deleting key {name}: {:?}", err);
     }
 }
+
+/// Checks the key store for malformed key files, loose permissions and
+/// duplicate key names.
+///
+/// # Output
+///
+/// Prints each problem on a separate line, or a confirmation when the key
+/// store is fine.
+///
+/// # Errors
+///
+/// Exits with status 1 if a problem is found or the keys directory can't be
+/// read.
+pub fn verify_keys() {
+    match verify_key_store() {
+        Ok(issues) if issues.is_empty() => println!("Key store OK"),
+        Ok(issues) => {
+            for issue in &issues {
+                println!("{issue}");
+            }
+            eprintln!("{} problem(s) found in the key store", issues.len());
+            exit(1);
+        }
+        Err(err) => {
+            eprintln!("Failed to check the key store: {err}");
+            exit(1);
+        }
+    }
+}
+
+/// Times Argon2id derivation for each candidate parameter set and prints
+/// the strongest set that stays within `target_ms`.
+pub fn kdf_bench(target_ms: u64) {
+    let target = Duration::from_millis(target_ms);
+    println!(
+        "{:>10}  {:>10}  {:>11}  {:>10}",
+        "MEMORY", "ITERATIONS", "PARALLELISM", "TIME"
+    );
+    let mut results = Vec::new();
+    for params in candidate_params() {
+        match bench_kdf(params, 3) {
+            Ok(result) => {
+                println!(
+                    "{:>7} MiB  {:>10}  {:>11}  {:>7} ms",
+                    params.memory_kib / 1024,
+                    params.iterations,
+                    params.parallelism,
+                    result.duration.as_millis()
+                );
+                // larger sets only get slower, no point measuring them
+                let over_target = result.duration > target * 2;
+                results.push(result);
+                if over_target {
+                    break;
+                }
+            }
+            Err(err) => {
+                eprintln!("Failed to benchmark {params:?}: {err}");
+                exit(1);
+            }
+        }
+    }
+
+    println!();
+    match recommend(&results, target) {
+        Some(params) => println!(
+            "Recommended for {target_ms} ms: memory {} MiB, iterations {}, parallelism {}",
+            params.memory_kib / 1024,
+            params.iterations,
+            params.parallelism
+        ),
+        None => println!("No parameter set derives a key within {target_ms} ms on this machine"),
+    }
+    let default = KdfParams::DEFAULT;
+    println!(
+        "Keys are currently encrypted with memory {} MiB, iterations {}, parallelism {}",
+        default.memory_kib / 1024,
+        default.iterations,
+        default.parallelism
+    );
+}
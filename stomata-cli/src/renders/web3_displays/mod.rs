@@ -1,2 +1,4 @@
 pub mod address_validation;
+pub mod display_address_validator;
+pub mod display_key_manager;
 pub mod key_encryption;
@@ -11,16 +11,15 @@ use ratatui::{
     Frame,
     crossterm::event::{KeyCode, KeyEvent},
     layout::{Constraint, Layout},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState},
 };
 
-#[cfg(feature = "web3")]
-use crate::structs::Feature;
 use crate::{
-    renders::render_widgets::render_paragraph::{self, paragraph_widget},
-    structs::{AppState, StomataState},
+    renders::render_widgets::render_paragraph::paragraph_widget,
+    structs::{AppState, Feature, StomataState},
+    theme::theme,
 };
 
 impl StomataState {
@@ -101,13 +100,15 @@ impl StomataState {
         } else {
             let items: Vec<ListItem> = self
                 .available_features
-                .iter()
-                .map(|(key, feature)| {
+                .values()
+                .map(|feature| {
                     let (name, desc) = match feature {
+                        #[cfg(feature = "core")]
                         Feature::Core => (
                             "System Monitor",
                             "Monitor CPU, Memory and Swap, Disk and Network metrics",
                         ),
+                        #[cfg(feature = "web3")]
                         Feature::Web3 => (
                             "Web3 Tools",
                             "Access web3 tools for address validation and more...",
@@ -119,7 +120,7 @@ impl StomataState {
                             name,
                             Style::default().add_modifier(Modifier::BOLD),
                         )),
-                        Line::from(Span::styled(desc, Style::default().fg(Color::Gray))),
+                        Line::from(Span::styled(desc, Style::default().fg(theme().muted))),
                     ])
                 })
                 .collect();
@@ -132,7 +133,8 @@ impl StomataState {
                 )
                 .highlight_style(
                     Style::default()
-                        .bg(Color::DarkGray)
+                        .bg(theme().selection_bg)
+                        .fg(theme().selection_fg)
                         .add_modifier(Modifier::BOLD),
                 )
                 .highlight_symbol("► ");
@@ -150,15 +152,13 @@ impl StomataState {
     /// user input.
     pub fn handle_feature_selection(&mut self, key: KeyEvent) -> bool {
         match key.code {
-            KeyCode::Up => {
-                if self.selected_feature > 0 {
-                    self.selected_feature -= 1;
-                }
+            KeyCode::Up if self.selected_feature > 0 => {
+                self.selected_feature -= 1;
             }
-            KeyCode::Down => {
-                if self.selected_feature < self.available_features.len().saturating_sub(1) {
-                    self.selected_feature += 1;
-                }
+            KeyCode::Down
+                if self.selected_feature < self.available_features.len().saturating_sub(1) =>
+            {
+                self.selected_feature += 1;
             }
             KeyCode::Enter => {
                 if let Some(&feature) = self.available_features.values().nth(self.selected_feature)
@@ -4,20 +4,69 @@
 //! feature enums, application state, CLI arguments, page navigation,
 //! UI state management, and ring buffers for time-series data storage.
 
-use std::collections::{HashMap, VecDeque};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet, VecDeque},
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
 
 use clap::Parser;
 use ratatui::{
-    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
     layout::Constraint,
     widgets::{Cell, TableState},
 };
+#[cfg(feature = "geoip")]
+use stomata_core::collectors::connections::geoip::GeoIp;
 use stomata_core::collectors::{
-    network::metrics::NetworkInterfaces, process::metrics::SingleProcessData,
+    BootAnalysis,
+    disks::DiskIo,
+    network::metrics::NetworkInterfaces,
+    process::{
+        ProcessGroupBy, ProcessLifetimes, ProcessStateFilter,
+        metrics::{ProcessData, ProcessDiskUsage, SingleProcessData},
+    },
+    system::scheduler::SchedulerRates,
 };
-use sysinfo::DiskUsage;
+use stomata_core::history::{AnomalyDetector, TimeSeries};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "web3")]
+use crate::features::core::exe_checksum::ExeChecksum;
 
-use crate::constants::{CLAMP_TREND_VALUE, MAX_HISTORY_IN_MEMORY, MAX_NETWORK_IN_MEMORY};
+use crate::{
+    config::config,
+    constants::{
+        CLAMP_TREND_VALUE, MAX_HISTORY_IN_MEMORY, MAX_NETWORK_IN_MEMORY, STATUS_MESSAGE_SECS,
+        TEXT_INPUT_MAX_BYTES,
+    },
+    features::core::{
+        affinity_editor::AffinityEditor,
+        audio_controls::AudioUIState,
+        bluetooth_controls::BluetoothUIState,
+        command_line::CommandLine,
+        command_palette::CommandPalette,
+        crash_events::CrashEventsUIState,
+        disks::DisksUIState,
+        follow::FollowTarget,
+        hardware::HardwareUIState,
+        history::HistoryUIState,
+        listening_ports::PortsUIState,
+        logs::LogsUIState,
+        metrics_layout::MetricsRow,
+        open_files::OpenFilesUIState,
+        power_controls::PowerUIState,
+        process_columns::{ColumnEditor, ProcessColumns},
+        process_env::ProcessEnvUIState,
+        service_actions::ServicesUIState,
+        signal_menu::SignalMenu,
+        tasks::TasksUIState,
+        vm_actions::VmsUIState,
+    },
+};
 
 /// Available application features determined by compile-time flags.
 ///
@@ -77,6 +126,9 @@ pub struct StomataState {
 ///
 /// # Custom refresh interval
 /// stomata -i --interval 500
+///
+/// # Light terminal background
+/// stomata -i --theme light
 /// ```
 #[derive(Parser, Debug, Clone)]
 #[command(name = "stomata")]
@@ -90,10 +142,30 @@ pub struct Cli {
     #[arg(short = 't', long, default_value_t = 1000)]
     pub interval: u64,
 
-    /// Enable data storage/persistence (feature-dependent behavior)
+    /// Record CPU, memory and network history to
+    /// `~/.local/share/stomata/metrics.db` for the History tab
     #[arg(short, long, default_value_t = false)]
     pub store: bool,
 
+    /// Keep the machine from suspending while the monitor runs, toggled
+    /// with `w` in the TUI
+    #[arg(long, default_value_t = false)]
+    pub inhibit_sleep: bool,
+
+    /// Write the process table to a CSV file and exit without the TUI
+    #[arg(long, value_name = "PATH")]
+    pub export_csv: Option<PathBuf>,
+
+    /// Color theme: dark, light, solarized, gruvbox or a theme from the config file
+    #[arg(long)]
+    pub theme: Option<String>,
+
+    /// Level of the log file (`~/.local/state/stomata/stomata.log`): off,
+    /// error, warn, info, debug, trace or filter directives such as
+    /// `stomata_core=debug`
+    #[arg(long, default_value = "warn")]
+    pub log_level: String,
+
     /// Feature to run in CLI mode (ignored in interactive mode)
     pub feature: Option<String>,
 
@@ -123,6 +195,86 @@ pub enum Page {
 
     /// Network interface statistics and trends
     Network,
+
+    /// Addresses and larger charts of the network interface with the given name
+    InterfaceDetail(String),
+
+    /// Metrics reported by external plugin executables
+    Plugins,
+
+    /// TCP and UDP sockets with suspicious connections flagged
+    Connections,
+
+    /// Resource usage aggregated per cgroup / systemd unit
+    Cgroups,
+
+    /// Kubernetes pods running on this node
+    Pods,
+
+    /// libvirt virtual machines
+    Vms,
+
+    /// Audio and Bluetooth devices with their controls
+    Controls,
+
+    /// OOM kills, segfaults and service crashes logged since boot
+    Events,
+
+    /// Disk throughput, latency and queue depth
+    Disks,
+
+    /// hwmon temperatures and fan speeds
+    Sensors,
+
+    /// Listening sockets with their owning process
+    Ports,
+
+    /// systemd services with start/stop/restart actions
+    Services,
+
+    /// Tail of the kernel log or journal
+    Logs,
+
+    /// CPU, memory and network history read from the `--store` database
+    History,
+
+    /// CPU and memory of every host `stomata connect` is connected to
+    Hosts,
+}
+
+/// Side of the split view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    /// Left half of the content area
+    Left,
+    /// Right half of the content area
+    Right,
+}
+
+/// Second pane of a split view, opened with `|`
+///
+/// The focused pane is the one in `App::current_page` and receives the
+/// keys; `SplitView` keeps the page of the other one until the focus
+/// switches to it with `Ctrl+Left`/`Ctrl+Right`. Both panes share the UI
+/// state, so the same page in both shows the same selection.
+#[derive(Debug, Clone)]
+pub struct SplitView {
+    /// Page of the pane without the focus
+    pub page: Page,
+
+    /// Tab index of `page`
+    pub tab_index: usize,
+
+    /// Side of the focused pane
+    pub focus: Pane,
+}
+
+/// Panel of the Controls page that receives the keys
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ControlsPanel {
+    #[default]
+    Audio,
+    Bluetooth,
 }
 
 impl Page {
@@ -132,9 +284,27 @@ impl Page {
     ///
     /// # Returns
     ///
-    /// Vector of static strings: `["System", "Metrics", "Processes", "Network"]`
+    /// Vector of static strings: `["System", "Metrics", "Processes", "Network", "Plugins", "Connections", "Cgroups", "Pods", "VMs", "Controls", "Events", "Disks", "Sensors", "Ports", "Services", "Logs"]`
     pub fn titles() -> Vec<&'static str> {
-        vec!["System", "Metrics", "Processes", "Network"]
+        vec![
+            "System",
+            "Metrics",
+            "Processes",
+            "Network",
+            "Plugins",
+            "Connections",
+            "Cgroups",
+            "Pods",
+            "VMs",
+            "Controls",
+            "Events",
+            "Disks",
+            "Sensors",
+            "Ports",
+            "Services",
+            "Logs",
+            "History",
+        ]
     }
 
     /// Converts a tab index to its corresponding page.
@@ -152,6 +322,19 @@ impl Page {
             1 => Page::Metrics,
             2 => Page::Processes,
             3 => Page::Network,
+            4 => Page::Plugins,
+            5 => Page::Connections,
+            6 => Page::Cgroups,
+            7 => Page::Pods,
+            8 => Page::Vms,
+            9 => Page::Controls,
+            10 => Page::Events,
+            11 => Page::Disks,
+            12 => Page::Sensors,
+            13 => Page::Ports,
+            14 => Page::Services,
+            15 => Page::Logs,
+            16 => Page::History,
             _ => Page::System,
         }
     }
@@ -189,6 +372,69 @@ pub trait TableRow {
     fn column_widths() -> Vec<Constraint>;
 }
 
+/// Editable single-line text input.
+///
+/// Holds the typed text in a zeroizing buffer so sensitive input such as
+/// passwords is wiped from memory when the input is dropped. The buffer is
+/// allocated once at [`TEXT_INPUT_MAX_BYTES`] and never grows, since a
+/// reallocation would free the old buffer without wiping it.
+pub struct TextInput {
+    /// Label shown in the input border
+    pub label: &'static str,
+
+    /// Current text of the input
+    pub value: Zeroizing<String>,
+
+    /// Whether the text should be rendered masked
+    pub masked: bool,
+}
+
+impl std::fmt::Debug for TextInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = if self.masked {
+            "[REDACTED]"
+        } else {
+            self.value.as_str()
+        };
+        f.debug_struct("TextInput")
+            .field("label", &self.label)
+            .field("value", &value)
+            .finish()
+    }
+}
+
+impl TextInput {
+    /// Creates an empty input with the given label.
+    pub fn new(label: &'static str, masked: bool) -> Self {
+        Self {
+            label,
+            value: Zeroizing::new(String::with_capacity(TEXT_INPUT_MAX_BYTES)),
+            masked,
+        }
+    }
+
+    /// Applies an editing key to the input.
+    ///
+    /// Printable characters are appended and `Backspace` removes the last
+    /// character. Characters that don't fit the buffer are dropped.
+    /// Returns `true` if the key was consumed.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char(c) => {
+                if self.value.len() + c.len_utf8() <= self.value.capacity() {
+                    self.value.push(c);
+                }
+                true
+            }
+            KeyCode::Backspace => {
+                self.value.pop();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Comprehensive UI state management for all monitoring views.
 ///
 /// Maintains state across different pages including table selections,
@@ -201,8 +447,168 @@ pub struct UIState {
     /// Disk I/O history for the currently viewed process
     pub single_process_disk_usage: SingleProcessDiskUsage,
 
+    /// CPU and memory history for the currently viewed process
+    pub single_process_usage: SingleProcessUsage,
+
+    /// Context switch, interrupt and fork rate history of the Metrics page
+    pub scheduler_history: SchedulerHistory,
+
+    /// Transient messages shown in the bottom bar, e.g. the result of a renice
+    pub status_bar: StatusBar,
+
+    /// Signals menu, open over the Processes or SingleProcess page
+    pub signal_menu: Option<SignalMenu>,
+
+    /// CPU affinity editor, open over the SingleProcess page
+    pub affinity_editor: Option<AffinityEditor>,
+
+    /// Column editor, open over the Processes page
+    pub column_editor: Option<ColumnEditor>,
+
+    /// Command line of the vim keymap profile, open in the status bar
+    pub command_line: Option<CommandLine>,
+
+    /// Command palette, open over any page
+    pub command_palette: Option<CommandPalette>,
+
+    /// Environment variables panel of the SingleProcess page
+    pub process_env: ProcessEnvUIState,
+
+    /// Open files panel of the SingleProcess page
+    pub open_files: OpenFilesUIState,
+
+    /// Tasks table of the SingleProcess page
+    pub tasks: TasksUIState,
+
+    /// Whether the memory breakdown panel of the SingleProcess page is shown
+    pub memory_breakdown_open: bool,
+
+    /// Process followed across restarts on the SingleProcess page
+    pub follow: Option<FollowTarget>,
+
+    /// Selection state of the connections table
+    pub connections_table: TableUIState,
+
+    /// Selection state of the cgroups table
+    pub cgroups_table: TableUIState,
+
+    /// Selection state of the pods table
+    pub pods_table: TableUIState,
+
+    /// Selection and pending actions of the VMs page
+    pub vms: VmsUIState,
+
+    /// Selection and last error of the audio panel
+    pub audio: AudioUIState,
+
+    /// Selection and last action of the Bluetooth panel
+    pub bluetooth: BluetoothUIState,
+
+    /// Panel of the Controls page with keyboard focus
+    pub controls_focus: ControlsPanel,
+
+    /// Brightness and power profile shown on the System page
+    pub power: PowerUIState,
+
+    /// Device list shown on the System page
+    pub hardware: HardwareUIState,
+
+    /// Crash events of the Events page, also marked on the network
+    /// sparklines
+    pub crash_events: CrashEventsUIState,
+
+    /// Boot time breakdown, or the error reading it. `None` until the
+    /// System page is first shown, since it doesn't change until reboot.
+    pub boot: Option<Result<BootAnalysis, String>>,
+
+    /// GeoIP databases for annotating connections, or the error opening
+    /// them. `None` when no database is configured.
+    #[cfg(feature = "geoip")]
+    pub geoip: Option<Result<GeoIp, String>>,
+
+    /// Whether metrics collection is paused; history isn't recorded while
+    /// paused so the frozen values aren't repeated in the charts
+    pub paused: bool,
+
     /// Time-series data for all network interfaces
     pub networks_state: Option<HashMap<String, NetworkInterfaceData>>,
+
+    /// Selected interface of the Network page
+    pub network_table: TableUIState,
+
+    /// Disk selection, history and mounts of the Disks page
+    pub disks: DisksUIState,
+
+    /// Selection and pending action of the Services page
+    pub services: ServicesUIState,
+
+    /// Filter and selection of the Ports page
+    pub ports: PortsUIState,
+
+    /// Filter, pause and entries of the Logs page
+    pub logs: LogsUIState,
+
+    /// Range and stored series of the History page
+    pub history: HistoryUIState,
+
+    /// Rolling baselines of the charted metrics and the anomalies found
+    /// against them
+    pub anomalies: AnomalyDetector,
+
+    /// Selected fan of the Sensors page
+    pub fans_table: TableUIState,
+
+    /// Selected host of the Hosts grid
+    pub hosts_table: TableUIState,
+
+    /// Temperature and RPM samples per fan, keyed by `chip/fan`
+    pub fan_history: HashMap<String, Ring<(f64, f64), MAX_HISTORY_IN_MEMORY>>,
+
+    /// Interface names in display order, to resolve the selection
+    pub network_names: Vec<String>,
+
+    /// Output of the user's scripting hook for the latest metrics refresh
+    pub script_output: Option<ScriptOutput>,
+
+    /// Rows of the Metrics page, from the config file
+    pub metrics_layout: Vec<MetricsRow>,
+
+    /// Checksum of the executable of the viewed process, once requested
+    #[cfg(feature = "web3")]
+    pub exe_checksum: Option<ExeChecksum>,
+
+    /// Open input for the expected executable checksum
+    #[cfg(feature = "web3")]
+    pub expected_hash_input: Option<TextInput>,
+}
+
+/// Output of the user's scripting hook for a single tick.
+///
+/// Produced by the `on_tick` function of the hooks script and consumed by
+/// the Metrics page.
+#[derive(Debug, Default)]
+pub struct ScriptOutput {
+    /// Derived values as (label, formatted value) pairs
+    pub values: Vec<(String, String)>,
+
+    /// Alert messages raised by the script
+    pub alerts: Vec<String>,
+
+    /// Gauge title overrides (default title -> replacement)
+    pub labels: HashMap<String, String>,
+
+    /// Compile or runtime error of the script
+    pub error: Option<String>,
+}
+
+impl ScriptOutput {
+    /// Returns the replacement for a widget title, or the title itself.
+    pub fn label<'a>(&'a self, default: &'a str) -> &'a str {
+        self.labels
+            .get(default)
+            .map(String::as_str)
+            .unwrap_or(default)
+    }
 }
 
 /// State management for the process list table.
@@ -219,6 +625,311 @@ pub struct ProcessesUIState {
 
     /// PID of the selected process (if any)
     pub selected_pid: Option<u32>,
+
+    /// Show the full path of the program in the command column
+    pub full_command: bool,
+
+    /// Processes started and exited between refreshes of the list
+    pub lifetimes: ProcessLifetimes,
+
+    /// Aggregate the list by user or executable name, `None` for the flat
+    /// list
+    pub group_by: Option<ProcessGroupBy>,
+
+    /// Keys of the groups showing their processes
+    pub expanded_groups: HashSet<String>,
+
+    /// Key of the selected group row, `None` when a process row is selected
+    pub selected_group: Option<String>,
+
+    /// Order of the columns and which of them are shown
+    pub columns: ProcessColumns,
+
+    /// Steps the table is scrolled to the right, clamped to the columns
+    /// and command lines when rendered
+    pub horizontal_scroll: usize,
+
+    /// Only list the processes of this cgroup path
+    pub cgroup_filter: Option<String>,
+
+    /// Cgroup path of the selected process
+    pub selected_cgroup: Option<String>,
+
+    /// Only list zombie or orphaned processes
+    pub state_filter: Option<ProcessStateFilter>,
+
+    /// Column the list is sorted by
+    pub sort: ProcessSort,
+
+    /// Rows visible at once, the distance PageUp and PageDown move
+    pub page_size: usize,
+
+    /// `g` was pressed and a second `g` jumps to the first row
+    pub pending_g: bool,
+
+    /// Text the processes are searched for in their name and command line
+    pub search: TextInput,
+
+    /// Whether keys go to the search box
+    pub searching: bool,
+}
+
+/// Column the process list is sorted by
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessSort {
+    /// Lowest PID first
+    #[default]
+    Pid,
+    /// Highest CPU usage first
+    Cpu,
+    /// Highest memory first
+    Memory,
+    /// Most memory swapped out first
+    Swap,
+    /// Most bytes read per second first
+    DiskRead,
+    /// Most bytes written per second first
+    DiskWrite,
+}
+
+impl ProcessSort {
+    /// The sort after this one
+    pub fn next(self) -> Self {
+        match self {
+            ProcessSort::Pid => ProcessSort::Cpu,
+            ProcessSort::Cpu => ProcessSort::Memory,
+            ProcessSort::Memory => ProcessSort::Swap,
+            ProcessSort::Swap => ProcessSort::DiskRead,
+            ProcessSort::DiskRead => ProcessSort::DiskWrite,
+            ProcessSort::DiskWrite => ProcessSort::Pid,
+        }
+    }
+
+    /// Parses the lowercase name of a sort column, e.g. `cpu`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "pid" => Some(ProcessSort::Pid),
+            "cpu" => Some(ProcessSort::Cpu),
+            "memory" | "mem" => Some(ProcessSort::Memory),
+            "swap" => Some(ProcessSort::Swap),
+            "read" => Some(ProcessSort::DiskRead),
+            "write" => Some(ProcessSort::DiskWrite),
+            _ => None,
+        }
+    }
+
+    /// Column name for the table title
+    pub fn label(self) -> &'static str {
+        match self {
+            ProcessSort::Pid => "PID",
+            ProcessSort::Cpu => "CPU",
+            ProcessSort::Memory => "Memory",
+            ProcessSort::Swap => "Swap",
+            ProcessSort::DiskRead => "Read",
+            ProcessSort::DiskWrite => "Write",
+        }
+    }
+
+    /// Sorts `processes` by this column; processes without a disk rate
+    /// yet go last
+    pub fn sort(self, processes: &mut [ProcessData]) {
+        let rate = |rate: Option<f64>| rate.unwrap_or(-1.0);
+        match self {
+            ProcessSort::Pid => processes.sort_by_key(|process| process.pid),
+            ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+            ProcessSort::Memory => processes.sort_by_key(|process| Reverse(process.memory)),
+            ProcessSort::Swap => processes.sort_by_key(|process| Reverse(process.swap)),
+            ProcessSort::DiskRead => {
+                processes.sort_by(|a, b| rate(b.disk_read_rate).total_cmp(&rate(a.disk_read_rate)))
+            }
+            ProcessSort::DiskWrite => processes
+                .sort_by(|a, b| rate(b.disk_write_rate).total_cmp(&rate(a.disk_write_rate))),
+        }
+    }
+}
+
+impl ProcessesUIState {
+    /// Keeps the selection on the same process across refreshes. `row` is
+    /// where the previously selected PID or group is listed now; when it is gone
+    /// the selected index stays, moved onto the last row when the list got
+    /// shorter.
+    pub fn keep_selection(&mut self, row: Option<usize>, row_count: usize) {
+        let selected = self.process_list.selected().unwrap_or_default();
+        let row = row.unwrap_or(selected.min(row_count.saturating_sub(1)));
+        self.process_list.select(Some(row));
+    }
+
+    /// Selects `row` on request of the user, so the next refresh keeps it
+    /// instead of following the previously selected process or group
+    pub fn select_row(&mut self, row: usize) {
+        self.process_list.select(Some(row));
+        self.selected_pid = None;
+        self.selected_group = None;
+    }
+
+    /// Moves the selection for the navigation keys: `Up`/`Down` by a row,
+    /// `PageUp`/`PageDown` by a page, `Home`/`gg` to the first row and
+    /// `End`/`G` to the last one. Returns `false` for any other key.
+    pub fn navigate(&mut self, key: KeyCode) -> bool {
+        let pending_g = std::mem::take(&mut self.pending_g);
+        let last = self.process_count.saturating_sub(1);
+        let selected = self.process_list.selected().unwrap_or_default();
+        let row = match key {
+            KeyCode::Down => (selected + 1).min(last),
+            KeyCode::Up => selected.saturating_sub(1),
+            KeyCode::PageDown => (selected + self.page_size).min(last),
+            KeyCode::PageUp => selected.saturating_sub(self.page_size),
+            KeyCode::Home => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            KeyCode::Char('g') if pending_g => 0,
+            KeyCode::Char('g') => {
+                self.pending_g = true;
+                return true;
+            }
+            _ => return false,
+        };
+        self.select_row(row);
+        true
+    }
+
+    /// Switches from the flat list to groups by user, then by name, then
+    /// back to the flat list, collapsing every group and selecting the top
+    /// row
+    pub fn cycle_group_by(&mut self) {
+        self.group_by = match self.group_by {
+            None => Some(ProcessGroupBy::User),
+            Some(ProcessGroupBy::User) => Some(ProcessGroupBy::Name),
+            Some(ProcessGroupBy::Name) => None,
+        };
+        self.expanded_groups.clear();
+        self.selected_group = None;
+        self.select_row(0);
+    }
+
+    /// Lists only the processes in the cgroup of the selected process, or
+    /// every process again when filtered. The selected process stays
+    /// selected, it is listed either way.
+    pub fn toggle_cgroup_filter(&mut self) {
+        self.cgroup_filter = match self.cgroup_filter {
+            Some(_) => None,
+            None => self.selected_cgroup.clone(),
+        };
+    }
+
+    /// Sorts by the next column; the selected process stays selected
+    /// wherever it moves
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+    }
+
+    /// Lists only the zombies, then only the orphans, then every process
+    /// again
+    pub fn cycle_state_filter(&mut self) {
+        self.state_filter = match self.state_filter {
+            None => Some(ProcessStateFilter::Zombies),
+            Some(ProcessStateFilter::Zombies) => Some(ProcessStateFilter::Orphans),
+            Some(ProcessStateFilter::Orphans) => None,
+        };
+        self.select_row(0);
+    }
+
+    /// Handles a key press for the search box: `/` opens it, then every
+    /// key edits it, `Enter` closes it keeping the search and `Esc` closes
+    /// it clearing the search. Returns `false` for other keys while the
+    /// box is closed.
+    pub fn handle_search_key(&mut self, key: KeyEvent) -> bool {
+        if !self.searching {
+            if key.code != KeyCode::Char('/') {
+                return false;
+            }
+            self.searching = true;
+            return true;
+        }
+        match key.code {
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.searching = false;
+                self.search.value.clear();
+                self.select_row(0);
+            }
+            _ => {
+                if self.search.handle_key(key) {
+                    self.select_row(0);
+                }
+            }
+        }
+        true
+    }
+
+    /// Expands the selected group or collapses it when expanded. Returns
+    /// `false` when a process row is selected.
+    pub fn toggle_selected_group(&mut self) -> bool {
+        let Some(key) = &self.selected_group else {
+            return false;
+        };
+        if !self.expanded_groups.remove(key) {
+            self.expanded_groups.insert(key.clone());
+        }
+        true
+    }
+}
+
+/// Selection state of a simple scrollable table.
+#[derive(Debug)]
+pub struct TableUIState {
+    /// Ratatui table state for selection and scrolling
+    pub list: TableState,
+
+    /// Number of rows in the table, for bounds checking
+    pub count: usize,
+}
+
+impl Default for TableUIState {
+    fn default() -> Self {
+        Self {
+            list: TableState::default().with_selected(0),
+            count: 0,
+        }
+    }
+}
+
+impl TableUIState {
+    /// Sets the number of rows, moving the selection onto the last row
+    /// when the table got shorter than the selected index
+    pub fn set_count(&mut self, count: usize) {
+        self.count = count;
+        if let Some(selected) = self.list.selected()
+            && selected >= count
+        {
+            self.list.select(Some(count.saturating_sub(1)));
+        }
+    }
+
+    /// Sets the rows of a table whose rows are identified by `keys`, in
+    /// table order, and moves the selection to the row of `key`, the key of
+    /// the row selected before. When that row is gone the selected index
+    /// stays as with [`TableUIState::set_count`].
+    pub fn set_keyed_rows<K: PartialEq>(&mut self, key: Option<&K>, keys: &[K]) {
+        self.set_count(keys.len());
+        if let Some(row) = key.and_then(|key| keys.iter().position(|k| k == key)) {
+            self.list.select(Some(row));
+        }
+    }
+
+    /// Moves the selection one row down, stopping at the last row
+    pub fn select_next(&mut self) {
+        if let Some(selected) = self.list.selected() {
+            self.list
+                .select(Some((selected + 1).min(self.count.saturating_sub(1))));
+        }
+    }
+
+    /// Moves the selection one row up, stopping at the first row
+    pub fn select_previous(&mut self) {
+        if let Some(selected) = self.list.selected() {
+            self.list.select(Some(selected.saturating_sub(1)));
+        }
+    }
 }
 
 impl Default for UIState {
@@ -228,9 +939,68 @@ impl Default for UIState {
                 process_list: TableState::default().with_selected(0),
                 process_count: 0,
                 selected_pid: None,
+                full_command: false,
+                lifetimes: ProcessLifetimes::default(),
+                group_by: None,
+                expanded_groups: HashSet::new(),
+                selected_group: None,
+                columns: ProcessColumns::new(&config().processes.columns),
+                horizontal_scroll: 0,
+                cgroup_filter: None,
+                selected_cgroup: None,
+                state_filter: None,
+                sort: ProcessSort::default(),
+                page_size: 1,
+                pending_g: false,
+                search: TextInput::new("Search (name or command)", false),
+                searching: false,
             },
             single_process_disk_usage: SingleProcessDiskUsage::default(),
+            single_process_usage: SingleProcessUsage::default(),
+            scheduler_history: SchedulerHistory::default(),
+            status_bar: StatusBar::default(),
+            signal_menu: None,
+            affinity_editor: None,
+            column_editor: None,
+            command_line: None,
+            command_palette: None,
+            process_env: ProcessEnvUIState::default(),
+            open_files: OpenFilesUIState::default(),
+            tasks: TasksUIState::default(),
+            memory_breakdown_open: false,
+            follow: None,
+            connections_table: TableUIState::default(),
+            cgroups_table: TableUIState::default(),
+            pods_table: TableUIState::default(),
+            vms: VmsUIState::default(),
+            audio: AudioUIState::default(),
+            bluetooth: BluetoothUIState::default(),
+            controls_focus: ControlsPanel::default(),
+            power: PowerUIState::default(),
+            hardware: HardwareUIState::default(),
+            boot: None,
+            crash_events: CrashEventsUIState::default(),
+            #[cfg(feature = "geoip")]
+            geoip: None,
+            paused: false,
             networks_state: None,
+            network_table: TableUIState::default(),
+            disks: DisksUIState::default(),
+            services: ServicesUIState::default(),
+            ports: PortsUIState::default(),
+            logs: LogsUIState::default(),
+            history: HistoryUIState::default(),
+            anomalies: AnomalyDetector::new(config().anomalies.sigmas, config().anomalies.window),
+            fans_table: TableUIState::default(),
+            hosts_table: TableUIState::default(),
+            fan_history: HashMap::new(),
+            network_names: Vec::new(),
+            script_output: None,
+            metrics_layout: config().metrics.rows(),
+            #[cfg(feature = "web3")]
+            exe_checksum: None,
+            #[cfg(feature = "web3")]
+            expected_hash_input: None,
         }
     }
 }
@@ -238,9 +1008,9 @@ impl Default for UIState {
 /// Wrapper for single process data display.
 ///
 /// Used to pass process details to the detailed process view.
-pub struct SingleProcessUI<'a> {
+pub struct SingleProcessUI {
     /// Process data including metrics and metadata
-    pub data: SingleProcessData<'a>,
+    pub data: SingleProcessData,
 }
 
 /// Time-series storage for a single process's disk I/O activity.
@@ -253,18 +1023,18 @@ pub struct SingleProcessDiskUsage {
     pub pid: u32,
 
     /// Historical disk read bytes (up to MAX_HISTORY_IN_MEMORY points)
-    pub disk_read_usage: VecDeque<u64>,
+    pub disk_read_usage: TimeSeries<u64>,
 
     /// Historical disk write bytes (up to MAX_HISTORY_IN_MEMORY points)
-    pub disk_write_usage: VecDeque<u64>,
+    pub disk_write_usage: TimeSeries<u64>,
 }
 
 impl Default for SingleProcessDiskUsage {
     fn default() -> Self {
         Self {
             pid: 0,
-            disk_read_usage: VecDeque::<u64>::with_capacity(MAX_HISTORY_IN_MEMORY),
-            disk_write_usage: VecDeque::<u64>::with_capacity(MAX_HISTORY_IN_MEMORY),
+            disk_read_usage: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+            disk_write_usage: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
         }
     }
 }
@@ -283,9 +1053,9 @@ impl SingleProcessDiskUsage {
     /// # Behavior
     ///
     /// - If PID changes: Clears all history and updates tracked PID
-    /// - If history exceeds 60 points: Removes oldest entry (FIFO)
-    /// - Appends new read/write byte counts to history
-    pub fn update_disk_history(&mut self, pid: u32, disk_usage: &DiskUsage) {
+    /// - Appends new read/write byte counts to history, dropping the oldest
+    ///   once `MAX_HISTORY_IN_MEMORY` points are held
+    pub fn update_disk_history(&mut self, pid: u32, disk_usage: &ProcessDiskUsage) {
         // reset the UI state data for disk write/read when changed at current displaying pid
         if pid != self.pid {
             self.disk_read_usage.clear();
@@ -293,48 +1063,104 @@ impl SingleProcessDiskUsage {
             self.pid = pid;
         }
 
-        if self.disk_read_usage.len() > 60 {
-            self.disk_read_usage.pop_front();
+        self.disk_read_usage.push(disk_usage.read_bytes);
+        self.disk_write_usage.push(disk_usage.written_bytes);
+    }
+
+    /// Tracks `pid` from now on without clearing the history, for a
+    /// followed process that restarted
+    pub fn reattach(&mut self, pid: u32) {
+        self.pid = pid;
+    }
+}
+
+/// Time-series storage for a single process's CPU and memory usage.
+///
+/// Keeps short spikes of the viewed process visible in sparkline charts
+/// between refreshes.
+#[derive(Debug)]
+pub struct SingleProcessUsage {
+    /// PID of the process being tracked
+    pub pid: u32,
+
+    /// Historical CPU usage in hundredths of a percent, so that processes
+    /// below 1% still draw a shape (up to MAX_HISTORY_IN_MEMORY points)
+    pub cpu_usage: TimeSeries<u64>,
+
+    /// Historical resident memory in bytes (up to MAX_HISTORY_IN_MEMORY points)
+    pub memory_usage: TimeSeries<u64>,
+}
+
+impl Default for SingleProcessUsage {
+    fn default() -> Self {
+        Self {
+            pid: 0,
+            cpu_usage: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+            memory_usage: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
         }
-        self.disk_read_usage.push_back(disk_usage.read_bytes);
+    }
+}
 
-        if self.disk_write_usage.len() > 60 {
-            self.disk_write_usage.pop_front();
+impl SingleProcessUsage {
+    /// Updates CPU and memory history with new measurements.
+    ///
+    /// Like the disk history, the history is cleared when the tracked PID
+    /// changes so two processes never share a chart.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Process ID of the current process
+    /// * `cpu_usage` - Current CPU usage in percent
+    /// * `memory` - Current memory usage in bytes
+    pub fn update_usage_history(&mut self, pid: u32, cpu_usage: f32, memory: u64) {
+        if pid != self.pid {
+            self.cpu_usage.clear();
+            self.memory_usage.clear();
+            self.pid = pid;
         }
-        self.disk_write_usage.push_back(disk_usage.written_bytes);
+
+        self.cpu_usage
+            .push((cpu_usage.max(0.0) * 100.0).round() as u64);
+        self.memory_usage.push(memory);
+    }
+
+    /// Tracks `pid` from now on without clearing the history, for a
+    /// followed process that restarted
+    pub fn reattach(&mut self, pid: u32) {
+        self.pid = pid;
     }
 }
 
 /// Time-series storage for a single network interface's statistics.
 ///
 /// Maintains historical data for bytes, packets, and errors in both
-/// transmit and receive directions using ring buffers for efficient
-/// memory usage and visualization.
+/// transmit and receive directions. Every series is pushed together, so
+/// the timestamps of any of them place the crash event markers.
 #[derive(Debug)]
 pub struct NetworkInterfaceData {
-    /// Bytes received over time
-    pub received_bytes: Ring<u64, MAX_NETWORK_IN_MEMORY>,
-    /// Bytes transmitted over time
-    pub transmitted_bytes: Ring<u64, MAX_NETWORK_IN_MEMORY>,
-    /// Packets received over time
-    pub packets_received: Ring<u64, MAX_NETWORK_IN_MEMORY>,
-    /// Packets transmitted over time
-    pub packets_transmitted: Ring<u64, MAX_NETWORK_IN_MEMORY>,
+    /// Bytes received per second over time
+    pub received_bytes: TimeSeries<u64>,
+    /// Bytes transmitted per second over time
+    pub transmitted_bytes: TimeSeries<u64>,
+    /// Packets received per second over time
+    pub packets_received: TimeSeries<u64>,
+    /// Packets transmitted per second over time
+    pub packets_transmitted: TimeSeries<u64>,
     /// Receive errors over time
-    pub errors_received: Ring<u64, MAX_NETWORK_IN_MEMORY>,
+    pub errors_received: TimeSeries<u64>,
     /// Transmit errors over time
-    pub errors_transmitted: Ring<u64, MAX_NETWORK_IN_MEMORY>,
+    pub errors_transmitted: TimeSeries<u64>,
 }
 
 impl Default for NetworkInterfaceData {
     fn default() -> Self {
         Self {
-            received_bytes: Ring::new(),
-            transmitted_bytes: Ring::new(),
-            packets_received: Ring::new(),
-            packets_transmitted: Ring::new(),
-            errors_received: Ring::new(),
-            errors_transmitted: Ring::new(),
+            received_bytes: TimeSeries::new(MAX_NETWORK_IN_MEMORY),
+            transmitted_bytes: TimeSeries::new(MAX_NETWORK_IN_MEMORY),
+            packets_received: TimeSeries::new(MAX_NETWORK_IN_MEMORY),
+            packets_transmitted: TimeSeries::new(MAX_NETWORK_IN_MEMORY),
+            errors_received: TimeSeries::new(MAX_NETWORK_IN_MEMORY),
+            errors_transmitted: TimeSeries::new(MAX_NETWORK_IN_MEMORY),
         }
     }
 }
@@ -342,30 +1168,192 @@ impl Default for NetworkInterfaceData {
 impl NetworkInterfaceData {
     /// Updates all network statistics with new measurements.
     ///
-    /// Pushes new values to all six ring buffers with clamping to prevent
-    /// visualization distortion from transient spikes.
+    /// Byte and packet rates are pushed as they are, rounded to whole units
+    /// per second, so the sparklines show the real traffic. Error counts
+    /// are clamped to prevent visualization distortion from transient
+    /// spikes.
     ///
     /// # Arguments
     ///
     /// * `network_data` - Current network interface statistics
-    ///
-    /// # Notes
-    ///
-    /// Uses `push_clamped` to smooth out extreme spikes that could distort
-    /// trend visualization while preserving genuine traffic patterns.
     pub fn update_network_history(&mut self, network_data: &NetworkInterfaces) {
+        let rates = &network_data.rates;
         self.received_bytes
-            .push_clamped(network_data.bytes_received);
+            .push(rates.bytes_received.round() as u64);
         self.transmitted_bytes
-            .push_clamped(network_data.bytes_transmitted);
+            .push(rates.bytes_transmitted.round() as u64);
         self.packets_received
-            .push_clamped(network_data.packets_received);
+            .push(rates.packets_received.round() as u64);
         self.packets_transmitted
-            .push_clamped(network_data.packets_transmitted);
+            .push(rates.packets_transmitted.round() as u64);
         self.errors_received
-            .push_clamped(network_data.errors_on_received);
+            .push_clamped(network_data.errors_on_received, CLAMP_TREND_VALUE);
         self.errors_transmitted
-            .push_clamped(network_data.errors_on_transmitted);
+            .push_clamped(network_data.errors_on_transmitted, CLAMP_TREND_VALUE);
+    }
+}
+
+/// Time-series storage for the activity of a disk.
+///
+/// Values are scaled to integers for the sparklines: latency in
+/// microseconds and queue depth in hundredths of a request.
+#[derive(Debug)]
+pub struct DiskIoHistory {
+    /// Average request latency over time
+    pub await_us: TimeSeries<u64>,
+    /// Average queue depth over time
+    pub queue_depth: TimeSeries<u64>,
+    /// Busy time in percent over time
+    pub utilization: TimeSeries<u64>,
+}
+
+impl Default for DiskIoHistory {
+    fn default() -> Self {
+        Self {
+            await_us: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+            queue_depth: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+            utilization: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+        }
+    }
+}
+
+impl DiskIoHistory {
+    /// Pushes the latest activity of the disk
+    pub fn update(&mut self, disk: &DiskIo) {
+        self.await_us
+            .push((disk.await_ms().unwrap_or(0.0) * 1000.0).round() as u64);
+        self.queue_depth
+            .push((disk.queue_depth * 100.0).round() as u64);
+        self.utilization.push(disk.utilization.round() as u64);
+    }
+}
+
+/// How a status bar message is highlighted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusLevel {
+    Info,
+    Error,
+}
+
+/// A message shown in the status bar until it expires
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub text: String,
+    pub level: StatusLevel,
+    shown_at: Instant,
+}
+
+/// Bottom bar showing the outcome of the last action for a few seconds
+#[derive(Debug, Default)]
+pub struct StatusBar {
+    message: Option<StatusMessage>,
+    /// Outcomes of the actions still running on worker threads
+    running: Vec<Receiver<Result<String, String>>>,
+}
+
+impl StatusBar {
+    /// Shows `text` as a plain message
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.show(text.into(), StatusLevel::Info);
+    }
+
+    /// Shows `text` highlighted as an error
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.show(text.into(), StatusLevel::Error);
+    }
+
+    /// Shows the message of a successful action or the error of a failed one
+    pub fn report(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(text) => self.info(text),
+            Err(text) => self.error(text),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.message = None;
+    }
+
+    /// Shows `text` while `action` runs on a worker thread, for actions
+    /// that can take seconds like starting a service. Its outcome is
+    /// reported by the first [`poll`](Self::poll) after it finished.
+    pub fn run_in_background<F>(&mut self, text: impl Into<String>, action: F)
+    where
+        F: FnOnce() -> Result<String, String> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // the app may have quit by the time the action finished
+            let _ = sender.send(action());
+        });
+        self.running.push(receiver);
+        self.info(text);
+    }
+
+    /// Reports the background actions that finished since the last poll
+    pub fn poll(&mut self) {
+        let mut finished = Vec::new();
+        self.running.retain(|receiver| match receiver.try_recv() {
+            Ok(result) => {
+                finished.push(result);
+                false
+            }
+            Err(TryRecvError::Empty) => true,
+            Err(TryRecvError::Disconnected) => {
+                finished.push(Err("Action stopped unexpectedly".to_string()));
+                false
+            }
+        });
+        for result in finished {
+            self.report(result);
+        }
+    }
+
+    /// The message to show, `None` once it is older than
+    /// `STATUS_MESSAGE_SECS`. Kept while a background action runs, so its
+    /// progress message doesn't disappear before the outcome arrives.
+    pub fn current(&self) -> Option<&StatusMessage> {
+        self.message.as_ref().filter(|message| {
+            !self.running.is_empty()
+                || message.shown_at.elapsed() < Duration::from_secs(STATUS_MESSAGE_SECS)
+        })
+    }
+
+    fn show(&mut self, text: String, level: StatusLevel) {
+        self.message = Some(StatusMessage {
+            text,
+            level,
+            shown_at: Instant::now(),
+        });
+    }
+}
+
+/// Time-series storage for the context switch, interrupt and fork rates
+/// of the Metrics page, in events per second
+#[derive(Debug)]
+pub struct SchedulerHistory {
+    pub context_switches: TimeSeries<u64>,
+    pub interrupts: TimeSeries<u64>,
+    pub forks: TimeSeries<u64>,
+}
+
+impl Default for SchedulerHistory {
+    fn default() -> Self {
+        Self {
+            context_switches: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+            interrupts: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+            forks: TimeSeries::new(MAX_HISTORY_IN_MEMORY),
+        }
+    }
+}
+
+impl SchedulerHistory {
+    /// Pushes the latest rates
+    pub fn update(&mut self, rates: &SchedulerRates) {
+        self.context_switches
+            .push(rates.context_switches.round() as u64);
+        self.interrupts.push(rates.interrupts.round() as u64);
+        self.forks.push(rates.forks.round() as u64);
     }
 }
 
@@ -425,63 +1413,20 @@ impl<T, const N: usize> Ring<T, N> {
     }
 }
 
-impl<T, const N: usize> Ring<T, N>
-where
-    T: Copy + Ord + From<u8>,
-{
-    /// Pushes a value with statistical clamping to reduce spike distortion.
-    ///
-    /// Instead of blindly accepting extreme values that could distort
-    /// visualization, this method clamps incoming values to a percentile
-    /// threshold based on historical data. This preserves genuine trends
-    /// while smoothing transient spikes.
-    ///
-    /// # Arguments
-    ///
-    /// * `value` - Raw value to push
-    ///
-    /// # Algorithm
-    ///
-    /// 1. If buffer is empty, push value directly (no history to compare)
-    /// 2. Collect all historical values plus the new value
-    /// 3. Calculate the percentile threshold (defined by `CLAMP_TREND_VALUE`)
-    /// 4. Find the percentile value using nth_element selection
-    /// 5. Clamp the new value to the percentile if it exceeds it
-    /// 6. Push the clamped value
-    ///
-    /// # Use Case
-    ///
-    /// Ideal for network throughput, disk I/O, or other metrics where brief
-    /// spikes (e.g., from system updates, backups) shouldn't dominate the
-    /// visual trend line.
-    ///
-    /// # Examples
-    ///
-    /// ```ignore
-    /// let mut ring: Ring<u64, 100> = Ring::new();
-    /// ring.push_clamped(100);  // Normal value
-    /// ring.push_clamped(500);  // Spike, may be clamped
-    /// ring.push_clamped(120);  // Normal value
-    /// ```
-    pub fn push_clamped(&mut self, value: T) {
-        if self.inner.is_empty() {
-            self.push(value);
-            return;
-        }
-
-        // collect historical values
-        let mut data: Vec<T> = self.inner.iter().copied().collect();
-        data.push(value);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // compute percentile index
-        let p_index = ((data.len() - 1) as f64 * CLAMP_TREND_VALUE).round() as usize;
-
-        // nth_element selection
-        let (_, p_val, _) = data.select_nth_unstable(p_index);
-
-        // clamp
-        let clamped = if value > *p_val { *p_val } else { value };
-
-        self.push(clamped);
+    #[test]
+    fn test_text_input_never_grows_its_buffer() {
+        let mut input = TextInput::new("Password", true);
+        let capacity = input.value.capacity();
+        let buffer = input.value.as_ptr();
+        for _ in 0..capacity + 10 {
+            input.handle_key(KeyEvent::from(KeyCode::Char('é')));
+        }
+        assert!(input.value.len() <= capacity);
+        assert_eq!(input.value.capacity(), capacity);
+        assert_eq!(input.value.as_ptr(), buffer);
     }
 }
@@ -0,0 +1,200 @@
+//! Color themes for the TUI
+//!
+//! Every widget helper takes its colors from the active `Theme` instead of
+//! hard-coding them. The theme is picked at startup from the `--theme`
+//! flag or the config file and defaults to `dark`; the command palette can
+//! switch it at runtime.
+
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use anyhow::anyhow;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::config::Config;
+
+/// Names of the built-in themes
+pub const BUILTIN_THEMES: [&str; 4] = ["dark", "light", "solarized", "gruvbox"];
+
+static ACTIVE_THEME: RwLock<Option<&'static Theme>> = RwLock::new(None);
+
+static DEFAULT_THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Colors used across the TUI.
+///
+/// In the config file colors are given by name (`"lightblue"`), as
+/// `"#rrggbb"` or as an ANSI index (`"42"`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Regular text and widget foreground
+    pub text: Color,
+
+    /// Secondary text such as descriptions
+    pub muted: Color,
+
+    /// Selected tab and other highlights
+    pub accent: Color,
+
+    /// Focused inputs and modal borders
+    pub focus: Color,
+
+    /// Field labels
+    pub label: Color,
+
+    /// Positive results
+    pub ok: Color,
+
+    /// Warnings and badges
+    pub warning: Color,
+
+    /// Errors, alerts and gauges above 90%
+    pub critical: Color,
+
+    /// Gauge fill
+    pub gauge: Color,
+
+    /// Gauge background and table header background
+    pub background: Color,
+
+    /// Foreground of the selected table row
+    pub selection_fg: Color,
+
+    /// Background of the selected table row or list item
+    pub selection_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// The original stomata colors, for dark terminals
+    pub fn dark() -> Self {
+        Self {
+            text: Color::White,
+            muted: Color::Gray,
+            accent: Color::Green,
+            focus: Color::Yellow,
+            label: Color::Cyan,
+            ok: Color::Green,
+            warning: Color::Yellow,
+            critical: Color::Red,
+            gauge: Color::LightBlue,
+            background: Color::Black,
+            selection_fg: Color::Black,
+            selection_bg: Color::White,
+        }
+    }
+
+    /// Dark text for terminals with a light background
+    pub fn light() -> Self {
+        Self {
+            text: Color::Black,
+            muted: Color::DarkGray,
+            accent: Color::Blue,
+            focus: Color::Magenta,
+            label: Color::Blue,
+            ok: Color::Green,
+            warning: Color::Rgb(181, 118, 20),
+            critical: Color::Red,
+            gauge: Color::Blue,
+            background: Color::Rgb(230, 230, 230),
+            selection_fg: Color::White,
+            selection_bg: Color::Blue,
+        }
+    }
+
+    /// Solarized dark palette
+    pub fn solarized() -> Self {
+        Self {
+            text: Color::Rgb(147, 161, 161),
+            muted: Color::Rgb(88, 110, 117),
+            accent: Color::Rgb(133, 153, 0),
+            focus: Color::Rgb(181, 137, 0),
+            label: Color::Rgb(42, 161, 152),
+            ok: Color::Rgb(133, 153, 0),
+            warning: Color::Rgb(203, 75, 22),
+            critical: Color::Rgb(220, 50, 47),
+            gauge: Color::Rgb(38, 139, 210),
+            background: Color::Rgb(0, 43, 54),
+            selection_fg: Color::Rgb(0, 43, 54),
+            selection_bg: Color::Rgb(147, 161, 161),
+        }
+    }
+
+    /// Gruvbox dark palette
+    pub fn gruvbox() -> Self {
+        Self {
+            text: Color::Rgb(235, 219, 178),
+            muted: Color::Rgb(168, 153, 132),
+            accent: Color::Rgb(184, 187, 38),
+            focus: Color::Rgb(250, 189, 47),
+            label: Color::Rgb(142, 192, 124),
+            ok: Color::Rgb(184, 187, 38),
+            warning: Color::Rgb(254, 128, 25),
+            critical: Color::Rgb(251, 73, 52),
+            gauge: Color::Rgb(131, 165, 152),
+            background: Color::Rgb(40, 40, 40),
+            selection_fg: Color::Rgb(40, 40, 40),
+            selection_bg: Color::Rgb(235, 219, 178),
+        }
+    }
+
+    /// Looks up a built-in theme by name
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            "gruvbox" => Some(Self::gruvbox()),
+            _ => None,
+        }
+    }
+
+    /// Picks the theme to use.
+    ///
+    /// The `--theme` flag wins over the `theme` config setting; user-defined
+    /// themes win over built-in ones with the same name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the selected theme doesn't exist.
+    pub fn resolve(flag: Option<&str>, config: &Config) -> anyhow::Result<Self> {
+        let Some(name) = flag.or(config.theme.as_deref()) else {
+            return Ok(Self::dark());
+        };
+
+        config
+            .themes
+            .get(name)
+            .cloned()
+            .or_else(|| Self::builtin(name))
+            .ok_or_else(|| {
+                let mut available: Vec<&str> = BUILTIN_THEMES.to_vec();
+                available.extend(config.themes.keys().map(String::as_str));
+                anyhow!(
+                    "unknown theme '{name}', available themes: {}",
+                    available.join(", ")
+                )
+            })
+    }
+}
+
+/// Sets the theme used from the next frame on. Every theme set stays in
+/// memory for the rest of the run, so widgets can keep borrowing the one
+/// they were built with; themes are only switched on request of the user.
+pub fn set_theme(theme: Theme) {
+    let theme: &'static Theme = Box::leak(Box::new(theme));
+    *ACTIVE_THEME.write().unwrap_or_else(PoisonError::into_inner) = Some(theme);
+}
+
+/// The active theme, `dark` if none was set
+pub fn theme() -> &'static Theme {
+    ACTIVE_THEME
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .unwrap_or_else(|| DEFAULT_THEME.get_or_init(Theme::dark))
+}
@@ -3,25 +3,36 @@
 //! Provides helper functions for converting between different units and
 //! formats commonly used in system monitoring displays.
 
-/// Converts bytes to megabytes.
-///
-/// Performs binary conversion (1024-based) from bytes to megabytes,
-/// returning a floating-point value for precision in display.
-///
-/// # Arguments
-///
-/// * `bytes` - Number of bytes to convert
-///
-/// # Returns
-///
-/// The equivalent value in megabytes as an f64
-///
-/// # Conversion
-///
-/// Uses binary (base-1024) conversion:
-/// - 1 KB = 1,024 bytes
-/// - 1 MB = 1,024 KB = 1,048,576 bytes
+use std::sync::atomic::{AtomicBool, Ordering};
 
-pub fn bytes_to_mb(bytes: u64) -> f64 {
-    (bytes as f64) / (1024.0 * 1024.0)
+use stomata_core::units::{ByteSize, UnitSystem};
+
+/// Whether byte counts are shown in SI units rather than binary ones
+static SI_UNITS: AtomicBool = AtomicBool::new(false);
+
+/// Sets the unit system used by [`format_bytes`] and [`format_rate`]
+pub fn set_unit_system(system: UnitSystem) {
+    SI_UNITS.store(system == UnitSystem::Si, Ordering::Relaxed);
+}
+
+/// The unit system byte counts are currently shown in, binary by default
+pub fn unit_system() -> UnitSystem {
+    if SI_UNITS.load(Ordering::Relaxed) {
+        UnitSystem::Si
+    } else {
+        UnitSystem::Binary
+    }
+}
+
+/// Formats a byte count in the largest fitting unit, e.g. `3.2 GiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    ByteSize::new(bytes).with_system(unit_system()).to_string()
+}
+
+/// Formats a transfer rate in bytes per second, e.g. `1.5 MiB/s`.
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    format!(
+        "{}/s",
+        ByteSize::from_f64(bytes_per_sec).with_system(unit_system())
+    )
 }
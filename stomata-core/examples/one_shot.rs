@@ -0,0 +1,41 @@
+//! Reads the memory usage and the busiest processes once.
+//!
+//! ```bash
+//! cargo run -p stomata-core --example one_shot
+//! ```
+
+use std::thread;
+
+use stomata_core::{
+    ByteSize, Metrics, MetricsToFetch, StomataSystemMetrics, sysinfo::MINIMUM_CPU_UPDATE_INTERVAL,
+};
+
+fn main() {
+    let mut metrics = StomataSystemMetrics::new();
+
+    if let Metrics::SystemResource(resources) = metrics.fetch(MetricsToFetch::SystemResource) {
+        let system = resources.system_metrics;
+        println!(
+            "memory: {} of {} used, swap: {} of {}",
+            ByteSize::new(system.memory_used),
+            ByteSize::new(system.memory_total),
+            ByteSize::new(system.swap_used),
+            ByteSize::new(system.swap_total),
+        );
+    }
+
+    // CPU usage is measured between two refreshes
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    if let Metrics::Processes(mut processes) = metrics.fetch(MetricsToFetch::Process) {
+        processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        for process in processes.iter().take(5) {
+            println!(
+                "{:>8} {:<20} {:>6.1}% {}",
+                process.pid,
+                process.name,
+                process.cpu_usage,
+                ByteSize::new(process.memory)
+            );
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Streams the values of the built-in collectors, each at its own
+//! interval, for the given number of seconds (10 by default).
+//!
+//! ```bash
+//! cargo run -p stomata-core --example streaming -- 30
+//! ```
+
+use std::{
+    env, thread,
+    time::{Duration, Instant},
+};
+
+use stomata_core::CollectorRegistry;
+
+fn main() {
+    let seconds = env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(10);
+    let until = Instant::now() + Duration::from_secs(seconds);
+
+    let mut registry = CollectorRegistry::with_builtin();
+    // rates need a baseline, which the collectors take when created
+    thread::sleep(Duration::from_secs(1));
+
+    while Instant::now() < until {
+        let now = Instant::now();
+        for (id, result) in registry.collect_due(now) {
+            match result {
+                Ok(metric) => {
+                    for value in &metric.values {
+                        println!(
+                            "{} {}.{} {} {}",
+                            metric.timestamp.to_rfc3339(),
+                            metric.collector,
+                            value.key(),
+                            value.value,
+                            value.unit.as_deref().unwrap_or_default()
+                        );
+                    }
+                }
+                Err(err) => eprintln!("{id}: {err}"),
+            }
+        }
+        thread::sleep(registry.next_due(Instant::now()));
+    }
+}
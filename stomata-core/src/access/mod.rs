@@ -0,0 +1,12 @@
+//! Access control for serving metrics remotely
+//!
+//! Every remote client presents a token that carries a scope. Read tokens
+//! can only fetch metrics; mutating actions such as signalling a process
+//! need a control token, so a leaked read token can't be used to disrupt
+//! the host. The agent checks the handshake and each signal request, and
+//! the HTTP API each request, with [`TokenStore::authorize`] before acting
+//! on it.
+
+pub mod tokens;
+
+pub use tokens::{AccessError, Action, ApiToken, Scope, TokenStore};
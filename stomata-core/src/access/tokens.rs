@@ -0,0 +1,194 @@
+//! API tokens and the scopes they grant
+
+use serde::{Deserialize, Serialize};
+
+/// What a token is allowed to do, a control token can also read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Can read metrics
+    Read,
+    /// Can also act on processes
+    Control,
+}
+
+impl Scope {
+    /// Name of the scope as written in config files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Control => "control",
+        }
+    }
+}
+
+/// A request of a remote client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Fetch metrics, alerts or any other read-only data
+    ReadMetrics,
+    /// Send a signal to a process
+    SignalProcess,
+}
+
+impl Action {
+    /// Scope a token needs to perform the action
+    pub fn required_scope(&self) -> Scope {
+        match self {
+            Action::ReadMetrics => Scope::Read,
+            Action::SignalProcess => Scope::Control,
+        }
+    }
+
+    /// Description of the action for error messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::ReadMetrics => "reading metrics",
+            Action::SignalProcess => "signalling a process",
+        }
+    }
+}
+
+/// A token a remote client authenticates with
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiToken {
+    /// Name to tell tokens apart in logs, never the secret itself
+    pub name: String,
+    /// Value the client presents
+    pub secret: String,
+    /// What the token may do
+    pub scope: Scope,
+}
+
+impl std::fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiToken")
+            .field("name", &self.name)
+            .field("secret", &"[REDACTED]")
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+/// Why a request was refused
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AccessError {
+    /// No token has the given secret
+    #[error("unknown token")]
+    UnknownToken,
+    /// The token exists but its scope doesn't allow the action
+    #[error("token `{token}` has {} scope, {} needs {}", .scope.as_str(), .action.as_str(), .action.required_scope().as_str())]
+    Forbidden {
+        /// Name of the token
+        token: String,
+        /// Scope of the token
+        scope: Scope,
+        /// The refused action
+        action: Action,
+    },
+}
+
+/// The tokens accepted by the serving layer
+#[derive(Debug, Clone, Default)]
+pub struct TokenStore {
+    tokens: Vec<ApiToken>,
+}
+
+impl TokenStore {
+    /// Creates a store accepting `tokens`
+    pub fn new(tokens: Vec<ApiToken>) -> Self {
+        Self { tokens }
+    }
+
+    /// Whether no token was configured
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Checks that the token with `secret` may perform `action` and returns
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AccessError::UnknownToken`] if no token has the secret and
+    /// [`AccessError::Forbidden`] if its scope doesn't allow the action.
+    pub fn authorize(&self, secret: &str, action: Action) -> Result<&ApiToken, AccessError> {
+        let token = self
+            .tokens
+            .iter()
+            .find(|token| constant_time_eq(token.secret.as_bytes(), secret.as_bytes()))
+            .ok_or(AccessError::UnknownToken)?;
+        if token.scope < action.required_scope() {
+            return Err(AccessError::Forbidden {
+                token: token.name.clone(),
+                scope: token.scope,
+                action,
+            });
+        }
+        Ok(token)
+    }
+}
+
+/// Compares secrets without returning early on the first differing byte,
+/// so response times don't leak how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> TokenStore {
+        TokenStore::new(vec![
+            ApiToken {
+                name: "grafana".to_string(),
+                secret: "read-secret".to_string(),
+                scope: Scope::Read,
+            },
+            ApiToken {
+                name: "ops".to_string(),
+                secret: "control-secret".to_string(),
+                scope: Scope::Control,
+            },
+        ])
+    }
+
+    #[test]
+    fn test_read_token_cant_mutate() {
+        let store = store();
+        assert!(store.authorize("read-secret", Action::ReadMetrics).is_ok());
+        assert_eq!(
+            store.authorize("read-secret", Action::SignalProcess),
+            Err(AccessError::Forbidden {
+                token: "grafana".to_string(),
+                scope: Scope::Read,
+                action: Action::SignalProcess,
+            })
+        );
+    }
+
+    #[test]
+    fn test_control_token_and_unknown_token() {
+        let store = store();
+        assert_eq!(
+            store
+                .authorize("control-secret", Action::SignalProcess)
+                .unwrap()
+                .name,
+            "ops"
+        );
+        assert!(
+            store
+                .authorize("control-secret", Action::ReadMetrics)
+                .is_ok()
+        );
+        assert_eq!(
+            store.authorize("control-secre", Action::ReadMetrics),
+            Err(AccessError::UnknownToken)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! The alert engine and the rule trait
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{alerts::log::AlertLog, collectors::backend::SystemBackend, errors::CoreError};
+
+/// How urgent an alert is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth a look
+    Info,
+    /// Likely a problem
+    Warning,
+    /// Needs attention now
+    Critical,
+}
+
+/// A condition reported by an alert rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    /// Name of the rule that raised the alert
+    pub rule: &'static str,
+    /// How urgent the alert is
+    pub severity: Severity,
+    /// What was found, shown to the user
+    pub message: String,
+    /// When the alert first fired
+    pub fired_at: DateTime<Utc>,
+}
+
+impl Alert {
+    /// An alert raised by `rule` firing now
+    pub fn new(rule: &'static str, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            severity,
+            message: message.into(),
+            fired_at: Utc::now(),
+        }
+    }
+
+    /// Two alerts are the same incident when rule and message match
+    fn same_incident(&self, other: &Alert) -> bool {
+        self.rule == other.rule && self.message == other.message
+    }
+}
+
+/// A check run on every tick against the live system state
+pub trait AlertRule: Send {
+    /// Short, stable rule name, e.g. `deleted_executable`
+    fn name(&self) -> &'static str;
+
+    /// Returns every alert that currently applies
+    fn evaluate(&self, system: &dyn SystemBackend) -> Vec<Alert>;
+}
+
+/// Runs the alert rules and tracks active and past alerts
+pub struct AlertEngine {
+    rules: Vec<Box<dyn AlertRule>>,
+    active: Vec<Alert>,
+    history: Vec<Alert>,
+    log: Option<AlertLog>,
+    /// Last failed write to `log`, until taken
+    log_error: Option<CoreError>,
+}
+
+impl std::fmt::Debug for AlertEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlertEngine")
+            .field(
+                "rules",
+                &self
+                    .rules
+                    .iter()
+                    .map(|rule| rule.name())
+                    .collect::<Vec<_>>(),
+            )
+            .field("active", &self.active)
+            .field("log", &self.log)
+            .finish()
+    }
+}
+
+impl Default for AlertEngine {
+    fn default() -> Self {
+        Self::new(vec![
+            Box::new(super::DeletedExecutableRule),
+            Box::new(super::SuspiciousConnectionRule::default()),
+        ])
+    }
+}
+
+impl AlertEngine {
+    /// An engine evaluating `rules`, without a log
+    pub fn new(rules: Vec<Box<dyn AlertRule>>) -> Self {
+        Self {
+            rules,
+            active: Vec::new(),
+            history: Vec::new(),
+            log: None,
+            log_error: None,
+        }
+    }
+
+    /// Also appends every newly fired alert to `log`
+    pub fn with_log(mut self, log: AlertLog) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Adds a rule evaluated from the next tick on
+    pub fn add_rule(&mut self, rule: Box<dyn AlertRule>) {
+        self.rules.push(rule);
+    }
+
+    /// Evaluates every rule and updates the active alerts.
+    ///
+    /// Alerts that were already active keep their original `fired_at`, newly
+    /// fired alerts are also appended to the history and the alert log. A
+    /// failed log write doesn't stop the alert from being shown, its error
+    /// is kept for [`AlertEngine::take_log_error`].
+    pub fn evaluate(&mut self, system: &dyn SystemBackend) {
+        let current: Vec<Alert> = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.evaluate(system))
+            .collect();
+        self.update(current);
+    }
+
+    fn update(&mut self, current: Vec<Alert>) {
+        let mut active = Vec::with_capacity(current.len());
+        for alert in current {
+            match self.active.iter().find(|old| old.same_incident(&alert)) {
+                Some(existing) => active.push(existing.clone()),
+                None => {
+                    tracing::info!(rule = alert.rule, severity = ?alert.severity, "{}", alert.message);
+                    if let Some(log) = &self.log
+                        && let Err(err) = log.append(&alert)
+                    {
+                        tracing::warn!("writing the alert log failed: {err}");
+                        self.log_error = Some(err);
+                    }
+                    self.history.push(alert.clone());
+                    active.push(alert);
+                }
+            }
+        }
+        self.active = active;
+    }
+
+    /// Alerts that applied on the last evaluation
+    pub fn active(&self) -> &[Alert] {
+        &self.active
+    }
+
+    /// Takes the error of the last failed alert log write, if any
+    pub fn take_log_error(&mut self) -> Option<CoreError> {
+        self.log_error.take()
+    }
+
+    /// Every alert that fired since the engine was created, oldest first
+    pub fn history(&self) -> &[Alert] {
+        &self.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_keeps_fired_at_and_records_history_once() {
+        let mut engine = AlertEngine::new(Vec::new());
+        let first = Alert::new("test", Severity::Warning, "disk full");
+        engine.update(vec![first.clone()]);
+
+        let mut repeated = Alert::new("test", Severity::Warning, "disk full");
+        repeated.fired_at = first.fired_at + chrono::Duration::seconds(5);
+        engine.update(vec![repeated]);
+
+        assert_eq!(engine.active().len(), 1);
+        assert_eq!(engine.active()[0].fired_at, first.fired_at);
+        assert_eq!(engine.history().len(), 1);
+
+        engine.update(Vec::new());
+        assert!(engine.active().is_empty());
+        assert_eq!(engine.history().len(), 1);
+    }
+
+    #[test]
+    fn test_update_appends_new_alerts_to_log() {
+        let path =
+            std::env::temp_dir().join(format!("stomata-alert-log-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let log = AlertLog::new(&path);
+        let mut engine = AlertEngine::new(Vec::new()).with_log(log.clone());
+
+        let alert = Alert::new("test", Severity::Critical, "disk full");
+        engine.update(vec![alert.clone()]);
+        engine.update(vec![alert.clone()]);
+
+        let logged = log.read_since(alert.fired_at).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].message, "disk full");
+    }
+}
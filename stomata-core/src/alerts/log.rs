@@ -0,0 +1,143 @@
+//! Appending fired alerts to a JSON Lines file
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alerts::engine::Alert,
+    alerts::engine::Severity,
+    constants::ALERT_LOG_FILE_NAME,
+    errors::{CoreError, CoreResult},
+};
+
+/// An alert as written to the alert log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggedAlert {
+    /// Name of the rule that raised the alert
+    pub rule: String,
+    /// How urgent the alert was
+    pub severity: Severity,
+    /// What was found
+    pub message: String,
+    /// RFC 3339 time the alert first fired
+    pub fired_at: String,
+}
+
+impl From<&Alert> for LoggedAlert {
+    fn from(alert: &Alert) -> Self {
+        Self {
+            rule: alert.rule.to_string(),
+            severity: alert.severity,
+            message: alert.message.clone(),
+            fired_at: alert.fired_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Append-only log of fired alerts, one JSON object per line
+#[derive(Debug, Clone)]
+pub struct AlertLog {
+    path: PathBuf,
+}
+
+impl AlertLog {
+    /// A log appending to the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The log under the user's data directory, e.g.
+    /// `~/.local/share/stomata/alerts.jsonl`
+    pub fn default_location() -> Option<Self> {
+        dirs::data_dir().map(|dir| Self::new(dir.join("stomata").join(ALERT_LOG_FILE_NAME)))
+    }
+
+    /// Location of the log file
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Appends one alert to the log, creating the file and its directory
+    /// when needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log can't be created or written.
+    pub fn append(&self, alert: &Alert) -> CoreResult<()> {
+        let write_error =
+            |err| CoreError::io(format!("failed to write {}", self.path.display()), err);
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(write_error)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(write_error)?;
+        let line = serde_json::to_string(&LoggedAlert::from(alert))
+            .map_err(|err| CoreError::collection(format!("failed to encode alert: {err}")))?;
+        writeln!(file, "{line}").map_err(write_error)?;
+        Ok(())
+    }
+
+    /// Reads the alerts that fired at or after `since`, oldest first. A log
+    /// that doesn't exist yet holds no alerts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log exists but can't be read.
+    pub fn read_since(&self, since: DateTime<Utc>) -> CoreResult<Vec<LoggedAlert>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(parse_alert_log(&contents, since)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(CoreError::io(
+                format!("failed to read {}", self.path.display()),
+                err,
+            )),
+        }
+    }
+}
+
+/// Parses the lines of an alert log, skipping lines that aren't valid
+/// entries, e.g. one cut short by a crash while it was written
+fn parse_alert_log(contents: &str, since: DateTime<Utc>) -> Vec<LoggedAlert> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<LoggedAlert>(line).ok())
+        .filter(|alert| {
+            DateTime::parse_from_rfc3339(&alert.fired_at)
+                .is_ok_and(|fired_at| fired_at.with_timezone(&Utc) >= since)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_alert_log_filters_by_time_and_skips_bad_lines() {
+        let contents = concat!(
+            r#"{"rule":"a","severity":"warning","message":"old","fired_at":"2026-01-01T00:00:00+00:00"}"#,
+            "\n",
+            r#"{"rule":"b","severity":"crit"#,
+            "\n",
+            r#"{"rule":"c","severity":"critical","message":"new","fired_at":"2026-01-08T12:00:00+00:00"}"#,
+            "\n",
+        );
+        let since = DateTime::parse_from_rfc3339("2026-01-05T00:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let alerts = parse_alert_log(contents, since);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, "c");
+        assert_eq!(alerts[0].severity, Severity::Critical);
+    }
+}
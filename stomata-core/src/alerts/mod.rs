@@ -0,0 +1,14 @@
+//! Alert rules evaluated against the live system state
+//!
+//! Each rule inspects the system on every tick and reports the alerts that
+//! currently apply. The `AlertEngine` keeps the active alerts and a history
+//! of when each alert first fired. With an `AlertLog` attached, fired alerts
+//! are also appended to disk so they can be exported after an incident.
+
+pub mod engine;
+pub mod log;
+pub mod rules;
+
+pub use engine::{Alert, AlertEngine, AlertRule, Severity};
+pub use log::{AlertLog, LoggedAlert};
+pub use rules::{DeletedExecutableRule, InodeUsageRule, SuspiciousConnectionRule, ThrottlingRule};
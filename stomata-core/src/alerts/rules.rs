@@ -0,0 +1,266 @@
+//! Built-in alert rules
+
+use std::{
+    collections::HashSet,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alerts::engine::{Alert, AlertRule, Severity},
+    collectors::{
+        backend::SystemBackend,
+        connections::metrics::{ConnectionAllowlist, ConnectionsMetrics},
+        disks::{InodeUsage, MountFilter},
+        process::collectors::is_exe_deleted,
+        system::cpufreq::{CpuFrequency, is_throttled},
+    },
+    constants::{
+        INODE_ALERT_DEFAULT_PERCENT, THROTTLE_ALERT_DEFAULT_SECS, THROTTLE_FREQUENCY_RATIO,
+        THROTTLE_LOAD_PERCENT,
+    },
+};
+
+/// Flags processes whose executable was deleted or replaced on disk.
+///
+/// Usually a service that wasn't restarted after an upgrade, but also a
+/// common trait of malware that removes its binary after starting.
+pub struct DeletedExecutableRule;
+
+impl AlertRule for DeletedExecutableRule {
+    fn name(&self) -> &'static str {
+        "deleted_executable"
+    }
+
+    fn evaluate(&self, system: &dyn SystemBackend) -> Vec<Alert> {
+        system
+            .processes()
+            .into_iter()
+            // skip threads, they share the executable of their process
+            .filter(|process| !process.thread)
+            .filter(|process| is_exe_deleted(process.pid))
+            .map(|process| {
+                Alert::new(
+                    self.name(),
+                    Severity::Warning,
+                    format!(
+                        "{} (PID {}) is running a deleted executable",
+                        process.name, process.pid
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Flags outbound connections to public addresses or uncommon ports from
+/// processes that aren't expected to use the network.
+#[derive(Default)]
+pub struct SuspiciousConnectionRule {
+    /// Processes and ports that aren't flagged
+    pub allowlist: ConnectionAllowlist,
+}
+
+impl SuspiciousConnectionRule {
+    /// A rule flagging connections outside `allowlist`
+    pub fn new(allowlist: ConnectionAllowlist) -> Self {
+        Self { allowlist }
+    }
+}
+
+impl AlertRule for SuspiciousConnectionRule {
+    fn name(&self) -> &'static str {
+        "suspicious_connection"
+    }
+
+    fn evaluate(&self, system: &dyn SystemBackend) -> Vec<Alert> {
+        ConnectionsMetrics::fetch(system)
+            .suspicious(&self.allowlist)
+            .into_iter()
+            .map(|(connection, reason)| {
+                Alert::new(
+                    self.name(),
+                    Severity::Warning,
+                    format!(
+                        "{} (PID {}) connected to {}: {reason}",
+                        connection.process_name.as_deref().unwrap_or("unknown"),
+                        connection.pid.unwrap_or_default(),
+                        connection.remote
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Flags filesystems running out of inodes.
+///
+/// Once every inode is used no file can be created, however much space is
+/// left, which byte-based disk monitoring never shows.
+pub struct InodeUsageRule {
+    /// Inode usage in percent from which a filesystem is flagged
+    pub threshold_percent: f64,
+    /// Mounts that are never flagged
+    pub filter: MountFilter,
+}
+
+impl Default for InodeUsageRule {
+    fn default() -> Self {
+        Self::new(INODE_ALERT_DEFAULT_PERCENT)
+    }
+}
+
+impl InodeUsageRule {
+    /// A rule flagging filesystems with `threshold_percent` or more of their
+    /// inodes in use
+    pub fn new(threshold_percent: f64) -> Self {
+        Self {
+            threshold_percent,
+            filter: MountFilter::default(),
+        }
+    }
+
+    /// Leaves out the mounts `filter` hides, like the Disks page does
+    pub fn with_filter(mut self, filter: MountFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// The alert for the filesystem mounted at `mount_point`, if its inode
+    /// usage reached the threshold. The message leaves out the exact usage
+    /// so the alert stays the same incident while the usage changes.
+    fn check(&self, mount_point: &str, inodes: InodeUsage) -> Option<Alert> {
+        (inodes.percent() >= self.threshold_percent).then(|| {
+            let severity = if inodes.free == 0 {
+                Severity::Critical
+            } else {
+                Severity::Warning
+            };
+            Alert::new(
+                self.name(),
+                severity,
+                format!(
+                    "{mount_point} has {}% or more of its inodes in use",
+                    self.threshold_percent
+                ),
+            )
+        })
+    }
+}
+
+impl AlertRule for InodeUsageRule {
+    fn name(&self) -> &'static str {
+        "inode_usage"
+    }
+
+    fn evaluate(&self, system: &dyn SystemBackend) -> Vec<Alert> {
+        let mut seen = HashSet::new();
+        system
+            .mounts()
+            .into_iter()
+            .filter(|mount| self.filter.allows(mount))
+            // bind mounts list the same filesystem more than once
+            .filter(|mount| seen.insert(mount.mount_point.clone()))
+            .filter_map(|mount| {
+                let inodes = InodeUsage::fetch(&mount.mount_point)?;
+                self.check(&mount.mount_point, inodes)
+            })
+            .collect()
+    }
+}
+
+/// Flags CPUs held far below their maximum frequency while busy, see
+/// [`is_throttled`], once that lasted for `sustained`.
+///
+/// Short dips are normal, a CPU throttled for long usually overheats
+/// because of a clogged fan or dried thermal paste.
+pub struct ThrottlingRule {
+    /// How long the CPUs must stay throttled before the alert fires
+    pub sustained: Duration,
+    /// Since when the CPUs are throttled, `None` while they aren't
+    throttled_since: Mutex<Option<Instant>>,
+}
+
+impl Default for ThrottlingRule {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(THROTTLE_ALERT_DEFAULT_SECS))
+    }
+}
+
+impl ThrottlingRule {
+    /// A rule flagging throttling that lasted for `sustained`
+    pub fn new(sustained: Duration) -> Self {
+        Self {
+            sustained,
+            throttled_since: Mutex::new(None),
+        }
+    }
+
+    /// Records whether the CPUs are `throttled` at `now` and returns
+    /// whether they have been for `sustained`
+    fn observe(&self, throttled: bool, now: Instant) -> bool {
+        let mut since = self
+            .throttled_since
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !throttled {
+            *since = None;
+            return false;
+        }
+        now.duration_since(*since.get_or_insert(now)) >= self.sustained
+    }
+}
+
+impl AlertRule for ThrottlingRule {
+    fn name(&self) -> &'static str {
+        "cpu_throttling"
+    }
+
+    fn evaluate(&self, system: &dyn SystemBackend) -> Vec<Alert> {
+        let throttled = is_throttled(&CpuFrequency::fetch_all(), system.global_cpu_usage());
+        if !self.observe(throttled, Instant::now()) {
+            return Vec::new();
+        }
+        vec![Alert::new(
+            self.name(),
+            Severity::Warning,
+            format!(
+                "CPUs below {:.0}% of their maximum frequency at over {:.0}% load for {}s or more",
+                THROTTLE_FREQUENCY_RATIO * 100.0,
+                THROTTLE_LOAD_PERCENT,
+                self.sustained.as_secs()
+            ),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttling_rule_needs_sustained_throttling() {
+        let rule = ThrottlingRule::new(Duration::from_secs(30));
+        let start = Instant::now();
+        assert!(!rule.observe(true, start));
+        assert!(!rule.observe(true, start + Duration::from_secs(20)));
+        assert!(rule.observe(true, start + Duration::from_secs(31)));
+        // a normal reading starts the wait over
+        assert!(!rule.observe(false, start + Duration::from_secs(32)));
+        assert!(!rule.observe(true, start + Duration::from_secs(40)));
+    }
+
+    #[test]
+    fn test_inode_usage_rule_threshold() {
+        let rule = InodeUsageRule::new(90.0);
+        let inodes = |free| InodeUsage { total: 1000, free };
+        assert_eq!(rule.check("/var", inodes(200)), None);
+
+        let alert = rule.check("/var", inodes(100)).unwrap();
+        assert_eq!(alert.severity, Severity::Warning);
+        assert_eq!(alert.message, "/var has 90% or more of its inodes in use");
+
+        let exhausted = rule.check("/var", inodes(0)).unwrap();
+        assert_eq!(exhausted.severity, Severity::Critical);
+    }
+}
@@ -0,0 +1,226 @@
+//! Reading audio devices from `pactl`
+
+use std::{process::Command, time::Duration};
+
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    collectors::{
+        audio::metrics::{AudioDevice, AudioDeviceKind, AudioMetrics},
+        command::run_with_timeout,
+    },
+    constants::{AUDIO_COMMAND_TIMEOUT_MS, MAX_VOLUME_PERCENT},
+    errors::{CoreError, CoreResult},
+};
+
+impl AudioDeviceKind {
+    /// `output` or `input`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AudioDeviceKind::Sink => "output",
+            AudioDeviceKind::Source => "input",
+        }
+    }
+
+    fn pactl_name(&self) -> &'static str {
+        match self {
+            AudioDeviceKind::Sink => "sink",
+            AudioDeviceKind::Source => "source",
+        }
+    }
+}
+
+/// Runs `pactl` with `args` and returns its stdout
+fn pactl(args: &[&str]) -> CoreResult<String> {
+    run_with_timeout(
+        Command::new("pactl").args(args),
+        Duration::from_millis(AUDIO_COMMAND_TIMEOUT_MS),
+    )
+}
+
+impl AudioMetrics {
+    /// Lists the sinks and sources of the PulseAudio or PipeWire (through
+    /// pipewire-pulse) sound server with `pactl`. Monitor sources, which
+    /// mirror a sink, are left out.
+    ///
+    /// Blocks for four `pactl` calls, up to `AUDIO_COMMAND_TIMEOUT_MS`
+    /// each; [`StomataSystemMetrics`] runs it on a background thread.
+    /// Never fails; when the sound server can't be queried the error is
+    /// returned in `AudioMetrics::error`.
+    ///
+    /// [`StomataSystemMetrics`]: crate::collectors::StomataSystemMetrics
+    pub fn fetch() -> Self {
+        let mut devices = Vec::new();
+        let mut error = None;
+
+        for kind in [AudioDeviceKind::Sink, AudioDeviceKind::Source] {
+            let list = format!("{}s", kind.pactl_name());
+            let default = pactl(&[&format!("get-default-{}", kind.pactl_name())])
+                .map(|name| name.trim().to_string())
+                .unwrap_or_default();
+
+            match pactl(&["-f", "json", "list", &list])
+                .and_then(|json| parse_pactl_devices(&json, kind, &default))
+            {
+                Ok(mut found) => {
+                    found.sort_by_key(|device| !device.is_default);
+                    devices.extend(found);
+                }
+                Err(err) => {
+                    error = Some(format!("pactl failed: {err}"));
+                    break;
+                }
+            }
+        }
+
+        Self {
+            timestamp: Utc::now(),
+            devices,
+            error,
+            pending: false,
+        }
+    }
+
+    /// No devices yet, while the first listing runs
+    pub fn pending() -> Self {
+        Self {
+            timestamp: Utc::now(),
+            devices: Vec::new(),
+            error: None,
+            pending: true,
+        }
+    }
+}
+
+impl AudioDevice {
+    /// Changes the volume by `delta` percentage points, clamped between 0
+    /// and `MAX_VOLUME_PERCENT`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the pactl error if the volume couldn't be set.
+    pub fn change_volume(&self, delta: i32) -> CoreResult<()> {
+        let volume = (self.volume_percent as i32 + delta).clamp(0, MAX_VOLUME_PERCENT as i32);
+        pactl(&[
+            &format!("set-{}-volume", self.kind.pactl_name()),
+            &self.name,
+            &format!("{volume}%"),
+        ])?;
+        Ok(())
+    }
+
+    /// Mutes or unmutes the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns the pactl error if the mute state couldn't be changed.
+    pub fn toggle_mute(&self) -> CoreResult<()> {
+        pactl(&[
+            &format!("set-{}-mute", self.kind.pactl_name()),
+            &self.name,
+            "toggle",
+        ])?;
+        Ok(())
+    }
+
+    /// Makes this the default device of its kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns the pactl error if the default couldn't be changed.
+    pub fn set_default(&self) -> CoreResult<()> {
+        pactl(&[
+            &format!("set-default-{}", self.kind.pactl_name()),
+            &self.name,
+        ])?;
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct PactlDevice {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    mute: bool,
+    /// Channel name to `{"value_percent": "65%", ...}`
+    #[serde(default)]
+    volume: serde_json::Map<String, Value>,
+}
+
+/// Parses the output of `pactl -f json list sinks` or `... sources`
+///
+/// `default` is the name of the default device of this kind.
+pub fn parse_pactl_devices(
+    json: &str,
+    kind: AudioDeviceKind,
+    default: &str,
+) -> CoreResult<Vec<AudioDevice>> {
+    let devices: Vec<PactlDevice> = serde_json::from_str(json).map_err(|err| {
+        CoreError::collection(format!(
+            "unexpected pactl output, pactl 16 or newer is needed: {err}"
+        ))
+    })?;
+
+    Ok(devices
+        .into_iter()
+        .filter(|device| !device.name.ends_with(".monitor"))
+        .map(|device| {
+            let channels: Vec<u32> = device
+                .volume
+                .values()
+                .filter_map(|channel| channel.get("value_percent")?.as_str())
+                .filter_map(|percent| percent.trim().trim_end_matches('%').parse().ok())
+                .collect();
+            let volume_percent = if channels.is_empty() {
+                0
+            } else {
+                channels.iter().sum::<u32>() / channels.len() as u32
+            };
+
+            AudioDevice {
+                kind,
+                is_default: device.name == default,
+                description: if device.description.is_empty() {
+                    device.name.clone()
+                } else {
+                    device.description
+                },
+                name: device.name,
+                volume_percent,
+                muted: device.mute,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pactl_devices() {
+        let json = r#"[
+            {"index": 1, "name": "alsa_output.analog-stereo", "description": "Built-in Audio", "mute": false,
+             "volume": {"front-left": {"value": 42598, "value_percent": "65%", "db": "-11.23 dB"},
+                        "front-right": {"value": 45875, "value_percent": "70%", "db": "-9.29 dB"}}},
+            {"index": 2, "name": "bluez_output.headset", "description": "Headset", "mute": true,
+             "volume": {"mono": {"value": 65536, "value_percent": "100%", "db": "0.00 dB"}}},
+            {"index": 3, "name": "alsa_output.analog-stereo.monitor", "description": "Monitor", "mute": false, "volume": {}}
+        ]"#;
+        let devices =
+            parse_pactl_devices(json, AudioDeviceKind::Sink, "bluez_output.headset").unwrap();
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].description, "Built-in Audio");
+        assert_eq!(devices[0].volume_percent, 67);
+        assert!(!devices[0].is_default);
+        assert!(devices[1].muted);
+        assert!(devices[1].is_default);
+        assert_eq!(devices[1].volume_percent, 100);
+
+        assert!(parse_pactl_devices("Sink #1", AudioDeviceKind::Sink, "").is_err());
+    }
+}
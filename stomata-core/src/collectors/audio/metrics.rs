@@ -0,0 +1,42 @@
+//! Audio device types
+
+use chrono::{DateTime, Utc};
+
+/// Whether a device plays or records audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioDeviceKind {
+    /// Output such as speakers or headphones
+    Sink,
+    /// Input such as a microphone
+    Source,
+}
+
+/// An audio output or input device
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioDevice {
+    /// Output or input
+    pub kind: AudioDeviceKind,
+    /// Name used to address the device, e.g. `alsa_output.pci-0000_00_1f.3.analog-stereo`
+    pub name: String,
+    /// Human readable name
+    pub description: String,
+    /// Volume averaged over the channels, 100 is unamplified
+    pub volume_percent: u32,
+    /// Whether the device is muted
+    pub muted: bool,
+    /// Whether this is the default device of its kind
+    pub is_default: bool,
+}
+
+/// Audio devices at one point in time
+#[derive(Debug, Clone)]
+pub struct AudioMetrics {
+    /// When the devices were listed
+    pub timestamp: DateTime<Utc>,
+    /// Sinks first, then sources, the default device of each kind first
+    pub devices: Vec<AudioDevice>,
+    /// Why the sound server couldn't be queried
+    pub error: Option<String>,
+    /// Whether the devices are still being listed for the first time
+    pub pending: bool,
+}
@@ -0,0 +1,6 @@
+//! Audio output and input devices from PulseAudio or PipeWire, read with `pactl`
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{AudioDevice, AudioDeviceKind, AudioMetrics};
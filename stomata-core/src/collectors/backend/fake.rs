@@ -0,0 +1,392 @@
+//! Scripted system state for tests
+
+use crate::collectors::{
+    backend::{ProcessSample, RefreshingBackend, SystemBackend},
+    connections::metrics::Connection,
+    disks::Mount,
+    network::{metrics::NetworkInterfaces, tunnels::TunnelStatus},
+    process::metrics::{ProcessData, SingleProcessData},
+};
+
+/// The state of a [`FakeSystem`] between two refreshes
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FakeFrame {
+    /// Returned by [`SystemBackend::cpu_count`]
+    pub cpu_count: usize,
+    /// Returned by [`SystemBackend::global_cpu_usage`]
+    pub cpu_usage: f32,
+    /// Returned by [`SystemBackend::cpu_usages`], named `cpu0`, `cpu1`, ...
+    pub per_cpu_usage: Vec<f32>,
+    /// Returned by [`SystemBackend::total_memory`]
+    pub memory_total: u64,
+    /// Returned by [`SystemBackend::used_memory`]
+    pub memory_used: u64,
+    /// Returned by [`SystemBackend::available_memory`]
+    pub memory_available: u64,
+    /// Returned by [`SystemBackend::free_memory`]
+    pub memory_free: u64,
+    /// Returned by [`SystemBackend::total_swap`]
+    pub swap_total: u64,
+    /// Returned by [`SystemBackend::used_swap`]
+    pub swap_used: u64,
+    /// Returned by [`SystemBackend::processes`], and as table rows by
+    /// [`SystemBackend::process_table`]
+    pub processes: Vec<ProcessSample>,
+    /// Returned by [`SystemBackend::sockets`]
+    pub sockets: Vec<Connection>,
+    /// Returned by [`SystemBackend::network_interfaces`]
+    pub interfaces: Vec<NetworkInterfaces>,
+    /// Returned by [`SystemBackend::mounts`]
+    pub mounts: Vec<Mount>,
+}
+
+/// Scripted [`SystemBackend`] for tests.
+///
+/// Starts on the first frame and moves to the next one on every
+/// [`advance`](Self::advance), or every refresh when used as a
+/// [`RefreshingBackend`], staying on the last frame once the script runs
+/// out.
+#[derive(Debug, Clone)]
+pub struct FakeSystem {
+    frames: Vec<FakeFrame>,
+    current: usize,
+}
+
+impl FakeSystem {
+    /// # Panics
+    ///
+    /// Panics if `frames` is empty.
+    pub fn new(frames: Vec<FakeFrame>) -> Self {
+        assert!(!frames.is_empty(), "a fake system needs at least one frame");
+        Self { frames, current: 0 }
+    }
+
+    /// A system that always reports `frame`
+    pub fn fixed(frame: FakeFrame) -> Self {
+        Self::new(vec![frame])
+    }
+
+    /// Moves to the next frame, like a refresh of the real system
+    pub fn advance(&mut self) {
+        self.current = (self.current + 1).min(self.frames.len() - 1);
+    }
+
+    fn frame(&self) -> &FakeFrame {
+        &self.frames[self.current]
+    }
+}
+
+impl SystemBackend for FakeSystem {
+    fn cpu_count(&self) -> usize {
+        self.frame().cpu_count
+    }
+
+    fn global_cpu_usage(&self) -> f32 {
+        self.frame().cpu_usage
+    }
+
+    fn cpu_usages(&self) -> Vec<(String, f32)> {
+        self.frame()
+            .per_cpu_usage
+            .iter()
+            .enumerate()
+            .map(|(index, usage)| (format!("cpu{index}"), *usage))
+            .collect()
+    }
+
+    fn total_memory(&self) -> u64 {
+        self.frame().memory_total
+    }
+
+    fn used_memory(&self) -> u64 {
+        self.frame().memory_used
+    }
+
+    fn available_memory(&self) -> u64 {
+        self.frame().memory_available
+    }
+
+    fn free_memory(&self) -> u64 {
+        self.frame().memory_free
+    }
+
+    fn total_swap(&self) -> u64 {
+        self.frame().swap_total
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.frame().swap_used
+    }
+
+    fn processes(&self) -> Vec<ProcessSample> {
+        self.frame().processes.clone()
+    }
+
+    fn process_table(&self) -> Vec<ProcessData> {
+        self.frame()
+            .processes
+            .iter()
+            .map(ProcessData::from)
+            .collect()
+    }
+
+    fn process_details(&self, pid: u32) -> Option<SingleProcessData> {
+        let process = self
+            .frame()
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)?;
+        Some(SingleProcessData {
+            basic_process_data: process.into(),
+            parent_pid: process.orphan.then_some(1),
+            ..Default::default()
+        })
+    }
+
+    fn commands(&self, name: &str) -> Vec<(u32, Vec<String>)> {
+        self.frame()
+            .processes
+            .iter()
+            .filter(|process| process.name == name)
+            .map(|process| (process.pid, Vec::new()))
+            .collect()
+    }
+
+    fn sockets(&self) -> Vec<Connection> {
+        self.frame().sockets.clone()
+    }
+
+    fn network_interfaces(&self) -> Vec<NetworkInterfaces> {
+        self.frame().interfaces.clone()
+    }
+
+    fn mounts(&self) -> Vec<Mount> {
+        self.frame().mounts.clone()
+    }
+
+    fn tunnels(&self) -> TunnelStatus {
+        TunnelStatus::default()
+    }
+}
+
+impl RefreshingBackend for FakeSystem {
+    fn refresh_cpu(&mut self) {
+        self.advance();
+    }
+
+    fn refresh_memory(&mut self) {
+        self.advance();
+    }
+
+    fn refresh_networks(&mut self) {
+        self.advance();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::{
+        connections::metrics::{ConnectionsMetrics, Protocol},
+        network::{metrics::NetworkMetrics, rates::NetworkRates},
+        system::{metrics::SystemCollector, scheduler::SchedulerRates, summary::SystemSummary},
+    };
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn frame(cpu_usage: f32, memory_used: u64) -> FakeFrame {
+        FakeFrame {
+            cpu_count: 8,
+            cpu_usage,
+            memory_total: 16 * GIB,
+            memory_used,
+            memory_available: 16 * GIB - memory_used,
+            memory_free: GIB,
+            swap_total: 4 * GIB,
+            swap_used: GIB,
+            processes: vec![ProcessSample {
+                pid: 1,
+                name: "init".to_string(),
+                cpu_usage: 0.1,
+                memory: 8 * 1024 * 1024,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fake_system_steps_through_frames() {
+        let mut system = FakeSystem::new(vec![frame(10.0, 4 * GIB), frame(55.0, 8 * GIB)]);
+
+        let first = SystemCollector::fetch(&system, None).system_metrics;
+        assert_eq!(first.cpu_count, 8);
+        assert_eq!(first.cpu_usage, 10.0);
+        assert_eq!(first.memory_used, 4 * GIB);
+
+        system.advance();
+        let scheduler = SchedulerRates {
+            context_switches: 1200.0,
+            interrupts: 800.0,
+            forks: 3.0,
+        };
+        let second = SystemCollector::fetch(&system, Some(scheduler)).system_metrics;
+        assert_eq!(second.cpu_usage, 55.0);
+        assert_eq!(second.memory_available, 8 * GIB);
+        assert_eq!(second.scheduler, Some(scheduler));
+
+        // the script ran out, the last frame stays
+        system.advance();
+        let summary = SystemSummary::fetch(&system);
+        assert_eq!(summary.cpu_usage, 55.0);
+        assert_eq!(summary.memory_percent, 50.0);
+        assert_eq!(summary.swap_percent, 25.0);
+    }
+
+    #[test]
+    fn test_without_swap() {
+        let system = FakeSystem::fixed(FakeFrame {
+            swap_total: 0,
+            swap_used: 0,
+            ..frame(5.0, GIB)
+        });
+
+        let metrics = SystemCollector::fetch(&system, None).system_metrics;
+        assert_eq!((metrics.swap_total, metrics.swap_used), (0, 0));
+        let summary = SystemSummary::fetch(&system);
+        assert_eq!(summary.swap_percent, 0.0);
+        assert!(summary.swap_percent.is_finite());
+    }
+
+    #[test]
+    fn test_summary_with_many_processes() {
+        let processes = (1..=10_000)
+            .map(|pid| ProcessSample {
+                pid,
+                name: format!("worker-{pid}"),
+                cpu_usage: (pid % 97) as f32 / 10.0,
+                memory: pid as u64 * 4096,
+                ..Default::default()
+            })
+            .chain(std::iter::once(ProcessSample {
+                pid: 10_001,
+                name: "busy".to_string(),
+                cpu_usage: 340.0,
+                memory: GIB,
+                ..Default::default()
+            }))
+            .collect();
+        let system = FakeSystem::fixed(FakeFrame {
+            processes,
+            ..frame(90.0, 12 * GIB)
+        });
+
+        assert_eq!(system.processes().len(), 10_001);
+        let top = SystemSummary::fetch(&system).top_process.unwrap();
+        assert_eq!(top.pid, 10_001);
+        assert_eq!(top.name, "busy");
+        assert_eq!(top.cpu_usage, 340.0);
+    }
+
+    #[test]
+    fn test_summary_without_processes() {
+        let system = FakeSystem::fixed(FakeFrame {
+            processes: Vec::new(),
+            ..frame(0.0, 0)
+        });
+        assert_eq!(SystemSummary::fetch(&system).top_process, None);
+    }
+
+    #[test]
+    fn test_summary_counts_zombies_and_orphans() {
+        let process = |pid, zombie, orphan| ProcessSample {
+            pid,
+            name: format!("worker-{pid}"),
+            zombie,
+            orphan,
+            ..Default::default()
+        };
+        let system = FakeSystem::fixed(FakeFrame {
+            processes: vec![
+                process(1, false, false),
+                process(20, true, false),
+                process(21, true, false),
+                process(30, false, true),
+            ],
+            ..frame(5.0, GIB)
+        });
+        let summary = SystemSummary::fetch(&system);
+        assert_eq!((summary.zombies, summary.orphans), (2, 1));
+    }
+
+    #[test]
+    fn test_process_table_and_details() {
+        let system = FakeSystem::fixed(FakeFrame {
+            processes: vec![
+                ProcessSample {
+                    pid: 20,
+                    name: "defunct".to_string(),
+                    zombie: true,
+                    ..Default::default()
+                },
+                ProcessSample {
+                    pid: 30,
+                    name: "lost".to_string(),
+                    orphan: true,
+                    ..Default::default()
+                },
+            ],
+            ..frame(5.0, GIB)
+        });
+
+        let table = ProcessData::fetch(&system);
+        assert_eq!(table.len(), 2);
+        assert!(table[0].is_zombie());
+        assert!(table[1].is_orphan());
+
+        let details = SingleProcessData::fetch(&system, 30).unwrap();
+        assert_eq!(details.basic_process_data.name, "lost");
+        assert_eq!(details.parent_pid, Some(1));
+        assert!(SingleProcessData::fetch(&system, 99).is_none());
+    }
+
+    #[test]
+    fn test_sockets_and_interfaces() {
+        let socket = Connection {
+            protocol: Protocol::Tcp,
+            local: "10.0.0.2:40000".parse().unwrap(),
+            remote: "93.184.216.34:443".parse().unwrap(),
+            state: "ESTABLISHED".to_string(),
+            inode: 4242,
+            pid: Some(1),
+            process_name: None,
+        };
+        let interface = |name: &str| NetworkInterfaces {
+            name: name.to_string(),
+            mtu: 1500,
+            ..Default::default()
+        };
+        let system = FakeSystem::fixed(FakeFrame {
+            sockets: vec![socket],
+            interfaces: vec![interface("wlan0"), interface("eth0")],
+            ..frame(5.0, GIB)
+        });
+
+        let connections = ConnectionsMetrics::fetch(&system);
+        assert_eq!(connections.connections.len(), 1);
+        assert_eq!(
+            connections.connections[0].process_name.as_deref(),
+            Some("init")
+        );
+        assert!(connections.ssh_tunnels.is_empty());
+
+        let networks = NetworkMetrics::fetch(&system, &NetworkRates::default());
+        let names: Vec<&str> = networks
+            .interfaces
+            .iter()
+            .map(|interface| interface.name.as_str())
+            .collect();
+        assert_eq!(names, ["eth0", "wlan0"]);
+        assert_eq!(networks.tunnels, TunnelStatus::default());
+    }
+}
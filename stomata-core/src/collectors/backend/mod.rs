@@ -0,0 +1,166 @@
+//! The system calls behind the CPU, memory, process, network and socket
+//! figures.
+//!
+//! The system resource, summary, process, network, connection, disk,
+//! cgroup and pod collectors, the alert rules and the registry read
+//! through [`SystemBackend`], so they can be driven by [`FakeSystem`] in
+//! tests with exact, scripted values instead of whatever the host happens
+//! to be doing. The host information, inventory and log collectors still
+//! read sysinfo directly, as does [`Sysinfo`] itself when converting
+//! sysinfo processes.
+
+use std::collections::HashMap;
+
+use crate::collectors::{
+    connections::metrics::Connection,
+    disks::Mount,
+    network::{metrics::NetworkInterfaces, rates::InterfaceTotals, tunnels::TunnelStatus},
+    process::metrics::{ProcessData, SingleProcessData},
+};
+
+pub mod fake;
+pub mod system;
+
+pub use fake::{FakeFrame, FakeSystem};
+pub use system::{OwnedSysinfo, Sysinfo};
+
+/// One process as seen by a [`SystemBackend`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProcessSample {
+    /// Process id
+    pub pid: u32,
+    /// Executable name
+    pub name: String,
+    /// CPU usage in percent of one core
+    pub cpu_usage: f32,
+    /// Resident memory in bytes
+    pub memory: u64,
+    /// Exited but not yet reaped by its parent
+    pub zombie: bool,
+    /// Re-parented to PID 1 after its parent exited
+    pub orphan: bool,
+    /// A thread listed like a process, sharing the memory and executable
+    /// of its process
+    pub thread: bool,
+    /// Bytes read from storage since the previous refresh
+    pub read_bytes: u64,
+    /// Bytes written to storage since the previous refresh
+    pub written_bytes: u64,
+    /// Bytes read from storage since the process started
+    pub total_read_bytes: u64,
+    /// Bytes written to storage since the process started
+    pub total_written_bytes: u64,
+}
+
+/// Source of the CPU, memory, process, network and socket figures.
+///
+/// Refreshing is left to the owner of the state, e.g.
+/// [`StomataSystemMetrics`](crate::collectors::StomataSystemMetrics)
+/// refreshes each category at its own interval.
+pub trait SystemBackend {
+    /// Number of logical CPUs
+    fn cpu_count(&self) -> usize;
+
+    /// Usage of all CPUs together in percent
+    fn global_cpu_usage(&self) -> f32;
+
+    /// Usage of each logical CPU in percent, by CPU name
+    fn cpu_usages(&self) -> Vec<(String, f32)>;
+
+    /// Installed memory in bytes
+    fn total_memory(&self) -> u64;
+
+    /// Memory in use in bytes
+    fn used_memory(&self) -> u64;
+
+    /// Memory available to new allocations in bytes
+    fn available_memory(&self) -> u64;
+
+    /// Memory not used for anything in bytes
+    fn free_memory(&self) -> u64;
+
+    /// Size of the swap space, 0 when there is none
+    fn total_swap(&self) -> u64;
+
+    /// Swap in use in bytes
+    fn used_swap(&self) -> u64;
+
+    /// Every running process
+    fn processes(&self) -> Vec<ProcessSample>;
+
+    /// Process table columns of every running process
+    fn process_table(&self) -> Vec<ProcessData>;
+
+    /// Details of the process `pid`, `None` when it doesn't exist
+    fn process_details(&self, pid: u32) -> Option<SingleProcessData>;
+
+    /// Command lines, without the program, of the processes named `name`
+    fn commands(&self, name: &str) -> Vec<(u32, Vec<String>)>;
+
+    /// Every TCP and UDP socket with the PID holding it open, where known
+    fn sockets(&self) -> Vec<Connection>;
+
+    /// Network interfaces with their counters, without rates
+    fn network_interfaces(&self) -> Vec<NetworkInterfaces>;
+
+    /// Cumulative counters of every network interface, for rates
+    fn interface_totals(&self) -> Vec<(String, InterfaceTotals)> {
+        self.network_interfaces()
+            .into_iter()
+            .map(|interface| {
+                let totals = InterfaceTotals {
+                    bytes_received: interface.total_bytes_received,
+                    bytes_transmitted: interface.total_bytes_transmitted,
+                    packets_received: interface.total_packets_received,
+                    packets_transmitted: interface.total_packets_transmitted,
+                };
+                (interface.name, totals)
+            })
+            .collect()
+    }
+
+    /// Mounted filesystems with their size, without inode usage
+    fn mounts(&self) -> Vec<Mount>;
+
+    /// WireGuard and Tailscale tunnels
+    fn tunnels(&self) -> TunnelStatus;
+}
+
+/// A [`SystemBackend`] owning its state, for collectors that refresh it
+/// themselves, like the ones of the
+/// [`CollectorRegistry`](crate::collectors::CollectorRegistry)
+pub trait RefreshingBackend: SystemBackend + Send {
+    /// Refreshes the CPU usage, global and per CPU
+    fn refresh_cpu(&mut self);
+
+    /// Refreshes memory and swap usage
+    fn refresh_memory(&mut self);
+
+    /// Refreshes the network interface list and counters
+    fn refresh_networks(&mut self);
+}
+
+impl From<&ProcessSample> for ProcessData {
+    fn from(sample: &ProcessSample) -> Self {
+        ProcessData {
+            pid: sample.pid,
+            name: sample.name.clone(),
+            cpu_usage: sample.cpu_usage,
+            memory: sample.memory,
+            status: if sample.zombie { "Zombie" } else { "Runnable" }.to_string(),
+            // an orphan keeps a session it doesn't lead, see `is_orphan`
+            parent_pid: sample.orphan.then_some(1),
+            session_id: Some(if sample.orphan { 0 } else { sample.pid }),
+            ..Default::default()
+        }
+    }
+}
+
+/// Process names by PID, for labelling sockets and tunnels
+pub(crate) fn process_names(system: &dyn SystemBackend) -> HashMap<u32, String> {
+    system
+        .processes()
+        .into_iter()
+        .map(|process| (process.pid, process.name))
+        .collect()
+}
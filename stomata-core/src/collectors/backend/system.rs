@@ -0,0 +1,386 @@
+//! The [`SystemBackend`] implementation over sysinfo and procfs
+
+use std::{collections::HashMap, fs};
+
+use sysinfo::{
+    CpuRefreshKind, Disks, MemoryRefreshKind, Networks, Pid, ProcessStatus, RefreshKind, System,
+    Users,
+};
+
+use crate::collectors::{
+    backend::{ProcessSample, RefreshingBackend, SystemBackend},
+    connections::{
+        collectors::{PROC_NET_TABLES, parse_proc_net},
+        metrics::Connection,
+    },
+    disks::Mount,
+    network::{
+        metrics::NetworkInterfaces, rates::InterfaceTotals, tunnels::TunnelStatus, wifi::WifiLink,
+    },
+    process::{
+        is_orphan,
+        metrics::{ProcessData, SingleProcessData},
+    },
+};
+
+/// The sysinfo state kept by
+/// [`StomataSystemMetrics`](crate::collectors::StomataSystemMetrics), as of
+/// its latest refreshes
+#[derive(Debug, Clone, Copy)]
+pub struct Sysinfo<'a> {
+    /// CPU, memory and processes
+    pub system: &'a System,
+    /// Resolves process owners to user names
+    pub users: &'a Users,
+    /// Network interface counters
+    pub networks: &'a Networks,
+}
+
+impl SystemBackend for Sysinfo<'_> {
+    fn cpu_count(&self) -> usize {
+        self.system.cpus().len()
+    }
+
+    fn global_cpu_usage(&self) -> f32 {
+        self.system.global_cpu_usage()
+    }
+
+    fn cpu_usages(&self) -> Vec<(String, f32)> {
+        self.system
+            .cpus()
+            .iter()
+            .map(|cpu| (cpu.name().to_string(), cpu.cpu_usage()))
+            .collect()
+    }
+
+    fn total_memory(&self) -> u64 {
+        self.system.total_memory()
+    }
+
+    fn used_memory(&self) -> u64 {
+        self.system.used_memory()
+    }
+
+    fn available_memory(&self) -> u64 {
+        self.system.available_memory()
+    }
+
+    fn free_memory(&self) -> u64 {
+        self.system.free_memory()
+    }
+
+    fn total_swap(&self) -> u64 {
+        self.system.total_swap()
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.system.used_swap()
+    }
+
+    fn processes(&self) -> Vec<ProcessSample> {
+        self.system
+            .processes()
+            .values()
+            .map(|process| {
+                let disk_usage = process.disk_usage();
+                ProcessSample {
+                    pid: process.pid().as_u32(),
+                    name: process.name().to_string_lossy().into_owned(),
+                    cpu_usage: process.cpu_usage(),
+                    memory: process.memory(),
+                    zombie: process.status() == ProcessStatus::Zombie,
+                    orphan: is_orphan(
+                        process.pid().as_u32(),
+                        process.parent().map(|pid| pid.as_u32()),
+                        process.session_id().map(|pid| pid.as_u32()),
+                    ),
+                    thread: process.thread_kind().is_some(),
+                    read_bytes: disk_usage.read_bytes,
+                    written_bytes: disk_usage.written_bytes,
+                    total_read_bytes: disk_usage.total_read_bytes,
+                    total_written_bytes: disk_usage.total_written_bytes,
+                }
+            })
+            .collect()
+    }
+
+    fn process_table(&self) -> Vec<ProcessData> {
+        self.system
+            .processes()
+            .values()
+            .map(|process| ProcessData::from((process, self.users)))
+            .collect()
+    }
+
+    fn process_details(&self, pid: u32) -> Option<SingleProcessData> {
+        let process = self.system.process(Pid::from_u32(pid))?;
+        let tasks = process
+            .tasks()
+            .map(|task_pids| {
+                task_pids
+                    .iter()
+                    .filter_map(|pid| self.system.process(*pid))
+                    .map(|task| ProcessData::from((task, self.users)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(SingleProcessData::from((process, tasks, self.users)))
+    }
+
+    fn commands(&self, name: &str) -> Vec<(u32, Vec<String>)> {
+        self.system
+            .processes()
+            .values()
+            .filter(|process| process.name() == name && process.thread_kind().is_none())
+            .map(|process| {
+                let args = process
+                    .cmd()
+                    .iter()
+                    .skip(1)
+                    .map(|arg| arg.to_string_lossy().to_string())
+                    .collect();
+                (process.pid().as_u32(), args)
+            })
+            .collect()
+    }
+
+    fn sockets(&self) -> Vec<Connection> {
+        let mut connections: Vec<Connection> = PROC_NET_TABLES
+            .iter()
+            .filter_map(|(path, protocol)| {
+                fs::read_to_string(path)
+                    .ok()
+                    .map(|contents| parse_proc_net(&contents, *protocol))
+            })
+            .flatten()
+            .collect();
+        let owners = socket_owners(self.system);
+        for connection in &mut connections {
+            connection.pid = owners.get(&connection.inode).copied();
+        }
+        connections
+    }
+
+    fn network_interfaces(&self) -> Vec<NetworkInterfaces> {
+        self.networks
+            .list()
+            .iter()
+            .map(|(name, data)| NetworkInterfaces {
+                name: name.clone(),
+                mac_address: data.mac_address().to_string(),
+                ip_networks: data
+                    .ip_networks()
+                    .iter()
+                    .map(|network| network.to_string())
+                    .collect(),
+                mtu: data.mtu(),
+                wifi: WifiLink::fetch(name),
+                rates: Default::default(),
+                errors_on_received: data.errors_on_received(),
+                total_errors_on_received: data.total_errors_on_received(),
+                errors_on_transmitted: data.errors_on_transmitted(),
+                total_errors_on_transmitted: data.total_errors_on_transmitted(),
+                packets_received: data.packets_received(),
+                total_packets_received: data.total_packets_received(),
+                packets_transmitted: data.packets_transmitted(),
+                total_packets_transmitted: data.total_packets_transmitted(),
+                bytes_received: data.received(),
+                total_bytes_received: data.total_received(),
+                bytes_transmitted: data.transmitted(),
+                total_bytes_transmitted: data.total_transmitted(),
+            })
+            .collect()
+    }
+
+    // skips the Wi-Fi link lookup of `network_interfaces`
+    fn interface_totals(&self) -> Vec<(String, InterfaceTotals)> {
+        self.networks
+            .list()
+            .iter()
+            .map(|(name, data)| {
+                let totals = InterfaceTotals {
+                    bytes_received: data.total_received(),
+                    bytes_transmitted: data.total_transmitted(),
+                    packets_received: data.total_packets_received(),
+                    packets_transmitted: data.total_packets_transmitted(),
+                };
+                (name.clone(), totals)
+            })
+            .collect()
+    }
+
+    fn mounts(&self) -> Vec<Mount> {
+        Disks::new_with_refreshed_list()
+            .iter()
+            .map(|disk| Mount {
+                device: disk.name().to_string_lossy().to_string(),
+                mount_point: disk.mount_point().display().to_string(),
+                file_system: disk.file_system().to_string_lossy().to_string(),
+                removable: disk.is_removable(),
+                total_bytes: disk.total_space(),
+                available_bytes: disk.available_space(),
+                inodes: None,
+                is_new: false,
+            })
+            .collect()
+    }
+
+    fn tunnels(&self) -> TunnelStatus {
+        TunnelStatus::fetch()
+    }
+}
+
+/// Sysinfo state owned by a single collector, see [`RefreshingBackend`].
+///
+/// Starts with CPU usage, memory and the network interfaces read once, so
+/// rates and usage are known from the first refresh on. Processes and
+/// users are left empty.
+#[derive(Debug)]
+pub struct OwnedSysinfo {
+    system: System,
+    users: Users,
+    networks: Networks,
+}
+
+impl OwnedSysinfo {
+    /// Takes the first CPU, memory and network readings
+    pub fn new() -> Self {
+        Self {
+            system: System::new_with_specifics(
+                RefreshKind::nothing()
+                    .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+                    .with_memory(MemoryRefreshKind::everything()),
+            ),
+            users: Users::new(),
+            networks: Networks::new_with_refreshed_list(),
+        }
+    }
+
+    fn view(&self) -> Sysinfo<'_> {
+        Sysinfo {
+            system: &self.system,
+            users: &self.users,
+            networks: &self.networks,
+        }
+    }
+}
+
+impl Default for OwnedSysinfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemBackend for OwnedSysinfo {
+    fn cpu_count(&self) -> usize {
+        self.view().cpu_count()
+    }
+
+    fn global_cpu_usage(&self) -> f32 {
+        self.view().global_cpu_usage()
+    }
+
+    fn cpu_usages(&self) -> Vec<(String, f32)> {
+        self.view().cpu_usages()
+    }
+
+    fn total_memory(&self) -> u64 {
+        self.view().total_memory()
+    }
+
+    fn used_memory(&self) -> u64 {
+        self.view().used_memory()
+    }
+
+    fn available_memory(&self) -> u64 {
+        self.view().available_memory()
+    }
+
+    fn free_memory(&self) -> u64 {
+        self.view().free_memory()
+    }
+
+    fn total_swap(&self) -> u64 {
+        self.view().total_swap()
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.view().used_swap()
+    }
+
+    fn processes(&self) -> Vec<ProcessSample> {
+        self.view().processes()
+    }
+
+    fn process_table(&self) -> Vec<ProcessData> {
+        self.view().process_table()
+    }
+
+    fn process_details(&self, pid: u32) -> Option<SingleProcessData> {
+        self.view().process_details(pid)
+    }
+
+    fn commands(&self, name: &str) -> Vec<(u32, Vec<String>)> {
+        self.view().commands(name)
+    }
+
+    fn sockets(&self) -> Vec<Connection> {
+        self.view().sockets()
+    }
+
+    fn network_interfaces(&self) -> Vec<NetworkInterfaces> {
+        self.view().network_interfaces()
+    }
+
+    fn interface_totals(&self) -> Vec<(String, InterfaceTotals)> {
+        self.view().interface_totals()
+    }
+
+    fn mounts(&self) -> Vec<Mount> {
+        self.view().mounts()
+    }
+
+    fn tunnels(&self) -> TunnelStatus {
+        self.view().tunnels()
+    }
+}
+
+impl RefreshingBackend for OwnedSysinfo {
+    fn refresh_cpu(&mut self) {
+        self.system.refresh_cpu_usage();
+    }
+
+    fn refresh_memory(&mut self) {
+        self.system.refresh_memory();
+    }
+
+    fn refresh_networks(&mut self) {
+        self.networks.refresh(true);
+    }
+}
+
+/// Maps socket inodes to the PID holding them open by scanning
+/// `/proc/<pid>/fd`. Processes of other users are skipped unless running
+/// as root.
+fn socket_owners(system: &System) -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    for pid in system.processes().keys() {
+        let Ok(entries) = fs::read_dir(format!("/proc/{pid}/fd")) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let Ok(target) = fs::read_link(entry.path()) else {
+                continue;
+            };
+            let target = target.to_string_lossy();
+            if let Some(inode) = target
+                .strip_prefix("socket:[")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|inode| inode.parse().ok())
+            {
+                owners.insert(inode, pid.as_u32());
+            }
+        }
+    }
+    owners
+}
@@ -0,0 +1,185 @@
+//! Reading devices from `bluetoothctl`
+
+use std::{process::Command, time::Duration};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::{
+        bluetooth::metrics::{BluetoothDevice, BluetoothMetrics},
+        command::run_with_timeout,
+    },
+    constants::{BLUETOOTH_CONNECT_TIMEOUT_MS, BLUETOOTH_TIMEOUT_MS},
+    errors::{CoreError, CoreResult},
+};
+
+fn bluetoothctl(args: &[&str], timeout_ms: u64) -> CoreResult<String> {
+    run_with_timeout(
+        Command::new("bluetoothctl").args(args),
+        Duration::from_millis(timeout_ms),
+    )
+}
+
+impl BluetoothMetrics {
+    /// Lists the paired and connected devices known to BlueZ with
+    /// `bluetoothctl`.
+    ///
+    /// Blocks for one `bluetoothctl` call per device, up to
+    /// `BLUETOOTH_TIMEOUT_MS` each; [`StomataSystemMetrics`] runs it on a
+    /// background thread. Never fails; when BlueZ can't be queried the
+    /// error is returned in `BluetoothMetrics::error`.
+    ///
+    /// [`StomataSystemMetrics`]: crate::collectors::StomataSystemMetrics
+    pub fn fetch() -> Self {
+        let (devices, error) = match bluetoothctl(&["devices"], BLUETOOTH_TIMEOUT_MS) {
+            Ok(list) => {
+                let mut devices: Vec<BluetoothDevice> = parse_devices_list(&list)
+                    .into_iter()
+                    .filter_map(|(address, name)| {
+                        let info = bluetoothctl(&["info", &address], BLUETOOTH_TIMEOUT_MS).ok()?;
+                        let mut device = parse_device_info(&info);
+                        device.address = address;
+                        if device.name.is_empty() {
+                            device.name = name;
+                        }
+                        Some(device)
+                    })
+                    .filter(|device| device.paired || device.connected)
+                    .collect();
+                devices.sort_by(|a, b| b.connected.cmp(&a.connected).then(a.name.cmp(&b.name)));
+                (devices, None)
+            }
+            Err(err) => (Vec::new(), Some(format!("bluetoothctl failed: {err}"))),
+        };
+
+        Self {
+            timestamp: Utc::now(),
+            devices,
+            error,
+            pending: false,
+        }
+    }
+
+    /// No devices yet, while the first listing runs
+    pub fn pending() -> Self {
+        Self {
+            timestamp: Utc::now(),
+            devices: Vec::new(),
+            error: None,
+            pending: true,
+        }
+    }
+}
+
+impl BluetoothDevice {
+    /// Connects to the device, waiting up to
+    /// `BLUETOOTH_CONNECT_TIMEOUT_MS` for it to answer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the bluetoothctl error, e.g. when the device is out of range.
+    pub fn connect(&self) -> CoreResult<()> {
+        let output = bluetoothctl(&["connect", &self.address], BLUETOOTH_CONNECT_TIMEOUT_MS)?;
+        check_action_output(&output)
+    }
+
+    /// Disconnects the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns the bluetoothctl error if the device couldn't be disconnected.
+    pub fn disconnect(&self) -> CoreResult<()> {
+        let output = bluetoothctl(&["disconnect", &self.address], BLUETOOTH_CONNECT_TIMEOUT_MS)?;
+        check_action_output(&output)
+    }
+}
+
+/// bluetoothctl exits successfully even when an action fails, the failure
+/// is only reported on stdout
+fn check_action_output(output: &str) -> CoreResult<()> {
+    match output
+        .lines()
+        .find(|line| line.starts_with("Failed to") || line.contains("not available"))
+    {
+        Some(line) => Err(CoreError::collection(line.trim())),
+        None => Ok(()),
+    }
+}
+
+/// Parses `Device <address> <name>` lines of `bluetoothctl devices` into
+/// `(address, name)` pairs
+pub fn parse_devices_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("Device ")?;
+            let (address, name) = rest.split_once(' ').unwrap_or((rest, ""));
+            Some((address.to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+/// Parses the indented `Key: value` lines of `bluetoothctl info <address>`.
+/// The address is left empty.
+pub fn parse_device_info(output: &str) -> BluetoothDevice {
+    let mut device = BluetoothDevice::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "Name" => device.name = value.to_string(),
+            "Paired" => device.paired = value == "yes",
+            "Connected" => device.connected = value == "yes",
+            // "Battery Percentage: 0x50 (80)"
+            "Battery Percentage" => {
+                device.battery_percent = value
+                    .split_once('(')
+                    .and_then(|(_, decimal)| decimal.trim_end_matches(')').parse().ok());
+            }
+            _ => {}
+        }
+    }
+    device
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bluetoothctl_output() {
+        let list = "Device 11:22:33:44:55:66 WH-1000XM4\nDevice AA:BB:CC:DD:EE:FF MX Keys\n";
+        assert_eq!(
+            parse_devices_list(list),
+            vec![
+                ("11:22:33:44:55:66".to_string(), "WH-1000XM4".to_string()),
+                ("AA:BB:CC:DD:EE:FF".to_string(), "MX Keys".to_string()),
+            ]
+        );
+
+        let info = "Device 11:22:33:44:55:66 (public)
+\tName: WH-1000XM4
+\tAlias: WH-1000XM4
+\tPaired: yes
+\tTrusted: yes
+\tConnected: yes
+\tUUID: Audio Sink                (0000110b-0000-1000-8000-00805f9b34fb)
+\tBattery Percentage: 0x46 (70)
+";
+        let device = parse_device_info(info);
+        assert_eq!(device.name, "WH-1000XM4");
+        assert!(device.paired);
+        assert!(device.connected);
+        assert_eq!(device.battery_percent, Some(70));
+
+        assert!(check_action_output("Attempting to connect to 11:22:33:44:55:66\nFailed to connect: org.bluez.Error.Failed").is_err());
+        assert!(
+            check_action_output(
+                "Attempting to connect to 11:22:33:44:55:66\nConnection successful"
+            )
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Bluetooth device types
+
+use chrono::{DateTime, Utc};
+
+/// A paired or connected Bluetooth device
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BluetoothDevice {
+    /// MAC address, e.g. `AA:BB:CC:DD:EE:FF`
+    pub address: String,
+    /// Name advertised by the device
+    pub name: String,
+    /// Whether the device is paired
+    pub paired: bool,
+    /// Whether the device is connected
+    pub connected: bool,
+    /// Battery level reported through the BlueZ battery service
+    pub battery_percent: Option<u8>,
+}
+
+/// Bluetooth devices at one point in time
+#[derive(Debug, Clone)]
+pub struct BluetoothMetrics {
+    /// When the devices were listed
+    pub timestamp: DateTime<Utc>,
+    /// Connected devices first, then by name
+    pub devices: Vec<BluetoothDevice>,
+    /// Why BlueZ couldn't be queried
+    pub error: Option<String>,
+    /// Whether the devices are still being listed for the first time
+    pub pending: bool,
+}
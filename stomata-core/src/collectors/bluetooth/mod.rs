@@ -0,0 +1,6 @@
+//! Paired Bluetooth devices, read with `bluetoothctl`
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{BluetoothDevice, BluetoothMetrics};
@@ -0,0 +1,175 @@
+//! Parsing `systemd-analyze time` and `blame`
+
+use std::{process::Command, time::Duration};
+
+use crate::{
+    collectors::{
+        boot::metrics::{BootAnalysis, UnitStartup},
+        command::run_with_timeout,
+    },
+    constants::{BOOT_SLOWEST_UNITS, SYSTEMD_ANALYZE_TIMEOUT_MS},
+    errors::{CoreError, CoreResult},
+};
+
+impl BootAnalysis {
+    /// Reads the boot stage times and the slowest units with
+    /// `systemd-analyze`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::UnsupportedPlatform`] if `systemd-analyze`
+    /// isn't installed, e.g. on a system not booted with systemd, or an
+    /// error if boot hasn't finished yet.
+    pub fn fetch() -> CoreResult<Self> {
+        let timeout = Duration::from_millis(SYSTEMD_ANALYZE_TIMEOUT_MS);
+        let time = run_with_timeout(Command::new("systemd-analyze").arg("time"), timeout).map_err(
+            |err| {
+                if err.is_not_found() {
+                    CoreError::unsupported("systemd-analyze is not installed")
+                } else {
+                    err
+                }
+            },
+        )?;
+        let mut analysis = parse_analyze_time(&time)?;
+        // blame needs the unit data of the system manager, which isn't
+        // always readable, so the stage times are still worth showing
+        if let Ok(blame) = run_with_timeout(
+            Command::new("systemd-analyze").args(["blame", "--no-pager"]),
+            timeout,
+        ) {
+            analysis.slowest_units = parse_analyze_blame(&blame, BOOT_SLOWEST_UNITS);
+        }
+        Ok(analysis)
+    }
+
+    /// Stages that were reported, in boot order, with their display names
+    pub fn stages(&self) -> Vec<(&'static str, Duration)> {
+        [
+            ("Firmware", self.firmware),
+            ("Loader", self.loader),
+            ("Kernel", self.kernel),
+            ("Initrd", self.initrd),
+            ("Userspace", self.userspace),
+        ]
+        .into_iter()
+        .filter_map(|(name, duration)| duration.map(|duration| (name, duration)))
+        .collect()
+    }
+}
+
+/// Parses the `Startup finished in ...` line of `systemd-analyze time`
+///
+/// # Errors
+///
+/// Returns an error if the output has no startup line, which is the case
+/// while the boot is still in progress.
+pub fn parse_analyze_time(output: &str) -> CoreResult<BootAnalysis> {
+    let line = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Startup finished in "))
+        .ok_or_else(|| CoreError::collection("boot has not finished yet"))?;
+    // drop the "= 24.8s" total, the stages are summed instead
+    let stages = line.split(" = ").next().unwrap_or(line);
+
+    let mut analysis = BootAnalysis::default();
+    for stage in stages.split(" + ") {
+        let Some((duration, name)) = stage.trim().split_once(" (") else {
+            continue;
+        };
+        let Some(duration) = parse_timespan(duration) else {
+            continue;
+        };
+        let slot = match name.trim_end_matches(')') {
+            "firmware" => &mut analysis.firmware,
+            "loader" => &mut analysis.loader,
+            "kernel" => &mut analysis.kernel,
+            "initrd" => &mut analysis.initrd,
+            "userspace" => &mut analysis.userspace,
+            _ => continue,
+        };
+        *slot = Some(duration);
+        analysis.total += duration;
+    }
+    Ok(analysis)
+}
+
+/// Parses the first `limit` lines of `systemd-analyze blame`
+pub fn parse_analyze_blame(output: &str, limit: usize) -> Vec<UnitStartup> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (duration, unit) = line.trim().rsplit_once(' ')?;
+            Some(UnitStartup {
+                unit: unit.to_string(),
+                duration: parse_timespan(duration)?,
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Parses a systemd time span such as `1min 2.345s`, `345ms` or `12us`
+pub fn parse_timespan(text: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut parsed = false;
+    for part in text.split_whitespace() {
+        let split = part
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(part.len());
+        let (value, unit) = part.split_at(split);
+        let value: f64 = value.parse().ok()?;
+        let seconds = match unit {
+            "d" => 86_400.0,
+            "h" => 3_600.0,
+            "min" => 60.0,
+            "s" => 1.0,
+            "ms" => 1e-3,
+            "us" | "µs" => 1e-6,
+            _ => return None,
+        };
+        total += value * seconds;
+        parsed = true;
+    }
+    parsed.then(|| Duration::from_secs_f64(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timespan() {
+        assert_eq!(parse_timespan("345ms"), Some(Duration::from_millis(345)));
+        assert_eq!(
+            parse_timespan("1min 2.5s"),
+            Some(Duration::from_millis(62_500))
+        );
+        assert_eq!(parse_timespan("12us"), Some(Duration::from_micros(12)));
+        assert_eq!(parse_timespan("soon"), None);
+    }
+
+    #[test]
+    fn test_parse_analyze_time() {
+        let output = "Startup finished in 7.5s (firmware) + 3s (loader) + 1.2s (kernel) + 10s (userspace) = 21.7s\ngraphical.target reached after 9.9s in userspace.\n";
+        let analysis = parse_analyze_time(output).unwrap();
+        assert_eq!(analysis.firmware, Some(Duration::from_millis(7500)));
+        assert_eq!(analysis.loader, Some(Duration::from_secs(3)));
+        assert_eq!(analysis.initrd, None);
+        assert_eq!(analysis.userspace, Some(Duration::from_secs(10)));
+        assert_eq!(analysis.total, Duration::from_millis(21_700));
+        assert_eq!(analysis.stages().len(), 4);
+
+        assert!(parse_analyze_time("Bootup is not yet finished.").is_err());
+    }
+
+    #[test]
+    fn test_parse_analyze_blame() {
+        let output = "1min 3.2s NetworkManager-wait-online.service\n    812ms systemd-udevd.service\n     40ms tmp.mount\n";
+        let units = parse_analyze_blame(output, 2);
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].unit, "NetworkManager-wait-online.service");
+        assert_eq!(units[0].duration, Duration::from_millis(63_200));
+        assert_eq!(units[1].duration, Duration::from_millis(812));
+    }
+}
@@ -0,0 +1,35 @@
+//! Boot timing types
+
+use std::time::Duration;
+
+/// Boot time breakdown as reported by `systemd-analyze`
+///
+/// Stages the machine doesn't report are `None`, e.g. `firmware` and
+/// `loader` on machines not booted through EFI, or `initrd` when no
+/// initial ramdisk is used.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BootAnalysis {
+    /// Time spent in the EFI firmware
+    pub firmware: Option<Duration>,
+    /// Time spent in the boot loader
+    pub loader: Option<Duration>,
+    /// Time from the kernel start to the initrd or userspace
+    pub kernel: Option<Duration>,
+    /// Time spent in the initial ramdisk
+    pub initrd: Option<Duration>,
+    /// Time until the default target was reached
+    pub userspace: Option<Duration>,
+    /// Sum of all the stages
+    pub total: Duration,
+    /// Units that took the longest to start, slowest first
+    pub slowest_units: Vec<UnitStartup>,
+}
+
+/// Startup time of a systemd unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitStartup {
+    /// Unit name, e.g. `NetworkManager.service`
+    pub unit: String,
+    /// Time the unit took to start
+    pub duration: Duration,
+}
@@ -0,0 +1,6 @@
+//! Boot time breakdown from `systemd-analyze`
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{BootAnalysis, UnitStartup};
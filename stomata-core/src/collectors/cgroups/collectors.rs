@@ -0,0 +1,203 @@
+//! Grouping processes by their cgroup from `/proc/<pid>/cgroup`
+
+use std::{collections::HashMap, fs};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::{
+        backend::SystemBackend,
+        cgroups::metrics::{CgroupMetrics, CgroupUsage},
+        kubernetes::collectors::parse_pod_cgroup,
+    },
+    constants::CONTAINER_ID_SHORT_LEN,
+};
+
+impl CgroupMetrics {
+    /// Groups the processes in `system` by cgroup and sums their usage.
+    ///
+    /// Threads are skipped so they aren't counted twice, and processes
+    /// whose cgroup can't be read (non-Linux, or already exited) are left
+    /// out.
+    pub fn fetch(system: &dyn SystemBackend) -> Self {
+        let mut cgroups: HashMap<String, CgroupUsage> = HashMap::new();
+
+        for process in system.processes() {
+            if process.thread {
+                continue;
+            }
+            let Some(path) = process_cgroup(process.pid) else {
+                continue;
+            };
+
+            let usage = cgroups.entry(path.clone()).or_insert_with(|| CgroupUsage {
+                unit: unit_name(&path),
+                path,
+                ..Default::default()
+            });
+            usage.pids.push(process.pid);
+            usage.cpu_usage += process.cpu_usage;
+            usage.memory += process.memory;
+            usage.disk_read_bytes += process.read_bytes;
+            usage.disk_written_bytes += process.written_bytes;
+        }
+
+        let mut cgroups: Vec<CgroupUsage> = cgroups.into_values().collect();
+        cgroups.sort_by(|a, b| {
+            b.cpu_usage
+                .total_cmp(&a.cpu_usage)
+                .then(b.memory.cmp(&a.memory))
+        });
+
+        Self {
+            timestamp: Utc::now(),
+            cgroups,
+        }
+    }
+}
+
+/// Cgroup path of `pid`, read from `/proc/<pid>/cgroup`
+pub fn process_cgroup(pid: u32) -> Option<String> {
+    fs::read_to_string(format!("/proc/{pid}/cgroup"))
+        .ok()
+        .and_then(|content| parse_cgroup_file(&content))
+}
+
+/// Extracts the cgroup path from the contents of a `/proc/<pid>/cgroup`
+/// file.
+///
+/// Uses the unified (cgroup v2) hierarchy when present and falls back to
+/// the systemd named hierarchy on cgroup v1 hosts.
+pub fn parse_cgroup_file(content: &str) -> Option<String> {
+    let mut systemd_v1 = None;
+
+    for line in content.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if id == "0" && controllers.is_empty() {
+            return Some(path.to_string());
+        }
+        if controllers == "name=systemd" {
+            systemd_v1 = Some(path.to_string());
+        }
+    }
+
+    systemd_v1
+}
+
+/// Display name of a cgroup: the last path component, which is the unit
+/// name for systemd managed cgroups such as `nginx.service` or
+/// `docker-<id>.scope`. The root cgroup is reported as `/`.
+pub fn unit_name(path: &str) -> String {
+    path.rsplit('/')
+        .find(|component| !component.is_empty())
+        .unwrap_or("/")
+        .to_string()
+}
+
+/// Names the container or systemd unit a cgroup belongs to.
+///
+/// Containers are labelled with their runtime and short id, e.g.
+/// `docker:3f2a9c1b7d4e` for `/system.slice/docker-<id>.scope` or
+/// `/docker/<id>`, `podman:...` for libpod scopes, `containerd:...` and
+/// `crio:...` for CRI scopes and `pod:<uid>` for Kubernetes pods without a
+/// container scope. LXC containers use their name. Anything else is the
+/// unit name, e.g. `nginx.service` or `user@1000.service`.
+pub fn container_label(path: &str) -> String {
+    let short = |id: &str| id.chars().take(CONTAINER_ID_SHORT_LEN).collect::<String>();
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    // runtimes may nest cgroups below the container scope, e.g. podman's
+    // `libpod-<id>.scope/container`
+    for component in components.iter().rev() {
+        let Some(scope) = component.strip_suffix(".scope") else {
+            continue;
+        };
+        for (prefix, runtime) in [
+            ("docker-", "docker"),
+            ("libpod-", "podman"),
+            ("cri-containerd-", "containerd"),
+            ("crio-", "crio"),
+        ] {
+            if let Some(id) = scope.strip_prefix(prefix) {
+                return format!("{runtime}:{}", short(id));
+            }
+        }
+    }
+
+    if let [.., "docker", id] = components.as_slice() {
+        return format!("docker:{}", short(id));
+    }
+    if let Some(name) = components
+        .iter()
+        .find_map(|component| component.strip_prefix("lxc.payload."))
+    {
+        return format!("lxc:{name}");
+    }
+    if let Some((uid, _)) = parse_pod_cgroup(path) {
+        return format!("pod:{}", short(&uid));
+    }
+    unit_name(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cgroup_file() {
+        assert_eq!(
+            parse_cgroup_file("0::/system.slice/nginx.service\n").as_deref(),
+            Some("/system.slice/nginx.service")
+        );
+
+        let v1 =
+            "12:memory:/system.slice/docker.service\n1:name=systemd:/system.slice/docker.service\n";
+        assert_eq!(
+            parse_cgroup_file(v1).as_deref(),
+            Some("/system.slice/docker.service")
+        );
+        assert_eq!(parse_cgroup_file("4:cpu:/\n"), None);
+    }
+
+    #[test]
+    fn test_unit_name() {
+        assert_eq!(unit_name("/system.slice/nginx.service"), "nginx.service");
+        assert_eq!(
+            unit_name("/user.slice/user-1000.slice/session-2.scope"),
+            "session-2.scope"
+        );
+        assert_eq!(unit_name("/"), "/");
+    }
+
+    #[test]
+    fn test_container_label() {
+        let id = "3f2a9c1b7d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8";
+        assert_eq!(
+            container_label(&format!("/system.slice/docker-{id}.scope")),
+            "docker:3f2a9c1b7d4e"
+        );
+        assert_eq!(
+            container_label(&format!("/docker/{id}")),
+            "docker:3f2a9c1b7d4e"
+        );
+        assert_eq!(
+            container_label(&format!(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{id}.scope/container"
+            )),
+            "podman:3f2a9c1b7d4e"
+        );
+        assert_eq!(container_label("/lxc.payload.web/init.scope"), "lxc:web");
+        assert_eq!(
+            container_label("/kubepods/burstable/pod1b2c3d4e-aaaa-bbbb-cccc-1234567890ab"),
+            "pod:1b2c3d4e-aaa"
+        );
+        assert_eq!(
+            container_label("/system.slice/nginx.service"),
+            "nginx.service"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Cgroup usage types
+
+use chrono::{DateTime, Utc};
+
+/// Resource usage of all processes in one cgroup, e.g. a systemd service
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CgroupUsage {
+    /// Full cgroup path such as `/system.slice/nginx.service`
+    pub path: String,
+    /// Last path component, the systemd unit for systemd managed cgroups
+    pub unit: String,
+    /// Member processes
+    pub pids: Vec<u32>,
+    /// Sum of the CPU usage of the member processes, in percent of one core
+    pub cpu_usage: f32,
+    /// Sum of the resident memory of the member processes in bytes
+    pub memory: u64,
+    /// Bytes read from disk since the last refresh
+    pub disk_read_bytes: u64,
+    /// Bytes written to disk since the last refresh
+    pub disk_written_bytes: u64,
+}
+
+/// Resource usage per cgroup at one point in time
+#[derive(Clone)]
+pub struct CgroupMetrics {
+    /// When the processes were grouped
+    pub timestamp: DateTime<Utc>,
+    /// Cgroups with at least one process, highest CPU usage first
+    pub cgroups: Vec<CgroupUsage>,
+}
@@ -0,0 +1,6 @@
+//! CPU, memory and disk I/O totals per cgroup
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{CgroupMetrics, CgroupUsage};
@@ -0,0 +1,197 @@
+//! Running external tools with a timeout
+
+use std::{
+    io::Read,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    constants::COMMAND_MAX_OUTPUT_BYTES,
+    errors::{CoreError, CoreResult},
+};
+
+/// Runs `command` and returns its stdout. The process is killed if it does
+/// not exit within `timeout`.
+///
+/// # Errors
+///
+/// Returns an error if the command can't be started, times out, prints
+/// more than [`COMMAND_MAX_OUTPUT_BYTES`] or exits unsuccessfully. The
+/// error carries the first line of stderr when there is one.
+pub fn run_with_timeout(command: &mut Command, timeout: Duration) -> CoreResult<String> {
+    let (status, output, stderr) = run_with_timeout_status(command, timeout)?;
+    if !status.success() {
+        return match stderr.lines().find(|line| !line.trim().is_empty()) {
+            Some(line) => Err(CoreError::collection(format!(
+                "exited with {status}: {}",
+                line.trim()
+            ))),
+            None => Err(CoreError::collection(format!("exited with {status}"))),
+        };
+    }
+    Ok(output)
+}
+
+/// Runs `command` like [`run_with_timeout`] but returns its exit status
+/// with stdout and stderr instead of failing on an unsuccessful exit, for
+/// commands that report results through their exit code.
+///
+/// # Errors
+///
+/// Returns an error if the command can't be started, times out or prints
+/// more than [`COMMAND_MAX_OUTPUT_BYTES`].
+pub(crate) fn run_with_timeout_status(
+    command: &mut Command,
+    timeout: Duration,
+) -> CoreResult<(ExitStatus, String, String)> {
+    tracing::debug!(program = %command.get_program().display(), "running command");
+    // in a group of its own, so the processes it leaves running can be
+    // killed along with it
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| {
+            CoreError::io(
+                format!("failed to start {}", command.get_program().display()),
+                err,
+            )
+        })?;
+
+    // read the pipes on separate threads so a chatty child can't fill them
+    // and block, one byte past the limit tells a full pipe from a truncated
+    // one
+    let read_pipe = |pipe: Option<Box<dyn Read + Send>>| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut output = String::new();
+            if let Some(pipe) = pipe {
+                let _ = pipe
+                    .take(COMMAND_MAX_OUTPUT_BYTES + 1)
+                    .read_to_string(&mut output);
+            }
+            let truncated = output.len() as u64 > COMMAND_MAX_OUTPUT_BYTES;
+            let _ = sender.send((output, truncated));
+        });
+        receiver
+    };
+    let stdout = read_pipe(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+    let stderr = read_pipe(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        let exited = child.try_wait().map_err(|err| {
+            CoreError::io(
+                format!("failed to wait for {}", command.get_program().display()),
+                err,
+            )
+        })?;
+        if let Some(status) = exited {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill_group(&mut child);
+            let _ = child.wait();
+            tracing::warn!(
+                program = %command.get_program().display(),
+                timeout_ms = timeout.as_millis(),
+                "command timed out"
+            );
+            return Err(CoreError::collection(format!(
+                "timed out after {} ms",
+                timeout.as_millis()
+            )));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    // a process the child left running in the background can hold the
+    // pipes open after it exited, the reads only get what is left of the
+    // timeout before the group is killed to end them
+    let remaining = || deadline.saturating_duration_since(Instant::now());
+    let output = stdout.recv_timeout(remaining()).map_err(|_| {
+        tracing::warn!(
+            program = %command.get_program().display(),
+            "command output still open after it exited"
+        );
+        kill_group(&mut child);
+        CoreError::collection(format!(
+            "timed out after {} ms reading the output, a background process holds it open",
+            timeout.as_millis()
+        ))
+    })?;
+    let stderr = stderr.recv_timeout(remaining()).unwrap_or_else(|_| {
+        kill_group(&mut child);
+        Default::default()
+    });
+    if output.1 || stderr.1 {
+        return Err(CoreError::collection(format!(
+            "output exceeds the limit of {COMMAND_MAX_OUTPUT_BYTES} bytes"
+        )));
+    }
+    Ok((status, output.0, stderr.0))
+}
+
+/// Kills `child` and every process it left running in its group, which
+/// closes the pipes they hold
+fn kill_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(group) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: kill only takes plain integers and touches no memory of
+        // this process; the group id is the child's pid, never 0 or below,
+        // so it can't address the caller's own group
+        unsafe { libc::kill(-group, libc::SIGKILL) };
+        return;
+    }
+    let _ = child.kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_is_read_until_exit() {
+        let output = run_with_timeout(
+            Command::new("sh").args(["-c", "echo out; echo err >&2"]),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(output, "out\n");
+        let failed = run_with_timeout(
+            Command::new("sh").args(["-c", "echo err >&2; exit 3"]),
+            Duration::from_secs(5),
+        );
+        assert!(failed.unwrap_err().to_string().contains("err"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_background_processes_holding_the_pipe_do_not_block() {
+        let started = Instant::now();
+        let result = run_with_timeout(
+            Command::new("sh").args(["-c", "sleep 5 & echo started"]),
+            Duration::from_millis(300),
+        );
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_past_the_limit_is_an_error() {
+        let script = format!("head -c {} /dev/zero", COMMAND_MAX_OUTPUT_BYTES + 1);
+        let result = run_with_timeout(
+            Command::new("sh").args(["-c", &script]),
+            Duration::from_secs(10),
+        );
+        assert!(result.unwrap_err().to_string().contains("limit"));
+    }
+}
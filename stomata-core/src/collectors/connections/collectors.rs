@@ -0,0 +1,382 @@
+//! Reading sockets from `/proc/net` and matching them to processes
+
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::{
+        backend::{SystemBackend, process_names},
+        connections::{
+            metrics::{Connection, ConnectionAllowlist, ConnectionsMetrics, Protocol},
+            ssh::SshTunnel,
+        },
+    },
+    constants::{COMMON_NETWORK_PROCESSES, COMMON_REMOTE_PORTS},
+};
+
+/// Socket tables in procfs with their protocol
+pub(crate) const PROC_NET_TABLES: [(&str, Protocol); 4] = [
+    ("/proc/net/tcp", Protocol::Tcp),
+    ("/proc/net/tcp6", Protocol::Tcp),
+    ("/proc/net/udp", Protocol::Udp),
+    ("/proc/net/udp6", Protocol::Udp),
+];
+
+impl Protocol {
+    /// Protocol name, e.g. `TCP`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "TCP",
+            Protocol::Udp => "UDP",
+        }
+    }
+}
+
+impl Connection {
+    /// Whether the socket is connected to a remote peer
+    pub fn is_established(&self) -> bool {
+        self.state == "ESTABLISHED"
+    }
+
+    /// Whether the socket waits for incoming connections
+    pub fn is_listening(&self) -> bool {
+        self.state == "LISTEN" || (self.protocol == Protocol::Udp && self.remote.port() == 0)
+    }
+
+    /// Whether the socket matches a filter typed by the user. A number
+    /// matches the local port exactly, anything else is looked up in the
+    /// local address and process name, ignoring case.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim();
+        if query.is_empty() {
+            return true;
+        }
+        if let Ok(port) = query.trim_start_matches(':').parse::<u16>() {
+            return self.local.port() == port;
+        }
+        let query = query.to_lowercase();
+        self.local.to_string().contains(&query)
+            || self
+                .process_name
+                .as_ref()
+                .is_some_and(|name| name.to_lowercase().contains(&query))
+    }
+}
+
+/// Whether `ip` belongs to a private, loopback, link-local or shared
+/// (CGNAT) range, i.e. it is not reachable on the public internet
+pub fn is_private_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                // 100.64.0.0/10 shared address space
+                || (first == 100 && (64..128).contains(&second))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ipv4) => is_private_ip(IpAddr::V4(ipv4)),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    // fc00::/7 unique local and fe80::/10 link-local
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+impl Default for ConnectionAllowlist {
+    fn default() -> Self {
+        Self {
+            processes: COMMON_NETWORK_PROCESSES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            ports: COMMON_REMOTE_PORTS.to_vec(),
+        }
+    }
+}
+
+impl ConnectionAllowlist {
+    /// Checks an outbound connection against the allowlist.
+    ///
+    /// Only established connections with a known owner are checked.
+    /// Inbound connections, i.e. those on one of `listening_ports`, and
+    /// loopback traffic are never flagged.
+    ///
+    /// # Returns
+    ///
+    /// The reason the connection looks suspicious, or `None`.
+    pub fn check(
+        &self,
+        connection: &Connection,
+        listening_ports: &HashSet<u16>,
+    ) -> Option<&'static str> {
+        let process = connection.process_name.as_deref()?;
+        if !connection.is_established()
+            || connection.remote.ip().is_loopback()
+            || listening_ports.contains(&connection.local.port())
+            || self.processes.iter().any(|allowed| allowed == process)
+        {
+            return None;
+        }
+
+        let public = !is_private_ip(connection.remote.ip());
+        let uncommon_port = !self.ports.contains(&connection.remote.port());
+        match (public, uncommon_port) {
+            (true, true) => Some("uncommon port on a public address"),
+            (true, false) => Some("public address"),
+            (false, true) => Some("uncommon port"),
+            (false, false) => None,
+        }
+    }
+}
+
+impl ConnectionsMetrics {
+    /// Sockets waiting for incoming connections, sorted by port and
+    /// protocol
+    pub fn listening(&self) -> Vec<&Connection> {
+        let mut listening: Vec<&Connection> = self
+            .connections
+            .iter()
+            .filter(|connection| connection.is_listening())
+            .collect();
+        listening.sort_by_key(|connection| {
+            (
+                connection.local.port(),
+                connection.protocol.as_str(),
+                connection.local.ip(),
+            )
+        });
+        listening
+    }
+
+    /// Local ports with a listening socket, used to tell inbound from
+    /// outbound connections
+    pub fn listening_ports(&self) -> HashSet<u16> {
+        self.connections
+            .iter()
+            .filter(|connection| connection.is_listening())
+            .map(|connection| connection.local.port())
+            .collect()
+    }
+
+    /// Connections flagged by `allowlist` together with the reason
+    pub fn suspicious<'a>(
+        &'a self,
+        allowlist: &ConnectionAllowlist,
+    ) -> Vec<(&'a Connection, &'static str)> {
+        let listening_ports = self.listening_ports();
+        self.connections
+            .iter()
+            .filter_map(|connection| {
+                allowlist
+                    .check(connection, &listening_ports)
+                    .map(|reason| (connection, reason))
+            })
+            .collect()
+    }
+}
+
+/// Name of a TCP state code from `/proc/net/tcp`, see `include/net/tcp_states.h`
+fn tcp_state_name(code: u8) -> &'static str {
+    match code {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        0x0C => "NEW_SYN_RECV",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Parses an `ADDRESS:PORT` pair from `/proc/net/*`.
+///
+/// The address is hex in host byte order, in 32-bit words for IPv6.
+fn parse_socket_addr(value: &str) -> Option<SocketAddr> {
+    let (address, port) = value.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let ip = match address.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(
+            u32::from_str_radix(address, 16).ok()?.to_le_bytes(),
+        )),
+        32 => {
+            let mut octets = [0u8; 16];
+            for (index, chunk) in octets.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(&address[index * 8..index * 8 + 8], 16).ok()?;
+                chunk.copy_from_slice(&word.to_le_bytes());
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Parses the contents of a `/proc/net/{tcp,tcp6,udp,udp6}` table.
+///
+/// Malformed lines are skipped. The owning process is filled in later.
+pub fn parse_proc_net(contents: &str, protocol: Protocol) -> Vec<Connection> {
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local = parse_socket_addr(fields.get(1)?)?;
+            let remote = parse_socket_addr(fields.get(2)?)?;
+            let state = tcp_state_name(u8::from_str_radix(fields.get(3)?, 16).ok()?);
+            let inode = fields.get(9)?.parse().ok()?;
+            Some(Connection {
+                protocol,
+                local,
+                remote,
+                state: state.to_string(),
+                inode,
+                pid: None,
+                process_name: None,
+            })
+        })
+        .collect()
+}
+
+impl ConnectionsMetrics {
+    /// Reads every TCP and UDP socket and attaches the owning process.
+    /// Empty on platforms without procfs.
+    pub fn fetch(system: &dyn SystemBackend) -> Self {
+        let mut connections = system.sockets();
+        let names = process_names(system);
+        for connection in &mut connections {
+            connection.process_name = connection.pid.and_then(|pid| names.get(&pid).cloned());
+        }
+
+        let ssh_tunnels = SshTunnel::collect(system, &connections);
+
+        Self {
+            timestamp: Utc::now(),
+            connections,
+            ssh_tunnels,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net_tcp() {
+        let contents = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21456 1 0000000000000000 100 0 0 10 0\n   1: 0F02000A:A1B2 22D8B85D:01BB 01 00000000:00000000 00:00000000 00000000  1000        0 98765 1 0000000000000000 20 4 30 10 -1\n";
+        let connections = parse_proc_net(contents, Protocol::Tcp);
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].local, "127.0.0.1:631".parse().unwrap());
+        assert!(connections[0].is_listening());
+        assert_eq!(connections[1].remote, "93.184.216.34:443".parse().unwrap());
+        assert_eq!(connections[1].state, "ESTABLISHED");
+        assert_eq!(connections[1].inode, 98765);
+    }
+
+    #[test]
+    fn test_private_ranges() {
+        for ip in [
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "100.64.0.1",
+            "fd00::1",
+            "fe80::1",
+        ] {
+            assert!(is_private_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["8.8.8.8", "100.128.0.1", "2606:4700::1111"] {
+            assert!(!is_private_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_allowlist_check() {
+        let connection = |process: &str, remote: &str| Connection {
+            protocol: Protocol::Tcp,
+            local: "10.0.0.2:50000".parse().unwrap(),
+            remote: remote.parse().unwrap(),
+            state: "ESTABLISHED".to_string(),
+            inode: 1,
+            pid: Some(42),
+            process_name: Some(process.to_string()),
+        };
+        let allowlist = ConnectionAllowlist {
+            processes: vec!["curl".to_string()],
+            ports: vec![443],
+        };
+        let listening = HashSet::new();
+
+        assert_eq!(
+            allowlist.check(&connection("curl", "8.8.8.8:4444"), &listening),
+            None
+        );
+        assert_eq!(
+            allowlist.check(&connection("cron", "8.8.8.8:4444"), &listening),
+            Some("uncommon port on a public address")
+        );
+        assert_eq!(
+            allowlist.check(&connection("cron", "8.8.8.8:443"), &listening),
+            Some("public address")
+        );
+        assert_eq!(
+            allowlist.check(&connection("cron", "10.0.0.1:4444"), &listening),
+            Some("uncommon port")
+        );
+        assert_eq!(
+            allowlist.check(&connection("cron", "10.0.0.1:443"), &listening),
+            None
+        );
+
+        let inbound = HashSet::from([50000]);
+        assert_eq!(
+            allowlist.check(&connection("cron", "8.8.8.8:4444"), &inbound),
+            None
+        );
+    }
+
+    #[test]
+    fn test_connection_matches() {
+        let connection = Connection {
+            protocol: Protocol::Tcp,
+            local: "127.0.0.1:8080".parse().unwrap(),
+            remote: "0.0.0.0:0".parse().unwrap(),
+            state: "LISTEN".to_string(),
+            inode: 1,
+            pid: Some(4242),
+            process_name: Some("Python3".to_string()),
+        };
+        assert!(connection.matches(""));
+        assert!(connection.matches("8080"));
+        assert!(connection.matches(":8080"));
+        assert!(!connection.matches("80"));
+        assert!(connection.matches("python"));
+        assert!(connection.matches("127.0.0"));
+        assert!(!connection.matches("nginx"));
+    }
+
+    #[test]
+    fn test_parse_ipv6_address() {
+        let addr = parse_socket_addr("00000000000000000000000001000000:0016").unwrap();
+        assert_eq!(addr, "[::1]:22".parse().unwrap());
+    }
+}
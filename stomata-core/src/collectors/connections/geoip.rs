@@ -0,0 +1,129 @@
+//! GeoIP annotation of remote endpoints
+//!
+//! Resolves remote addresses to a country and autonomous system using local
+//! MaxMind databases. Nothing is downloaded; the databases are supplied by
+//! the user. Only available with the `geoip` feature.
+
+use std::{collections::HashMap, fmt, net::IpAddr, path::Path};
+
+use maxminddb::{Reader, geoip2};
+
+use crate::{
+    collectors::connections::collectors::is_private_ip,
+    constants::GEOIP_CACHE_CAPACITY,
+    errors::{CoreError, CoreResult},
+};
+
+/// Country and autonomous system of a remote address
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeoInfo {
+    /// ISO 3166-1 country code, e.g. `US`
+    pub country: Option<String>,
+
+    /// Autonomous system number
+    pub asn: Option<u32>,
+
+    /// Organization operating the autonomous system
+    pub organization: Option<String>,
+}
+
+impl fmt::Display for GeoInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(country) = &self.country {
+            parts.push(country.clone());
+        }
+        if let Some(asn) = self.asn {
+            parts.push(format!("AS{asn}"));
+        }
+        if let Some(organization) = &self.organization {
+            parts.push(organization.clone());
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// Looks up remote addresses in local MaxMind databases.
+///
+/// Either database is optional; a GeoLite2/GeoIP2 Country or City database
+/// provides the country and an ASN database the autonomous system. Results,
+/// including misses, are cached per address.
+pub struct GeoIp {
+    country_db: Option<Reader<Vec<u8>>>,
+    asn_db: Option<Reader<Vec<u8>>>,
+    cache: HashMap<IpAddr, Option<GeoInfo>>,
+}
+
+impl fmt::Debug for GeoIp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GeoIp")
+            .field("country_db", &self.country_db.is_some())
+            .field("asn_db", &self.asn_db.is_some())
+            .field("cached", &self.cache.len())
+            .finish()
+    }
+}
+
+impl GeoIp {
+    /// Opens the given databases.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database can't be read or isn't a valid
+    /// MaxMind database.
+    pub fn open(country_db: Option<&Path>, asn_db: Option<&Path>) -> CoreResult<Self> {
+        let open = |path: &Path| {
+            Reader::open_readfile(path).map_err(|err| {
+                CoreError::collection(format!(
+                    "failed to open GeoIP database {}: {err}",
+                    path.display()
+                ))
+            })
+        };
+        Ok(Self {
+            country_db: country_db.map(open).transpose()?,
+            asn_db: asn_db.map(open).transpose()?,
+            cache: HashMap::new(),
+        })
+    }
+
+    /// Country and ASN of `ip`. Private addresses and addresses missing
+    /// from both databases return `None`.
+    pub fn lookup(&mut self, ip: IpAddr) -> Option<&GeoInfo> {
+        if is_private_ip(ip) {
+            return None;
+        }
+
+        if !self.cache.contains_key(&ip) {
+            // connections churn, so drop everything rather than tracking recency
+            if self.cache.len() >= GEOIP_CACHE_CAPACITY {
+                self.cache.clear();
+            }
+            let info = self.lookup_uncached(ip);
+            self.cache.insert(ip, info);
+        }
+        self.cache.get(&ip).and_then(Option::as_ref)
+    }
+
+    fn lookup_uncached(&self, ip: IpAddr) -> Option<GeoInfo> {
+        let mut info = GeoInfo::default();
+
+        if let Some(reader) = &self.country_db
+            && let Ok(country) = reader.lookup::<geoip2::Country>(ip)
+        {
+            info.country = country
+                .country
+                .and_then(|country| country.iso_code)
+                .map(str::to_string);
+        }
+
+        if let Some(reader) = &self.asn_db
+            && let Ok(asn) = reader.lookup::<geoip2::Asn>(ip)
+        {
+            info.asn = asn.autonomous_system_number;
+            info.organization = asn.autonomous_system_organization.map(str::to_string);
+        }
+
+        (info != GeoInfo::default()).then_some(info)
+    }
+}
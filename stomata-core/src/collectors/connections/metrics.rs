@@ -0,0 +1,58 @@
+//! Connection types and the suspicious connection allowlist
+
+use std::net::SocketAddr;
+
+use chrono::{DateTime, Utc};
+
+use crate::collectors::connections::ssh::SshTunnel;
+
+/// Transport protocol of a socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Transmission Control Protocol
+    Tcp,
+    /// User Datagram Protocol
+    Udp,
+}
+
+/// A TCP or UDP socket and the process that owns it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Connection {
+    /// TCP or UDP
+    pub protocol: Protocol,
+    /// Local address and port
+    pub local: SocketAddr,
+    /// Remote address and port, unspecified for listening sockets
+    pub remote: SocketAddr,
+    /// TCP state such as `ESTABLISHED` or `LISTEN`, UDP sockets report
+    /// `ESTABLISHED` when connected and `CLOSE` otherwise
+    pub state: String,
+    /// Socket inode, used to find the owning process
+    pub inode: u64,
+    /// Owning process, `None` when it can't be found (another user's process)
+    pub pid: Option<u32>,
+    /// Name of the owning process
+    pub process_name: Option<String>,
+}
+
+/// Open sockets at one point in time
+#[derive(Debug, Clone)]
+pub struct ConnectionsMetrics {
+    /// When the sockets were read
+    pub timestamp: DateTime<Utc>,
+    /// Sockets of every protocol and address family
+    pub connections: Vec<Connection>,
+    /// SSH clients with port forwards
+    pub ssh_tunnels: Vec<SshTunnel>,
+}
+
+/// Processes and remote ports that are expected to open network
+/// connections. Established connections outside the allowlist are flagged
+/// when they go to a public address or an uncommon port.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionAllowlist {
+    /// Process names expected to open connections
+    pub processes: Vec<String>,
+    /// Remote ports that aren't considered uncommon
+    pub ports: Vec<u16>,
+}
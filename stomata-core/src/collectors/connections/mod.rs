@@ -0,0 +1,9 @@
+//! TCP and UDP sockets with their owning process
+
+pub mod collectors;
+#[cfg(feature = "geoip")]
+pub mod geoip;
+pub mod metrics;
+pub mod ssh;
+
+pub use metrics::{Connection, ConnectionAllowlist, ConnectionsMetrics, Protocol};
@@ -0,0 +1,228 @@
+//! SSH tunnel detection
+//!
+//! Finds running `ssh` client processes and parses the `-L`, `-R` and `-D`
+//! port forwards from their command lines, together with the local ports
+//! each of them is actually listening on.
+
+use std::collections::BTreeSet;
+
+use crate::{
+    collectors::{backend::SystemBackend, connections::metrics::Connection},
+    constants::SSH_OPTIONS_WITH_ARGUMENT,
+};
+
+/// Direction of an SSH port forward
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardKind {
+    /// `-L`, a local port forwarded to a host reachable from the server
+    Local,
+    /// `-R`, a port on the server forwarded back to this side
+    Remote,
+    /// `-D`, a local SOCKS proxy
+    Dynamic,
+}
+
+/// A single port forward of an SSH client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forward {
+    /// Local, remote or dynamic
+    pub kind: ForwardKind,
+    /// Listening side, `[bind_address:]port` or a unix socket path
+    pub listen: String,
+    /// Where connections are forwarded to, `None` for dynamic forwards
+    pub target: Option<String>,
+}
+
+/// A running SSH client with its forwards
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshTunnel {
+    /// Process id of the client
+    pub pid: u32,
+    /// `[user@]host` the client connected to
+    pub destination: Option<String>,
+    /// Port forwards from the command line
+    pub forwards: Vec<Forward>,
+    /// Local TCP ports the process is listening on
+    pub listening_ports: Vec<u16>,
+}
+
+impl ForwardKind {
+    /// Lowercase name of the forward kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForwardKind::Local => "local",
+            ForwardKind::Remote => "remote",
+            ForwardKind::Dynamic => "dynamic",
+        }
+    }
+}
+
+impl SshTunnel {
+    /// Every `ssh` client process that forwards at least one port.
+    ///
+    /// `connections` are the sockets of the same refresh, used to find the
+    /// ports each client listens on.
+    pub fn collect(system: &dyn SystemBackend, connections: &[Connection]) -> Vec<SshTunnel> {
+        let mut tunnels: Vec<SshTunnel> = system
+            .commands("ssh")
+            .into_iter()
+            .filter_map(|(pid, args)| {
+                let (destination, forwards) = parse_ssh_args(&args);
+                if forwards.is_empty() {
+                    return None;
+                }
+
+                let listening_ports: BTreeSet<u16> = connections
+                    .iter()
+                    .filter(|connection| connection.pid == Some(pid) && connection.is_listening())
+                    .map(|connection| connection.local.port())
+                    .collect();
+
+                Some(SshTunnel {
+                    pid,
+                    destination,
+                    forwards,
+                    listening_ports: listening_ports.into_iter().collect(),
+                })
+            })
+            .collect();
+        tunnels.sort_by_key(|tunnel| tunnel.pid);
+        tunnels
+    }
+}
+
+/// Parses the destination and port forwards from ssh command line
+/// arguments, excluding the program name.
+///
+/// Options are parsed like ssh does: flags can be grouped (`-fNL`) and an
+/// option's argument can be attached (`-L8080:db:5432`) or follow as the
+/// next argument. The first non-option argument is the destination;
+/// anything after it is the remote command and ignored.
+pub fn parse_ssh_args(args: &[String]) -> (Option<String>, Vec<Forward>) {
+    let mut forwards = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let Some(flags) = arg.strip_prefix('-').filter(|flags| !flags.is_empty()) else {
+            return (Some(arg.clone()), forwards);
+        };
+
+        for (index, flag) in flags.char_indices() {
+            if !SSH_OPTIONS_WITH_ARGUMENT.contains(flag) {
+                continue;
+            }
+            let attached = &flags[index + flag.len_utf8()..];
+            let value = if attached.is_empty() {
+                args.next().cloned().unwrap_or_default()
+            } else {
+                attached.to_string()
+            };
+            let kind = match flag {
+                'L' => Some(ForwardKind::Local),
+                'R' => Some(ForwardKind::Remote),
+                'D' => Some(ForwardKind::Dynamic),
+                _ => None,
+            };
+            if let Some(forward) = kind.and_then(|kind| parse_forward(kind, &value)) {
+                forwards.push(forward);
+            }
+            break;
+        }
+    }
+
+    (None, forwards)
+}
+
+/// Parses a forward specification such as `[bind_address:]port:host:hostport`
+/// (`-L`/`-R`) or `[bind_address:]port` (`-D` and dynamic `-R`).
+pub fn parse_forward(kind: ForwardKind, spec: &str) -> Option<Forward> {
+    let parts = split_spec(spec);
+    let (listen, target) = match (kind, parts.len()) {
+        (ForwardKind::Dynamic, 1) | (ForwardKind::Remote, 1) => (parts.join(":"), None),
+        (ForwardKind::Dynamic, 2) => (parts.join(":"), None),
+        (_, 2) => (parts[0].clone(), Some(parts[1].clone())),
+        (_, 3) => (parts[0].clone(), Some(format!("{}:{}", parts[1], parts[2]))),
+        (_, 4) => (
+            format!("{}:{}", parts[0], parts[1]),
+            Some(format!("{}:{}", parts[2], parts[3])),
+        ),
+        _ => return None,
+    };
+    Some(Forward {
+        kind,
+        listen,
+        target,
+    })
+}
+
+/// Splits a forward specification on `:`, keeping bracketed IPv6
+/// addresses such as `[::1]` in one piece
+fn split_spec(spec: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_brackets = false;
+    for c in spec.chars() {
+        match c {
+            '[' => in_brackets = true,
+            ']' => in_brackets = false,
+            ':' if !in_brackets => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_ssh_args() {
+        let (destination, forwards) = parse_ssh_args(&args(
+            "-fN -L 8080:db.internal:5432 -R[::1]:9000:localhost:22 -D1080 -p 2222 user@bastion uptime",
+        ));
+        assert_eq!(destination.as_deref(), Some("user@bastion"));
+        assert_eq!(
+            forwards,
+            vec![
+                Forward {
+                    kind: ForwardKind::Local,
+                    listen: "8080".to_string(),
+                    target: Some("db.internal:5432".to_string()),
+                },
+                Forward {
+                    kind: ForwardKind::Remote,
+                    listen: "[::1]:9000".to_string(),
+                    target: Some("localhost:22".to_string()),
+                },
+                Forward {
+                    kind: ForwardKind::Dynamic,
+                    listen: "1080".to_string(),
+                    target: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ssh_args_grouped_flags() {
+        let (destination, forwards) = parse_ssh_args(&args("-NfL127.0.0.1:3000:web:80 host"));
+        assert_eq!(destination.as_deref(), Some("host"));
+        assert_eq!(forwards.len(), 1);
+        assert_eq!(forwards[0].listen, "127.0.0.1:3000");
+        assert_eq!(forwards[0].target.as_deref(), Some("web:80"));
+
+        let (destination, forwards) = parse_ssh_args(&args("-i key.pem host"));
+        assert_eq!(destination.as_deref(), Some("host"));
+        assert!(forwards.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! Parsing crash events from `journalctl`
+
+use std::{process::Command, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::{
+    collectors::{
+        command::run_with_timeout,
+        crashes::metrics::{CrashEvent, CrashKind},
+    },
+    constants::{CRASH_LOG_PATTERN, JOURNALCTL_TIMEOUT_MS},
+    errors::{CoreError, CoreResult},
+};
+
+impl CrashKind {
+    /// Display name of the failure kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrashKind::OomKill => "OOM kill",
+            CrashKind::Segfault => "Segfault",
+            CrashKind::ServiceCrash => "Service crash",
+        }
+    }
+}
+
+impl CrashEvent {
+    /// Reads the OOM kills, segfaults and failed services logged since
+    /// `since` from journald, which includes the kernel log, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::UnsupportedPlatform`] if `journalctl` isn't
+    /// installed, or an error if it fails, e.g. when the user can't read the
+    /// system journal.
+    pub fn fetch(since: DateTime<Utc>) -> CoreResult<Vec<Self>> {
+        let output = run_with_timeout(
+            Command::new("journalctl").args([
+                "--since",
+                &format!("@{}", since.timestamp()),
+                "--grep",
+                CRASH_LOG_PATTERN,
+                "--output",
+                "json",
+                "--no-pager",
+                "--quiet",
+            ]),
+            Duration::from_millis(JOURNALCTL_TIMEOUT_MS),
+        )
+        .map_err(|err| {
+            if err.is_not_found() {
+                CoreError::unsupported("journalctl is not installed")
+            } else {
+                err
+            }
+        })?;
+        Ok(parse_journal(&output))
+    }
+
+    /// Classifies a log message, returning `None` for unrelated messages
+    pub fn from_message(timestamp: DateTime<Utc>, message: &str) -> Option<Self> {
+        let (kind, subject) = if let Some((_, killed)) = message.split_once("Killed process ") {
+            // "Out of memory: Killed process 1234 (stress) total-vm:..."
+            let name = killed.split_once('(')?.1.split_once(')')?.0;
+            (CrashKind::OomKill, name)
+        } else if let Some((process, _)) = message.split_once(": segfault at ") {
+            // "stress[1234]: segfault at 0 ip ..."
+            let name = process.rsplit_once('[').map_or(process, |(name, _)| name);
+            (CrashKind::Segfault, name)
+        } else if let Some((unit, _)) = message.split_once(": Failed with result ") {
+            (CrashKind::ServiceCrash, unit)
+        } else {
+            return None;
+        };
+        Some(Self {
+            timestamp,
+            kind,
+            subject: subject.trim().to_string(),
+            message: message.trim().to_string(),
+        })
+    }
+}
+
+/// Parses `journalctl --output json` lines into crash events
+pub fn parse_journal(output: &str) -> Vec<CrashEvent> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let entry: Value = serde_json::from_str(line).ok()?;
+            // the realtime timestamp is in microseconds since the epoch
+            let micros: i64 = entry.get("__REALTIME_TIMESTAMP")?.as_str()?.parse().ok()?;
+            let timestamp = DateTime::from_timestamp_micros(micros)?;
+            CrashEvent::from_message(timestamp, entry.get("MESSAGE")?.as_str()?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_message_classifies_failures() {
+        let now = Utc::now();
+        let oom = CrashEvent::from_message(
+            now,
+            "Out of memory: Killed process 4242 (stress) total-vm:1048576kB",
+        )
+        .unwrap();
+        assert_eq!(oom.kind, CrashKind::OomKill);
+        assert_eq!(oom.subject, "stress");
+
+        let segfault = CrashEvent::from_message(
+            now,
+            "my app[77]: segfault at 0 ip 000055d5 sp 00007ffc error 4 in app",
+        )
+        .unwrap();
+        assert_eq!(segfault.kind, CrashKind::Segfault);
+        assert_eq!(segfault.subject, "my app");
+
+        let service =
+            CrashEvent::from_message(now, "nginx.service: Failed with result 'core-dump'.")
+                .unwrap();
+        assert_eq!(service.kind, CrashKind::ServiceCrash);
+        assert_eq!(service.subject, "nginx.service");
+
+        assert!(CrashEvent::from_message(now, "Started nginx.service.").is_none());
+    }
+
+    #[test]
+    fn test_parse_journal() {
+        let output = concat!(
+            r#"{"__REALTIME_TIMESTAMP":"1700000000000000","MESSAGE":"Out of memory: Killed process 1 (a)"}"#,
+            "\n",
+            r#"{"__REALTIME_TIMESTAMP":"1700000001000000","MESSAGE":[104,105]}"#,
+            "\n",
+            "not json\n",
+        );
+        let events = parse_journal(output);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].timestamp.timestamp(), 1_700_000_000);
+    }
+}
@@ -0,0 +1,27 @@
+//! Crash event types
+
+use chrono::{DateTime, Utc};
+
+/// Kind of failure found in the logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashKind {
+    /// A process killed by the kernel OOM killer
+    OomKill,
+    /// A process killed for an invalid memory access
+    Segfault,
+    /// A systemd service that stopped with a failure result
+    ServiceCrash,
+}
+
+/// Failure of a process or service reported in the kernel log or journald
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashEvent {
+    /// When the failure was logged
+    pub timestamp: DateTime<Utc>,
+    /// What kind of failure it was
+    pub kind: CrashKind,
+    /// Process name, or unit name for service crashes
+    pub subject: String,
+    /// Log message the event was read from
+    pub message: String,
+}
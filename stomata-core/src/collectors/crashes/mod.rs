@@ -0,0 +1,6 @@
+//! OOM kills, segfaults and service crashes from the journal
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{CrashEvent, CrashKind};
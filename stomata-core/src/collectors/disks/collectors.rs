@@ -0,0 +1,228 @@
+//! Reading disk counters from `/proc/diskstats`
+
+use std::{collections::HashMap, fs, path::Path, time::Instant};
+
+use chrono::Utc;
+
+#[cfg(feature = "smart")]
+use std::time::Duration;
+
+#[cfg(feature = "smart")]
+use crate::{
+    collectors::disks::smart::{SmartHealth, is_physical},
+    constants::SMART_REFRESH_SECS,
+};
+use crate::{
+    collectors::{
+        backend::SystemBackend,
+        disks::metrics::{DiskCollector, DiskCounters, DiskIo, DiskMetrics},
+    },
+    constants::{DISKSTATS_PATH, SECTOR_SIZE, SYS_BLOCK_DIR},
+};
+
+impl DiskCollector {
+    /// Reads the disk counters and computes the activity since the
+    /// previous fetch, along with the mounts of `system`.
+    ///
+    /// Never fails; when `/proc/diskstats` can't be read the error is
+    /// returned in `DiskMetrics::error` with an empty disk list.
+    pub fn fetch(&mut self, system: &dyn SystemBackend) -> DiskMetrics {
+        let mounts = self.mounts.fetch(system);
+        let stats = match fs::read_to_string(DISKSTATS_PATH) {
+            Ok(stats) => stats,
+            Err(err) => {
+                tracing::warn!("failed to read {DISKSTATS_PATH}: {err}");
+                return DiskMetrics {
+                    timestamp: Utc::now(),
+                    mounts,
+                    error: Some(format!("failed to read {DISKSTATS_PATH}: {err}")),
+                    ..Default::default()
+                };
+            }
+        };
+        // partitions have no entry in /sys/block
+        let counters = parse_diskstats(&stats)
+            .into_iter()
+            .filter(|(name, _)| {
+                !name.starts_with("loop")
+                    && !name.starts_with("ram")
+                    && Path::new(SYS_BLOCK_DIR).join(name).exists()
+            })
+            .collect::<HashMap<_, _>>();
+        #[cfg(feature = "smart")]
+        self.refresh_smart(counters.keys());
+        DiskMetrics {
+            timestamp: Utc::now(),
+            disks: self.update(counters, Instant::now()),
+            mounts,
+            #[cfg(feature = "smart")]
+            smart: self.smart.clone(),
+            error: None,
+        }
+    }
+
+    /// Reads the S.M.A.R.T. health of the physical disks among `disks`
+    /// when it is older than `SMART_REFRESH_SECS`
+    #[cfg(feature = "smart")]
+    fn refresh_smart<'a>(&mut self, disks: impl Iterator<Item = &'a String>) {
+        let stale = self.smart_fetched_at.is_none_or(|fetched_at| {
+            fetched_at.elapsed() >= Duration::from_secs(SMART_REFRESH_SECS)
+        });
+        if !stale {
+            return;
+        }
+        self.smart = disks
+            .filter(|disk| is_physical(disk))
+            .map(|disk| (disk.clone(), SmartHealth::fetch(disk)))
+            .collect();
+        self.smart_fetched_at = Some(Instant::now());
+    }
+
+    /// Computes the activity of every disk from the counters taken at
+    /// `now`, and keeps them for the next update. Disks seen for the first
+    /// time are left out.
+    pub fn update(&mut self, counters: HashMap<String, DiskCounters>, now: Instant) -> Vec<DiskIo> {
+        let elapsed_ms = self
+            .previous_at
+            .map(|previous| now.duration_since(previous).as_secs_f64() * 1000.0)
+            .filter(|elapsed| *elapsed > 0.0);
+
+        let mut disks: Vec<DiskIo> = match elapsed_ms {
+            Some(elapsed_ms) => counters
+                .iter()
+                .filter_map(|(name, current)| {
+                    let previous = self.previous.get(name)?;
+                    Some(disk_io(name, previous, current, elapsed_ms))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        disks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.previous = counters;
+        self.previous_at = Some(now);
+        disks
+    }
+}
+
+/// Activity between two samples of the counters `elapsed_ms` apart.
+/// Counters that went backwards count as no activity.
+fn disk_io(name: &str, previous: &DiskCounters, current: &DiskCounters, elapsed_ms: f64) -> DiskIo {
+    let delta = |current: u64, previous: u64| current.saturating_sub(previous) as f64;
+    let per_sec = |value: f64| value / elapsed_ms * 1000.0;
+    let average = |time: f64, count: f64| (count > 0.0).then(|| time / count);
+
+    let reads = delta(current.reads, previous.reads);
+    let writes = delta(current.writes, previous.writes);
+    DiskIo {
+        name: name.to_string(),
+        reads_per_sec: per_sec(reads),
+        writes_per_sec: per_sec(writes),
+        read_bytes_per_sec: per_sec(
+            delta(current.sectors_read, previous.sectors_read) * SECTOR_SIZE,
+        ),
+        write_bytes_per_sec: per_sec(
+            delta(current.sectors_written, previous.sectors_written) * SECTOR_SIZE,
+        ),
+        read_await_ms: average(delta(current.read_ms, previous.read_ms), reads),
+        write_await_ms: average(delta(current.write_ms, previous.write_ms), writes),
+        queue_depth: delta(current.weighted_io_ms, previous.weighted_io_ms) / elapsed_ms,
+        in_flight: current.in_flight,
+        utilization: (delta(current.io_ms, previous.io_ms) / elapsed_ms * 100.0).min(100.0),
+    }
+}
+
+impl DiskIo {
+    /// Average time a request took, reads and writes together
+    pub fn await_ms(&self) -> Option<f64> {
+        match (self.read_await_ms, self.write_await_ms) {
+            (Some(read), Some(write)) => {
+                let total = self.reads_per_sec + self.writes_per_sec;
+                Some((read * self.reads_per_sec + write * self.writes_per_sec) / total)
+            }
+            (read, write) => read.or(write),
+        }
+    }
+}
+
+/// Parses `/proc/diskstats` into the counters of every device
+pub fn parse_diskstats(stats: &str) -> HashMap<String, DiskCounters> {
+    stats
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // major, minor and name come before the counters
+            let counter = |index: usize| -> Option<u64> { fields.get(index + 2)?.parse().ok() };
+            Some((
+                fields.get(2)?.to_string(),
+                DiskCounters {
+                    reads: counter(1)?,
+                    sectors_read: counter(3)?,
+                    read_ms: counter(4)?,
+                    writes: counter(5)?,
+                    sectors_written: counter(7)?,
+                    write_ms: counter(8)?,
+                    in_flight: counter(9)?,
+                    io_ms: counter(10)?,
+                    weighted_io_ms: counter(11)?,
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_diskstats() {
+        let stats = " 259       0 nvme0n1 1000 10 80000 500 2000 20 160000 4000 3 6000 9000 0 0 0 0\n   7       0 loop0 5 0 10 0 0 0 0 0 0 0 0\n";
+        let counters = parse_diskstats(stats);
+        assert_eq!(counters.len(), 2);
+        let nvme = counters["nvme0n1"];
+        assert_eq!(nvme.reads, 1000);
+        assert_eq!(nvme.sectors_read, 80000);
+        assert_eq!(nvme.write_ms, 4000);
+        assert_eq!(nvme.in_flight, 3);
+        assert_eq!(nvme.weighted_io_ms, 9000);
+    }
+
+    #[test]
+    fn test_update_computes_latency_and_queue_depth() {
+        let start = Instant::now();
+        let mut collector = DiskCollector::default();
+        let sample = |reads, read_ms, writes, write_ms, io_ms, weighted_io_ms| {
+            HashMap::from([(
+                "sda".to_string(),
+                DiskCounters {
+                    reads,
+                    sectors_read: reads * 8,
+                    read_ms,
+                    writes,
+                    sectors_written: 0,
+                    write_ms,
+                    in_flight: 2,
+                    io_ms,
+                    weighted_io_ms,
+                },
+            )])
+        };
+        assert!(collector.update(sample(0, 0, 0, 0, 0, 0), start).is_empty());
+
+        let disks = collector.update(
+            sample(100, 200, 50, 500, 500, 2000),
+            start + Duration::from_secs(1),
+        );
+        let sda = &disks[0];
+        assert_eq!(sda.reads_per_sec, 100.0);
+        assert_eq!(sda.read_bytes_per_sec, 100.0 * 8.0 * 512.0);
+        assert_eq!(sda.read_await_ms, Some(2.0));
+        assert_eq!(sda.write_await_ms, Some(10.0));
+        assert_eq!(sda.await_ms(), Some(700.0 / 150.0));
+        assert_eq!(sda.queue_depth, 2.0);
+        assert_eq!(sda.utilization, 50.0);
+    }
+}
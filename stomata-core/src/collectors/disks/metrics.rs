@@ -0,0 +1,91 @@
+//! Disk I/O types
+
+use std::{collections::HashMap, time::Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::collectors::disks::mounts::{Mount, MountWatcher};
+#[cfg(feature = "smart")]
+use crate::collectors::disks::smart::SmartHealth;
+
+/// Cumulative counters of a block device from `/proc/diskstats`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiskCounters {
+    /// Completed reads
+    pub reads: u64,
+    /// Sectors read, 512 bytes each
+    pub sectors_read: u64,
+    /// Milliseconds spent on reads
+    pub read_ms: u64,
+    /// Completed writes
+    pub writes: u64,
+    /// Sectors written, 512 bytes each
+    pub sectors_written: u64,
+    /// Milliseconds spent on writes
+    pub write_ms: u64,
+    /// Requests issued to the device and not completed yet
+    pub in_flight: u64,
+    /// Milliseconds the device had at least one request in flight
+    pub io_ms: u64,
+    /// Milliseconds spent on requests weighted by the number in flight
+    pub weighted_io_ms: u64,
+}
+
+/// I/O activity of a block device since the previous fetch, computed like
+/// `iostat -x`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiskIo {
+    /// Device name, e.g. `nvme0n1`
+    pub name: String,
+    /// Completed reads per second
+    pub reads_per_sec: f64,
+    /// Completed writes per second
+    pub writes_per_sec: f64,
+    /// Bytes read per second
+    pub read_bytes_per_sec: f64,
+    /// Bytes written per second
+    pub write_bytes_per_sec: f64,
+    /// Average time a read took, queueing included, in milliseconds.
+    /// `None` when no read completed.
+    pub read_await_ms: Option<f64>,
+    /// Average time a write took, queueing included, in milliseconds.
+    /// `None` when no write completed.
+    pub write_await_ms: Option<f64>,
+    /// Average number of requests queued or in flight
+    pub queue_depth: f64,
+    /// Requests in flight at the time of the fetch
+    pub in_flight: u64,
+    /// Share of the time the device was busy, in percent
+    pub utilization: f64,
+}
+
+/// Disk I/O and mounted filesystems at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct DiskMetrics {
+    /// When the counters were read
+    pub timestamp: DateTime<Utc>,
+    /// Whole disks sorted by name, without partitions, loop and ram devices.
+    /// Empty on the first fetch, which only sets the baseline.
+    pub disks: Vec<DiskIo>,
+    /// Mounted filesystems, read again on every fetch
+    pub mounts: Vec<Mount>,
+    /// S.M.A.R.T. health per physical disk name
+    #[cfg(feature = "smart")]
+    pub smart: HashMap<String, SmartHealth>,
+    /// Why `/proc/diskstats` couldn't be read, e.g. on non-Linux platforms
+    pub error: Option<String>,
+}
+
+/// Reads `/proc/diskstats` and keeps the previous counters of every disk to
+/// turn them into rates, and watches the mount table
+#[derive(Debug, Default)]
+pub struct DiskCollector {
+    pub(crate) previous: HashMap<String, DiskCounters>,
+    pub(crate) previous_at: Option<Instant>,
+    /// Mount table of the previous fetch, to flag new mounts
+    pub mounts: MountWatcher,
+    #[cfg(feature = "smart")]
+    pub(crate) smart: HashMap<String, SmartHealth>,
+    #[cfg(feature = "smart")]
+    pub(crate) smart_fetched_at: Option<Instant>,
+}
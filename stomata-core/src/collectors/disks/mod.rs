@@ -0,0 +1,11 @@
+//! Disk I/O rates, mounted filesystems with their inode usage and SMART
+//! health
+
+pub mod collectors;
+pub mod metrics;
+pub mod mounts;
+#[cfg(feature = "smart")]
+pub mod smart;
+
+pub use metrics::{DiskCollector, DiskIo, DiskMetrics};
+pub use mounts::{InodeUsage, Mount, MountFilter};
@@ -0,0 +1,307 @@
+//! Mounted filesystems, their inode usage and mount table changes
+//!
+//! The mount list is read again on every fetch, so media plugged in while
+//! the app runs show up without a restart. Mounts that appeared since the
+//! first fetch are flagged as new for a while so they can be highlighted.
+//! A filesystem can run out of inodes (lots of small files) long before it
+//! runs out of space, so the inode counts are read alongside the sizes.
+//! A [`MountFilter`] hides mounts nobody wants to watch, such as container
+//! overlays or snap images.
+
+use std::{
+    collections::{HashMap, HashSet},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    collectors::{backend::SystemBackend, command::run_with_timeout},
+    constants::{NEW_MOUNT_HIGHLIGHT_SECS, UNMOUNT_TIMEOUT_MS},
+    errors::CoreResult,
+};
+
+/// A mounted filesystem
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mount {
+    /// Block device, e.g. `/dev/sdb1`
+    pub device: String,
+    /// Where the filesystem is mounted
+    pub mount_point: String,
+    /// File system type, e.g. `ext4`
+    pub file_system: String,
+    /// Whether the device is removable media such as a USB stick
+    pub removable: bool,
+    /// Size of the filesystem in bytes
+    pub total_bytes: u64,
+    /// Space available to unprivileged users in bytes
+    pub available_bytes: u64,
+    /// Inode counts, `None` for filesystems without a fixed inode table
+    /// (e.g. btrfs) or when they can't be read
+    pub inodes: Option<InodeUsage>,
+    /// Whether the filesystem was mounted recently while the app was running
+    pub is_new: bool,
+}
+
+/// Inodes of a filesystem, from `statvfs`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InodeUsage {
+    /// Inodes of the filesystem
+    pub total: u64,
+    /// Inodes not in use
+    pub free: u64,
+}
+
+impl InodeUsage {
+    /// Reads the inode counts of the filesystem mounted at `mount_point`.
+    /// Filesystems reporting no inodes allocate them dynamically and can't
+    /// run out, so they have none.
+    #[cfg(unix)]
+    pub fn fetch(mount_point: &str) -> Option<Self> {
+        let path = std::ffi::CString::new(mount_point).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 || stat.f_files == 0 {
+            return None;
+        }
+        Some(Self {
+            total: stat.f_files as u64,
+            free: stat.f_ffree as u64,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn fetch(_mount_point: &str) -> Option<Self> {
+        None
+    }
+
+    /// Inodes in use
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.free)
+    }
+
+    /// Inodes in use in percent of the total, like `df -i`
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used() as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// Include and exclude patterns for mounts.
+///
+/// A pattern matches a mount when it matches its mount point, its device or
+/// its filesystem type, so `/snap/*`, `/dev/loop*` and `overlay` all work.
+/// `*` stands for any run of characters, everything else must match
+/// exactly.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MountFilter {
+    /// Only mounts matching one of these patterns are kept; every mount
+    /// when empty
+    pub include: Vec<String>,
+    /// Mounts matching one of these patterns are dropped, even when
+    /// included
+    pub exclude: Vec<String>,
+}
+
+impl MountFilter {
+    /// Whether `mount` passes the filter
+    pub fn allows(&self, mount: &Mount) -> bool {
+        let matches = |pattern: &String| {
+            [&mount.mount_point, &mount.device, &mount.file_system]
+                .into_iter()
+                .any(|field| wildcard_match(pattern, field))
+        };
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// Matches `text` against `pattern`, where `*` matches any run of
+/// characters, including none
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // without a `*` the only part is the whole pattern
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Tracks the mount table between fetches to flag new mounts
+#[derive(Debug, Default)]
+pub struct MountWatcher {
+    /// Mounts left out of every fetch
+    pub filter: MountFilter,
+    /// Mount points of the previous fetch, `None` before the first one
+    known: Option<HashSet<String>>,
+    /// When each new mount point was first seen
+    mounted_at: HashMap<String, Instant>,
+}
+
+impl MountWatcher {
+    /// Lists the mounted filesystems of `system` passing the filter,
+    /// sorted by mount point
+    pub fn fetch(&mut self, system: &dyn SystemBackend) -> Vec<Mount> {
+        let mounts = system
+            .mounts()
+            .into_iter()
+            .filter(|mount| self.filter.allows(mount))
+            .map(|mount| Mount {
+                inodes: InodeUsage::fetch(&mount.mount_point),
+                ..mount
+            })
+            .collect();
+        self.update(mounts, Instant::now())
+    }
+
+    /// Flags the mounts that appeared since the first update and were
+    /// first seen less than `NEW_MOUNT_HIGHLIGHT_SECS` before `now`
+    pub fn update(&mut self, mut mounts: Vec<Mount>, now: Instant) -> Vec<Mount> {
+        let current: HashSet<String> = mounts
+            .iter()
+            .map(|mount| mount.mount_point.clone())
+            .collect();
+        if let Some(known) = &self.known {
+            for mount_point in current.difference(known) {
+                self.mounted_at.insert(mount_point.clone(), now);
+            }
+        }
+        let highlight = Duration::from_secs(NEW_MOUNT_HIGHLIGHT_SECS);
+        self.mounted_at.retain(|mount_point, mounted_at| {
+            current.contains(mount_point) && now.duration_since(*mounted_at) < highlight
+        });
+
+        for mount in &mut mounts {
+            mount.is_new = self.mounted_at.contains_key(&mount.mount_point);
+        }
+        mounts.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        self.known = Some(current);
+        mounts
+    }
+}
+
+impl Mount {
+    /// Unmounts the filesystem through udisks, which lets users unmount
+    /// removable media without root, falling back to `umount`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filesystem is busy or the user isn't
+    /// allowed to unmount it.
+    pub fn unmount(&self) -> CoreResult<()> {
+        let timeout = Duration::from_millis(UNMOUNT_TIMEOUT_MS);
+        match run_with_timeout(
+            Command::new("udisksctl").args(["unmount", "--block-device", &self.device]),
+            timeout,
+        ) {
+            Ok(_) => Ok(()),
+            Err(err) if err.is_not_found() => {
+                run_with_timeout(Command::new("umount").arg(&self.mount_point), timeout)
+                    .map(|_| ())
+                    .map_err(|err| err.context(format!("umount {} failed", self.mount_point)))
+            }
+            Err(err) => Err(err.context(format!("udisksctl unmount {} failed", self.device))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mount(mount_point: &str) -> Mount {
+        Mount {
+            mount_point: mount_point.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_update_flags_new_mounts() {
+        let start = Instant::now();
+        let mut watcher = MountWatcher::default();
+        // mounts present at startup are not new
+        let mounts = watcher.update(vec![mount("/")], start);
+        assert!(!mounts[0].is_new);
+
+        let mounts = watcher.update(
+            vec![mount("/"), mount("/media/usb")],
+            start + Duration::from_secs(1),
+        );
+        assert!(!mounts[0].is_new);
+        assert!(mounts[1].is_new);
+
+        let later = start + Duration::from_secs(NEW_MOUNT_HIGHLIGHT_SECS + 2);
+        let mounts = watcher.update(vec![mount("/"), mount("/media/usb")], later);
+        assert!(!mounts[1].is_new);
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("/snap/*", "/snap/core22/1380"));
+        assert!(wildcard_match("/dev/loop*", "/dev/loop12"));
+        assert!(wildcard_match("overlay", "overlay"));
+        assert!(!wildcard_match("overlay", "overlayfs"));
+        assert!(wildcard_match(
+            "*/docker/*",
+            "/var/lib/docker/overlay2/abc/merged"
+        ));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("/boot/*", "/boot"));
+        // the last part can't reuse characters of the first
+        assert!(!wildcard_match("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn test_mount_filter() {
+        let snap = Mount {
+            mount_point: "/snap/firefox/4033".to_string(),
+            device: "/dev/loop3".to_string(),
+            file_system: "ext4".to_string(),
+            ..Default::default()
+        };
+        let root = Mount {
+            mount_point: "/".to_string(),
+            device: "/dev/nvme0n1p2".to_string(),
+            file_system: "ext4".to_string(),
+            ..Default::default()
+        };
+        let exclude_loops = MountFilter {
+            exclude: vec!["/dev/loop*".to_string()],
+            ..Default::default()
+        };
+        assert!(!exclude_loops.allows(&snap));
+        assert!(exclude_loops.allows(&root));
+
+        let only_ext4 = MountFilter {
+            include: vec!["ext4".to_string()],
+            exclude: vec!["/snap/*".to_string()],
+        };
+        assert!(only_ext4.allows(&root));
+        assert!(!only_ext4.allows(&snap));
+        assert!(MountFilter::default().allows(&snap));
+    }
+
+    #[test]
+    fn test_inode_percent() {
+        let inodes = InodeUsage {
+            total: 1_000_000,
+            free: 50_000,
+        };
+        assert_eq!(inodes.used(), 950_000);
+        assert_eq!(inodes.percent(), 95.0);
+        assert_eq!(InodeUsage::default().percent(), 0.0);
+    }
+}
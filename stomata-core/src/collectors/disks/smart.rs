@@ -0,0 +1,219 @@
+//! S.M.A.R.T. health of physical disks
+//!
+//! Read with `smartctl --json`, which needs root for most devices. The
+//! attributes change slowly, so they are read again only every
+//! `SMART_REFRESH_SECS` seconds rather than on every fetch.
+
+use std::{path::Path, process::Command, time::Duration};
+
+use serde::Deserialize;
+
+use crate::{
+    collectors::command::run_with_timeout_status,
+    constants::{
+        SMART_REALLOCATED_WARNING, SMART_TEMPERATURE_WARNING_C, SMARTCTL_TIMEOUT_MS, SYS_BLOCK_DIR,
+    },
+    errors::{CoreError, CoreResult},
+};
+
+/// Reallocated sectors count of ATA disks
+const REALLOCATED_SECTOR_ATTRIBUTE: u64 = 5;
+
+/// smartctl exit status bits meaning no data was read: the command line
+/// didn't parse or the device couldn't be opened
+const SMARTCTL_FATAL_BITS: i32 = 0b11;
+
+/// Health summary of a disk
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SmartHealth {
+    /// Result of the overall health self-assessment
+    pub passed: Option<bool>,
+    /// Current drive temperature in °C
+    pub temperature_c: Option<i64>,
+    /// Sectors remapped after read or write errors, only reported by ATA
+    /// disks
+    pub reallocated_sectors: Option<u64>,
+    /// Hours the drive has been powered on
+    pub power_on_hours: Option<u64>,
+    /// Why the attributes couldn't be read, e.g. missing permissions
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SmartctlOutput {
+    smartctl: Option<SmartctlInfo>,
+    smart_status: Option<SmartStatus>,
+    temperature: Option<Temperature>,
+    power_on_time: Option<PowerOnTime>,
+    ata_smart_attributes: Option<AtaAttributes>,
+}
+
+#[derive(Deserialize)]
+struct SmartctlInfo {
+    #[serde(default)]
+    messages: Vec<SmartctlMessage>,
+}
+
+#[derive(Deserialize)]
+struct SmartctlMessage {
+    string: String,
+}
+
+#[derive(Deserialize)]
+struct SmartStatus {
+    passed: bool,
+}
+
+#[derive(Deserialize)]
+struct Temperature {
+    current: i64,
+}
+
+#[derive(Deserialize)]
+struct PowerOnTime {
+    hours: u64,
+}
+
+#[derive(Deserialize)]
+struct AtaAttributes {
+    table: Vec<AtaAttribute>,
+}
+
+#[derive(Deserialize)]
+struct AtaAttribute {
+    id: u64,
+    raw: AtaRawValue,
+}
+
+#[derive(Deserialize)]
+struct AtaRawValue {
+    value: u64,
+}
+
+impl SmartHealth {
+    /// Reads the health of `/dev/<disk>`.
+    ///
+    /// Never fails; when smartctl can't be run or can't open the device
+    /// the error is carried in `error`.
+    pub fn fetch(disk: &str) -> Self {
+        let device = format!("/dev/{disk}");
+        let output = run_with_timeout_status(
+            Command::new("smartctl").args(["--json", "-H", "-A", &device]),
+            Duration::from_millis(SMARTCTL_TIMEOUT_MS),
+        );
+        let result = match output {
+            Ok((status, stdout, _)) => parse_smartctl(&stdout, status.code().unwrap_or(0)),
+            Err(err) if err.is_not_found() => {
+                Err(CoreError::unsupported("smartctl is not installed"))
+            }
+            Err(err) => Err(err.context(format!("smartctl {device} failed"))),
+        };
+        result.unwrap_or_else(|err| Self {
+            error: Some(err.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Thresholds the disk exceeds, empty for a healthy disk
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.passed == Some(false) {
+            warnings.push("health check failed".to_string());
+        }
+        if let Some(sectors) = self
+            .reallocated_sectors
+            .filter(|sectors| *sectors > SMART_REALLOCATED_WARNING)
+        {
+            warnings.push(format!("{sectors} reallocated sectors"));
+        }
+        if let Some(temperature) = self
+            .temperature_c
+            .filter(|temperature| *temperature >= SMART_TEMPERATURE_WARNING_C)
+        {
+            warnings.push(format!("{temperature} °C"));
+        }
+        warnings
+    }
+}
+
+/// Whether `/dev/<disk>` is a physical disk that can report S.M.A.R.T.
+/// data. Device mapper, RAID and zram devices have no `device` link in
+/// sysfs, and optical drives are left out.
+pub fn is_physical(disk: &str) -> bool {
+    !disk.starts_with("sr") && Path::new(SYS_BLOCK_DIR).join(disk).join("device").exists()
+}
+
+/// Parses `smartctl --json` output that exited with `exit_status`.
+///
+/// smartctl sets exit status bits for failing disks while still printing
+/// their attributes, so only the bits meaning nothing was read are
+/// treated as errors.
+///
+/// # Errors
+///
+/// Returns the first message smartctl printed when no data was read, or
+/// an error if the output isn't valid JSON.
+pub fn parse_smartctl(json: &str, exit_status: i32) -> CoreResult<SmartHealth> {
+    let output: SmartctlOutput = serde_json::from_str(json)
+        .map_err(|err| CoreError::collection(format!("unexpected smartctl output: {err}")))?;
+    if exit_status & SMARTCTL_FATAL_BITS != 0 {
+        let message = output
+            .smartctl
+            .and_then(|info| info.messages.into_iter().next())
+            .map(|message| message.string)
+            .unwrap_or_else(|| format!("smartctl exited with {exit_status}"));
+        return Err(CoreError::collection(message));
+    }
+    Ok(SmartHealth {
+        passed: output.smart_status.map(|status| status.passed),
+        temperature_c: output.temperature.map(|temperature| temperature.current),
+        reallocated_sectors: output.ata_smart_attributes.and_then(|attributes| {
+            attributes
+                .table
+                .into_iter()
+                .find(|attribute| attribute.id == REALLOCATED_SECTOR_ATTRIBUTE)
+                .map(|attribute| attribute.raw.value)
+        }),
+        power_on_hours: output.power_on_time.map(|time| time.hours),
+        error: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_smartctl() {
+        let ata = r#"{
+            "smartctl": {"exit_status": 8, "messages": []},
+            "smart_status": {"passed": false},
+            "temperature": {"current": 41},
+            "power_on_time": {"hours": 21034},
+            "ata_smart_attributes": {"table": [
+                {"id": 1, "name": "Raw_Read_Error_Rate", "raw": {"value": 0, "string": "0"}},
+                {"id": 5, "name": "Reallocated_Sector_Ct", "raw": {"value": 12, "string": "12"}}
+            ]}
+        }"#;
+        let health = parse_smartctl(ata, 8).unwrap();
+        assert_eq!(health.passed, Some(false));
+        assert_eq!(health.temperature_c, Some(41));
+        assert_eq!(health.power_on_hours, Some(21034));
+        assert_eq!(health.reallocated_sectors, Some(12));
+        assert_eq!(
+            health.warnings(),
+            vec!["health check failed", "12 reallocated sectors"]
+        );
+
+        let nvme = r#"{"smart_status": {"passed": true}, "temperature": {"current": 35}}"#;
+        let health = parse_smartctl(nvme, 0).unwrap();
+        assert_eq!(health.reallocated_sectors, None);
+        assert!(health.warnings().is_empty());
+
+        let denied = r#"{"smartctl": {"exit_status": 2, "messages": [
+            {"string": "Smartctl open device: /dev/sda failed: Permission denied", "severity": "error"}
+        ]}}"#;
+        let err = parse_smartctl(denied, 2).unwrap_err();
+        assert!(err.to_string().contains("Permission denied"));
+    }
+}
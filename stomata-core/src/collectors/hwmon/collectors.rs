@@ -0,0 +1,184 @@
+//! Reading hwmon chips from sysfs
+
+use std::{fs, path::Path};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::hwmon::metrics::{Fan, HwmonChip, HwmonMetrics, PwmMode, TemperatureSensor},
+    constants::HWMON_DIR,
+};
+
+impl HwmonMetrics {
+    /// Reads the temperatures and fans of every hwmon chip.
+    ///
+    /// Machines without hwmon, e.g. most VMs, yield no chips.
+    pub fn fetch() -> Self {
+        let mut chips: Vec<HwmonChip> = fs::read_dir(HWMON_DIR)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| HwmonChip::read(&entry.path()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        chips.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            timestamp: Utc::now(),
+            chips,
+        }
+    }
+
+    /// Hottest temperature of all chips
+    pub fn hottest(&self) -> Option<f64> {
+        self.chips
+            .iter()
+            .flat_map(|chip| &chip.temperatures)
+            .map(|sensor| sensor.celsius)
+            .reduce(f64::max)
+    }
+}
+
+impl HwmonChip {
+    /// Reads the chip in the hwmon directory `dir`, `None` when it has
+    /// neither temperatures nor fans
+    pub fn read(dir: &Path) -> Option<Self> {
+        let read = |file: String| {
+            fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        let read_number = |file: String| read(file).and_then(|value| value.parse::<i64>().ok());
+        let indices = |prefix: &str| {
+            let mut indices: Vec<u32> = fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter_map(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()?
+                        .strip_prefix(prefix)?
+                        .strip_suffix("_input")?
+                        .parse()
+                        .ok()
+                })
+                .collect();
+            indices.sort();
+            indices
+        };
+        // values are in millidegrees
+        let celsius = |file: String| read_number(file).map(|value| value as f64 / 1000.0);
+
+        let temperatures: Vec<TemperatureSensor> = indices("temp")
+            .into_iter()
+            .filter_map(|index| {
+                Some(TemperatureSensor {
+                    label: read(format!("temp{index}_label"))
+                        .unwrap_or_else(|| format!("temp{index}")),
+                    celsius: celsius(format!("temp{index}_input"))?,
+                    high: celsius(format!("temp{index}_max")),
+                    critical: celsius(format!("temp{index}_crit")),
+                })
+            })
+            .collect();
+        let fans: Vec<Fan> = indices("fan")
+            .into_iter()
+            .filter_map(|index| {
+                Some(Fan {
+                    label: read(format!("fan{index}_label"))
+                        .unwrap_or_else(|| format!("fan{index}")),
+                    rpm: read_number(format!("fan{index}_input"))?.max(0) as u64,
+                    pwm: read_number(format!("pwm{index}"))
+                        .map(|pwm| pwm.clamp(0, u8::MAX as i64) as u8),
+                    pwm_mode: read_number(format!("pwm{index}_enable")).map(|mode| match mode {
+                        0 => PwmMode::Full,
+                        1 => PwmMode::Manual,
+                        _ => PwmMode::Automatic,
+                    }),
+                })
+            })
+            .collect();
+
+        if temperatures.is_empty() && fans.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: read("name".to_string()).unwrap_or_else(|| {
+                dir.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            }),
+            temperatures,
+            fans,
+        })
+    }
+
+    /// Hottest temperature of the chip, which its fans usually follow
+    pub fn hottest(&self) -> Option<f64> {
+        self.temperatures
+            .iter()
+            .map(|sensor| sensor.celsius)
+            .reduce(f64::max)
+    }
+}
+
+impl Fan {
+    /// Duty cycle in percent
+    pub fn pwm_percent(&self) -> Option<f64> {
+        self.pwm.map(|pwm| pwm as f64 / u8::MAX as f64 * 100.0)
+    }
+}
+
+impl PwmMode {
+    /// Lowercase name of the mode
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PwmMode::Full => "full",
+            PwmMode::Manual => "manual",
+            PwmMode::Automatic => "auto",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_chip() {
+        let dir = std::env::temp_dir().join(format!("stomata-hwmon-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (file, value) in [
+            ("name", "nct6775\n"),
+            ("temp1_input", "45000\n"),
+            ("temp1_label", "SYSTIN\n"),
+            ("temp2_input", "61500\n"),
+            ("temp2_crit", "100000\n"),
+            ("fan2_input", "1250\n"),
+            ("pwm2", "128\n"),
+            ("pwm2_enable", "5\n"),
+        ] {
+            fs::write(dir.join(file), value).unwrap();
+        }
+
+        let chip = HwmonChip::read(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(chip.name, "nct6775");
+        assert_eq!(chip.temperatures.len(), 2);
+        assert_eq!(chip.temperatures[0].label, "SYSTIN");
+        assert_eq!(chip.temperatures[1].label, "temp2");
+        assert_eq!(chip.temperatures[1].critical, Some(100.0));
+        assert_eq!(chip.hottest(), Some(61.5));
+        assert_eq!(
+            chip.fans,
+            vec![Fan {
+                label: "fan2".to_string(),
+                rpm: 1250,
+                pwm: Some(128),
+                pwm_mode: Some(PwmMode::Automatic),
+            }]
+        );
+    }
+}
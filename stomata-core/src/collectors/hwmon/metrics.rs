@@ -0,0 +1,60 @@
+//! Sensor and fan types
+
+use chrono::{DateTime, Utc};
+
+/// A temperature input of a hwmon chip
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemperatureSensor {
+    /// Label from `tempN_label`, `tempN` when the driver has none
+    pub label: String,
+    /// Current reading
+    pub celsius: f64,
+    /// Temperature the driver considers high, from `tempN_max`
+    pub high: Option<f64>,
+    /// Temperature the hardware shuts down at, from `tempN_crit`
+    pub critical: Option<f64>,
+}
+
+/// How the PWM output driving a fan is controlled, from `pwmN_enable`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PwmMode {
+    /// Full speed, no control
+    Full,
+    /// Duty cycle set by software through `pwmN`
+    Manual,
+    /// Duty cycle set by the chip's own fan curve
+    Automatic,
+}
+
+/// A fan of a hwmon chip
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fan {
+    /// Label from `fanN_label`, `fanN` when the driver has none
+    pub label: String,
+    /// Current speed
+    pub rpm: u64,
+    /// Duty cycle of the matching `pwmN` output, 0 to 255
+    pub pwm: Option<u8>,
+    /// How the fan's PWM output is controlled
+    pub pwm_mode: Option<PwmMode>,
+}
+
+/// A hwmon chip from `/sys/class/hwmon` with its sensors
+#[derive(Debug, Clone, PartialEq)]
+pub struct HwmonChip {
+    /// Driver name, e.g. `coretemp`, `k10temp` or `nct6775`
+    pub name: String,
+    /// Temperature inputs
+    pub temperatures: Vec<TemperatureSensor>,
+    /// Fan inputs
+    pub fans: Vec<Fan>,
+}
+
+/// Temperatures and fans at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct HwmonMetrics {
+    /// When the sensors were read
+    pub timestamp: DateTime<Utc>,
+    /// Chips with at least one temperature or fan, sorted by name
+    pub chips: Vec<HwmonChip>,
+}
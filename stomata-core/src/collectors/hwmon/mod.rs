@@ -0,0 +1,6 @@
+//! Temperature sensors and fans from `/sys/class/hwmon`
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{Fan, HwmonChip, HwmonMetrics, PwmMode, TemperatureSensor};
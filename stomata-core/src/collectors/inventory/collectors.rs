@@ -0,0 +1,280 @@
+//! Collecting the inventory from sysinfo, sysfs and `dmidecode`
+
+use std::{process::Command, time::Duration};
+
+use serde_json::Value;
+use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, Networks, RefreshKind, System};
+
+use crate::{
+    collectors::{
+        command::run_with_timeout,
+        inventory::metrics::{
+            CpuInventory, Dimm, DiskInventory, GpuInventory, Inventory, MemoryInventory,
+            NicInventory,
+        },
+        pci::PciDevice,
+    },
+    constants::{DMIDECODE_TIMEOUT_MS, PCI_CLASS_DISPLAY},
+};
+
+impl Inventory {
+    /// Gathers the CPU, memory, disks, NICs and GPUs of the machine.
+    ///
+    /// Sources that aren't available on the platform, like `dmidecode` or
+    /// `/sys/bus/pci`, leave their part of the inventory empty instead of
+    /// failing the whole document.
+    pub fn fetch() -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_cpu(CpuRefreshKind::nothing().with_frequency())
+                .with_memory(MemoryRefreshKind::nothing().with_ram()),
+        );
+        let cpu = system
+            .cpus()
+            .first()
+            .map(|cpu| CpuInventory {
+                model: cpu.brand().trim().to_string(),
+                vendor: cpu.vendor_id().to_string(),
+                physical_cores: System::physical_core_count(),
+                logical_cores: system.cpus().len(),
+                frequency_mhz: cpu.frequency(),
+            })
+            .unwrap_or_default();
+
+        let disks = Disks::new_with_refreshed_list()
+            .iter()
+            .map(|disk| DiskInventory {
+                name: disk.name().to_string_lossy().to_string(),
+                mount_point: disk.mount_point().display().to_string(),
+                file_system: disk.file_system().to_string_lossy().to_string(),
+                kind: disk.kind().to_string(),
+                total_bytes: disk.total_space(),
+                removable: disk.is_removable(),
+            })
+            .collect();
+
+        let mut nics: Vec<NicInventory> = Networks::new_with_refreshed_list()
+            .iter()
+            .filter(|(_, data)| !data.mac_address().is_unspecified())
+            .map(|(name, data)| NicInventory {
+                name: name.clone(),
+                mac: data.mac_address().to_string(),
+            })
+            .collect();
+        nics.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let gpus = PciDevice::fetch()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|device| (device.class >> 16) as u8 == PCI_CLASS_DISPLAY)
+            .map(|device| GpuInventory {
+                address: device.address,
+                vendor: device.vendor_name,
+                model: device.device_name,
+                driver: device.driver,
+            })
+            .collect();
+
+        Self {
+            hostname: System::host_name(),
+            collected_at: chrono::Local::now().to_rfc3339(),
+            cpu,
+            memory: MemoryInventory {
+                total_bytes: system.total_memory(),
+                dimms: read_dimms(),
+            },
+            disks,
+            nics,
+            gpus,
+        }
+    }
+
+    /// Serializes the inventory as pretty printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Serializes the inventory as a YAML document with keys in
+    /// alphabetical order
+    pub fn to_yaml(&self) -> serde_json::Result<String> {
+        let mut out = String::from("---\n");
+        write_yaml(&serde_json::to_value(self)?, 0, &mut out);
+        Ok(out)
+    }
+}
+
+/// Reads the memory modules with `dmidecode`, which needs root to access
+/// the DMI tables. Returns an empty list when it can't be run.
+fn read_dimms() -> Vec<Dimm> {
+    run_with_timeout(
+        Command::new("dmidecode").args(["-t", "memory"]),
+        Duration::from_millis(DMIDECODE_TIMEOUT_MS),
+    )
+    .map(|output| parse_dmidecode_memory(&output))
+    .unwrap_or_default()
+}
+
+/// Parses the `Memory Device` sections of `dmidecode -t memory`, skipping
+/// empty slots.
+pub fn parse_dmidecode_memory(output: &str) -> Vec<Dimm> {
+    let mut dimms = Vec::new();
+    let mut current: Option<Dimm> = None;
+
+    for line in output.lines() {
+        if line.trim() == "Memory Device" {
+            dimms.extend(current.take());
+            current = Some(Dimm::default());
+            continue;
+        }
+        // a new handle ends the section
+        if line.starts_with("Handle ") {
+            dimms.extend(current.take());
+            continue;
+        }
+        let Some(dimm) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let known = |value: &str| {
+            (!value.is_empty() && !matches!(value, "Unknown" | "Not Specified" | "None"))
+                .then(|| value.to_string())
+        };
+        match key {
+            "Locator" => dimm.locator = value.to_string(),
+            "Size" => dimm.size = value.to_string(),
+            "Type" => dimm.kind = known(value),
+            "Speed" => dimm.speed = known(value),
+            "Manufacturer" => dimm.manufacturer = known(value),
+            "Part Number" => dimm.part_number = known(value),
+            "Serial Number" => dimm.serial_number = known(value),
+            _ => {}
+        }
+    }
+    dimms.extend(current);
+    dimms.retain(|dimm| !dimm.size.is_empty() && dimm.size != "No Module Installed");
+    dimms
+}
+
+/// Writes `value` as block style YAML indented by `indent` levels
+fn write_yaml(value: &Value, indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                out.push_str(&format!("{pad}{key}:"));
+                write_yaml_child(value, indent, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                out.push_str(&format!("{pad}-"));
+                match item {
+                    // nested collections start on the line after the dash
+                    Value::Object(map) if !map.is_empty() => {
+                        let mut nested = String::new();
+                        write_yaml(item, indent + 1, &mut nested);
+                        // pull the first key up next to the dash
+                        out.push(' ');
+                        out.push_str(nested.trim_start());
+                    }
+                    _ => write_yaml_child(item, indent, out),
+                }
+            }
+        }
+        scalar => out.push_str(&format!("{pad}{}\n", yaml_scalar(scalar))),
+    }
+}
+
+/// Writes the value of a mapping key or sequence item that follows a `:` or `-`
+fn write_yaml_child(value: &Value, indent: usize, out: &mut String) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            out.push('\n');
+            write_yaml(value, indent + 1, out);
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push('\n');
+            write_yaml(value, indent + 1, out);
+        }
+        scalar => out.push_str(&format!(" {}\n", yaml_scalar(scalar))),
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Number(number) => number.to_string(),
+        // JSON strings are valid YAML double quoted scalars
+        Value::String(text) => Value::String(text.clone()).to_string(),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DMIDECODE_OUTPUT: &str = "\
+# dmidecode 3.5
+Getting SMBIOS data from sysfs.
+
+Handle 0x0040, DMI type 17, 92 bytes
+Memory Device
+\tSize: 16 GB
+\tLocator: DIMM_A1
+\tType: DDR4
+\tSpeed: 3200 MT/s
+\tManufacturer: Samsung
+\tSerial Number: 12345678
+\tPart Number: M378A2G43AB3-CWE
+\tRank: 2
+
+Handle 0x0041, DMI type 17, 92 bytes
+Memory Device
+\tSize: No Module Installed
+\tLocator: DIMM_A2
+\tType: Unknown
+";
+
+    #[test]
+    fn test_parse_dmidecode_memory_skips_empty_slots() {
+        let dimms = parse_dmidecode_memory(DMIDECODE_OUTPUT);
+        assert_eq!(
+            dimms,
+            vec![Dimm {
+                locator: "DIMM_A1".to_string(),
+                size: "16 GB".to_string(),
+                kind: Some("DDR4".to_string()),
+                speed: Some("3200 MT/s".to_string()),
+                manufacturer: Some("Samsung".to_string()),
+                part_number: Some("M378A2G43AB3-CWE".to_string()),
+                serial_number: Some("12345678".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_to_yaml_nests_collections() {
+        let inventory = Inventory {
+            hostname: Some("box".to_string()),
+            collected_at: "2025-01-01T00:00:00+00:00".to_string(),
+            nics: vec![NicInventory {
+                name: "eth0".to_string(),
+                mac: "aa:bb:cc:dd:ee:ff".to_string(),
+            }],
+            ..Default::default()
+        };
+        let yaml = inventory.to_yaml().unwrap();
+        assert!(yaml.starts_with("---\ncollected_at: \"2025-01-01T00:00:00+00:00\"\n"));
+        assert!(yaml.contains("\ncpu:\n  frequency_mhz: 0\n"));
+        assert!(yaml.contains("\nhostname: \"box\"\n"));
+        assert!(yaml.contains("\n  dimms: []\n"));
+        assert!(yaml.contains("\nnics:\n  - mac: \"aa:bb:cc:dd:ee:ff\"\n    name: \"eth0\"\n"));
+        assert!(yaml.contains("\ngpus: []\n"));
+    }
+}
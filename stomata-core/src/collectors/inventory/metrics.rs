@@ -0,0 +1,105 @@
+//! Inventory types
+
+use serde::Serialize;
+
+/// Hardware inventory of the machine, meant for asset tracking
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Inventory {
+    /// Host name, `None` where the platform doesn't report one
+    pub hostname: Option<String>,
+    /// RFC 3339 time the inventory was gathered at
+    pub collected_at: String,
+    /// Processor model and core counts
+    pub cpu: CpuInventory,
+    /// Installed memory
+    pub memory: MemoryInventory,
+    /// Mounted disks
+    pub disks: Vec<DiskInventory>,
+    /// Network interfaces with a MAC address
+    pub nics: Vec<NicInventory>,
+    /// Display controllers found on the PCI bus
+    pub gpus: Vec<GpuInventory>,
+}
+
+/// Processor of the machine
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CpuInventory {
+    /// Brand string, e.g. `AMD Ryzen 7 5800X 8-Core Processor`
+    pub model: String,
+    /// Vendor id, e.g. `AuthenticAMD`
+    pub vendor: String,
+    /// `None` when the physical core count can't be determined
+    pub physical_cores: Option<usize>,
+    /// Hardware threads
+    pub logical_cores: usize,
+    /// Current frequency of the first core
+    pub frequency_mhz: u64,
+}
+
+/// Total memory and the installed modules
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryInventory {
+    /// Memory usable by the system
+    pub total_bytes: u64,
+    /// Installed memory modules, empty when `dmidecode` isn't available
+    /// or isn't permitted to read the DMI tables
+    pub dimms: Vec<Dimm>,
+}
+
+/// Installed memory module as reported by `dmidecode -t memory`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Dimm {
+    /// Slot label, e.g. `DIMM_A1`
+    pub locator: String,
+    /// Size as printed by dmidecode, e.g. `16 GB`
+    pub size: String,
+    /// Memory type, e.g. `DDR4`
+    pub kind: Option<String>,
+    /// Configured speed, e.g. `3200 MT/s`
+    pub speed: Option<String>,
+    /// Module vendor as stored in the SPD
+    pub manufacturer: Option<String>,
+    /// Vendor part number
+    pub part_number: Option<String>,
+    /// Module serial number
+    pub serial_number: Option<String>,
+}
+
+/// A mounted disk
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiskInventory {
+    /// Device name, e.g. `/dev/nvme0n1p2`
+    pub name: String,
+    /// Where the disk is mounted
+    pub mount_point: String,
+    /// File system type, e.g. `ext4`
+    pub file_system: String,
+    /// `HDD`, `SSD` or `Unknown`
+    pub kind: String,
+    /// Size of the file system
+    pub total_bytes: u64,
+    /// Whether the disk is removable media
+    pub removable: bool,
+}
+
+/// A network interface
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NicInventory {
+    /// Interface name, e.g. `eth0`
+    pub name: String,
+    /// Hardware address, e.g. `00:11:22:33:44:55`
+    pub mac: String,
+}
+
+/// A display controller
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GpuInventory {
+    /// PCI address, e.g. `0000:01:00.0`
+    pub address: String,
+    /// Vendor name from pci.ids when it is installed
+    pub vendor: Option<String>,
+    /// Device name from pci.ids when it is installed
+    pub model: Option<String>,
+    /// Kernel driver bound to the device
+    pub driver: Option<String>,
+}
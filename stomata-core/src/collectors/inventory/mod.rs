@@ -0,0 +1,6 @@
+//! Hardware inventory: CPU, memory modules, disks, NICs and GPUs
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::Inventory;
@@ -0,0 +1,203 @@
+//! Grouping processes by pod from their cgroup paths
+
+use std::{collections::HashMap, fs, path::Path};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::{
+        backend::SystemBackend,
+        cgroups::metrics::CgroupMetrics,
+        kubernetes::metrics::{PodMetrics, PodUsage, QosClass},
+    },
+    constants::{KUBELET_DIR, KUBELET_PROCESS_NAMES, POD_LOGS_DIR},
+};
+
+impl QosClass {
+    /// Class name as used by Kubernetes
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QosClass::Guaranteed => "Guaranteed",
+            QosClass::Burstable => "Burstable",
+            QosClass::BestEffort => "BestEffort",
+        }
+    }
+}
+
+impl PodMetrics {
+    /// Maps local processes to pods through their cgroup paths and sums
+    /// their usage per pod.
+    ///
+    /// Pod namespaces and names come from the kubelet's pod log
+    /// directories, so no API server access is needed. Nothing is
+    /// collected when no kubelet is detected.
+    pub fn fetch(system: &dyn SystemBackend) -> Self {
+        let kubelet_detected = kubelet_detected(system);
+        if !kubelet_detected {
+            return Self {
+                timestamp: Utc::now(),
+                kubelet_detected,
+                pods: Vec::new(),
+            };
+        }
+
+        let names = pod_names(Path::new(POD_LOGS_DIR));
+        let mut pods: HashMap<String, PodUsage> = HashMap::new();
+
+        for cgroup in CgroupMetrics::fetch(system).cgroups {
+            let Some((uid, qos)) = parse_pod_cgroup(&cgroup.path) else {
+                continue;
+            };
+            let pod = pods.entry(uid.clone()).or_insert_with(|| {
+                let (namespace, name) = names.get(&uid).cloned().unzip();
+                PodUsage {
+                    uid,
+                    namespace,
+                    name,
+                    qos,
+                    containers: 0,
+                    pids: Vec::new(),
+                    cpu_usage: 0.0,
+                    memory: 0,
+                    disk_read_bytes: 0,
+                    disk_written_bytes: 0,
+                }
+            });
+            pod.containers += 1;
+            pod.pids.extend(cgroup.pids);
+            pod.cpu_usage += cgroup.cpu_usage;
+            pod.memory += cgroup.memory;
+            pod.disk_read_bytes += cgroup.disk_read_bytes;
+            pod.disk_written_bytes += cgroup.disk_written_bytes;
+        }
+
+        let mut pods: Vec<PodUsage> = pods.into_values().collect();
+        pods.sort_by(|a, b| {
+            b.cpu_usage
+                .total_cmp(&a.cpu_usage)
+                .then(b.memory.cmp(&a.memory))
+        });
+
+        Self {
+            timestamp: Utc::now(),
+            kubelet_detected,
+            pods,
+        }
+    }
+}
+
+/// Whether a kubelet runs on this host, either as its own process or
+/// embedded in a k3s/k0s binary
+pub fn kubelet_detected(system: &dyn SystemBackend) -> bool {
+    system.processes().iter().any(|process| {
+        KUBELET_PROCESS_NAMES
+            .iter()
+            .any(|kubelet| process.name.starts_with(kubelet))
+    }) && Path::new(KUBELET_DIR).exists()
+}
+
+/// Extracts the pod UID and QoS class from a container cgroup path.
+///
+/// Handles both kubelet cgroup drivers:
+///
+/// - cgroupfs: `/kubepods/burstable/pod<uid>/<container>`
+/// - systemd: `/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod<uid>.slice/cri-containerd-<id>.scope`,
+///   where the dashes of the UID are escaped as underscores
+///
+/// Guaranteed pods sit directly below the `kubepods` cgroup.
+pub fn parse_pod_cgroup(path: &str) -> Option<(String, QosClass)> {
+    if !path.contains("kubepods") {
+        return None;
+    }
+
+    let uid = path.split('/').find_map(|component| {
+        let component = component.strip_suffix(".slice").unwrap_or(component);
+        let uid = match component.rfind("-pod") {
+            Some(index) => &component[index + 4..],
+            None => component.strip_prefix("pod")?,
+        };
+        (!uid.is_empty()).then(|| uid.replace('_', "-"))
+    })?;
+
+    let qos = if path.contains("besteffort") {
+        QosClass::BestEffort
+    } else if path.contains("burstable") {
+        QosClass::Burstable
+    } else {
+        QosClass::Guaranteed
+    };
+
+    Some((uid, qos))
+}
+
+/// Namespace and name of every pod with a log directory, keyed by UID.
+///
+/// The kubelet names these directories `<namespace>_<name>_<uid>`; neither
+/// namespaces nor pod names can contain underscores.
+pub fn pod_names(logs_dir: &Path) -> HashMap<String, (String, String)> {
+    let Ok(entries) = fs::read_dir(logs_dir) else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            parse_pod_log_dir(&dir_name)
+        })
+        .map(|(namespace, name, uid)| (uid, (namespace, name)))
+        .collect()
+}
+
+/// Splits a pod log directory name into namespace, name and UID
+pub fn parse_pod_log_dir(dir_name: &str) -> Option<(String, String, String)> {
+    let mut parts = dir_name.splitn(3, '_');
+    let (Some(namespace), Some(name), Some(uid)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    Some((namespace.to_string(), name.to_string(), uid.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pod_cgroup() {
+        assert_eq!(
+            parse_pod_cgroup("/kubepods/burstable/pod3f1c9a2e-1b2c-4d5e-8f90-123456789abc/4a5b6c"),
+            Some((
+                "3f1c9a2e-1b2c-4d5e-8f90-123456789abc".to_string(),
+                QosClass::Burstable
+            ))
+        );
+        assert_eq!(
+            parse_pod_cgroup(
+                "/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod3f1c9a2e_1b2c_4d5e_8f90_123456789abc.slice/cri-containerd-4a5b6c.scope"
+            ),
+            Some((
+                "3f1c9a2e-1b2c-4d5e-8f90-123456789abc".to_string(),
+                QosClass::BestEffort
+            ))
+        );
+        assert_eq!(
+            parse_pod_cgroup("/kubepods.slice/kubepods-pod0a1b.slice/cri-containerd-4a5b6c.scope"),
+            Some(("0a1b".to_string(), QosClass::Guaranteed))
+        );
+        assert_eq!(parse_pod_cgroup("/system.slice/kubelet.service"), None);
+    }
+
+    #[test]
+    fn test_parse_pod_log_dir() {
+        assert_eq!(
+            parse_pod_log_dir("kube-system_coredns-5d78c9869d-x2x7q_0a1b"),
+            Some((
+                "kube-system".to_string(),
+                "coredns-5d78c9869d-x2x7q".to_string(),
+                "0a1b".to_string()
+            ))
+        );
+        assert_eq!(parse_pod_log_dir("not-a-pod"), None);
+    }
+}
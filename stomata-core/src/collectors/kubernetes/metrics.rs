@@ -0,0 +1,50 @@
+//! Pod usage types
+
+use chrono::{DateTime, Utc};
+
+/// Kubernetes quality of service class of a pod, from its cgroup placement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QosClass {
+    /// Requests equal limits for every container
+    Guaranteed,
+    /// At least one request or limit is set
+    Burstable,
+    /// No requests or limits
+    BestEffort,
+}
+
+/// Resource usage of all local processes belonging to one pod
+#[derive(Debug, Clone, PartialEq)]
+pub struct PodUsage {
+    /// Pod UID from the cgroup path
+    pub uid: String,
+    /// Namespace and name are `None` when the pod log directory is missing
+    pub namespace: Option<String>,
+    /// Pod name
+    pub name: Option<String>,
+    /// QoS class from the cgroup hierarchy
+    pub qos: QosClass,
+    /// Number of container cgroups with running processes
+    pub containers: usize,
+    /// Member processes
+    pub pids: Vec<u32>,
+    /// Sum of the CPU usage of the member processes, in percent of one core
+    pub cpu_usage: f32,
+    /// Sum of the resident memory of the member processes in bytes
+    pub memory: u64,
+    /// Bytes read from disk since the last refresh
+    pub disk_read_bytes: u64,
+    /// Bytes written to disk since the last refresh
+    pub disk_written_bytes: u64,
+}
+
+/// Pods running on this host at one point in time
+#[derive(Clone)]
+pub struct PodMetrics {
+    /// When the processes were grouped
+    pub timestamp: DateTime<Utc>,
+    /// Whether a kubelet runs on this host, pods are only collected if so
+    pub kubelet_detected: bool,
+    /// Pods with at least one local process, highest CPU usage first
+    pub pods: Vec<PodUsage>,
+}
@@ -0,0 +1,6 @@
+//! CPU, memory and disk I/O per Kubernetes pod from the kubelet's cgroups
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{PodMetrics, PodUsage, QosClass};
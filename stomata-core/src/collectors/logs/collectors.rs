@@ -0,0 +1,195 @@
+//! Parsing log levels and keeping the latest entries
+
+use std::collections::VecDeque;
+
+use chrono::Utc;
+
+use crate::{
+    collectors::logs::{
+        metrics::{LogCollector, LogEntry, LogLevel, LogMetrics, LogSource},
+        sources::{DmesgSource, JournalSource},
+    },
+    constants::LOG_MAX_ENTRIES,
+};
+
+impl LogLevel {
+    /// Level of a syslog priority, the facility bits are ignored
+    pub fn from_priority(priority: u8) -> Self {
+        match priority & 7 {
+            0 => LogLevel::Emergency,
+            1 => LogLevel::Alert,
+            2 => LogLevel::Critical,
+            3 => LogLevel::Error,
+            4 => LogLevel::Warning,
+            5 => LogLevel::Notice,
+            6 => LogLevel::Info,
+            _ => LogLevel::Debug,
+        }
+    }
+
+    /// Short level name as printed by `dmesg`, e.g. `warn`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Emergency => "emerg",
+            LogLevel::Alert => "alert",
+            LogLevel::Critical => "crit",
+            LogLevel::Error => "err",
+            LogLevel::Warning => "warn",
+            LogLevel::Notice => "notice",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+        }
+    }
+}
+
+impl LogEntry {
+    /// Whether the message or the source contains `query`, ignoring case.
+    /// An empty query matches every entry.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.message.to_lowercase().contains(&query)
+            || self.source.to_lowercase().contains(&query)
+    }
+}
+
+impl Default for LogCollector {
+    /// Kernel messages from the journal, or from the kernel ring buffer on
+    /// systems without a readable journal
+    fn default() -> Self {
+        Self::new(Box::new(JournalSource::kernel())).with_fallback(Box::new(DmesgSource::default()))
+    }
+}
+
+impl LogCollector {
+    /// Tails `source`
+    pub fn new(source: Box<dyn LogSource>) -> Self {
+        Self {
+            source,
+            fallback: None,
+            entries: VecDeque::new(),
+            read_once: false,
+        }
+    }
+
+    /// Switches to `fallback` when the source fails before it read
+    /// anything
+    pub fn with_fallback(mut self, fallback: Box<dyn LogSource>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Reads the new entries of the source and returns the most recent
+    /// `LOG_MAX_ENTRIES` entries. Never fails, the error is carried in the
+    /// metrics and the entries read before stay.
+    pub fn fetch(&mut self) -> LogMetrics {
+        let mut result = self.source.read();
+        if result.is_err()
+            && !self.read_once
+            && let Some(fallback) = self.fallback.take()
+        {
+            tracing::info!(
+                from = self.source.name(),
+                to = fallback.name(),
+                "log source unavailable, falling back"
+            );
+            self.source = fallback;
+            result = self.source.read();
+        }
+
+        let error = match result {
+            Ok(entries) => {
+                self.read_once = true;
+                self.push(entries);
+                None
+            }
+            Err(err) => {
+                tracing::warn!(source = self.source.name(), "reading logs failed: {err}");
+                Some(err.to_string())
+            }
+        };
+        LogMetrics {
+            timestamp: Some(Utc::now()),
+            source: self.source.name(),
+            entries: self.entries.iter().cloned().collect(),
+            error,
+        }
+    }
+
+    fn push(&mut self, entries: Vec<LogEntry>) {
+        self.entries.extend(entries);
+        while self.entries.len() > LOG_MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{CoreError, CoreResult};
+
+    /// Source that fails or returns one numbered entry per read
+    #[derive(Debug)]
+    struct TestSource {
+        fail: bool,
+        reads: usize,
+    }
+
+    impl LogSource for TestSource {
+        fn name(&self) -> &'static str {
+            if self.fail { "failing" } else { "test" }
+        }
+
+        fn read(&mut self) -> CoreResult<Vec<LogEntry>> {
+            if self.fail {
+                return Err(CoreError::collection("no log"));
+            }
+            self.reads += 1;
+            Ok(vec![LogEntry {
+                timestamp: Utc::now(),
+                level: LogLevel::Info,
+                source: "test".to_string(),
+                message: format!("read {}", self.reads),
+            }])
+        }
+    }
+
+    #[test]
+    fn test_fetch_falls_back_and_keeps_entries() {
+        let mut collector = LogCollector::new(Box::new(TestSource {
+            fail: true,
+            reads: 0,
+        }))
+        .with_fallback(Box::new(TestSource {
+            fail: false,
+            reads: 0,
+        }));
+
+        let first = collector.fetch();
+        assert_eq!(first.source, "test");
+        assert!(first.error.is_none());
+
+        let second = collector.fetch();
+        assert_eq!(second.entries.len(), 2);
+        assert_eq!(second.entries[1].message, "read 2");
+    }
+
+    #[test]
+    fn test_level_ignores_facility_and_entry_matches() {
+        // <14> is facility user (1), priority info (6)
+        assert_eq!(LogLevel::from_priority(14), LogLevel::Info);
+        assert_eq!(LogLevel::from_priority(3), LogLevel::Error);
+
+        let entry = LogEntry {
+            timestamp: Utc::now(),
+            level: LogLevel::Warning,
+            source: "kernel".to_string(),
+            message: "usb 1-1: new high-speed USB device".to_string(),
+        };
+        assert!(entry.matches("USB"));
+        assert!(entry.matches("kern"));
+        assert!(entry.matches(" "));
+        assert!(!entry.matches("nvme"));
+    }
+}
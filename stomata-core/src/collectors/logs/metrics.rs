@@ -0,0 +1,79 @@
+//! Log entry types and the [`LogSource`] trait
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+
+use crate::errors::CoreResult;
+
+/// Severity of a log entry, the syslog priorities from most to least
+/// severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// System is unusable
+    Emergency,
+    /// Action must be taken immediately
+    Alert,
+    /// Critical conditions
+    Critical,
+    /// Error conditions
+    Error,
+    /// Warning conditions
+    Warning,
+    /// Normal but significant condition
+    Notice,
+    /// Informational message
+    Info,
+    /// Debug-level message
+    Debug,
+}
+
+/// A line of a log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// When the entry was logged
+    pub timestamp: DateTime<Utc>,
+    /// Syslog priority of the entry
+    pub level: LogLevel,
+    /// Program that logged the entry, `kernel` for kernel messages
+    pub source: String,
+    /// Text of the entry
+    pub message: String,
+}
+
+/// A backend the log entries are read from
+pub trait LogSource: Send + std::fmt::Debug {
+    /// Short name of the backend shown to the user, e.g. `journald`
+    fn name(&self) -> &'static str;
+
+    /// Returns the entries logged since the previous call, oldest first.
+    /// The first call returns the most recent backlog.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log can't be read.
+    fn read(&mut self) -> CoreResult<Vec<LogEntry>>;
+}
+
+/// The most recent entries of a log
+#[derive(Debug, Clone, Default)]
+pub struct LogMetrics {
+    /// When the log was last read, `None` before the first read
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Name of the source the entries were read from
+    pub source: &'static str,
+    /// Entries oldest first
+    pub entries: Vec<LogEntry>,
+    /// Why the log couldn't be read on the last refresh
+    pub error: Option<String>,
+}
+
+/// Tails a log source, keeping the most recent entries between refreshes
+#[derive(Debug)]
+pub struct LogCollector {
+    pub(crate) source: Box<dyn LogSource>,
+    /// Source used instead when `source` fails before it read anything
+    pub(crate) fallback: Option<Box<dyn LogSource>>,
+    pub(crate) entries: VecDeque<LogEntry>,
+    pub(crate) read_once: bool,
+}
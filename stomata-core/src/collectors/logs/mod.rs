@@ -0,0 +1,12 @@
+//! Kernel and system log tail
+//!
+//! Log entries come from a [`LogSource`], so backends other than journald
+//! and the kernel ring buffer can be added by implementing the trait. The
+//! [`LogCollector`] keeps the most recent entries of its source.
+
+pub mod collectors;
+pub mod metrics;
+pub mod sources;
+
+pub use metrics::{LogCollector, LogEntry, LogLevel, LogMetrics, LogSource};
+pub use sources::{DmesgSource, JournalSource};
@@ -0,0 +1,216 @@
+//! journald and kernel ring buffer log sources
+
+use std::{process::Command, time::Duration};
+
+use chrono::DateTime;
+use serde_json::Value;
+use sysinfo::System;
+
+use crate::{
+    collectors::{
+        command::run_with_timeout,
+        logs::metrics::{LogEntry, LogLevel, LogSource},
+    },
+    constants::{DMESG_TIMEOUT_MS, JOURNALCTL_TIMEOUT_MS, LOG_BACKLOG_LINES},
+    errors::CoreResult,
+};
+
+/// Reads the journal with `journalctl`, resuming after the last entry
+/// through the journal cursor
+#[derive(Debug, Default)]
+pub struct JournalSource {
+    /// Only the kernel messages, like `journalctl --dmesg`
+    kernel_only: bool,
+    cursor: Option<String>,
+}
+
+impl JournalSource {
+    /// Every entry of the journal
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// The kernel messages of the current boot
+    pub fn kernel() -> Self {
+        Self {
+            kernel_only: true,
+            cursor: None,
+        }
+    }
+}
+
+impl LogSource for JournalSource {
+    fn name(&self) -> &'static str {
+        if self.kernel_only {
+            "journald (kernel)"
+        } else {
+            "journald"
+        }
+    }
+
+    fn read(&mut self) -> CoreResult<Vec<LogEntry>> {
+        let lines = LOG_BACKLOG_LINES.to_string();
+        let mut command = Command::new("journalctl");
+        command.args([
+            "--output",
+            "json",
+            "--no-pager",
+            "--quiet",
+            "--lines",
+            &lines,
+        ]);
+        if self.kernel_only {
+            command.arg("--dmesg");
+        }
+        if let Some(cursor) = &self.cursor {
+            command.args(["--after-cursor", cursor]);
+        }
+        let output = run_with_timeout(&mut command, Duration::from_millis(JOURNALCTL_TIMEOUT_MS))?;
+        let (entries, cursor) = parse_journal_entries(&output);
+        if cursor.is_some() {
+            self.cursor = cursor;
+        }
+        Ok(entries)
+    }
+}
+
+/// Reads the kernel ring buffer with `dmesg`, which may need root when
+/// `kernel.dmesg_restrict` is set
+#[derive(Debug, Default)]
+pub struct DmesgSource {
+    /// Seconds since boot of the last entry read
+    last_seen: Option<f64>,
+}
+
+impl LogSource for DmesgSource {
+    fn name(&self) -> &'static str {
+        "dmesg"
+    }
+
+    fn read(&mut self) -> CoreResult<Vec<LogEntry>> {
+        let output = run_with_timeout(
+            Command::new("dmesg").args(["--raw", "--nopager"]),
+            Duration::from_millis(DMESG_TIMEOUT_MS),
+        )?;
+        let boot = DateTime::from_timestamp(System::boot_time() as i64, 0).unwrap_or_default();
+        let mut records = parse_dmesg(&output);
+        match self.last_seen {
+            Some(last_seen) => records.retain(|(seconds, _, _)| *seconds > last_seen),
+            None => {
+                let skip = records.len().saturating_sub(LOG_BACKLOG_LINES);
+                records.drain(..skip);
+            }
+        }
+        if let Some((seconds, _, _)) = records.last() {
+            self.last_seen = Some(*seconds);
+        }
+        Ok(records
+            .into_iter()
+            .map(|(seconds, level, message)| LogEntry {
+                timestamp: boot + chrono::Duration::microseconds((seconds * 1e6) as i64),
+                level,
+                source: "kernel".to_string(),
+                message,
+            })
+            .collect())
+    }
+}
+
+/// Parses `journalctl --output json` lines into log entries, returning
+/// the cursor of the last entry along with them
+fn parse_journal_entries(output: &str) -> (Vec<LogEntry>, Option<String>) {
+    let mut cursor = None;
+    let entries = output
+        .lines()
+        .filter_map(|line| {
+            let entry: Value = serde_json::from_str(line).ok()?;
+            if let Some(entry_cursor) = entry.get("__CURSOR").and_then(Value::as_str) {
+                cursor = Some(entry_cursor.to_string());
+            }
+            // the realtime timestamp is in microseconds since the epoch
+            let micros: i64 = entry.get("__REALTIME_TIMESTAMP")?.as_str()?.parse().ok()?;
+            let priority = entry
+                .get("PRIORITY")
+                .and_then(Value::as_str)
+                .and_then(|priority| priority.parse().ok())
+                .unwrap_or(6);
+            let source = ["SYSLOG_IDENTIFIER", "_COMM"]
+                .iter()
+                .find_map(|field| entry.get(*field).and_then(Value::as_str))
+                .unwrap_or_default();
+            Some(LogEntry {
+                timestamp: DateTime::from_timestamp_micros(micros)?,
+                level: LogLevel::from_priority(priority),
+                source: source.to_string(),
+                // binary messages are logged as byte arrays and are skipped
+                message: entry.get("MESSAGE")?.as_str()?.to_string(),
+            })
+        })
+        .collect();
+    (entries, cursor)
+}
+
+/// Parses `dmesg --raw` lines such as `<6>[    1.234567] message` into
+/// seconds since boot, level and message. Continuation lines of a
+/// multi-line message take the time and level of the line before them.
+fn parse_dmesg(output: &str) -> Vec<(f64, LogLevel, String)> {
+    let mut records: Vec<(f64, LogLevel, String)> = Vec::new();
+    for line in output.lines() {
+        let parsed = line.strip_prefix('<').and_then(|rest| {
+            let (priority, rest) = rest.split_once(">[")?;
+            let (seconds, message) = rest.split_once(']')?;
+            Some((
+                seconds.trim().parse::<f64>().ok()?,
+                LogLevel::from_priority(priority.parse().ok()?),
+                message.strip_prefix(' ').unwrap_or(message).to_string(),
+            ))
+        });
+        match parsed {
+            Some(record) => records.push(record),
+            None => {
+                if let Some((seconds, level, _)) = records.last() {
+                    records.push((*seconds, *level, line.trim().to_string()));
+                }
+            }
+        }
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_journal_entries() {
+        let output = concat!(
+            r#"{"__CURSOR":"s=1","__REALTIME_TIMESTAMP":"1700000000000000","PRIORITY":"3","SYSLOG_IDENTIFIER":"kernel","MESSAGE":"I/O error, dev sda"}"#,
+            "\n",
+            r#"{"__CURSOR":"s=2","__REALTIME_TIMESTAMP":"1700000001000000","_COMM":"sshd","MESSAGE":[104,105]}"#,
+            "\n",
+            r#"{"__CURSOR":"s=3","__REALTIME_TIMESTAMP":"1700000002000000","_COMM":"sshd","MESSAGE":"Accepted publickey"}"#,
+            "\n",
+        );
+        let (entries, cursor) = parse_journal_entries(output);
+        assert_eq!(cursor.as_deref(), Some("s=3"));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, LogLevel::Error);
+        assert_eq!(entries[0].source, "kernel");
+        assert_eq!(entries[1].level, LogLevel::Info);
+        assert_eq!(entries[1].source, "sshd");
+    }
+
+    #[test]
+    fn test_parse_dmesg() {
+        let output = "<6>[    0.000000] Linux version 6.8.0\n<3>[   12.500000] nvme0: I/O error\n  continued here\n";
+        let records = parse_dmesg(output);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].2, "Linux version 6.8.0");
+        assert_eq!(records[1].0, 12.5);
+        assert_eq!(records[1].1, LogLevel::Error);
+        assert_eq!(
+            records[2],
+            (12.5, LogLevel::Error, "continued here".to_string())
+        );
+    }
+}
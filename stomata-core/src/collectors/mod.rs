@@ -1,10 +1,61 @@
+//! System metric collectors
+//!
+//! Each collector lives in its own module, split into `metrics` for the
+//! data types and `collectors` for reading them from the system. Most of
+//! them are driven through [`StomataSystemMetrics`], while
+//! [`CollectorRegistry`] exposes a uniform [`Collector`] interface for
+//! exporters.
+
+pub mod audio;
+pub mod backend;
+pub mod bluetooth;
+pub mod boot;
+pub mod cgroups;
+pub mod command;
+pub mod connections;
+pub mod crashes;
+pub mod disks;
+pub mod hwmon;
+pub mod inventory;
+pub mod kubernetes;
+pub mod logs;
 pub mod network;
+pub mod pci;
+pub mod plugins;
+pub mod poller;
+pub mod power;
 pub mod process;
+pub mod registry;
+pub mod services;
+pub mod sessions;
 pub mod structs;
 pub mod structs_impls;
 pub mod system;
 pub mod system_info;
+pub mod usb;
+pub mod vms;
 
+pub use audio::AudioMetrics;
+pub use backend::{FakeSystem, OwnedSysinfo, RefreshingBackend, Sysinfo, SystemBackend};
+pub use bluetooth::BluetoothMetrics;
+pub use boot::BootAnalysis;
+pub use cgroups::CgroupMetrics;
+pub use connections::ConnectionsMetrics;
+pub use crashes::CrashEvent;
+pub use disks::DiskMetrics;
+pub use hwmon::HwmonMetrics;
+pub use inventory::Inventory;
+pub use kubernetes::PodMetrics;
+pub use logs::LogMetrics;
 pub use network::NetworkMetrics;
+pub use plugins::{Plugin, PluginMetrics, PluginRunner};
+pub use poller::Poller;
+pub use power::PowerMetrics;
 pub use process::{ProcessData, SingleProcessData};
+pub use registry::{Collector, CollectorRegistry, Metric, MetricValue};
+pub use services::ServiceMetrics;
+pub use sessions::SessionMetrics;
+pub use structs::{Metrics, MetricsCategory, MetricsToFetch, StomataSystemMetrics};
+pub use system::{SystemCollector, SystemMetrics};
 pub use system_info::SystemInfo;
+pub use vms::VmMetrics;
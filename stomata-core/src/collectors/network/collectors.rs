@@ -1,34 +1,27 @@
+//! Building the interface list from sysinfo
+
 use chrono::Utc;
-use sysinfo::Networks;
 
-use crate::collectors::network::metrics::{NetworkInterfaces, NetworkMetrics};
+use crate::collectors::{
+    backend::SystemBackend,
+    network::{metrics::NetworkMetrics, rates::NetworkRates},
+};
 
 impl NetworkMetrics {
-    pub fn fetch(networks: &Networks) -> Self {
+    /// Reads the interfaces of the refreshed `system` sorted by name,
+    /// with the rates computed by the latest update of `rates`
+    pub fn fetch(system: &dyn SystemBackend, rates: &NetworkRates) -> Self {
         let timestamp = Utc::now();
-        let interfaces: Vec<NetworkInterfaces> = networks
-            .list()
-            .iter()
-            .map(|(name, data)| NetworkInterfaces {
-                name: name.clone(),
-                errors_on_received: data.errors_on_received(),
-                total_errors_on_received: data.total_errors_on_received(),
-                errors_on_transmitted: data.errors_on_transmitted(),
-                total_errors_on_transmitted: data.total_errors_on_transmitted(),
-                packets_received: data.packets_received(),
-                total_packets_received: data.total_packets_received(),
-                packets_transmitted: data.packets_transmitted(),
-                total_packets_transmitted: data.total_packets_transmitted(),
-                bytes_received: data.received(),
-                total_bytes_received: data.total_received(),
-                bytes_transmitted: data.transmitted(),
-                total_bytes_transmitted: data.total_transmitted(),
-            })
-            .collect();
+        let mut interfaces = system.network_interfaces();
+        for interface in &mut interfaces {
+            interface.rates = rates.get(&interface.name);
+        }
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
 
         Self {
             timestamp,
             interfaces,
+            tunnels: system.tunnels(),
         }
     }
 }
@@ -1,23 +1,58 @@
+//! Network interface types
+
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::collectors::network::{rates::InterfaceRates, tunnels::TunnelStatus, wifi::WifiLink};
 
+/// Network interfaces at one point in time
+#[derive(Clone, Serialize, Deserialize)]
 pub struct NetworkMetrics {
+    /// When the interfaces were read
     pub timestamp: DateTime<Utc>,
+    /// Interfaces sorted by name
     pub interfaces: Vec<NetworkInterfaces>,
+    /// WireGuard and Tailscale tunnels with their peers
+    pub tunnels: TunnelStatus,
 }
 
-#[derive(Default)]
+/// Addresses and traffic counters of one interface
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct NetworkInterfaces {
+    /// Interface name, e.g. `eth0`
     pub name: String,
+    /// Hardware address, `00:00:00:00:00:00` for interfaces without one
+    pub mac_address: String,
+    /// Assigned addresses with their prefix length, e.g. `192.168.1.2/24`
+    pub ip_networks: Vec<String>,
+    /// Largest packet size in bytes
+    pub mtu: u64,
+    /// Link to the access point, `None` for wired interfaces
+    pub wifi: Option<WifiLink>,
+    /// Traffic per second since the previous fetch
+    pub rates: InterfaceRates,
+    /// Receive errors since the previous refresh
     pub errors_on_received: u64,
+    /// Receive errors since boot
     pub total_errors_on_received: u64,
+    /// Transmit errors since the previous refresh
     pub errors_on_transmitted: u64,
+    /// Transmit errors since boot
     pub total_errors_on_transmitted: u64,
+    /// Packets received since the previous refresh
     pub packets_received: u64,
+    /// Packets received since boot
     pub total_packets_received: u64,
+    /// Packets transmitted since the previous refresh
     pub packets_transmitted: u64,
+    /// Packets transmitted since boot
     pub total_packets_transmitted: u64,
+    /// Bytes received since the previous refresh
     pub bytes_received: u64,
+    /// Bytes received since boot
     pub total_bytes_received: u64,
+    /// Bytes transmitted since the previous refresh
     pub bytes_transmitted: u64,
+    /// Bytes transmitted since boot
     pub total_bytes_transmitted: u64,
 }
@@ -1,4 +1,9 @@
+//! Network interfaces, traffic rates, Wi-Fi links and tunnels
+
 pub mod collectors;
 pub mod metrics;
+pub mod rates;
+pub mod tunnels;
+pub mod wifi;
 
 pub use metrics::NetworkMetrics;
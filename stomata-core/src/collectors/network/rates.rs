@@ -0,0 +1,151 @@
+//! Per-second network rates
+//!
+//! sysinfo reports the traffic since the previous refresh, which depends on
+//! how long ago that refresh was. Rates are computed instead from the
+//! cumulative counters of two consecutive snapshots and the time between
+//! them, so they stay comparable whatever the refresh interval.
+
+use std::{collections::HashMap, time::Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::collectors::backend::SystemBackend;
+
+/// Cumulative counters of an interface at one point in time
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterfaceTotals {
+    /// Bytes received since boot
+    pub bytes_received: u64,
+    /// Bytes transmitted since boot
+    pub bytes_transmitted: u64,
+    /// Packets received since boot
+    pub packets_received: u64,
+    /// Packets transmitted since boot
+    pub packets_transmitted: u64,
+}
+
+/// Traffic of an interface per second
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceRates {
+    /// Bytes received per second
+    pub bytes_received: f64,
+    /// Bytes transmitted per second
+    pub bytes_transmitted: f64,
+    /// Packets received per second
+    pub packets_received: f64,
+    /// Packets transmitted per second
+    pub packets_transmitted: f64,
+}
+
+/// Computes interface rates from consecutive snapshots of their counters
+#[derive(Debug, Default)]
+pub struct NetworkRates {
+    previous: HashMap<String, InterfaceTotals>,
+    previous_at: Option<Instant>,
+    rates: HashMap<String, InterfaceRates>,
+}
+
+impl NetworkRates {
+    /// Takes a snapshot of the refreshed interfaces of `system` and
+    /// computes the rates since the previous one
+    pub fn update(&mut self, system: &dyn SystemBackend) {
+        self.update_from(system.interface_totals(), Instant::now());
+    }
+
+    /// Computes the rates from `totals` taken at `now`.
+    ///
+    /// Interfaces seen for the first time have no rate until the next
+    /// snapshot. Counters that went backwards, e.g. after the interface was
+    /// recreated, count as no traffic.
+    pub fn update_from(
+        &mut self,
+        totals: impl IntoIterator<Item = (String, InterfaceTotals)>,
+        now: Instant,
+    ) {
+        let elapsed = self
+            .previous_at
+            .map(|previous| now.duration_since(previous).as_secs_f64())
+            .filter(|elapsed| *elapsed > 0.0);
+        let totals: HashMap<String, InterfaceTotals> = totals.into_iter().collect();
+
+        self.rates = totals
+            .iter()
+            .filter_map(|(name, current)| {
+                let previous = self.previous.get(name)?;
+                let elapsed = elapsed?;
+                let rate =
+                    |current: u64, previous: u64| current.saturating_sub(previous) as f64 / elapsed;
+                Some((
+                    name.clone(),
+                    InterfaceRates {
+                        bytes_received: rate(current.bytes_received, previous.bytes_received),
+                        bytes_transmitted: rate(
+                            current.bytes_transmitted,
+                            previous.bytes_transmitted,
+                        ),
+                        packets_received: rate(current.packets_received, previous.packets_received),
+                        packets_transmitted: rate(
+                            current.packets_transmitted,
+                            previous.packets_transmitted,
+                        ),
+                    },
+                ))
+            })
+            .collect();
+        self.previous = totals;
+        self.previous_at = Some(now);
+    }
+
+    /// Rates of the interface computed by the latest update
+    pub fn get(&self, name: &str) -> InterfaceRates {
+        self.rates.get(name).copied().unwrap_or_default()
+    }
+
+    /// Forgets the previous snapshot, so the next update only sets a new
+    /// baseline
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn totals(bytes: u64, packets: u64) -> (String, InterfaceTotals) {
+        (
+            "eth0".to_string(),
+            InterfaceTotals {
+                bytes_received: bytes,
+                bytes_transmitted: bytes / 2,
+                packets_received: packets,
+                packets_transmitted: packets,
+            },
+        )
+    }
+
+    #[test]
+    fn test_rates_use_elapsed_time() {
+        let start = Instant::now();
+        let mut rates = NetworkRates::default();
+        rates.update_from([totals(1_000, 10)], start);
+        assert_eq!(rates.get("eth0"), InterfaceRates::default());
+
+        rates.update_from([totals(5_000, 30)], start + Duration::from_secs(2));
+        let eth0 = rates.get("eth0");
+        assert_eq!(eth0.bytes_received, 2_000.0);
+        assert_eq!(eth0.bytes_transmitted, 1_000.0);
+        assert_eq!(eth0.packets_received, 10.0);
+    }
+
+    #[test]
+    fn test_rates_ignore_counter_resets() {
+        let start = Instant::now();
+        let mut rates = NetworkRates::default();
+        rates.update_from([totals(5_000, 30)], start);
+        rates.update_from([totals(100, 1)], start + Duration::from_secs(1));
+        assert_eq!(rates.get("eth0"), InterfaceRates::default());
+    }
+}
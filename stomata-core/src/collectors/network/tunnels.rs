@@ -0,0 +1,263 @@
+//! WireGuard and Tailscale tunnel status
+//!
+//! WireGuard interfaces are found through sysfs and their peers listed
+//! with `wg show <interface> dump`, which needs root. Tailscale peers come
+//! from `tailscale status --json` when the daemon is running.
+
+use std::{collections::HashMap, fs, process::Command, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    collectors::command::run_with_timeout,
+    constants::{SYS_CLASS_NET_DIR, TUNNEL_COMMAND_TIMEOUT_MS},
+    errors::{CoreError, CoreResult},
+};
+
+/// Kind of VPN a tunnel belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TunnelKind {
+    /// A WireGuard interface
+    WireGuard,
+    /// The Tailscale network
+    Tailscale,
+}
+
+/// A peer of a tunnel with its last handshake and transfer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TunnelPeer {
+    /// Host name for Tailscale peers, shortened public key for WireGuard
+    pub name: String,
+    /// Last known address of the peer, `None` until it connected
+    pub endpoint: Option<String>,
+    /// Allowed IPs for WireGuard, Tailscale IPs for Tailscale
+    pub addresses: String,
+    /// `None` if the peer never completed a handshake
+    pub last_handshake: Option<DateTime<Utc>>,
+    /// Bytes received from the peer
+    pub rx_bytes: u64,
+    /// Bytes sent to the peer
+    pub tx_bytes: u64,
+    /// Only reported by Tailscale
+    pub online: Option<bool>,
+}
+
+/// A WireGuard interface or the Tailscale network with its peers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tunnel {
+    /// WireGuard or Tailscale
+    pub kind: TunnelKind,
+    /// Interface name, e.g. `wg0` or `tailscale0`
+    pub interface: String,
+    /// Peers of the tunnel
+    pub peers: Vec<TunnelPeer>,
+}
+
+/// All detected tunnels and why some of them couldn't be inspected
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TunnelStatus {
+    /// Tunnels that could be inspected
+    pub tunnels: Vec<Tunnel>,
+    /// Why a tunnel couldn't be inspected, e.g. `wg` needs root
+    pub errors: Vec<String>,
+}
+
+impl TunnelKind {
+    /// Display name of the VPN
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TunnelKind::WireGuard => "WireGuard",
+            TunnelKind::Tailscale => "Tailscale",
+        }
+    }
+}
+
+impl TunnelStatus {
+    /// Detects WireGuard interfaces and the Tailscale daemon.
+    ///
+    /// Never fails; tunnels that exist but can't be inspected are reported
+    /// in `errors`. Hosts without any VPN yield an empty status.
+    pub fn fetch() -> Self {
+        let mut status = TunnelStatus::default();
+        let timeout = Duration::from_millis(TUNNEL_COMMAND_TIMEOUT_MS);
+
+        for interface in wireguard_interfaces() {
+            match run_with_timeout(
+                Command::new("wg").args(["show", &interface, "dump"]),
+                timeout,
+            ) {
+                Ok(dump) => status.tunnels.push(Tunnel {
+                    kind: TunnelKind::WireGuard,
+                    peers: parse_wg_dump(&dump),
+                    interface,
+                }),
+                Err(err) => status.errors.push(format!("{interface}: {err}")),
+            }
+        }
+
+        match run_with_timeout(
+            Command::new("tailscale").args(["status", "--json"]),
+            timeout,
+        ) {
+            Ok(json) => match parse_tailscale_status(&json) {
+                Ok(tunnel) => status.tunnels.push(tunnel),
+                Err(err) => status.errors.push(format!("tailscale: {err}")),
+            },
+            // tailscale isn't installed, nothing to report
+            Err(err) if err.is_not_found() => {}
+            // installed but the daemon isn't running
+            Err(_) => {}
+        }
+
+        status
+    }
+}
+
+/// Names of the WireGuard interfaces, identified by `DEVTYPE=wireguard` in
+/// their sysfs uevent file
+pub fn wireguard_interfaces() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(SYS_CLASS_NET_DIR) else {
+        return Vec::new();
+    };
+
+    let mut interfaces: Vec<String> = entries
+        .flatten()
+        .filter(|entry| {
+            fs::read_to_string(entry.path().join("uevent"))
+                .is_ok_and(|uevent| uevent.lines().any(|line| line == "DEVTYPE=wireguard"))
+        })
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    interfaces.sort();
+    interfaces
+}
+
+/// Parses the peers from `wg show <interface> dump`.
+///
+/// The first line describes the interface itself. Every following line is
+/// a tab separated peer: public key, preshared key, endpoint, allowed IPs,
+/// latest handshake (unix seconds, 0 if never), rx bytes, tx bytes and
+/// persistent keepalive.
+pub fn parse_wg_dump(dump: &str) -> Vec<TunnelPeer> {
+    dump.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 8 {
+                return None;
+            }
+            let public_key = fields[0];
+            Some(TunnelPeer {
+                name: public_key.chars().take(8).collect(),
+                endpoint: (fields[2] != "(none)").then(|| fields[2].to_string()),
+                addresses: fields[3].to_string(),
+                last_handshake: fields[4]
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|seconds| *seconds > 0)
+                    .and_then(|seconds| DateTime::from_timestamp(seconds, 0)),
+                rx_bytes: fields[5].parse().unwrap_or(0),
+                tx_bytes: fields[6].parse().unwrap_or(0),
+                online: None,
+            })
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TailscaleStatus {
+    #[serde(default)]
+    peer: HashMap<String, TailscalePeer>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TailscalePeer {
+    #[serde(default)]
+    host_name: String,
+    #[serde(default)]
+    tailscale_ips: Vec<String>,
+    #[serde(default)]
+    cur_addr: String,
+    #[serde(default)]
+    rx_bytes: u64,
+    #[serde(default)]
+    tx_bytes: u64,
+    #[serde(default)]
+    online: bool,
+    /// RFC 3339 timestamp, the zero time if there was no handshake
+    #[serde(default)]
+    last_handshake: String,
+}
+
+/// Parses the peers from `tailscale status --json`
+pub fn parse_tailscale_status(json: &str) -> CoreResult<Tunnel> {
+    let status: TailscaleStatus = serde_json::from_str(json).map_err(|err| {
+        CoreError::collection(format!("unexpected tailscale status output: {err}"))
+    })?;
+    let mut peers: Vec<TunnelPeer> = status
+        .peer
+        .into_values()
+        .map(|peer| TunnelPeer {
+            name: peer.host_name,
+            endpoint: (!peer.cur_addr.is_empty()).then_some(peer.cur_addr),
+            addresses: peer.tailscale_ips.join(","),
+            // tailscale reports the zero time for peers without a handshake
+            last_handshake: DateTime::parse_from_rfc3339(&peer.last_handshake)
+                .ok()
+                .map(|time| time.with_timezone(&Utc))
+                .filter(|time| time.timestamp() > 0),
+            rx_bytes: peer.rx_bytes,
+            tx_bytes: peer.tx_bytes,
+            online: Some(peer.online),
+        })
+        .collect();
+    peers.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Tunnel {
+        kind: TunnelKind::Tailscale,
+        interface: "tailscale".to_string(),
+        peers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wg_dump() {
+        let dump = "cPrivKey=\tcPubKey=\t51820\toff\nxTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=\t(none)\t203.0.113.5:51820\t10.0.0.2/32\t1700000000\t1024\t2048\t25\nhAL7b5ibvdRwpSbVgw5ZdoQcnhVkbBpHBM+ebnU5G3I=\t(none)\t(none)\t10.0.0.3/32\t0\t0\t0\toff\n";
+        let peers = parse_wg_dump(dump);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].name, "xTIBA5rb");
+        assert_eq!(peers[0].endpoint.as_deref(), Some("203.0.113.5:51820"));
+        assert_eq!(
+            peers[0].last_handshake.map(|t| t.timestamp()),
+            Some(1_700_000_000)
+        );
+        assert_eq!(peers[0].rx_bytes, 1024);
+        assert_eq!(peers[0].tx_bytes, 2048);
+        assert_eq!(peers[1].endpoint, None);
+        assert_eq!(peers[1].last_handshake, None);
+    }
+
+    #[test]
+    fn test_parse_tailscale_status() {
+        let json = r#"{"Self": {"HostName": "laptop"}, "Peer": {
+            "nodekey:abc": {"HostName": "nas", "TailscaleIPs": ["100.64.0.2"], "CurAddr": "192.168.1.10:41641",
+                            "RxBytes": 10, "TxBytes": 20, "Online": true, "LastHandshake": "2024-01-01T00:00:00Z"},
+            "nodekey:def": {"HostName": "phone", "TailscaleIPs": ["100.64.0.3"], "CurAddr": "",
+                            "Online": false, "LastHandshake": "0001-01-01T00:00:00Z"}
+        }}"#;
+        let tunnel = parse_tailscale_status(json).unwrap();
+        assert_eq!(tunnel.peers.len(), 2);
+        assert_eq!(tunnel.peers[0].name, "nas");
+        assert_eq!(tunnel.peers[0].online, Some(true));
+        assert!(tunnel.peers[0].last_handshake.is_some());
+        assert_eq!(tunnel.peers[1].endpoint, None);
+        assert_eq!(tunnel.peers[1].last_handshake, None);
+    }
+}
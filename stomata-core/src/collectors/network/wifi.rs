@@ -0,0 +1,149 @@
+//! Wi-Fi link status of wireless interfaces
+//!
+//! Wireless interfaces are found through sysfs and their link read with
+//! `iw dev <interface> link`, which queries nl80211 and needs no root.
+
+use std::{path::Path, process::Command, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    collectors::command::run_with_timeout,
+    constants::{IW_TIMEOUT_MS, SYS_CLASS_NET_DIR},
+};
+
+/// Link of a wireless interface to its access point
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WifiLink {
+    /// `None` when the interface isn't associated with an access point
+    pub ssid: Option<String>,
+    /// Received signal strength
+    pub signal_dbm: Option<i32>,
+    /// Bitrate of the last received frame
+    pub rx_bitrate_mbps: Option<f64>,
+    /// Bitrate of the last transmitted frame
+    pub tx_bitrate_mbps: Option<f64>,
+    /// Channel frequency, e.g. `5180` for channel 36
+    pub frequency_mhz: Option<u32>,
+    /// Why the link couldn't be read, e.g. `iw` isn't installed
+    pub error: Option<String>,
+}
+
+impl WifiLink {
+    /// Reads the link of `interface`, `None` for wired interfaces.
+    ///
+    /// Never fails; when `iw` can't be run the error is carried in
+    /// `error`.
+    pub fn fetch(interface: &str) -> Option<Self> {
+        if !is_wireless(interface) {
+            return None;
+        }
+        let output = run_with_timeout(
+            Command::new("iw").args(["dev", interface, "link"]),
+            Duration::from_millis(IW_TIMEOUT_MS),
+        );
+        Some(match output {
+            Ok(output) => parse_iw_link(&output),
+            Err(err) if err.is_not_found() => Self {
+                error: Some("iw is not installed".to_string()),
+                ..Default::default()
+            },
+            Err(err) => Self {
+                error: Some(format!("iw dev {interface} link failed: {err}")),
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Channel number of the link frequency
+    pub fn channel(&self) -> Option<u32> {
+        self.frequency_mhz.and_then(frequency_to_channel)
+    }
+
+    /// Signal strength mapped linearly from -100 dBm (0%) to -50 dBm (100%)
+    pub fn quality(&self) -> Option<u8> {
+        self.signal_dbm
+            .map(|dbm| ((dbm + 100) * 2).clamp(0, 100) as u8)
+    }
+}
+
+/// Whether `interface` is an 802.11 interface, which have a `wireless`
+/// directory in sysfs
+pub fn is_wireless(interface: &str) -> bool {
+    Path::new(SYS_CLASS_NET_DIR)
+        .join(interface)
+        .join("wireless")
+        .exists()
+}
+
+/// Parses the output of `iw dev <interface> link`.
+///
+/// A connected interface prints `Connected to <bssid>` followed by
+/// indented `key: value` lines; a disconnected one prints
+/// `Not connected.`.
+pub fn parse_iw_link(output: &str) -> WifiLink {
+    let mut link = WifiLink::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let first = value.split_whitespace().next().unwrap_or_default();
+        match key {
+            "SSID" => link.ssid = Some(value.to_string()),
+            "signal" => link.signal_dbm = first.parse().ok(),
+            "freq" => link.frequency_mhz = first.parse::<f64>().ok().map(|freq| freq as u32),
+            "rx bitrate" => link.rx_bitrate_mbps = first.parse().ok(),
+            "tx bitrate" => link.tx_bitrate_mbps = first.parse().ok(),
+            _ => {}
+        }
+    }
+    link
+}
+
+/// Maps a 2.4, 5 or 6 GHz frequency to its channel number
+pub fn frequency_to_channel(frequency_mhz: u32) -> Option<u32> {
+    match frequency_mhz {
+        2484 => Some(14),
+        2412..=2472 => Some((frequency_mhz - 2407) / 5),
+        5955..=7115 => Some((frequency_mhz - 5950) / 5),
+        5160..=5885 => Some((frequency_mhz - 5000) / 5),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iw_link() {
+        let output = "Connected to aa:bb:cc:dd:ee:ff (on wlan0)
+\tSSID: Home Network
+\tfreq: 5180.0
+\tRX: 123456 bytes (789 packets)
+\tTX: 65432 bytes (321 packets)
+\tsignal: -52 dBm
+\trx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
+\ttx bitrate: 780.0 MBit/s VHT-MCS 8 80MHz short GI VHT-NSS 2
+";
+        let link = parse_iw_link(output);
+        assert_eq!(link.ssid.as_deref(), Some("Home Network"));
+        assert_eq!(link.signal_dbm, Some(-52));
+        assert_eq!(link.frequency_mhz, Some(5180));
+        assert_eq!(link.channel(), Some(36));
+        assert_eq!(link.quality(), Some(96));
+        assert_eq!(link.rx_bitrate_mbps, Some(866.7));
+        assert_eq!(link.tx_bitrate_mbps, Some(780.0));
+
+        assert_eq!(parse_iw_link("Not connected.\n"), WifiLink::default());
+    }
+
+    #[test]
+    fn test_frequency_to_channel() {
+        assert_eq!(frequency_to_channel(2412), Some(1));
+        assert_eq!(frequency_to_channel(2484), Some(14));
+        assert_eq!(frequency_to_channel(5955), Some(1));
+        assert_eq!(frequency_to_channel(900), None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Reading PCI devices from sysfs and naming them from pci.ids
+
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{
+    collectors::pci::metrics::PciDevice,
+    constants::{PCI_CLASS_NAMES, PCI_DEVICES_DIR, PCI_IDS_PATHS},
+    errors::{CoreError, CoreResult},
+};
+
+impl PciDevice {
+    /// Lists the PCI devices sorted by address, with names from the
+    /// pci.ids database when it is installed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::UnsupportedPlatform`] if `/sys/bus/pci/devices`
+    /// doesn't exist, e.g. when the platform isn't Linux, or an error if it
+    /// can't be read.
+    pub fn fetch() -> CoreResult<Vec<Self>> {
+        let entries = fs::read_dir(PCI_DEVICES_DIR).map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => {
+                CoreError::unsupported(format!("{PCI_DEVICES_DIR} does not exist"))
+            }
+            _ => CoreError::io(format!("failed to read {PCI_DEVICES_DIR}"), err),
+        })?;
+        let mut devices: Vec<PciDevice> = entries
+            .flatten()
+            .map(|entry| Self::read(&entry.file_name().to_string_lossy(), &entry.path()))
+            .collect();
+        devices.sort_by(|a, b| a.address.cmp(&b.address));
+
+        if let Some(ids) = PCI_IDS_PATHS
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+        {
+            let names = parse_pci_ids(&ids);
+            for device in &mut devices {
+                device.vendor_name = names.get(&(device.vendor_id.clone(), None)).cloned();
+                device.device_name = names
+                    .get(&(device.vendor_id.clone(), Some(device.device_id.clone())))
+                    .cloned();
+            }
+        }
+        Ok(devices)
+    }
+
+    fn read(address: &str, path: &Path) -> Self {
+        let attribute = |file: &str| -> String {
+            fs::read_to_string(path.join(file))
+                .map(|value| value.trim().trim_start_matches("0x").to_string())
+                .unwrap_or_default()
+        };
+        let link_name = |file: &str| -> Option<String> {
+            let target = fs::read_link(path.join(file)).ok()?;
+            Some(target.file_name()?.to_string_lossy().to_string())
+        };
+
+        Self {
+            address: address.to_string(),
+            class: u32::from_str_radix(&attribute("class"), 16).unwrap_or(0),
+            vendor_id: attribute("vendor"),
+            device_id: attribute("device"),
+            vendor_name: None,
+            device_name: None,
+            driver: link_name("driver"),
+            iommu_group: link_name("iommu_group").and_then(|group| group.parse().ok()),
+        }
+    }
+
+    /// Generic name of the device class, e.g. `VGA compatible controller`
+    pub fn class_name(&self) -> &'static str {
+        let base = (self.class >> 16) as u8;
+        let sub = (self.class >> 8) as u8;
+        PCI_CLASS_NAMES
+            .iter()
+            .find(|(class, subclass, _)| *class == base && *subclass == Some(sub))
+            .or_else(|| {
+                PCI_CLASS_NAMES
+                    .iter()
+                    .find(|(class, subclass, _)| *class == base && subclass.is_none())
+            })
+            .map(|(_, _, name)| *name)
+            .unwrap_or("Unclassified device")
+    }
+}
+
+/// Parses the vendor and device names of a pci.ids database.
+///
+/// Vendors are unindented `vvvv  Name` lines, their devices follow as
+/// tab-indented `dddd  Name` lines. Subsystems (two tabs) and the class
+/// section at the end are skipped. Keys are `(vendor, None)` for vendors
+/// and `(vendor, Some(device))` for devices.
+pub fn parse_pci_ids(ids: &str) -> HashMap<(String, Option<String>), String> {
+    let mut names = HashMap::new();
+    let mut vendor: Option<String> = None;
+    for line in ids.lines() {
+        if line.starts_with('#') || line.trim().is_empty() || line.starts_with("\t\t") {
+            continue;
+        }
+        if line.starts_with("C ") {
+            // the device class list, no more vendors after it
+            break;
+        }
+        let (indented, line) = match line.strip_prefix('\t') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+        let Some((id, name)) = line.split_once("  ") else {
+            continue;
+        };
+        let id = id.to_lowercase();
+        let name = name.trim().to_string();
+        if indented {
+            if let Some(vendor) = &vendor {
+                names.insert((vendor.clone(), Some(id)), name);
+            }
+        } else {
+            names.insert((id.clone(), None), name);
+            vendor = Some(id);
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pci_ids() {
+        let ids = "# comment
+10de  NVIDIA Corporation
+\t2684  AD102 [GeForce RTX 4090]
+\t\t1043 889d  TUF RTX 4090
+8086  Intel Corporation
+\t7a60  Raptor Lake USB 3.2 Gen 2x2 XHCI Host Controller
+C 00  Unclassified device
+\t00  Non-VGA unclassified device
+";
+        let names = parse_pci_ids(ids);
+        assert_eq!(
+            names.get(&("10de".to_string(), None)).unwrap(),
+            "NVIDIA Corporation"
+        );
+        assert_eq!(
+            names
+                .get(&("10de".to_string(), Some("2684".to_string())))
+                .unwrap(),
+            "AD102 [GeForce RTX 4090]"
+        );
+        assert!(names.contains_key(&("8086".to_string(), Some("7a60".to_string()))));
+        assert_eq!(names.len(), 4);
+
+        let gpu = PciDevice {
+            class: 0x030000,
+            ..PciDevice::default()
+        };
+        assert_eq!(gpu.class_name(), "VGA compatible controller");
+        let bridge = PciDevice {
+            class: 0x068000,
+            ..PciDevice::default()
+        };
+        assert_eq!(bridge.class_name(), "Bridge");
+    }
+}
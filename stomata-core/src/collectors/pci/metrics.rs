@@ -0,0 +1,22 @@
+//! PCI device type
+
+/// A PCI device from `/sys/bus/pci/devices`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PciDevice {
+    /// Domain, bus, device and function, e.g. `0000:01:00.0`
+    pub address: String,
+    /// 24 bit class code, e.g. `0x030000` for a VGA controller
+    pub class: u32,
+    /// Hex vendor id without prefix, e.g. `10de`
+    pub vendor_id: String,
+    /// Hex device id without prefix
+    pub device_id: String,
+    /// Vendor name from the pci.ids database when it is installed
+    pub vendor_name: Option<String>,
+    /// Device name from the pci.ids database when it is installed
+    pub device_name: Option<String>,
+    /// Kernel driver in use, e.g. `nvidia` or `vfio-pci`
+    pub driver: Option<String>,
+    /// IOMMU group, `None` when the IOMMU is disabled
+    pub iommu_group: Option<u32>,
+}
@@ -0,0 +1,6 @@
+//! PCI devices from `/sys/bus/pci/devices`
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::PciDevice;
@@ -0,0 +1,299 @@
+//! Discovering and running plugins
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::{
+        command::run_with_timeout,
+        plugins::metrics::{Plugin, PluginMetrics, PluginOutput, PluginRecord},
+        poller::Poller,
+    },
+    constants::{PLUGIN_TIMEOUT_MS, PLUGINS_DIR_NAME, PLUGINS_RUN_INTERVAL_MS},
+    errors::{CoreError, CoreResult},
+};
+
+/// Default plugins directory, `<config dir>/stomata/plugins`
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("stomata").join(PLUGINS_DIR_NAME))
+}
+
+/// Parses what a plugin printed on stdout.
+///
+/// A plugin prints either a single JSON document or newline delimited JSON
+/// (one document per line), each shaped like
+/// `{"title": "GPU", "metrics": [{"label": "temp", "value": 61.0, "unit": "C", "max": 100.0}]}`.
+/// With NDJSON the lines are merged in order, so a later line overrides an
+/// earlier metric with the same label.
+pub fn parse_plugin_output(stdout: &str) -> CoreResult<PluginRecord> {
+    if let Ok(record) = serde_json::from_str::<PluginRecord>(stdout) {
+        return Ok(record);
+    }
+
+    let mut merged = PluginRecord::default();
+    for line in stdout.lines().filter(|line| !line.trim().is_empty()) {
+        let record: PluginRecord = serde_json::from_str(line)
+            .map_err(|err| CoreError::collection(format!("invalid plugin line: {line}: {err}")))?;
+        if record.title.is_some() {
+            merged.title = record.title;
+        }
+        for metric in record.metrics {
+            match merged.metrics.iter_mut().find(|m| m.label == metric.label) {
+                Some(existing) => *existing = metric,
+                None => merged.metrics.push(metric),
+            }
+        }
+    }
+    Ok(merged)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+impl Plugin {
+    /// Scans `dir` for executables. Hidden files are skipped and a missing
+    /// directory simply yields no plugins. Plugins are named by their full
+    /// file name, so `gpu.sh` and `gpu.py` stay two plugins.
+    pub fn discover(dir: &Path) -> Vec<Plugin> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut plugins: Vec<Plugin> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| is_executable(path))
+            .filter_map(|path| {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                if name.starts_with('.') {
+                    return None;
+                }
+                Some(Plugin { name, path })
+            })
+            .collect();
+
+        plugins.sort_by(|a, b| a.name.cmp(&b.name));
+        plugins
+    }
+
+    /// Runs the plugin once and parses its output. The plugin is killed if it
+    /// does not exit within `PLUGIN_TIMEOUT_MS`.
+    pub fn run(&self) -> CoreResult<PluginRecord> {
+        let output = run_with_timeout(
+            &mut Command::new(&self.path),
+            Duration::from_millis(PLUGIN_TIMEOUT_MS),
+        )?;
+        parse_plugin_output(&output)
+    }
+}
+
+impl PluginMetrics {
+    /// Runs every plugin once, a failing plugin only sets its `error`.
+    ///
+    /// Blocks for as long as the plugins take, up to `PLUGIN_TIMEOUT_MS`
+    /// each; [`PluginRunner`] runs them off the calling thread.
+    pub fn run(plugins: &[Plugin]) -> Self {
+        let plugins = plugins
+            .iter()
+            .map(|plugin| match plugin.run() {
+                Ok(record) => PluginOutput {
+                    name: plugin.name.clone(),
+                    record,
+                    ..Default::default()
+                },
+                Err(err) => {
+                    tracing::warn!(plugin = %plugin.name, "plugin failed: {err}");
+                    PluginOutput {
+                        name: plugin.name.clone(),
+                        error: Some(err.to_string()),
+                        ..Default::default()
+                    }
+                }
+            })
+            .collect();
+
+        Self {
+            timestamp: Utc::now(),
+            plugins_dir: plugins_dir(),
+            plugins,
+        }
+    }
+
+    /// Every plugin marked as not run yet
+    fn pending(plugins: &[Plugin]) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            plugins_dir: plugins_dir(),
+            plugins: plugins
+                .iter()
+                .map(|plugin| PluginOutput {
+                    name: plugin.name.clone(),
+                    pending: true,
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Runs the plugins on a background [`Poller`], so a slow plugin never
+/// holds up a fetch.
+///
+/// The thread starts on the first [`fetch`](Self::fetch) and runs every
+/// plugin each interval, skipping the runs nobody fetched in between. It
+/// stops when the runner is dropped.
+#[derive(Debug)]
+pub struct PluginRunner {
+    plugins: Vec<Plugin>,
+    poller: Poller<PluginMetrics>,
+}
+
+impl PluginRunner {
+    /// A runner for `plugins`, running them every
+    /// `PLUGINS_RUN_INTERVAL_MS`
+    pub fn new(plugins: Vec<Plugin>) -> Self {
+        let run = plugins.clone();
+        Self {
+            plugins,
+            poller: Poller::new(Duration::from_millis(PLUGINS_RUN_INTERVAL_MS), move || {
+                PluginMetrics::run(&run)
+            }),
+        }
+    }
+
+    /// Sets the time between two runs
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.poller = self.poller.with_interval(interval);
+        self
+    }
+
+    /// The plugins being run
+    pub fn plugins(&self) -> &[Plugin] {
+        &self.plugins
+    }
+
+    /// Output of the latest run, with every plugin pending until the
+    /// first run finished
+    pub fn fetch(&mut self) -> PluginMetrics {
+        if self.plugins.is_empty() {
+            return PluginMetrics::pending(&self.plugins);
+        }
+        self.poller
+            .latest()
+            .unwrap_or_else(|| PluginMetrics::pending(&self.plugins))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_document() {
+        let output = r#"{
+            "title": "GPU",
+            "metrics": [{"label": "temp", "value": 61.5, "unit": "C", "max": 100}]
+        }"#;
+        let record = parse_plugin_output(output).unwrap();
+        assert_eq!(record.title.as_deref(), Some("GPU"));
+        assert_eq!(record.metrics.len(), 1);
+        assert_eq!(record.metrics[0].max, Some(100.0));
+    }
+
+    #[test]
+    fn test_parse_ndjson_later_lines_override() {
+        let output = "{\"metrics\": [{\"label\": \"a\", \"value\": 1}]}\n\n{\"metrics\": [{\"label\": \"a\", \"value\": 2}, {\"label\": \"b\", \"value\": 3}]}\n";
+        let record = parse_plugin_output(output).unwrap();
+        assert_eq!(record.metrics.len(), 2);
+        assert_eq!(record.metrics[0].value, 2.0);
+        assert_eq!(record.metrics[1].label, "b");
+    }
+
+    #[test]
+    fn test_parse_invalid_output_fails() {
+        assert!(parse_plugin_output("not json").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_runner_does_not_wait_for_plugins() {
+        use std::{
+            os::unix::fs::PermissionsExt,
+            thread,
+            time::{Duration, Instant},
+        };
+
+        let dir = std::env::temp_dir().join(format!("stomata-plugins-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("slow");
+        fs::write(
+            &script,
+            "#!/bin/sh\nsleep 0.2\necho '{\"metrics\": [{\"label\": \"a\", \"value\": 7}]}'\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut runner =
+            PluginRunner::new(Plugin::discover(&dir)).with_interval(Duration::from_millis(50));
+
+        let started = Instant::now();
+        let first = runner.fetch();
+        assert!(started.elapsed() < Duration::from_millis(150));
+        assert_eq!(first.plugins.len(), 1);
+        assert!(first.plugins[0].pending);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let output = loop {
+            let metrics = runner.fetch();
+            if !metrics.plugins[0].pending || Instant::now() > deadline {
+                break metrics.plugins[0].clone();
+            }
+            thread::sleep(Duration::from_millis(20));
+        };
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(output.error, None);
+        assert_eq!(output.record.metrics[0].value, 7.0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover_keeps_plugins_sharing_a_stem() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("stomata-plugins-stem-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["gpu.sh", "gpu.py", ".hidden"] {
+            let script = dir.join(name);
+            fs::write(&script, "#!/bin/sh\n").unwrap();
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let names: Vec<String> = Plugin::discover(&dir)
+            .into_iter()
+            .map(|plugin| plugin.name)
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(names, ["gpu.py", "gpu.sh"]);
+    }
+
+    #[test]
+    fn test_runner_without_plugins() {
+        let mut runner = PluginRunner::new(Vec::new());
+        assert!(runner.fetch().plugins.is_empty());
+        assert!(!runner.poller.is_started());
+    }
+}
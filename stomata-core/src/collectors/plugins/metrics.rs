@@ -0,0 +1,67 @@
+//! Plugin types
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An executable discovered in the plugins directory
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    /// File name of the executable
+    pub name: String,
+    /// Full path of the executable
+    pub path: PathBuf,
+}
+
+/// A single metric emitted by a plugin.
+///
+/// Values with a `max` are rendered as gauges, everything else as text.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginValue {
+    /// What the value measures
+    pub label: String,
+    /// The reading
+    pub value: f64,
+    /// Unit appended to the value, e.g. `°C`
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// Upper bound of the gauge
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+/// One JSON document (or one NDJSON line) printed by a plugin on stdout
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginRecord {
+    /// Heading of the plugin's panel, its name when unset
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Values to display
+    #[serde(default)]
+    pub metrics: Vec<PluginValue>,
+}
+
+/// Result of running a single plugin once
+#[derive(Debug, Clone, Default)]
+pub struct PluginOutput {
+    /// File name of the plugin
+    pub name: String,
+    /// What the plugin printed, empty on error
+    pub record: PluginRecord,
+    /// Why the plugin failed, e.g. a timeout or invalid JSON
+    pub error: Option<String>,
+    /// The plugin didn't finish its first run yet
+    pub pending: bool,
+}
+
+/// Output of every discovered plugin
+#[derive(Debug, Clone)]
+pub struct PluginMetrics {
+    /// When the plugins were run
+    pub timestamp: DateTime<Utc>,
+    /// Directory the plugins were discovered in, `None` without a config dir
+    pub plugins_dir: Option<PathBuf>,
+    /// Plugins sorted by name
+    pub plugins: Vec<PluginOutput>,
+}
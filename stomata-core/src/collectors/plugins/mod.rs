@@ -0,0 +1,7 @@
+//! External plugin executables that print metrics as JSON
+
+pub mod collectors;
+pub mod metrics;
+
+pub use collectors::PluginRunner;
+pub use metrics::{Plugin, PluginMetrics};
@@ -0,0 +1,176 @@
+//! Running slow collectors on a background thread
+//!
+//! Collectors shelling out to helpers like `bluetoothctl` or running
+//! plugins can take seconds. A [`Poller`] runs them off the calling thread
+//! and serves the latest result, so a fetch never waits for them.
+
+use std::{
+    fmt,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+/// Runs a collector on a background thread and keeps its latest result.
+///
+/// The thread starts on the first [`latest`](Self::latest) and runs the
+/// collector each interval, skipping the runs nobody asked for in between.
+/// It stops when the poller is dropped.
+pub struct Poller<T> {
+    /// Taken by the thread when it starts
+    collect: Option<Box<dyn FnMut() -> T + Send>>,
+    interval: Duration,
+    latest: Arc<Mutex<Option<T>>>,
+    /// Asked for since the last run
+    wanted: Arc<AtomicBool>,
+    /// Wakes the thread for a run right away, dropping it stops the thread
+    wake: Option<Sender<()>>,
+}
+
+/// Asks a [`Poller`] for a run right away from another thread, e.g. once
+/// an action changed what it collects
+#[derive(Debug, Clone)]
+pub struct PollerRefresh {
+    wanted: Arc<AtomicBool>,
+    wake: Option<Sender<()>>,
+}
+
+impl<T: Clone + Send + 'static> Poller<T> {
+    /// A poller running `collect` every `interval`
+    pub fn new(interval: Duration, collect: impl FnMut() -> T + Send + 'static) -> Self {
+        Self {
+            collect: Some(Box::new(collect)),
+            interval,
+            latest: Arc::default(),
+            wanted: Arc::default(),
+            wake: None,
+        }
+    }
+
+    /// Sets the time between two runs
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Result of the latest run, `None` until the first run finished
+    pub fn latest(&mut self) -> Option<T> {
+        self.wanted.store(true, Ordering::Relaxed);
+        if self.wake.is_none() {
+            self.start();
+        }
+        self.latest
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Whether the thread was started by a first [`latest`](Self::latest)
+    pub fn is_started(&self) -> bool {
+        self.wake.is_some()
+    }
+
+    /// A handle running the collector again right away once used, without
+    /// waiting for the interval
+    pub fn refresher(&self) -> PollerRefresh {
+        PollerRefresh {
+            wanted: Arc::clone(&self.wanted),
+            wake: self.wake.clone(),
+        }
+    }
+
+    fn start(&mut self) {
+        let Some(mut collect) = self.collect.take() else {
+            return;
+        };
+        let (wake, woken) = mpsc::channel::<()>();
+        let latest = Arc::clone(&self.latest);
+        let wanted = Arc::clone(&self.wanted);
+        let interval = self.interval;
+        thread::spawn(move || {
+            loop {
+                if wanted.swap(false, Ordering::Relaxed) {
+                    let value = collect();
+                    *latest.lock().unwrap_or_else(PoisonError::into_inner) = Some(value);
+                }
+                if woken.recv_timeout(interval) == Err(RecvTimeoutError::Disconnected) {
+                    break;
+                }
+            }
+        });
+        self.wake = Some(wake);
+    }
+}
+
+impl PollerRefresh {
+    /// Wakes the poller for a run, picked up by the next
+    /// [`latest`](Poller::latest) once it finished
+    pub fn refresh(&self) {
+        self.wanted.store(true, Ordering::Relaxed);
+        if let Some(wake) = &self.wake {
+            // the poller may be gone already
+            let _ = wake.send(());
+        }
+    }
+}
+
+impl<T> fmt::Debug for Poller<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Poller")
+            .field("interval", &self.interval)
+            .field("started", &self.wake.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::AtomicUsize, time::Instant};
+
+    use super::*;
+
+    /// Calls `latest` until it has a value other than `skip`
+    fn wait_for(poller: &mut Poller<usize>, skip: Option<usize>) -> Option<usize> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let latest = poller.latest();
+            if latest != skip || Instant::now() > deadline {
+                return latest;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_latest_does_not_wait_for_the_collector() {
+        let mut poller = Poller::new(Duration::from_secs(3600), || {
+            thread::sleep(Duration::from_millis(200));
+            1
+        });
+        assert!(!poller.is_started());
+
+        let started = Instant::now();
+        assert_eq!(poller.latest(), None);
+        assert!(started.elapsed() < Duration::from_millis(150));
+        assert!(poller.is_started());
+        assert_eq!(wait_for(&mut poller, None), Some(1));
+    }
+
+    #[test]
+    fn test_refresh_runs_before_the_interval() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&runs);
+        let mut poller = Poller::new(Duration::from_secs(3600), move || {
+            counted.fetch_add(1, Ordering::Relaxed) + 1
+        });
+        assert_eq!(wait_for(&mut poller, None), Some(1));
+
+        let refresher = poller.refresher();
+        thread::spawn(move || refresher.refresh());
+        assert_eq!(wait_for(&mut poller, Some(1)), Some(2));
+    }
+}
@@ -0,0 +1,212 @@
+//! Reading backlights and the active power profile
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use crate::{
+    collectors::{
+        command::run_with_timeout,
+        power::metrics::{Backlight, PowerMetrics, PowerProfile},
+    },
+    constants::{BACKLIGHT_DIR, POWER_PROFILES_TIMEOUT_MS},
+    errors::{CoreError, CoreResult},
+};
+
+impl PowerMetrics {
+    /// Reads the backlight and the active power profile.
+    ///
+    /// Either is `None` on machines without them, e.g. desktops without a
+    /// backlight or systems without power-profiles-daemon.
+    pub fn fetch() -> Self {
+        let available_profiles = powerprofilesctl(&["list"])
+            .map(|list| parse_profiles_list(&list))
+            .unwrap_or_default();
+        let profile = if available_profiles.is_empty() {
+            None
+        } else {
+            powerprofilesctl(&["get"])
+                .ok()
+                .and_then(|name| PowerProfile::from_name(name.trim()))
+        };
+
+        Self {
+            backlight: Backlight::fetch(),
+            profile,
+            available_profiles,
+        }
+    }
+
+    /// The profile after the active one, wrapping around
+    pub fn next_profile(&self) -> Option<PowerProfile> {
+        let current = self
+            .available_profiles
+            .iter()
+            .position(|profile| Some(*profile) == self.profile)?;
+        self.available_profiles
+            .get((current + 1) % self.available_profiles.len())
+            .copied()
+    }
+}
+
+impl Backlight {
+    /// Reads the first backlight device, if any
+    pub fn fetch() -> Option<Self> {
+        let mut devices: Vec<PathBuf> = fs::read_dir(BACKLIGHT_DIR)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        devices.sort();
+        devices.iter().find_map(|dir| Self::read(dir))
+    }
+
+    /// Reads the backlight device at `dir`
+    pub fn read(dir: &Path) -> Option<Self> {
+        let read_value = |file: &str| -> Option<u64> {
+            fs::read_to_string(dir.join(file)).ok()?.trim().parse().ok()
+        };
+        Some(Self {
+            name: dir.file_name()?.to_string_lossy().to_string(),
+            brightness: read_value("brightness")?,
+            max_brightness: read_value("max_brightness").filter(|max| *max > 0)?,
+        })
+    }
+
+    /// Brightness in percent of the maximum
+    pub fn percent(&self) -> u32 {
+        (self.brightness * 100 / self.max_brightness) as u32
+    }
+
+    /// Sets the brightness to `percent` of the maximum, never below 1% so
+    /// the screen doesn't go black.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the brightness file can't be written; it is
+    /// owned by root unless a udev rule grants access to the `video` group.
+    pub fn set_percent(&self, percent: u32) -> CoreResult<()> {
+        let percent = percent.clamp(1, 100) as u64;
+        let value = (self.max_brightness * percent / 100).max(1);
+        let path = Path::new(BACKLIGHT_DIR).join(&self.name).join("brightness");
+        fs::write(&path, value.to_string()).map_err(|err| match err.kind() {
+            ErrorKind::PermissionDenied => CoreError::PermissionDenied(format!(
+                "writing {} needs root or a udev rule granting the video group write access",
+                path.display()
+            )),
+            _ => CoreError::io(format!("failed to write {}", path.display()), err),
+        })
+    }
+}
+
+impl PowerProfile {
+    /// Parses a profile name as printed by `powerprofilesctl`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "power-saver" => Some(PowerProfile::PowerSaver),
+            "balanced" => Some(PowerProfile::Balanced),
+            "performance" => Some(PowerProfile::Performance),
+            _ => None,
+        }
+    }
+
+    /// Profile name as used by `powerprofilesctl`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PowerProfile::PowerSaver => "power-saver",
+            PowerProfile::Balanced => "balanced",
+            PowerProfile::Performance => "performance",
+        }
+    }
+
+    /// Makes this the active profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns the powerprofilesctl error, e.g. when polkit denies the change.
+    pub fn apply(&self) -> CoreResult<()> {
+        powerprofilesctl(&["set", self.as_str()])
+            .map_err(|err| err.context(format!("failed to switch to {}", self.as_str())))?;
+        Ok(())
+    }
+}
+
+fn powerprofilesctl(args: &[&str]) -> CoreResult<String> {
+    run_with_timeout(
+        Command::new("powerprofilesctl").args(args),
+        Duration::from_millis(POWER_PROFILES_TIMEOUT_MS),
+    )
+}
+
+/// Parses the output of `powerprofilesctl list`, where each profile is a
+/// `name:` line followed by more indented `key: value` details and the
+/// active profile is prefixed with `*`
+pub fn parse_profiles_list(output: &str) -> Vec<PowerProfile> {
+    let mut profiles: Vec<PowerProfile> = output
+        .lines()
+        .filter_map(|line| {
+            let name = line
+                .trim()
+                .trim_start_matches('*')
+                .trim()
+                .strip_suffix(':')?;
+            PowerProfile::from_name(name)
+        })
+        .collect();
+    // powerprofilesctl lists the most performant profile first
+    profiles.reverse();
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profiles_list() {
+        let output = "  performance:
+    CpuDriver:\tintel_pstate
+    Degraded:   no
+
+* balanced:
+    CpuDriver:\tintel_pstate
+    PlatformDriver:\tplatform_profile
+
+  power-saver:
+    CpuDriver:\tintel_pstate
+";
+        let profiles = parse_profiles_list(output);
+        assert_eq!(
+            profiles,
+            vec![
+                PowerProfile::PowerSaver,
+                PowerProfile::Balanced,
+                PowerProfile::Performance
+            ]
+        );
+
+        let metrics = PowerMetrics {
+            backlight: None,
+            profile: Some(PowerProfile::Performance),
+            available_profiles: profiles,
+        };
+        assert_eq!(metrics.next_profile(), Some(PowerProfile::PowerSaver));
+    }
+
+    #[test]
+    fn test_read_backlight() {
+        let dir = std::env::temp_dir().join(format!("stomata-backlight-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("brightness"), "240\n").unwrap();
+        fs::write(dir.join("max_brightness"), "960\n").unwrap();
+
+        let backlight = Backlight::read(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(backlight.brightness, 240);
+        assert_eq!(backlight.percent(), 25);
+    }
+}
@@ -0,0 +1,77 @@
+//! Sleep inhibitor held while the monitor runs
+//!
+//! Uses `systemd-inhibit`, which holds a logind inhibitor lock for as long
+//! as the command it wraps runs. Wrapping `sleep infinity` keeps the lock
+//! until the child is killed, which happens when the inhibitor is dropped.
+
+use std::{
+    io::Read,
+    process::{Child, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    constants::SLEEP_INHIBITOR_STARTUP_MS,
+    errors::{CoreError, CoreResult},
+};
+
+/// Blocks system suspend and idle sleep until dropped
+#[derive(Debug)]
+pub struct SleepInhibitor {
+    child: Child,
+}
+
+impl SleepInhibitor {
+    /// Takes a sleep and idle inhibitor lock, listed with `why` in
+    /// `systemd-inhibit --list`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `systemd-inhibit` isn't installed or exits
+    /// right away, e.g. when polkit denies the lock.
+    pub fn acquire(why: &str) -> CoreResult<Self> {
+        let mut child = Command::new("systemd-inhibit")
+            .args([
+                "--what=sleep:idle",
+                "--who=stomata",
+                &format!("--why={why}"),
+                "--mode=block",
+                "sleep",
+                "infinity",
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| CoreError::io("failed to start systemd-inhibit", err))?;
+
+        // a denied lock makes systemd-inhibit exit immediately
+        thread::sleep(Duration::from_millis(SLEEP_INHIBITOR_STARTUP_MS));
+        let exited = child
+            .try_wait()
+            .map_err(|err| CoreError::io("failed to wait for systemd-inhibit", err))?;
+        if let Some(status) = exited {
+            let mut stderr = String::new();
+            if let Some(mut pipe) = child.stderr.take() {
+                let _ = pipe.read_to_string(&mut stderr);
+            }
+            return Err(match stderr.lines().find(|line| !line.trim().is_empty()) {
+                Some(line) => CoreError::collection(format!(
+                    "systemd-inhibit exited with {status}: {}",
+                    line.trim()
+                )),
+                None => CoreError::collection(format!("systemd-inhibit exited with {status}")),
+            });
+        }
+        Ok(Self { child })
+    }
+}
+
+impl Drop for SleepInhibitor {
+    /// Releases the lock by ending `systemd-inhibit`
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,34 @@
+//! Power types
+
+/// Screen backlight from `/sys/class/backlight`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backlight {
+    /// Name of the backlight device, e.g. `intel_backlight`
+    pub name: String,
+    /// Raw brightness value, between 0 and `max_brightness`
+    pub brightness: u64,
+    /// Brightness at full power
+    pub max_brightness: u64,
+}
+
+/// Power profile of power-profiles-daemon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerProfile {
+    /// Lower performance to save battery
+    PowerSaver,
+    /// Balance performance and consumption
+    Balanced,
+    /// Highest performance
+    Performance,
+}
+
+/// Backlight and power profile at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct PowerMetrics {
+    /// `None` when the machine has no controllable backlight
+    pub backlight: Option<Backlight>,
+    /// `None` when power-profiles-daemon isn't available
+    pub profile: Option<PowerProfile>,
+    /// Profiles supported by the machine, in the order they are listed
+    pub available_profiles: Vec<PowerProfile>,
+}
@@ -0,0 +1,8 @@
+//! Screen brightness, power profiles and sleep inhibition
+
+pub mod collectors;
+pub mod inhibit;
+pub mod metrics;
+
+pub use inhibit::SleepInhibitor;
+pub use metrics::{Backlight, PowerMetrics, PowerProfile};
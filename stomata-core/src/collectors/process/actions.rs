@@ -0,0 +1,439 @@
+//! Actions that change a running process
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{MAX_NICE, MIN_NICE},
+    errors::{CoreError, CoreResult},
+};
+
+/// Returns the nice value of `pid`.
+///
+/// # Errors
+///
+/// Returns an error if the process doesn't exist or the platform has no
+/// nice values. Unix platforms stomata can't reset `errno` on are
+/// reported as unsupported.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+    target_os = "aix"
+))]
+pub fn get_priority(pid: u32) -> CoreResult<i32> {
+    // getpriority can legitimately return -1, so errors are told apart by errno
+    // SAFETY: errno_location returns the calling thread's errno, valid for
+    // writes for as long as the thread lives
+    unsafe { *errno_location() = 0 };
+    // SAFETY: getpriority only reads its integer arguments, an unknown pid
+    // is reported through errno
+    let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t) };
+    if nice == -1 && std::io::Error::last_os_error().raw_os_error() != Some(0) {
+        return Err(priority_error(pid, std::io::Error::last_os_error()));
+    }
+    Ok(nice)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+    target_os = "aix"
+)))]
+pub fn get_priority(_pid: u32) -> CoreResult<i32> {
+    Err(CoreError::unsupported(
+        "reading nice values isn't supported on this platform",
+    ))
+}
+
+/// Sets the nice value of `pid`, from -20 (highest priority) to 19
+/// (lowest).
+///
+/// # Errors
+///
+/// Returns an error if `nice` is out of range, the process doesn't exist
+/// or the caller lacks permission. Only root (or `CAP_SYS_NICE`) may lower
+/// the nice value or renice another user's process.
+#[cfg(unix)]
+pub fn set_priority(pid: u32, nice: i32) -> CoreResult<()> {
+    if !(MIN_NICE..=MAX_NICE).contains(&nice) {
+        return Err(CoreError::invalid(format!(
+            "nice value {nice} is outside {MIN_NICE}..={MAX_NICE}"
+        )));
+    }
+    // SAFETY: setpriority only reads its integer arguments, failures are
+    // reported through errno
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) } == -1 {
+        return Err(priority_error(pid, std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn set_priority(_pid: u32, _nice: i32) -> CoreResult<()> {
+    Err(CoreError::unsupported(
+        "nice values are only supported on Unix",
+    ))
+}
+
+/// Returns the CPUs `pid` may run on.
+///
+/// # Errors
+///
+/// Returns an error if the process doesn't exist or the platform isn't
+/// Linux.
+#[cfg(target_os = "linux")]
+pub fn get_affinity(pid: u32) -> CoreResult<Vec<usize>> {
+    // SAFETY: cpu_set_t is a plain bit array, all zeroes is the empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::cpu_set_t>();
+    // SAFETY: `set` is a live cpu_set_t of exactly `size` bytes for the
+    // kernel to fill
+    if unsafe { libc::sched_getaffinity(pid as libc::pid_t, size, &mut set) } == -1 {
+        return Err(affinity_error(pid, std::io::Error::last_os_error()));
+    }
+    Ok((0..libc::CPU_SETSIZE as usize)
+        // SAFETY: every index is below CPU_SETSIZE, inside the set
+        .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &set) })
+        .collect())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_affinity(_pid: u32) -> CoreResult<Vec<usize>> {
+    Err(CoreError::unsupported(
+        "CPU affinity is only supported on Linux",
+    ))
+}
+
+/// Pins `pid` to `cpus`.
+///
+/// # Errors
+///
+/// Returns an error if `cpus` is empty or names no online CPU, the process
+/// doesn't exist, or it belongs to another user and the caller lacks
+/// `CAP_SYS_NICE`.
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: u32, cpus: &[usize]) -> CoreResult<()> {
+    if cpus.is_empty() {
+        return Err(CoreError::invalid(
+            "a process must be allowed to run on at least one CPU",
+        ));
+    }
+    // SAFETY: cpu_set_t is a plain bit array, all zeroes is the empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for cpu in cpus {
+        // CPU_SET doesn't check its index, one past the set would write
+        // out of bounds
+        if *cpu >= libc::CPU_SETSIZE as usize {
+            return Err(CoreError::invalid(format!("CPU {cpu} is out of range")));
+        }
+        // SAFETY: `cpu` was checked to be below CPU_SETSIZE just above
+        unsafe { libc::CPU_SET(*cpu, &mut set) };
+    }
+    let size = std::mem::size_of::<libc::cpu_set_t>();
+    // SAFETY: `set` is an initialized cpu_set_t of exactly `size` bytes,
+    // only read by the kernel
+    if unsafe { libc::sched_setaffinity(pid as libc::pid_t, size, &set) } == -1 {
+        return Err(affinity_error(pid, std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity(_pid: u32, _cpus: &[usize]) -> CoreResult<()> {
+    Err(CoreError::unsupported(
+        "CPU affinity is only supported on Linux",
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn affinity_error(pid: u32, err: std::io::Error) -> CoreError {
+    match err.raw_os_error() {
+        Some(libc::EPERM) => CoreError::PermissionDenied(
+            "changing the affinity of another user's process needs root or CAP_SYS_NICE"
+                .to_string(),
+        ),
+        Some(libc::ESRCH) => CoreError::io(format!("process {pid} does not exist"), err),
+        Some(libc::EINVAL) => CoreError::invalid("none of the selected CPUs is online"),
+        _ => CoreError::io(format!("failed to change the affinity of {pid}"), err),
+    }
+}
+
+/// Formats CPU numbers in the kernel's cpulist format, e.g. `0-3,6`
+pub fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut cpus = cpus.to_vec();
+    cpus.sort_unstable();
+    cpus.dedup();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Signals that can be sent to a process from the signals menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Signal {
+    /// Hangup, often used to reload the configuration
+    Hup,
+    /// Interrupt, like Ctrl-C
+    Int,
+    /// Polite request to terminate
+    Term,
+    /// Terminate immediately, can't be caught
+    Kill,
+    /// Suspend, can't be caught
+    Stop,
+    /// Resume a stopped process
+    Cont,
+    /// User-defined signal 1
+    Usr1,
+    /// User-defined signal 2
+    Usr2,
+}
+
+impl Signal {
+    /// Every signal, in the order the menu lists them
+    pub const ALL: [Signal; 8] = [
+        Signal::Hup,
+        Signal::Int,
+        Signal::Term,
+        Signal::Kill,
+        Signal::Stop,
+        Signal::Cont,
+        Signal::Usr1,
+        Signal::Usr2,
+    ];
+
+    /// Signal name, e.g. `SIGTERM`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Signal::Hup => "SIGHUP",
+            Signal::Int => "SIGINT",
+            Signal::Term => "SIGTERM",
+            Signal::Kill => "SIGKILL",
+            Signal::Stop => "SIGSTOP",
+            Signal::Cont => "SIGCONT",
+            Signal::Usr1 => "SIGUSR1",
+            Signal::Usr2 => "SIGUSR2",
+        }
+    }
+
+    /// Parses a signal name, with or without the `SIG` prefix and in any
+    /// case, e.g. `TERM`, `sigkill` or `SIGHUP`
+    pub fn from_name(name: &str) -> Option<Signal> {
+        let name = name.to_ascii_uppercase();
+        let name = name.strip_prefix("SIG").unwrap_or(&name);
+        Signal::ALL
+            .into_iter()
+            .find(|signal| &signal.as_str()[3..] == name)
+    }
+
+    /// What the signal usually makes a process do
+    pub fn description(&self) -> &'static str {
+        match self {
+            Signal::Hup => "hang up, many daemons reload their config",
+            Signal::Int => "interrupt, like Ctrl+C",
+            Signal::Term => "ask to terminate gracefully",
+            Signal::Kill => "kill immediately, can't be caught",
+            Signal::Stop => "suspend, can't be caught",
+            Signal::Cont => "resume a stopped process",
+            Signal::Usr1 => "user defined 1",
+            Signal::Usr2 => "user defined 2",
+        }
+    }
+
+    #[cfg(unix)]
+    fn number(&self) -> libc::c_int {
+        match self {
+            Signal::Hup => libc::SIGHUP,
+            Signal::Int => libc::SIGINT,
+            Signal::Term => libc::SIGTERM,
+            Signal::Kill => libc::SIGKILL,
+            Signal::Stop => libc::SIGSTOP,
+            Signal::Cont => libc::SIGCONT,
+            Signal::Usr1 => libc::SIGUSR1,
+            Signal::Usr2 => libc::SIGUSR2,
+        }
+    }
+}
+
+/// Sends `signal` to `pid`.
+///
+/// # Errors
+///
+/// Returns an error if the process doesn't exist or belongs to another
+/// user and the caller isn't root.
+#[cfg(unix)]
+pub fn send_signal(pid: u32, signal: Signal) -> CoreResult<()> {
+    // pid 0 and negative pids address process groups, never send to those
+    let Ok(pid_t) = libc::pid_t::try_from(pid) else {
+        return Err(CoreError::invalid(format!("invalid pid {pid}")));
+    };
+    if pid_t <= 0 {
+        return Err(CoreError::invalid(format!("invalid pid {pid}")));
+    }
+    // SAFETY: kill only reads its integer arguments and touches no memory,
+    // pids of 0 and below were rejected above
+    if unsafe { libc::kill(pid_t, signal.number()) } == -1 {
+        let err = std::io::Error::last_os_error();
+        return Err(match err.raw_os_error() {
+            Some(libc::EPERM) => CoreError::PermissionDenied(format!(
+                "sending {} to another user's process needs root",
+                signal.as_str()
+            )),
+            Some(libc::ESRCH) => CoreError::io(format!("process {pid} does not exist"), err),
+            _ => CoreError::io(format!("failed to send {} to {pid}", signal.as_str()), err),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn send_signal(_pid: u32, _signal: Signal) -> CoreResult<()> {
+    Err(CoreError::unsupported("signals are only supported on Unix"))
+}
+
+#[cfg(unix)]
+fn priority_error(pid: u32, err: std::io::Error) -> CoreError {
+    match err.raw_os_error() {
+        Some(libc::EPERM | libc::EACCES) => CoreError::PermissionDenied(
+            "lowering the nice value or renicing another user's process needs root or CAP_SYS_NICE"
+                .to_string(),
+        ),
+        Some(libc::ESRCH) => CoreError::io(format!("process {pid} does not exist"), err),
+        _ => CoreError::io(format!("failed to renice process {pid}"), err),
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__errno_location() }
+}
+
+#[cfg(any(
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly"
+))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__error() }
+}
+
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__errno() }
+}
+
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::___errno() }
+}
+
+#[cfg(target_os = "aix")]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::_Errno() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_priority() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+
+        let result = set_priority(pid, 5).and_then(|()| get_priority(pid));
+        assert!(set_priority(pid, MAX_NICE + 1).is_err());
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(result.unwrap(), 5);
+    }
+
+    #[test]
+    fn test_signal_from_name() {
+        assert_eq!(Signal::from_name("TERM"), Some(Signal::Term));
+        assert_eq!(Signal::from_name("sigkill"), Some(Signal::Kill));
+        assert_eq!(Signal::from_name("SIGUSR1"), Some(Signal::Usr1));
+        assert_eq!(Signal::from_name("SIGSEGV"), None);
+        assert_eq!(Signal::from_name(""), None);
+    }
+
+    #[test]
+    fn test_format_cpu_list() {
+        assert_eq!(format_cpu_list(&[0, 1, 2, 3, 6]), "0-3,6");
+        assert_eq!(format_cpu_list(&[5, 1, 3, 2]), "1-3,5");
+        assert_eq!(format_cpu_list(&[]), "");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_set_affinity() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+
+        let result = set_affinity(pid, &[0]).and_then(|()| get_affinity(pid));
+        let empty = set_affinity(pid, &[]);
+        let past_the_set = set_affinity(pid, &[0, libc::CPU_SETSIZE as usize]);
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(result.unwrap(), vec![0]);
+        assert!(empty.is_err());
+        assert!(matches!(past_the_set, Err(CoreError::InvalidInput(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_send_signal() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+
+        send_signal(pid, Signal::Stop).unwrap();
+        send_signal(pid, Signal::Cont).unwrap();
+        send_signal(pid, Signal::Usr1).unwrap();
+        let status = child.wait().unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGUSR1));
+        assert!(send_signal(0, Signal::Term).is_err());
+    }
+}
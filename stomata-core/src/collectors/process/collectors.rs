@@ -1,68 +1,457 @@
-use sysinfo::{Pid, Process, System};
+//! Converting sysinfo processes and reading `/proc/<pid>` details
 
-use crate::collectors::process::metrics::{ProcessData, SingleProcessData};
+use std::{ffi::OsString, fs};
 
-impl From<&Process> for ProcessData {
-    fn from(process: &Process) -> Self {
+use sysinfo::{DiskUsage, Process, Users};
+
+use crate::{
+    collectors::backend::SystemBackend,
+    collectors::cgroups::collectors::process_cgroup,
+    collectors::process::actions::{get_affinity, get_priority},
+    collectors::process::metrics::{
+        MemoryBreakdown, OpenFile, OpenFileKind, ProcessData, ProcessDiskUsage, SeccompMode,
+        SecurityContext, SingleProcessData,
+    },
+    constants::CAPABILITY_NAMES,
+};
+
+impl From<(&Process, &Users)> for ProcessData {
+    fn from((process, users): (&Process, &Users)) -> Self {
+        let status = proc_status(process.pid().as_u32());
+        let user = process.user_id().map(|uid| {
+            users
+                .get_user_by_id(uid)
+                .map(|user| user.name().to_string())
+                .unwrap_or_else(|| uid.to_string())
+        });
         ProcessData {
             pid: process.pid().as_u32(),
             name: process.name().to_string_lossy().to_string(),
             cpu_usage: process.cpu_usage(),
             memory: process.memory(),
             status: process.status().to_string(),
+            user,
+            command: process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect(),
+            exe_path: process.exe().map(|exe| exe.to_string_lossy().to_string()),
+            exe_deleted: is_exe_deleted(process.pid().as_u32()),
+            threads: status.as_deref().and_then(parse_thread_count),
+            swap: status.as_deref().and_then(parse_vm_swap),
+            cgroup: process_cgroup(process.pid().as_u32()),
+            parent_pid: process.parent().map(|pid| pid.as_u32()),
+            session_id: process.session_id().map(|pid| pid.as_u32()),
+            // rates need two refreshes, see `ProcessIoTracker::apply`
+            disk_read_rate: None,
+            disk_write_rate: None,
         }
     }
 }
 
+/// Checks whether the executable of `pid` was deleted or replaced on disk.
+///
+/// The kernel appends ` (deleted)` to the `/proc/<pid>/exe` link target once
+/// the original file is unlinked, which also happens when a package upgrade
+/// replaces the binary.
+#[cfg(target_os = "linux")]
+pub fn is_exe_deleted(pid: u32) -> bool {
+    fs::read_link(format!("/proc/{pid}/exe"))
+        .map(|target| target.to_string_lossy().ends_with(" (deleted)"))
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_exe_deleted(_pid: u32) -> bool {
+    false
+}
+
+/// Reads `/proc/<pid>/status`, for the thread count and swap usage that
+/// sysinfo doesn't provide: the process list is refreshed without its
+/// tasks, so sysinfo can't count them, and it has no per-process swap.
+#[cfg(target_os = "linux")]
+pub fn proc_status(pid: u32) -> Option<String> {
+    fs::read_to_string(format!("/proc/{pid}/status")).ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn proc_status(_pid: u32) -> Option<String> {
+    None
+}
+
+/// Parses the `Threads` field of a `/proc/<pid>/status` file
+pub fn parse_thread_count(status: &str) -> Option<u32> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))
+        .and_then(|count| count.trim().parse().ok())
+}
+
+/// Parses the `VmSwap` field of a `/proc/<pid>/status` file, in bytes.
+/// Kernel threads have no such field.
+pub fn parse_vm_swap(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmSwap:"))
+        .and_then(|swap| swap.trim().strip_suffix("kB"))
+        .and_then(|kb| kb.trim().parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
 impl ProcessData {
-    pub fn fetch(system: &System) -> Vec<Self> {
-        let processes: Vec<ProcessData> =
-            system.processes().values().map(ProcessData::from).collect();
-        return processes;
+    /// Table columns of every process of the last refresh of `system`
+    pub fn fetch(system: &dyn SystemBackend) -> Vec<Self> {
+        system.process_table()
+    }
+}
+
+impl From<DiskUsage> for ProcessDiskUsage {
+    fn from(usage: DiskUsage) -> Self {
+        ProcessDiskUsage {
+            read_bytes: usage.read_bytes,
+            written_bytes: usage.written_bytes,
+            total_read_bytes: usage.total_read_bytes,
+            total_written_bytes: usage.total_written_bytes,
+        }
     }
 }
 
 // Single Process
-impl<'a> From<(&'a Process, Vec<&'a Process>)> for SingleProcessData<'a> {
-    fn from((process, tasks): (&'a Process, Vec<&'a Process>)) -> Self {
-        let disk_usage = process.disk_usage();
-        let current_working_dir = if let Some(cwd) = process.cwd() {
-            Some(cwd.to_string_lossy().to_string())
-        } else {
-            None
-        };
+impl From<(&Process, Vec<ProcessData>, &Users)> for SingleProcessData {
+    fn from((process, tasks, users): (&Process, Vec<ProcessData>, &Users)) -> Self {
+        let disk_usage = process.disk_usage().into();
+        let current_working_dir = process.cwd().map(|cwd| cwd.to_string_lossy().to_string());
         let start_time = process.start_time();
         let running_time = process.run_time();
-        let parent_pid = process.parent();
+        let parent_pid = process.parent().map(|pid| pid.as_u32());
+        let security = SecurityContext::fetch(process.pid().as_u32());
+        let environment = parse_environ(process.environ());
+        let open_files = OpenFile::list(process.pid().as_u32());
+        let memory_breakdown = MemoryBreakdown::fetch(process.pid().as_u32());
+        let nice = get_priority(process.pid().as_u32()).ok();
+        let cpu_affinity = get_affinity(process.pid().as_u32()).ok();
 
         SingleProcessData {
-            basic_process_data: ProcessData::from(process),
-            tasks: tasks,
+            basic_process_data: ProcessData::from((process, users)),
+            tasks,
             disk_usage,
             start_time,
             running_time,
             current_working_dir,
             parent_pid,
+            security,
+            environment,
+            open_files,
+            memory_breakdown,
+            nice,
+            cpu_affinity,
         }
     }
 }
 
-impl SingleProcessData<'_> {
-    pub fn fetch(system: &mut System, pid: u32) -> Option<SingleProcessData<'_>> {
-        if let Some(process) = system.process(Pid::from_u32(pid)) {
-            let tasks = if let Some(task_pids) = process.tasks() {
-                task_pids
-                    .iter()
-                    .filter_map(|p| system.process(*p))
-                    .collect()
-            } else {
-                Vec::new()
+/// Splits `NAME=value` entries of a process environment, sorted by name.
+/// Entries without `=` are kept with an empty value.
+pub fn parse_environ(environ: &[OsString]) -> Vec<(String, String)> {
+    let mut variables: Vec<(String, String)> = environ
+        .iter()
+        .map(|entry| {
+            let entry = entry.to_string_lossy();
+            match entry.split_once('=') {
+                Some((name, value)) => (name.to_string(), value.to_string()),
+                None => (entry.to_string(), String::new()),
+            }
+        })
+        .collect();
+    variables.sort();
+    variables
+}
+
+impl SingleProcessData {
+    /// Details of the process `pid`, `None` when it doesn't exist
+    pub fn fetch(system: &dyn SystemBackend, pid: u32) -> Option<SingleProcessData> {
+        system.process_details(pid)
+    }
+}
+
+impl MemoryBreakdown {
+    /// Reads `/proc/<pid>/smaps_rollup`, `None` when it can't be read
+    pub fn fetch(pid: u32) -> Option<Self> {
+        fs::read_to_string(format!("/proc/{pid}/smaps_rollup"))
+            .ok()
+            .and_then(|rollup| Self::parse(&rollup))
+    }
+
+    /// Parses the contents of a `smaps_rollup` file, whose values are in
+    /// kB. `None` when it has no `Rss` line.
+    pub fn parse(rollup: &str) -> Option<Self> {
+        let mut breakdown = MemoryBreakdown::default();
+        let mut has_rss = false;
+        for line in rollup.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
             };
+            let Some(kb) = value
+                .trim()
+                .strip_suffix("kB")
+                .and_then(|kb| kb.trim().parse::<u64>().ok())
+            else {
+                continue;
+            };
+            let bytes = kb * 1024;
+            match key {
+                "Rss" => {
+                    breakdown.rss = bytes;
+                    has_rss = true;
+                }
+                "Pss" => breakdown.pss = bytes,
+                "Shared_Clean" => breakdown.shared_clean = bytes,
+                "Shared_Dirty" => breakdown.shared_dirty = bytes,
+                "Private_Clean" => breakdown.private_clean = bytes,
+                "Private_Dirty" => breakdown.private_dirty = bytes,
+                "Anonymous" => breakdown.anonymous = bytes,
+                "Swap" => breakdown.swap = bytes,
+                "SwapPss" => breakdown.swap_pss = bytes,
+                _ => {}
+            }
+        }
+        has_rss.then_some(breakdown)
+    }
+}
 
-            let single_process_data = SingleProcessData::from((process, tasks));
-            Some(single_process_data)
+impl OpenFile {
+    /// Lists the open file descriptors of `pid`, `None` if they can't be read
+    #[cfg(target_os = "linux")]
+    pub fn list(pid: u32) -> Option<Vec<Self>> {
+        let mut files: Vec<OpenFile> = fs::read_dir(format!("/proc/{pid}/fd"))
+            .ok()?
+            .flatten()
+            .filter_map(|entry| {
+                let fd = entry.file_name().to_str()?.parse().ok()?;
+                // the descriptor may have been closed since listing the directory
+                let target = fs::read_link(entry.path()).ok()?;
+                let target = target.to_string_lossy().to_string();
+                Some(OpenFile {
+                    fd,
+                    kind: OpenFileKind::from_target(&target),
+                    target,
+                })
+            })
+            .collect();
+        files.sort_by_key(|file| file.fd);
+        Some(files)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn list(_pid: u32) -> Option<Vec<Self>> {
+        None
+    }
+}
+
+impl OpenFileKind {
+    /// Classifies the target of a `/proc/<pid>/fd` link
+    pub fn from_target(target: &str) -> Self {
+        if target.starts_with('/') {
+            OpenFileKind::File
+        } else if target.starts_with("socket:") {
+            OpenFileKind::Socket
+        } else if target.starts_with("pipe:") {
+            OpenFileKind::Pipe
+        } else if target.starts_with("anon_inode:") {
+            OpenFileKind::AnonInode
         } else {
-            None
+            OpenFileKind::Other
         }
     }
+
+    /// Lowercase name of the kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpenFileKind::File => "file",
+            OpenFileKind::Socket => "socket",
+            OpenFileKind::Pipe => "pipe",
+            OpenFileKind::AnonInode => "anon inode",
+            OpenFileKind::Other => "other",
+        }
+    }
+}
+
+impl SeccompMode {
+    /// Lowercase name of the mode
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeccompMode::Disabled => "disabled",
+            SeccompMode::Strict => "strict",
+            SeccompMode::Filter => "filter",
+        }
+    }
+}
+
+impl SecurityContext {
+    /// Reads the security context of `pid` from procfs.
+    ///
+    /// Fields that can't be read are left as `None`, so this never fails.
+    pub fn fetch(pid: u32) -> Self {
+        let mut context = fs::read_to_string(format!("/proc/{pid}/status"))
+            .map(|status| Self::parse_status(&status))
+            .unwrap_or_default();
+
+        context.lsm_label = fs::read_to_string(format!("/proc/{pid}/attr/current"))
+            .ok()
+            .map(|label| label.trim_end_matches(['\0', '\n']).to_string())
+            .filter(|label| !label.is_empty());
+
+        context
+    }
+
+    /// Parses the capability, seccomp and no_new_privs fields of a
+    /// `/proc/<pid>/status` file.
+    pub fn parse_status(status: &str) -> Self {
+        let mut context = SecurityContext::default();
+
+        for line in status.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key {
+                "CapEff" => context.cap_effective = u64::from_str_radix(value, 16).ok(),
+                "CapPrm" => context.cap_permitted = u64::from_str_radix(value, 16).ok(),
+                "CapBnd" => context.cap_bounding = u64::from_str_radix(value, 16).ok(),
+                "NoNewPrivs" => context.no_new_privs = Some(value == "1"),
+                "Seccomp" => {
+                    context.seccomp = match value {
+                        "0" => Some(SeccompMode::Disabled),
+                        "1" => Some(SeccompMode::Strict),
+                        "2" => Some(SeccompMode::Filter),
+                        _ => None,
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        context
+    }
+
+    /// Names of the capabilities set in `mask`. Bits without a known name
+    /// are reported as `CAP_<bit>`.
+    pub fn capability_names(mask: u64) -> Vec<String> {
+        (0..64)
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| match CAPABILITY_NAMES.get(bit) {
+                Some(name) => name.to_string(),
+                None => format!("CAP_{bit}"),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_smaps_rollup() {
+        let rollup =
+            "55d0c8a3c000-7ffd4b5fe000 ---p 00000000 00:00 0                          [rollup]
+Rss:               10240 kB
+Pss:                6144 kB
+Pss_Anon:           4096 kB
+Shared_Clean:       3072 kB
+Shared_Dirty:       1024 kB
+Private_Clean:      2048 kB
+Private_Dirty:      4096 kB
+Anonymous:          4096 kB
+Swap:                512 kB
+SwapPss:             256 kB
+";
+        let breakdown = MemoryBreakdown::parse(rollup).unwrap();
+        assert_eq!(breakdown.rss, 10240 * 1024);
+        assert_eq!(breakdown.pss, 6144 * 1024);
+        assert_eq!(breakdown.shared(), 4096 * 1024);
+        assert_eq!(breakdown.private(), 6144 * 1024);
+        assert_eq!(breakdown.swap, 512 * 1024);
+        assert_eq!(breakdown.swap_pss, 256 * 1024);
+        assert_eq!(MemoryBreakdown::parse(""), None);
+    }
+
+    #[test]
+    fn test_parse_thread_count() {
+        let status = "Name:\tpostgres\nState:\tS (sleeping)\nThreads:\t12\nSigQ:\t0/63504\n";
+        assert_eq!(parse_thread_count(status), Some(12));
+        assert_eq!(parse_thread_count("Name:\tinit\n"), None);
+    }
+
+    #[test]
+    fn test_parse_vm_swap() {
+        let status = "Name:\tjava\nVmRSS:\t  204800 kB\nVmSwap:\t   51200 kB\nThreads:\t40\n";
+        assert_eq!(parse_vm_swap(status), Some(50 * 1024 * 1024));
+        assert_eq!(parse_vm_swap("Name:\tkthreadd\nThreads:\t1\n"), None);
+    }
+
+    #[test]
+    fn test_parse_status_security_fields() {
+        let status = "Name:\tnginx\nCapPrm:\t0000000000003000\nCapEff:\t0000000000000400\nCapBnd:\t000001ffffffffff\nNoNewPrivs:\t1\nSeccomp:\t2\n";
+        let context = SecurityContext::parse_status(status);
+        assert_eq!(context.cap_effective, Some(0x400));
+        assert_eq!(context.cap_permitted, Some(0x3000));
+        assert_eq!(context.cap_bounding, Some(0x1ff_ffff_ffff));
+        assert_eq!(context.no_new_privs, Some(true));
+        assert_eq!(context.seccomp, Some(SeccompMode::Filter));
+    }
+
+    #[test]
+    fn test_parse_environ() {
+        let environ = ["PATH=/usr/bin:/bin", "TOKEN=a=b", "EMPTY="].map(OsString::from);
+        assert_eq!(
+            parse_environ(&environ),
+            vec![
+                ("EMPTY".to_string(), String::new()),
+                ("PATH".to_string(), "/usr/bin:/bin".to_string()),
+                ("TOKEN".to_string(), "a=b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_file_kind() {
+        assert_eq!(
+            OpenFileKind::from_target("/var/log/syslog"),
+            OpenFileKind::File
+        );
+        assert_eq!(
+            OpenFileKind::from_target("socket:[31337]"),
+            OpenFileKind::Socket
+        );
+        assert_eq!(OpenFileKind::from_target("pipe:[4242]"), OpenFileKind::Pipe);
+        assert_eq!(
+            OpenFileKind::from_target("anon_inode:[eventfd]"),
+            OpenFileKind::AnonInode
+        );
+        assert_eq!(
+            OpenFileKind::from_target("net:[4026531840]"),
+            OpenFileKind::Other
+        );
+
+        #[cfg(target_os = "linux")]
+        {
+            let files = OpenFile::list(std::process::id()).unwrap();
+            assert!(files.iter().any(|file| file.fd == 0));
+        }
+    }
+
+    #[test]
+    fn test_capability_names() {
+        assert_eq!(
+            SecurityContext::capability_names(0x400),
+            vec!["CAP_NET_BIND_SERVICE"]
+        );
+        assert_eq!(
+            SecurityContext::capability_names(0x3000),
+            vec!["CAP_NET_ADMIN", "CAP_NET_RAW"]
+        );
+        assert_eq!(SecurityContext::capability_names(1 << 50), vec!["CAP_50"]);
+        assert!(SecurityContext::capability_names(0).is_empty());
+    }
 }
@@ -0,0 +1,114 @@
+//! Totals of the processes sharing a user or an executable name
+
+use std::collections::HashMap;
+
+use crate::collectors::process::ProcessData;
+
+/// What processes are grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessGroupBy {
+    /// The user owning the process
+    User,
+    /// The executable name
+    Name,
+}
+
+impl ProcessGroupBy {
+    /// Lowercase name, e.g. for a table title
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProcessGroupBy::User => "user",
+            ProcessGroupBy::Name => "name",
+        }
+    }
+
+    /// Group key of `process`; processes without a user share `unknown`
+    pub fn key(self, process: &ProcessData) -> &str {
+        match self {
+            ProcessGroupBy::User => process.user.as_deref().unwrap_or("unknown"),
+            ProcessGroupBy::Name => &process.name,
+        }
+    }
+}
+
+/// Processes sharing a group key, with their summed usage
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessGroup {
+    /// User or executable name the processes share
+    pub key: String,
+    /// Summed CPU usage, 100% per core
+    pub cpu_usage: f32,
+    /// Summed resident memory in bytes
+    pub memory: u64,
+    /// Summed threads of the members whose thread count is known
+    pub threads: u32,
+    /// Members of the group, in the order of the list they came from
+    pub pids: Vec<u32>,
+}
+
+impl ProcessGroup {
+    /// Groups `processes` by `by`, busiest group first; groups with the
+    /// same CPU usage are ordered by memory, then by key
+    pub fn group(processes: &[ProcessData], by: ProcessGroupBy) -> Vec<Self> {
+        let mut groups: HashMap<&str, ProcessGroup> = HashMap::new();
+        for process in processes {
+            let key = by.key(process);
+            let group = groups.entry(key).or_insert_with(|| ProcessGroup {
+                key: key.to_string(),
+                cpu_usage: 0.0,
+                memory: 0,
+                threads: 0,
+                pids: Vec::new(),
+            });
+            group.cpu_usage += process.cpu_usage;
+            group.memory += process.memory;
+            group.threads += process.threads.unwrap_or(0);
+            group.pids.push(process.pid);
+        }
+        let mut groups: Vec<ProcessGroup> = groups.into_values().collect();
+        groups.sort_by(|a, b| {
+            b.cpu_usage
+                .total_cmp(&a.cpu_usage)
+                .then(b.memory.cmp(&a.memory))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, user: &str, cpu_usage: f32, memory: u64) -> ProcessData {
+        ProcessData {
+            pid,
+            name: name.to_string(),
+            user: Some(user.to_string()),
+            cpu_usage,
+            memory,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_by_name_and_user() {
+        let processes = [
+            process(1, "nginx", "www", 1.5, 100),
+            process(2, "postgres", "postgres", 4.0, 500),
+            process(3, "nginx", "www", 2.0, 200),
+            process(4, "bash", "alice", 0.0, 10),
+        ];
+
+        let by_name = ProcessGroup::group(&processes, ProcessGroupBy::Name);
+        let keys: Vec<&str> = by_name.iter().map(|group| group.key.as_str()).collect();
+        assert_eq!(keys, ["postgres", "nginx", "bash"]);
+        assert_eq!(by_name[1].cpu_usage, 3.5);
+        assert_eq!(by_name[1].memory, 300);
+        assert_eq!(by_name[1].pids, [1, 3]);
+
+        let by_user = ProcessGroup::group(&processes, ProcessGroupBy::User);
+        assert_eq!(by_user.len(), 3);
+        assert_eq!(by_user[2].key, "alice");
+    }
+}
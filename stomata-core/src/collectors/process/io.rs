@@ -0,0 +1,155 @@
+//! Disk read and write rates per process
+//!
+//! sysinfo keeps the bytes read and written since each process started, so
+//! the rates are computed from two consecutive snapshots and the time
+//! between them, like the scheduler rates.
+
+use std::{collections::HashMap, time::Instant};
+
+use crate::collectors::{backend::SystemBackend, process::ProcessData};
+
+/// Bytes read and written by one process since it started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessIoCounters {
+    /// Bytes read from storage
+    pub read_bytes: u64,
+    /// Bytes written to storage
+    pub written_bytes: u64,
+}
+
+/// Disk rates of one process in bytes per second
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProcessIoRate {
+    /// Bytes read per second
+    pub read: f64,
+    /// Bytes written per second
+    pub write: f64,
+}
+
+/// Computes disk rates per process from consecutive snapshots
+#[derive(Debug, Default)]
+pub struct ProcessIoTracker {
+    previous: Option<(HashMap<u32, ProcessIoCounters>, Instant)>,
+    rates: HashMap<u32, ProcessIoRate>,
+}
+
+impl ProcessIoTracker {
+    /// Takes a snapshot of the disk counters of every process of `system`,
+    /// right after refreshing it with disk usage
+    pub fn update(&mut self, system: &dyn SystemBackend) {
+        let counters = system
+            .processes()
+            .into_iter()
+            .map(|process| {
+                let counters = ProcessIoCounters {
+                    read_bytes: process.total_read_bytes,
+                    written_bytes: process.total_written_bytes,
+                };
+                (process.pid, counters)
+            })
+            .collect();
+        self.update_from(counters, Instant::now());
+    }
+
+    /// Computes the rates from `counters` taken at `now`. Processes
+    /// new since the previous snapshot have no rate yet and counters that
+    /// went backwards (a reused PID) count as no activity.
+    pub fn update_from(&mut self, counters: HashMap<u32, ProcessIoCounters>, now: Instant) {
+        if let Some((previous, previous_at)) = &self.previous {
+            let elapsed = now.duration_since(*previous_at).as_secs_f64();
+            if elapsed <= 0.0 {
+                return;
+            }
+            self.rates = counters
+                .iter()
+                .filter_map(|(pid, current)| {
+                    let previous = previous.get(pid)?;
+                    let rate = |current: u64, previous: u64| {
+                        current.saturating_sub(previous) as f64 / elapsed
+                    };
+                    let rate = ProcessIoRate {
+                        read: rate(current.read_bytes, previous.read_bytes),
+                        write: rate(current.written_bytes, previous.written_bytes),
+                    };
+                    Some((*pid, rate))
+                })
+                .collect();
+        }
+        self.previous = Some((counters, now));
+    }
+
+    /// Forgets the previous snapshot, so the next update only sets a new
+    /// baseline
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Rates of `pid` since the previous snapshot, `None` until it was in
+    /// two snapshots
+    pub fn rate(&self, pid: u32) -> Option<ProcessIoRate> {
+        self.rates.get(&pid).copied()
+    }
+
+    /// Fills in the disk rates of `processes`
+    pub fn apply(&self, processes: &mut [ProcessData]) {
+        for process in processes {
+            let rate = self.rate(process.pid);
+            process.disk_read_rate = rate.map(|rate| rate.read);
+            process.disk_write_rate = rate.map(|rate| rate.write);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn counters(entries: &[(u32, u64, u64)]) -> HashMap<u32, ProcessIoCounters> {
+        entries
+            .iter()
+            .map(|&(pid, read_bytes, written_bytes)| {
+                (
+                    pid,
+                    ProcessIoCounters {
+                        read_bytes,
+                        written_bytes,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rates_between_snapshots() {
+        let start = Instant::now();
+        let mut tracker = ProcessIoTracker::default();
+        tracker.update_from(counters(&[(10, 1000, 0), (11, 500, 500)]), start);
+        assert_eq!(tracker.rate(10), None);
+
+        let later = start + Duration::from_secs(2);
+        tracker.update_from(
+            counters(&[(10, 5000, 2000), (11, 100, 500), (12, 9, 9)]),
+            later,
+        );
+        assert_eq!(
+            tracker.rate(10),
+            Some(ProcessIoRate {
+                read: 2000.0,
+                write: 1000.0
+            })
+        );
+        // PID reused by a process that read less
+        assert_eq!(tracker.rate(11), Some(ProcessIoRate::default()));
+        // not in the previous snapshot
+        assert_eq!(tracker.rate(12), None);
+
+        tracker.reset();
+        tracker.update_from(
+            counters(&[(10, 9000, 2000)]),
+            later + Duration::from_secs(60),
+        );
+        assert_eq!(tracker.rate(10), None);
+    }
+}
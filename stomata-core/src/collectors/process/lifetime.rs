@@ -0,0 +1,239 @@
+//! Processes started and exited between two refreshes of the process list
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{collectors::process::ProcessData, constants::MAX_RECENT_PROCESS_EVENTS};
+
+/// Whether a process appeared or disappeared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessEventKind {
+    /// The process wasn't in the previous list
+    Born,
+    /// The process is gone from the list
+    Exited,
+}
+
+/// A process that started or exited between two refreshes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessEvent {
+    /// Whether the process started or exited
+    pub kind: ProcessEventKind,
+    /// Process id
+    pub pid: u32,
+    /// Executable name
+    pub name: String,
+    /// Refresh the change was noticed at
+    pub time: DateTime<Utc>,
+    /// How long an exited process was seen running, `None` for births and
+    /// for processes already running when tracking started
+    pub lifetime: Option<Duration>,
+}
+
+/// A process of the last list
+#[derive(Debug)]
+struct Tracked {
+    name: String,
+    /// When the process was first seen, `None` for the first list
+    born: Option<DateTime<Utc>>,
+}
+
+/// Compares the PID set of each process list to the previous one.
+///
+/// The first list only sets the baseline. A PID whose name changed is
+/// treated as reused: the old process exited and a new one was born.
+#[derive(Debug, Default)]
+pub struct ProcessLifetimes {
+    processes: Option<HashMap<u32, Tracked>>,
+    recent: VecDeque<ProcessEvent>,
+}
+
+impl ProcessLifetimes {
+    /// Compares `processes` to the previous list and returns the processes
+    /// born and exited since, births first
+    pub fn update(&mut self, processes: &[ProcessData], now: DateTime<Utc>) -> Vec<ProcessEvent> {
+        let Some(previous) = self.processes.as_mut() else {
+            self.processes = Some(
+                processes
+                    .iter()
+                    .map(|process| {
+                        let tracked = Tracked {
+                            name: process.name.clone(),
+                            born: None,
+                        };
+                        (process.pid, tracked)
+                    })
+                    .collect(),
+            );
+            return Vec::new();
+        };
+
+        let mut current = HashMap::with_capacity(processes.len());
+        let mut events = Vec::new();
+        for process in processes {
+            let tracked = match previous.remove(&process.pid) {
+                Some(tracked) if tracked.name == process.name => tracked,
+                reused => {
+                    if let Some(tracked) = reused {
+                        previous.insert(process.pid, tracked);
+                    }
+                    events.push(ProcessEvent {
+                        kind: ProcessEventKind::Born,
+                        pid: process.pid,
+                        name: process.name.clone(),
+                        time: now,
+                        lifetime: None,
+                    });
+                    Tracked {
+                        name: process.name.clone(),
+                        born: Some(now),
+                    }
+                }
+            };
+            current.insert(process.pid, tracked);
+        }
+        let mut exited: Vec<ProcessEvent> = previous
+            .drain()
+            .map(|(pid, tracked)| ProcessEvent {
+                kind: ProcessEventKind::Exited,
+                pid,
+                name: tracked.name,
+                time: now,
+                lifetime: tracked.born.map(|born| now - born),
+            })
+            .collect();
+        exited.sort_by_key(|event| event.pid);
+        events.extend(exited);
+        self.processes = Some(current);
+
+        for event in &events {
+            self.recent.push_front(event.clone());
+        }
+        self.recent.truncate(MAX_RECENT_PROCESS_EVENTS);
+        events
+    }
+
+    /// Births and exits noticed so far, newest first
+    pub fn recent(&self) -> impl Iterator<Item = &ProcessEvent> {
+        self.recent.iter()
+    }
+
+    /// Exits noticed so far, newest first
+    pub fn exited(&self) -> impl Iterator<Item = &ProcessEvent> {
+        self.recent
+            .iter()
+            .filter(|event| event.kind == ProcessEventKind::Exited)
+    }
+
+    /// When `pid` was born, `None` when it was running before tracking
+    /// started or isn't running
+    pub fn born_at(&self, pid: u32) -> Option<DateTime<Utc>> {
+        self.processes.as_ref()?.get(&pid)?.born
+    }
+
+    /// Forgets every process and event, the next list sets a new baseline
+    pub fn clear(&mut self) {
+        self.processes = None;
+        self.recent.clear();
+    }
+}
+
+/// Finds the process that took over from an exited one: the process
+/// running the same executable, or with the same name when the executable
+/// path isn't known. With several candidates, e.g. the workers of a pool,
+/// the highest PID is taken as the most recently started.
+pub fn find_successor<'a>(
+    processes: &'a [ProcessData],
+    exited_pid: u32,
+    name: &str,
+    exe_path: Option<&str>,
+) -> Option<&'a ProcessData> {
+    processes
+        .iter()
+        .filter(|process| process.pid != exited_pid)
+        .filter(|process| match exe_path {
+            Some(exe_path) => process.exe_path.as_deref() == Some(exe_path),
+            None => process.name == name,
+        })
+        .max_by_key(|process| process.pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str) -> ProcessData {
+        ProcessData {
+            pid,
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_first_list_sets_the_baseline() {
+        let mut lifetimes = ProcessLifetimes::default();
+        let events = lifetimes.update(&[process(1, "init"), process(2, "sshd")], Utc::now());
+        assert!(events.is_empty());
+        assert_eq!(lifetimes.born_at(1), None);
+    }
+
+    #[test]
+    fn test_born_and_exited() {
+        let start = Utc::now();
+        let mut lifetimes = ProcessLifetimes::default();
+        lifetimes.update(&[process(1, "init"), process(2, "sshd")], start);
+
+        let later = start + Duration::seconds(2);
+        let events = lifetimes.update(&[process(1, "init"), process(3, "worker")], later);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, ProcessEventKind::Born);
+        assert_eq!(events[0].pid, 3);
+        assert_eq!(events[1].kind, ProcessEventKind::Exited);
+        assert_eq!(events[1].name, "sshd");
+        assert_eq!(events[1].lifetime, None);
+        assert_eq!(lifetimes.born_at(3), Some(later));
+
+        let end = later + Duration::seconds(5);
+        lifetimes.update(&[process(1, "init")], end);
+        let exited: Vec<_> = lifetimes.exited().collect();
+        assert_eq!(exited[0].pid, 3);
+        assert_eq!(exited[0].lifetime, Some(Duration::seconds(5)));
+        assert_eq!(exited[1].pid, 2);
+    }
+
+    #[test]
+    fn test_find_successor() {
+        let mut daemon = process(200, "daemon");
+        daemon.exe_path = Some("/usr/bin/daemon".to_string());
+        let mut other = process(300, "daemon");
+        other.exe_path = Some("/opt/daemon".to_string());
+        let processes = [process(1, "init"), daemon, other];
+
+        let by_exe = find_successor(&processes, 100, "daemon", Some("/usr/bin/daemon"));
+        assert_eq!(by_exe.map(|process| process.pid), Some(200));
+        let by_name = find_successor(&processes, 100, "daemon", None);
+        assert_eq!(by_name.map(|process| process.pid), Some(300));
+        assert!(find_successor(&processes, 100, "missing", None).is_none());
+    }
+
+    #[test]
+    fn test_reused_pid() {
+        let start = Utc::now();
+        let mut lifetimes = ProcessLifetimes::default();
+        lifetimes.update(&[process(7, "cron")], start);
+        let events = lifetimes.update(&[process(7, "backup")], start);
+        let kinds: Vec<_> = events
+            .iter()
+            .map(|event| (event.kind, event.name.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (ProcessEventKind::Born, "backup"),
+                (ProcessEventKind::Exited, "cron")
+            ]
+        );
+    }
+}
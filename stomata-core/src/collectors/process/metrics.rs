@@ -1,21 +1,188 @@
-use sysinfo::{DiskUsage, Pid, Process};
+//! Process types
 
-#[derive(Debug, Clone, PartialEq, Default)]
+use serde::{Deserialize, Serialize};
+
+/// Columns of the process table for one process
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct ProcessData {
+    /// Process id
     pub pid: u32,
+    /// Executable name
     pub name: String,
+    /// CPU usage since the previous refresh, 100% per core
     pub cpu_usage: f32,
+    /// Resident memory in bytes
     pub memory: u64,
+    /// Scheduling state, e.g. `Runnable` or `Sleeping`
     pub status: String,
+    /// Name of the user owning the process, or the uid when it has no name
+    pub user: Option<String>,
+    /// Command line arguments, empty for kernel threads
+    pub command: Vec<String>,
+    /// Path of the executable, `None` when it can't be read
+    pub exe_path: Option<String>,
+    /// The executable was deleted or replaced on disk after the process started
+    pub exe_deleted: bool,
+    /// Number of threads, `None` where it can't be read
+    pub threads: Option<u32>,
+    /// Memory swapped out in bytes, `None` where it can't be read (kernel
+    /// threads or not Linux)
+    pub swap: Option<u64>,
+    /// Cgroup path, e.g. `/system.slice/docker-<id>.scope`, `None` where
+    /// it can't be read
+    pub cgroup: Option<String>,
+    /// PID of the parent process, `None` for the first process
+    pub parent_pid: Option<u32>,
+    /// PID of the session leader, `None` where it can't be read
+    pub session_id: Option<u32>,
+    /// Bytes read from storage per second, `None` until the process was
+    /// seen in two refreshes
+    pub disk_read_rate: Option<f64>,
+    /// Bytes written to storage per second, `None` until the process was
+    /// seen in two refreshes
+    pub disk_write_rate: Option<f64>,
 }
 
+/// Everything shown on the detail view of one process
 #[derive(Default, Clone)]
-pub struct SingleProcessData<'a> {
+pub struct SingleProcessData {
+    /// The process table columns
     pub basic_process_data: ProcessData,
-    pub tasks: Vec<&'a Process>,
-    pub disk_usage: DiskUsage,
+    /// Threads of the process, empty where sysinfo doesn't list them
+    pub tasks: Vec<ProcessData>,
+    /// Bytes read and written
+    pub disk_usage: ProcessDiskUsage,
+    /// Start time in seconds since the Unix epoch
     pub start_time: u64,
+    /// Seconds since the process started
     pub running_time: u64,
+    /// Working directory, `None` when it can't be read
     pub current_working_dir: Option<String>,
-    pub parent_pid: Option<Pid>,
+    /// PID of the parent process
+    pub parent_pid: Option<u32>,
+    /// Capabilities, seccomp mode and security label
+    pub security: SecurityContext,
+    /// Environment variables sorted by name, empty when they can't be read
+    /// (e.g. another user's process)
+    pub environment: Vec<(String, String)>,
+    /// Open file descriptors sorted by number, `None` when they can't be
+    /// read (another user's process or not Linux)
+    pub open_files: Option<Vec<OpenFile>>,
+    /// Nice value, from -20 (highest priority) to 19 (lowest)
+    pub nice: Option<i32>,
+    /// CPUs the process may run on, `None` when unsupported
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// Memory split into shared, private and swapped pages, `None` when
+    /// `/proc/<pid>/smaps_rollup` can't be read (another user's process,
+    /// kernels before 4.14 or not Linux)
+    pub memory_breakdown: Option<MemoryBreakdown>,
+}
+
+/// Memory of a process from `/proc/<pid>/smaps_rollup`, in bytes
+///
+/// RSS counts shared pages in full for every process mapping them, PSS
+/// divides them among those processes, so the PSS of all processes adds up
+/// to the memory actually in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryBreakdown {
+    /// Resident set size
+    pub rss: u64,
+    /// Proportional set size, shared pages divided by their mappers
+    pub pss: u64,
+    /// Resident pages also mapped by other processes, unmodified
+    pub shared_clean: u64,
+    /// Resident pages also mapped by other processes, modified
+    pub shared_dirty: u64,
+    /// Resident pages mapped only by this process, unmodified
+    pub private_clean: u64,
+    /// Resident pages mapped only by this process, modified
+    pub private_dirty: u64,
+    /// Anonymous memory, e.g. the heap and stacks
+    pub anonymous: u64,
+    /// Pages swapped out
+    pub swap: u64,
+    /// Swapped out pages divided among the processes sharing them
+    pub swap_pss: u64,
+}
+
+impl MemoryBreakdown {
+    /// Resident pages shared with other processes
+    pub fn shared(&self) -> u64 {
+        self.shared_clean + self.shared_dirty
+    }
+
+    /// Resident pages of this process only
+    pub fn private(&self) -> u64 {
+        self.private_clean + self.private_dirty
+    }
+}
+
+/// Bytes read and written by a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessDiskUsage {
+    /// Bytes read since the previous refresh
+    pub read_bytes: u64,
+    /// Bytes written since the previous refresh
+    pub written_bytes: u64,
+    /// Bytes read since the process started
+    pub total_read_bytes: u64,
+    /// Bytes written since the process started
+    pub total_written_bytes: u64,
+}
+
+/// What an open file descriptor refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenFileKind {
+    /// A regular file or directory
+    File,
+    /// A network or Unix socket
+    Socket,
+    /// A pipe or FIFO
+    Pipe,
+    /// eventfd, epoll, timerfd and other anonymous inodes
+    AnonInode,
+    /// Anything else, e.g. a device
+    Other,
+}
+
+/// An open file descriptor of a process from `/proc/<pid>/fd`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenFile {
+    /// Descriptor number
+    pub fd: u32,
+    /// Link target, a path or e.g. `socket:[12345]`
+    pub target: String,
+    /// What the target is
+    pub kind: OpenFileKind,
+}
+
+/// Seccomp mode of a process, from the `Seccomp` field of `/proc/<pid>/status`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeccompMode {
+    /// No seccomp filtering
+    Disabled,
+    /// Only read, write, exit and sigreturn are allowed
+    Strict,
+    /// System calls are checked by a BPF filter
+    Filter,
+}
+
+/// Linux security context of a process.
+///
+/// Every field is `None` when it could not be read, either because the
+/// platform isn't Linux or because the process belongs to another user.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SecurityContext {
+    /// Effective capability set as a bitmask
+    pub cap_effective: Option<u64>,
+    /// Permitted capability set as a bitmask
+    pub cap_permitted: Option<u64>,
+    /// Bounding capability set as a bitmask
+    pub cap_bounding: Option<u64>,
+    /// Seccomp mode
+    pub seccomp: Option<SeccompMode>,
+    /// Whether the process can no longer gain privileges through execve
+    pub no_new_privs: Option<bool>,
+    /// SELinux or AppArmor label from `/proc/<pid>/attr/current`
+    pub lsm_label: Option<String>,
 }
@@ -1,4 +1,22 @@
+//! Process list, single process details, process actions, totals per user
+//! or name, thread details, disk rates, zombie and orphan detection and the
+//! processes started and exited between refreshes
+
+pub mod actions;
 pub mod collectors;
+pub mod groups;
+pub mod io;
+pub mod lifetime;
 pub mod metrics;
+pub mod state;
+pub mod threads;
 
-pub use metrics::{ProcessData, SingleProcessData};
+pub use groups::{ProcessGroup, ProcessGroupBy};
+pub use io::{ProcessIoCounters, ProcessIoRate, ProcessIoTracker};
+pub use lifetime::{ProcessEvent, ProcessEventKind, ProcessLifetimes, find_successor};
+pub use metrics::{
+    MemoryBreakdown, OpenFile, OpenFileKind, ProcessData, ProcessDiskUsage, SeccompMode,
+    SecurityContext, SingleProcessData,
+};
+pub use state::{ProcessStateFilter, is_orphan};
+pub use threads::ThreadDetails;
@@ -0,0 +1,87 @@
+//! Zombie and orphaned processes
+//!
+//! A zombie has exited but its parent never reaped it, and an orphan lost
+//! its parent and was re-parented to PID 1. Both usually point at a bug in
+//! the parent, and neither shows up when sorting by CPU or memory.
+
+use crate::collectors::process::ProcessData;
+
+/// Whether a process with these ids was re-parented to PID 1.
+///
+/// Services started by init are children of PID 1 too, but init makes
+/// them the leader of their own session, while an orphan keeps the
+/// session of the parent it lost.
+pub fn is_orphan(pid: u32, parent_pid: Option<u32>, session_id: Option<u32>) -> bool {
+    pid != 1 && parent_pid == Some(1) && session_id.is_some_and(|session| session != pid)
+}
+
+impl ProcessData {
+    /// Exited but not yet reaped by its parent
+    pub fn is_zombie(&self) -> bool {
+        self.status == "Zombie"
+    }
+
+    /// Re-parented to PID 1 after its parent exited, see [`is_orphan`]
+    pub fn is_orphan(&self) -> bool {
+        is_orphan(self.pid, self.parent_pid, self.session_id)
+    }
+}
+
+/// Process states the process list can be filtered to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessStateFilter {
+    /// Processes that exited but were not reaped
+    Zombies,
+    /// Processes re-parented to PID 1
+    Orphans,
+}
+
+impl ProcessStateFilter {
+    /// Lowercase name, e.g. for a table title
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProcessStateFilter::Zombies => "zombies",
+            ProcessStateFilter::Orphans => "orphans",
+        }
+    }
+
+    /// Whether `process` is in this state
+    pub fn matches(self, process: &ProcessData) -> bool {
+        match self {
+            ProcessStateFilter::Zombies => process.is_zombie(),
+            ProcessStateFilter::Orphans => process.is_orphan(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_orphan() {
+        // init itself
+        assert!(!is_orphan(1, None, Some(1)));
+        // a service, leading its own session
+        assert!(!is_orphan(812, Some(1), Some(812)));
+        // a worker left behind by a killed shell
+        assert!(is_orphan(4310, Some(1), Some(4102)));
+        // still has its parent
+        assert!(!is_orphan(4311, Some(4102), Some(4102)));
+        // session unknown
+        assert!(!is_orphan(4312, Some(1), None));
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let zombie = ProcessData {
+            pid: 77,
+            status: "Zombie".to_string(),
+            parent_pid: Some(70),
+            session_id: Some(70),
+            ..Default::default()
+        };
+        assert!(ProcessStateFilter::Zombies.matches(&zombie));
+        assert!(!ProcessStateFilter::Orphans.matches(&zombie));
+    }
+}
@@ -0,0 +1,125 @@
+//! Scheduling details of one thread of a process, read from
+//! `/proc/<pid>/task/<tid>`
+
+use std::{fs, time::Duration};
+
+/// Details of one thread, for telling the busy threads of a process apart
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThreadDetails {
+    /// Thread id
+    pub tid: u32,
+    /// Thread name, set by the program, e.g. `tokio-runtime-w`
+    pub name: String,
+    /// Scheduling state letter, e.g. `R` or `S`
+    pub state: char,
+    /// CPU time spent in user mode since the thread started
+    pub user_time: Duration,
+    /// CPU time spent in the kernel since the thread started
+    pub system_time: Duration,
+    /// Kernel scheduling priority
+    pub priority: i64,
+    /// Nice value
+    pub nice: i64,
+    /// CPU the thread last ran on
+    pub processor: Option<u32>,
+    /// Times the thread gave up the CPU, e.g. to wait for IO
+    pub voluntary_switches: Option<u64>,
+    /// Times the thread was preempted
+    pub involuntary_switches: Option<u64>,
+}
+
+impl ThreadDetails {
+    /// Reads thread `tid` of process `pid`, `None` when it has exited or
+    /// procfs isn't available
+    pub fn fetch(pid: u32, tid: u32) -> Option<Self> {
+        let dir = format!("/proc/{pid}/task/{tid}");
+        let stat = fs::read_to_string(format!("{dir}/stat")).ok()?;
+        let mut details = Self::parse_stat(&stat, clock_ticks_per_second())?;
+        if let Ok(status) = fs::read_to_string(format!("{dir}/status")) {
+            details.parse_status(&status);
+        }
+        Some(details)
+    }
+
+    /// Parses a `/proc/<pid>/task/<tid>/stat` line. The name may contain
+    /// spaces and parentheses, so the fields are counted from the last `)`.
+    pub fn parse_stat(stat: &str, ticks_per_second: u64) -> Option<Self> {
+        let (head, rest) = stat.rsplit_once(')')?;
+        let (tid, name) = head.split_once(" (")?;
+        // the fields after the name, starting with field 3 (state)
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        let field = |number: usize| fields.get(number - 3).copied();
+        let ticks = |number: usize| -> Option<Duration> {
+            let ticks: u64 = field(number)?.parse().ok()?;
+            Some(Duration::from_millis(
+                ticks * 1000 / ticks_per_second.max(1),
+            ))
+        };
+
+        Some(ThreadDetails {
+            tid: tid.trim().parse().ok()?,
+            name: name.to_string(),
+            state: field(3)?.chars().next()?,
+            user_time: ticks(14)?,
+            system_time: ticks(15)?,
+            priority: field(18)?.parse().ok()?,
+            nice: field(19)?.parse().ok()?,
+            processor: field(39).and_then(|cpu| cpu.parse().ok()),
+            voluntary_switches: None,
+            involuntary_switches: None,
+        })
+    }
+
+    /// Fills in the context switch counts of a
+    /// `/proc/<pid>/task/<tid>/status` file
+    pub fn parse_status(&mut self, status: &str) {
+        for line in status.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().parse().ok();
+            match key {
+                "voluntary_ctxt_switches" => self.voluntary_switches = value,
+                "nonvoluntary_ctxt_switches" => self.involuntary_switches = value,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Clock ticks per second the kernel counts CPU times in
+#[cfg(unix)]
+fn clock_ticks_per_second() -> u64 {
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 { ticks as u64 } else { 100 }
+}
+
+#[cfg(not(unix))]
+fn clock_ticks_per_second() -> u64 {
+    100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_task_stat() {
+        let stat = "4242 (tokio (w) 1) R 4200 4200 4200 0 -1 4194368 120 0 0 0 250 75 0 0 20 0 8 0 12345 1000000 500 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 -1 3 0 0 0 0 0\n";
+        let mut details = ThreadDetails::parse_stat(stat, 100).unwrap();
+        assert_eq!(details.tid, 4242);
+        assert_eq!(details.name, "tokio (w) 1");
+        assert_eq!(details.state, 'R');
+        assert_eq!(details.user_time, Duration::from_millis(2500));
+        assert_eq!(details.system_time, Duration::from_millis(750));
+        assert_eq!(details.priority, 20);
+        assert_eq!(details.nice, 0);
+        assert_eq!(details.processor, Some(3));
+
+        details.parse_status(
+            "Name:\ttokio\nvoluntary_ctxt_switches:\t150\nnonvoluntary_ctxt_switches:\t7\n",
+        );
+        assert_eq!(details.voluntary_switches, Some(150));
+        assert_eq!(details.involuntary_switches, Some(7));
+    }
+}
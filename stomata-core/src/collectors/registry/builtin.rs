@@ -0,0 +1,360 @@
+//! Collectors registered by [`CollectorRegistry::with_builtin`](super::CollectorRegistry::with_builtin)
+//!
+//! Each one owns its source, so it can run on any thread and at its own
+//! pace. CPU, memory, network and mounts are read through a
+//! [`RefreshingBackend`], so the collectors run against a
+//! [`FakeSystem`](crate::collectors::FakeSystem) in tests. GPU values come
+//! from plugins until there is a native collector.
+
+use std::time::Duration;
+
+use crate::{
+    collectors::{
+        backend::{OwnedSysinfo, RefreshingBackend},
+        disks::metrics::DiskCollector,
+        hwmon::metrics::HwmonMetrics,
+        network::rates::NetworkRates,
+        plugins::{
+            collectors::plugins_dir,
+            metrics::{Plugin, PluginMetrics},
+        },
+        registry::{Collector, Metric, MetricValue},
+    },
+    constants::{
+        CPU_COLLECT_INTERVAL_MS, DISK_COLLECT_INTERVAL_MS, MEMORY_COLLECT_INTERVAL_MS,
+        NETWORK_COLLECT_INTERVAL_MS, PLUGINS_COLLECT_INTERVAL_MS, SENSORS_COLLECT_INTERVAL_MS,
+    },
+    errors::{CoreError, CoreResult},
+};
+
+/// Global and per-core CPU usage
+pub struct Cpu {
+    backend: Box<dyn RefreshingBackend>,
+}
+
+impl Cpu {
+    /// Takes the first reading, so usage is known from the first collect
+    /// that comes at least `MINIMUM_CPU_UPDATE_INTERVAL` later
+    pub fn new() -> Self {
+        Self::with_backend(Box::new(OwnedSysinfo::new()))
+    }
+
+    /// A collector reading `backend`, refreshed on every collect
+    pub fn with_backend(backend: Box<dyn RefreshingBackend>) -> Self {
+        Self { backend }
+    }
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for Cpu {
+    fn id(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn collect(&mut self) -> CoreResult<Metric> {
+        self.backend.refresh_cpu();
+        let mut values =
+            vec![MetricValue::new("usage", self.backend.global_cpu_usage() as f64).unit("percent")];
+        values.extend(self.backend.cpu_usages().into_iter().map(|(name, usage)| {
+            MetricValue::new("usage", usage as f64)
+                .instance(name)
+                .unit("percent")
+        }));
+        Ok(Metric::new(self.id(), values))
+    }
+
+    fn interval_hint(&self) -> Duration {
+        Duration::from_millis(CPU_COLLECT_INTERVAL_MS)
+    }
+}
+
+/// RAM and swap usage
+pub struct Memory {
+    backend: Box<dyn RefreshingBackend>,
+}
+
+impl Memory {
+    /// A collector reading memory through its own sysinfo state
+    pub fn new() -> Self {
+        Self::with_backend(Box::new(OwnedSysinfo::new()))
+    }
+
+    /// A collector reading `backend`, refreshed on every collect
+    pub fn with_backend(backend: Box<dyn RefreshingBackend>) -> Self {
+        Self { backend }
+    }
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for Memory {
+    fn id(&self) -> &'static str {
+        "memory"
+    }
+
+    fn collect(&mut self) -> CoreResult<Metric> {
+        self.backend.refresh_memory();
+        let used = self.backend.used_memory();
+        let total = self.backend.total_memory();
+        let used_percent = if total == 0 {
+            0.0
+        } else {
+            used as f64 * 100.0 / total as f64
+        };
+        Ok(Metric::new(
+            self.id(),
+            vec![
+                MetricValue::new("used", used as f64).unit("bytes"),
+                MetricValue::new("total", total as f64).unit("bytes"),
+                MetricValue::new("available", self.backend.available_memory() as f64).unit("bytes"),
+                MetricValue::new("used_percent", used_percent).unit("percent"),
+                MetricValue::new("swap_used", self.backend.used_swap() as f64).unit("bytes"),
+                MetricValue::new("swap_total", self.backend.total_swap() as f64).unit("bytes"),
+            ],
+        ))
+    }
+
+    fn interval_hint(&self) -> Duration {
+        Duration::from_millis(MEMORY_COLLECT_INTERVAL_MS)
+    }
+}
+
+/// Traffic per second of every interface
+pub struct Network {
+    backend: Box<dyn RefreshingBackend>,
+    rates: NetworkRates,
+}
+
+impl Network {
+    /// Takes the first snapshot of the counters, so rates are known from
+    /// the first collect
+    pub fn new() -> Self {
+        Self::with_backend(Box::new(OwnedSysinfo::new()))
+    }
+
+    /// A collector reading `backend`, refreshed on every collect. Its
+    /// current counters are the first snapshot.
+    pub fn with_backend(backend: Box<dyn RefreshingBackend>) -> Self {
+        let mut rates = NetworkRates::default();
+        rates.update(backend.as_ref());
+        Self { backend, rates }
+    }
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for Network {
+    fn id(&self) -> &'static str {
+        "network"
+    }
+
+    fn collect(&mut self) -> CoreResult<Metric> {
+        self.backend.refresh_networks();
+        self.rates.update(self.backend.as_ref());
+        let mut names: Vec<String> = self
+            .backend
+            .interface_totals()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        let values = names
+            .into_iter()
+            .flat_map(|name| {
+                let rates = self.rates.get(&name);
+                [
+                    ("rx_bytes_per_sec", rates.bytes_received, "bytes/s"),
+                    ("tx_bytes_per_sec", rates.bytes_transmitted, "bytes/s"),
+                    ("rx_packets_per_sec", rates.packets_received, "packets/s"),
+                    ("tx_packets_per_sec", rates.packets_transmitted, "packets/s"),
+                ]
+                .map(|(metric, value, unit)| {
+                    MetricValue::new(metric, value).instance(&name).unit(unit)
+                })
+            })
+            .collect();
+        Ok(Metric::new(self.id(), values))
+    }
+
+    fn interval_hint(&self) -> Duration {
+        Duration::from_millis(NETWORK_COLLECT_INTERVAL_MS)
+    }
+}
+
+/// Activity of every disk and usage of every mounted filesystem
+pub struct Disk {
+    backend: Box<dyn RefreshingBackend>,
+    disks: DiskCollector,
+}
+
+impl Disk {
+    /// Reads the counters once, so rates are known from the first collect
+    pub fn new() -> Self {
+        Self::with_backend(Box::new(OwnedSysinfo::new()))
+    }
+
+    /// A collector listing the mounts of `backend`
+    pub fn with_backend(backend: Box<dyn RefreshingBackend>) -> Self {
+        let mut disks = DiskCollector::default();
+        disks.fetch(backend.as_ref());
+        Self { backend, disks }
+    }
+}
+
+impl Default for Disk {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for Disk {
+    fn id(&self) -> &'static str {
+        "disk"
+    }
+
+    fn collect(&mut self) -> CoreResult<Metric> {
+        let metrics = self.disks.fetch(self.backend.as_ref());
+        if let Some(error) = metrics.error
+            && metrics.mounts.is_empty()
+        {
+            return Err(CoreError::collection(error));
+        }
+        let mut values: Vec<MetricValue> = metrics
+            .disks
+            .iter()
+            .flat_map(|disk| {
+                [
+                    ("reads_per_sec", disk.reads_per_sec, "ops/s"),
+                    ("writes_per_sec", disk.writes_per_sec, "ops/s"),
+                    ("read_bytes_per_sec", disk.read_bytes_per_sec, "bytes/s"),
+                    ("write_bytes_per_sec", disk.write_bytes_per_sec, "bytes/s"),
+                    ("queue_depth", disk.queue_depth, "requests"),
+                    ("utilization", disk.utilization, "percent"),
+                ]
+                .map(|(metric, value, unit)| {
+                    MetricValue::new(metric, value)
+                        .instance(&disk.name)
+                        .unit(unit)
+                })
+            })
+            .collect();
+        values.extend(
+            metrics
+                .mounts
+                .iter()
+                .filter(|mount| mount.total_bytes > 0)
+                .map(|mount| {
+                    let used = mount.total_bytes.saturating_sub(mount.available_bytes);
+                    MetricValue::new(
+                        "used_percent",
+                        used as f64 * 100.0 / mount.total_bytes as f64,
+                    )
+                    .instance(&mount.mount_point)
+                    .unit("percent")
+                }),
+        );
+        Ok(Metric::new(self.id(), values))
+    }
+
+    fn interval_hint(&self) -> Duration {
+        Duration::from_millis(DISK_COLLECT_INTERVAL_MS)
+    }
+}
+
+/// Temperatures and fan speeds of the hwmon chips
+#[derive(Debug, Default)]
+pub struct Sensors;
+
+impl Collector for Sensors {
+    fn id(&self) -> &'static str {
+        "sensors"
+    }
+
+    fn collect(&mut self) -> CoreResult<Metric> {
+        let metrics = HwmonMetrics::fetch();
+        let values = metrics
+            .chips
+            .iter()
+            .flat_map(|chip| {
+                let temperatures = chip.temperatures.iter().map(|sensor| {
+                    MetricValue::new("temperature", sensor.celsius)
+                        .instance(format!("{}.{}", chip.name, sensor.label))
+                        .unit("celsius")
+                });
+                let fans = chip.fans.iter().map(|fan| {
+                    MetricValue::new("fan", fan.rpm as f64)
+                        .instance(format!("{}.{}", chip.name, fan.label))
+                        .unit("rpm")
+                });
+                temperatures.chain(fans).collect::<Vec<_>>()
+            })
+            .collect();
+        Ok(Metric::new(self.id(), values))
+    }
+
+    fn interval_hint(&self) -> Duration {
+        Duration::from_millis(SENSORS_COLLECT_INTERVAL_MS)
+    }
+}
+
+/// Values printed by the user's plugins, see [`Plugin`]
+#[derive(Debug, Default)]
+pub struct Plugins {
+    plugins: Vec<Plugin>,
+}
+
+impl Plugins {
+    /// A collector running `plugins`
+    pub fn new(plugins: Vec<Plugin>) -> Self {
+        Self { plugins }
+    }
+
+    /// The plugins in the default plugins directory
+    pub fn discover() -> Self {
+        Self::new(
+            plugins_dir()
+                .map(|dir| Plugin::discover(&dir))
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl Collector for Plugins {
+    fn id(&self) -> &'static str {
+        "plugins"
+    }
+
+    fn collect(&mut self) -> CoreResult<Metric> {
+        let metrics = PluginMetrics::run(&self.plugins);
+        let values = metrics
+            .plugins
+            .iter()
+            .flat_map(|output| {
+                output.record.metrics.iter().map(|value| MetricValue {
+                    name: value.label.clone(),
+                    instance: Some(output.name.clone()),
+                    value: value.value,
+                    unit: value.unit.clone(),
+                })
+            })
+            .collect();
+        Ok(Metric::new(self.id(), values))
+    }
+
+    fn interval_hint(&self) -> Duration {
+        Duration::from_millis(PLUGINS_COLLECT_INTERVAL_MS)
+    }
+}
@@ -0,0 +1,333 @@
+//! Uniform access to the metric collectors
+//!
+//! Every collector that produces plain numbers implements [`Collector`] and
+//! is registered in a [`CollectorRegistry`]. Exporters and one-shot
+//! commands iterate over the registry, so a new collector shows up in all
+//! of them once it is registered. The TUI pages keep using the richer
+//! per-page metrics of [`StomataSystemMetrics`](crate::collectors::structs::StomataSystemMetrics).
+
+pub mod builtin;
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    collectors::backend::{OwnedSysinfo, RefreshingBackend},
+    errors::CoreResult,
+};
+
+/// One value read by a collector
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricValue {
+    /// What is measured, e.g. `usage` or `rx_bytes_per_sec`
+    pub name: String,
+    /// Which device or object the value belongs to, e.g. `eth0`, `None`
+    /// for system wide values
+    pub instance: Option<String>,
+    /// The reading
+    pub value: f64,
+    /// Unit of `value`, e.g. `percent` or `bytes/s`
+    pub unit: Option<String>,
+}
+
+impl MetricValue {
+    /// A system wide value without unit
+    pub fn new(name: impl Into<String>, value: f64) -> Self {
+        Self {
+            name: name.into(),
+            instance: None,
+            value,
+            unit: None,
+        }
+    }
+
+    /// Sets the device or object the value belongs to
+    pub fn instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Sets the unit of the value
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Dotted name including the instance, e.g. `eth0.rx_bytes_per_sec`
+    pub fn key(&self) -> String {
+        match &self.instance {
+            Some(instance) => format!("{instance}.{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Everything one collector read at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Metric {
+    /// Id of the collector that produced the values
+    pub collector: &'static str,
+    /// When the values were read
+    pub timestamp: DateTime<Utc>,
+    /// The values read
+    pub values: Vec<MetricValue>,
+}
+
+impl Metric {
+    /// Values read by `collector`, timestamped now
+    pub fn new(collector: &'static str, values: Vec<MetricValue>) -> Self {
+        Self {
+            collector,
+            timestamp: Utc::now(),
+            values,
+        }
+    }
+}
+
+/// A source of metrics that can be registered in a [`CollectorRegistry`]
+pub trait Collector: Send {
+    /// Short unique name, e.g. `cpu`, also used as the metric prefix by
+    /// exporters
+    fn id(&self) -> &'static str;
+
+    /// Reads the current values. Collectors reporting rates compute them
+    /// since the previous call, so the first call may return zeros.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source can't be read.
+    fn collect(&mut self) -> CoreResult<Metric>;
+
+    /// How often collecting is worthwhile; values read more often are
+    /// noisy or unchanged
+    fn interval_hint(&self) -> Duration;
+}
+
+struct Registered {
+    collector: Box<dyn Collector>,
+    collected_at: Option<Instant>,
+}
+
+/// The registered collectors, in registration order
+#[derive(Default)]
+pub struct CollectorRegistry {
+    collectors: Vec<Registered>,
+}
+
+impl std::fmt::Debug for CollectorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CollectorRegistry")
+            .field("collectors", &self.ids().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl CollectorRegistry {
+    /// An empty registry, see [`with_builtin`](Self::with_builtin)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the CPU, memory, network, disk, sensor and plugin
+    /// collectors
+    pub fn with_builtin() -> Self {
+        Self::with_backend(|| Box::new(OwnedSysinfo::new()))
+    }
+
+    /// The builtin collectors, with CPU, memory, network and mounts read
+    /// through the backends made by `backend`, one per collector
+    pub fn with_backend(backend: impl Fn() -> Box<dyn RefreshingBackend>) -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(builtin::Cpu::with_backend(backend())));
+        registry.register(Box::new(builtin::Memory::with_backend(backend())));
+        registry.register(Box::new(builtin::Network::with_backend(backend())));
+        registry.register(Box::new(builtin::Disk::with_backend(backend())));
+        registry.register(Box::new(builtin::Sensors));
+        registry.register(Box::new(builtin::Plugins::discover()));
+        registry
+    }
+
+    /// Adds `collector`, replacing a registered collector with the same id
+    pub fn register(&mut self, collector: Box<dyn Collector>) {
+        let registered = Registered {
+            collector,
+            collected_at: None,
+        };
+        match self
+            .collectors
+            .iter_mut()
+            .find(|existing| existing.collector.id() == registered.collector.id())
+        {
+            Some(existing) => *existing = registered,
+            None => self.collectors.push(registered),
+        }
+    }
+
+    /// Removes the collector with `id`, returning whether it was registered
+    pub fn unregister(&mut self, id: &str) -> bool {
+        let before = self.collectors.len();
+        self.collectors
+            .retain(|registered| registered.collector.id() != id);
+        self.collectors.len() != before
+    }
+
+    /// Ids of the registered collectors, in registration order
+    pub fn ids(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.collectors
+            .iter()
+            .map(|registered| registered.collector.id())
+    }
+
+    /// Number of registered collectors
+    pub fn len(&self) -> usize {
+        self.collectors.len()
+    }
+
+    /// Whether no collector is registered
+    pub fn is_empty(&self) -> bool {
+        self.collectors.is_empty()
+    }
+
+    /// Keeps only the collectors whose id is in `ids`
+    pub fn retain(&mut self, ids: &[&str]) {
+        self.collectors
+            .retain(|registered| ids.contains(&registered.collector.id()));
+    }
+
+    /// Runs every collector, whatever its interval hint
+    pub fn collect_all(&mut self) -> Vec<(&'static str, CoreResult<Metric>)> {
+        self.collect_where(Instant::now(), |_| true)
+    }
+
+    /// Runs the collectors whose interval hint elapsed since they last ran
+    /// at `now`, and the ones that never ran
+    pub fn collect_due(&mut self, now: Instant) -> Vec<(&'static str, CoreResult<Metric>)> {
+        self.collect_where(now, |registered| {
+            registered.collected_at.is_none_or(|collected_at| {
+                now.saturating_duration_since(collected_at) >= registered.collector.interval_hint()
+            })
+        })
+    }
+
+    /// Time until the next collector is due, zero if one already is
+    pub fn next_due(&self, now: Instant) -> Duration {
+        self.collectors
+            .iter()
+            .map(|registered| match registered.collected_at {
+                Some(collected_at) => registered
+                    .collector
+                    .interval_hint()
+                    .saturating_sub(now.saturating_duration_since(collected_at)),
+                None => Duration::ZERO,
+            })
+            .min()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    fn collect_where(
+        &mut self,
+        now: Instant,
+        due: impl Fn(&Registered) -> bool,
+    ) -> Vec<(&'static str, CoreResult<Metric>)> {
+        self.collectors
+            .iter_mut()
+            .filter(|registered| due(registered))
+            .map(|registered| {
+                registered.collected_at = Some(now);
+                let id = registered.collector.id();
+                let result = registered.collector.collect();
+                if let Err(err) = &result {
+                    tracing::warn!(collector = id, "collecting failed: {err}");
+                }
+                (id, result)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::CoreError;
+
+    /// Collector counting its calls, failing when `fail` is set
+    struct Counter {
+        id: &'static str,
+        calls: u32,
+        fail: bool,
+    }
+
+    impl Counter {
+        fn new(id: &'static str) -> Box<Self> {
+            Box::new(Self {
+                id,
+                calls: 0,
+                fail: false,
+            })
+        }
+    }
+
+    impl Collector for Counter {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+
+        fn collect(&mut self) -> CoreResult<Metric> {
+            if self.fail {
+                return Err(CoreError::collection("no source"));
+            }
+            self.calls += 1;
+            Ok(Metric::new(
+                self.id,
+                vec![MetricValue::new("calls", self.calls as f64)],
+            ))
+        }
+
+        fn interval_hint(&self) -> Duration {
+            Duration::from_secs(10)
+        }
+    }
+
+    #[test]
+    fn test_register_replaces_same_id() {
+        let mut registry = CollectorRegistry::new();
+        registry.register(Counter::new("a"));
+        registry.register(Counter::new("b"));
+        registry.register(Counter::new("a"));
+        assert_eq!(registry.ids().collect::<Vec<_>>(), ["a", "b"]);
+        assert!(registry.unregister("a"));
+        assert!(!registry.unregister("a"));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_due_honours_interval_hint() {
+        let mut registry = CollectorRegistry::new();
+        registry.register(Counter::new("a"));
+        let failing = Box::new(Counter {
+            fail: true,
+            ..*Counter::new("b")
+        });
+        registry.register(failing);
+
+        let start = Instant::now();
+        let first = registry.collect_due(start);
+        assert_eq!(first.len(), 2);
+        assert!(first[1].1.is_err());
+        assert!(
+            registry
+                .collect_due(start + Duration::from_secs(5))
+                .is_empty()
+        );
+        assert_eq!(
+            registry.next_due(start + Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+
+        let second = registry.collect_due(start + Duration::from_secs(10));
+        let metric = second[0].1.as_ref().unwrap();
+        assert_eq!(metric.values[0].value, 2.0);
+    }
+}
@@ -0,0 +1,404 @@
+//! Listing and controlling services through the systemd D-Bus API
+//!
+//! The systemd manager on the system bus lists the units and queues the
+//! start, stop and restart jobs, the usage of each unit comes from its
+//! cgroup.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::services::metrics::{ServiceAction, ServiceCollector, ServiceMetrics},
+    constants::CGROUP_ROOT,
+    errors::CoreResult,
+};
+
+impl ServiceAction {
+    /// Lowercase verb of the action, e.g. `restart`
+    pub fn command(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+        }
+    }
+
+    /// The systemd manager method queueing the job
+    pub fn method(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "StartUnit",
+            ServiceAction::Stop => "StopUnit",
+            ServiceAction::Restart => "RestartUnit",
+        }
+    }
+
+    /// Applies the action to `unit` and waits for its job to finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns the systemd error, e.g. when the user isn't allowed to
+    /// manage the unit, or when the unit failed. Without root, polkit has
+    /// to allow the action since there is no terminal to prompt for a
+    /// password on.
+    pub fn apply(&self, unit: &str) -> CoreResult<()> {
+        dbus::apply(*self, unit)
+    }
+}
+
+impl ServiceCollector {
+    /// Sets the minimum time between two listings, e.g. the refresh
+    /// interval of the page showing them. Defaults to
+    /// `SERVICES_REFRESH_MS`.
+    pub fn set_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval = interval;
+    }
+
+    /// Lists every loaded service unit with the memory and CPU usage of
+    /// the running ones, or returns the previous listing when it is newer
+    /// than the refresh interval.
+    ///
+    /// Never fails; when systemd can't be queried the error is returned in
+    /// `ServiceMetrics::error` with an empty service list.
+    pub fn fetch(&mut self) -> ServiceMetrics {
+        if let Some((listed_at, metrics)) = &self.latest
+            && listed_at.elapsed() < self.refresh_interval
+        {
+            return metrics.clone();
+        }
+        let metrics = self.list();
+        self.latest = Some((Instant::now(), metrics.clone()));
+        metrics
+    }
+
+    fn list(&mut self) -> ServiceMetrics {
+        let units = match dbus::list_services(self) {
+            Ok(units) => units,
+            Err(err) => {
+                tracing::warn!("listing services failed: {err}");
+                return ServiceMetrics {
+                    timestamp: Utc::now(),
+                    services: Vec::new(),
+                    error: Some(format!("Listing services failed: {err}")),
+                };
+            }
+        };
+
+        let now = Instant::now();
+        let mut previous_cpu = HashMap::new();
+        let mut services = Vec::with_capacity(units.len());
+        for (mut service, control_group) in units {
+            if let Some(control_group) = control_group {
+                let dir = Path::new(CGROUP_ROOT).join(control_group.trim_start_matches('/'));
+                service.memory = fs::read_to_string(dir.join("memory.current"))
+                    .ok()
+                    .and_then(|memory| memory.trim().parse().ok());
+                if let Some(usage_usec) = fs::read_to_string(dir.join("cpu.stat"))
+                    .ok()
+                    .and_then(|stat| parse_usage_usec(&stat))
+                {
+                    if let Some((then, previous)) = self.previous_cpu.get(&service.unit) {
+                        let elapsed = now.duration_since(*then).as_micros() as f64;
+                        if elapsed > 0.0 && usage_usec >= *previous {
+                            service.cpu_usage =
+                                Some(((usage_usec - previous) as f64 / elapsed * 100.0) as f32);
+                        }
+                    }
+                    previous_cpu.insert(service.unit.clone(), (now, usage_usec));
+                }
+                service.control_group = Some(control_group);
+            }
+            services.push(service);
+        }
+        self.previous_cpu = previous_cpu;
+
+        ServiceMetrics {
+            timestamp: Utc::now(),
+            services,
+            error: None,
+        }
+    }
+}
+
+/// Total CPU time of a cgroup in microseconds, from its `cpu.stat`
+pub fn parse_usage_usec(stat: &str) -> Option<u64> {
+    stat.lines()
+        .find_map(|line| line.strip_prefix("usage_usec "))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(target_os = "linux")]
+mod dbus {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use zbus::{
+        blocking::{Connection, connection},
+        zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
+    };
+
+    use crate::{
+        collectors::services::metrics::{Service, ServiceAction, ServiceCollector},
+        constants::{
+            SYSTEMD_BUS_NAME, SYSTEMD_JOB_POLL_MS, SYSTEMD_JOB_TIMEOUT_MS,
+            SYSTEMD_MANAGER_INTERFACE, SYSTEMD_MANAGER_PATH, SYSTEMD_TIMEOUT_MS,
+        },
+        errors::{CoreError, CoreResult},
+    };
+
+    /// An entry of the manager's `ListUnits` reply: name, description,
+    /// load state, active state, sub state, followed unit, object path,
+    /// job id, job type and job path
+    pub(super) type UnitStatus = (
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+        OwnedObjectPath,
+        u32,
+        String,
+        OwnedObjectPath,
+    );
+
+    /// Every loaded service unit sorted by name, with the cgroup of the
+    /// active ones
+    pub(super) fn list_services(
+        collector: &mut ServiceCollector,
+    ) -> CoreResult<Vec<(Service, Option<String>)>> {
+        let bus = match collector.bus.take() {
+            Some(bus) => bus,
+            None => system_bus()?,
+        };
+        let units: Vec<UnitStatus> = call_manager(&bus, "ListUnits", &())?;
+        let services = services_from_units(units)
+            .into_iter()
+            .map(|(service, path)| {
+                // units without processes, e.g. oneshots that exited, have
+                // an empty control group
+                let control_group = (service.active_state == "active")
+                    .then(|| property::<String>(&bus, &path, "Service", "ControlGroup").ok())
+                    .flatten()
+                    .filter(|control_group| !control_group.is_empty());
+                (service, control_group)
+            })
+            .collect();
+        collector.bus = Some(bus);
+        Ok(services)
+    }
+
+    /// The service units of a `ListUnits` reply sorted by name, with their
+    /// object paths
+    pub(super) fn services_from_units(units: Vec<UnitStatus>) -> Vec<(Service, OwnedObjectPath)> {
+        let mut services: Vec<(Service, OwnedObjectPath)> = units
+            .into_iter()
+            .filter(|unit| unit.0.ends_with(".service"))
+            .map(
+                |(unit, description, load_state, active_state, sub_state, _, path, ..)| {
+                    let service = Service {
+                        unit,
+                        description,
+                        load_state,
+                        active_state,
+                        sub_state,
+                        ..Default::default()
+                    };
+                    (service, path)
+                },
+            )
+            .collect();
+        services.sort_by(|a, b| a.0.unit.cmp(&b.0.unit));
+        services
+    }
+
+    pub(super) fn apply(action: ServiceAction, unit: &str) -> CoreResult<()> {
+        let bus = system_bus()?;
+        let job: OwnedObjectPath = call_manager(&bus, action.method(), &(unit, "replace"))?;
+
+        // the job object goes away once the job finished
+        let deadline = Instant::now() + Duration::from_millis(SYSTEMD_JOB_TIMEOUT_MS);
+        while property::<u32>(&bus, &job, "Job", "Id").is_ok() {
+            if Instant::now() >= deadline {
+                return Err(CoreError::collection(format!(
+                    "{} {unit} didn't finish within {}s",
+                    action.command(),
+                    SYSTEMD_JOB_TIMEOUT_MS / 1000
+                )));
+            }
+            thread::sleep(Duration::from_millis(SYSTEMD_JOB_POLL_MS));
+        }
+
+        let path: OwnedObjectPath = call_manager(&bus, "GetUnit", &(unit,))?;
+        let state = property::<String>(&bus, &path, "Unit", "ActiveState")
+            .map_err(|err| dbus_error("read the unit state", err))?;
+        if state == "failed" {
+            return Err(CoreError::collection(format!(
+                "{unit} failed, see journalctl -u {unit}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn system_bus() -> CoreResult<Connection> {
+        connection::Builder::system()
+            .map(|builder| builder.method_timeout(Duration::from_millis(SYSTEMD_TIMEOUT_MS)))
+            .and_then(|builder| builder.build())
+            .map_err(|err| dbus_error("connect to the system bus", err))
+    }
+
+    /// Calls `method` of the systemd manager and decodes the reply
+    fn call_manager<B, R>(bus: &Connection, method: &str, body: &B) -> CoreResult<R>
+    where
+        B: serde::Serialize + zbus::zvariant::DynamicType,
+        R: for<'d> serde::Deserialize<'d> + zbus::zvariant::Type,
+    {
+        bus.call_method(
+            Some(SYSTEMD_BUS_NAME),
+            SYSTEMD_MANAGER_PATH,
+            Some(SYSTEMD_MANAGER_INTERFACE),
+            method,
+            body,
+        )
+        .and_then(|reply| reply.body().deserialize())
+        .map_err(|err| dbus_error(method, err))
+    }
+
+    /// Reads `name` of the `org.freedesktop.systemd1.<interface>` interface
+    /// of the object at `path`
+    fn property<T>(
+        bus: &Connection,
+        path: &ObjectPath,
+        interface: &str,
+        name: &str,
+    ) -> zbus::Result<T>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<zbus::Error>,
+    {
+        let interface = format!("org.freedesktop.systemd1.{interface}");
+        let reply = bus.call_method(
+            Some(SYSTEMD_BUS_NAME),
+            path,
+            Some("org.freedesktop.DBus.Properties"),
+            "Get",
+            &(interface.as_str(), name),
+        )?;
+        let value: OwnedValue = reply.body().deserialize()?;
+        T::try_from(value).map_err(Into::into)
+    }
+
+    /// Sorts a D-Bus failure into the matching [`CoreError`]: refusals by
+    /// polkit are permission errors, a missing bus means there is no
+    /// systemd to talk to
+    fn dbus_error(context: &str, err: zbus::Error) -> CoreError {
+        match &err {
+            zbus::Error::MethodError(name, ..)
+                if matches!(
+                    name.as_str(),
+                    "org.freedesktop.DBus.Error.AccessDenied"
+                        | "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired"
+                ) =>
+            {
+                CoreError::PermissionDenied(format!("{context}: {err}"))
+            }
+            zbus::Error::InputOutput(_) | zbus::Error::Address(_) => {
+                CoreError::unsupported(format!("{context}: {err}"))
+            }
+            _ => CoreError::collection(format!("{context}: {err}")),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod dbus {
+    use crate::{
+        collectors::services::metrics::{Service, ServiceAction, ServiceCollector},
+        errors::{CoreError, CoreResult},
+    };
+
+    pub(super) fn list_services(
+        _collector: &mut ServiceCollector,
+    ) -> CoreResult<Vec<(Service, Option<String>)>> {
+        Err(CoreError::unsupported("services need systemd on Linux"))
+    }
+
+    pub(super) fn apply(_action: ServiceAction, _unit: &str) -> CoreResult<()> {
+        Err(CoreError::unsupported("services need systemd on Linux"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_usage_usec() {
+        let stat = "usage_usec 123456\nuser_usec 100000\nsystem_usec 23456\n";
+        assert_eq!(parse_usage_usec(stat), Some(123456));
+        assert_eq!(parse_usage_usec("user_usec 1\n"), None);
+    }
+
+    #[test]
+    fn test_fetch_reuses_the_listing_within_the_interval() {
+        let mut collector = ServiceCollector::default();
+        collector.set_refresh_interval(Duration::from_secs(3600));
+        let first = collector.fetch();
+        let second = collector.fetch();
+        assert_eq!(first.timestamp, second.timestamp);
+
+        collector.set_refresh_interval(Duration::ZERO);
+        assert!(collector.fetch().timestamp > first.timestamp);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_services_from_units() {
+        use zbus::zvariant::OwnedObjectPath;
+
+        let unit = |name: &str, active: &str, sub: &str| {
+            let path = format!(
+                "/org/freedesktop/systemd1/unit/{}",
+                name.replace(['.', '-'], "_")
+            );
+            (
+                name.to_string(),
+                format!("{name} description"),
+                "loaded".to_string(),
+                active.to_string(),
+                sub.to_string(),
+                String::new(),
+                OwnedObjectPath::try_from(path).unwrap(),
+                0,
+                String::new(),
+                OwnedObjectPath::try_from("/").unwrap(),
+            )
+        };
+        let services = dbus::services_from_units(vec![
+            unit("ssh.service", "active", "running"),
+            unit("dev-sda1.device", "active", "plugged"),
+            unit("cups.service", "failed", "failed"),
+            unit("apparmor.service", "inactive", "dead"),
+        ]);
+
+        let names: Vec<&str> = services
+            .iter()
+            .map(|(service, _)| service.unit.as_str())
+            .collect();
+        assert_eq!(names, ["apparmor.service", "cups.service", "ssh.service"]);
+        assert_eq!(services[1].0.active_state, "failed");
+        assert_eq!(services[2].0.sub_state, "running");
+        assert_eq!(services[2].0.description, "ssh.service description");
+        assert_eq!(
+            services[2].1.as_str(),
+            "/org/freedesktop/systemd1/unit/ssh_service"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Service types
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::constants::SERVICES_REFRESH_MS;
+
+/// A systemd service unit with the usage of its cgroup
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Service {
+    /// Unit name such as `nginx.service`
+    pub unit: String,
+    /// Description from the unit file
+    pub description: String,
+    /// `loaded`, `not-found` or `masked`
+    pub load_state: String,
+    /// `active`, `inactive`, `failed`, `activating` or `deactivating`
+    pub active_state: String,
+    /// Unit type specific state such as `running`, `exited` or `dead`
+    pub sub_state: String,
+    /// cgroup path of the unit, only set while it runs
+    pub control_group: Option<String>,
+    /// `memory.current` of the unit's cgroup in bytes
+    pub memory: Option<u64>,
+    /// CPU usage of the unit's cgroup since the previous fetch, in percent
+    /// of one core
+    pub cpu_usage: Option<f32>,
+}
+
+/// Service units at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct ServiceMetrics {
+    /// When the services were listed
+    pub timestamp: DateTime<Utc>,
+    /// Service units sorted by name
+    pub services: Vec<Service>,
+    /// Why systemd couldn't be queried, e.g. on hosts without systemd
+    pub error: Option<String>,
+}
+
+/// Action that can be applied to a service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    /// Start the unit
+    Start,
+    /// Stop the unit
+    Stop,
+    /// Stop and start the unit again
+    Restart,
+}
+
+/// Queries systemd over D-Bus and keeps the previous CPU time of every
+/// unit cgroup to compute usage
+///
+/// Listing takes a D-Bus call per running unit, so the list is kept and
+/// only read again once it is older than the refresh interval.
+#[derive(Debug)]
+pub struct ServiceCollector {
+    pub(crate) previous_cpu: HashMap<String, (Instant, u64)>,
+    /// Minimum time between two listings
+    pub(crate) refresh_interval: Duration,
+    /// The latest listing and when it was taken
+    pub(crate) latest: Option<(Instant, ServiceMetrics)>,
+    /// Connection to the system bus, opened on the first fetch and again
+    /// after a failed one
+    #[cfg(target_os = "linux")]
+    pub(crate) bus: Option<zbus::blocking::Connection>,
+}
+
+impl Default for ServiceCollector {
+    fn default() -> Self {
+        Self {
+            previous_cpu: HashMap::new(),
+            refresh_interval: Duration::from_millis(SERVICES_REFRESH_MS),
+            latest: None,
+            #[cfg(target_os = "linux")]
+            bus: None,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! systemd services and the actions that can be taken on them
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{Service, ServiceAction, ServiceCollector, ServiceMetrics};
@@ -0,0 +1,130 @@
+//! Reading sessions from utmp
+
+use std::{path::Path, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+use crate::collectors::sessions::metrics::{SessionMetrics, UserSession};
+
+impl SessionMetrics {
+    /// Reads the user sessions from utmp, like `who` does. Platforms
+    /// without utmp report no sessions.
+    pub fn fetch() -> Self {
+        let now = Utc::now();
+        Self {
+            timestamp: Some(now),
+            sessions: read_sessions(now),
+        }
+    }
+
+    /// Number of distinct users with at least one session
+    pub fn user_count(&self) -> usize {
+        let mut users: Vec<&str> = self
+            .sessions
+            .iter()
+            .map(|session| session.user.as_str())
+            .collect();
+        users.sort_unstable();
+        users.dedup();
+        users.len()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_sessions(now: DateTime<Utc>) -> Vec<UserSession> {
+    let mut sessions = Vec::new();
+    // utmp is read through the libc iterator, which keeps global state,
+    // so the whole walk happens between setutxent and endutxent
+    unsafe {
+        libc::setutxent();
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+            let entry = &*entry;
+            if entry.ut_type != libc::USER_PROCESS {
+                continue;
+            }
+            let tty = fixed_c_string(&entry.ut_line);
+            let host = fixed_c_string(&entry.ut_host);
+            sessions.push(UserSession {
+                user: fixed_c_string(&entry.ut_user),
+                idle: terminal_idle(&tty, now),
+                tty,
+                host: (!host.is_empty()).then_some(host),
+                login_time: DateTime::from_timestamp(entry.ut_tv.tv_sec.into(), 0)
+                    .unwrap_or_default(),
+                pid: entry.ut_pid as u32,
+            });
+        }
+        libc::endutxent();
+    }
+    sessions.sort_by_key(|session| session.login_time);
+    sessions
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_sessions(_now: DateTime<Utc>) -> Vec<UserSession> {
+    Vec::new()
+}
+
+/// Idle time of a terminal, the time since its device was last read,
+/// which is how `w` computes it
+fn terminal_idle(tty: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let accessed = Path::new("/dev")
+        .join(tty)
+        .metadata()
+        .ok()?
+        .accessed()
+        .ok()?;
+    (now - DateTime::<Utc>::from(accessed)).to_std().ok()
+}
+
+/// Converts a fixed-size utmp field to a string, the field is only NUL
+/// terminated when shorter than its array
+fn fixed_c_string(field: &[std::ffi::c_char]) -> String {
+    let bytes: Vec<u8> = field
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| *c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c_chars(text: &str) -> Vec<std::ffi::c_char> {
+        text.bytes().map(|b| b as std::ffi::c_char).collect()
+    }
+
+    #[test]
+    fn test_fixed_c_string_stops_at_nul_or_end() {
+        assert_eq!(fixed_c_string(&c_chars("pts/0\0\0\0")), "pts/0");
+        assert_eq!(fixed_c_string(&c_chars("tty1")), "tty1");
+        assert_eq!(fixed_c_string(&c_chars("\0garbage")), "");
+    }
+
+    #[test]
+    fn test_user_count_counts_each_user_once() {
+        let session = |user: &str, tty: &str| UserSession {
+            user: user.to_string(),
+            tty: tty.to_string(),
+            host: None,
+            login_time: DateTime::default(),
+            idle: None,
+            pid: 1,
+        };
+        let metrics = SessionMetrics {
+            timestamp: None,
+            sessions: vec![
+                session("alice", "pts/0"),
+                session("bob", "pts/1"),
+                session("alice", "pts/2"),
+            ],
+        };
+        assert_eq!(metrics.user_count(), 2);
+    }
+}
@@ -0,0 +1,33 @@
+//! Session types
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// A logged-in user session, one line of `who`
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserSession {
+    /// User name
+    pub user: String,
+    /// Terminal of the session, e.g. `pts/0` or `tty1`
+    pub tty: String,
+    /// Remote host or X display the session came from, `None` for local
+    /// logins
+    pub host: Option<String>,
+    /// When the user logged in
+    pub login_time: DateTime<Utc>,
+    /// Time since the terminal last saw input, `None` when the session has
+    /// no terminal device
+    pub idle: Option<Duration>,
+    /// PID of the login process
+    pub pid: u32,
+}
+
+/// The users logged in to the machine, read from utmp
+#[derive(Debug, Clone, Default)]
+pub struct SessionMetrics {
+    /// When utmp was read, `None` before the first read
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Sessions, earliest login first
+    pub sessions: Vec<UserSession>,
+}
@@ -0,0 +1,6 @@
+//! Logged-in user sessions from utmp
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{SessionMetrics, UserSession};
@@ -1,79 +1,500 @@
-use std::collections::VecDeque;
-use sysinfo::{Networks, System};
+//! The [`StomataSystemMetrics`] entry point and the metrics it returns
 
-use crate::collectors::{
-    SystemInfo,
-    network::metrics::NetworkMetrics,
-    process::metrics::{ProcessData, SingleProcessData},
-    system::metrics::{SystemCollector, SystemMetrics},
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use sysinfo::{Networks, System, Users};
+
+use crate::{
+    collectors::{
+        SystemInfo,
+        audio::metrics::AudioMetrics,
+        backend::Sysinfo,
+        bluetooth::metrics::BluetoothMetrics,
+        cgroups::metrics::CgroupMetrics,
+        connections::metrics::ConnectionsMetrics,
+        disks::metrics::{DiskCollector, DiskMetrics},
+        hwmon::metrics::HwmonMetrics,
+        kubernetes::metrics::PodMetrics,
+        logs::metrics::{LogCollector, LogMetrics},
+        network::{metrics::NetworkMetrics, rates::NetworkRates},
+        plugins::{
+            collectors::PluginRunner,
+            collectors::plugins_dir,
+            metrics::{Plugin, PluginMetrics},
+        },
+        poller::Poller,
+        power::metrics::PowerMetrics,
+        process::{
+            ProcessIoTracker,
+            metrics::{ProcessData, SingleProcessData},
+        },
+        services::metrics::{ServiceCollector, ServiceMetrics},
+        sessions::metrics::SessionMetrics,
+        system::{
+            metrics::SystemCollector, scheduler::SchedulerRateTracker, summary::SystemSummary,
+        },
+        vms::metrics::{VmCollector, VmMetrics},
+    },
+    constants::{AUDIO_REFRESH_MS, BLUETOOTH_REFRESH_MS, SUMMARY_PROCESSES_REFRESH_SECS},
 };
 
+/// Entry point for collecting metrics from the local machine.
+///
+/// Keeps the sysinfo state between fetches so CPU usage and rates are
+/// computed against the previous refresh, and refreshes each category no
+/// more often than its refresh interval.
 #[derive(Debug)]
 pub struct StomataSystemMetrics {
+    /// sysinfo state for CPU, memory and processes
     pub system: System,
+    /// Network interface counters
     pub network: Networks,
+    /// Per-second rates computed from consecutive network refreshes
+    pub network_rates: NetworkRates,
+    /// Per-second context switch, interrupt and fork rates from `/proc/stat`
+    pub scheduler_rates: SchedulerRateTracker,
+    /// Per-second disk rates of every process from consecutive refreshes
+    /// with disk usage
+    pub process_io: ProcessIoTracker,
+    /// Resolves process owners to user names
+    pub users: Users,
+    /// Runs the plugins discovered in the plugins directory at startup
+    pub plugins: PluginRunner,
+    /// Lists virtual machines and tracks their CPU time
+    pub vms: VmCollector,
+    /// Disk counters of the previous refresh and the mount table
+    pub disks: DiskCollector,
+    /// Lists systemd services and tracks their CPU time
+    pub services: ServiceCollector,
+    /// Keeps the latest log entries
+    pub logs: LogCollector,
+    /// Lists the audio devices on a background thread
+    pub audio: Poller<AudioMetrics>,
+    /// Lists the Bluetooth devices on a background thread
+    pub bluetooth: Poller<BluetoothMetrics>,
+    /// When each category was last refreshed
+    pub(crate) refreshed_at: HashMap<MetricsCategory, Instant>,
+    /// Minimum time between two refreshes of a category, see
+    /// [`StomataSystemMetrics::set_refresh_interval`]
+    pub(crate) refresh_intervals: HashMap<MetricsCategory, Duration>,
+    /// While paused nothing is refreshed and every fetch returns the data
+    /// of the moment collection was paused
+    paused: Option<PausedSnapshot>,
+}
+
+/// Metrics read straight from their source on every fetch, kept while
+/// paused so they freeze like the sysinfo backed metrics
+#[derive(Debug, Default)]
+struct PausedSnapshot {
+    connections: Option<ConnectionsMetrics>,
+    plugins: Option<PluginMetrics>,
+    vms: Option<VmMetrics>,
+    disks: Option<DiskMetrics>,
+    hwmon: Option<HwmonMetrics>,
+    services: Option<ServiceMetrics>,
+    sessions: Option<SessionMetrics>,
+    logs: Option<LogMetrics>,
+    audio: Option<AudioMetrics>,
+    power: Option<PowerMetrics>,
+    bluetooth: Option<BluetoothMetrics>,
+}
+
+impl Default for StomataSystemMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StomataSystemMetrics {
+    /// Takes a first snapshot of the whole system and discovers the plugins
     pub fn new() -> Self {
         let system = System::new_all();
         let network = Networks::new();
-        Self { system, network }
+        let plugins = plugins_dir()
+            .map(|dir| Plugin::discover(&dir))
+            .unwrap_or_default();
+        Self {
+            system,
+            network,
+            network_rates: NetworkRates::default(),
+            scheduler_rates: SchedulerRateTracker::default(),
+            process_io: ProcessIoTracker::default(),
+            users: Users::new_with_refreshed_list(),
+            plugins: PluginRunner::new(plugins),
+            vms: VmCollector::default(),
+            disks: DiskCollector::default(),
+            services: ServiceCollector::default(),
+            logs: LogCollector::default(),
+            audio: Poller::new(Duration::from_millis(AUDIO_REFRESH_MS), AudioMetrics::fetch),
+            bluetooth: Poller::new(
+                Duration::from_millis(BLUETOOTH_REFRESH_MS),
+                BluetoothMetrics::fetch,
+            ),
+            refreshed_at: HashMap::new(),
+            refresh_intervals: HashMap::new(),
+            paused: None,
+        }
+    }
+
+    /// The sysinfo state as of the latest refreshes, for the collectors
+    /// reading through a [`SystemBackend`](crate::collectors::SystemBackend)
+    pub fn sysinfo(&self) -> Sysinfo<'_> {
+        Sysinfo {
+            system: &self.system,
+            users: &self.users,
+            networks: &self.network,
+        }
+    }
+
+    /// Whether collection is paused, see [`pause`](Self::pause)
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
     }
 
-    pub fn fetch(&mut self, fetch_metrics: MetricsToFetch) -> Metrics<'_> {
+    /// Stops refreshing, so fetches keep returning the current values
+    pub fn pause(&mut self) {
+        self.paused.get_or_insert_with(PausedSnapshot::default);
+    }
+
+    /// Resumes refreshing.
+    ///
+    /// CPU usage and network traffic are deltas between two refreshes, so
+    /// they are refreshed once right away; otherwise the first values after
+    /// resuming would cover the whole pause.
+    pub fn resume(&mut self) {
+        if self.paused.take().is_some() {
+            // the refresh below sets a new baseline for the scheduler rates
+            self.scheduler_rates.reset();
+            self.process_io.reset();
+            self.refresh_metrics(MetricsCategory::Basic);
+            self.refresh_metrics(MetricsCategory::ProcessesWithoutTasks);
+            // the counters moved during the pause, start the rates afresh
+            self.network_rates.reset();
+            self.refresh_metrics(MetricsCategory::Networks);
+        }
+    }
+
+    /// Refreshes what `fetch_metrics` needs and returns it as the matching
+    /// [`Metrics`] variant
+    pub fn fetch(&mut self, fetch_metrics: MetricsToFetch) -> Metrics {
         match fetch_metrics {
             MetricsToFetch::SystemInfo => Metrics::SystemInfo(SystemInfo::new()),
             MetricsToFetch::SystemResource => {
                 self.refresh_metrics(MetricsCategory::Basic);
-                Metrics::SystemResource(SystemCollector::fetch(&mut self.system))
+                let scheduler = self.scheduler_rates.rates();
+                Metrics::SystemResource(SystemCollector::fetch(&self.sysinfo(), scheduler))
             }
             MetricsToFetch::Process => {
-                self.refresh_metrics(MetricsCategory::ProcessesWithoutTasks);
-                Metrics::Processes(ProcessData::fetch(&self.system))
+                self.refresh_metrics(MetricsCategory::ProcessesWithDiskUsage);
+                let mut processes = ProcessData::fetch(&self.sysinfo());
+                self.process_io.apply(&mut processes);
+                Metrics::Processes(processes)
             }
             MetricsToFetch::SingleProcessPid(pid) => {
                 self.refresh_metrics(MetricsCategory::ProcessWithPid(pid));
-                Metrics::SingleProcessPid(SingleProcessData::fetch(&mut self.system, pid))
+                Metrics::SingleProcessPid(
+                    SingleProcessData::fetch(&self.sysinfo(), pid).map(Box::new),
+                )
             }
             MetricsToFetch::Networks => {
                 self.refresh_metrics(MetricsCategory::Networks);
-                Metrics::Networks(NetworkMetrics::fetch(&self.network))
+                Metrics::Networks(NetworkMetrics::fetch(&self.sysinfo(), &self.network_rates))
+            }
+            MetricsToFetch::Plugins => {
+                let plugins = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .plugins
+                        .get_or_insert_with(|| self.plugins.fetch())
+                        .clone(),
+                    None => self.plugins.fetch(),
+                };
+                Metrics::Plugins(plugins)
+            }
+            MetricsToFetch::Connections => {
+                // socket owners are looked up through the process list
+                self.refresh_metrics(MetricsCategory::ProcessesWithoutTasks);
+                // built from the fields, the snapshot borrows `self.paused`
+                let sysinfo = Sysinfo {
+                    system: &self.system,
+                    users: &self.users,
+                    networks: &self.network,
+                };
+                let connections = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .connections
+                        .get_or_insert_with(|| ConnectionsMetrics::fetch(&sysinfo))
+                        .clone(),
+                    None => ConnectionsMetrics::fetch(&sysinfo),
+                };
+                Metrics::Connections(connections)
+            }
+            MetricsToFetch::Audio => {
+                let audio = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .audio
+                        .get_or_insert_with(|| {
+                            self.audio.latest().unwrap_or_else(AudioMetrics::pending)
+                        })
+                        .clone(),
+                    None => self.audio.latest().unwrap_or_else(AudioMetrics::pending),
+                };
+                Metrics::Audio(audio)
+            }
+            MetricsToFetch::Power => {
+                let power = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .power
+                        .get_or_insert_with(PowerMetrics::fetch)
+                        .clone(),
+                    None => PowerMetrics::fetch(),
+                };
+                Metrics::Power(power)
+            }
+            MetricsToFetch::Bluetooth => {
+                let bluetooth = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .bluetooth
+                        .get_or_insert_with(|| {
+                            self.bluetooth
+                                .latest()
+                                .unwrap_or_else(BluetoothMetrics::pending)
+                        })
+                        .clone(),
+                    None => self
+                        .bluetooth
+                        .latest()
+                        .unwrap_or_else(BluetoothMetrics::pending),
+                };
+                Metrics::Bluetooth(bluetooth)
+            }
+            MetricsToFetch::Vms => {
+                let vms = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot.vms.get_or_insert_with(|| self.vms.fetch()).clone(),
+                    None => self.vms.fetch(),
+                };
+                Metrics::Vms(vms)
+            }
+            MetricsToFetch::Disks => {
+                // built from the fields, the snapshot borrows `self.paused`
+                let sysinfo = Sysinfo {
+                    system: &self.system,
+                    users: &self.users,
+                    networks: &self.network,
+                };
+                let disks = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .disks
+                        .get_or_insert_with(|| self.disks.fetch(&sysinfo))
+                        .clone(),
+                    None => self.disks.fetch(&sysinfo),
+                };
+                Metrics::Disks(disks)
+            }
+            MetricsToFetch::Services => {
+                let services = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .services
+                        .get_or_insert_with(|| self.services.fetch())
+                        .clone(),
+                    None => self.services.fetch(),
+                };
+                Metrics::Services(services)
+            }
+            MetricsToFetch::Hwmon => {
+                let hwmon = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .hwmon
+                        .get_or_insert_with(HwmonMetrics::fetch)
+                        .clone(),
+                    None => HwmonMetrics::fetch(),
+                };
+                Metrics::Hwmon(hwmon)
+            }
+            MetricsToFetch::Logs => {
+                let logs = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .logs
+                        .get_or_insert_with(|| self.logs.fetch())
+                        .clone(),
+                    None => self.logs.fetch(),
+                };
+                Metrics::Logs(logs)
+            }
+            MetricsToFetch::Sessions => {
+                let sessions = match self.paused.as_mut() {
+                    Some(snapshot) => snapshot
+                        .sessions
+                        .get_or_insert_with(SessionMetrics::fetch)
+                        .clone(),
+                    None => SessionMetrics::fetch(),
+                };
+                Metrics::Sessions(sessions)
+            }
+            MetricsToFetch::Summary => {
+                self.refresh_metrics(MetricsCategory::Basic);
+                // only the top process comes from the process list, pages
+                // showing processes refresh it more often themselves
+                self.refresh_metrics_within(
+                    MetricsCategory::ProcessesWithoutTasks,
+                    Duration::from_secs(SUMMARY_PROCESSES_REFRESH_SECS),
+                );
+                Metrics::Summary(SystemSummary::fetch(&self.sysinfo()))
+            }
+            MetricsToFetch::Cgroups => {
+                self.refresh_metrics(MetricsCategory::ProcessesWithDiskUsage);
+                Metrics::Cgroups(CgroupMetrics::fetch(&self.sysinfo()))
+            }
+            MetricsToFetch::Pods => {
+                self.refresh_metrics(MetricsCategory::ProcessesWithoutTasks);
+                Metrics::Pods(PodMetrics::fetch(&self.sysinfo()))
             }
         }
     }
 }
 
+/// What to collect in [`StomataSystemMetrics::fetch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MetricsToFetch {
+    /// Operating system and host details
     SystemInfo,
+    /// CPU, memory, swap and pressure
     SystemResource,
+    /// The process list
     Process,
+    /// Details of one process by PID
     SingleProcessPid(u32),
+    /// Network interfaces with their rates
     Networks,
+    /// Output of the discovered plugins
+    Plugins,
+    /// Open sockets with their processes
+    Connections,
+    /// Resource usage per cgroup
+    Cgroups,
+    /// Resource usage per Kubernetes pod
+    Pods,
+    /// libvirt virtual machines
+    Vms,
+    /// Audio devices
+    Audio,
+    /// Backlight and power profile
+    Power,
+    /// Paired Bluetooth devices
+    Bluetooth,
+    /// Disk I/O rates
+    Disks,
+    /// Temperatures and fans
+    Hwmon,
+    /// systemd services
+    Services,
+    /// Logged-in user sessions
+    Sessions,
+    /// Recent log entries
+    Logs,
+    /// One-line system summary
+    Summary,
+}
+
+impl MetricsToFetch {
+    /// Whether a [`remote`](crate::remote) agent answers this request.
+    /// The others read state only meaningful on the local machine or
+    /// carry data that isn't serializable yet.
+    pub fn is_remote(&self) -> bool {
+        matches!(
+            self,
+            MetricsToFetch::SystemInfo
+                | MetricsToFetch::SystemResource
+                | MetricsToFetch::Process
+                | MetricsToFetch::Networks
+                | MetricsToFetch::Summary
+        )
+    }
 }
 
-// Response metrics
-pub enum Metrics<'a> {
+/// Result of [`StomataSystemMetrics::fetch`], one variant per
+/// [`MetricsToFetch`] request
+///
+/// Only the variants of [`MetricsToFetch::is_remote`] requests can be
+/// serialized, serializing another one fails.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Metrics {
+    /// Answer to [`MetricsToFetch::SystemInfo`]
     SystemInfo(SystemInfo),
+    /// Answer to [`MetricsToFetch::SystemResource`]
     SystemResource(SystemCollector),
+    /// Answer to [`MetricsToFetch::Process`]
     Processes(Vec<ProcessData>),
-    SingleProcessPid(Option<SingleProcessData<'a>>),
+    /// Answer to [`MetricsToFetch::SingleProcessPid`], `None` when no such
+    /// process exists
+    #[serde(skip)]
+    SingleProcessPid(Option<Box<SingleProcessData>>),
+    /// Answer to [`MetricsToFetch::Networks`]
     Networks(NetworkMetrics),
+    /// Answer to [`MetricsToFetch::Plugins`]
+    #[serde(skip)]
+    Plugins(PluginMetrics),
+    /// Answer to [`MetricsToFetch::Connections`]
+    #[serde(skip)]
+    Connections(ConnectionsMetrics),
+    /// Answer to [`MetricsToFetch::Cgroups`]
+    #[serde(skip)]
+    Cgroups(CgroupMetrics),
+    /// Answer to [`MetricsToFetch::Pods`]
+    #[serde(skip)]
+    Pods(PodMetrics),
+    /// Answer to [`MetricsToFetch::Vms`]
+    #[serde(skip)]
+    Vms(VmMetrics),
+    /// Answer to [`MetricsToFetch::Audio`]
+    #[serde(skip)]
+    Audio(AudioMetrics),
+    /// Answer to [`MetricsToFetch::Power`]
+    #[serde(skip)]
+    Power(PowerMetrics),
+    /// Answer to [`MetricsToFetch::Bluetooth`]
+    #[serde(skip)]
+    Bluetooth(BluetoothMetrics),
+    /// Answer to [`MetricsToFetch::Disks`]
+    #[serde(skip)]
+    Disks(DiskMetrics),
+    /// Answer to [`MetricsToFetch::Hwmon`]
+    #[serde(skip)]
+    Hwmon(HwmonMetrics),
+    /// Answer to [`MetricsToFetch::Services`]
+    #[serde(skip)]
+    Services(ServiceMetrics),
+    /// Answer to [`MetricsToFetch::Sessions`]
+    #[serde(skip)]
+    Sessions(SessionMetrics),
+    /// Answer to [`MetricsToFetch::Logs`]
+    #[serde(skip)]
+    Logs(LogMetrics),
+    /// Answer to [`MetricsToFetch::Summary`]
+    Summary(SystemSummary),
 }
 
+/// Part of the sysinfo state that can be refreshed on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MetricsCategory {
-    ProcessesWithoutTasks, // refreshes processes but not tasks
-    Processes,             // refreshes all processes with tasks
+    /// The process table columns of every process
+    ProcessesWithoutTasks,
+    /// Same with disk I/O, for per-cgroup totals
+    ProcessesWithDiskUsage,
+    /// All processes with their tasks
+    Processes,
+    /// A single process with its tasks
     ProcessWithPid(u32),
+    /// Memory and swap usage
     Memory,
+    /// CPU usage
     CPU,
-    AllResources, // refreshes everything
-    Basic,        // refreshes CPU + Memory usage
+    /// Everything sysinfo knows about
+    AllResources,
+    /// CPU and memory usage
+    Basic,
+    /// Network interface counters
     Networks,
 }
-
-#[derive(Debug)]
-pub enum MetricsHistory {
-    Single(SystemMetrics),
-    History(VecDeque<SystemMetrics>),
-}
@@ -1,17 +1,79 @@
-use sysinfo::{Pid, ProcessRefreshKind};
+//! Refreshing the sysinfo state behind [`StomataSystemMetrics`]
 
-use crate::collectors::structs::{MetricsCategory, StomataSystemMetrics};
+use std::time::{Duration, Instant};
+
+use sysinfo::{MINIMUM_CPU_UPDATE_INTERVAL, Pid, ProcessRefreshKind, UpdateKind};
+
+use crate::collectors::{
+    backend::Sysinfo,
+    structs::{MetricsCategory, StomataSystemMetrics},
+};
 
 impl StomataSystemMetrics {
+    /// Sets the minimum time between two refreshes of `category`, e.g. to
+    /// refresh processes less often than CPU usage. Defaults to sysinfo's
+    /// minimum CPU update interval.
+    pub fn set_refresh_interval(&mut self, category: MetricsCategory, interval: Duration) {
+        self.refresh_intervals.insert(category, interval);
+    }
+
+    /// Minimum time between two refreshes of `category`
+    pub fn refresh_interval(&self, category: MetricsCategory) -> Duration {
+        self.refresh_intervals
+            .get(&category)
+            .copied()
+            .unwrap_or(MINIMUM_CPU_UPDATE_INTERVAL)
+    }
+
+    /// Refreshes `category` unless it was refreshed less than its refresh
+    /// interval ago.
+    ///
+    /// CPU usage is a delta between two refreshes, so refreshing again right
+    /// away (several fetches in one frame) would only yield noise.
     pub fn refresh_metrics(&mut self, refresh_category: MetricsCategory) {
+        self.refresh_metrics_within(refresh_category, self.refresh_interval(refresh_category));
+    }
+
+    /// Refreshes `category` unless it was refreshed less than `max_age`
+    /// ago, for fetches that can live with older data than the page
+    /// showing it
+    pub fn refresh_metrics_within(&mut self, refresh_category: MetricsCategory, max_age: Duration) {
+        if self.is_paused() || self.refreshed_within(refresh_category, max_age) {
+            return;
+        }
+        // the fuller process list refreshes cover the table columns too
+        if refresh_category == MetricsCategory::ProcessesWithoutTasks
+            && [
+                MetricsCategory::ProcessesWithDiskUsage,
+                MetricsCategory::Processes,
+            ]
+            .into_iter()
+            .any(|category| self.refreshed_within(category, max_age))
+        {
+            return;
+        }
+        let _span = tracing::trace_span!("refresh", category = ?refresh_category).entered();
+        self.refreshed_at.insert(refresh_category, Instant::now());
         match refresh_category {
             MetricsCategory::ProcessesWithoutTasks => {
                 let _processes_updated = self.system.refresh_processes_specifics(
                     sysinfo::ProcessesToUpdate::All,
                     true,
-                    ProcessRefreshKind::everything().without_tasks(),
+                    process_table_refresh_kind(),
                 );
             }
+            MetricsCategory::ProcessesWithDiskUsage => {
+                let _processes_updated = self.system.refresh_processes_specifics(
+                    sysinfo::ProcessesToUpdate::All,
+                    true,
+                    process_table_refresh_kind().with_disk_usage(),
+                );
+                self.process_io.update(&Sysinfo {
+                    system: &self.system,
+                    users: &self.users,
+                    networks: &self.network,
+                });
+            }
             MetricsCategory::Processes => {
                 let _processes_updated = self.system.refresh_processes_specifics(
                     sysinfo::ProcessesToUpdate::All,
@@ -20,9 +82,12 @@ impl StomataSystemMetrics {
                 );
             }
             MetricsCategory::ProcessWithPid(pid) => {
-                self.system.refresh_processes(
+                // the details (cwd, environment, disk usage, tasks) are
+                // only read for the process being viewed
+                let _processes_updated = self.system.refresh_processes_specifics(
                     sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
                     true,
+                    ProcessRefreshKind::everything(),
                 );
             }
             MetricsCategory::CPU => {
@@ -33,14 +98,38 @@ impl StomataSystemMetrics {
             }
             MetricsCategory::AllResources => {
                 self.system.refresh_all();
+                self.users.refresh();
             }
             MetricsCategory::Basic => {
                 self.system.refresh_memory();
                 self.system.refresh_cpu_usage();
+                self.scheduler_rates.update();
             }
             MetricsCategory::Networks => {
                 self.network.refresh(true);
+                self.network_rates.update(&Sysinfo {
+                    system: &self.system,
+                    users: &self.users,
+                    networks: &self.network,
+                });
             }
         }
     }
+
+    fn refreshed_within(&self, category: MetricsCategory, max_age: Duration) -> bool {
+        self.refreshed_at
+            .get(&category)
+            .is_some_and(|at| at.elapsed() < max_age)
+    }
+}
+
+/// What the process table shows: CPU and memory on every refresh, owner,
+/// command line and executable once per process since they rarely change
+fn process_table_refresh_kind() -> ProcessRefreshKind {
+    ProcessRefreshKind::nothing()
+        .with_cpu()
+        .with_memory()
+        .with_user(UpdateKind::OnlyIfNotSet)
+        .with_cmd(UpdateKind::OnlyIfNotSet)
+        .with_exe(UpdateKind::OnlyIfNotSet)
 }
@@ -1,14 +1,35 @@
+//! Reading system resources from sysinfo and `/proc`
+
+use std::fs;
+
 use chrono::Utc;
-use sysinfo::System;
 
-use crate::collectors::system::metrics::{SystemCollector, SystemMetrics};
+use crate::{
+    collectors::{
+        backend::SystemBackend,
+        system::{
+            cpufreq::CpuFrequency,
+            metrics::{Pressure, PressureAverages, SystemCollector, SystemMetrics},
+            scheduler::SchedulerRates,
+        },
+    },
+    constants::PRESSURE_DIR,
+};
 
 impl SystemCollector {
-    pub fn fetch(system: &mut System) -> Self {
-        let cpu_count = system.cpus().len();
+    /// Collects the resource usage of the refreshed `system`, with the
+    /// `scheduler` rates computed by the caller since they need the
+    /// previous read
+    pub fn fetch(system: &dyn SystemBackend, scheduler: Option<SchedulerRates>) -> Self {
+        let cpu_count = system.cpu_count();
         let cpu_usage = system.global_cpu_usage();
         let memory_used = system.used_memory();
         let memory_total = system.total_memory();
+        let memory_available = system.available_memory();
+        let memory_free = system.free_memory();
+        let (memory_cached, memory_buffers) = read_meminfo()
+            .map(|(cached, buffers)| (Some(cached), Some(buffers)))
+            .unwrap_or_default();
         let swap_used = system.used_swap();
         let swap_total = system.total_swap();
 
@@ -19,9 +40,112 @@ impl SystemCollector {
                 cpu_usage,
                 memory_used,
                 memory_total,
+                memory_available,
+                memory_free,
+                memory_cached,
+                memory_buffers,
                 swap_used,
                 swap_total,
+                pressure: read_pressure(),
+                scheduler,
+                cpu_frequencies: CpuFrequency::fetch_all(),
             },
         }
     }
 }
+
+/// Page cache and buffer sizes in bytes from `/proc/meminfo`
+fn read_meminfo() -> Option<(u64, u64)> {
+    fs::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|meminfo| parse_meminfo(&meminfo))
+}
+
+/// Parses the page cache and buffer sizes in bytes from the contents of
+/// `/proc/meminfo`.
+///
+/// Reclaimable slab (`SReclaimable`) is counted as cache, matching what
+/// `free` reports.
+pub fn parse_meminfo(meminfo: &str) -> Option<(u64, u64)> {
+    let field = |name: &str| {
+        meminfo.lines().find_map(|line| {
+            let value = line.strip_prefix(name)?.strip_prefix(':')?;
+            let kib = value
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()?;
+            Some(kib * 1024)
+        })
+    };
+
+    let cached = field("Cached")? + field("SReclaimable").unwrap_or(0);
+    let buffers = field("Buffers")?;
+    Some((cached, buffers))
+}
+
+/// Reads the CPU, memory and IO pressure, `None` when any is missing
+fn read_pressure() -> Option<Pressure> {
+    let read = |resource: &str| {
+        fs::read_to_string(format!("{PRESSURE_DIR}/{resource}"))
+            .ok()
+            .and_then(|contents| parse_pressure(&contents))
+    };
+    Some(Pressure {
+        cpu: read("cpu")?,
+        memory: read("memory")?,
+        io: read("io")?,
+    })
+}
+
+/// Parses the `some` line of a `/proc/pressure` file, e.g.
+/// `some avg10=3.76 avg60=2.71 avg300=2.00 total=110879707`
+pub fn parse_pressure(contents: &str) -> Option<PressureAverages> {
+    let line = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("some "))?;
+    let field = |name: &str| {
+        line.split_whitespace().find_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            (key == name).then(|| value.parse::<f32>().ok()).flatten()
+        })
+    };
+    Some(PressureAverages {
+        avg10: field("avg10")?,
+        avg60: field("avg60")?,
+        avg300: field("avg300")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo = "MemTotal:       16318412 kB\nMemFree:         1234567 kB\nMemAvailable:    9876543 kB\nBuffers:          204800 kB\nCached:          4096000 kB\nSwapCached:         1024 kB\nSReclaimable:     512000 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            Some(((4_096_000 + 512_000) * 1024, 204_800 * 1024))
+        );
+        assert_eq!(parse_meminfo("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_parse_pressure() {
+        let contents = "some avg10=3.76 avg60=2.71 avg300=2.00 total=110879707\nfull avg10=0.50 avg60=0.10 avg300=0.00 total=42\n";
+        assert_eq!(
+            parse_pressure(contents),
+            Some(PressureAverages {
+                avg10: 3.76,
+                avg60: 2.71,
+                avg300: 2.00,
+            })
+        );
+        assert_eq!(
+            parse_pressure("full avg10=1.00 avg60=1.00 avg300=1.00\n"),
+            None
+        );
+    }
+}
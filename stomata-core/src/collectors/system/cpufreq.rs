@@ -0,0 +1,145 @@
+//! CPU frequency scaling from Linux cpufreq
+//!
+//! Every CPU has a `cpufreq` directory under `/sys/devices/system/cpu`
+//! with its current, minimum and maximum frequency in kHz and the governor
+//! choosing between them. A CPU held far below its maximum while busy is
+//! being throttled, usually because it runs too hot.
+
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{CPUFREQ_DIR, THROTTLE_FREQUENCY_RATIO, THROTTLE_LOAD_PERCENT};
+
+/// Frequency scaling state of one CPU
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuFrequency {
+    /// Logical CPU number, `cpu<n>` in sysfs
+    pub cpu: usize,
+    /// Current frequency in MHz
+    pub current_mhz: u64,
+    /// Lowest frequency the hardware supports in MHz
+    pub min_mhz: u64,
+    /// Highest frequency the hardware supports in MHz
+    pub max_mhz: u64,
+    /// Scaling governor, e.g. `powersave` or `performance`
+    pub governor: Option<String>,
+}
+
+impl CpuFrequency {
+    /// Reads every CPU with cpufreq support, sorted by CPU number. Empty on
+    /// other platforms, in most VMs and without a cpufreq driver.
+    pub fn fetch_all() -> Vec<Self> {
+        Self::read_all(Path::new(CPUFREQ_DIR))
+    }
+
+    /// Reads the `cpu<n>/cpufreq` directories under `dir`
+    pub fn read_all(dir: &Path) -> Vec<Self> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut frequencies: Vec<Self> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let cpu = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("cpu")?
+                    .parse()
+                    .ok()?;
+                Self::read(cpu, &entry.path().join("cpufreq"))
+            })
+            .collect();
+        frequencies.sort_by_key(|frequency| frequency.cpu);
+        frequencies
+    }
+
+    /// Reads the cpufreq directory of `cpu`, `None` without a current
+    /// frequency
+    fn read(cpu: usize, dir: &Path) -> Option<Self> {
+        let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+        let mhz = |name: &str| {
+            read(name)
+                .and_then(|khz| khz.trim().parse::<u64>().ok())
+                .map(|khz| khz / 1000)
+        };
+        Some(Self {
+            cpu,
+            current_mhz: mhz("scaling_cur_freq")?,
+            min_mhz: mhz("cpuinfo_min_freq").unwrap_or_default(),
+            max_mhz: mhz("cpuinfo_max_freq").unwrap_or_default(),
+            governor: read("scaling_governor").map(|governor| governor.trim().to_string()),
+        })
+    }
+}
+
+/// Whether the CPUs look throttled: the system is busy (`cpu_usage` at
+/// least `THROTTLE_LOAD_PERCENT`) yet the CPUs run on average below
+/// `THROTTLE_FREQUENCY_RATIO` of their maximum frequency. `false` without
+/// frequencies.
+pub fn is_throttled(frequencies: &[CpuFrequency], cpu_usage: f32) -> bool {
+    let max: u64 = frequencies.iter().map(|frequency| frequency.max_mhz).sum();
+    if max == 0 || cpu_usage < THROTTLE_LOAD_PERCENT {
+        return false;
+    }
+    let current: u64 = frequencies
+        .iter()
+        .map(|frequency| frequency.current_mhz)
+        .sum();
+    (current as f64) < max as f64 * THROTTLE_FREQUENCY_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn frequency(current_mhz: u64) -> CpuFrequency {
+        CpuFrequency {
+            current_mhz,
+            max_mhz: 4000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_read_all() {
+        let dir = env::temp_dir().join(format!("stomata-cpufreq-{}", std::process::id()));
+        for (cpu, khz) in [(1, "2400000"), (0, "800000")] {
+            let cpufreq = dir.join(format!("cpu{cpu}")).join("cpufreq");
+            fs::create_dir_all(&cpufreq).unwrap();
+            fs::write(cpufreq.join("scaling_cur_freq"), format!("{khz}\n")).unwrap();
+            fs::write(cpufreq.join("cpuinfo_min_freq"), "400000\n").unwrap();
+            fs::write(cpufreq.join("cpuinfo_max_freq"), "4700000\n").unwrap();
+            fs::write(cpufreq.join("scaling_governor"), "powersave\n").unwrap();
+        }
+        // not a CPU
+        fs::create_dir_all(dir.join("cpuidle")).unwrap();
+
+        let frequencies = CpuFrequency::read_all(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(frequencies.len(), 2);
+        assert_eq!(
+            frequencies[0],
+            CpuFrequency {
+                cpu: 0,
+                current_mhz: 800,
+                min_mhz: 400,
+                max_mhz: 4700,
+                governor: Some("powersave".to_string()),
+            }
+        );
+        assert_eq!(frequencies[1].current_mhz, 2400);
+    }
+
+    #[test]
+    fn test_is_throttled() {
+        let slow = [frequency(1200), frequency(1400)];
+        assert!(is_throttled(&slow, 95.0));
+        // idle CPUs clock down on purpose
+        assert!(!is_throttled(&slow, 10.0));
+        assert!(!is_throttled(&[frequency(3900), frequency(3600)], 95.0));
+        assert!(!is_throttled(&[], 95.0));
+    }
+}
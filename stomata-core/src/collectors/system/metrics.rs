@@ -1,17 +1,75 @@
+//! System resource types
+
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::collectors::system::{cpufreq::CpuFrequency, scheduler::SchedulerRates};
 
-#[derive(Debug, Default, Clone)]
+/// CPU, memory and swap usage at one point in time
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SystemMetrics {
+    /// When the values were read
     pub timestamp: DateTime<Utc>,
+    /// Logical CPUs
     pub cpu_count: usize,
+    /// Average usage of all CPUs in percent
     pub cpu_usage: f32,
+    /// Memory in use in bytes
     pub memory_used: u64,
+    /// Installed memory in bytes
     pub memory_total: u64,
+    /// Memory that can be handed to new allocations without swapping,
+    /// including reclaimable cache
+    pub memory_available: u64,
+    /// Memory not used for anything, not even cache
+    pub memory_free: u64,
+    /// Page cache, `None` where the platform doesn't report it
+    pub memory_cached: Option<u64>,
+    /// Kernel buffers, `None` where the platform doesn't report it
+    pub memory_buffers: Option<u64>,
+    /// Swap in use in bytes
     pub swap_used: u64,
+    /// Swap size in bytes
     pub swap_total: u64,
+    /// Pressure Stall Information, `None` on kernels without PSI and on
+    /// other platforms
+    pub pressure: Option<Pressure>,
+    /// Context switches, interrupts and forks per second, `None` until two
+    /// refreshes were made and on platforms without `/proc/stat`
+    pub scheduler: Option<SchedulerRates>,
+    /// Frequency and governor of every CPU, empty without cpufreq (other
+    /// platforms and most VMs)
+    #[serde(default)]
+    pub cpu_frequencies: Vec<CpuFrequency>,
+}
+
+/// Share of wall time, in percent, in which at least one task was stalled
+/// waiting for a resource, averaged over 10, 60 and 300 seconds
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PressureAverages {
+    /// Average over the last 10 seconds
+    pub avg10: f32,
+    /// Average over the last 60 seconds
+    pub avg60: f32,
+    /// Average over the last 300 seconds
+    pub avg300: f32,
+}
+
+/// Pressure Stall Information from `/proc/pressure`, a saturation signal
+/// that shows tasks waiting even when utilization looks fine
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    /// Tasks waiting for a CPU
+    pub cpu: PressureAverages,
+    /// Tasks waiting for memory, e.g. reclaim or swap-in
+    pub memory: PressureAverages,
+    /// Tasks waiting for I/O
+    pub io: PressureAverages,
 }
 
-#[derive(Debug)]
+/// Answer to a system resource fetch
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemCollector {
+    /// The values read
     pub system_metrics: SystemMetrics,
 }
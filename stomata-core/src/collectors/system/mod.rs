@@ -1,2 +1,10 @@
+//! CPU, memory, swap, pressure, scheduler and CPU frequency metrics
+
 pub mod collectors;
+pub mod cpufreq;
 pub mod metrics;
+pub mod scheduler;
+pub mod summary;
+
+pub use cpufreq::CpuFrequency;
+pub use metrics::{SystemCollector, SystemMetrics};
@@ -0,0 +1,156 @@
+//! Context switch, interrupt and fork rates
+//!
+//! `/proc/stat` only has counters since boot, so the rates are computed
+//! from two consecutive reads and the time between them, like the network
+//! rates.
+
+use std::{fs, time::Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::PROC_STAT_PATH;
+
+/// Cumulative scheduler counters since boot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchedulerCounters {
+    /// Context switches, the `ctxt` line
+    pub context_switches: u64,
+    /// Interrupts serviced, the first field of the `intr` line
+    pub interrupts: u64,
+    /// Processes and threads created, the `processes` line
+    pub forks: u64,
+}
+
+/// Scheduler activity per second
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerRates {
+    /// Context switches per second
+    pub context_switches: f64,
+    /// Interrupts per second
+    pub interrupts: f64,
+    /// Processes and threads created per second
+    pub forks: f64,
+}
+
+/// Computes scheduler rates from consecutive reads of `/proc/stat`
+#[derive(Debug, Default)]
+pub struct SchedulerRateTracker {
+    previous: Option<(SchedulerCounters, Instant)>,
+    rates: Option<SchedulerRates>,
+}
+
+impl SchedulerRateTracker {
+    /// Reads `/proc/stat` and computes the rates since the previous read.
+    /// Platforms without it have no rates.
+    pub fn update(&mut self) {
+        let counters = fs::read_to_string(PROC_STAT_PATH)
+            .ok()
+            .and_then(|stat| parse_proc_stat(&stat));
+        match counters {
+            Some(counters) => self.update_from(counters, Instant::now()),
+            None => self.reset(),
+        }
+    }
+
+    /// Computes the rates from `counters` read at `now`. The first read
+    /// only sets the baseline; counters that went backwards count as no
+    /// activity.
+    pub fn update_from(&mut self, counters: SchedulerCounters, now: Instant) {
+        if let Some((previous, previous_at)) = self.previous {
+            let elapsed = now.duration_since(previous_at).as_secs_f64();
+            if elapsed > 0.0 {
+                let rate =
+                    |current: u64, previous: u64| current.saturating_sub(previous) as f64 / elapsed;
+                self.rates = Some(SchedulerRates {
+                    context_switches: rate(counters.context_switches, previous.context_switches),
+                    interrupts: rate(counters.interrupts, previous.interrupts),
+                    forks: rate(counters.forks, previous.forks),
+                });
+            }
+        }
+        self.previous = Some((counters, now));
+    }
+
+    /// Rates computed by the latest update, `None` until two reads were
+    /// made
+    pub fn rates(&self) -> Option<SchedulerRates> {
+        self.rates
+    }
+
+    /// Forgets the previous read, so the next update only sets a new
+    /// baseline
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Parses the `ctxt`, `intr` and `processes` counters of `/proc/stat`.
+/// Only the first `intr` value, the total, is used.
+pub fn parse_proc_stat(stat: &str) -> Option<SchedulerCounters> {
+    let field = |name: &str| {
+        stat.lines().find_map(|line| {
+            let mut values = line.split_whitespace();
+            (values.next()? == name)
+                .then(|| values.next()?.parse::<u64>().ok())
+                .flatten()
+        })
+    };
+    Some(SchedulerCounters {
+        context_switches: field("ctxt")?,
+        interrupts: field("intr")?,
+        forks: field("processes")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let stat = "cpu  10 0 20 300 0 0 0 0 0 0\nintr 5000 12 0 3\nctxt 80000\nbtime 1700000000\nprocesses 1234\nprocs_running 2\n";
+        assert_eq!(
+            parse_proc_stat(stat),
+            Some(SchedulerCounters {
+                context_switches: 80_000,
+                interrupts: 5000,
+                forks: 1234,
+            })
+        );
+        assert_eq!(parse_proc_stat("ctxt 1\n"), None);
+    }
+
+    #[test]
+    fn test_update_from_computes_rates_per_second() {
+        let mut tracker = SchedulerRateTracker::default();
+        let start = Instant::now();
+        tracker.update_from(
+            SchedulerCounters {
+                context_switches: 1000,
+                interrupts: 500,
+                forks: 10,
+            },
+            start,
+        );
+        assert_eq!(tracker.rates(), None);
+
+        tracker.update_from(
+            SchedulerCounters {
+                context_switches: 5000,
+                interrupts: 400,
+                forks: 30,
+            },
+            start + Duration::from_secs(2),
+        );
+        assert_eq!(
+            tracker.rates(),
+            Some(SchedulerRates {
+                context_switches: 2000.0,
+                interrupts: 0.0,
+                forks: 10.0,
+            })
+        );
+    }
+}
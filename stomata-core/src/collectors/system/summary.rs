@@ -0,0 +1,81 @@
+//! One-line system summary: load, memory, the busiest process and the
+//! number of zombie and orphaned processes
+
+use serde::{Deserialize, Serialize};
+
+use crate::collectors::backend::SystemBackend;
+
+/// Process using the most CPU at the last refresh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopProcess {
+    /// Process id
+    pub pid: u32,
+    /// Executable name
+    pub name: String,
+    /// CPU usage in percent of one core
+    pub cpu_usage: f32,
+}
+
+/// Headline figures shown on every page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemSummary {
+    /// Global CPU usage in percent
+    pub cpu_usage: f32,
+    /// Memory in use in percent of the installed memory
+    pub memory_percent: f64,
+    /// Swap in use in percent of the swap space, 0 without swap
+    pub swap_percent: f64,
+    /// Process using the most CPU, `None` without processes
+    pub top_process: Option<TopProcess>,
+    /// Processes that exited but were not reaped by their parent
+    #[serde(default)]
+    pub zombies: usize,
+    /// Processes re-parented to PID 1 after their parent exited
+    #[serde(default)]
+    pub orphans: usize,
+}
+
+impl SystemSummary {
+    /// Reads the summary from the last refresh of `system`
+    pub fn fetch(system: &dyn SystemBackend) -> Self {
+        let processes = system.processes();
+        let zombies = processes.iter().filter(|process| process.zombie).count();
+        let orphans = processes.iter().filter(|process| process.orphan).count();
+        let top_process = processes
+            .into_iter()
+            .max_by(|a, b| a.cpu_usage.total_cmp(&b.cpu_usage))
+            .map(|process| TopProcess {
+                pid: process.pid,
+                name: process.name,
+                cpu_usage: process.cpu_usage,
+            });
+        Self {
+            cpu_usage: system.global_cpu_usage(),
+            memory_percent: percent(system.used_memory(), system.total_memory()),
+            swap_percent: percent(system.used_swap(), system.total_swap()),
+            top_process,
+            zombies,
+            orphans,
+        }
+    }
+}
+
+/// `used` as a percentage of `total`, 0 when there is no total (no swap)
+fn percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 / total as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_without_total() {
+        assert_eq!(percent(512, 2048), 25.0);
+        assert_eq!(percent(0, 0), 0.0);
+    }
+}
@@ -1,8 +1,17 @@
+//! Reading host information from sysinfo
+
 use sysinfo::System;
 
 use crate::collectors::system_info::metrics::SystemInfo;
 
+impl Default for SystemInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SystemInfo {
+    /// Reads the host information, `Unknown` for values the platform lacks
     pub fn new() -> Self {
         Self {
             os_name: System::name().unwrap_or_else(|| "Unknown".to_string()),
@@ -1,7 +1,16 @@
-#[derive(Debug, Clone)]
+//! System information type
+
+use serde::{Deserialize, Serialize};
+
+/// Operating system and host of the machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
+    /// Distribution name, e.g. `Ubuntu`
     pub os_name: String,
+    /// Distribution version, e.g. `24.04`
     pub os_version: String,
+    /// Kernel release, e.g. `6.8.0-45-generic`
     pub kernel_version: String,
+    /// Host name
     pub hostname: String,
 }
@@ -1,3 +1,5 @@
+//! Operating system and host information
+
 pub mod collectors;
 pub mod metrics;
 
@@ -0,0 +1,177 @@
+//! Reading USB devices from sysfs and arranging them as a tree
+
+use std::{fs, path::Path};
+
+use crate::{
+    collectors::usb::metrics::UsbDevice,
+    constants::USB_DEVICES_DIR,
+    errors::{CoreError, CoreResult},
+};
+
+impl UsbDevice {
+    /// Enumerates the USB devices as a tree of buses, hubs and devices.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::UnsupportedPlatform`] if `/sys/bus/usb/devices`
+    /// doesn't exist, e.g. when the platform isn't Linux, or an error if it
+    /// can't be read.
+    pub fn fetch() -> CoreResult<Vec<Self>> {
+        let devices = fs::read_dir(USB_DEVICES_DIR).map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => {
+                CoreError::unsupported(format!("{USB_DEVICES_DIR} does not exist"))
+            }
+            _ => CoreError::io(format!("failed to read {USB_DEVICES_DIR}"), err),
+        })?;
+
+        let mut interface_drivers: Vec<(String, String)> = Vec::new();
+        let mut flat = Vec::new();
+        for entry in devices.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            match name.split_once(':') {
+                // interfaces are named `<device>:<config>.<interface>`
+                Some((device, _)) => {
+                    if let Some(driver) = driver_name(&path) {
+                        interface_drivers.push((device.to_string(), driver));
+                    }
+                }
+                None => flat.push(Self::read(&name, &path)),
+            }
+        }
+
+        for device in &mut flat {
+            device.drivers = interface_drivers
+                .iter()
+                .filter(|(name, _)| *name == device.sysfs_name)
+                .map(|(_, driver)| driver.clone())
+                .collect();
+            device.drivers.sort();
+            device.drivers.dedup();
+        }
+        Ok(build_tree(flat))
+    }
+
+    fn read(name: &str, path: &Path) -> Self {
+        let attribute = |file: &str| -> Option<String> {
+            fs::read_to_string(path.join(file))
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let number = |file: &str| -> u32 {
+            attribute(file)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0)
+        };
+
+        Self {
+            sysfs_name: name.to_string(),
+            bus: number("busnum"),
+            device_number: number("devnum"),
+            vendor_id: attribute("idVendor").unwrap_or_default(),
+            product_id: attribute("idProduct").unwrap_or_default(),
+            manufacturer: attribute("manufacturer"),
+            product: attribute("product"),
+            speed: attribute("speed"),
+            drivers: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Product name, falling back to the vendor:product id
+    pub fn display_name(&self) -> String {
+        match (&self.manufacturer, &self.product) {
+            (Some(manufacturer), Some(product)) => format!("{manufacturer} {product}"),
+            (None, Some(product)) => product.clone(),
+            _ => format!("{}:{}", self.vendor_id, self.product_id),
+        }
+    }
+}
+
+fn driver_name(path: &Path) -> Option<String> {
+    let target = fs::read_link(path.join("driver")).ok()?;
+    Some(target.file_name()?.to_string_lossy().to_string())
+}
+
+/// sysfs name of the hub `name` is plugged into: `1-4.2` hangs off `1-4`,
+/// `1-4` off the root hub `usb1`. Root hubs have no parent.
+pub fn parent_name(name: &str) -> Option<String> {
+    if name.starts_with("usb") {
+        return None;
+    }
+    match name.rsplit_once('.') {
+        Some((parent, _)) => Some(parent.to_string()),
+        None => {
+            let (bus, _) = name.split_once('-')?;
+            Some(format!("usb{bus}"))
+        }
+    }
+}
+
+/// Nests `devices` under their hubs. Devices whose hub is missing are kept
+/// at the top level. Siblings are sorted by sysfs name.
+pub fn build_tree(mut devices: Vec<UsbDevice>) -> Vec<UsbDevice> {
+    // attach the deepest devices first so hubs are complete before they
+    // are attached to their own parent
+    devices.sort_by_key(|device| std::cmp::Reverse(device.sysfs_name.matches(['-', '.']).count()));
+
+    let mut roots = Vec::new();
+    while !devices.is_empty() {
+        let device = devices.remove(0);
+        let parent = parent_name(&device.sysfs_name)
+            .and_then(|parent| devices.iter_mut().find(|other| other.sysfs_name == parent));
+        match parent {
+            Some(parent) => parent.children.push(device),
+            None => roots.push(device),
+        }
+    }
+
+    sort_tree(&mut roots);
+    roots
+}
+
+fn sort_tree(devices: &mut [UsbDevice]) {
+    devices.sort_by(|a, b| (a.bus, &a.sysfs_name).cmp(&(b.bus, &b.sysfs_name)));
+    for device in devices {
+        sort_tree(&mut device.children);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(name: &str, bus: u32) -> UsbDevice {
+        UsbDevice {
+            sysfs_name: name.to_string(),
+            bus,
+            ..UsbDevice::default()
+        }
+    }
+
+    #[test]
+    fn test_build_tree() {
+        assert_eq!(parent_name("usb1"), None);
+        assert_eq!(parent_name("1-4"), Some("usb1".to_string()));
+        assert_eq!(parent_name("1-4.2.1"), Some("1-4.2".to_string()));
+
+        let tree = build_tree(vec![
+            device("1-4.2", 1),
+            device("usb2", 2),
+            device("1-4", 1),
+            device("usb1", 1),
+            device("1-1", 1),
+            device("3-1", 3),
+        ]);
+        let names: Vec<&str> = tree.iter().map(|d| d.sysfs_name.as_str()).collect();
+        assert_eq!(names, vec!["usb1", "usb2", "3-1"]);
+        let bus_one: Vec<&str> = tree[0]
+            .children
+            .iter()
+            .map(|d| d.sysfs_name.as_str())
+            .collect();
+        assert_eq!(bus_one, vec!["1-1", "1-4"]);
+        assert_eq!(tree[0].children[1].children[0].sysfs_name, "1-4.2");
+    }
+}
@@ -0,0 +1,28 @@
+//! USB device type
+
+/// A USB device from `/sys/bus/usb/devices`, with the devices plugged into
+/// it when it is a hub
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UsbDevice {
+    /// sysfs name encoding the port path, e.g. `usb1` for a root hub or
+    /// `1-4.2` for port 2 of the hub on port 4 of bus 1
+    pub sysfs_name: String,
+    /// Bus number
+    pub bus: u32,
+    /// Device number on the bus, as shown by `lsusb`
+    pub device_number: u32,
+    /// Hex vendor id, e.g. `046d`
+    pub vendor_id: String,
+    /// Hex product id
+    pub product_id: String,
+    /// Manufacturer string of the device
+    pub manufacturer: Option<String>,
+    /// Product string of the device
+    pub product: Option<String>,
+    /// Negotiated speed in Mbit/s as reported by the kernel, e.g. `480`
+    pub speed: Option<String>,
+    /// Kernel drivers bound to the device's interfaces
+    pub drivers: Vec<String>,
+    /// Devices plugged into this hub
+    pub children: Vec<UsbDevice>,
+}
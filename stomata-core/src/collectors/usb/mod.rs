@@ -0,0 +1,6 @@
+//! USB devices from `/sys/bus/usb/devices`
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::UsbDevice;
@@ -0,0 +1,220 @@
+//! Listing and controlling virtual machines with `virsh`
+
+use std::{
+    collections::HashMap,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::{
+        command::run_with_timeout,
+        vms::metrics::{Vm, VmAction, VmCollector, VmMetrics, VmState},
+    },
+    constants::VIRSH_TIMEOUT_MS,
+    errors::CoreResult,
+};
+
+impl VmState {
+    /// Maps the numeric `virDomainState` reported by libvirt
+    pub fn from_code(code: u64) -> Self {
+        match code {
+            1 => VmState::Running,
+            2 => VmState::Blocked,
+            3 => VmState::Paused,
+            4 => VmState::ShuttingDown,
+            5 => VmState::ShutOff,
+            6 => VmState::Crashed,
+            7 => VmState::Suspended,
+            _ => VmState::Unknown,
+        }
+    }
+
+    /// Lowercase name of the state as printed by `virsh`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VmState::Running => "running",
+            VmState::Blocked => "blocked",
+            VmState::Paused => "paused",
+            VmState::ShuttingDown => "shutting down",
+            VmState::ShutOff => "shut off",
+            VmState::Crashed => "crashed",
+            VmState::Suspended => "suspended",
+            VmState::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the VM has a running QEMU process
+    pub fn is_active(&self) -> bool {
+        !matches!(self, VmState::ShutOff | VmState::Crashed | VmState::Unknown)
+    }
+}
+
+impl VmAction {
+    /// The virsh subcommand performing the action
+    pub fn command(&self) -> &'static str {
+        match self {
+            VmAction::Start => "start",
+            VmAction::Shutdown => "shutdown",
+        }
+    }
+}
+
+impl VmCollector {
+    /// A collector connecting to `uri`, virsh's default when `None`
+    pub fn new(uri: Option<String>) -> Self {
+        Self {
+            uri,
+            previous_cpu: HashMap::new(),
+        }
+    }
+
+    /// Lists every defined VM, running or not, with its usage.
+    ///
+    /// Never fails; when libvirt can't be queried the error is returned in
+    /// `VmMetrics::error` with an empty VM list.
+    pub fn fetch(&mut self) -> VmMetrics {
+        let output = run_with_timeout(
+            self.virsh().args(["domstats", "--raw"]),
+            Duration::from_millis(VIRSH_TIMEOUT_MS),
+        );
+
+        let mut vms = match output {
+            Ok(output) => parse_domstats(&output),
+            Err(err) => {
+                tracing::warn!("virsh domstats failed: {err}");
+                return VmMetrics {
+                    timestamp: Utc::now(),
+                    vms: Vec::new(),
+                    error: Some(format!("virsh domstats failed: {err}")),
+                };
+            }
+        };
+
+        let now = Instant::now();
+        let mut previous_cpu = HashMap::new();
+        for (vm, cpu_time) in vms.iter_mut() {
+            if let Some(cpu_time) = *cpu_time {
+                if let Some((then, previous)) = self.previous_cpu.get(&vm.name) {
+                    let elapsed = now.duration_since(*then).as_nanos() as f64;
+                    if elapsed > 0.0 && cpu_time >= *previous {
+                        vm.cpu_usage =
+                            Some(((cpu_time - previous) as f64 / elapsed * 100.0) as f32);
+                    }
+                }
+                previous_cpu.insert(vm.name.clone(), (now, cpu_time));
+            }
+        }
+        self.previous_cpu = previous_cpu;
+
+        VmMetrics {
+            timestamp: Utc::now(),
+            vms: vms.into_iter().map(|(vm, _)| vm).collect(),
+            error: None,
+        }
+    }
+
+    /// Starts or gracefully shuts down the VM called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the virsh error, e.g. when the VM is already running or the
+    /// user isn't allowed to manage it.
+    pub fn apply(&self, name: &str, action: VmAction) -> CoreResult<()> {
+        run_with_timeout(
+            self.virsh().args([action.command(), name]),
+            Duration::from_millis(VIRSH_TIMEOUT_MS),
+        )?;
+        Ok(())
+    }
+
+    fn virsh(&self) -> Command {
+        let mut command = Command::new("virsh");
+        if let Some(uri) = &self.uri {
+            command.args(["--connect", uri]);
+        }
+        command
+    }
+}
+
+/// Parses the output of `virsh domstats --raw` into VMs and their
+/// cumulative CPU time in nanoseconds.
+///
+/// Each domain starts with a `Domain: '<name>'` line followed by indented
+/// `key=value` statistics. Memory statistics are reported in KiB.
+pub fn parse_domstats(output: &str) -> Vec<(Vm, Option<u64>)> {
+    let mut domains: Vec<(String, HashMap<&str, u64>)> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("Domain:") {
+            let name = name.trim().trim_matches(['\'', '"']).to_string();
+            domains.push((name, HashMap::new()));
+        } else if let Some((key, value)) = line.split_once('=')
+            && let Some((_, stats)) = domains.last_mut()
+            && let Ok(value) = value.parse::<u64>()
+        {
+            stats.insert(key, value);
+        }
+    }
+
+    domains
+        .into_iter()
+        .map(|(name, stats)| {
+            let get = |key: &str| stats.get(key).copied();
+            // per device statistics are numbered, e.g. block.0.rd.bytes
+            let sum = |prefix: &str, suffix: &str| -> u64 {
+                stats
+                    .iter()
+                    .filter(|(key, _)| key.starts_with(prefix) && key.ends_with(suffix))
+                    .map(|(_, value)| value)
+                    .sum()
+            };
+
+            let vm = Vm {
+                name,
+                state: VmState::from_code(get("state.state").unwrap_or(0)),
+                vcpus: get("vcpu.current").or(get("vcpu.maximum")).unwrap_or(0) as u32,
+                cpu_usage: None,
+                memory_used: get("balloon.rss").or(get("balloon.current")).unwrap_or(0) * 1024,
+                memory_max: get("balloon.maximum").unwrap_or(0) * 1024,
+                disk_read_bytes: sum("block.", ".rd.bytes"),
+                disk_written_bytes: sum("block.", ".wr.bytes"),
+                net_rx_bytes: sum("net.", ".rx.bytes"),
+                net_tx_bytes: sum("net.", ".tx.bytes"),
+            };
+            (vm, get("cpu.time"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_domstats() {
+        let output = "Domain: 'web'\n  state.state=1\n  state.reason=1\n  cpu.time=5000000000\n  balloon.current=2097152\n  balloon.maximum=4194304\n  balloon.rss=1048576\n  vcpu.current=2\n  vcpu.maximum=4\n  net.count=1\n  net.0.rx.bytes=1000\n  net.0.tx.bytes=2000\n  block.count=2\n  block.0.rd.bytes=300\n  block.0.wr.bytes=400\n  block.1.rd.bytes=700\n  block.1.wr.bytes=600\n\nDomain: 'backup'\n  state.state=5\n  state.reason=2\n  balloon.maximum=1048576\n\n";
+        let vms = parse_domstats(output);
+        assert_eq!(vms.len(), 2);
+
+        let (web, cpu_time) = &vms[0];
+        assert_eq!(web.name, "web");
+        assert_eq!(web.state, VmState::Running);
+        assert_eq!(web.vcpus, 2);
+        assert_eq!(web.memory_used, 1024 * 1024 * 1024);
+        assert_eq!(web.memory_max, 4 * 1024 * 1024 * 1024);
+        assert_eq!(web.disk_read_bytes, 1000);
+        assert_eq!(web.disk_written_bytes, 1000);
+        assert_eq!(web.net_rx_bytes, 1000);
+        assert_eq!(web.net_tx_bytes, 2000);
+        assert_eq!(*cpu_time, Some(5_000_000_000));
+
+        let (backup, cpu_time) = &vms[1];
+        assert_eq!(backup.state, VmState::ShutOff);
+        assert!(!backup.state.is_active());
+        assert_eq!(*cpu_time, None);
+    }
+}
@@ -0,0 +1,82 @@
+//! Virtual machine types
+
+use std::{collections::HashMap, time::Instant};
+
+use chrono::{DateTime, Utc};
+
+/// State of a libvirt domain, from the `state.state` statistic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmState {
+    /// Running on a CPU or ready to
+    Running,
+    /// Blocked on a resource
+    Blocked,
+    /// Paused by the user
+    Paused,
+    /// Being shut down
+    ShuttingDown,
+    /// Not running
+    ShutOff,
+    /// Stopped after a crash
+    Crashed,
+    /// Suspended by the guest's power management
+    Suspended,
+    /// A state libvirt didn't report or stomata doesn't know
+    Unknown,
+}
+
+/// A virtual machine defined in libvirt with its resource usage
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vm {
+    /// Domain name
+    pub name: String,
+    /// Current state
+    pub state: VmState,
+    /// Virtual CPUs
+    pub vcpus: u32,
+    /// CPU usage since the previous fetch in percent of one core, `None` on
+    /// the first fetch and for stopped VMs
+    pub cpu_usage: Option<f32>,
+    /// Memory used by the VM in bytes, the QEMU resident set when libvirt
+    /// reports it
+    pub memory_used: u64,
+    /// Maximum memory of the VM in bytes
+    pub memory_max: u64,
+    /// Total bytes read from all disks since the VM started
+    pub disk_read_bytes: u64,
+    /// Total bytes written to all disks since the VM started
+    pub disk_written_bytes: u64,
+    /// Total bytes received on all interfaces since the VM started
+    pub net_rx_bytes: u64,
+    /// Total bytes transmitted on all interfaces since the VM started
+    pub net_tx_bytes: u64,
+}
+
+/// Virtual machines at one point in time
+#[derive(Debug, Clone)]
+pub struct VmMetrics {
+    /// When the VMs were listed
+    pub timestamp: DateTime<Utc>,
+    /// Defined VMs, running or not
+    pub vms: Vec<Vm>,
+    /// Why libvirt couldn't be queried, e.g. `virsh` is not installed
+    pub error: Option<String>,
+}
+
+/// Power action applied to a VM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmAction {
+    /// Boot a stopped VM
+    Start,
+    /// Graceful shutdown through the guest's ACPI handler
+    Shutdown,
+}
+
+/// Queries libvirt through `virsh` and keeps the previous CPU time of
+/// every VM to turn it into a usage percentage
+#[derive(Debug, Default)]
+pub struct VmCollector {
+    /// libvirt connection URI, `None` uses virsh's default
+    pub uri: Option<String>,
+    pub(crate) previous_cpu: HashMap<String, (Instant, u64)>,
+}
@@ -0,0 +1,6 @@
+//! libvirt virtual machines, read and controlled with `virsh`
+
+pub mod collectors;
+pub mod metrics;
+
+pub use metrics::{Vm, VmAction, VmCollector, VmMetrics, VmState};
@@ -1 +1,344 @@
-pub const MAX_HISTORY: usize = 1000;
+//! Limits, timeouts and system paths shared by the collectors
+
+/// Directory under the stomata config dir holding plugin executables
+pub const PLUGINS_DIR_NAME: &str = "plugins";
+/// Time a plugin gets to print its output before it is killed
+pub const PLUGIN_TIMEOUT_MS: u64 = 500;
+/// Time between two runs of the plugins while their output is being read
+pub const PLUGINS_RUN_INTERVAL_MS: u64 = 2000;
+/// Largest stdout or stderr read from an external command, far above what
+/// the tools print so a runaway one can't exhaust memory
+pub const COMMAND_MAX_OUTPUT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Linux capability names indexed by capability bit, see capabilities(7)
+pub const CAPABILITY_NAMES: [&str; 41] = [
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+/// Processes expected to open network connections, see `ConnectionAllowlist`
+pub const COMMON_NETWORK_PROCESSES: [&str; 30] = [
+    "firefox",
+    "chrome",
+    "chromium",
+    "brave",
+    "ssh",
+    "sshd",
+    "curl",
+    "wget",
+    "git",
+    "git-remote-http",
+    "apt",
+    "http",
+    "https",
+    "dnf",
+    "pacman",
+    "packagekitd",
+    "systemd-resolve",
+    "systemd-timesyn",
+    "NetworkManager",
+    "chronyd",
+    "ntpd",
+    "dhclient",
+    "avahi-daemon",
+    "dockerd",
+    "containerd",
+    "tailscaled",
+    "cargo",
+    "rustup",
+    "code",
+    "thunderbird",
+];
+
+/// Remote ports that are not considered uncommon, see `ConnectionAllowlist`
+pub const COMMON_REMOTE_PORTS: [u16; 13] = [
+    22, 53, 80, 123, 443, 465, 587, 853, 993, 995, 5353, 8080, 8443,
+];
+/// Number of IP lookups kept by the GeoIP cache
+pub const GEOIP_CACHE_CAPACITY: usize = 4096;
+/// Kubelet state directory, present on Kubernetes nodes
+pub const KUBELET_DIR: &str = "/var/lib/kubelet";
+/// Process names of the kubelet in upstream Kubernetes, k3s and k0s
+pub const KUBELET_PROCESS_NAMES: [&str; 3] = ["kubelet", "k3s", "k0s"];
+/// Container logs written by the kubelet
+pub const POD_LOGS_DIR: &str = "/var/log/pods";
+/// Timeout of a `virsh` call
+pub const VIRSH_TIMEOUT_MS: u64 = 2000;
+/// Timeout of a `wg` or `tailscale` call
+pub const TUNNEL_COMMAND_TIMEOUT_MS: u64 = 1000;
+/// ssh options that take an argument, see ssh(1)
+pub const SSH_OPTIONS_WITH_ARGUMENT: &str = "BbcDEeFIiJLlmOoPpQRSWw";
+/// Timeout of a `pactl` call
+pub const AUDIO_COMMAND_TIMEOUT_MS: u64 = 1000;
+/// Time between two listings of the audio devices on the background
+/// thread, each takes four `pactl` calls
+pub const AUDIO_REFRESH_MS: u64 = 2000;
+/// Upper bound when raising the volume, above 100 the signal is amplified
+pub const MAX_VOLUME_PERCENT: u32 = 150;
+/// Backlight devices exposed by the kernel
+pub const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+/// Timeout of a `powerprofilesctl` call
+pub const POWER_PROFILES_TIMEOUT_MS: u64 = 1000;
+/// Timeout of a `bluetoothctl` call
+pub const BLUETOOTH_TIMEOUT_MS: u64 = 1000;
+/// Connecting waits for the device to answer, which takes a few seconds
+pub const BLUETOOTH_CONNECT_TIMEOUT_MS: u64 = 8000;
+/// Time between two listings of the Bluetooth devices on the background
+/// thread, each takes one `bluetoothctl` call per device
+pub const BLUETOOTH_REFRESH_MS: u64 = 3000;
+/// Highest priority nice value
+pub const MIN_NICE: i32 = -20;
+/// Lowest priority nice value
+pub const MAX_NICE: i32 = 19;
+/// USB devices exposed by the kernel
+pub const USB_DEVICES_DIR: &str = "/sys/bus/usb/devices";
+/// PCI devices exposed by the kernel
+pub const PCI_DEVICES_DIR: &str = "/sys/bus/pci/devices";
+/// Where distributions install the pci.ids database
+pub const PCI_IDS_PATHS: [&str; 3] = [
+    "/usr/share/hwdata/pci.ids",
+    "/usr/share/misc/pci.ids",
+    "/usr/share/pci.ids",
+];
+/// PCI class names by class and optional subclass code, the subclass-less
+/// entry is the fallback for a class
+pub const PCI_CLASS_NAMES: [(u8, Option<u8>, &str); 28] = [
+    (0x00, None, "Unclassified device"),
+    (0x01, Some(0x01), "IDE interface"),
+    (0x01, Some(0x06), "SATA controller"),
+    (0x01, Some(0x07), "SAS controller"),
+    (0x01, Some(0x08), "Non-Volatile memory controller"),
+    (0x01, None, "Mass storage controller"),
+    (0x02, Some(0x00), "Ethernet controller"),
+    (0x02, Some(0x80), "Network controller"),
+    (0x02, None, "Network controller"),
+    (0x03, Some(0x00), "VGA compatible controller"),
+    (0x03, Some(0x02), "3D controller"),
+    (0x03, None, "Display controller"),
+    (0x04, Some(0x03), "Audio device"),
+    (0x04, None, "Multimedia controller"),
+    (0x05, None, "Memory controller"),
+    (0x06, Some(0x00), "Host bridge"),
+    (0x06, Some(0x01), "ISA bridge"),
+    (0x06, Some(0x04), "PCI bridge"),
+    (0x06, None, "Bridge"),
+    (0x07, None, "Communication controller"),
+    (0x08, None, "Generic system peripheral"),
+    (0x09, None, "Input device controller"),
+    (0x0c, Some(0x03), "USB controller"),
+    (0x0c, Some(0x05), "SMBus"),
+    (0x0c, None, "Serial bus controller"),
+    (0x0d, None, "Wireless controller"),
+    (0x10, None, "Encryption controller"),
+    (0x12, None, "Processing accelerators"),
+];
+/// PCI base class of display controllers, i.e. GPUs
+pub const PCI_CLASS_DISPLAY: u8 = 0x03;
+/// Timeout of a `dmidecode` call
+pub const DMIDECODE_TIMEOUT_MS: u64 = 2000;
+/// Timeout of a `systemd-analyze` call
+pub const SYSTEMD_ANALYZE_TIMEOUT_MS: u64 = 3000;
+/// Number of units listed in the boot analysis
+pub const BOOT_SLOWEST_UNITS: usize = 10;
+/// Timeout of a `journalctl` call
+pub const JOURNALCTL_TIMEOUT_MS: u64 = 5000;
+/// Log messages of OOM kills, segfaults and failed systemd services
+pub const CRASH_LOG_PATTERN: &str = "Killed process|segfault at|Failed with result";
+/// Per-disk I/O counters
+pub const DISKSTATS_PATH: &str = "/proc/diskstats";
+/// Whole disks are listed here, partitions are not
+pub const SYS_BLOCK_DIR: &str = "/sys/block";
+/// `/proc/diskstats` counts 512 byte sectors whatever the device sector size
+pub const SECTOR_SIZE: f64 = 512.0;
+/// How long a newly mounted filesystem stays highlighted
+pub const NEW_MOUNT_HIGHLIGHT_SECS: u64 = 60;
+/// Timeout of an unmount, which flushes pending writes first
+pub const UNMOUNT_TIMEOUT_MS: u64 = 10_000;
+/// Network interfaces exposed by the kernel
+pub const SYS_CLASS_NET_DIR: &str = "/sys/class/net";
+/// Timeout of an `iw` call
+pub const IW_TIMEOUT_MS: u64 = 1000;
+/// Timeout of a `smartctl` call
+pub const SMARTCTL_TIMEOUT_MS: u64 = 3000;
+/// How often the S.M.A.R.T. attributes are read again
+pub const SMART_REFRESH_SECS: u64 = 600;
+/// Disk temperature from which the health badge warns
+pub const SMART_TEMPERATURE_WARNING_C: i64 = 60;
+/// Reallocated sectors above which the health badge warns
+pub const SMART_REALLOCATED_WARNING: u64 = 0;
+/// Hardware monitoring chips exposed by the kernel
+pub const HWMON_DIR: &str = "/sys/class/hwmon";
+/// How long systemd-inhibit gets to fail before its lock is assumed held
+pub const SLEEP_INHIBITOR_STARTUP_MS: u64 = 200;
+/// How long without input and with flat metrics before the machine is idle
+pub const IDLE_AFTER_SECS: u64 = 300;
+/// CPU usage change in percentage points that counts as activity
+pub const IDLE_FLAT_CPU_DELTA: f32 = 5.0;
+/// Time between history writes while idle
+pub const IDLE_WRITE_INTERVAL_SECS: u64 = 60;
+/// Mount point of the cgroup v2 hierarchy
+pub const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+/// Well-known name of systemd on the system bus
+pub const SYSTEMD_BUS_NAME: &str = "org.freedesktop.systemd1";
+/// Object path of the systemd manager
+pub const SYSTEMD_MANAGER_PATH: &str = "/org/freedesktop/systemd1";
+/// Interface of the systemd manager listing units and queueing jobs
+pub const SYSTEMD_MANAGER_INTERFACE: &str = "org.freedesktop.systemd1.Manager";
+/// Timeout of a D-Bus call to systemd
+pub const SYSTEMD_TIMEOUT_MS: u64 = 2000;
+/// Starting or stopping a unit waits for the job to finish
+pub const SYSTEMD_JOB_TIMEOUT_MS: u64 = 30_000;
+/// Time between two checks whether a job finished
+pub const SYSTEMD_JOB_POLL_MS: u64 = 100;
+/// Minimum time between two listings of the services, see
+/// `ServiceCollector::set_refresh_interval`
+pub const SERVICES_REFRESH_MS: u64 = 1000;
+/// Alerts log in the stomata data directory
+pub const ALERT_LOG_FILE_NAME: &str = "alerts.jsonl";
+/// Entries read from a log on the first refresh of the Logs page
+pub const LOG_BACKLOG_LINES: usize = 500;
+/// Entries the log collector keeps, the oldest are dropped first
+pub const LOG_MAX_ENTRIES: usize = 2000;
+/// Timeout of a `dmesg` call
+pub const DMESG_TIMEOUT_MS: u64 = 2000;
+/// Pressure stall information
+pub const PRESSURE_DIR: &str = "/proc/pressure";
+/// Kernel and system statistics since boot
+pub const PROC_STAT_PATH: &str = "/proc/stat";
+/// How often the summary strip refreshes the process list for the top
+/// process when the visible page doesn't refresh it anyway
+pub const SUMMARY_PROCESSES_REFRESH_SECS: u64 = 5;
+/// Interval hint of the registered CPU collector
+pub const CPU_COLLECT_INTERVAL_MS: u64 = 1000;
+/// Interval hint of the registered memory collector
+pub const MEMORY_COLLECT_INTERVAL_MS: u64 = 1000;
+/// Interval hint of the registered network collector
+pub const NETWORK_COLLECT_INTERVAL_MS: u64 = 1000;
+/// Interval hint of the registered disk collector
+pub const DISK_COLLECT_INTERVAL_MS: u64 = 2000;
+/// Interval hint of the registered sensors collector
+pub const SENSORS_COLLECT_INTERVAL_MS: u64 = 5000;
+/// Plugins run a process per plugin, so they are collected sparingly
+pub const PLUGINS_COLLECT_INTERVAL_MS: u64 = 10_000;
+/// Largest StatsD packet, small enough not to be fragmented on common links
+pub const STATSD_MAX_PACKET_BYTES: usize = 1432;
+/// Version of the agent protocol, bumped on incompatible changes
+pub const REMOTE_PROTOCOL_VERSION: u32 = 2;
+/// Largest frame of the agent protocol, far above a process list so a
+/// corrupt length can't make the reader allocate gigabytes
+pub const REMOTE_MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
+/// Smallest frame body sent zstd-compressed to clients asking for it,
+/// short answers aren't worth compressing
+pub const REMOTE_COMPRESS_MIN_BYTES: usize = 1024;
+/// How long the remote client waits to connect to, write to or hear from
+/// an agent before giving up on the request
+pub const REMOTE_IO_TIMEOUT_MS: u64 = 5000;
+/// Time between two attempts of the remote client to reach a down agent
+pub const REMOTE_RECONNECT_SECS: u64 = 5;
+/// Time between two fetches of a request polled from an agent
+pub const REMOTE_POLL_INTERVAL_MS: u64 = 1000;
+/// How long the agent waits for the next frame of a client before
+/// dropping it, well above the refresh interval of the TUI
+pub const REMOTE_IDLE_TIMEOUT_SECS: u64 = 60;
+/// Clients the agent serves at once, later ones are refused until one
+/// disconnects
+pub const REMOTE_MAX_CLIENTS: usize = 16;
+/// How long a server reading a refused connection's request waits for it,
+/// so closing the socket doesn't reset it before the refusal is read
+pub const REFUSE_DRAIN_MS: u64 = 200;
+/// Samples of each usage metric the API server keeps, an hour at
+/// [`API_HISTORY_INTERVAL_MS`]
+pub const API_HISTORY_SAMPLES: usize = 3600;
+/// Time between two samples of the API server's usage history
+pub const API_HISTORY_INTERVAL_MS: u64 = 1000;
+/// Largest request head the API server reads before refusing it
+pub const API_MAX_REQUEST_BYTES: usize = 16 * 1024;
+/// How long the API server waits to read a request or write its answer,
+/// so a client trickling bytes can't hold a connection open
+pub const API_IO_TIMEOUT_MS: u64 = 5000;
+/// Connections the API server serves at once, later ones are answered
+/// with 503 until one finishes
+pub const API_MAX_CONNECTIONS: usize = 32;
+/// Smallest API response body sent compressed to clients accepting it,
+/// below it the gzip or zstd framing outweighs the savings
+pub const API_COMPRESS_MIN_BYTES: usize = 1024;
+/// Keep alive announced to MQTT brokers; metrics are published far more
+/// often, so no pings are needed
+pub const MQTT_KEEP_ALIVE_SECS: u16 = 60;
+/// How long the MQTT sink waits to connect to or write to the broker
+pub const MQTT_IO_TIMEOUT_MS: u64 = 5000;
+/// Prefix of the files written by a snapshot directory
+pub const SNAPSHOT_FILE_PREFIX: &str = "snapshot-";
+/// File name of the metrics history database in the stomata data directory
+pub const METRIC_STORE_FILE_NAME: &str = "metrics.db";
+/// Age after which raw samples of the metric store are rolled into minute
+/// averages
+pub const STORE_RAW_RETENTION_HOURS: i64 = 6;
+/// Age after which minute averages are rolled into hour averages
+pub const STORE_MINUTE_RETENTION_DAYS: i64 = 7;
+/// Age after which hour averages are deleted
+pub const STORE_HOUR_RETENTION_DAYS: i64 = 365;
+/// How often the history recorder rolls up and prunes the store
+pub const STORE_COMPACT_INTERVAL_SECS: u64 = 600;
+/// Standard deviations from the rolling mean beyond which a sample is an
+/// anomaly
+pub const ANOMALY_DEFAULT_SIGMAS: f64 = 3.0;
+/// Samples of the rolling baseline a new sample is compared to
+pub const ANOMALY_DEFAULT_WINDOW: usize = 60;
+/// Samples a baseline needs before anything is flagged against it
+pub const ANOMALY_MIN_SAMPLES: usize = 10;
+/// Anomalies the detector remembers, newest first
+pub const MAX_RECENT_ANOMALIES: usize = 50;
+/// Process births and exits the lifetime tracker remembers, newest first
+pub const MAX_RECENT_PROCESS_EVENTS: usize = 100;
+/// Characters of a container id kept in container labels, like `docker ps`
+pub const CONTAINER_ID_SHORT_LEN: usize = 12;
+/// Inode usage in percent above which a filesystem raises an alert
+pub const INODE_ALERT_DEFAULT_PERCENT: f64 = 90.0;
+/// Directory with the `cpu<n>/cpufreq` frequency scaling state of every CPU
+pub const CPUFREQ_DIR: &str = "/sys/devices/system/cpu";
+/// CPU usage in percent from which a low frequency counts as throttling
+pub const THROTTLE_LOAD_PERCENT: f32 = 80.0;
+/// Share of the maximum frequency below which busy CPUs count as throttled
+pub const THROTTLE_FREQUENCY_RATIO: f64 = 0.5;
+/// Seconds of throttling before the throttling alert fires
+pub const THROTTLE_ALERT_DEFAULT_SECS: u64 = 30;
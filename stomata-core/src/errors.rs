@@ -0,0 +1,121 @@
+//! The error type returned by stomata-core's fallible APIs
+
+use std::io;
+
+/// Result of a fallible stomata-core call
+pub type CoreResult<T> = Result<T, CoreError>;
+
+/// Why a collector or action failed. The variants group failures by what a
+/// caller can do about them, so the TUI can suggest root for
+/// [`CoreError::PermissionDenied`] or hide a page for
+/// [`CoreError::UnsupportedPlatform`] without matching on message text.
+#[derive(Debug, thiserror::Error)]
+pub enum CoreError {
+    /// The data source answered but not usefully: a command exited
+    /// unsuccessfully, timed out or printed output that didn't parse
+    #[error("{0}")]
+    CollectionFailed(String),
+    /// The platform lacks the feature, or the tool providing it isn't
+    /// installed
+    #[error("{0}")]
+    UnsupportedPlatform(String),
+    /// The caller lacks the privileges for the operation
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+    /// An argument was rejected before anything was attempted
+    #[error("{0}")]
+    InvalidInput(String),
+    /// Reading a file or running a command failed
+    #[error("{context}: {source}")]
+    Io {
+        /// What was being done, e.g. `read /proc/diskstats`
+        context: String,
+        /// The underlying error
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl CoreError {
+    /// A [`CoreError::CollectionFailed`] with `message`
+    pub fn collection(message: impl Into<String>) -> Self {
+        CoreError::CollectionFailed(message.into())
+    }
+
+    /// A [`CoreError::UnsupportedPlatform`] with `message`
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        CoreError::UnsupportedPlatform(message.into())
+    }
+
+    /// A [`CoreError::InvalidInput`] with `message`
+    pub fn invalid(message: impl Into<String>) -> Self {
+        CoreError::InvalidInput(message.into())
+    }
+
+    /// Wraps `source` with what was being done. Permission errors become
+    /// [`CoreError::PermissionDenied`] so callers only have one variant to
+    /// check.
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        let context = context.into();
+        if source.kind() == io::ErrorKind::PermissionDenied {
+            return CoreError::PermissionDenied(context);
+        }
+        CoreError::Io { context, source }
+    }
+
+    /// Prefixes the message with `context`, keeping the category
+    pub fn context(self, context: impl std::fmt::Display) -> Self {
+        match self {
+            CoreError::CollectionFailed(message) => {
+                CoreError::CollectionFailed(format!("{context}: {message}"))
+            }
+            CoreError::UnsupportedPlatform(message) => {
+                CoreError::UnsupportedPlatform(format!("{context}: {message}"))
+            }
+            CoreError::PermissionDenied(message) => {
+                CoreError::PermissionDenied(format!("{context}: {message}"))
+            }
+            CoreError::InvalidInput(message) => {
+                CoreError::InvalidInput(format!("{context}: {message}"))
+            }
+            CoreError::Io {
+                context: inner,
+                source,
+            } => CoreError::Io {
+                context: format!("{context}: {inner}"),
+                source,
+            },
+        }
+    }
+
+    /// Whether the error comes from a command or file that doesn't exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, CoreError::Io { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_sorts_permission_errors() {
+        let denied = CoreError::io(
+            "failed to write /sys/class/backlight",
+            io::Error::from(io::ErrorKind::PermissionDenied),
+        );
+        assert!(matches!(denied, CoreError::PermissionDenied(_)));
+        assert_eq!(
+            denied.to_string(),
+            "permission denied: failed to write /sys/class/backlight"
+        );
+
+        let missing = CoreError::io(
+            "failed to start smartctl",
+            io::Error::from(io::ErrorKind::NotFound),
+        );
+        assert!(missing.is_not_found());
+        assert!(!CoreError::collection("exited with 1").is_not_found());
+        assert!(missing.context("smart").is_not_found());
+    }
+}
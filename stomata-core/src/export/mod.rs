@@ -0,0 +1,27 @@
+//! Forwarding collected metrics to external systems
+//!
+//! A [`MetricSink`] receives every [`Metric`] read from a
+//! [`CollectorRegistry`](crate::CollectorRegistry) and delivers it in the
+//! format of its target, so the same collection loop can feed any of them.
+
+pub mod mqtt;
+pub mod statsd;
+
+pub use mqtt::{MqttCredentials, MqttSink};
+pub use statsd::StatsdSink;
+
+use crate::{collectors::Metric, errors::CoreResult};
+
+/// A destination for collected metrics
+pub trait MetricSink {
+    /// Short name of the target, e.g. `statsd`
+    fn name(&self) -> &'static str;
+
+    /// Delivers the values of `metric`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the values couldn't be delivered. The sink stays
+    /// usable, so the next metric can be sent.
+    fn send(&mut self, metric: &Metric) -> CoreResult<()>;
+}
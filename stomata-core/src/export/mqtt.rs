@@ -0,0 +1,423 @@
+//! MQTT publishing with optional Home Assistant discovery
+//!
+//! Every value is published to `<prefix>/<collector>[/<instance>]/<name>`,
+//! e.g. `stomata/nas/network/eth0/rx_bytes_per_sec`, as a plain number.
+//! With Home Assistant discovery enabled, the first value of each metric is
+//! preceded by a retained sensor config under the discovery prefix, so the
+//! sensors show up grouped under one device per host.
+//!
+//! Only the small part of MQTT 3.1.1 a publisher needs is implemented:
+//! CONNECT, QoS 0 PUBLISH and DISCONNECT, over plain TCP.
+
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use serde_json::json;
+
+use crate::{
+    collectors::{Metric, MetricValue},
+    constants::{MQTT_IO_TIMEOUT_MS, MQTT_KEEP_ALIVE_SECS},
+    errors::{CoreError, CoreResult},
+    export::MetricSink,
+};
+
+/// Broker login of an [`MqttSink`]
+#[derive(Clone, PartialEq, Eq)]
+pub struct MqttCredentials {
+    /// User name
+    pub username: String,
+    /// Password, `None` to log in with the user name only
+    pub password: Option<String>,
+}
+
+impl std::fmt::Debug for MqttCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttCredentials")
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "[REDACTED]"))
+            .finish()
+    }
+}
+
+/// Publishes metrics to an MQTT broker, reconnecting after a failed
+/// publish
+#[derive(Debug)]
+pub struct MqttSink {
+    address: String,
+    client_id: String,
+    credentials: Option<MqttCredentials>,
+    prefix: String,
+    /// Discovery prefix and device name when Home Assistant discovery is
+    /// enabled
+    discovery: Option<(String, String)>,
+    /// Topics whose discovery config was published on this connection
+    announced: HashSet<String>,
+    stream: Option<TcpStream>,
+}
+
+impl MqttSink {
+    /// Connects to the broker at `address`, e.g. `127.0.0.1:1883`,
+    /// publishing under the topic `prefix`, e.g. `stomata/nas`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the broker can't be reached or refuses the
+    /// connection.
+    pub fn connect(
+        address: impl Into<String>,
+        client_id: impl Into<String>,
+        prefix: impl Into<String>,
+        credentials: Option<MqttCredentials>,
+    ) -> CoreResult<Self> {
+        let mut sink = Self {
+            address: address.into(),
+            client_id: client_id.into(),
+            credentials,
+            prefix: prefix.into().trim_end_matches('/').to_string(),
+            discovery: None,
+            announced: HashSet::new(),
+            stream: None,
+        };
+        sink.reconnect()?;
+        Ok(sink)
+    }
+
+    /// Publishes Home Assistant discovery configs under `discovery_prefix`,
+    /// usually `homeassistant`, grouping the sensors under a device named
+    /// `device`
+    pub fn with_home_assistant(
+        mut self,
+        discovery_prefix: impl Into<String>,
+        device: impl Into<String>,
+    ) -> Self {
+        self.discovery = Some((discovery_prefix.into(), device.into()));
+        self
+    }
+
+    /// State topic of one value
+    pub fn topic(&self, collector: &str, value: &MetricValue) -> String {
+        let mut segments = vec![self.prefix.clone(), topic_segment(collector)];
+        if let Some(instance) = &value.instance {
+            segments.push(topic_segment(instance));
+        }
+        segments.push(topic_segment(&value.name));
+        segments.join("/")
+    }
+
+    /// Topic and retained payload of the Home Assistant sensor of one
+    /// value, `None` without discovery
+    pub fn discovery_config(
+        &self,
+        collector: &str,
+        value: &MetricValue,
+    ) -> Option<(String, String)> {
+        let (discovery_prefix, device) = self.discovery.as_ref()?;
+        let node_id = object_id(device);
+        let key = match &value.instance {
+            Some(instance) => format!("{collector}_{instance}_{}", value.name),
+            None => format!("{collector}_{}", value.name),
+        };
+        let object = object_id(&key);
+        let name = match &value.instance {
+            Some(instance) => format!("{collector} {instance} {}", value.name),
+            None => format!("{collector} {}", value.name),
+        };
+        let mut config = json!({
+            "name": name.replace('_', " "),
+            "unique_id": format!("stomata_{node_id}_{object}"),
+            "state_topic": self.topic(collector, value),
+            "state_class": "measurement",
+            "device": {
+                "identifiers": [format!("stomata_{node_id}")],
+                "name": device,
+                "manufacturer": "stomata",
+            },
+        });
+        if let Some((unit, device_class)) = value.unit.as_deref().map(home_assistant_unit) {
+            config["unit_of_measurement"] = json!(unit);
+            if let Some(device_class) = device_class {
+                config["device_class"] = json!(device_class);
+            }
+        }
+        Some((
+            format!("{discovery_prefix}/sensor/{node_id}/{object}/config"),
+            config.to_string(),
+        ))
+    }
+
+    fn publish(&mut self, topic: &str, payload: &[u8], retain: bool) -> CoreResult<()> {
+        let packet = publish_packet(topic, payload, retain)?;
+        let stream = match self.stream.as_mut() {
+            Some(stream) => stream,
+            None => self.reconnect()?,
+        };
+        if let Err(err) = stream.write_all(&packet) {
+            self.stream = None;
+            return Err(CoreError::io(
+                format!("failed to publish to {}", self.address),
+                err,
+            ));
+        }
+        Ok(())
+    }
+
+    fn reconnect(&mut self) -> CoreResult<&mut TcpStream> {
+        let timeout = Duration::from_millis(MQTT_IO_TIMEOUT_MS);
+        let mut stream = TcpStream::connect(&self.address)
+            .map_err(|err| CoreError::io(format!("failed to connect to {}", self.address), err))?;
+        stream
+            .set_read_timeout(Some(timeout))
+            .and_then(|()| stream.set_write_timeout(Some(timeout)))
+            .map_err(|err| CoreError::io("failed to set the socket timeouts", err))?;
+        let packet = connect_packet(&self.client_id, self.credentials.as_ref())?;
+        stream
+            .write_all(&packet)
+            .map_err(|err| CoreError::io("failed to send CONNECT", err))?;
+        let mut connack = [0; 4];
+        stream
+            .read_exact(&mut connack)
+            .map_err(|err| CoreError::io("failed to read CONNACK", err))?;
+        if connack[0] != 0x20 {
+            return Err(CoreError::collection(format!(
+                "{} answered CONNECT with packet type {:#04x}",
+                self.address, connack[0]
+            )));
+        }
+        match connack[3] {
+            0 => {}
+            4 | 5 => {
+                return Err(CoreError::PermissionDenied(format!(
+                    "{} refused the credentials",
+                    self.address
+                )));
+            }
+            code => {
+                return Err(CoreError::collection(format!(
+                    "{} refused the connection with code {code}",
+                    self.address
+                )));
+            }
+        }
+        // retained configs survive, but a restarted broker may have lost them
+        self.announced.clear();
+        Ok(self.stream.insert(stream))
+    }
+}
+
+impl MetricSink for MqttSink {
+    fn name(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn send(&mut self, metric: &Metric) -> CoreResult<()> {
+        for value in metric.values.iter().filter(|value| value.value.is_finite()) {
+            let topic = self.topic(metric.collector, value);
+            if !self.announced.contains(&topic)
+                && let Some((config_topic, config)) = self.discovery_config(metric.collector, value)
+            {
+                self.publish(&config_topic, config.as_bytes(), true)?;
+                self.announced.insert(topic.clone());
+            }
+            self.publish(&topic, value.value.to_string().as_bytes(), false)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MqttSink {
+    fn drop(&mut self) {
+        if let Some(stream) = self.stream.as_mut() {
+            let _ = stream.write_all(&[0xe0, 0x00]);
+        }
+    }
+}
+
+/// Topic level with the wildcard and separator characters replaced
+fn topic_segment(value: &str) -> String {
+    value.replace(['/', '+', '#', '\0'], "_")
+}
+
+/// Home Assistant object id: lowercase ASCII letters, digits and `_`
+fn object_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Home Assistant unit and device class of a registry unit
+fn home_assistant_unit(unit: &str) -> (&str, Option<&'static str>) {
+    match unit {
+        "percent" => ("%", None),
+        "celsius" => ("°C", Some("temperature")),
+        "bytes" => ("B", Some("data_size")),
+        "bytes/s" => ("B/s", Some("data_rate")),
+        other => (other, None),
+    }
+}
+
+/// Appends the MQTT variable length encoding of `length`
+fn encode_remaining_length(mut length: usize, packet: &mut Vec<u8>) -> CoreResult<()> {
+    if length > 268_435_455 {
+        return Err(CoreError::invalid("MQTT packet too large"));
+    }
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if length == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Appends a length-prefixed UTF-8 string
+fn encode_string(value: &[u8], buffer: &mut Vec<u8>) -> CoreResult<()> {
+    let length = u16::try_from(value.len())
+        .map_err(|_| CoreError::invalid("MQTT string longer than 65535 bytes"))?;
+    buffer.extend_from_slice(&length.to_be_bytes());
+    buffer.extend_from_slice(value);
+    Ok(())
+}
+
+fn connect_packet(client_id: &str, credentials: Option<&MqttCredentials>) -> CoreResult<Vec<u8>> {
+    let mut flags = 0x02; // clean session
+    let mut body = Vec::new();
+    encode_string(b"MQTT", &mut body)?;
+    body.push(4); // protocol level 3.1.1
+    let flags_index = body.len();
+    body.push(0);
+    body.extend_from_slice(&MQTT_KEEP_ALIVE_SECS.to_be_bytes());
+    encode_string(client_id.as_bytes(), &mut body)?;
+    if let Some(credentials) = credentials {
+        flags |= 0x80;
+        encode_string(credentials.username.as_bytes(), &mut body)?;
+        if let Some(password) = &credentials.password {
+            flags |= 0x40;
+            encode_string(password.as_bytes(), &mut body)?;
+        }
+    }
+    body[flags_index] = flags;
+
+    let mut packet = vec![0x10];
+    encode_remaining_length(body.len(), &mut packet)?;
+    packet.extend_from_slice(&body);
+    Ok(packet)
+}
+
+fn publish_packet(topic: &str, payload: &[u8], retain: bool) -> CoreResult<Vec<u8>> {
+    let mut body = Vec::new();
+    encode_string(topic.as_bytes(), &mut body)?;
+    body.extend_from_slice(payload);
+    let mut packet = vec![if retain { 0x31 } else { 0x30 }];
+    encode_remaining_length(body.len(), &mut packet)?;
+    packet.extend_from_slice(&body);
+    Ok(packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, thread};
+
+    use super::*;
+
+    /// A broker accepting one client, returning everything it received
+    fn broker() -> (String, thread::JoinHandle<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = vec![0; 2];
+            stream.read_exact(&mut received).unwrap();
+            let mut rest = vec![0; received[1] as usize];
+            stream.read_exact(&mut rest).unwrap();
+            stream.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap();
+            received.extend(rest);
+            stream.read_to_end(&mut received).unwrap();
+            received
+        });
+        (address, handle)
+    }
+
+    #[test]
+    fn test_remaining_length_uses_the_variable_encoding() {
+        let encode = |length| {
+            let mut buffer = Vec::new();
+            encode_remaining_length(length, &mut buffer).unwrap();
+            buffer
+        };
+        assert_eq!(encode(0), vec![0x00]);
+        assert_eq!(encode(127), vec![0x7f]);
+        assert_eq!(encode(128), vec![0x80, 0x01]);
+        assert_eq!(encode(16_383), vec![0xff, 0x7f]);
+        assert_eq!(encode(2_097_152), vec![0x80, 0x80, 0x80, 0x01]);
+    }
+
+    #[test]
+    fn test_packets_match_the_specification() {
+        assert_eq!(
+            publish_packet("a/b", b"42", false).unwrap(),
+            vec![0x30, 0x07, 0x00, 0x03, b'a', b'/', b'b', b'4', b'2']
+        );
+        assert_eq!(publish_packet("t", b"", true).unwrap()[0], 0x31);
+
+        let credentials = MqttCredentials {
+            username: "u".to_string(),
+            password: Some("p".to_string()),
+        };
+        let connect = connect_packet("id", Some(&credentials)).unwrap();
+        assert_eq!(connect[0], 0x10);
+        assert_eq!(&connect[2..8], b"\x00\x04MQTT");
+        assert_eq!(connect[8], 4);
+        assert_eq!(connect[9], 0xc2);
+        assert!(connect.ends_with(b"\x00\x02id\x00\x01u\x00\x01p"));
+    }
+
+    #[test]
+    fn test_values_are_published_with_their_discovery_config() {
+        let (address, broker) = broker();
+        let mut sink = MqttSink::connect(address, "stomata-test", "stomata/nas/", None)
+            .unwrap()
+            .with_home_assistant("homeassistant", "nas");
+        let value = MetricValue::new("temperature", 41.5)
+            .instance("coretemp/Core 0")
+            .unit("celsius");
+        assert_eq!(
+            sink.topic("sensors", &value),
+            "stomata/nas/sensors/coretemp_Core 0/temperature"
+        );
+        let (config_topic, config) = sink.discovery_config("sensors", &value).unwrap();
+        assert_eq!(
+            config_topic,
+            "homeassistant/sensor/nas/sensors_coretemp_core_0_temperature/config"
+        );
+        let config: serde_json::Value = serde_json::from_str(&config).unwrap();
+        assert_eq!(config["unit_of_measurement"], "°C");
+        assert_eq!(config["device_class"], "temperature");
+        assert_eq!(config["device"]["name"], "nas");
+
+        let metric = Metric::new("sensors", vec![value]);
+        sink.send(&metric).unwrap();
+        sink.send(&metric).unwrap();
+        drop(sink);
+
+        let received = broker.join().unwrap();
+        let text = String::from_utf8_lossy(&received);
+        assert_eq!(text.matches("homeassistant/sensor/").count(), 1);
+        assert_eq!(text.matches("41.5").count(), 2);
+        assert!(received.ends_with(&[0xe0, 0x00]));
+    }
+}
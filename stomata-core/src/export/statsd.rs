@@ -0,0 +1,182 @@
+//! StatsD and DogStatsD output over UDP
+//!
+//! Every value is sent as a gauge named `<prefix>.<collector>.<key>`, e.g.
+//! `stomata.network.eth0.rx_bytes_per_sec:1024|g`. With DogStatsD tags the
+//! instance becomes an `instance` tag instead of a name segment, so
+//! Datadog can group the interfaces of one metric:
+//! `stomata.network.rx_bytes_per_sec:1024|g|#instance:eth0`.
+
+use std::net::{ToSocketAddrs, UdpSocket};
+
+use crate::{
+    collectors::{Metric, MetricValue},
+    constants::STATSD_MAX_PACKET_BYTES,
+    errors::{CoreError, CoreResult},
+    export::MetricSink,
+};
+
+/// Sends metrics to a StatsD server, batching lines into packets
+#[derive(Debug)]
+pub struct StatsdSink {
+    socket: UdpSocket,
+    prefix: String,
+    /// Tags appended to every line, `None` for plain StatsD
+    tags: Option<Vec<String>>,
+}
+
+impl StatsdSink {
+    /// A sink sending plain StatsD gauges to `address`, e.g.
+    /// `127.0.0.1:8125`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address can't be resolved or no local UDP
+    /// socket can be bound.
+    pub fn connect(address: impl ToSocketAddrs, prefix: impl Into<String>) -> CoreResult<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")
+            .or_else(|_| UdpSocket::bind("[::]:0"))
+            .map_err(|err| CoreError::io("failed to bind a UDP socket", err))?;
+        socket
+            .connect(address)
+            .map_err(|err| CoreError::io("failed to resolve the StatsD address", err))?;
+        Ok(Self {
+            socket,
+            prefix: prefix.into(),
+            tags: None,
+        })
+    }
+
+    /// Switches to DogStatsD lines carrying `tags`, e.g. `env:prod`, on
+    /// top of the instance tag
+    pub fn with_dogstatsd_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// The gauge line of one value
+    pub fn line(&self, collector: &str, value: &MetricValue) -> String {
+        let Some(tags) = &self.tags else {
+            let name = join_name(&[&self.prefix, collector, &value.key()]);
+            return format!("{name}:{}|g", value.value);
+        };
+        let name = join_name(&[&self.prefix, collector, &value.name]);
+        let mut line_tags: Vec<String> = tags.iter().map(|tag| sanitize_tag(tag)).collect();
+        if let Some(instance) = &value.instance {
+            line_tags.push(format!("instance:{}", sanitize_tag(instance)));
+        }
+        if line_tags.is_empty() {
+            format!("{name}:{}|g", value.value)
+        } else {
+            format!("{name}:{}|g|#{}", value.value, line_tags.join(","))
+        }
+    }
+}
+
+impl MetricSink for StatsdSink {
+    fn name(&self) -> &'static str {
+        "statsd"
+    }
+
+    fn send(&mut self, metric: &Metric) -> CoreResult<()> {
+        let lines: Vec<String> = metric
+            .values
+            .iter()
+            .filter(|value| value.value.is_finite())
+            .map(|value| self.line(metric.collector, value))
+            .collect();
+        for packet in packets(&lines, STATSD_MAX_PACKET_BYTES) {
+            self.socket
+                .send(packet.as_bytes())
+                .map_err(|err| CoreError::io("failed to send to StatsD", err))?;
+        }
+        Ok(())
+    }
+}
+
+/// Joins `lines` with newlines into packets of at most `max_bytes`. A line
+/// longer than that is sent on its own.
+pub fn packets(lines: &[String], max_bytes: usize) -> Vec<String> {
+    let mut packets = Vec::new();
+    let mut packet = String::new();
+    for line in lines {
+        if !packet.is_empty() && packet.len() + 1 + line.len() > max_bytes {
+            packets.push(std::mem::take(&mut packet));
+        }
+        if !packet.is_empty() {
+            packet.push('\n');
+        }
+        packet.push_str(line);
+    }
+    if !packet.is_empty() {
+        packets.push(packet);
+    }
+    packets
+}
+
+/// Dotted metric name with the characters StatsD reserves replaced
+fn join_name(segments: &[&str]) -> String {
+    segments
+        .iter()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.replace([':', '|', '@', '#', ' ', '\n'], "_"))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Tag with the separators of the tag list replaced
+fn sanitize_tag(tag: &str) -> String {
+    tag.replace([',', '|', '#', ' ', '\n'], "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sink() -> StatsdSink {
+        StatsdSink::connect("127.0.0.1:8125", "stomata").unwrap()
+    }
+
+    #[test]
+    fn test_plain_lines_keep_the_instance_in_the_name() {
+        let value = MetricValue::new("rx_bytes_per_sec", 1024.0).instance("eth0");
+        assert_eq!(
+            sink().line("network", &value),
+            "stomata.network.eth0.rx_bytes_per_sec:1024|g"
+        );
+        let value = MetricValue::new("usage", 12.5);
+        assert_eq!(sink().line("cpu", &value), "stomata.cpu.usage:12.5|g");
+    }
+
+    #[test]
+    fn test_dogstatsd_lines_tag_the_instance() {
+        let sink = sink().with_dogstatsd_tags(vec!["env:prod".to_string()]);
+        let value = MetricValue::new("rx_bytes_per_sec", 1024.0).instance("eth0");
+        assert_eq!(
+            sink.line("network", &value),
+            "stomata.network.rx_bytes_per_sec:1024|g|#env:prod,instance:eth0"
+        );
+        let sink = sink.with_dogstatsd_tags(Vec::new());
+        let value = MetricValue::new("used", 1.0);
+        assert_eq!(sink.line("memory", &value), "stomata.memory.used:1|g");
+    }
+
+    #[test]
+    fn test_reserved_characters_are_replaced() {
+        let value = MetricValue::new("temp", 40.0).instance("coretemp:Core 0");
+        assert_eq!(
+            sink().line("sensors", &value),
+            "stomata.sensors.coretemp_Core_0.temp:40|g"
+        );
+    }
+
+    #[test]
+    fn test_lines_are_batched_up_to_the_packet_size() {
+        let lines: Vec<String> = ["a:1|g", "b:2|g", "c:3|g"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(packets(&lines, 11), vec!["a:1|g\nb:2|g", "c:3|g"]);
+        assert_eq!(packets(&lines, 4), vec!["a:1|g", "b:2|g", "c:3|g"]);
+        assert!(packets(&[], 100).is_empty());
+    }
+}
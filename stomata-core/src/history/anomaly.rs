@@ -0,0 +1,268 @@
+//! Anomalies against a rolling baseline
+//!
+//! A sample is an anomaly when it lies more than N standard deviations
+//! from the mean of the samples before it, so a spike stands out on a
+//! quiet metric even while it stays under any absolute threshold. The
+//! baseline needs `ANOMALY_MIN_SAMPLES` samples before it flags anything,
+//! and a perfectly flat baseline flags nothing, since any change would be
+//! infinitely many deviations away.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+
+use crate::constants::{
+    ANOMALY_DEFAULT_SIGMAS, ANOMALY_DEFAULT_WINDOW, ANOMALY_MIN_SAMPLES, MAX_RECENT_ANOMALIES,
+};
+
+/// Mean and standard deviation of the latest `window` values
+#[derive(Debug, Clone)]
+pub struct RollingBaseline {
+    window: usize,
+    values: VecDeque<f64>,
+    sum: f64,
+    sum_of_squares: f64,
+}
+
+impl RollingBaseline {
+    /// A baseline over the latest `window` values, at least one
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            values: VecDeque::with_capacity(window),
+            sum: 0.0,
+            sum_of_squares: 0.0,
+        }
+    }
+
+    /// Adds `value`, dropping the oldest value once the window is full
+    pub fn push(&mut self, value: f64) {
+        if self.values.len() == self.window
+            && let Some(oldest) = self.values.pop_front()
+        {
+            self.sum -= oldest;
+            self.sum_of_squares -= oldest * oldest;
+        }
+        self.values.push_back(value);
+        self.sum += value;
+        self.sum_of_squares += value * value;
+    }
+
+    /// Number of values in the window
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no value was pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Mean of the values, `None` while empty
+    pub fn mean(&self) -> Option<f64> {
+        (!self.values.is_empty()).then(|| self.sum / self.values.len() as f64)
+    }
+
+    /// Population standard deviation of the values, `None` while empty
+    pub fn stddev(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let variance = self.sum_of_squares / self.values.len() as f64 - mean * mean;
+        // rounding can push the variance of a flat window slightly below 0
+        Some(variance.max(0.0).sqrt())
+    }
+
+    /// Signed number of standard deviations `value` lies from the mean,
+    /// `None` until the baseline has `ANOMALY_MIN_SAMPLES` values or while
+    /// it is flat
+    pub fn z_score(&self, value: f64) -> Option<f64> {
+        if self.values.len() < ANOMALY_MIN_SAMPLES {
+            return None;
+        }
+        let (mean, stddev) = (self.mean()?, self.stddev()?);
+        // relative to the mean, so float noise on a flat window isn't a
+        // deviation
+        if stddev <= f64::EPSILON * mean.abs().max(1.0) {
+            return None;
+        }
+        Some((value - mean) / stddev)
+    }
+}
+
+/// A sample far from the baseline of its metric
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    /// Metric the sample belongs to, e.g. `cpu` or `eth0 rx`
+    pub metric: String,
+    /// When the sample was taken
+    pub time: DateTime<Utc>,
+    /// The sample
+    pub value: f64,
+    /// Mean of the baseline the sample was compared to
+    pub mean: f64,
+    /// Standard deviation of that baseline
+    pub stddev: f64,
+}
+
+impl Anomaly {
+    /// Signed number of standard deviations the sample lies from the mean
+    pub fn sigmas(&self) -> f64 {
+        (self.value - self.mean) / self.stddev
+    }
+}
+
+/// Keeps a rolling baseline per metric and remembers the latest anomalies
+#[derive(Debug, Clone)]
+pub struct AnomalyDetector {
+    /// Deviations beyond which a sample is an anomaly
+    pub sigmas: f64,
+    /// Samples of each baseline
+    pub window: usize,
+    baselines: HashMap<String, RollingBaseline>,
+    /// Metrics whose last sample was an anomaly, so a lasting spike is
+    /// reported once
+    anomalous: HashSet<String>,
+    recent: VecDeque<Anomaly>,
+}
+
+impl Default for AnomalyDetector {
+    fn default() -> Self {
+        Self::new(ANOMALY_DEFAULT_SIGMAS, ANOMALY_DEFAULT_WINDOW)
+    }
+}
+
+impl AnomalyDetector {
+    /// Flags samples more than `sigmas` deviations from the mean of the
+    /// `window` samples before them
+    pub fn new(sigmas: f64, window: usize) -> Self {
+        Self {
+            sigmas,
+            window,
+            baselines: HashMap::new(),
+            anomalous: HashSet::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Compares `value` of `metric` taken at `time` to its baseline, then
+    /// adds it to the baseline. Returns the anomaly when the sample starts
+    /// one; following anomalous samples of the same spike aren't reported
+    /// again.
+    pub fn observe(&mut self, metric: &str, time: DateTime<Utc>, value: f64) -> Option<&Anomaly> {
+        let baseline = self
+            .baselines
+            .entry(metric.to_string())
+            .or_insert_with(|| RollingBaseline::new(self.window));
+        let anomaly = baseline
+            .z_score(value)
+            .filter(|z| z.abs() > self.sigmas)
+            .map(|_| Anomaly {
+                metric: metric.to_string(),
+                time,
+                value,
+                mean: baseline.mean().unwrap_or_default(),
+                stddev: baseline.stddev().unwrap_or_default(),
+            });
+        baseline.push(value);
+
+        let Some(anomaly) = anomaly else {
+            self.anomalous.remove(metric);
+            return None;
+        };
+        if !self.anomalous.insert(metric.to_string()) {
+            return None;
+        }
+        if self.recent.len() == MAX_RECENT_ANOMALIES {
+            self.recent.pop_back();
+        }
+        self.recent.push_front(anomaly);
+        self.recent.front()
+    }
+
+    /// The anomalies reported so far, newest first
+    pub fn recent(&self) -> impl Iterator<Item = &Anomaly> {
+        self.recent.iter()
+    }
+
+    /// Forgets every baseline and anomaly, e.g. when switching hosts
+    pub fn clear(&mut self) {
+        self.baselines.clear();
+        self.anomalous.clear();
+        self.recent.clear();
+    }
+}
+
+/// Whether each of `values` lies more than `sigmas` deviations from the
+/// mean of the `window` values before it, for coloring a chart
+pub fn anomaly_flags(
+    values: impl IntoIterator<Item = f64>,
+    window: usize,
+    sigmas: f64,
+) -> Vec<bool> {
+    let mut baseline = RollingBaseline::new(window);
+    values
+        .into_iter()
+        .map(|value| {
+            let flagged = baseline.z_score(value).is_some_and(|z| z.abs() > sigmas);
+            baseline.push(value);
+            flagged
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A noisy but steady signal around 10
+    fn steady(count: usize) -> Vec<f64> {
+        (0..count).map(|i| 10.0 + (i % 3) as f64).collect()
+    }
+
+    #[test]
+    fn test_baseline_mean_and_stddev() {
+        let mut baseline = RollingBaseline::new(4);
+        for value in [100.0, 2.0, 4.0, 4.0, 6.0] {
+            baseline.push(value);
+        }
+        // the first value left the window
+        assert_eq!(baseline.len(), 4);
+        assert_eq!(baseline.mean(), Some(4.0));
+        assert!((baseline.stddev().unwrap() - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_flags_spike_but_not_flat_or_short_series() {
+        let mut values = steady(20);
+        values.push(50.0);
+        values.push(11.0);
+        let flags = anomaly_flags(values, 60, 3.0);
+        assert_eq!(flags.iter().filter(|flag| **flag).count(), 1);
+        assert!(flags[20]);
+
+        // too few samples for a baseline
+        assert!(!anomaly_flags([1.0, 1.0, 100.0], 60, 3.0).contains(&true));
+        // a flat baseline flags nothing
+        let mut flat = vec![0.0; 20];
+        flat.push(1000.0);
+        assert!(!anomaly_flags(flat, 60, 3.0).contains(&true));
+    }
+
+    #[test]
+    fn test_detector_reports_a_lasting_spike_once() {
+        let mut detector = AnomalyDetector::new(3.0, 60);
+        let now = Utc::now();
+        for value in steady(20) {
+            assert!(detector.observe("cpu", now, value).is_none());
+        }
+        let anomaly = detector.observe("cpu", now, 90.0).cloned().unwrap();
+        assert!(anomaly.sigmas() > 3.0);
+        assert!(detector.observe("cpu", now, 95.0).is_none());
+        detector.observe("cpu", now, 11.0);
+
+        let recent: Vec<&Anomaly> = detector.recent().collect();
+        assert_eq!(recent, [&anomaly]);
+        detector.clear();
+        assert_eq!(detector.recent().count(), 0);
+    }
+}
@@ -0,0 +1,57 @@
+//! In-memory metrics history
+//!
+//! [`TimeSeries`] keeps the latest samples of a metric with their
+//! timestamps, dropping the oldest once full, and answers the min, max and
+//! average queries and downsampling that charts need. The `anomaly` module
+//! flags samples far from the rolling mean of their metric.
+
+pub mod anomaly;
+pub mod series;
+
+pub use anomaly::{Anomaly, AnomalyDetector, RollingBaseline, anomaly_flags};
+pub use series::{Sample, SampleValue, TimeSeries};
+
+use chrono::Duration;
+
+use crate::errors::{CoreError, CoreResult};
+
+/// Parses a range of history such as `7d`, `12h`, `30m` or `90s`
+///
+/// # Errors
+///
+/// Returns [`CoreError::InvalidInput`] if the amount isn't a number or the
+/// unit isn't one of `d`, `h`, `m` and `s`.
+pub fn parse_range(value: &str) -> CoreResult<Duration> {
+    let value = value.trim();
+    let split = value.char_indices().last().map_or(0, |(index, _)| index);
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| {
+        CoreError::invalid(format!(
+            "invalid range `{value}`, expected e.g. 7d, 12h or 30m"
+        ))
+    })?;
+    match unit {
+        "d" => Ok(Duration::days(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "s" => Ok(Duration::seconds(amount)),
+        _ => Err(CoreError::invalid(format!(
+            "unknown unit in `{value}`, use d, h, m or s"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges_parse_with_their_unit() {
+        assert_eq!(parse_range("7d").unwrap(), Duration::days(7));
+        assert_eq!(parse_range(" 10m ").unwrap(), Duration::minutes(10));
+        assert_eq!(parse_range("90s").unwrap(), Duration::seconds(90));
+        assert!(parse_range("10").is_err());
+        assert!(parse_range("5w").is_err());
+        assert!(parse_range("").is_err());
+    }
+}
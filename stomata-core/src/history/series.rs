@@ -0,0 +1,260 @@
+//! Fixed capacity time series of samples
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::history::anomaly_flags;
+
+/// A value and when it was measured
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Sample<T> {
+    /// When the value was measured
+    pub time: DateTime<Utc>,
+    /// The measured value
+    pub value: T,
+}
+
+/// Values that can be averaged
+pub trait SampleValue: Copy {
+    /// The value as `f64`, for averages
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_sample_value {
+    ($($t:ty),*) => {
+        $(impl SampleValue for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_sample_value!(u8, u16, u32, u64, usize, i32, i64, f32, f64);
+
+/// Fixed capacity history of timestamped samples, oldest first.
+///
+/// Pushing onto a full series drops the oldest sample.
+#[derive(Debug, Clone)]
+pub struct TimeSeries<T> {
+    samples: VecDeque<Sample<T>>,
+    capacity: usize,
+}
+
+impl<T> TimeSeries<T> {
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "a time series needs room for one sample");
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Appends `value` measured now
+    pub fn push(&mut self, value: T) {
+        self.push_at(Utc::now(), value);
+    }
+
+    /// Appends `value` measured at `time`
+    pub fn push_at(&mut self, time: DateTime<Utc>, value: T) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample { time, value });
+    }
+
+    /// Number of samples held
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether no sample is held
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Most samples held before the oldest are dropped
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Drops every sample
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// The newest sample
+    pub fn latest(&self) -> Option<&Sample<T>> {
+        self.samples.back()
+    }
+
+    /// Samples from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &Sample<T>> {
+        self.samples.iter()
+    }
+
+    /// Values from oldest to newest
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.samples.iter().map(|sample| &sample.value)
+    }
+
+    /// Timestamps from oldest to newest
+    pub fn times(&self) -> impl Iterator<Item = &DateTime<Utc>> {
+        self.samples.iter().map(|sample| &sample.time)
+    }
+
+    /// Samples taken at or after `since`, oldest first
+    pub fn since(&self, since: DateTime<Utc>) -> impl Iterator<Item = &Sample<T>> {
+        self.samples
+            .iter()
+            .filter(move |sample| sample.time >= since)
+    }
+}
+
+impl<T: Copy> TimeSeries<T> {
+    /// Values from oldest to newest in one slice, e.g. for a sparkline
+    pub fn to_vec(&self) -> Vec<T> {
+        self.values().copied().collect()
+    }
+}
+
+impl<T: Copy + PartialOrd> TimeSeries<T> {
+    /// Smallest value held
+    pub fn min(&self) -> Option<T> {
+        self.values()
+            .copied()
+            .reduce(|min, value| if value < min { value } else { min })
+    }
+
+    /// Largest value held
+    pub fn max(&self) -> Option<T> {
+        self.values()
+            .copied()
+            .reduce(|max, value| if value > max { value } else { max })
+    }
+}
+
+impl<T: Copy + Ord> TimeSeries<T> {
+    /// Appends `value`, clamped to the `percentile` (0 to 1) of the held
+    /// values and itself, so a brief spike doesn't flatten the rest of a
+    /// chart.
+    pub fn push_clamped(&mut self, value: T, percentile: f64) {
+        if self.samples.is_empty() {
+            self.push(value);
+            return;
+        }
+        let mut values = self.to_vec();
+        values.push(value);
+        let index = ((values.len() - 1) as f64 * percentile).round() as usize;
+        let (_, limit, _) = values.select_nth_unstable(index);
+        let clamped = value.min(*limit);
+        self.push(clamped);
+    }
+}
+
+impl<T: SampleValue> TimeSeries<T> {
+    /// Whether each value held is an anomaly against the `window` values
+    /// before it, oldest first, see [`anomaly_flags`]
+    pub fn anomalies(&self, window: usize, sigmas: f64) -> Vec<bool> {
+        anomaly_flags(self.values().map(|value| value.to_f64()), window, sigmas)
+    }
+
+    /// Mean of the values held, `None` when empty
+    pub fn average(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.values().map(|value| value.to_f64()).sum();
+        Some(sum / self.samples.len() as f64)
+    }
+
+    /// Averages consecutive samples into at most `points` samples, each
+    /// stamped with the time of its last sample. Series that already fit
+    /// are returned as they are.
+    pub fn downsample(&self, points: usize) -> Vec<Sample<f64>> {
+        if points == 0 {
+            return Vec::new();
+        }
+        let per_bucket = self.samples.len().div_ceil(points).max(1);
+        let samples: Vec<&Sample<T>> = self.samples.iter().collect();
+        samples
+            .chunks(per_bucket)
+            .map(|bucket| {
+                let sum: f64 = bucket.iter().map(|sample| sample.value.to_f64()).sum();
+                Sample {
+                    time: bucket[bucket.len() - 1].time,
+                    value: sum / bucket.len() as f64,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, second).unwrap()
+    }
+
+    fn series(values: &[u64]) -> TimeSeries<u64> {
+        let mut series = TimeSeries::new(values.len().max(1));
+        for (second, value) in values.iter().enumerate() {
+            series.push_at(at(second as u32), *value);
+        }
+        series
+    }
+
+    #[test]
+    fn test_drops_oldest_when_full() {
+        let mut series = TimeSeries::new(3);
+        for value in 1..=5u64 {
+            series.push_at(at(value as u32), value);
+        }
+        assert_eq!(series.len(), 3);
+        assert_eq!(series.to_vec(), vec![3, 4, 5]);
+        assert_eq!(series.latest().map(|sample| sample.time), Some(at(5)));
+        assert_eq!(series.since(at(4)).count(), 2);
+    }
+
+    #[test]
+    fn test_min_max_average() {
+        let series = series(&[4, 9, 2, 5]);
+        assert_eq!(series.min(), Some(2));
+        assert_eq!(series.max(), Some(9));
+        assert_eq!(series.average(), Some(5.0));
+
+        let empty: TimeSeries<u64> = TimeSeries::new(4);
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.average(), None);
+    }
+
+    #[test]
+    fn test_downsample_averages_buckets() {
+        let series = series(&[1, 3, 5, 7, 9]);
+        let points = series.downsample(2);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].value, 3.0);
+        assert_eq!(points[0].time, at(2));
+        assert_eq!(points[1].value, 8.0);
+        assert_eq!(points[1].time, at(4));
+
+        assert_eq!(series.downsample(10).len(), 5);
+        assert!(series.downsample(0).is_empty());
+    }
+
+    #[test]
+    fn test_push_clamped_limits_spikes() {
+        let mut series = series(&[10, 12, 11, 13, 12, 10, 11, 12, 13, 11]);
+        series.push_clamped(1000, 0.9);
+        assert_eq!(series.latest().map(|sample| sample.value), Some(13));
+    }
+}
@@ -1,4 +1,55 @@
+//! System metrics collection behind the stomata TUI
+//!
+//! `stomata-core` reads CPU, memory, processes, network, disks, sensors,
+//! services and more from the local machine, with no dependency on the
+//! terminal UI, so the collectors can be embedded in other tools.
+//!
+//! There are two ways in:
+//!
+//! - [`StomataSystemMetrics`] refreshes the sysinfo state on demand and
+//!   returns one [`Metrics`] variant per [`MetricsToFetch`] request. This
+//!   is what the TUI uses and gives access to every collector.
+//! - [`CollectorRegistry`] holds boxed [`Collector`]s that all return flat
+//!   [`Metric`] values, which suits exporters that only forward numbers.
+//!
+//! [`remote`] serves [`StomataSystemMetrics`] fetches over TCP, so they can
+//! be made from another machine.
+//!
+//! ```
+//! use stomata_core::{Metrics, MetricsToFetch, StomataSystemMetrics};
+//!
+//! let mut metrics = StomataSystemMetrics::new();
+//! if let Metrics::SystemResource(resources) = metrics.fetch(MetricsToFetch::SystemResource) {
+//!     let memory = resources.system_metrics;
+//!     println!("{} of {} bytes used", memory.memory_used, memory.memory_total);
+//! }
+//! ```
+//!
+//! Types from sysinfo that appear in the API are those of the re-exported
+//! [`sysinfo`] crate, so embedders don't have to match its version. See
+//! `examples/` for one-shot and streaming collection.
+
+#![warn(missing_docs)]
+
+pub mod access;
+pub mod alerts;
 pub mod collectors;
 pub mod constants;
+pub mod errors;
+pub mod export;
+pub mod history;
+pub mod remote;
+pub mod storage;
+pub mod units;
 
-pub use collectors::{NetworkMetrics, ProcessData, SingleProcessData, SystemInfo};
+pub use collectors::{
+    AudioMetrics, BluetoothMetrics, BootAnalysis, CgroupMetrics, Collector, CollectorRegistry,
+    ConnectionsMetrics, CrashEvent, DiskMetrics, HwmonMetrics, Inventory, LogMetrics, Metric,
+    MetricValue, Metrics, MetricsCategory, MetricsToFetch, NetworkMetrics, PluginMetrics,
+    PodMetrics, PowerMetrics, ProcessData, ServiceMetrics, SessionMetrics, SingleProcessData,
+    StomataSystemMetrics, SystemInfo, SystemMetrics, VmMetrics,
+};
+pub use errors::{CoreError, CoreResult};
+pub use history::TimeSeries;
+pub use sysinfo;
+pub use units::{ByteSize, UnitSystem};
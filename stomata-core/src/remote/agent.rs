@@ -0,0 +1,404 @@
+//! The serving side of the remote protocol
+//!
+//! Clients present a token in their handshake, checked against the
+//! agent's [`TokenStore`]. An agent without tokens only listens on a
+//! loopback address, where every local client is let in to read metrics.
+//! Actions changing the machine, like signalling a process, always need a
+//! token with the control scope.
+
+use std::{
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    access::{Action, TokenStore},
+    collectors::{
+        MetricsToFetch, StomataSystemMetrics, SystemInfo,
+        process::actions::{Signal, send_signal},
+    },
+    constants::{
+        REMOTE_IDLE_TIMEOUT_SECS, REMOTE_IO_TIMEOUT_MS, REMOTE_MAX_CLIENTS, REMOTE_PROTOCOL_VERSION,
+    },
+    errors::{CoreError, CoreResult},
+    remote::{
+        ConnectionSlots, Request, Response, drain, read_frame, write_compressed_frame, write_frame,
+    },
+};
+
+/// Answers fetch requests of remote clients from one shared
+/// [`StomataSystemMetrics`], so rates are computed once for all of them
+#[derive(Debug)]
+pub struct Agent {
+    listener: TcpListener,
+    tokens: Arc<TokenStore>,
+    metrics: Arc<Mutex<StomataSystemMetrics>>,
+    slots: ConnectionSlots,
+}
+
+impl Agent {
+    /// Listens on `address`, e.g. `0.0.0.0:7070`, letting in the clients
+    /// with a token of `tokens`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address can't be bound, or `tokens` is
+    /// empty and the address isn't a loopback one.
+    pub fn bind(address: impl ToSocketAddrs, tokens: TokenStore) -> CoreResult<Self> {
+        let listener = TcpListener::bind(address)
+            .map_err(|err| CoreError::io("failed to bind the agent address", err))?;
+        let local = listener
+            .local_addr()
+            .map_err(|err| CoreError::io("failed to read the agent address", err))?;
+        if tokens.is_empty() && !local.ip().is_loopback() {
+            return Err(CoreError::invalid(format!(
+                "refusing to serve {local} without tokens, configure one or listen on localhost"
+            )));
+        }
+        Ok(Self {
+            listener,
+            tokens: Arc::new(tokens),
+            metrics: Arc::new(Mutex::new(StomataSystemMetrics::new())),
+            slots: ConnectionSlots::new(REMOTE_MAX_CLIENTS),
+        })
+    }
+
+    /// Serves at most `limit` clients at once instead of
+    /// [`REMOTE_MAX_CLIENTS`]
+    pub fn with_max_clients(mut self, limit: usize) -> Self {
+        self.slots = ConnectionSlots::new(limit);
+        self
+    }
+
+    /// The address actually bound, useful when binding port 0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket has no local address.
+    pub fn local_addr(&self) -> CoreResult<SocketAddr> {
+        self.listener
+            .local_addr()
+            .map_err(|err| CoreError::io("failed to read the agent address", err))
+    }
+
+    /// Accepts clients until the listener fails, serving each on its own
+    /// thread. A failing client is logged and dropped without affecting
+    /// the others; a client silent for [`REMOTE_IDLE_TIMEOUT_SECS`] is
+    /// dropped, and clients past the limit are refused.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if accepting a connection fails.
+    pub fn serve(&self) -> CoreResult<()> {
+        loop {
+            let (mut stream, peer) = self
+                .listener
+                .accept()
+                .map_err(|err| CoreError::io("failed to accept a client", err))?;
+            if let Err(err) = stream
+                .set_read_timeout(Some(Duration::from_secs(REMOTE_IDLE_TIMEOUT_SECS)))
+                .and_then(|()| {
+                    stream.set_write_timeout(Some(Duration::from_millis(REMOTE_IO_TIMEOUT_MS)))
+                })
+            {
+                tracing::warn!(%peer, %err, "failed to set the socket timeouts");
+                continue;
+            }
+            let Some(slot) = self.slots.acquire() else {
+                tracing::warn!(%peer, "too many clients, refusing");
+                let refused = Response::Refused("too many clients, retry later".to_string());
+                if let Err(err) = write_frame(&mut stream, &refused) {
+                    tracing::debug!(%peer, %err, "failed to refuse a client");
+                }
+                drain(&mut stream);
+                continue;
+            };
+            tracing::info!(%peer, "client connected");
+            let metrics = Arc::clone(&self.metrics);
+            let tokens = Arc::clone(&self.tokens);
+            thread::spawn(move || {
+                match serve_client(stream, &tokens, &metrics) {
+                    Ok(()) => tracing::info!(%peer, "client disconnected"),
+                    Err(err) => tracing::warn!(%peer, %err, "client dropped"),
+                }
+                drop(slot);
+            });
+        }
+    }
+}
+
+fn serve_client(
+    mut stream: TcpStream,
+    tokens: &TokenStore,
+    metrics: &Mutex<StomataSystemMetrics>,
+) -> CoreResult<()> {
+    // the token of the handshake, `None` until it succeeded
+    let mut greeted: Option<Option<String>> = None;
+    let mut compress = false;
+    while let Some(request) = read_frame::<Request>(&mut stream)? {
+        let (response, close) = match (request, &greeted) {
+            (Request::Hello { version, .. }, _) if version != REMOTE_PROTOCOL_VERSION => (
+                Response::Unsupported(format!(
+                    "protocol version {version} isn't supported, the agent speaks {REMOTE_PROTOCOL_VERSION}"
+                )),
+                false,
+            ),
+            (
+                Request::Hello {
+                    token,
+                    compress: asked,
+                    ..
+                },
+                _,
+            ) => match check_token(tokens, token.as_deref(), Action::ReadMetrics) {
+                Ok(()) => {
+                    greeted = Some(token);
+                    let hello = Response::Hello {
+                        version: REMOTE_PROTOCOL_VERSION,
+                        hostname: SystemInfo::new().hostname,
+                        compress: asked,
+                    };
+                    (hello, false)
+                }
+                Err(reason) => (Response::Refused(reason), true),
+            },
+            (_, None) => (
+                Response::Refused("the handshake must come first".to_string()),
+                true,
+            ),
+            (Request::Fetch(request), Some(_)) => (answer(request, metrics), false),
+            (Request::Signal { pid, signal }, Some(token)) => {
+                (signal_process(tokens, token.as_deref(), pid, signal), false)
+            }
+        };
+        if compress {
+            write_compressed_frame(&mut stream, &response)?;
+        } else {
+            write_frame(&mut stream, &response)?;
+        }
+        // the hello itself goes out plain, the frames after it compressed
+        // if the client asked
+        if let Response::Hello {
+            compress: asked, ..
+        } = response
+        {
+            compress = asked;
+        }
+        if close {
+            return Err(CoreError::PermissionDenied("client refused".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `token` may perform `action`, the reason of the refusal
+/// otherwise. An agent without tokens lets everyone read, which
+/// [`Agent::bind`] only allows on loopback addresses, but nobody act.
+fn check_token(tokens: &TokenStore, token: Option<&str>, action: Action) -> Result<(), String> {
+    if tokens.is_empty() {
+        return match action {
+            Action::ReadMetrics => Ok(()),
+            _ => Err(format!(
+                "{} needs a control token and the agent has none",
+                action.as_str()
+            )),
+        };
+    }
+    let token = token.ok_or_else(|| "the agent requires a token".to_string())?;
+    tokens
+        .authorize(token, action)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+fn signal_process(tokens: &TokenStore, token: Option<&str>, pid: u32, signal: Signal) -> Response {
+    if let Err(reason) = check_token(tokens, token, Action::SignalProcess) {
+        return Response::Refused(reason);
+    }
+    match send_signal(pid, signal) {
+        Ok(()) => {
+            tracing::info!(pid, signal = signal.as_str(), "signal sent for a client");
+            Response::Done
+        }
+        Err(err) => Response::Failed(err.to_string()),
+    }
+}
+
+fn answer(request: MetricsToFetch, metrics: &Mutex<StomataSystemMetrics>) -> Response {
+    if !request.is_remote() {
+        return Response::Unsupported(format!("{request:?} isn't served remotely"));
+    }
+    let mut metrics = metrics.lock().unwrap_or_else(PoisonError::into_inner);
+    Response::Metrics(metrics.fetch(request))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        access::{ApiToken, Scope},
+        collectors::Metrics,
+        remote::RemoteMetrics,
+    };
+
+    fn tokens() -> TokenStore {
+        TokenStore::new(vec![
+            ApiToken {
+                name: "laptop".to_string(),
+                secret: "read-secret".to_string(),
+                scope: Scope::Read,
+            },
+            ApiToken {
+                name: "ops".to_string(),
+                secret: "control-secret".to_string(),
+                scope: Scope::Control,
+            },
+        ])
+    }
+
+    #[test]
+    fn test_clients_fetch_from_a_running_agent() {
+        let agent = Agent::bind("127.0.0.1:0", TokenStore::default()).unwrap();
+        let address = agent.local_addr().unwrap();
+        thread::spawn(move || agent.serve());
+
+        let mut client = RemoteMetrics::connect(address.to_string(), None).unwrap();
+        assert_eq!(client.hostname(), SystemInfo::new().hostname);
+        assert!(matches!(
+            client.fetch(MetricsToFetch::SystemResource),
+            Ok(Metrics::SystemResource(_))
+        ));
+        assert!(client.fetch(MetricsToFetch::Audio).is_err());
+        // the connection stays usable after a refused request
+        assert!(matches!(
+            client.fetch(MetricsToFetch::Summary),
+            Ok(Metrics::Summary(_))
+        ));
+    }
+
+    #[test]
+    fn test_lazy_clients_connect_on_the_first_fetch() {
+        let agent = Agent::bind("127.0.0.1:0", TokenStore::default()).unwrap();
+        let address = agent.local_addr().unwrap().to_string();
+        thread::spawn(move || agent.serve());
+
+        let mut client = RemoteMetrics::new(address.clone(), None);
+        assert!(!client.is_connected());
+        assert_eq!(client.hostname(), address);
+        assert!(client.fetch(MetricsToFetch::Summary).is_ok());
+        assert!(client.is_connected());
+        assert_eq!(client.hostname(), SystemInfo::new().hostname);
+    }
+
+    #[test]
+    fn test_compressing_clients_get_the_same_answers() {
+        let agent = Agent::bind("127.0.0.1:0", TokenStore::default()).unwrap();
+        let address = agent.local_addr().unwrap().to_string();
+        thread::spawn(move || agent.serve());
+
+        let mut client = RemoteMetrics::new(address, None)
+            .with_compression()
+            .open()
+            .unwrap();
+        // the process list is well past the compression threshold
+        assert!(matches!(
+            client.fetch(MetricsToFetch::Process),
+            Ok(Metrics::Processes(processes)) if !processes.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_agents_with_tokens_refuse_clients_without_one() {
+        let agent = Agent::bind("127.0.0.1:0", tokens()).unwrap();
+        let address = agent.local_addr().unwrap().to_string();
+        thread::spawn(move || agent.serve());
+
+        assert!(matches!(
+            RemoteMetrics::connect(address.clone(), None),
+            Err(CoreError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            RemoteMetrics::connect(address.clone(), Some("wrong".to_string())),
+            Err(CoreError::PermissionDenied(_))
+        ));
+        let mut client = RemoteMetrics::connect(address, Some("read-secret".to_string())).unwrap();
+        assert!(client.fetch(MetricsToFetch::Summary).is_ok());
+    }
+
+    #[test]
+    fn test_fetches_before_the_handshake_are_refused() {
+        let agent = Agent::bind("127.0.0.1:0", TokenStore::default()).unwrap();
+        let address = agent.local_addr().unwrap();
+        thread::spawn(move || agent.serve());
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write_frame(&mut stream, &Request::Fetch(MetricsToFetch::Summary)).unwrap();
+        assert!(matches!(
+            read_frame::<Response>(&mut stream).unwrap(),
+            Some(Response::Refused(_))
+        ));
+    }
+
+    #[test]
+    fn test_clients_past_the_limit_are_refused() {
+        let agent = Agent::bind("127.0.0.1:0", TokenStore::default())
+            .unwrap()
+            .with_max_clients(1);
+        let address = agent.local_addr().unwrap().to_string();
+        thread::spawn(move || agent.serve());
+
+        let _first = RemoteMetrics::connect(address.clone(), None).unwrap();
+        assert!(matches!(
+            RemoteMetrics::connect(address, None),
+            Err(CoreError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_signals_need_a_control_token() {
+        let agent = Agent::bind("127.0.0.1:0", tokens()).unwrap();
+        let address = agent.local_addr().unwrap().to_string();
+        thread::spawn(move || agent.serve());
+        // a child of the test, so signalling it harms nothing
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+
+        let mut reader =
+            RemoteMetrics::connect(address.clone(), Some("read-secret".to_string())).unwrap();
+        assert!(matches!(
+            reader.signal(child.id(), Signal::Term),
+            Err(CoreError::PermissionDenied(_))
+        ));
+        // the refusal leaves the connection usable for reading
+        assert!(reader.fetch(MetricsToFetch::Summary).is_ok());
+        assert!(child.try_wait().unwrap().is_none());
+
+        let mut operator =
+            RemoteMetrics::connect(address, Some("control-secret".to_string())).unwrap();
+        operator.signal(child.id(), Signal::Term).unwrap();
+        assert!(child.wait().unwrap().code().is_none());
+    }
+
+    #[test]
+    fn test_agents_without_tokens_refuse_signals() {
+        let agent = Agent::bind("127.0.0.1:0", TokenStore::default()).unwrap();
+        let address = agent.local_addr().unwrap().to_string();
+        thread::spawn(move || agent.serve());
+
+        let mut client = RemoteMetrics::connect(address, None).unwrap();
+        assert!(matches!(
+            client.signal(std::process::id(), Signal::Term),
+            Err(CoreError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_agents_without_tokens_stay_on_loopback() {
+        assert!(Agent::bind("0.0.0.0:0", TokenStore::default()).is_err());
+        assert!(Agent::bind("0.0.0.0:0", tokens()).is_ok());
+    }
+}
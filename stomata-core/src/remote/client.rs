@@ -0,0 +1,242 @@
+//! The client side of the remote protocol
+
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    collectors::{Metrics, MetricsToFetch, process::actions::Signal},
+    constants::{REMOTE_IO_TIMEOUT_MS, REMOTE_PROTOCOL_VERSION, REMOTE_RECONNECT_SECS},
+    errors::{CoreError, CoreResult},
+    remote::{Request, Response, read_frame, write_frame},
+};
+
+/// Connection to an [`Agent`](crate::remote::Agent), fetching the same
+/// [`Metrics`] as [`StomataSystemMetrics::fetch`](crate::StomataSystemMetrics::fetch)
+/// does locally.
+///
+/// A dropped connection is reopened on a later fetch, at most every
+/// [`REMOTE_RECONNECT_SECS`], so a restarted agent is picked up again
+/// without a down one stalling every fetch.
+#[derive(Debug)]
+pub struct RemoteMetrics {
+    address: String,
+    hostname: String,
+    /// Secret presented in the handshake
+    token: Option<String>,
+    /// Whether the handshake asks for compressed answers
+    compress: bool,
+    stream: Option<TcpStream>,
+    /// When connecting last failed, to space out the retries
+    failed_at: Option<Instant>,
+}
+
+impl RemoteMetrics {
+    /// Connects to the agent at `address`, e.g. `server:7070`, presenting
+    /// `token`, and checks it speaks the same protocol version
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent can't be reached or refuses the
+    /// handshake, [`CoreError::PermissionDenied`] when it refuses the token.
+    pub fn connect(address: impl Into<String>, token: Option<String>) -> CoreResult<Self> {
+        Self::new(address, token).open()
+    }
+
+    /// A client for the agent at `address` that connects on the first
+    /// fetch, for agents that may not be up yet. The host name is the
+    /// address until then.
+    pub fn new(address: impl Into<String>, token: Option<String>) -> Self {
+        let address = address.into();
+        Self {
+            hostname: address.clone(),
+            address,
+            token,
+            compress: false,
+            stream: None,
+            failed_at: None,
+        }
+    }
+
+    /// Asks the agent for zstd-compressed answers, worth it over slow
+    /// links. Agents that don't compress answer plainly.
+    pub fn with_compression(mut self) -> Self {
+        self.compress = true;
+        self
+    }
+
+    /// Connects right away instead of on the first fetch
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent can't be reached or refuses the
+    /// handshake, [`CoreError::PermissionDenied`] when it refuses the token.
+    pub fn open(mut self) -> CoreResult<Self> {
+        self.reconnect()?;
+        Ok(self)
+    }
+
+    /// Whether the connection is open; it is closed until the first fetch
+    /// of a [`new`](Self::new) client and after a failed fetch
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Address the client was connected with
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Host name the agent reported
+    pub fn hostname(&self) -> &str {
+        &self.hostname
+    }
+
+    /// Asks the agent for `request`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent doesn't serve the request, or the
+    /// connection failed and couldn't be reopened.
+    pub fn fetch(&mut self, request: MetricsToFetch) -> CoreResult<Metrics> {
+        if !request.is_remote() {
+            return Err(CoreError::unsupported(format!(
+                "{request:?} isn't served remotely"
+            )));
+        }
+        let stream = match self.stream.as_mut() {
+            Some(stream) => stream,
+            None => self.reconnect()?,
+        };
+        let response = write_frame(stream, &Request::Fetch(request))
+            .and_then(|()| read_frame::<Response>(stream));
+        match response {
+            Ok(Some(Response::Metrics(metrics))) => Ok(metrics),
+            Ok(Some(Response::Unsupported(reason))) => Err(CoreError::unsupported(reason)),
+            Ok(Some(Response::Refused(reason))) => {
+                self.stream = None;
+                Err(CoreError::PermissionDenied(reason))
+            }
+            Ok(Some(_)) => Err(CoreError::collection(format!(
+                "{} answered a fetch with something else",
+                self.address
+            ))),
+            Ok(None) => {
+                self.stream = None;
+                Err(CoreError::collection(format!(
+                    "{} closed the connection",
+                    self.address
+                )))
+            }
+            Err(err) => {
+                self.stream = None;
+                Err(err.context(&self.address))
+            }
+        }
+    }
+
+    /// Asks the agent to send `signal` to its process `pid`, which needs a
+    /// token with the control scope
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::PermissionDenied`] if the agent refuses the
+    /// token, and an error if sending failed on the agent or the
+    /// connection failed and couldn't be reopened.
+    pub fn signal(&mut self, pid: u32, signal: Signal) -> CoreResult<()> {
+        let stream = match self.stream.as_mut() {
+            Some(stream) => stream,
+            None => self.reconnect()?,
+        };
+        let response = write_frame(stream, &Request::Signal { pid, signal })
+            .and_then(|()| read_frame::<Response>(stream));
+        match response {
+            Ok(Some(Response::Done)) => Ok(()),
+            Ok(Some(Response::Failed(reason))) => Err(CoreError::collection(reason)),
+            Ok(Some(Response::Refused(reason))) => Err(CoreError::PermissionDenied(reason)),
+            Ok(Some(_)) => Err(CoreError::collection(format!(
+                "{} answered a signal with something else",
+                self.address
+            ))),
+            Ok(None) => {
+                self.stream = None;
+                Err(CoreError::collection(format!(
+                    "{} closed the connection",
+                    self.address
+                )))
+            }
+            Err(err) => {
+                self.stream = None;
+                Err(err.context(&self.address))
+            }
+        }
+    }
+
+    /// Opens a new connection and updates the host name from the handshake
+    fn reconnect(&mut self) -> CoreResult<&mut TcpStream> {
+        if let Some(failed_at) = self.failed_at
+            && failed_at.elapsed() < Duration::from_secs(REMOTE_RECONNECT_SECS)
+        {
+            return Err(CoreError::collection(format!(
+                "{} is unreachable, retrying shortly",
+                self.address
+            )));
+        }
+        match handshake(&self.address, self.token.clone(), self.compress) {
+            Ok((stream, hostname)) => {
+                self.hostname = hostname;
+                self.failed_at = None;
+                Ok(self.stream.insert(stream))
+            }
+            Err(err) => {
+                self.failed_at = Some(Instant::now());
+                Err(err)
+            }
+        }
+    }
+}
+
+fn handshake(
+    address: &str,
+    token: Option<String>,
+    compress: bool,
+) -> CoreResult<(TcpStream, String)> {
+    let timeout = Duration::from_millis(REMOTE_IO_TIMEOUT_MS);
+    let mut stream = connect(address, timeout)
+        .map_err(|err| CoreError::io(format!("failed to connect to {address}"), err))?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|()| stream.set_write_timeout(Some(timeout)))
+        .map_err(|err| CoreError::io("failed to set the socket timeouts", err))?;
+    write_frame(
+        &mut stream,
+        &Request::Hello {
+            version: REMOTE_PROTOCOL_VERSION,
+            token,
+            compress,
+        },
+    )?;
+    match read_frame::<Response>(&mut stream)? {
+        Some(Response::Hello { hostname, .. }) => Ok((stream, hostname)),
+        Some(Response::Unsupported(reason)) => Err(CoreError::unsupported(reason)),
+        Some(Response::Refused(reason)) => Err(CoreError::PermissionDenied(format!(
+            "{address} refused the connection: {reason}"
+        ))),
+        _ => Err(CoreError::collection(format!(
+            "{address} didn't answer the handshake"
+        ))),
+    }
+}
+
+/// Connects to the first resolved address of `address` that answers
+fn connect(address: &str, timeout: Duration) -> std::io::Result<TcpStream> {
+    let mut last_error = None;
+    for address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_error = Some(err),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| std::io::Error::other("no address resolved")))
+}
@@ -0,0 +1,147 @@
+//! Polling an agent off the calling thread
+
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use crate::{
+    collectors::{Metrics, MetricsToFetch, poller::Poller},
+    constants::REMOTE_POLL_INTERVAL_MS,
+    remote::RemoteMetrics,
+};
+
+/// Host name and connection state of the client, updated after each fetch
+#[derive(Debug)]
+struct HostStatus {
+    hostname: String,
+    connected: bool,
+}
+
+/// A [`RemoteMetrics`] client polled on background [`Poller`]s, one per
+/// request, so a hung or unreachable agent never holds up the caller.
+///
+/// A request gets its poller on its first [`latest`](Self::latest), which
+/// only reads the result of the last run. The pollers stop when the host is
+/// dropped.
+#[derive(Debug)]
+pub struct RemoteHost {
+    client: Arc<Mutex<RemoteMetrics>>,
+    address: String,
+    status: Arc<Mutex<HostStatus>>,
+    interval: Duration,
+    pollers: Vec<(MetricsToFetch, Poller<Result<Metrics, String>>)>,
+}
+
+impl RemoteHost {
+    /// Polls `client` every `REMOTE_POLL_INTERVAL_MS`
+    pub fn new(client: RemoteMetrics) -> Self {
+        let status = HostStatus {
+            hostname: client.hostname().to_string(),
+            connected: client.is_connected(),
+        };
+        Self {
+            address: client.address().to_string(),
+            client: Arc::new(Mutex::new(client)),
+            status: Arc::new(Mutex::new(status)),
+            interval: Duration::from_millis(REMOTE_POLL_INTERVAL_MS),
+            pollers: Vec::new(),
+        }
+    }
+
+    /// Sets the time between two fetches of a request. Requests polled
+    /// already start over with a fresh poller.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+        self.pollers.clear();
+    }
+
+    /// Address the client was connected with
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Host name the agent reported, the address until it was reached
+    pub fn hostname(&self) -> String {
+        self.status
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .hostname
+            .clone()
+    }
+
+    /// Whether the last fetch found the connection open
+    pub fn is_connected(&self) -> bool {
+        self.status
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .connected
+    }
+
+    /// The client shared with the pollers, for requests like signals that
+    /// are sent once rather than polled. Locking it waits for a running
+    /// fetch, so use it off the UI thread.
+    pub fn client(&self) -> Arc<Mutex<RemoteMetrics>> {
+        Arc::clone(&self.client)
+    }
+
+    /// Result of the latest fetch of `request`, `None` until the first
+    /// one finished
+    pub fn latest(&mut self, request: MetricsToFetch) -> Option<Result<Metrics, String>> {
+        let index = match self
+            .pollers
+            .iter()
+            .position(|(polled, _)| *polled == request)
+        {
+            Some(index) => index,
+            None => {
+                self.pollers.push((request, self.poller(request)));
+                self.pollers.len() - 1
+            }
+        };
+        self.pollers[index].1.latest()
+    }
+
+    fn poller(&self, request: MetricsToFetch) -> Poller<Result<Metrics, String>> {
+        let client = Arc::clone(&self.client);
+        let status = Arc::clone(&self.status);
+        Poller::new(self.interval, move || {
+            let mut client = client.lock().unwrap_or_else(PoisonError::into_inner);
+            let result = client.fetch(request).map_err(|err| err.to_string());
+            let mut status = status.lock().unwrap_or_else(PoisonError::into_inner);
+            status.hostname = client.hostname().to_string();
+            status.connected = client.is_connected();
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::TcpListener,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_hung_agent_does_not_block_latest() {
+        // accepts connections but never answers the handshake
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            let _held: Vec<_> = listener.incoming().collect();
+        });
+
+        let mut host = RemoteHost::new(RemoteMetrics::new(address.clone(), None));
+        let started = Instant::now();
+        assert!(host.latest(MetricsToFetch::Summary).is_none());
+        thread::sleep(Duration::from_millis(50));
+        assert!(host.latest(MetricsToFetch::Summary).is_none());
+        assert_eq!(host.hostname(), address);
+        assert!(!host.is_connected());
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,764 @@
+//! Read-only JSON API over HTTP
+//!
+//! [`ApiServer`] answers `GET` requests with the same data the TUI shows,
+//! for dashboards and scripts:
+//!
+//! - `/api/v1/system` - host details with CPU, memory and swap usage
+//! - `/api/v1/processes` - the process list
+//! - `/api/v1/networks` - interfaces with their rates
+//! - `/api/v1/history?metric=cpu&range=10m` - samples of `cpu`, `memory`
+//!   or `swap` usage in percent, kept in memory while the server runs
+//!
+//! When the [`TokenStore`] holds tokens, every request needs an
+//! `Authorization: Bearer <secret>` header with a token allowed to read
+//! metrics. Without tokens the server only listens on a loopback address,
+//! since the process list shows users and full command lines.
+//!
+//! Only one request is served per connection, and at most
+//! [`API_MAX_CONNECTIONS`] connections at once; requests that are too
+//! large or too slow to arrive are refused. Bodies of at least
+//! [`API_COMPRESS_MIN_BYTES`] are compressed for clients sending
+//! `Accept-Encoding: zstd` or `gzip`, zstd first when both are accepted;
+//! the process list shrinks to a fraction.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+    time::Duration,
+};
+
+use chrono::Utc;
+use flate2::{Compression, write::GzEncoder};
+use serde::Serialize;
+
+use crate::{
+    access::{AccessError, Action, TokenStore},
+    collectors::{Metrics, MetricsToFetch, StomataSystemMetrics, SystemInfo, SystemMetrics},
+    constants::{
+        API_COMPRESS_MIN_BYTES, API_HISTORY_INTERVAL_MS, API_HISTORY_SAMPLES, API_IO_TIMEOUT_MS,
+        API_MAX_CONNECTIONS, API_MAX_REQUEST_BYTES,
+    },
+    errors::{CoreError, CoreResult},
+    history::{Sample, TimeSeries, parse_range},
+    remote::{ConnectionSlots, drain},
+};
+
+/// Why a request couldn't be read
+#[derive(Debug, thiserror::Error)]
+pub enum RequestError {
+    /// The request head is larger than [`API_MAX_REQUEST_BYTES`]
+    #[error("request headers exceed {API_MAX_REQUEST_BYTES} bytes")]
+    TooLarge,
+    /// The request was malformed, or reading it failed or timed out
+    #[error(transparent)]
+    Invalid(#[from] CoreError),
+}
+
+impl RequestError {
+    /// HTTP status of the refusal
+    pub fn status(&self) -> u16 {
+        match self {
+            RequestError::TooLarge => 413,
+            RequestError::Invalid(CoreError::Io { source, .. })
+                if matches!(source.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                408
+            }
+            RequestError::Invalid(_) => 400,
+        }
+    }
+}
+
+/// A parsed HTTP request line and the headers the API looks at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// Request method, e.g. `GET`
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Query parameters, not percent-decoded
+    pub query: HashMap<String, String>,
+    /// Secret of an `Authorization: Bearer` header
+    pub bearer: Option<String>,
+    /// Compression an `Accept-Encoding` header allows for the body
+    pub encoding: Option<ContentEncoding>,
+}
+
+/// Compression of a response body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    /// `Content-Encoding: gzip`, understood by every HTTP client
+    Gzip,
+    /// `Content-Encoding: zstd`, smaller and faster to compress than gzip
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Name of the coding in `Accept-Encoding` and `Content-Encoding`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    /// The coding an `Accept-Encoding` value allows, preferring zstd
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        [Self::Zstd, Self::Gzip]
+            .into_iter()
+            .find(|encoding| accepts(accept_encoding, encoding.as_str()))
+    }
+
+    fn compress(self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::encode_all(body, 0),
+        }
+    }
+}
+
+impl HttpRequest {
+    /// Reads the request line and headers from `reader`, ignoring any body.
+    /// At most [`API_MAX_REQUEST_BYTES`] are read, so a client that never
+    /// ends a line can't grow the buffer without limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequestError::TooLarge`] if the request is larger than
+    /// [`API_MAX_REQUEST_BYTES`], and [`RequestError::Invalid`] if the read
+    /// fails or the request line is malformed.
+    pub fn read(reader: &mut impl BufRead) -> Result<Self, RequestError> {
+        let mut reader = reader.take(API_MAX_REQUEST_BYTES as u64 + 1);
+        let mut head = Vec::new();
+        let mut line = String::new();
+        let mut total = 0;
+        loop {
+            line.clear();
+            let read = reader
+                .read_line(&mut line)
+                .map_err(|err| CoreError::io("failed to read the request", err))?;
+            total += read;
+            if total > API_MAX_REQUEST_BYTES {
+                return Err(RequestError::TooLarge);
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            if read == 0 || line.is_empty() {
+                break;
+            }
+            head.push(line.to_string());
+        }
+
+        let mut lines = head.iter();
+        let request_line = lines
+            .next()
+            .ok_or_else(|| CoreError::invalid("empty request"))?;
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(
+                CoreError::invalid(format!("malformed request line `{request_line}`")).into(),
+            );
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (key.to_string(), value.to_string())
+            })
+            .collect();
+        let mut bearer = None;
+        let mut encoding = None;
+        for (name, value) in lines.filter_map(|header| header.split_once(':')) {
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("authorization") {
+                bearer = value
+                    .trim()
+                    .split_once(' ')
+                    .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+                    .map(|(_, secret)| secret.trim().to_string());
+            } else if name.eq_ignore_ascii_case("accept-encoding") {
+                encoding = ContentEncoding::negotiate(value);
+            }
+        }
+        Ok(Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            query,
+            bearer,
+            encoding,
+        })
+    }
+}
+
+/// Whether an `Accept-Encoding` value allows `coding`, e.g. `gzip, br` or
+/// `*;q=0.5` for gzip, unless its quality is zero
+fn accepts(accept_encoding: &str, coding: &str) -> bool {
+    accept_encoding.split(',').any(|offered| {
+        let mut params = offered.split(';').map(str::trim);
+        let name = params.next().unwrap_or_default();
+        let refused = params.any(|param| {
+            param
+                .strip_prefix("q=")
+                .and_then(|quality| quality.parse::<f32>().ok())
+                .is_some_and(|quality| quality == 0.0)
+        });
+        (name.eq_ignore_ascii_case(coding) || name == "*") && !refused
+    })
+}
+
+/// Status and JSON body of an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// JSON document sent as the body
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn json(value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status: 200, body },
+            Err(err) => Self::error(500, &format!("failed to encode the response: {err}")),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+
+    /// Writes the response with the headers closing the connection, the
+    /// body compressed with `encoding` if it is at least
+    /// [`API_COMPRESS_MIN_BYTES`] long
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if compressing or the write fails.
+    pub fn write(
+        &self,
+        writer: &mut impl Write,
+        encoding: Option<ContentEncoding>,
+    ) -> CoreResult<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Content Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        };
+        let (header, body) = match encoding {
+            Some(encoding) if self.body.len() >= API_COMPRESS_MIN_BYTES => {
+                let compressed = encoding
+                    .compress(self.body.as_bytes())
+                    .map_err(|err| CoreError::io("failed to compress the response", err))?;
+                (
+                    format!("Content-Encoding: {}\r\n", encoding.as_str()),
+                    Cow::Owned(compressed),
+                )
+            }
+            _ => (String::new(), Cow::Borrowed(self.body.as_bytes())),
+        };
+        write!(
+            writer,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\n{header}Vary: Accept-Encoding\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            body.len(),
+        )
+        .and_then(|()| writer.write_all(&body))
+        .and_then(|()| writer.flush())
+        .map_err(|err| CoreError::io("failed to write the response", err))
+    }
+}
+
+/// Usage history sampled in the background, in percent
+#[derive(Debug)]
+struct UsageHistory {
+    cpu: TimeSeries<f64>,
+    memory: TimeSeries<f64>,
+    swap: TimeSeries<f64>,
+}
+
+impl UsageHistory {
+    fn record(&mut self, metrics: &SystemMetrics) {
+        let percent = |used: u64, total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                used as f64 / total as f64 * 100.0
+            }
+        };
+        self.cpu.push(metrics.cpu_usage as f64);
+        self.memory
+            .push(percent(metrics.memory_used, metrics.memory_total));
+        self.swap
+            .push(percent(metrics.swap_used, metrics.swap_total));
+    }
+
+    fn series(&self, metric: &str) -> Option<&TimeSeries<f64>> {
+        match metric {
+            "cpu" => Some(&self.cpu),
+            "memory" => Some(&self.memory),
+            "swap" => Some(&self.swap),
+            _ => None,
+        }
+    }
+}
+
+/// Body of `/api/v1/system`
+#[derive(Serialize)]
+struct SystemResponse {
+    info: SystemInfo,
+    metrics: SystemMetrics,
+}
+
+/// Body of `/api/v1/history`
+#[derive(Serialize)]
+struct HistoryResponse<'a> {
+    metric: &'a str,
+    unit: &'static str,
+    samples: Vec<Sample<f64>>,
+}
+
+/// Serves the JSON API, sampling the usage history in the background
+#[derive(Debug)]
+pub struct ApiServer {
+    listener: TcpListener,
+    tokens: TokenStore,
+    metrics: Arc<Mutex<StomataSystemMetrics>>,
+    history: Arc<Mutex<UsageHistory>>,
+    slots: ConnectionSlots,
+}
+
+impl ApiServer {
+    /// Listens on `address`, e.g. `127.0.0.1:8686`. An empty `tokens`
+    /// store lets every request through, so it is only accepted on a
+    /// loopback address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address can't be bound, or `tokens` is
+    /// empty and the address isn't a loopback one.
+    pub fn bind(address: impl ToSocketAddrs, tokens: TokenStore) -> CoreResult<Self> {
+        let listener = TcpListener::bind(address)
+            .map_err(|err| CoreError::io("failed to bind the API address", err))?;
+        let local = listener
+            .local_addr()
+            .map_err(|err| CoreError::io("failed to read the API address", err))?;
+        if tokens.is_empty() && !local.ip().is_loopback() {
+            return Err(CoreError::invalid(format!(
+                "refusing to serve {local} without tokens, configure one under [serve] or listen on localhost"
+            )));
+        }
+        Ok(Self {
+            listener,
+            tokens,
+            metrics: Arc::new(Mutex::new(StomataSystemMetrics::new())),
+            history: Arc::new(Mutex::new(UsageHistory {
+                cpu: TimeSeries::new(API_HISTORY_SAMPLES),
+                memory: TimeSeries::new(API_HISTORY_SAMPLES),
+                swap: TimeSeries::new(API_HISTORY_SAMPLES),
+            })),
+            slots: ConnectionSlots::new(API_MAX_CONNECTIONS),
+        })
+    }
+
+    /// Serves at most `limit` connections at once instead of
+    /// [`API_MAX_CONNECTIONS`]
+    pub fn with_max_connections(mut self, limit: usize) -> Self {
+        self.slots = ConnectionSlots::new(limit);
+        self
+    }
+
+    /// The address actually bound, useful when binding port 0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket has no local address.
+    pub fn local_addr(&self) -> CoreResult<SocketAddr> {
+        self.listener
+            .local_addr()
+            .map_err(|err| CoreError::io("failed to read the API address", err))
+    }
+
+    /// Starts sampling the history and accepts requests until the listener
+    /// fails, serving each connection on its own thread. Connections past
+    /// the limit are answered with 503 right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if accepting a connection fails.
+    pub fn serve(self) -> CoreResult<()> {
+        let metrics = Arc::clone(&self.metrics);
+        let history = Arc::clone(&self.history);
+        thread::spawn(move || {
+            loop {
+                let fetched = metrics
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .fetch(MetricsToFetch::SystemResource);
+                if let Metrics::SystemResource(resources) = fetched {
+                    history
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .record(&resources.system_metrics);
+                }
+                thread::sleep(Duration::from_millis(API_HISTORY_INTERVAL_MS));
+            }
+        });
+
+        let server = Arc::new(self);
+        loop {
+            let (mut stream, peer) = server
+                .listener
+                .accept()
+                .map_err(|err| CoreError::io("failed to accept a client", err))?;
+            let timeout = Some(Duration::from_millis(API_IO_TIMEOUT_MS));
+            if let Err(err) = stream
+                .set_read_timeout(timeout)
+                .and_then(|()| stream.set_write_timeout(timeout))
+            {
+                tracing::warn!(%peer, %err, "failed to set the socket timeouts");
+                continue;
+            }
+            let Some(slot) = server.slots.acquire() else {
+                tracing::warn!(%peer, "too many connections, refusing");
+                let busy = HttpResponse::error(503, "too many connections, retry later");
+                if let Err(err) = busy.write(&mut &stream, None) {
+                    tracing::debug!(%peer, %err, "failed to refuse a connection");
+                }
+                drain(&mut stream);
+                continue;
+            };
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                if let Err(err) = server.serve_connection(stream) {
+                    tracing::warn!(%peer, %err, "request failed");
+                }
+                drop(slot);
+            });
+        }
+    }
+
+    fn serve_connection(&self, stream: TcpStream) -> CoreResult<()> {
+        let mut reader = BufReader::new(&stream);
+        let (response, encoding) = match HttpRequest::read(&mut reader) {
+            Ok(request) => {
+                let response = self.respond(&request);
+                tracing::debug!(path = %request.path, status = response.status, "API request");
+                (response, request.encoding)
+            }
+            Err(err) => (HttpResponse::error(err.status(), &err.to_string()), None),
+        };
+        response.write(&mut &stream, encoding)
+    }
+
+    /// Answers `request`
+    pub fn respond(&self, request: &HttpRequest) -> HttpResponse {
+        if request.method != "GET" {
+            return HttpResponse::error(405, "only GET is supported");
+        }
+        if let Some(response) = self.check_token(request) {
+            return response;
+        }
+        match request.path.trim_end_matches('/') {
+            "/api/v1/system" => match self.fetch(MetricsToFetch::SystemResource) {
+                Metrics::SystemResource(resources) => HttpResponse::json(&SystemResponse {
+                    info: SystemInfo::new(),
+                    metrics: resources.system_metrics,
+                }),
+                _ => HttpResponse::error(500, "unexpected metrics"),
+            },
+            "/api/v1/processes" => match self.fetch(MetricsToFetch::Process) {
+                Metrics::Processes(processes) => HttpResponse::json(&processes),
+                _ => HttpResponse::error(500, "unexpected metrics"),
+            },
+            "/api/v1/networks" => match self.fetch(MetricsToFetch::Networks) {
+                Metrics::Networks(networks) => HttpResponse::json(&networks),
+                _ => HttpResponse::error(500, "unexpected metrics"),
+            },
+            "/api/v1/history" => self.history(request),
+            _ => HttpResponse::error(404, "unknown endpoint"),
+        }
+    }
+
+    /// The refusal of a request without a suitable token, `None` to let it
+    /// through
+    fn check_token(&self, request: &HttpRequest) -> Option<HttpResponse> {
+        if self.tokens.is_empty() {
+            return None;
+        }
+        let Some(secret) = &request.bearer else {
+            return Some(HttpResponse::error(401, "missing bearer token"));
+        };
+        match self.tokens.authorize(secret, Action::ReadMetrics) {
+            Ok(_) => None,
+            Err(err @ AccessError::UnknownToken) => {
+                Some(HttpResponse::error(401, &err.to_string()))
+            }
+            Err(err @ AccessError::Forbidden { .. }) => {
+                Some(HttpResponse::error(403, &err.to_string()))
+            }
+        }
+    }
+
+    fn fetch(&self, request: MetricsToFetch) -> Metrics {
+        self.metrics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .fetch(request)
+    }
+
+    fn history(&self, request: &HttpRequest) -> HttpResponse {
+        let Some(metric) = request.query.get("metric") else {
+            return HttpResponse::error(400, "missing `metric`, one of cpu, memory or swap");
+        };
+        let range = match request.query.get("range").map(|range| parse_range(range)) {
+            Some(Ok(range)) => range,
+            Some(Err(err)) => return HttpResponse::error(400, &err.to_string()),
+            None => chrono::Duration::minutes(10),
+        };
+        let history = self.history.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(series) = history.series(metric) else {
+            return HttpResponse::error(
+                400,
+                &format!("unknown metric `{metric}`, expected cpu, memory or swap"),
+            );
+        };
+        HttpResponse::json(&HistoryResponse {
+            metric,
+            unit: "percent",
+            samples: series.since(Utc::now() - range).copied().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::{ApiToken, Scope};
+
+    fn request(raw: &str) -> HttpRequest {
+        HttpRequest::read(&mut raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_requests_parse_path_query_and_bearer() {
+        let parsed = request(
+            "GET /api/v1/history?metric=cpu&range=10m HTTP/1.1\r\nHost: x\r\nauthorization: Bearer s3cret\r\n\r\n",
+        );
+        assert_eq!(parsed.method, "GET");
+        assert_eq!(parsed.path, "/api/v1/history");
+        assert_eq!(parsed.query["metric"], "cpu");
+        assert_eq!(parsed.query["range"], "10m");
+        assert_eq!(parsed.bearer.as_deref(), Some("s3cret"));
+        assert_eq!(parsed.encoding, None);
+        assert!(HttpRequest::read(&mut "\r\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_accept_encoding_enables_gzip() {
+        assert!(accepts("gzip", "gzip"));
+        assert!(accepts("br, GZIP;q=0.8", "gzip"));
+        assert!(accepts("*", "gzip"));
+        assert!(!accepts("br, deflate", "gzip"));
+        assert!(!accepts("gzip;q=0", "gzip"));
+        assert_eq!(
+            request("GET / HTTP/1.1\r\nAccept-Encoding: gzip, deflate\r\n\r\n").encoding,
+            Some(ContentEncoding::Gzip)
+        );
+    }
+
+    #[test]
+    fn test_accept_encoding_prefers_zstd() {
+        use ContentEncoding::{Gzip, Zstd};
+
+        assert_eq!(ContentEncoding::negotiate("gzip, zstd"), Some(Zstd));
+        assert_eq!(ContentEncoding::negotiate("*"), Some(Zstd));
+        assert_eq!(ContentEncoding::negotiate("zstd;q=0, gzip"), Some(Gzip));
+        assert_eq!(ContentEncoding::negotiate("br"), None);
+    }
+
+    #[test]
+    fn test_large_bodies_are_gzipped_when_accepted() {
+        use flate2::read::GzDecoder;
+
+        let response = HttpResponse {
+            status: 200,
+            body: format!("[{}0]", "0,".repeat(API_COMPRESS_MIN_BYTES)),
+        };
+        let mut plain = Vec::new();
+        response.write(&mut plain, None).unwrap();
+        let mut compressed = Vec::new();
+        response
+            .write(&mut compressed, Some(ContentEncoding::Gzip))
+            .unwrap();
+
+        let split = |raw: &[u8]| {
+            let end = raw.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+            (
+                String::from_utf8(raw[..end].to_vec()).unwrap(),
+                raw[end + 4..].to_vec(),
+            )
+        };
+        let (head, body) = split(&compressed);
+        assert!(head.contains("Content-Encoding: gzip"), "{head}");
+        assert!(head.contains(&format!("Content-Length: {}", body.len())));
+        assert!(body.len() < response.body.len() / 10);
+        let mut decoded = String::new();
+        GzDecoder::new(body.as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, response.body);
+        assert!(!split(&plain).0.contains("Content-Encoding"));
+
+        // the gzip framing isn't worth it for short errors
+        let mut short = Vec::new();
+        HttpResponse::error(404, "unknown endpoint")
+            .write(&mut short, Some(ContentEncoding::Gzip))
+            .unwrap();
+        assert!(!split(&short).0.contains("Content-Encoding"));
+    }
+
+    #[test]
+    fn test_large_bodies_are_zstd_compressed_when_accepted() {
+        let response = HttpResponse {
+            status: 200,
+            body: format!("[{}0]", "0,".repeat(API_COMPRESS_MIN_BYTES)),
+        };
+        let mut compressed = Vec::new();
+        response
+            .write(&mut compressed, Some(ContentEncoding::Zstd))
+            .unwrap();
+
+        let end = compressed
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .unwrap();
+        let head = String::from_utf8_lossy(&compressed[..end]);
+        let body = &compressed[end + 4..];
+        assert!(head.contains("Content-Encoding: zstd"), "{head}");
+        assert!(head.contains(&format!("Content-Length: {}", body.len())));
+        assert_eq!(zstd::decode_all(body).unwrap(), response.body.as_bytes());
+    }
+
+    #[test]
+    fn test_requests_without_newline_stop_at_the_limit() {
+        let endless = "a".repeat(API_MAX_REQUEST_BYTES * 4);
+        let err = HttpRequest::read(&mut endless.as_bytes()).unwrap_err();
+        assert!(matches!(err, RequestError::TooLarge));
+        assert_eq!(err.status(), 413);
+
+        let many_headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X-Filler: yes\r\n".repeat(API_MAX_REQUEST_BYTES / 8)
+        );
+        assert_eq!(
+            HttpRequest::read(&mut many_headers.as_bytes())
+                .unwrap_err()
+                .status(),
+            413
+        );
+        assert_eq!(
+            HttpRequest::read(&mut "\r\n".as_bytes())
+                .unwrap_err()
+                .status(),
+            400
+        );
+    }
+
+    #[test]
+    fn test_connections_past_the_limit_get_503() {
+        let server = ApiServer::bind("127.0.0.1:0", TokenStore::default())
+            .unwrap()
+            .with_max_connections(1);
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.serve());
+
+        // holds the only slot by never sending its request
+        let _idle = TcpStream::connect(address).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let mut refused = TcpStream::connect(address).unwrap();
+        refused
+            .write_all(b"GET /api/v1/system HTTP/1.1\r\n\r\n")
+            .unwrap();
+        let mut answer = String::new();
+        refused.read_to_string(&mut answer).unwrap();
+        assert!(answer.starts_with("HTTP/1.1 503"), "{answer}");
+    }
+
+    #[test]
+    fn test_endpoints_answer_with_json() {
+        let server = ApiServer::bind("127.0.0.1:0", TokenStore::default()).unwrap();
+        let response = server.respond(&request("GET /api/v1/system HTTP/1.1\r\n\r\n"));
+        assert_eq!(response.status, 200);
+        let body: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert!(body["metrics"]["memory_total"].is_u64());
+
+        let history = server.respond(&request(
+            "GET /api/v1/history?metric=cpu&range=1m HTTP/1.1\r\n\r\n",
+        ));
+        assert_eq!(history.status, 200);
+        assert!(history.body.contains(r#""unit":"percent""#));
+
+        let status = |raw: &str| server.respond(&request(raw)).status;
+        assert_eq!(
+            status("GET /api/v1/history?metric=disk HTTP/1.1\r\n\r\n"),
+            400
+        );
+        assert_eq!(
+            status("GET /api/v1/history?metric=cpu&range=1x HTTP/1.1\r\n\r\n"),
+            400
+        );
+        assert_eq!(status("GET /api/v2/system HTTP/1.1\r\n\r\n"), 404);
+        assert_eq!(status("POST /api/v1/system HTTP/1.1\r\n\r\n"), 405);
+    }
+
+    #[test]
+    fn test_servers_without_tokens_stay_on_loopback() {
+        assert!(ApiServer::bind("0.0.0.0:0", TokenStore::default()).is_err());
+        assert!(ApiServer::bind("127.0.0.1:0", TokenStore::default()).is_ok());
+        let tokens = TokenStore::new(vec![ApiToken {
+            name: "grafana".to_string(),
+            secret: "read-secret".to_string(),
+            scope: Scope::Read,
+        }]);
+        assert!(ApiServer::bind("0.0.0.0:0", tokens).is_ok());
+    }
+
+    #[test]
+    fn test_configured_tokens_are_required() {
+        let tokens = TokenStore::new(vec![ApiToken {
+            name: "grafana".to_string(),
+            secret: "read-secret".to_string(),
+            scope: Scope::Read,
+        }]);
+        let server = ApiServer::bind("127.0.0.1:0", tokens).unwrap();
+        let status = |raw: &str| server.respond(&request(raw)).status;
+        assert_eq!(status("GET /api/v1/networks HTTP/1.1\r\n\r\n"), 401);
+        assert_eq!(
+            status("GET /api/v1/networks HTTP/1.1\r\nAuthorization: Bearer wrong\r\n\r\n"),
+            401
+        );
+        assert_eq!(
+            status("GET /api/v1/networks HTTP/1.1\r\nAuthorization: Bearer read-secret\r\n\r\n"),
+            200
+        );
+    }
+}
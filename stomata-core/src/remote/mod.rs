@@ -0,0 +1,346 @@
+//! Serving metrics to another machine
+//!
+//! An [`Agent`] runs the collectors headlessly and answers fetch requests
+//! over TCP; a [`RemoteMetrics`] client sends them, so a TUI on one machine
+//! can show the metrics of another. A [`RemoteHost`] polls a client on
+//! background threads, so the TUI never waits on a slow agent.
+//!
+//! Every message is a frame: a 4-byte big-endian length followed by that
+//! many bytes of JSON. The client opens with [`Request::Hello`] carrying
+//! its token, the agent answers with [`Response::Hello`] or
+//! [`Response::Refused`]. A client setting `compress` in its hello gets the
+//! agent's larger frames zstd-compressed, marked by the highest bit of the
+//! length, which pays off over slow links. Then each [`Request::Fetch`]
+//! gets one [`Response::Metrics`] or [`Response::Unsupported`]. Only the
+//! requests of [`MetricsToFetch::is_remote`] are answered. A
+//! [`Request::Signal`] needs a token with [`Scope::Control`] and gets
+//! [`Response::Done`], [`Response::Failed`] or [`Response::Refused`].
+//!
+//! [`ApiServer`] serves a read-only subset of the same data as JSON over
+//! HTTP, for dashboards and scripts rather than the TUI.
+//!
+//! [`Scope::Control`]: crate::access::Scope::Control
+
+pub mod agent;
+pub mod client;
+pub mod host;
+pub mod http;
+
+pub use agent::Agent;
+pub use client::RemoteMetrics;
+pub use host::RemoteHost;
+pub use http::ApiServer;
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::TcpStream,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    collectors::{Metrics, MetricsToFetch, process::actions::Signal},
+    constants::{REFUSE_DRAIN_MS, REMOTE_COMPRESS_MIN_BYTES, REMOTE_MAX_FRAME_BYTES},
+    errors::{CoreError, CoreResult},
+};
+
+/// Message from a client to an agent
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Request {
+    /// First message of a connection
+    Hello {
+        /// [`REMOTE_PROTOCOL_VERSION`](crate::constants::REMOTE_PROTOCOL_VERSION)
+        /// of the client
+        version: u32,
+        /// Secret of the client's token, checked by the agent's
+        /// [`TokenStore`](crate::access::TokenStore)
+        token: Option<String>,
+        /// Asks for the agent's answers as compressed frames, missing from
+        /// older clients
+        #[serde(default)]
+        compress: bool,
+    },
+    /// Asks for one [`Metrics`] answer
+    Fetch(MetricsToFetch),
+    /// Sends a signal to a process of the agent's machine
+    Signal {
+        /// Process the signal is sent to
+        pid: u32,
+        /// Signal to send
+        signal: Signal,
+    },
+}
+
+/// Message from an agent to a client
+#[derive(Serialize, Deserialize)]
+pub enum Response {
+    /// Answer to [`Request::Hello`]
+    Hello {
+        /// Protocol version of the agent
+        version: u32,
+        /// Host name of the agent's machine
+        hostname: String,
+        /// Whether the agent compresses its answers, missing from older
+        /// agents that never do
+        #[serde(default)]
+        compress: bool,
+    },
+    /// Answer to a [`Request::Fetch`]
+    Metrics(Metrics),
+    /// The request can't be answered, with the reason
+    Unsupported(String),
+    /// The client isn't allowed to make the request, with the reason
+    Refused(String),
+    /// The action of the request was carried out
+    Done,
+    /// The action of the request failed, with the reason
+    Failed(String),
+}
+
+/// Counts the connections a server is serving, to turn clients away once
+/// `limit` of them are
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectionSlots {
+    active: Arc<AtomicUsize>,
+    limit: usize,
+}
+
+impl ConnectionSlots {
+    /// Slots for at most `limit` connections at once
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            active: Arc::new(AtomicUsize::new(0)),
+            limit,
+        }
+    }
+
+    /// Takes a slot, `None` when all are taken. The slot is given back
+    /// when the returned guard is dropped.
+    pub(crate) fn acquire(&self) -> Option<ConnectionSlot> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < self.limit).then_some(active + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot(Arc::clone(&self.active)))
+    }
+}
+
+/// A taken [`ConnectionSlots`] slot, given back on drop
+#[derive(Debug)]
+pub(crate) struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Reads what the peer of a refused connection already sent, for at most
+/// [`REFUSE_DRAIN_MS`]. Closing a socket with unread input resets it, and
+/// the peer would lose the refusal written just before.
+pub(crate) fn drain(stream: &mut TcpStream) {
+    if stream
+        .set_read_timeout(Some(Duration::from_millis(REFUSE_DRAIN_MS)))
+        .is_ok()
+    {
+        let _ = stream.read(&mut [0; 4096]);
+    }
+}
+
+/// Bit of a frame length marking a zstd-compressed body, far above
+/// [`REMOTE_MAX_FRAME_BYTES`]
+const COMPRESSED_FRAME: u32 = 1 << 31;
+
+/// Writes `message` as one frame
+///
+/// # Errors
+///
+/// Returns an error if the message is larger than a frame may be or the
+/// write fails.
+pub fn write_frame(writer: &mut impl Write, message: &impl Serialize) -> CoreResult<()> {
+    write_frame_with(writer, message, false)
+}
+
+/// Writes `message` as one frame, zstd-compressed if it is at least
+/// [`REMOTE_COMPRESS_MIN_BYTES`] long. Only peers that asked for it in
+/// their hello may be sent one.
+///
+/// # Errors
+///
+/// Returns an error if the message is larger than a frame may be or
+/// compressing or the write fails.
+pub fn write_compressed_frame(writer: &mut impl Write, message: &impl Serialize) -> CoreResult<()> {
+    write_frame_with(writer, message, true)
+}
+
+fn write_frame_with(
+    writer: &mut impl Write,
+    message: &impl Serialize,
+    compress: bool,
+) -> CoreResult<()> {
+    let mut body = serde_json::to_vec(message)
+        .map_err(|err| CoreError::invalid(format!("failed to encode a frame: {err}")))?;
+    if body.len() > REMOTE_MAX_FRAME_BYTES {
+        return Err(CoreError::invalid(format!(
+            "frame of {} bytes exceeds the limit of {REMOTE_MAX_FRAME_BYTES}",
+            body.len()
+        )));
+    }
+    let mut length = body.len() as u32;
+    if compress && body.len() >= REMOTE_COMPRESS_MIN_BYTES {
+        body = zstd::encode_all(body.as_slice(), 0)
+            .map_err(|err| CoreError::io("failed to compress a frame", err))?;
+        length = body.len() as u32 | COMPRESSED_FRAME;
+    }
+    writer
+        .write_all(&length.to_be_bytes())
+        .and_then(|_| writer.write_all(&body))
+        .and_then(|_| writer.flush())
+        .map_err(|err| CoreError::io("failed to write a frame", err))
+}
+
+/// Reads one frame, compressed or not, `None` when the peer closed the
+/// connection between frames
+///
+/// # Errors
+///
+/// Returns an error if the read fails, the frame is too large or its JSON
+/// doesn't decode to `T`.
+pub fn read_frame<T: DeserializeOwned>(reader: &mut impl Read) -> CoreResult<Option<T>> {
+    let mut length = [0; 4];
+    match reader.read_exact(&mut length) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(CoreError::io("failed to read a frame", err)),
+    }
+    let length = u32::from_be_bytes(length);
+    let compressed = length & COMPRESSED_FRAME != 0;
+    let length = (length & !COMPRESSED_FRAME) as usize;
+    if length > REMOTE_MAX_FRAME_BYTES {
+        return Err(CoreError::collection(format!(
+            "frame of {length} bytes exceeds the limit of {REMOTE_MAX_FRAME_BYTES}"
+        )));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| CoreError::io("failed to read a frame", err))?;
+    if compressed {
+        body = decompress(&body)?;
+    }
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|err| CoreError::collection(format!("invalid frame: {err}")))
+}
+
+/// Decompresses a frame body, stopping at [`REMOTE_MAX_FRAME_BYTES`] so
+/// a small frame can't expand to gigabytes
+fn decompress(body: &[u8]) -> CoreResult<Vec<u8>> {
+    let mut decoded = Vec::new();
+    zstd::Decoder::new(body)
+        .and_then(|decoder| {
+            decoder
+                .take(REMOTE_MAX_FRAME_BYTES as u64 + 1)
+                .read_to_end(&mut decoded)
+        })
+        .map_err(|err| CoreError::io("failed to decompress a frame", err))?;
+    if decoded.len() > REMOTE_MAX_FRAME_BYTES {
+        return Err(CoreError::collection(format!(
+            "frame exceeds the limit of {REMOTE_MAX_FRAME_BYTES} bytes once decompressed"
+        )));
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_round_trip() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, &Request::Fetch(MetricsToFetch::Networks)).unwrap();
+        write_frame(
+            &mut buffer,
+            &Request::Hello {
+                version: 1,
+                token: None,
+                compress: false,
+            },
+        )
+        .unwrap();
+        let first_length = u32::from_be_bytes(buffer[..4].try_into().unwrap()) as usize;
+        assert_eq!(&buffer[4..4 + first_length], br#"{"Fetch":"Networks"}"#);
+
+        let mut reader = buffer.as_slice();
+        let first: Request = read_frame(&mut reader).unwrap().unwrap();
+        assert!(matches!(first, Request::Fetch(MetricsToFetch::Networks)));
+        let second: Request = read_frame(&mut reader).unwrap().unwrap();
+        assert!(matches!(second, Request::Hello { version: 1, .. }));
+        assert!(read_frame::<Request>(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_large_frames_are_compressed_on_request() {
+        let message = Response::Unsupported("x".repeat(REMOTE_COMPRESS_MIN_BYTES * 8));
+        let mut buffer = Vec::new();
+        write_compressed_frame(&mut buffer, &message).unwrap();
+        let length = u32::from_be_bytes(buffer[..4].try_into().unwrap());
+        assert_ne!(length & COMPRESSED_FRAME, 0);
+        assert!(buffer.len() < REMOTE_COMPRESS_MIN_BYTES);
+
+        let mut reader = buffer.as_slice();
+        let decoded: Response = read_frame(&mut reader).unwrap().unwrap();
+        assert!(
+            matches!(decoded, Response::Unsupported(reason) if reason.len() == REMOTE_COMPRESS_MIN_BYTES * 8)
+        );
+
+        // short frames go out as they are
+        let mut short = Vec::new();
+        write_compressed_frame(&mut short, &Response::Done).unwrap();
+        assert_eq!(&short[4..], br#""Done""#);
+    }
+
+    #[test]
+    fn test_hellos_of_older_peers_do_not_compress() {
+        let hello: Request =
+            serde_json::from_str(r#"{"Hello":{"version":2,"token":null}}"#).unwrap();
+        assert!(matches!(
+            hello,
+            Request::Hello {
+                compress: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_oversized_frames_are_rejected() {
+        let mut reader: &[u8] = &u32::MAX.to_be_bytes();
+        assert!(read_frame::<Request>(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_connection_slots_are_given_back_on_drop() {
+        let slots = ConnectionSlots::new(2);
+        let first = slots.acquire().unwrap();
+        let _second = slots.acquire().unwrap();
+        assert!(slots.acquire().is_none());
+        drop(first);
+        assert!(slots.acquire().is_some());
+    }
+
+    #[test]
+    fn test_local_only_metrics_fail_to_encode() {
+        let mut buffer = Vec::new();
+        let response = Response::Metrics(Metrics::Sessions(Default::default()));
+        assert!(write_frame(&mut buffer, &response).is_err());
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Idle detection and write throttling for history storage
+//!
+//! The machine counts as idle once there was no user input and CPU usage
+//! stayed flat for a while. While idle, the `WriteThrottle` lets a sample
+//! through only every `idle_interval`; the first write after the idle
+//! period ends is let through right away and carries the gap, so readers
+//! of the history know the sparse stretch was quiet rather than missing.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::constants::{IDLE_AFTER_SECS, IDLE_FLAT_CPU_DELTA, IDLE_WRITE_INTERVAL_SECS};
+
+/// Detects periods without user input and with flat CPU usage
+#[derive(Debug, Clone)]
+pub struct IdleDetector {
+    /// How long input and metrics must be quiet before the machine is idle
+    pub idle_after: Duration,
+    /// CPU usage change, in percentage points, that counts as activity
+    pub flat_cpu_delta: f32,
+    last_input: Instant,
+    last_change: Instant,
+    /// CPU usage at the last change, what later samples are compared to
+    baseline_cpu: Option<f32>,
+}
+
+/// A quiet period that was stored sparsely
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdleGap {
+    /// When the machine went idle
+    pub from: DateTime<Utc>,
+    /// When activity resumed
+    pub to: DateTime<Utc>,
+}
+
+/// A sample the throttle lets through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageWrite {
+    /// The idle period that just ended, set on the first write after it
+    pub gap: Option<IdleGap>,
+}
+
+/// Decides which samples are written, sparsely while idle
+#[derive(Debug, Clone)]
+pub struct WriteThrottle {
+    /// Time between writes while active, usually the refresh interval
+    pub interval: Duration,
+    /// Time between writes while idle
+    pub idle_interval: Duration,
+    last_write: Option<Instant>,
+    idle_since: Option<DateTime<Utc>>,
+}
+
+impl Default for IdleDetector {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(IDLE_AFTER_SECS), IDLE_FLAT_CPU_DELTA)
+    }
+}
+
+impl IdleDetector {
+    /// A detector going idle after `idle_after` without input and with CPU
+    /// usage moving less than `flat_cpu_delta` percentage points
+    pub fn new(idle_after: Duration, flat_cpu_delta: f32) -> Self {
+        let now = Instant::now();
+        Self {
+            idle_after,
+            flat_cpu_delta,
+            last_input: now,
+            last_change: now,
+            baseline_cpu: None,
+        }
+    }
+
+    /// Records a key press or other user input at `now`
+    pub fn record_input(&mut self, now: Instant) {
+        self.last_input = now;
+    }
+
+    /// Records the CPU usage sampled at `now`; a change of more than
+    /// `flat_cpu_delta` from the last change counts as activity
+    pub fn observe(&mut self, cpu_usage: f32, now: Instant) {
+        let changed = self
+            .baseline_cpu
+            .is_none_or(|baseline| (cpu_usage - baseline).abs() > self.flat_cpu_delta);
+        if changed {
+            self.baseline_cpu = Some(cpu_usage);
+            self.last_change = now;
+        }
+    }
+
+    /// Whether input and CPU usage have been quiet for `idle_after`
+    pub fn is_idle(&self, now: Instant) -> bool {
+        let last_activity = self.last_input.max(self.last_change);
+        now.saturating_duration_since(last_activity) >= self.idle_after
+    }
+}
+
+impl WriteThrottle {
+    /// Throttle writing every `interval` while active and every
+    /// `IDLE_WRITE_INTERVAL_SECS` while idle
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            idle_interval: Duration::from_secs(IDLE_WRITE_INTERVAL_SECS),
+            last_write: None,
+            idle_since: None,
+        }
+    }
+
+    /// Whether the sample taken at `now` (`at` on the wall clock) should
+    /// be written, given whether the machine is `idle`
+    pub fn poll(&mut self, idle: bool, now: Instant, at: DateTime<Utc>) -> Option<StorageWrite> {
+        let mut gap = None;
+        match (idle, self.idle_since) {
+            (true, None) => self.idle_since = Some(at),
+            (false, Some(from)) => {
+                gap = Some(IdleGap { from, to: at });
+                self.idle_since = None;
+            }
+            _ => {}
+        }
+
+        let interval = if idle {
+            self.idle_interval
+        } else {
+            self.interval
+        };
+        let due = self
+            .last_write
+            .is_none_or(|last_write| now.saturating_duration_since(last_write) >= interval);
+        if !due && gap.is_none() {
+            return None;
+        }
+        self.last_write = Some(now);
+        Some(StorageWrite { gap })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_detector() {
+        let start = Instant::now();
+        let mut detector = IdleDetector::new(Duration::from_secs(60), 5.0);
+        detector.observe(10.0, start);
+        detector.observe(12.0, start + Duration::from_secs(30));
+        assert!(!detector.is_idle(start + Duration::from_secs(59)));
+        assert!(detector.is_idle(start + Duration::from_secs(61)));
+
+        // a jump in CPU usage is activity
+        detector.observe(40.0, start + Duration::from_secs(70));
+        assert!(!detector.is_idle(start + Duration::from_secs(100)));
+
+        detector.record_input(start + Duration::from_secs(140));
+        assert!(!detector.is_idle(start + Duration::from_secs(180)));
+        assert!(detector.is_idle(start + Duration::from_secs(200)));
+    }
+
+    #[test]
+    fn test_write_throttle_annotates_gap() {
+        let start = Instant::now();
+        let at = |secs: i64| DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap();
+        let mut throttle = WriteThrottle::new(Duration::from_secs(1));
+        throttle.idle_interval = Duration::from_secs(60);
+        let poll = |throttle: &mut WriteThrottle, idle, secs: u64| {
+            throttle.poll(idle, start + Duration::from_secs(secs), at(secs as i64))
+        };
+
+        assert_eq!(
+            poll(&mut throttle, false, 0),
+            Some(StorageWrite { gap: None })
+        );
+        assert!(poll(&mut throttle, false, 1).is_some());
+        // idle: only every idle_interval
+        assert!(poll(&mut throttle, true, 2).is_none());
+        assert!(poll(&mut throttle, true, 30).is_none());
+        assert!(poll(&mut throttle, true, 61).is_some());
+        assert!(poll(&mut throttle, true, 62).is_none());
+        // activity resumes: written right away with the gap
+        assert_eq!(
+            poll(&mut throttle, false, 63),
+            Some(StorageWrite {
+                gap: Some(IdleGap {
+                    from: at(2),
+                    to: at(63)
+                })
+            })
+        );
+        assert!(poll(&mut throttle, false, 64).is_some());
+    }
+}
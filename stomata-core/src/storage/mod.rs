@@ -0,0 +1,22 @@
+//! Helpers for persisting metrics history
+//!
+//! Long-running history stores grow with every sample, most of them taken
+//! while nothing happens. The `idle` module detects those periods so the
+//! writer can sample them sparsely and mark the gap instead. The
+//! `snapshot` module writes periodic snapshots to a directory and rotates
+//! them, for long-term trends without a database. [`MetricStore`] keeps the
+//! history in SQLite when the TUI runs with `--store`, fed by a
+//! [`HistoryRecorder`], and rolls old samples into coarser averages by the
+//! ages of a [`RetentionPolicy`].
+
+pub mod idle;
+pub mod recorder;
+pub mod retention;
+pub mod snapshot;
+pub mod store;
+
+pub use idle::{IdleDetector, IdleGap, StorageWrite, WriteThrottle};
+pub use recorder::HistoryRecorder;
+pub use retention::{CompactionReport, RetentionPolicy};
+pub use snapshot::{Snapshot, SnapshotDir};
+pub use store::{MetricStore, RangeStats};
@@ -0,0 +1,187 @@
+//! Feeding the metric store while the TUI runs
+//!
+//! [`HistoryRecorder`] owns its own CPU, memory and network collectors, so
+//! the history is recorded whichever page is open. It writes `cpu`,
+//! `memory` and `swap` usage in percent and the `network_rx` and
+//! `network_tx` rates in bytes per second, summed over the interfaces,
+//! through a [`WriteThrottle`] so idle stretches stay small on disk. Every
+//! `STORE_COMPACT_INTERVAL_SECS` it also compacts the store by its
+//! [`RetentionPolicy`], the first time one interval after it was created.
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+use crate::{
+    collectors::{CollectorRegistry, Metric},
+    constants::STORE_COMPACT_INTERVAL_SECS,
+    errors::CoreResult,
+    storage::{IdleDetector, MetricStore, RetentionPolicy, WriteThrottle},
+};
+
+/// Samples the system every interval and writes it to a [`MetricStore`]
+#[derive(Debug)]
+pub struct HistoryRecorder {
+    store: MetricStore,
+    registry: CollectorRegistry,
+    idle: IdleDetector,
+    throttle: WriteThrottle,
+    sampled_at: Option<Instant>,
+    retention: RetentionPolicy,
+    /// Creation time until the first compaction, so starting the TUI
+    /// doesn't wait for one
+    compacted_at: Instant,
+}
+
+impl HistoryRecorder {
+    /// Records to `store` every `interval` while the machine is active
+    pub fn new(store: MetricStore, interval: Duration) -> Self {
+        let mut registry = CollectorRegistry::with_builtin();
+        registry.retain(&["cpu", "memory", "network"]);
+        Self {
+            store,
+            registry,
+            idle: IdleDetector::default(),
+            throttle: WriteThrottle::new(interval),
+            sampled_at: None,
+            retention: RetentionPolicy::default(),
+            compacted_at: Instant::now(),
+        }
+    }
+
+    /// Compacts the store by `retention` instead of the default ages
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// The store written to, for the pages reading the history
+    pub fn store(&self) -> &MetricStore {
+        &self.store
+    }
+
+    /// Records a key press, which keeps the machine from counting as idle
+    pub fn record_input(&mut self) {
+        self.idle.record_input(Instant::now());
+    }
+
+    /// Samples the system when the interval has passed and writes the
+    /// sample unless the throttle skips it, compacting the store first when
+    /// due. Calls in between do nothing, so this can run on every frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a collector fails or the store can't be
+    /// written.
+    pub fn tick(&mut self) -> CoreResult<()> {
+        let now = Instant::now();
+        if self
+            .sampled_at
+            .is_some_and(|at| now.saturating_duration_since(at) < self.throttle.interval)
+        {
+            return Ok(());
+        }
+        self.sampled_at = Some(now);
+
+        if now.saturating_duration_since(self.compacted_at)
+            >= Duration::from_secs(STORE_COMPACT_INTERVAL_SECS)
+        {
+            self.compacted_at = now;
+            self.store.compact(&self.retention, Utc::now())?;
+        }
+
+        let mut values = Vec::new();
+        for (_, result) in self.registry.collect_all() {
+            values.extend(recorded_values(&result?));
+        }
+        if let Some((_, cpu)) = values.iter().find(|(name, _)| *name == "cpu") {
+            self.idle.observe(*cpu as f32, now);
+        }
+
+        let at = Utc::now();
+        let Some(write) = self.throttle.poll(self.idle.is_idle(now), now, at) else {
+            return Ok(());
+        };
+        if let Some(gap) = write.gap {
+            self.store.insert_gap(gap)?;
+        }
+        self.store.insert(at, &values)
+    }
+}
+
+/// The stored values of one collector reading
+fn recorded_values(metric: &Metric) -> Vec<(&'static str, f64)> {
+    let value = |name: &str| {
+        metric
+            .values
+            .iter()
+            .find(|value| value.instance.is_none() && value.name == name)
+            .map(|value| value.value)
+    };
+    let sum = |name: &str| {
+        metric
+            .values
+            .iter()
+            .filter(|value| value.name == name)
+            .map(|value| value.value)
+            .sum::<f64>()
+    };
+    match metric.collector {
+        "cpu" => value("usage")
+            .map(|usage| ("cpu", usage))
+            .into_iter()
+            .collect(),
+        "memory" => {
+            let mut values: Vec<(&'static str, f64)> = value("used_percent")
+                .map(|used| ("memory", used))
+                .into_iter()
+                .collect();
+            if let (Some(used), Some(total)) = (value("swap_used"), value("swap_total")) {
+                let percent = if total > 0.0 {
+                    used / total * 100.0
+                } else {
+                    0.0
+                };
+                values.push(("swap", percent));
+            }
+            values
+        }
+        "network" => vec![
+            ("network_rx", sum("rx_bytes_per_sec")),
+            ("network_tx", sum("tx_bytes_per_sec")),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::MetricValue;
+
+    #[test]
+    fn test_recorded_values_sum_interfaces() {
+        let network = Metric::new(
+            "network",
+            vec![
+                MetricValue::new("rx_bytes_per_sec", 100.0).instance("eth0"),
+                MetricValue::new("rx_bytes_per_sec", 50.0).instance("wlan0"),
+                MetricValue::new("tx_bytes_per_sec", 10.0).instance("eth0"),
+            ],
+        );
+        assert_eq!(
+            recorded_values(&network),
+            [("network_rx", 150.0), ("network_tx", 10.0)]
+        );
+
+        let memory = Metric::new(
+            "memory",
+            vec![
+                MetricValue::new("used_percent", 40.0),
+                MetricValue::new("swap_used", 0.0),
+                MetricValue::new("swap_total", 0.0),
+            ],
+        );
+        assert_eq!(recorded_values(&memory), [("memory", 40.0), ("swap", 0.0)]);
+    }
+}
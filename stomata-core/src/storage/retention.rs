@@ -0,0 +1,89 @@
+//! How long the metric store keeps each resolution
+//!
+//! Raw samples are rolled into one-minute averages once older than
+//! `raw_for`, minute averages into one-hour averages once older than
+//! `minute_for`, and hour averages are deleted after `hour_for`. The
+//! rollups keep the lowest and highest value of their period, so the
+//! min/max of a range stay right after the raw samples are gone.
+
+use chrono::Duration;
+
+use crate::{
+    constants::{
+        STORE_HOUR_RETENTION_DAYS, STORE_MINUTE_RETENTION_DAYS, STORE_RAW_RETENTION_HOURS,
+    },
+    errors::{CoreError, CoreResult},
+};
+
+/// Ages at which the store rolls up and deletes samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// How long raw samples are kept before they become minute averages
+    pub raw_for: Duration,
+    /// How long minute averages are kept before they become hour averages
+    pub minute_for: Duration,
+    /// How long hour averages are kept before they are deleted
+    pub hour_for: Duration,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            raw_for: Duration::hours(STORE_RAW_RETENTION_HOURS),
+            minute_for: Duration::days(STORE_MINUTE_RETENTION_DAYS),
+            hour_for: Duration::days(STORE_HOUR_RETENTION_DAYS),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Checks that every age is positive and longer than the one of the
+    /// finer resolution
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::InvalidInput`] naming the first age out of
+    /// order.
+    pub fn validate(&self) -> CoreResult<()> {
+        if self.raw_for <= Duration::zero() {
+            return Err(CoreError::invalid("raw samples must be kept for some time"));
+        }
+        if self.minute_for <= self.raw_for {
+            return Err(CoreError::invalid(
+                "minute averages must be kept longer than raw samples",
+            ));
+        }
+        if self.hour_for <= self.minute_for {
+            return Err(CoreError::invalid(
+                "hour averages must be kept longer than minute averages",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Rows changed by one compaction of the store
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionReport {
+    /// Raw samples rolled into minute averages
+    pub raw_rolled: usize,
+    /// Minute averages rolled into hour averages
+    pub minutes_rolled: usize,
+    /// Hour averages and idle gaps deleted for their age
+    pub expired: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rejects_ages_out_of_order() {
+        assert!(RetentionPolicy::default().validate().is_ok());
+        let policy = RetentionPolicy {
+            minute_for: Duration::hours(1),
+            ..RetentionPolicy::default()
+        };
+        assert!(policy.validate().is_err());
+    }
+}
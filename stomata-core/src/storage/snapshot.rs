@@ -0,0 +1,303 @@
+//! Periodic snapshots written to a directory with rotation
+//!
+//! Each [`Snapshot`] is one JSON file named after the time it was taken,
+//! `snapshot-20250101T120000Z.json`, so the names sort chronologically and
+//! rotation only has to drop the first ones. Files are written next to
+//! their final name and renamed into place, so readers never see a
+//! partial snapshot. With [`SnapshotDir::with_gzip`] they are written
+//! gzip-compressed as `.json.gz`, with [`SnapshotDir::with_zstd`]
+//! zstd-compressed as `.json.zst`, a fraction of the size for long
+//! retention.
+
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use flate2::{Compression, write::GzEncoder};
+use serde::Serialize;
+
+use crate::{
+    collectors::Metric,
+    constants::SNAPSHOT_FILE_PREFIX,
+    errors::{CoreError, CoreResult},
+};
+
+/// The values of the registered collectors at one point in time
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    /// When the snapshot was taken
+    pub taken_at: DateTime<Utc>,
+    /// Host the values were read on
+    pub hostname: String,
+    /// One entry per collector that succeeded
+    pub metrics: Vec<Metric>,
+}
+
+impl Snapshot {
+    /// Snapshot of `metrics` read on `hostname`, timestamped now
+    pub fn new(hostname: impl Into<String>, metrics: Vec<Metric>) -> Self {
+        Self {
+            taken_at: Utc::now(),
+            hostname: hostname.into(),
+            metrics,
+        }
+    }
+
+    /// Name of the file the snapshot is stored in
+    pub fn file_name(&self) -> String {
+        format!(
+            "{SNAPSHOT_FILE_PREFIX}{}.json",
+            self.taken_at.format("%Y%m%dT%H%M%SZ")
+        )
+    }
+}
+
+/// Directory of snapshots keeping only the latest ones
+#[derive(Debug, Clone)]
+pub struct SnapshotDir {
+    dir: PathBuf,
+    keep: usize,
+    compression: Option<SnapshotCompression>,
+}
+
+/// Compression of the snapshot files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SnapshotCompression {
+    Gzip,
+    Zstd,
+}
+
+impl SnapshotCompression {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => ".gz",
+            Self::Zstd => ".zst",
+        }
+    }
+}
+
+impl SnapshotDir {
+    /// Snapshots in `dir`, keeping the `keep` newest, `0` keeps all
+    pub fn new(dir: impl Into<PathBuf>, keep: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            keep,
+            compression: None,
+        }
+    }
+
+    /// Writes the snapshots gzip-compressed, named `.json.gz`
+    pub fn with_gzip(mut self) -> Self {
+        self.compression = Some(SnapshotCompression::Gzip);
+        self
+    }
+
+    /// Writes the snapshots zstd-compressed, named `.json.zst`, smaller
+    /// and faster to write than gzip
+    pub fn with_zstd(mut self) -> Self {
+        self.compression = Some(SnapshotCompression::Zstd);
+        self
+    }
+
+    /// Directory the snapshots are written to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes `snapshot`, creating the directory if needed, then removes
+    /// the snapshots beyond the kept count. Returns the path written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or file can't be written or an
+    /// old snapshot can't be removed.
+    pub fn write(&self, snapshot: &Snapshot) -> CoreResult<PathBuf> {
+        fs::create_dir_all(&self.dir).map_err(|err| {
+            CoreError::io(format!("failed to create {}", self.dir.display()), err)
+        })?;
+        let mut name = snapshot.file_name();
+        if let Some(compression) = self.compression {
+            name.push_str(compression.extension());
+        }
+        let path = self.dir.join(&name);
+        let partial = self.dir.join(format!("{name}.partial"));
+        let document = serde_json::to_vec_pretty(snapshot)
+            .map_err(|err| CoreError::collection(format!("failed to encode snapshot: {err}")))?;
+        let written = match self.compression {
+            Some(SnapshotCompression::Gzip) => File::create(&partial).and_then(|file| {
+                let mut encoder = GzEncoder::new(file, Compression::default());
+                encoder.write_all(&document)?;
+                encoder.finish().map(drop)
+            }),
+            Some(SnapshotCompression::Zstd) => File::create(&partial)
+                .and_then(|file| zstd::stream::copy_encode(document.as_slice(), file, 0)),
+            None => fs::write(&partial, document),
+        };
+        written
+            .map_err(|err| CoreError::io(format!("failed to write {}", partial.display()), err))?;
+        fs::rename(&partial, &path)
+            .map_err(|err| CoreError::io(format!("failed to write {}", path.display()), err))?;
+        self.prune()?;
+        Ok(path)
+    }
+
+    /// Paths of the stored snapshots, compressed or not, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be read.
+    pub fn list(&self) -> CoreResult<Vec<PathBuf>> {
+        let entries = fs::read_dir(&self.dir)
+            .map_err(|err| CoreError::io(format!("failed to read {}", self.dir.display()), err))?;
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(SNAPSHOT_FILE_PREFIX)
+                            && [".json", ".json.gz", ".json.zst"]
+                                .iter()
+                                .any(|extension| name.ends_with(extension))
+                    })
+            })
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Removes the oldest snapshots beyond the kept count and returns
+    /// their paths
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be read or a snapshot can't
+    /// be removed.
+    pub fn prune(&self) -> CoreResult<Vec<PathBuf>> {
+        if self.keep == 0 {
+            return Ok(Vec::new());
+        }
+        let paths = self.list()?;
+        let excess = paths.len().saturating_sub(self.keep);
+        let removed: Vec<PathBuf> = paths.into_iter().take(excess).collect();
+        for path in &removed {
+            fs::remove_file(path).map_err(|err| {
+                CoreError::io(format!("failed to remove {}", path.display()), err)
+            })?;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::collectors::MetricValue;
+
+    fn snapshot_at(second: u32) -> Snapshot {
+        let mut snapshot = Snapshot::new(
+            "host",
+            vec![Metric::new("cpu", vec![MetricValue::new("usage", 12.5)])],
+        );
+        snapshot.taken_at = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, second).unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn test_file_names_sort_by_time() {
+        assert_eq!(snapshot_at(5).file_name(), "snapshot-20250101T120005Z.json");
+        assert!(snapshot_at(5).file_name() < snapshot_at(30).file_name());
+    }
+
+    #[test]
+    fn test_write_keeps_newest_snapshots() {
+        let dir = std::env::temp_dir().join(format!("stomata-snapshots-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("notes.txt"), "unrelated").unwrap();
+        let snapshots = SnapshotDir::new(&dir, 2);
+
+        for second in [1, 2, 3] {
+            snapshots.write(&snapshot_at(second)).unwrap();
+        }
+
+        let names: Vec<String> = snapshots
+            .list()
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        let notes_kept = dir.join("notes.txt").exists();
+        let document = fs::read_to_string(dir.join(&names[1])).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            names,
+            [
+                "snapshot-20250101T120002Z.json",
+                "snapshot-20250101T120003Z.json"
+            ]
+        );
+        assert!(notes_kept);
+        assert!(document.contains("\"collector\": \"cpu\""));
+    }
+
+    #[test]
+    fn test_gzip_snapshots_rotate_with_plain_ones() {
+        use std::io::Read;
+
+        use flate2::read::GzDecoder;
+
+        let dir = std::env::temp_dir().join(format!("stomata-snapshots-gz-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        SnapshotDir::new(&dir, 0).write(&snapshot_at(1)).unwrap();
+        let snapshots = SnapshotDir::new(&dir, 2).with_gzip();
+        let written = snapshots.write(&snapshot_at(2)).unwrap();
+        snapshots.write(&snapshot_at(3)).unwrap();
+
+        let names: Vec<String> = snapshots
+            .list()
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        let mut document = String::new();
+        GzDecoder::new(File::open(&written).unwrap())
+            .read_to_string(&mut document)
+            .unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            names,
+            [
+                "snapshot-20250101T120002Z.json.gz",
+                "snapshot-20250101T120003Z.json.gz"
+            ]
+        );
+        assert!(document.contains("\"collector\": \"cpu\""));
+    }
+
+    #[test]
+    fn test_zstd_snapshots_decode() {
+        let dir =
+            std::env::temp_dir().join(format!("stomata-snapshots-zst-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let snapshots = SnapshotDir::new(&dir, 0).with_zstd();
+        let written = snapshots.write(&snapshot_at(1)).unwrap();
+
+        let listed = snapshots.list().unwrap();
+        let document = zstd::decode_all(File::open(&written).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(written.to_string_lossy().ends_with(".json.zst"));
+        assert_eq!(listed, [written]);
+        assert!(
+            String::from_utf8(document)
+                .unwrap()
+                .contains("\"collector\": \"cpu\"")
+        );
+    }
+}
@@ -0,0 +1,533 @@
+//! SQLite store of the metrics history
+//!
+//! Every sample is one row of `samples` holding the metric name, the time
+//! in milliseconds since the Unix epoch and the value. Quiet periods the
+//! [`WriteThrottle`](super::WriteThrottle) sampled sparsely are kept in
+//! `idle_gaps`, so charts can tell them apart from missing data. Queries
+//! average the samples into buckets in SQL, so a day of one second samples
+//! never has to be loaded to draw a chart.
+//!
+//! [`MetricStore::compact`] keeps the database bounded: old samples move
+//! to the `samples_1m` and `samples_1h` rollups, which also hold the
+//! lowest and highest value of their period and the number of samples
+//! averaged into it. Queries read all three tables, so a range reaching
+//! past the raw samples is still charted.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, Transaction, params};
+
+use crate::{
+    constants::METRIC_STORE_FILE_NAME,
+    errors::{CoreError, CoreResult},
+    history::Sample,
+    storage::{CompactionReport, IdleGap, RetentionPolicy},
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS samples (
+        metric TEXT NOT NULL,
+        at INTEGER NOT NULL,
+        value REAL NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS samples_metric_at ON samples (metric, at);
+    CREATE TABLE IF NOT EXISTS idle_gaps (
+        from_at INTEGER NOT NULL,
+        to_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS samples_1m (
+        metric TEXT NOT NULL,
+        at INTEGER NOT NULL,
+        value REAL NOT NULL,
+        min REAL NOT NULL,
+        max REAL NOT NULL,
+        count INTEGER NOT NULL DEFAULT 1,
+        PRIMARY KEY (metric, at)
+    );
+    CREATE TABLE IF NOT EXISTS samples_1h (
+        metric TEXT NOT NULL,
+        at INTEGER NOT NULL,
+        value REAL NOT NULL,
+        min REAL NOT NULL,
+        max REAL NOT NULL,
+        count INTEGER NOT NULL DEFAULT 1,
+        PRIMARY KEY (metric, at)
+    );
+";
+
+/// Every resolution of a metric between two times, as `at`, `value`, `min`
+/// and `max` columns; raw samples are their own min and max
+const ALL_SAMPLES: &str = "
+    SELECT at, value, value AS min, value AS max FROM samples
+    WHERE metric = ?1 AND at >= ?2 AND at <= ?3
+    UNION ALL
+    SELECT at, value, min, max FROM samples_1m
+    WHERE metric = ?1 AND at >= ?2 AND at <= ?3
+    UNION ALL
+    SELECT at, value, min, max FROM samples_1h
+    WHERE metric = ?1 AND at >= ?2 AND at <= ?3
+";
+
+/// Rollup tables, which stores created before they counted their samples
+/// lack the `count` column of
+const ROLLUP_TABLES: [&str; 2] = ["samples_1m", "samples_1h"];
+
+const MINUTE_MS: i64 = 60_000;
+const HOUR_MS: i64 = 3_600_000;
+
+/// Lowest, average and highest value of a metric over a range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeStats {
+    /// Lowest value
+    pub min: f64,
+    /// Average of the values
+    pub avg: f64,
+    /// Highest value
+    pub max: f64,
+}
+
+/// Metrics history kept in a SQLite database
+#[derive(Debug)]
+pub struct MetricStore {
+    connection: Connection,
+}
+
+impl MetricStore {
+    /// Opens the database at `path`, creating it and its directory when
+    /// needed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be created or the file
+    /// isn't a usable database.
+    pub fn open(path: impl AsRef<Path>) -> CoreResult<Self> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| CoreError::io(format!("failed to create {}", dir.display()), err))?;
+        }
+        let connection = Connection::open(path)
+            .map_err(|err| store_error(&format!("failed to open {}", path.display()), err))?;
+        Self::with_connection(connection)
+    }
+
+    /// A database that lives in memory only, for tests and previews
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SQLite can't create the schema.
+    pub fn open_in_memory() -> CoreResult<Self> {
+        let connection = Connection::open_in_memory()
+            .map_err(|err| store_error("failed to open in-memory store", err))?;
+        Self::with_connection(connection)
+    }
+
+    /// The database under the user's data directory, e.g.
+    /// `~/.local/share/stomata/metrics.db`
+    pub fn default_location() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("stomata").join(METRIC_STORE_FILE_NAME))
+    }
+
+    fn with_connection(connection: Connection) -> CoreResult<Self> {
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|err| store_error("failed to create the store schema", err))?;
+        for table in ROLLUP_TABLES {
+            add_count_column(&connection, table)
+                .map_err(|err| store_error(&format!("failed to migrate {table}"), err))?;
+        }
+        Ok(Self { connection })
+    }
+
+    /// Stores one sample per `(metric, value)` pair, all taken `at`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rows can't be written.
+    pub fn insert(&mut self, at: DateTime<Utc>, values: &[(&str, f64)]) -> CoreResult<()> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| store_error("failed to write samples", err))?;
+        {
+            let mut statement = transaction
+                .prepare_cached("INSERT INTO samples (metric, at, value) VALUES (?1, ?2, ?3)")
+                .map_err(|err| store_error("failed to write samples", err))?;
+            for (metric, value) in values {
+                statement
+                    .execute(params![metric, at.timestamp_millis(), value])
+                    .map_err(|err| store_error("failed to write samples", err))?;
+            }
+        }
+        transaction
+            .commit()
+            .map_err(|err| store_error("failed to write samples", err))
+    }
+
+    /// Stores a quiet period that was sampled sparsely
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the row can't be written.
+    pub fn insert_gap(&mut self, gap: IdleGap) -> CoreResult<()> {
+        self.connection
+            .execute(
+                "INSERT INTO idle_gaps (from_at, to_at) VALUES (?1, ?2)",
+                params![gap.from.timestamp_millis(), gap.to.timestamp_millis()],
+            )
+            .map_err(|err| store_error("failed to write idle gap", err))?;
+        Ok(())
+    }
+
+    /// Samples of `metric` between `from` and `to`, averaged into at most
+    /// `points` equal buckets, oldest first. Each bucket is stamped with
+    /// its start; empty buckets are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn query(
+        &self,
+        metric: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        points: usize,
+    ) -> CoreResult<Vec<Sample<f64>>> {
+        let (from, to) = (from.timestamp_millis(), to.timestamp_millis());
+        let bucket = ((to - from) / points.max(1) as i64).max(1);
+        let mut statement = self
+            .connection
+            .prepare_cached(&format!(
+                "SELECT (at - ?2) / ?4 AS bucket, AVG(value) FROM ({ALL_SAMPLES})
+                 GROUP BY bucket ORDER BY bucket"
+            ))
+            .map_err(|err| store_error("failed to query samples", err))?;
+        let rows = statement
+            .query_map(params![metric, from, to, bucket], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?))
+            })
+            .map_err(|err| store_error("failed to query samples", err))?;
+        rows.map(|row| {
+            let (index, value) = row.map_err(|err| store_error("failed to query samples", err))?;
+            let time = DateTime::from_timestamp_millis(from + index * bucket).unwrap_or_default();
+            Ok(Sample { time, value })
+        })
+        .collect()
+    }
+
+    /// Lowest, average and highest value of `metric` between `from` and
+    /// `to`, `None` without samples
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn stats(
+        &self,
+        metric: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> CoreResult<Option<RangeStats>> {
+        self.connection
+            .query_row(
+                &format!("SELECT MIN(min), AVG(value), MAX(max) FROM ({ALL_SAMPLES})"),
+                params![metric, from.timestamp_millis(), to.timestamp_millis()],
+                |row| {
+                    Ok(match (row.get(0)?, row.get(1)?, row.get(2)?) {
+                        (Some(min), Some(avg), Some(max)) => Some(RangeStats { min, avg, max }),
+                        _ => None,
+                    })
+                },
+            )
+            .map_err(|err| store_error("failed to query samples", err))
+    }
+
+    /// Quiet periods overlapping `from` to `to`, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn gaps(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> CoreResult<Vec<IdleGap>> {
+        let mut statement = self
+            .connection
+            .prepare_cached(
+                "SELECT from_at, to_at FROM idle_gaps
+                 WHERE to_at >= ?1 AND from_at <= ?2 ORDER BY from_at",
+            )
+            .map_err(|err| store_error("failed to query idle gaps", err))?;
+        let rows = statement
+            .query_map(
+                params![from.timestamp_millis(), to.timestamp_millis()],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .map_err(|err| store_error("failed to query idle gaps", err))?;
+        rows.map(|row| {
+            let (from, to) = row.map_err(|err| store_error("failed to query idle gaps", err))?;
+            Ok(IdleGap {
+                from: DateTime::from_timestamp_millis(from).unwrap_or_default(),
+                to: DateTime::from_timestamp_millis(to).unwrap_or_default(),
+            })
+        })
+        .collect()
+    }
+
+    /// Rolls the samples older than the ages of `policy` into the coarser
+    /// rollups and deletes the hour averages and idle gaps past
+    /// `policy.hour_for`, as of `now`. Cutoffs are aligned to whole
+    /// minutes and hours, so no period is rolled up in two parts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::InvalidInput`] for a policy that fails
+    /// [`RetentionPolicy::validate`], or an error if the database can't be
+    /// written; nothing is changed then.
+    pub fn compact(
+        &mut self,
+        policy: &RetentionPolicy,
+        now: DateTime<Utc>,
+    ) -> CoreResult<CompactionReport> {
+        policy.validate()?;
+        let now = now.timestamp_millis();
+        let raw_cutoff = align(now - policy.raw_for.num_milliseconds(), MINUTE_MS);
+        let minute_cutoff = align(now - policy.minute_for.num_milliseconds(), HOUR_MS);
+        let hour_cutoff = now - policy.hour_for.num_milliseconds();
+
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| store_error("failed to compact the store", err))?;
+        let raw_rolled = roll_up(&transaction, "samples", "samples_1m", MINUTE_MS, raw_cutoff)?;
+        let minutes_rolled = roll_up(
+            &transaction,
+            "samples_1m",
+            "samples_1h",
+            HOUR_MS,
+            minute_cutoff,
+        )?;
+        let expired = transaction
+            .execute("DELETE FROM samples_1h WHERE at < ?1", params![hour_cutoff])
+            .and_then(|hours| {
+                let gaps = transaction.execute(
+                    "DELETE FROM idle_gaps WHERE to_at < ?1",
+                    params![hour_cutoff],
+                )?;
+                Ok(hours + gaps)
+            })
+            .map_err(|err| store_error("failed to expire old samples", err))?;
+        transaction
+            .commit()
+            .map_err(|err| store_error("failed to compact the store", err))?;
+        Ok(CompactionReport {
+            raw_rolled,
+            minutes_rolled,
+            expired,
+        })
+    }
+}
+
+/// Start of the period of length `period_ms` that `at` falls in
+fn align(at: i64, period_ms: i64) -> i64 {
+    at.div_euclid(period_ms) * period_ms
+}
+
+/// Averages the rows of `from` older than `cutoff` into periods of
+/// `period_ms` in `into`, then deletes them. A period already in `into` is
+/// merged by the number of samples behind each side, so the result is the
+/// mean of all of them. Returns the number of rows rolled up.
+fn roll_up(
+    transaction: &Transaction<'_>,
+    from: &str,
+    into: &str,
+    period_ms: i64,
+    cutoff: i64,
+) -> CoreResult<usize> {
+    // raw samples have no min, max and count columns of their own
+    let (min, max, count) = if from == "samples" {
+        ("value", "value", "1")
+    } else {
+        ("min", "max", "count")
+    };
+    transaction
+        .execute(
+            &format!(
+                "INSERT INTO {into} (metric, at, value, min, max, count)
+                 SELECT metric, (at / ?1) * ?1 AS period,
+                     SUM(value * {count}) / SUM({count}), MIN({min}), MAX({max}), SUM({count})
+                 FROM {from} WHERE at < ?2 GROUP BY metric, period
+                 ON CONFLICT (metric, at) DO UPDATE SET
+                     value = (value * count + excluded.value * excluded.count)
+                         / (count + excluded.count),
+                     min = MIN(min, excluded.min),
+                     max = MAX(max, excluded.max),
+                     count = count + excluded.count"
+            ),
+            params![period_ms, cutoff],
+        )
+        .and_then(|_| {
+            transaction.execute(
+                &format!("DELETE FROM {from} WHERE at < ?1"),
+                params![cutoff],
+            )
+        })
+        .map_err(|err| store_error(&format!("failed to roll up {from}"), err))
+}
+
+/// Adds the `count` column to `table` of a store created without it, its
+/// rows count as one sample each
+fn add_count_column(connection: &Connection, table: &str) -> rusqlite::Result<()> {
+    let has_count = connection
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{table}') WHERE name = 'count'"
+        ))?
+        .exists([])?;
+    if !has_count {
+        connection.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN count INTEGER NOT NULL DEFAULT 1"
+        ))?;
+    }
+    Ok(())
+}
+
+fn store_error(context: &str, err: rusqlite::Error) -> CoreError {
+    CoreError::collection(format!("{context}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+
+    fn at(second: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap() + Duration::seconds(second)
+    }
+
+    #[test]
+    fn test_query_averages_into_buckets() {
+        let mut store = MetricStore::open_in_memory().unwrap();
+        for second in 0..10 {
+            store
+                .insert(at(second), &[("cpu", second as f64), ("memory", 50.0)])
+                .unwrap();
+        }
+
+        let samples = store.query("cpu", at(0), at(10), 5).unwrap();
+        let values: Vec<f64> = samples.iter().map(|sample| sample.value).collect();
+        assert_eq!(values, [0.5, 2.5, 4.5, 6.5, 8.5]);
+        assert_eq!(samples[1].time, at(2));
+        assert!(store.query("swap", at(0), at(10), 5).unwrap().is_empty());
+
+        let stats = store.stats("cpu", at(2), at(5)).unwrap().unwrap();
+        assert_eq!(
+            stats,
+            RangeStats {
+                min: 2.0,
+                avg: 3.5,
+                max: 5.0
+            }
+        );
+        assert_eq!(store.stats("cpu", at(20), at(30)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_compact_rolls_up_and_expires() {
+        let mut store = MetricStore::open_in_memory().unwrap();
+        let now = at(0) + Duration::days(30);
+        for second in 0..120 {
+            store
+                .insert(at(second), &[("cpu", (second % 60) as f64)])
+                .unwrap();
+        }
+        store.insert(now, &[("cpu", 99.0)]).unwrap();
+        let policy = RetentionPolicy {
+            raw_for: Duration::hours(1),
+            minute_for: Duration::days(1),
+            hour_for: Duration::days(60),
+        };
+
+        let report = store.compact(&policy, now).unwrap();
+        assert_eq!(
+            report,
+            CompactionReport {
+                raw_rolled: 120,
+                minutes_rolled: 2,
+                expired: 0,
+            }
+        );
+        // the two minutes end up in one hour with their extremes
+        let stats = store.stats("cpu", at(0), at(3600)).unwrap().unwrap();
+        assert_eq!(
+            stats,
+            RangeStats {
+                min: 0.0,
+                avg: 29.5,
+                max: 59.0
+            }
+        );
+        assert_eq!(store.query("cpu", at(0), now, 10).unwrap().len(), 2);
+
+        let later = RetentionPolicy {
+            hour_for: Duration::days(2),
+            ..policy
+        };
+        assert_eq!(store.compact(&later, now).unwrap().expired, 1);
+        assert_eq!(store.stats("cpu", at(0), at(3600)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_merged_periods_are_weighted_by_their_samples() {
+        let mut store = MetricStore::open_in_memory().unwrap();
+        let now = at(0) + Duration::days(1);
+        let policy = RetentionPolicy {
+            raw_for: Duration::hours(1),
+            minute_for: Duration::days(2),
+            hour_for: Duration::days(60),
+        };
+        for second in 0..3 {
+            store.insert(at(second), &[("cpu", 10.0)]).unwrap();
+        }
+        store.compact(&policy, now).unwrap();
+        // a late sample of the same minute is merged into its rollup
+        store.insert(at(30), &[("cpu", 50.0)]).unwrap();
+        store.compact(&policy, now).unwrap();
+
+        let stats = store.stats("cpu", at(0), at(59)).unwrap().unwrap();
+        assert_eq!(stats.avg, 20.0);
+        assert_eq!((stats.min, stats.max), (10.0, 50.0));
+    }
+
+    #[test]
+    fn test_stores_without_counts_are_migrated() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE samples_1m (
+                     metric TEXT NOT NULL, at INTEGER NOT NULL, value REAL NOT NULL,
+                     min REAL NOT NULL, max REAL NOT NULL, PRIMARY KEY (metric, at)
+                 );
+                 INSERT INTO samples_1m VALUES ('cpu', 0, 1.0, 1.0, 1.0);",
+            )
+            .unwrap();
+        let store = MetricStore::with_connection(connection).unwrap();
+        let count: i64 = store
+            .connection
+            .query_row("SELECT count FROM samples_1m", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_gaps_overlapping_the_range() {
+        let mut store = MetricStore::open_in_memory().unwrap();
+        let gap = IdleGap {
+            from: at(10),
+            to: at(100),
+        };
+        store.insert_gap(gap).unwrap();
+
+        assert_eq!(store.gaps(at(50), at(200)).unwrap(), [gap]);
+        assert!(store.gaps(at(101), at(200)).unwrap().is_empty());
+    }
+}
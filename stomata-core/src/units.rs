@@ -0,0 +1,142 @@
+//! Human readable byte counts
+//!
+//! [`ByteSize`] picks the largest unit that keeps the value at or above 1,
+//! so 3 GiB of memory reads `3.0 GiB` rather than `3072.0 MB`.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How byte counts are scaled for display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    /// Powers of 1024: KiB, MiB, GiB
+    #[default]
+    Binary,
+    /// Powers of 1000: kB, MB, GB
+    Si,
+}
+
+impl UnitSystem {
+    /// The other system
+    pub fn toggled(&self) -> Self {
+        match self {
+            UnitSystem::Binary => UnitSystem::Si,
+            UnitSystem::Si => UnitSystem::Binary,
+        }
+    }
+
+    fn base(&self) -> f64 {
+        match self {
+            UnitSystem::Binary => 1024.0,
+            UnitSystem::Si => 1000.0,
+        }
+    }
+
+    fn units(&self) -> [&'static str; 6] {
+        match self {
+            UnitSystem::Binary => ["B", "KiB", "MiB", "GiB", "TiB", "PiB"],
+            UnitSystem::Si => ["B", "kB", "MB", "GB", "TB", "PB"],
+        }
+    }
+}
+
+/// A number of bytes that displays in the largest fitting unit.
+///
+/// Whole bytes are shown without decimals and scaled values with one,
+/// unless the format string asks for a precision:
+///
+/// ```
+/// use stomata_core::units::ByteSize;
+///
+/// assert_eq!(ByteSize::new(512).to_string(), "512 B");
+/// assert_eq!(ByteSize::new(3 * 1024 * 1024 * 1024).to_string(), "3.0 GiB");
+/// assert_eq!(format!("{:.2}", ByteSize::new(1_500_000).si()), "1.50 MB");
+/// assert_eq!(format!("{}/s", ByteSize::from_f64(2048.0)), "2.0 KiB/s");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ByteSize {
+    bytes: f64,
+    system: UnitSystem,
+}
+
+impl ByteSize {
+    /// A whole number of bytes
+    pub fn new(bytes: u64) -> Self {
+        Self::from_f64(bytes as f64)
+    }
+
+    /// Fractional byte counts, e.g. a transfer rate in bytes per second.
+    /// Negative values are shown as zero.
+    pub fn from_f64(bytes: f64) -> Self {
+        Self {
+            bytes: bytes.max(0.0),
+            system: UnitSystem::default(),
+        }
+    }
+
+    /// Scales in `system` units
+    pub fn with_system(mut self, system: UnitSystem) -> Self {
+        self.system = system;
+        self
+    }
+
+    /// Scales by powers of 1000 instead of 1024
+    pub fn si(self) -> Self {
+        self.with_system(UnitSystem::Si)
+    }
+
+    /// The unscaled byte count
+    pub fn bytes(&self) -> f64 {
+        self.bytes
+    }
+
+    /// The value in the largest unit keeping it at or above 1, and that
+    /// unit
+    pub fn scaled(&self) -> (f64, &'static str) {
+        let units = self.system.units();
+        let mut value = self.bytes;
+        let mut unit = 0;
+        while value >= self.system.base() && unit < units.len() - 1 {
+            value /= self.system.base();
+            unit += 1;
+        }
+        (value, units[unit])
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (value, unit) = self.scaled();
+        let precision = match f.precision() {
+            Some(precision) => precision,
+            None if unit == "B" => 0,
+            None => 1,
+        };
+        write!(f, "{value:.precision$} {unit}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scales_to_largest_unit() {
+        assert_eq!(ByteSize::new(0).to_string(), "0 B");
+        assert_eq!(ByteSize::new(1023).to_string(), "1023 B");
+        assert_eq!(ByteSize::new(1024).to_string(), "1.0 KiB");
+        assert_eq!(ByteSize::new(5 * 1024_u64.pow(4)).to_string(), "5.0 TiB");
+        assert_eq!(ByteSize::new(1000).si().to_string(), "1.0 kB");
+        assert_eq!(ByteSize::new(2_500_000_000).si().to_string(), "2.5 GB");
+        assert_eq!(ByteSize::from_f64(-3.0).to_string(), "0 B");
+    }
+
+    #[test]
+    fn test_honours_precision() {
+        assert_eq!(format!("{:.2}", ByteSize::new(1536)), "1.50 KiB");
+        assert_eq!(format!("{:.0}", ByteSize::new(1536)), "2 KiB");
+        assert_eq!(ByteSize::new(1536).scaled(), (1.5, "KiB"));
+    }
+}
@@ -1 +1,2 @@
 pub const EVM_ADDRESS_HEX_LENGTH: usize = 42;
+pub const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
@@ -1,9 +1,31 @@
-use sha3::{Digest, Keccak256};
-
-use crate::constants::EVM_ADDRESS_HEX_LENGTH;
+use crate::{
+    constants::{BASE58_ALPHABET, EVM_ADDRESS_HEX_LENGTH},
+    providers::hashing::keccak256,
+};
 
 pub struct AddressValidator;
 
+/// Blockchain family an address format belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Chain {
+    /// Ethereum and other EVM compatible chains
+    Evm,
+    /// Bitcoin legacy (base58) or segwit (bech32) addresses
+    Bitcoin,
+    /// Solana base58 encoded public keys
+    Solana,
+}
+
+impl Chain {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Chain::Evm => "EVM (Ethereum, Polygon, BSC, ...)",
+            Chain::Bitcoin => "Bitcoin",
+            Chain::Solana => "Solana",
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ValidationResult {
     Valid { checksummed: String },
@@ -12,6 +34,18 @@ pub enum ValidationResult {
     InvalidCharacters,
 }
 
+impl ValidationResult {
+    /// Human readable reason for an invalid result, `None` when valid
+    pub fn reason(&self) -> Option<&'static str> {
+        match self {
+            ValidationResult::Valid { .. } => None,
+            ValidationResult::InvalidLength => Some("expected 0x followed by 40 hex characters"),
+            ValidationResult::InvalidPrefix => Some("address must start with 0x"),
+            ValidationResult::InvalidCharacters => Some("address contains non-hex characters"),
+        }
+    }
+}
+
 impl AddressValidator {
     pub fn validate(address: &str) -> ValidationResult {
         // checking length 0x + 40 hex characters
@@ -33,14 +67,36 @@ impl AddressValidator {
 
         // checksum
         let checksummed = Self::checksum_encode(addr_without_prefix);
-        return ValidationResult::Valid {
+        ValidationResult::Valid {
             checksummed: format!("0x{checksummed}"),
-        };
+        }
+    }
+
+    /// Guesses which chain an address belongs to from its format alone.
+    ///
+    /// Only EVM addresses are fully validated, Bitcoin and Solana are
+    /// detected by prefix, length and alphabet.
+    pub fn detect_chain(address: &str) -> Option<Chain> {
+        let is_base58 = |s: &str| s.chars().all(|c| BASE58_ALPHABET.contains(c));
+        let is_segwit = address.starts_with("bc1") && (14..=74).contains(&address.len());
+        let is_legacy_bitcoin = (address.starts_with('1') || address.starts_with('3'))
+            && (26..=35).contains(&address.len())
+            && is_base58(address);
+
+        if address.starts_with("0x") {
+            Some(Chain::Evm)
+        } else if is_segwit || is_legacy_bitcoin {
+            Some(Chain::Bitcoin)
+        } else if (32..=44).contains(&address.len()) && is_base58(address) {
+            Some(Chain::Solana)
+        } else {
+            None
+        }
     }
 
     fn checksum_encode(address: &str) -> String {
         let address_lower = address.to_lowercase();
-        let hash = Self::keccak256(address_lower.as_bytes());
+        let hash = keccak256(address_lower.as_bytes());
         let hash_hex = hex::encode(hash);
 
         address_lower
@@ -61,12 +117,6 @@ impl AddressValidator {
             })
             .collect()
     }
-
-    fn keccak256(data: &[u8]) -> [u8; 32] {
-        let mut hasher = Keccak256::new();
-        hasher.update(data);
-        hasher.finalize().into()
-    }
 }
 
 #[cfg(test)]
@@ -107,4 +157,25 @@ mod tests {
         let result = AddressValidator::validate(addr);
         assert_eq!(result, ValidationResult::InvalidCharacters);
     }
+
+    #[test]
+    fn test_detect_chain() {
+        assert_eq!(
+            AddressValidator::detect_chain("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Some(Chain::Evm)
+        );
+        assert_eq!(
+            AddressValidator::detect_chain("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"),
+            Some(Chain::Bitcoin)
+        );
+        assert_eq!(
+            AddressValidator::detect_chain("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+            Some(Chain::Bitcoin)
+        );
+        assert_eq!(
+            AddressValidator::detect_chain("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T"),
+            Some(Chain::Solana)
+        );
+        assert_eq!(AddressValidator::detect_chain("hello"), None);
+    }
 }
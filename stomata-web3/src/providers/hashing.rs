@@ -0,0 +1,77 @@
+use std::{fs::File, io::Read, path::Path};
+
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+
+/// Keccak-256 digest of `data`, as used for EVM address checksums
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// SHA-256 digest of `data`
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Lowercase hex SHA-256 digest of a file, read in chunks so large files
+/// aren't loaded into memory at once
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Compares two hex digests, ignoring case, surrounding whitespace and a
+/// `0x` prefix
+pub fn digests_match(a: &str, b: &str) -> bool {
+    let normalize = |digest: &str| {
+        let digest = digest.trim();
+        digest
+            .strip_prefix("0x")
+            .unwrap_or(digest)
+            .to_ascii_lowercase()
+    };
+    normalize(a) == normalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_known_vector() {
+        assert_eq!(
+            hex::encode(sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_sha256_file_matches_in_memory_digest() {
+        let path = std::env::temp_dir().join(format!("stomata-hash-{}", std::process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        let digest = sha256_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(digest, hex::encode(sha256(b"abc")));
+    }
+
+    #[test]
+    fn test_digests_match_ignores_case_and_prefix() {
+        assert!(digests_match("0xABCDEF", " abcdef\n"));
+        assert!(!digests_match("abcdef", "abcdee"));
+    }
+}
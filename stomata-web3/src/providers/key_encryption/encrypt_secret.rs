@@ -1,38 +1,34 @@
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce, aead::Aead};
 use argon2::Argon2;
 use rand::random;
+use zeroize::Zeroizing;
 
-use crate::providers::key_encryption::structs::{CryptoData, EncryptPrivateKey};
+use crate::providers::key_encryption::{
+    errors::CryptoError,
+    secret::SecretBytes,
+    structs::{CryptoData, EncryptPrivateKey},
+};
 
 // ==== Core Encryption Functions ====
 
-fn derive_key(password: &str, salt: &[u8]) -> [u8; 32] {
-    let mut key = [0u8; 32];
+fn derive_key(password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, CryptoError> {
+    let mut key = Zeroizing::new([0u8; 32]);
     Argon2::default()
-        .hash_password_into(password.as_bytes(), salt, &mut key)
-        .unwrap();
-    key
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .map_err(|_| CryptoError::KeyDerivation)?;
+    Ok(key)
 }
 
-pub fn encrypt_private_key(pk: &[u8], password: &str) -> Option<EncryptPrivateKey> {
+pub fn encrypt_private_key(pk: &[u8], password: &str) -> Result<EncryptPrivateKey, CryptoError> {
     let salt = random::<[u8; 16]>();
     let nonce = random::<[u8; 12]>();
-    let key = derive_key(password, &salt);
-    let cipher = match Aes256Gcm::new_from_slice(&key) {
-        Ok(res) => res,
-        Err(_err) => {
-            return None;
-        }
-    };
-    let ciphertext = match cipher.encrypt(Nonce::from_slice(&nonce), pk) {
-        Ok(c_text) => c_text,
-        Err(err) => {
-            eprintln!("Error in encrypting private key {:?}", err);
-            return None;
-        }
-    };
+    let key = derive_key(password, &salt)?;
+    let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| CryptoError::KeyDerivation)?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), pk)
+        .map_err(|_| CryptoError::EncryptionFailed)?;
 
-    Some(EncryptPrivateKey {
+    Ok(EncryptPrivateKey {
         crypto_key: CryptoData {
             cipher: "aes-256-gcm".to_string(),
             salt: hex::encode(salt),
@@ -43,47 +39,27 @@ pub fn encrypt_private_key(pk: &[u8], password: &str) -> Option<EncryptPrivateKe
     })
 }
 
-pub fn decrypt_private_key(data: &EncryptPrivateKey, password: &str) -> Option<Vec<u8>> {
-    let salt = match hex::decode(&data.crypto_key.salt) {
-        Ok(salt) => salt,
-        Err(err) => {
-            eprintln!("Error in decoding salt {:?}", err);
-            return None;
-        }
-    };
-
-    let nonce = match hex::decode(&data.crypto_key.nonce) {
-        Ok(nonce) => nonce,
-        Err(err) => {
-            eprintln!("Error in decoding nonce {:?}", err);
-            return None;
-        }
-    };
+pub fn decrypt_private_key(
+    data: &EncryptPrivateKey,
+    password: &str,
+) -> Result<SecretBytes, CryptoError> {
+    let salt = hex::decode(&data.crypto_key.salt).map_err(|_| CryptoError::InvalidField("salt"))?;
+    let nonce =
+        hex::decode(&data.crypto_key.nonce).map_err(|_| CryptoError::InvalidField("nonce"))?;
+    // `Nonce::from_slice` panics on any other length
+    if nonce.len() != 12 {
+        return Err(CryptoError::InvalidField("nonce"));
+    }
+    let ciphertext = hex::decode(&data.crypto_key.ciphertext)
+        .map_err(|_| CryptoError::InvalidField("ciphertext"))?;
 
-    let ciphertext = match hex::decode(&data.crypto_key.ciphertext) {
-        Ok(ciphertext) => ciphertext,
-        Err(err) => {
-            eprintln!("Error in decoding ciphertext {:?}", err);
-            return None;
-        }
-    };
-
-    let key = derive_key(password, &salt);
-    let cipher = match Aes256Gcm::new_from_slice(&key) {
-        Ok(cipher) => cipher,
-        Err(err) => {
-            eprintln!("Error in generating cipher {:?}", err);
-            return None;
-        }
-    };
+    let key = derive_key(password, &salt)?;
+    let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| CryptoError::KeyDerivation)?;
 
-    match cipher.decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref()) {
-        Ok(res) => Some(res),
-        Err(err) => {
-            eprintln!("Error in decrypting key {:?}", err);
-            return None;
-        }
-    }
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+        .map(SecretBytes::new)
+        .map_err(|_| CryptoError::DecryptionFailed)
 }
 
 #[cfg(test)]
@@ -101,7 +77,7 @@ mod tests {
         let decrypted =
             decrypt_private_key(&encrypted, password).expect("Decryption should succeed");
 
-        assert_eq!(private_key.as_slice(), decrypted.as_slice());
+        assert_eq!(private_key.as_slice(), decrypted.expose_secret());
     }
 
     #[test]
@@ -115,8 +91,9 @@ mod tests {
 
         let decrypted = decrypt_private_key(&encrypted, wrong_password);
 
-        assert!(
-            decrypted.is_none(),
+        assert_eq!(
+            decrypted.unwrap_err(),
+            CryptoError::DecryptionFailed,
             "Decryption with wrong password should fail"
         );
     }
@@ -132,7 +109,7 @@ mod tests {
         let decrypted =
             decrypt_private_key(&encrypted, password).expect("Decryption should succeed");
 
-        assert_eq!(private_key.as_slice(), decrypted.as_slice());
+        assert_eq!(private_key.as_slice(), decrypted.expose_secret());
     }
 
     #[test]
@@ -146,7 +123,7 @@ mod tests {
         let decrypted =
             decrypt_private_key(&encrypted, password).expect("Decryption should succeed");
 
-        assert_eq!(private_key, decrypted);
+        assert_eq!(private_key.as_slice(), decrypted.expose_secret());
     }
 
     #[test]
@@ -160,7 +137,7 @@ mod tests {
         let decrypted =
             decrypt_private_key(&encrypted, password).expect("Decryption should succeed");
 
-        assert_eq!(private_key.as_slice(), decrypted.as_slice());
+        assert_eq!(private_key.as_slice(), decrypted.expose_secret());
     }
 
     #[test]
@@ -185,7 +162,7 @@ mod tests {
         let decrypted1 = decrypt_private_key(&encrypted1, password).unwrap();
         let decrypted2 = decrypt_private_key(&encrypted2, password).unwrap();
         assert_eq!(decrypted1, decrypted2);
-        assert_eq!(private_key.as_slice(), decrypted1.as_slice());
+        assert_eq!(private_key.as_slice(), decrypted1.expose_secret());
     }
 
     #[test]
@@ -200,8 +177,9 @@ mod tests {
         encrypted.crypto_key.salt = "invalid_hex_string".to_string();
 
         let decrypted = decrypt_private_key(&encrypted, password);
-        assert!(
-            decrypted.is_none(),
+        assert_eq!(
+            decrypted.unwrap_err(),
+            CryptoError::InvalidField("salt"),
             "Decryption with corrupted salt should fail"
         );
     }
@@ -218,8 +196,9 @@ mod tests {
         encrypted.crypto_key.nonce = "not_valid_hex".to_string();
 
         let decrypted = decrypt_private_key(&encrypted, password);
-        assert!(
-            decrypted.is_none(),
+        assert_eq!(
+            decrypted.unwrap_err(),
+            CryptoError::InvalidField("nonce"),
             "Decryption with corrupted nonce should fail"
         );
     }
@@ -240,12 +219,25 @@ mod tests {
         encrypted.crypto_key.ciphertext = hex::encode(bytes);
 
         let decrypted = decrypt_private_key(&encrypted, password);
-        assert!(
-            decrypted.is_none(),
+        assert_eq!(
+            decrypted.unwrap_err(),
+            CryptoError::DecryptionFailed,
             "Decryption with corrupted ciphertext should fail"
         );
     }
 
+    #[test]
+    fn test_nonce_of_wrong_length_fails() {
+        let mut encrypted =
+            encrypt_private_key(b"secret_key", "password").expect("Encryption should succeed");
+        encrypted.crypto_key.nonce = "abcd".to_string();
+
+        assert_eq!(
+            decrypt_private_key(&encrypted, "password").unwrap_err(),
+            CryptoError::InvalidField("nonce")
+        );
+    }
+
     #[test]
     fn test_binary_private_key() {
         let private_key: Vec<u8> = (0..=255).collect();
@@ -257,7 +249,7 @@ mod tests {
         let decrypted =
             decrypt_private_key(&encrypted, password).expect("Decryption should succeed");
 
-        assert_eq!(private_key, decrypted);
+        assert_eq!(private_key.as_slice(), decrypted.expose_secret());
     }
 
     #[test]
@@ -7,8 +7,41 @@ pub enum StorageError {
     KeyNotFound(String),
     KeyAlreadyExists(String),
     InvalidKeyName(String),
+    Crypto(CryptoError),
 }
 
+/// Why encrypting or decrypting a key failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CryptoError {
+    /// A field of the key file isn't valid hex or has the wrong length
+    InvalidField(&'static str),
+    /// The encryption key couldn't be derived from the password
+    KeyDerivation,
+    /// Encrypting the key failed
+    EncryptionFailed,
+    /// The password is wrong or the key file was tampered with
+    DecryptionFailed,
+}
+
+impl From<CryptoError> for StorageError {
+    fn from(err: CryptoError) -> Self {
+        StorageError::Crypto(err)
+    }
+}
+
+impl std::fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CryptoError::InvalidField(field) => write!(f, "Corrupted key file: invalid {}", field),
+            CryptoError::KeyDerivation => write!(f, "Could not derive a key from the password"),
+            CryptoError::EncryptionFailed => write!(f, "Encryption failed"),
+            CryptoError::DecryptionFailed => write!(f, "Decryption failed - wrong password?"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
 impl From<io::Error> for StorageError {
     fn from(err: io::Error) -> Self {
         StorageError::IoError(err)
@@ -29,6 +62,7 @@ impl std::fmt::Display for StorageError {
             StorageError::KeyNotFound(name) => write!(f, "Key '{}' not found", name),
             StorageError::KeyAlreadyExists(name) => write!(f, "Key '{}' already exists", name),
             StorageError::InvalidKeyName(name) => write!(f, "Invalid key name: '{}'", name),
+            StorageError::Crypto(e) => write!(f, "{}", e),
         }
     }
 }
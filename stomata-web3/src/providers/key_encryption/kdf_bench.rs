@@ -0,0 +1,144 @@
+use std::time::{Duration, Instant};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use zeroize::Zeroizing;
+
+/// Argon2id cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes over the memory
+    pub iterations: u32,
+    /// Number of lanes
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// The Argon2 defaults keys are currently encrypted with
+    pub const DEFAULT: KdfParams = KdfParams {
+        memory_kib: Params::DEFAULT_M_COST,
+        iterations: Params::DEFAULT_T_COST,
+        parallelism: Params::DEFAULT_P_COST,
+    };
+
+    /// Builds an Argon2id instance with these parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters are out of Argon2's range.
+    pub fn argon2(&self) -> Result<Argon2<'static>, argon2::Error> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+
+    /// Relative cost of the parameters, used to rank them
+    fn cost(&self) -> u64 {
+        self.memory_kib as u64 * self.iterations as u64
+    }
+}
+
+/// Time one parameter set took to derive a key on this machine
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KdfBenchResult {
+    pub params: KdfParams,
+    pub duration: Duration,
+}
+
+/// Parameter sets measured by the benchmark, from the defaults up to
+/// 256 MiB and four passes
+pub fn candidate_params() -> Vec<KdfParams> {
+    let mut candidates = vec![KdfParams::DEFAULT];
+    for memory_kib in [64 * 1024, 128 * 1024, 256 * 1024] {
+        for iterations in [2, 3, 4] {
+            candidates.push(KdfParams {
+                memory_kib,
+                iterations,
+                parallelism: 1,
+            });
+        }
+    }
+    candidates
+}
+
+/// Measures how long deriving one key takes with `params`, the fastest of
+/// `runs` derivations so background load doesn't inflate the result.
+///
+/// # Errors
+///
+/// Returns an error if the parameters are out of Argon2's range.
+pub fn bench_kdf(params: KdfParams, runs: usize) -> Result<KdfBenchResult, argon2::Error> {
+    let argon2 = params.argon2()?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    let mut fastest = Duration::MAX;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        argon2.hash_password_into(b"kdf-bench password", b"kdf-bench-salt16", key.as_mut())?;
+        fastest = fastest.min(start.elapsed());
+    }
+    Ok(KdfBenchResult {
+        params,
+        duration: fastest,
+    })
+}
+
+/// Picks the most expensive parameters that still derive a key within
+/// `target`, `None` when even the cheapest set is slower
+pub fn recommend(results: &[KdfBenchResult], target: Duration) -> Option<KdfParams> {
+    results
+        .iter()
+        .filter(|result| result.duration <= target)
+        .max_by_key(|result| (result.params.cost(), result.params.memory_kib))
+        .map(|result| result.params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(memory_kib: u32, iterations: u32, millis: u64) -> KdfBenchResult {
+        KdfBenchResult {
+            params: KdfParams {
+                memory_kib,
+                iterations,
+                parallelism: 1,
+            },
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn test_recommend_picks_strongest_within_target() {
+        let results = [
+            result(19 * 1024, 2, 30),
+            result(64 * 1024, 3, 180),
+            result(128 * 1024, 2, 240),
+            result(256 * 1024, 2, 480),
+        ];
+        let recommended = recommend(&results, Duration::from_millis(250)).unwrap();
+        assert_eq!(recommended.memory_kib, 128 * 1024);
+        assert_eq!(recommended.iterations, 2);
+        assert_eq!(recommend(&results, Duration::from_millis(10)), None);
+    }
+
+    #[test]
+    fn test_bench_kdf_runs_small_params() {
+        let params = KdfParams {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let result = bench_kdf(params, 2).unwrap();
+        assert_eq!(result.params, params);
+        assert!(
+            bench_kdf(
+                KdfParams {
+                    memory_kib: 1,
+                    ..params
+                },
+                1
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,4 +1,7 @@
 pub mod encrypt_secret;
 pub mod errors;
+pub mod kdf_bench;
+pub mod secret;
 pub mod store_secrets;
 pub mod structs;
+pub mod verify;
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Byte buffer for plaintext key material that is wiped from memory on drop.
+///
+/// The contents are never printed by `Debug`; use [`SecretBytes::expose_secret`]
+/// to read them explicitly.
+#[derive(Clone, Default)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Borrow the plaintext bytes
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<String> for SecretBytes {
+    fn from(text: String) -> Self {
+        Self(text.into_bytes())
+    }
+}
+
+/// Compares in constant time, so the time taken doesn't reveal how many
+/// leading bytes match. Only the lengths may differ in timing.
+impl PartialEq for SecretBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {}])", self.0.len())
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_does_not_leak_contents() {
+        let secret = SecretBytes::from("hunter2".to_string());
+        let printed = format!("{:?}", secret);
+        assert!(!printed.contains("hunter2"));
+        assert_eq!(printed, "SecretBytes([REDACTED; 7])");
+    }
+
+    #[test]
+    fn test_expose_secret_returns_bytes() {
+        let secret = SecretBytes::new(vec![1, 2, 3]);
+        assert_eq!(secret.expose_secret(), &[1, 2, 3]);
+        assert_eq!(secret.len(), 3);
+    }
+
+    #[test]
+    fn test_equality_compares_contents() {
+        let secret = SecretBytes::new(vec![1, 2, 3]);
+        assert_eq!(secret, SecretBytes::new(vec![1, 2, 3]));
+        assert_ne!(secret, SecretBytes::new(vec![1, 2, 4]));
+        assert_ne!(secret, SecretBytes::new(vec![1, 2]));
+        assert_eq!(SecretBytes::default(), SecretBytes::new(Vec::new()));
+    }
+}
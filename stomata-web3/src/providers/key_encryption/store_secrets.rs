@@ -4,6 +4,7 @@ use crate::providers::{
     encrypt_secret::{decrypt_private_key, encrypt_private_key},
     key_encryption::{
         errors::StorageError,
+        secret::SecretBytes,
         structs::{EncryptPrivateKey, KeyMetadata},
     },
 };
@@ -138,10 +139,10 @@ pub fn list_keys() -> Result<Vec<String>, StorageError> {
         let entry = entry?;
         let path = entry.path();
 
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
-                keys.push(name.to_string());
-            }
+        if path.extension().and_then(|s| s.to_str()) == Some("json")
+            && let Some(name) = path.file_stem().and_then(|s| s.to_str())
+        {
+            keys.push(name.to_string());
         }
     }
 
@@ -149,6 +150,26 @@ pub fn list_keys() -> Result<Vec<String>, StorageError> {
     Ok(keys)
 }
 
+/// List all stored keys with their metadata.
+///
+/// Keys whose file has no metadata, or can't be read, are still listed by
+/// name with an `unknown` creation date so they remain manageable.
+pub fn list_keys_with_metadata() -> Result<Vec<KeyMetadata>, StorageError> {
+    let keys = list_keys()?
+        .into_iter()
+        .map(|name| {
+            load_encrypted_key(&name)
+                .ok()
+                .and_then(|encrypted| encrypted.metadata)
+                .unwrap_or(KeyMetadata {
+                    name,
+                    created_at: "unknown".to_string(),
+                })
+        })
+        .collect();
+    Ok(keys)
+}
+
 /// Delete a stored key
 pub fn delete_key(name: &str) -> Result<(), StorageError> {
     let key_path = get_key_path(name)?;
@@ -170,23 +191,20 @@ pub fn key_exists(name: &str) -> Result<bool, StorageError> {
 // === High-level convenience functions ===
 
 /// Store a new private key with encryption
-pub fn store_key(name: &str, private_key: &[u8], password: &str) -> Result<(), StorageError> {
-    let encrypted = encrypt_private_key(private_key, password).ok_or_else(|| {
-        StorageError::IoError(io::Error::new(io::ErrorKind::Other, "Encryption failed"))
-    })?;
+pub fn store_key(
+    name: &str,
+    private_key: &SecretBytes,
+    password: &str,
+) -> Result<(), StorageError> {
+    let encrypted = encrypt_private_key(private_key.expose_secret(), password)?;
 
     save_encrypted_key(name, &encrypted)?;
     Ok(())
 }
 
 /// Retrieve and decrypt a private key
-pub fn retrieve_key(name: &str, password: &str) -> Result<Vec<u8>, StorageError> {
+pub fn retrieve_key(name: &str, password: &str) -> Result<SecretBytes, StorageError> {
     let encrypted = load_encrypted_key(name)?;
 
-    decrypt_private_key(&encrypted, password).ok_or_else(|| {
-        StorageError::IoError(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "Decryption failed - wrong password?",
-        ))
-    })
+    Ok(decrypt_private_key(&encrypted, password)?)
 }
@@ -6,7 +6,7 @@ pub struct EncryptPrivateKey {
     pub metadata: Option<KeyMetadata>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyMetadata {
     pub name: String,
     pub created_at: String,
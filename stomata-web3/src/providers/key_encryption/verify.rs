@@ -0,0 +1,176 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use crate::providers::key_encryption::{
+    errors::StorageError, store_secrets::get_keys_dir, structs::EncryptPrivateKey,
+};
+
+/// Permissions the keys directory is created with
+pub const KEYS_DIR_MODE: u32 = 0o700;
+
+/// Permissions key files are written with
+pub const KEY_FILE_MODE: u32 = 0o600;
+
+/// A problem found in the key store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyStoreIssue {
+    /// A key file that can't be read as an encrypted key
+    Malformed { file: String, reason: String },
+    /// A key file or the keys directory that other users can access
+    Permissions {
+        path: String,
+        mode: u32,
+        expected: u32,
+    },
+    /// Several key files carrying the same name in their metadata
+    DuplicateName { name: String, files: Vec<String> },
+}
+
+impl fmt::Display for KeyStoreIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyStoreIssue::Malformed { file, reason } => {
+                write!(f, "{file}: not a valid key file ({reason})")
+            }
+            KeyStoreIssue::Permissions {
+                path,
+                mode,
+                expected,
+            } => write!(
+                f,
+                "{path}: permissions {mode:04o} allow access by other users, expected {expected:04o}"
+            ),
+            KeyStoreIssue::DuplicateName { name, files } => {
+                write!(f, "name '{name}' is used by {}", files.join(", "))
+            }
+        }
+    }
+}
+
+/// Scans the keys directory for malformed key files, permissions that let
+/// other users read keys, and duplicate key names.
+///
+/// A key store that doesn't exist yet has no issues.
+///
+/// # Errors
+///
+/// Returns an error if the keys directory exists but can't be read.
+pub fn verify_key_store() -> Result<Vec<KeyStoreIssue>, StorageError> {
+    let keys_dir = get_keys_dir()?;
+    if !keys_dir.exists() {
+        return Ok(Vec::new());
+    }
+    verify_keys_dir(&keys_dir)
+}
+
+/// Scans the key files of `dir`, see [`verify_key_store`]
+pub fn verify_keys_dir(dir: &Path) -> Result<Vec<KeyStoreIssue>, StorageError> {
+    let mut issues = Vec::new();
+    if let Some(issue) = permission_issue(dir, KEYS_DIR_MODE)? {
+        issues.push(issue);
+    }
+
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    paths.retain(|path| path.extension().and_then(|s| s.to_str()) == Some("json"));
+    paths.sort();
+
+    let mut names: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for path in &paths {
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if let Some(issue) = permission_issue(path, KEY_FILE_MODE)? {
+            issues.push(issue);
+        }
+        let parsed = fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<EncryptPrivateKey>(&json).map_err(|err| err.to_string())
+            });
+        match parsed {
+            Ok(encrypted) => {
+                if let Some(metadata) = encrypted.metadata {
+                    names.entry(metadata.name).or_default().push(file);
+                }
+            }
+            Err(reason) => issues.push(KeyStoreIssue::Malformed { file, reason }),
+        }
+    }
+
+    issues.extend(
+        names
+            .into_iter()
+            .filter(|(_, files)| files.len() > 1)
+            .map(|(name, files)| KeyStoreIssue::DuplicateName { name, files }),
+    );
+    Ok(issues)
+}
+
+/// Reports `path` when group or other users have any access to it
+#[cfg(unix)]
+fn permission_issue(path: &Path, expected: u32) -> Result<Option<KeyStoreIssue>, StorageError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)?.permissions().mode() & 0o777;
+    Ok((mode & 0o077 != 0).then(|| KeyStoreIssue::Permissions {
+        path: path.display().to_string(),
+        mode,
+        expected,
+    }))
+}
+
+#[cfg(not(unix))]
+fn permission_issue(_path: &Path, _expected: u32) -> Result<Option<KeyStoreIssue>, StorageError> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_JSON: &str = r#"{"crypto_key":{"cipher":"aes-256-gcm","salt":"00","nonce":"00","ciphertext":"00"},"metadata":{"name":"wallet","created_at":"2026-01-01T00:00:00Z"}}"#;
+
+    #[test]
+    fn test_verify_keys_dir_reports_each_issue() {
+        let dir = std::env::temp_dir().join(format!("stomata-verify-keys-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("wallet.json"), KEY_JSON).unwrap();
+        fs::write(dir.join("wallet-copy.json"), KEY_JSON).unwrap();
+        fs::write(dir.join("broken.json"), "{not json").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).unwrap();
+            for file in ["wallet.json", "broken.json"] {
+                fs::set_permissions(dir.join(file), fs::Permissions::from_mode(0o600)).unwrap();
+            }
+            fs::set_permissions(
+                dir.join("wallet-copy.json"),
+                fs::Permissions::from_mode(0o644),
+            )
+            .unwrap();
+        }
+
+        let issues = verify_keys_dir(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            KeyStoreIssue::Malformed { file, .. } if file == "broken.json"
+        )));
+        assert!(issues.contains(&KeyStoreIssue::DuplicateName {
+            name: "wallet".to_string(),
+            files: vec!["wallet-copy.json".to_string(), "wallet.json".to_string()],
+        }));
+        #[cfg(unix)]
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            KeyStoreIssue::Permissions { path, mode: 0o644, .. } if path.ends_with("wallet-copy.json")
+        )));
+        assert_eq!(issues.len(), if cfg!(unix) { 3 } else { 2 });
+    }
+}
@@ -1,7 +1,15 @@
 pub mod address;
+pub mod hashing;
 mod key_encryption;
 
 pub use key_encryption::{
     encrypt_secret,
-    store_secrets::{delete_key, list_keys, retrieve_key, store_key},
+    errors::{CryptoError, StorageError},
+    kdf_bench::{KdfBenchResult, KdfParams, bench_kdf, candidate_params, recommend},
+    secret::SecretBytes,
+    store_secrets::{
+        delete_key, key_exists, list_keys, list_keys_with_metadata, retrieve_key, store_key,
+    },
+    structs::KeyMetadata,
+    verify::{KeyStoreIssue, verify_key_store},
 };